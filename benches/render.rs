@@ -0,0 +1,93 @@
+//! Frame times for the list views on a 5k-task vault from `seed`, drawn into a
+//! `TestBackend`. Only the rows in view are built, so a frame should cost about
+//! the same at any vault size. Run with `cargo bench --bench render`; the run
+//! fails when a view's median frame is over `FRAME_BUDGET`.
+
+use criterion::{BenchmarkId, Criterion};
+use ratatui::{backend::TestBackend, Terminal};
+use std::cell::RefCell;
+use std::thread;
+use std::time::{Duration, Instant};
+use tasktui::tui::{App, ViewMode};
+use tasktui_core::models;
+use tasktui_core::seed;
+use tasktui_core::storage::Storage;
+use tempfile::TempDir;
+
+const TASKS: usize = 5_000;
+
+/// A third of a frame at 60 fps, for a release build
+const FRAME_BUDGET: Duration = Duration::from_millis(5);
+
+const VIEWS: [(&str, ViewMode); 3] =
+    [("compact", ViewMode::Compact), ("kanban", ViewMode::Kanban), ("projects", ViewMode::Projects)];
+
+thread_local! {
+    /// Time per frame of every sample, by view
+    static SAMPLES: RefCell<Vec<(&'static str, Duration)>> = const { RefCell::new(Vec::new()) };
+}
+
+fn app() -> (TempDir, App) {
+    let dir = TempDir::new().unwrap();
+    let storage = Storage::new(dir.path().to_path_buf()).unwrap();
+    let mut items = seed::generate(TASKS, TASKS / 50, 1, models::today());
+    storage.write_tasks(&mut items, "Seed").unwrap();
+
+    let mut app = App::new(dir.path().to_path_buf()).unwrap();
+    while app.is_loading() {
+        app.poll_loading().unwrap();
+        thread::sleep(Duration::from_millis(1));
+    }
+    app.status_message = None;
+    app.briefing = None;
+    (dir, app)
+}
+
+fn frames(criterion: &mut Criterion) {
+    let (_dir, mut app) = app();
+    let mut terminal = Terminal::new(TestBackend::new(160, 50)).unwrap();
+    for (name, view) in VIEWS {
+        app.view_mode = view;
+        criterion.bench_with_input(BenchmarkId::new("frame", name), &TASKS, |b, _| {
+            b.iter_custom(|iters| {
+                let start = Instant::now();
+                for _ in 0..iters {
+                    terminal.draw(|frame| app.render(frame)).unwrap();
+                }
+                let took = start.elapsed();
+                SAMPLES.with(|samples| samples.borrow_mut().push((name, took / iters as u32)));
+                took
+            })
+        });
+    }
+}
+
+/// Fail if any view's median frame is over `FRAME_BUDGET`
+fn check_budget() {
+    let mut over = Vec::new();
+    for (name, _) in VIEWS {
+        let mut times: Vec<Duration> =
+            SAMPLES.with(|samples| samples.borrow().iter().filter(|s| s.0 == name).map(|s| s.1).collect());
+        if times.is_empty() {
+            continue;
+        }
+        times.sort();
+        let median = times[times.len() / 2];
+        println!("{:<10} {:>6} tasks {:>10.2?} (budget {:.2?})", name, TASKS, median, FRAME_BUDGET);
+        if median > FRAME_BUDGET {
+            over.push(format!("{}: {:?} > {:?}", name, median, FRAME_BUDGET));
+        }
+    }
+    assert!(over.is_empty(), "over budget:\n{}", over.join("\n"));
+}
+
+fn main() {
+    let mut criterion = Criterion::default()
+        .sample_size(20)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(2))
+        .configure_from_args();
+    frames(&mut criterion);
+    criterion.final_summary();
+    check_budget();
+}
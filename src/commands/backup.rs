@@ -0,0 +1,127 @@
+use crate::backup::{self, SecretString, PASSPHRASE_ENV};
+use crate::config::AppConfig;
+use crate::storage::Storage;
+use anyhow::{Context, Result};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+/// Write the data directory to a timestamped backup in `dir` (`.backups` by
+/// default), asking for a passphrase to encrypt it with when `encrypt` is set
+pub fn backup_create(data_dir: PathBuf, dir: Option<PathBuf>, encrypt: bool) -> Result<()> {
+    if !data_dir.exists() {
+        anyhow::bail!("No data directory at {}", data_dir.display());
+    }
+    let passphrase = if encrypt { Some(passphrase(true)?) } else { None };
+    let dir = dir.unwrap_or_else(|| backup::default_dir(&data_dir));
+    let path = backup::create(&data_dir, &dir, passphrase.as_ref())?;
+    println!("Backed up {} to {}", data_dir.display(), path.display());
+    Ok(())
+}
+
+/// Print the backups in `dir` (`.backups` by default), newest first
+pub fn backup_list(data_dir: PathBuf, dir: Option<PathBuf>) -> Result<()> {
+    let dir = dir.unwrap_or_else(|| backup::default_dir(&data_dir));
+    let backups = backup::list(&dir)?;
+    if backups.is_empty() {
+        println!("No backups in {}", dir.display());
+        return Ok(());
+    }
+    let width = backups.iter().map(|b| b.file_name().len()).max().unwrap_or_default();
+    for backup in backups {
+        println!(
+            "{:<width$}  {}  {:>9}{}",
+            backup.file_name(),
+            backup.created_at.format("%Y-%m-%d %H:%M:%S"),
+            backup::format_size(backup.size),
+            if backup.encrypted { "  encrypted" } else { "" },
+        );
+    }
+    Ok(())
+}
+
+/// Put the data directory back as it was in the backup at `file`. The backup
+/// is read through and summarized first, and once confirmed (or with `yes`)
+/// the current state is backed up before anything is replaced.
+pub fn backup_restore(data_dir: PathBuf, file: &Path, yes: bool) -> Result<()> {
+    if !file.is_file() {
+        anyhow::bail!("No backup at {}", file.display());
+    }
+    let passphrase = if backup::is_encrypted(file) { Some(passphrase(false)?) } else { None };
+    let contents = backup::contents(file, passphrase.as_ref())?;
+    println!("{}: {} tasks and {} other files", file.display(), contents.tasks, contents.other_files);
+
+    let prompt = format!(
+        "Replace everything in {} with it? Tasks created since are removed; the current state is backed up first.",
+        data_dir.display()
+    );
+    if !yes && !confirm(&prompt)? {
+        println!("Nothing restored");
+        return Ok(());
+    }
+
+    let mut storage = Storage::new(data_dir.clone())?;
+    let config = AppConfig::load(&data_dir)?;
+    storage.backups = config.backups;
+    storage.set_git_config(&config.git);
+    let safety = storage.restore_backup(file, passphrase.as_ref())?;
+    if let Some(e) = storage.last_sync_error() {
+        eprintln!("Warning: Git sync failed: {}. Changes saved locally.", e);
+    }
+    println!("Restored {} tasks. The previous state is in {}", contents.tasks, safety.display());
+    Ok(())
+}
+
+/// The passphrase from `TASKTUI_BACKUP_PASSPHRASE`, or asked for without
+/// echoing it; a new one is asked for twice
+fn passphrase(new: bool) -> Result<SecretString> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(SecretString::from(passphrase));
+    }
+    let passphrase = rpassword::prompt_password("Passphrase: ").context("Failed to read the passphrase")?;
+    if passphrase.is_empty() {
+        anyhow::bail!("The passphrase can't be empty");
+    }
+    if new && rpassword::prompt_password("Repeat passphrase: ")? != passphrase {
+        anyhow::bail!("The passphrases don't match");
+    }
+    Ok(SecretString::from(passphrase))
+}
+
+fn confirm(prompt: &str) -> Result<bool> {
+    print!("{} [y/N] ", prompt);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ItemType, TaskItem};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_backup_restore_keeps_the_current_state() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_dir = temp_dir.path().to_path_buf();
+        let storage = Storage::new(data_dir.clone()).unwrap();
+        let mut kept = TaskItem::new("Renew passport".to_string(), ItemType::Task);
+        storage.write_task(&mut kept).unwrap();
+        backup_create(data_dir.clone(), None, false).unwrap();
+        let file = backup::list(&backup::default_dir(&data_dir)).unwrap()[0].path.clone();
+
+        let mut added = TaskItem::new("Call Sam".to_string(), ItemType::Task);
+        storage.write_task(&mut added).unwrap();
+        storage.delete_task(&storage.load_all_tasks().unwrap().into_iter().find(|t| t.frontmatter.id == kept.frontmatter.id).unwrap()).unwrap();
+
+        backup_restore(data_dir.clone(), &file, true).unwrap();
+        let titles: Vec<String> = storage.load_all_tasks().unwrap().into_iter().map(|t| t.frontmatter.title).collect();
+        assert_eq!(titles, ["Renew passport"]);
+        // The state before the restore, with both changes, is a snapshot of its own
+        let backups = backup::list(&backup::default_dir(&data_dir)).unwrap();
+        assert_eq!(backups.len(), 2);
+        assert_eq!(backups[0].reason.as_deref(), Some("restore"));
+        assert_eq!(backup::contents(&backups[0].path, None).unwrap().tasks, 1);
+    }
+}
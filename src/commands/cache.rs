@@ -0,0 +1,10 @@
+use crate::llm::cache;
+use anyhow::Result;
+
+/// Delete the cached LLM enrichment results
+pub fn cache_clear() -> Result<()> {
+    let path = cache::default_path();
+    let count = cache::clear(&path)?;
+    println!("Cleared {} cached enrichment(s) from {}", count, path.display());
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use crate::config::AppConfig;
+use crate::llm::{EnrichedTask, TaskEnricher};
+use crate::models::{Due, ItemType, Priority, Status, TaskItem};
+use crate::storage::Storage;
+use tasktui::tui;
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// Capture raw text straight into the inbox.
+/// Skips LLM enrichment so capture stays instant; clarification happens during triage.
+/// With `popup` the text is typed into a one-line prompt instead (for a terminal
+/// window opened by a desktop hotkey). With `enrich` a detached process enriches
+/// the task afterwards, so the prompt still closes right away.
+pub fn capture(data_dir: PathBuf, text: &str, popup: bool, enrich: bool) -> Result<()> {
+    let text = if popup {
+        match tui::capture_prompt()? {
+            Some(text) if !text.is_empty() => text,
+            _ => return Ok(()),
+        }
+    } else {
+        text.trim().to_string()
+    };
+    if text.is_empty() {
+        anyhow::bail!("Nothing to capture");
+    }
+
+    let mut storage = Storage::new(data_dir.clone())?;
+    let config = AppConfig::load(&data_dir)?;
+    storage.hooks = config.hooks;
+    storage.set_git_config(&config.git);
+
+    let mut task = TaskItem::new(text, ItemType::Task);
+    task.frontmatter.status = Status::Inbox;
+    storage.write_task(&mut task)?;
+    if let Some(e) = storage.last_sync_error() {
+        eprintln!("Warning: Git sync failed: {}. Changes saved locally.", e);
+    }
+
+    if enrich {
+        Command::new(std::env::current_exe()?)
+            .arg("--data-dir")
+            .arg(&data_dir)
+            .args(["enrich", &task.frontmatter.id.to_string()])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .context("Failed to start background enrichment")?;
+    }
+
+    if !popup {
+        println!("Captured to inbox: {} ({})", task.frontmatter.title, task.code());
+    }
+    Ok(())
+}
+
+/// Enrich a captured task in place: the LLM (or offline rules) fills in the title,
+/// due date, priority and tags. The task stays in the inbox for triage.
+pub fn enrich(data_dir: PathBuf, id: &str) -> Result<()> {
+    let config = AppConfig::load(&data_dir)?;
+    let mut storage = Storage::new(data_dir.clone())?;
+    storage.set_git_config(&config.git);
+    let id = storage.resolve_id(id)?;
+    let mut task = storage.parse_file(&data_dir.join(format!("{}.md", id)))?;
+
+    let goals_context = config.goals_context();
+    let goals_ref = if goals_context.is_empty() { None } else { Some(goals_context.as_str()) };
+    let enricher = TaskEnricher::from_config(&config, &data_dir);
+    let enriched = enricher.enrich_sync(&task.frontmatter.title, goals_ref);
+    apply_enrichment(&mut task, enriched);
+
+    storage.write_task(&mut task)?;
+    if let Some(e) = enricher.last_error() {
+        tracing::warn!(error = %e, task = %id, "Captured task enriched with offline rules");
+    }
+    Ok(())
+}
+
+/// Take the title, due date, priority and tags from `enriched`, keeping tags
+/// the task already has. The context becomes the body only when it's empty.
+pub(super) fn apply_enrichment(task: &mut TaskItem, enriched: EnrichedTask) {
+    task.frontmatter.title = enriched.title;
+    if let Some(due) = enriched.due_date.as_deref().and_then(Due::parse) {
+        task.frontmatter.due_date = Some(due);
+    }
+    if let Some(priority) = enriched.priority.as_deref().and_then(Priority::parse) {
+        task.frontmatter.priority = priority;
+    }
+    for tag in enriched.tags {
+        if !task.frontmatter.tags.contains(&tag) {
+            task.frontmatter.tags.push(tag);
+        }
+    }
+    if let Some(context) = enriched.context.filter(|_| task.body.trim().is_empty()) {
+        task.body = context;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_enrich_captured_task() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_dir = temp_dir.path().to_path_buf();
+        capture(data_dir.clone(), "call Sam about the lease tomorrow urgent", false, false).unwrap();
+        let storage = Storage::new(data_dir.clone()).unwrap();
+        let id = storage.load_all_tasks().unwrap()[0].frontmatter.id;
+
+        // Without an API key the offline rules do the enriching
+        enrich(data_dir, &id.to_string()).unwrap();
+        let task = &storage.load_all_tasks().unwrap()[0];
+        assert_eq!(task.frontmatter.title, "Call Sam about the lease");
+        assert_eq!(task.frontmatter.priority, Priority::P1);
+        assert_eq!(task.frontmatter.tags, ["@phone"]);
+        assert!(task.frontmatter.due_date.is_some());
+        assert_eq!(task.frontmatter.status, Status::Inbox);
+    }
+}
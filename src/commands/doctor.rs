@@ -0,0 +1,88 @@
+use crate::config::AppConfig;
+use crate::models::{self, Orphan};
+use crate::storage::Storage;
+use crate::validate;
+use anyhow::Result;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+/// Check the config and every task file, listing config settings that can't be
+/// read and task files that don't parse, have invalid values (including custom
+/// fields their project defines), or belong to a deleted or archived project.
+/// With `reset_config` the config is backed up and replaced by the defaults first.
+/// Fails when anything is found so scripts can use the exit code.
+pub fn doctor(data_dir: PathBuf, reset_config: bool) -> Result<()> {
+    if reset_config {
+        let backup = AppConfig::reset(&data_dir)?;
+        println!("Saved the old config to {} and wrote the defaults", backup.display());
+    }
+    let config_path = AppConfig::config_path(&data_dir);
+    let config_problems: Vec<String> = match AppConfig::load_checked(&data_dir) {
+        Ok((_, issues)) => issues.iter().map(|issue| format!("{} (using the default)", issue)).collect(),
+        Err(e) => vec![format!("{:#}", e)],
+    };
+
+    let storage = Storage::new(data_dir)?;
+    let mut problems = storage.diagnose()?;
+    if !config_problems.is_empty() {
+        problems.push((config_path, config_problems));
+    }
+
+    let tasks = storage.load_all_tasks()?;
+    let mut report = |path: &Path, problem: String| match problems.iter_mut().find(|(p, _)| p == path) {
+        Some((_, found)) => found.push(problem),
+        None => problems.push((path.to_path_buf(), vec![problem])),
+    };
+    for task in &tasks {
+        for warning in validate::custom_fields(&task.frontmatter, models::field_defs(&tasks, task)) {
+            report(&task.file_path, warning.to_string());
+        }
+    }
+    for (task, why) in models::orphans(&tasks) {
+        let parent = task.frontmatter.parent_goal_id.unwrap_or_default();
+        let problem = match why {
+            Orphan::Missing => format!("project {} no longer exists", parent),
+            Orphan::Archived => match tasks.iter().find(|p| p.frontmatter.id == parent) {
+                Some(project) => format!("project {} ({}) is archived", project.code(), project.frontmatter.title),
+                None => format!("project {} is archived", parent),
+            },
+        };
+        report(&task.file_path, problem);
+    }
+    problems.sort();
+
+    if problems.is_empty() {
+        println!("All task files look good.");
+        return Ok(());
+    }
+
+    let count: usize = problems.iter().map(|(_, found)| found.len()).sum();
+    for (path, found) in &problems {
+        println!("{}", path.display());
+        for problem in found {
+            println!("  - {}", problem);
+        }
+    }
+    anyhow::bail!("{} problem(s) in {} file(s)", count, problems.len())
+}
+
+/// Before the TUI starts: when the config can't be read at all, show why and,
+/// in a terminal, offer to back it up and start from the default settings
+pub fn check_config(data_dir: &Path) -> Result<()> {
+    let Err(e) = AppConfig::load_checked(data_dir) else {
+        return Ok(());
+    };
+    if !io::stdin().is_terminal() {
+        return Err(e);
+    }
+    eprintln!("{:#}", e);
+    eprint!("Back it up to {} and start with the default settings? [y/N] ", AppConfig::backup_path(data_dir).display());
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+        return Err(e);
+    }
+    AppConfig::reset(data_dir)?;
+    Ok(())
+}
@@ -0,0 +1,207 @@
+use crate::config::AppConfig;
+use crate::filter::FilterExpr;
+use crate::llm::{EnrichedTask, TaskEnricher};
+use crate::models::{Due, ItemType, TaskItem};
+use crate::storage::Storage;
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+/// A structured field `enrich --missing` selects tasks by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum EnrichField {
+    #[value(name = "due_date", alias = "due")]
+    DueDate,
+    Tags,
+    Body,
+}
+
+impl EnrichField {
+    fn is_missing(self, task: &TaskItem) -> bool {
+        match self {
+            EnrichField::DueDate => task.frontmatter.due_date.is_none(),
+            EnrichField::Tags => task.frontmatter.tags.is_empty(),
+            EnrichField::Body => task.body.trim().is_empty(),
+        }
+    }
+}
+
+/// Run existing tasks through the LLM to fill in what they lack: an actionable
+/// title, a due date, tags and a body. Tasks are those matching `query` (open
+/// tasks by default) that miss any of `missing`. The changes are listed as a
+/// diff and written as one commit once confirmed, or straight away with `yes`.
+pub fn enrich_bulk(data_dir: PathBuf, query: Option<&str>, missing: &[EnrichField], yes: bool) -> Result<()> {
+    let config = AppConfig::load(&data_dir)?;
+    let mut storage = Storage::new(data_dir.clone())?;
+    storage.hooks = config.hooks.clone();
+    storage.backups = config.backups;
+    storage.set_git_config(&config.git);
+    let tasks = storage.load_all_tasks()?;
+
+    let filter = match query {
+        Some(query) => FilterExpr::parse(query, |name| {
+            let needle = name.to_lowercase().replace('-', " ");
+            tasks.iter()
+                .find(|t| t.is_project() && t.frontmatter.title.to_lowercase().contains(&needle))
+                .map(|t| t.frontmatter.id)
+        })
+        .map_err(|e| anyhow::anyhow!(e))
+        .context("Invalid --filter")?,
+        None => FilterExpr::default(),
+    };
+    let selected = select(&tasks, &filter, missing);
+    if selected.is_empty() {
+        println!("No tasks to enrich");
+        return Ok(());
+    }
+
+    let enricher = TaskEnricher::from_config(&config, &data_dir);
+    if !enricher.is_available() {
+        eprintln!("No LLM configured; using the offline rules");
+    }
+    let goals_context = config.goals_context();
+    let goals_ref = if goals_context.is_empty() { None } else { Some(goals_context.as_str()) };
+    let titles: Vec<String> = selected.iter().map(|t| t.frontmatter.title.clone()).collect();
+    let enriched = enricher.enrich_batch(&titles, goals_ref, |done, total| {
+        eprint!("\rEnriching {}/{}", done, total);
+        let _ = io::stderr().flush();
+    });
+    eprintln!();
+    if let Some(e) = enricher.last_error() {
+        eprintln!("Warning: {}", e);
+    }
+
+    let mut changed = Vec::new();
+    for (task, enriched) in selected.into_iter().zip(enriched) {
+        let mut updated = task.clone();
+        fill_missing(&mut updated, enriched);
+        let diff = diff(task, &updated);
+        if !diff.is_empty() {
+            println!("{} {}", task.code(), task.frontmatter.title);
+            for line in diff {
+                println!("  {}", line);
+            }
+            changed.push(updated);
+        }
+    }
+    if changed.is_empty() {
+        println!("Nothing to change");
+        return Ok(());
+    }
+
+    if !yes && !confirm(&format!("Apply changes to {} tasks?", changed.len()))? {
+        println!("No changes made");
+        return Ok(());
+    }
+    storage.snapshot("enrich")?;
+    let message = format!("Enrich {} tasks", changed.len());
+    storage.write_tasks(&mut changed, &message)?;
+    if let Some(e) = storage.last_sync_error() {
+        eprintln!("Warning: Git sync failed: {}. Changes saved locally.", e);
+    }
+    println!("Enriched {} tasks", changed.len());
+    Ok(())
+}
+
+/// Tasks matching `filter` that miss any of the `missing` fields. Projects are
+/// left out, and closed tasks too unless the filter asks for a status.
+fn select<'a>(tasks: &'a [TaskItem], filter: &FilterExpr, missing: &[EnrichField]) -> Vec<&'a TaskItem> {
+    let today = crate::models::today();
+    tasks.iter()
+        .filter(|t| t.frontmatter.item_type == ItemType::Task)
+        .filter(|t| !filter.statuses.is_empty() || !t.frontmatter.status.is_closed())
+        .filter(|t| filter.matches(t, today))
+        .filter(|t| missing.is_empty() || missing.iter().any(|field| field.is_missing(t)))
+        .collect()
+}
+
+/// Take the LLM's title and add its tags, but only fill the due date and body
+/// when the task has none. Priorities are left alone.
+fn fill_missing(task: &mut TaskItem, enriched: EnrichedTask) {
+    if !enriched.title.trim().is_empty() {
+        task.frontmatter.title = enriched.title;
+    }
+    if task.frontmatter.due_date.is_none() {
+        task.frontmatter.due_date = enriched.due_date.as_deref().and_then(Due::parse);
+    }
+    for tag in enriched.tags {
+        if !task.frontmatter.tags.contains(&tag) {
+            task.frontmatter.tags.push(tag);
+        }
+    }
+    if let Some(context) = enriched.context.filter(|_| task.body.trim().is_empty()) {
+        task.body = context;
+    }
+}
+
+/// `- old` / `+ new` lines for the fields that differ
+fn diff(before: &TaskItem, after: &TaskItem) -> Vec<String> {
+    let (old, new) = (&before.frontmatter, &after.frontmatter);
+    let mut lines = Vec::new();
+    if old.title != new.title {
+        lines.push(format!("- title: {}", old.title));
+        lines.push(format!("+ title: {}", new.title));
+    }
+    if old.due_date != new.due_date {
+        lines.push(format!("+ due: {}", new.due_date.map(|d| d.to_string()).unwrap_or_default()));
+    }
+    let added: Vec<String> = new.tags.iter().filter(|t| !old.tags.contains(t)).map(|t| format!("#{}", t)).collect();
+    if !added.is_empty() {
+        lines.push(format!("+ tags: {}", added.join(" ")));
+    }
+    if before.body != after.body {
+        lines.push(format!("+ body: {}", after.body.lines().next().unwrap_or_default()));
+    }
+    lines
+}
+
+fn confirm(prompt: &str) -> Result<bool> {
+    print!("{} [y/N] ", prompt);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Priority, Status};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_enrich_bulk_fills_missing_fields() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_dir = temp_dir.path().to_path_buf();
+        let storage = Storage::new(data_dir.clone()).unwrap();
+        let mut vague = TaskItem::new("call the landlord tomorrow".to_string(), ItemType::Task);
+        vague.frontmatter.priority = Priority::P3;
+        let mut dated = TaskItem::new("email Sam".to_string(), ItemType::Task);
+        dated.frontmatter.due_date = Due::parse("2030-01-01");
+        let mut waiting = TaskItem::new("buy stamps".to_string(), ItemType::Task);
+        waiting.frontmatter.status = Status::Waiting;
+        let mut done = TaskItem::new("call mom today".to_string(), ItemType::Task);
+        done.set_status(Status::Done);
+        for task in [&mut vague, &mut dated, &mut waiting, &mut done] {
+            storage.write_task(task).unwrap();
+        }
+
+        let tasks = storage.load_all_tasks().unwrap();
+        let filter = FilterExpr::parse("status:active", |_| None).unwrap();
+        let selected: Vec<_> = select(&tasks, &filter, &[EnrichField::DueDate]).iter().map(|t| t.frontmatter.id).collect();
+        assert_eq!(selected, [vague.frontmatter.id]);
+        assert_eq!(select(&tasks, &FilterExpr::default(), &[]).len(), 3);
+
+        // Without an API key the offline rules do the enriching
+        enrich_bulk(data_dir, Some("status:active"), &[EnrichField::DueDate], true).unwrap();
+        let tasks = storage.load_all_tasks().unwrap();
+        let vague = tasks.iter().find(|t| t.frontmatter.id == vague.frontmatter.id).unwrap();
+        assert_eq!(vague.frontmatter.title, "Call the landlord");
+        assert!(vague.frontmatter.due_date.is_some());
+        assert_eq!(vague.frontmatter.tags, ["@phone"]);
+        assert_eq!(vague.frontmatter.priority, Priority::P3);
+        let dated = tasks.iter().find(|t| t.frontmatter.id == dated.frontmatter.id).unwrap();
+        assert_eq!(dated.frontmatter.title, "email Sam");
+    }
+}
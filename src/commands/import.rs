@@ -0,0 +1,41 @@
+use crate::config::AppConfig;
+use crate::import::{self, Source};
+use crate::models;
+use crate::storage::Storage;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Turn the checklist items in `file` into tasks, written as one change
+pub fn import(data_dir: PathBuf, source: Source, file: &Path) -> Result<()> {
+    let content = fs::read_to_string(file).with_context(|| format!("Failed to read {}", file.display()))?;
+    let mut storage = Storage::new(data_dir.clone())?;
+    let config = AppConfig::load(&data_dir)?;
+    storage.hooks = config.hooks;
+    storage.backups = config.backups;
+    storage.set_git_config(&config.git);
+    let projects: Vec<_> = storage.load_all_tasks()?
+        .into_iter()
+        .filter(|t| t.is_project())
+        .collect();
+
+    let mut tasks = match source {
+        Source::Markdown => import::from_markdown(&content, &projects, models::today())?,
+    };
+    if tasks.is_empty() {
+        anyhow::bail!("No checklist items (- [ ] ...) found in {}", file.display());
+    }
+
+    let subtasks = tasks.iter()
+        .filter(|t| t.frontmatter.parent_goal_id.is_some_and(|id| tasks.iter().any(|p| p.frontmatter.id == id)))
+        .count();
+    storage.snapshot("import")?;
+    let message = format!("Import {} tasks from {}", tasks.len(), file.display());
+    storage.write_tasks(&mut tasks, &message)?;
+    if let Some(e) = storage.last_sync_error() {
+        eprintln!("Warning: Git sync failed: {}. Changes saved locally.", e);
+    }
+
+    println!("Imported {} tasks ({} subtasks) from {}", tasks.len(), subtasks, file.display());
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use crate::logging;
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::thread;
+use std::time::Duration;
+
+/// Print the last `lines` lines of the newest log file, optionally following new output
+pub fn logs(lines: usize, follow: bool) -> Result<()> {
+    let Some(path) = logging::latest_log_file() else {
+        anyhow::bail!("No log files in {}", logging::log_dir().display());
+    };
+
+    let mut file = File::open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+
+    let all: Vec<&str> = contents.lines().collect();
+    for line in &all[all.len().saturating_sub(lines)..] {
+        println!("{}", line);
+    }
+
+    if !follow {
+        return Ok(());
+    }
+
+    eprintln!("==> Following {} (Ctrl-C to stop)", path.display());
+    let mut position = file.stream_position()?;
+    loop {
+        thread::sleep(Duration::from_millis(500));
+
+        let len = file.metadata()?.len();
+        if len < position {
+            // Truncated or rotated in place: start over
+            position = 0;
+        }
+        if len > position {
+            file.seek(SeekFrom::Start(position))?;
+            let mut chunk = String::new();
+            file.read_to_string(&mut chunk)?;
+            print!("{}", chunk);
+            io::stdout().flush()?;
+            position = file.stream_position()?;
+        }
+    }
+}
@@ -0,0 +1,35 @@
+mod backup;
+mod cache;
+mod capture;
+mod doctor;
+mod enrich;
+mod import;
+mod logs;
+mod notify;
+#[cfg(feature = "plugins")]
+mod plugins;
+mod project;
+mod retro;
+mod seed;
+mod show;
+mod sync;
+mod tag;
+mod voice;
+
+pub use backup::{backup_create, backup_list, backup_restore};
+pub use cache::cache_clear;
+pub use capture::{capture, enrich};
+pub use doctor::{check_config, doctor};
+pub use enrich::{enrich_bulk, EnrichField};
+pub use import::import;
+pub use logs::logs;
+pub use notify::notify;
+#[cfg(feature = "plugins")]
+pub use plugins::{plugin_call, plugin_list};
+pub use project::project_report;
+pub use retro::retro;
+pub use seed::seed;
+pub use show::show;
+pub use sync::sync_caldav;
+pub use tag::tag_rename;
+pub use voice::voice;
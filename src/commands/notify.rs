@@ -0,0 +1,56 @@
+use crate::config::AppConfig;
+use crate::integrations::slack::{self, Alerted, Message};
+use crate::models;
+use crate::storage::Storage;
+use anyhow::Result;
+use std::path::PathBuf;
+
+/// Post the morning digest or due-soon alerts to Slack. Meant to run from cron:
+/// the digest once in the morning, due-soon every few minutes.
+pub fn notify(data_dir: PathBuf, message: Message, print: bool) -> Result<()> {
+    let config = AppConfig::load(&data_dir)?;
+    let slack_config = config.slack.as_ref();
+    let webhook_url = slack_config.and_then(|c| c.webhook_url.as_deref());
+    if webhook_url.is_none() && !print {
+        anyhow::bail!(
+            "No Slack webhook configured; add `slack: {{webhook_url: ...}}` to {}",
+            AppConfig::config_path(&data_dir).display()
+        );
+    }
+
+    let storage = Storage::new(data_dir.clone())?;
+    let tasks = storage.load_all_tasks()?;
+    let (today, now) = (models::today(), models::now_local());
+
+    let mut alerted = None;
+    let text = match message {
+        Message::Digest => slack::digest(&tasks, today, now),
+        Message::DueSoon => {
+            let hours = slack_config.map_or(2, |c| c.due_soon_hours);
+            let state = alerted.insert(Alerted::load(&data_dir));
+            match slack::due_soon(&tasks, today, now, hours, state) {
+                Some(text) => text,
+                None => return Ok(()),
+            }
+        }
+    };
+
+    match webhook_url.filter(|_| !print) {
+        Some(webhook_url) => slack::post(webhook_url, &text)?,
+        None => {
+            println!("{}", text);
+            return Ok(());
+        }
+    }
+
+    // Only remember alerts once they were delivered
+    if let Some(alerted) = alerted {
+        if let Some(git_sync) = &storage.git_sync {
+            if let Err(e) = git_sync.exclude(Alerted::FILE_NAME) {
+                tracing::warn!(error = ?e, "Failed to keep notification state out of git");
+            }
+        }
+        alerted.save(&data_dir)?;
+    }
+    Ok(())
+}
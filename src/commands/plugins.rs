@@ -0,0 +1,43 @@
+use crate::config::AppConfig;
+use crate::plugins::Plugins;
+use crate::storage::Storage;
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+fn load(data_dir: PathBuf) -> Result<Plugins> {
+    let mut storage = Storage::new(data_dir.clone())?;
+    let config = AppConfig::load(&data_dir)?;
+    storage.activity_log = config.activity_log;
+    storage.hooks = config.hooks;
+    storage.set_git_config(&config.git);
+    Plugins::load(storage, &[])
+}
+
+/// List the plugins in `plugins/` and the tools each registered
+pub fn plugin_list(data_dir: PathBuf) -> Result<()> {
+    let plugins = load(data_dir)?;
+    if plugins.loaded.is_empty() && plugins.failed.is_empty() {
+        println!("No plugins");
+    }
+    for plugin in &plugins.loaded {
+        println!("{} ({})", plugin.name, plugin.path.display());
+        for tool in &plugin.tools {
+            println!("  {:<24} {}", tool["name"].as_str().unwrap_or(""), tool["description"].as_str().unwrap_or(""));
+        }
+    }
+    for (path, error) in &plugins.failed {
+        println!("{}: failed to load: {}", path.display(), error);
+    }
+    Ok(())
+}
+
+/// Call a plugin tool with JSON arguments, printing what it returns
+pub fn plugin_call(data_dir: PathBuf, tool: &str, arguments: Option<&str>) -> Result<()> {
+    let arguments = match arguments {
+        Some(text) => serde_json::from_str(text).context("Arguments must be JSON")?,
+        None => serde_json::json!({}),
+    };
+    let result = load(data_dir)?.call(tool, arguments)?;
+    println!("{}", serde_json::to_string_pretty(&result)?);
+    Ok(())
+}
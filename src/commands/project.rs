@@ -0,0 +1,32 @@
+use crate::export::{self, ReportFormat};
+use crate::models;
+use crate::storage::Storage;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::PathBuf;
+
+/// Write a project's status report, printed or written to `output`
+pub fn project_report(data_dir: PathBuf, id: &str, format: ReportFormat, output: Option<PathBuf>) -> Result<()> {
+    let storage = Storage::new(data_dir)?;
+    let tasks = storage.load_all_tasks()?;
+    let id = storage.resolve_id(id)?;
+    let project = tasks.iter()
+        .find(|t| t.frontmatter.id == id)
+        .with_context(|| format!("No task with id {}", id))?;
+    if !project.is_project() {
+        anyhow::bail!("{} is a task, not a project", project.frontmatter.title);
+    }
+
+    let content = match format {
+        ReportFormat::Md => export::project_report(project, &tasks, models::today()).to_markdown(),
+        ReportFormat::Csv => export::project_report_csv(project, &tasks),
+    };
+    match output {
+        Some(path) => {
+            fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+            println!("Wrote {}", path.display());
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use crate::config::AppConfig;
+use crate::llm::TaskEnricher;
+use crate::models;
+use crate::retro::{self, WeekReview, RETRO_MAX_TOKENS, RETRO_PROMPT};
+use crate::storage::Storage;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::PathBuf;
+
+/// Write the retrospective for `week` (default: this week) to `reviews/YYYY-WW.md`,
+/// with an LLM-written narrative above the facts when a key is configured
+pub fn retro(data_dir: PathBuf, week: Option<&str>, no_llm: bool) -> Result<()> {
+    let monday = match week {
+        Some(week) => retro::parse_week(week).with_context(|| format!("'{}' is not a YYYY-WW week", week))?,
+        None => retro::week_start(models::today()),
+    };
+
+    let storage = Storage::new(data_dir.clone())?;
+    let config = AppConfig::load(&data_dir)?;
+    let review = WeekReview::new(&storage.load_all_tasks()?, &config, monday);
+    let facts = review.to_markdown();
+
+    let enricher = TaskEnricher::from_config(&config, &data_dir);
+    let narrative = if no_llm || !enricher.is_available() {
+        None
+    } else {
+        let prompt = format!("{}\n{}", facts, config.goals_context());
+        match enricher.complete_sync(RETRO_PROMPT, &prompt, RETRO_MAX_TOKENS) {
+            Ok(text) => Some(text),
+            Err(e) => {
+                eprintln!("No narrative ({}); writing the facts only", e);
+                None
+            }
+        }
+    };
+
+    let mut content = format!("# Retrospective {}\n\n", review.week);
+    if let Some(narrative) = narrative {
+        content.push_str(narrative.trim());
+        content.push_str("\n\n");
+    }
+    content.push_str(&facts);
+
+    let path = retro::review_path(&data_dir, &review.week);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    storage.commit(&format!("Retro: week {}", review.week));
+
+    println!("Wrote {}", path.display());
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use crate::config::AppConfig;
+use crate::models;
+use crate::seed;
+use crate::storage::Storage;
+use anyhow::Result;
+use std::path::PathBuf;
+
+/// Fill the data directory with a synthetic vault, written as one change.
+/// Refuses to mix into existing tasks unless `force` is set.
+pub fn seed(data_dir: PathBuf, tasks: usize, projects: usize, seed: u64, force: bool) -> Result<()> {
+    let mut storage = Storage::new(data_dir.clone())?;
+    storage.set_git_config(&AppConfig::load(&data_dir)?.git);
+    let existing = storage.load_all_tasks()?.len();
+    if existing > 0 && !force {
+        anyhow::bail!(
+            "{} already has {} tasks; seed an empty directory (e.g. --data-dir /tmp/demo) or pass --force",
+            data_dir.display(),
+            existing
+        );
+    }
+
+    let mut items = seed::generate(tasks, projects, seed, models::today());
+    storage.write_tasks(&mut items, &format!("Seed {} tasks and {} projects", tasks, projects))?;
+    if let Some(e) = storage.last_sync_error() {
+        eprintln!("Warning: Git sync failed: {}. Changes saved locally.", e);
+    }
+
+    println!("Generated {} tasks and {} projects in {}", tasks, projects, data_dir.display());
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use crate::export::{self, Format};
+use crate::models;
+use crate::storage::Storage;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::PathBuf;
+
+/// Render a task or project as a standalone document, printed or written to `output`
+pub fn show(data_dir: PathBuf, id: &str, format: Format, output: Option<PathBuf>) -> Result<()> {
+    let storage = Storage::new(data_dir)?;
+    let tasks = storage.load_all_tasks()?;
+    let id = storage.resolve_id(id)?;
+    let item = tasks.iter()
+        .find(|t| t.frontmatter.id == id)
+        .with_context(|| format!("No task with id {}", id))?;
+
+    let content = export::document(item, &tasks, models::today()).render(format);
+    match output {
+        Some(path) => {
+            fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+            println!("Wrote {}", path.display());
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use crate::caldav::{self, CaldavClient, SyncState};
+use crate::config::AppConfig;
+use crate::storage::Storage;
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+/// Two-way sync with the configured CalDAV calendar
+pub fn sync_caldav(data_dir: PathBuf) -> Result<()> {
+    let config = AppConfig::load(&data_dir)?;
+    let Some(caldav) = &config.caldav else {
+        anyhow::bail!(
+            "No CalDAV calendar configured; add `caldav: {{url, username, password}}` to {}",
+            AppConfig::config_path(&data_dir).display()
+        );
+    };
+
+    let mut storage = Storage::new(data_dir.clone())?;
+    storage.activity_log = config.activity_log;
+    storage.hooks = config.hooks.clone();
+    storage.set_git_config(&config.git);
+    if let Some(git_sync) = &storage.git_sync {
+        if let Err(e) = git_sync.exclude(SyncState::FILE_NAME) {
+            tracing::warn!(error = ?e, "Failed to keep CalDAV sync state out of git");
+        }
+    }
+
+    let client = CaldavClient::new(caldav)?;
+    let runtime = tokio::runtime::Runtime::new().context("Failed to start the async runtime")?;
+    let report = runtime.block_on(caldav::sync(&storage, &client, &data_dir))?;
+    if let Some(e) = storage.last_sync_error() {
+        eprintln!("Warning: Git sync failed: {}. Changes saved locally.", e);
+    }
+
+    for error in &report.errors {
+        eprintln!("Skipped {}", error);
+    }
+    println!(
+        "CalDAV sync: {} pushed, {} pulled, {} deleted on the server, {} archived",
+        report.pushed, report.pulled, report.deleted, report.archived
+    );
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use crate::config::AppConfig;
+use crate::storage::Storage;
+use crate::tags;
+use anyhow::Result;
+use std::path::PathBuf;
+
+/// Rename a tag on every task, as one change, and in the config's workstreams
+/// and Kanban columns
+pub fn tag_rename(data_dir: PathBuf, old: &str, new: &str) -> Result<()> {
+    let (old, new) = (tags::parse_tag(old)?, tags::parse_tag(new)?);
+    if old == new {
+        anyhow::bail!("'{}' and '{}' are the same tag", old, new);
+    }
+
+    let mut storage = Storage::new(data_dir.clone())?;
+    let mut config = AppConfig::load(&data_dir)?;
+    storage.backups = config.backups;
+    storage.set_git_config(&config.git);
+    let mut changed = tags::rename(&storage.load_all_tasks()?, &old, &new);
+    if !changed.is_empty() {
+        storage.snapshot("tag-rename")?;
+        storage.write_tasks(&mut changed, &format!("Rename tag #{} to #{}", old, new))?;
+        if let Some(e) = storage.last_sync_error() {
+            eprintln!("Warning: Git sync failed: {}. Changes saved locally.", e);
+        }
+    }
+
+    if config.rename_tag(&old, &new) {
+        config.save(&data_dir)?;
+    }
+
+    println!("Retagged {} tasks from #{} to #{}", changed.len(), old, new);
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use super::capture::apply_enrichment;
+use crate::config::AppConfig;
+use crate::integrations::transcribe;
+use crate::llm::TaskEnricher;
+use crate::models::{ItemType, Status, TaskItem};
+use crate::storage::Storage;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Files changed more recently than this may still be syncing from the phone
+const SETTLE_TIME: Duration = Duration::from_secs(5);
+
+/// Transcribe voice memos into inbox tasks: each file given, or with `watch`
+/// every audio file that appears in that folder, checked every `interval`
+/// seconds. Watched memos are moved to `processed/` (or `failed/`) beside them.
+pub fn voice(data_dir: PathBuf, files: &[PathBuf], watch: Option<&Path>, interval: u64) -> Result<()> {
+    let config = AppConfig::load(&data_dir)?;
+    let api_key = config.transcription.api_key.clone()
+        .or_else(|| config.openai_api_key.clone())
+        .context("Transcription needs an API key: set transcription.api_key or openai_api_key in the config")?;
+    let mut storage = Storage::new(data_dir.clone())?;
+    storage.hooks = config.hooks.clone();
+    storage.set_git_config(&config.git);
+    let enricher = TaskEnricher::from_config(&config, &data_dir);
+
+    let ingest = |path: &Path| -> Result<TaskItem> {
+        let transcript = transcribe::transcribe(&config.transcription, &api_key, path)?;
+        let mut task = task_from_transcript(&enricher, &config, &transcript, path)?;
+        storage.write_task(&mut task)?;
+        if let Some(e) = storage.last_sync_error() {
+            eprintln!("Warning: Git sync failed: {}. Changes saved locally.", e);
+        }
+        println!("{} → inbox: {} ({})", path.display(), task.frontmatter.title, task.code());
+        Ok(task)
+    };
+
+    for file in files {
+        ingest(file)?;
+    }
+    let Some(folder) = watch else {
+        return Ok(());
+    };
+
+    println!("Watching {} for voice memos (Ctrl-C to stop)", folder.display());
+    loop {
+        for memo in settled_memos(folder)? {
+            let outcome = ingest(&memo);
+            if let Err(e) = &outcome {
+                eprintln!("{}: {:#}", memo.display(), e);
+                tracing::warn!(file = %memo.display(), error = %format!("{:#}", e), "Voice memo failed");
+            }
+            move_into(&memo, if outcome.is_ok() { "processed" } else { "failed" })?;
+        }
+        std::thread::sleep(Duration::from_secs(interval.max(1)));
+    }
+}
+
+/// An inbox task from a memo's transcript: the enricher picks the title, due
+/// date, priority and tags, and the transcript is kept in the body
+fn task_from_transcript(enricher: &TaskEnricher, config: &AppConfig, transcript: &str, path: &Path) -> Result<TaskItem> {
+    if transcript.trim().is_empty() {
+        anyhow::bail!("Nothing was said");
+    }
+    let goals_context = config.goals_context();
+    let goals_ref = if goals_context.is_empty() { None } else { Some(goals_context.as_str()) };
+    let enriched = enricher.enrich_sync(transcript, goals_ref);
+
+    let mut task = TaskItem::new(String::new(), ItemType::Task);
+    task.frontmatter.status = Status::Inbox;
+    apply_enrichment(&mut task, enriched);
+    let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    let memo = format!("Voice memo `{}`:\n\n> {}", name, transcript.trim());
+    task.body = if task.body.trim().is_empty() { memo } else { format!("{}\n\n{}", task.body.trim(), memo) };
+    Ok(task)
+}
+
+/// Audio files directly in `folder` that haven't changed for `SETTLE_TIME`, oldest first
+fn settled_memos(folder: &Path) -> Result<Vec<PathBuf>> {
+    let now = SystemTime::now();
+    let mut memos: Vec<(SystemTime, PathBuf)> = fs::read_dir(folder)
+        .with_context(|| format!("Failed to read {}", folder.display()))?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()) && transcribe::is_audio(&entry.path()))
+        .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+        .filter(|(modified, _)| now.duration_since(*modified).unwrap_or_default() >= SETTLE_TIME)
+        .collect();
+    memos.sort();
+    Ok(memos.into_iter().map(|(_, path)| path).collect())
+}
+
+/// Move `file` into the `subdir` folder next to it, so it isn't picked up again
+fn move_into(file: &Path, subdir: &str) -> Result<()> {
+    let dir = file.parent().unwrap_or(Path::new(".")).join(subdir);
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let name = file.file_name().context("Memo has no file name")?;
+    fs::rename(file, dir.join(name)).with_context(|| format!("Failed to move {}", file.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Priority;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_task_from_transcript() {
+        let enricher = TaskEnricher::new(None);
+        let config = AppConfig::default();
+        let path = Path::new("memos/2025-03-12 09.14.m4a");
+        let task = task_from_transcript(&enricher, &config, "Call the plumber tomorrow, it's urgent", path).unwrap();
+        assert_eq!(task.frontmatter.status, Status::Inbox);
+        assert_eq!(task.frontmatter.priority, Priority::P1);
+        assert!(task.frontmatter.due_date.is_some());
+        assert!(task.body.ends_with("Voice memo `2025-03-12 09.14.m4a`:\n\n> Call the plumber tomorrow, it's urgent"));
+        assert!(task_from_transcript(&enricher, &config, "  ", path).is_err());
+
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("memo.m4a"), b"audio").unwrap();
+        fs::write(dir.path().join("notes.txt"), b"text").unwrap();
+        // Just written, so possibly still syncing
+        assert!(settled_memos(dir.path()).unwrap().is_empty());
+        move_into(&dir.path().join("memo.m4a"), "processed").unwrap();
+        assert!(dir.path().join("processed/memo.m4a").exists());
+    }
+}
@@ -0,0 +1,9 @@
+//! The terminal UI, as a library so the render bench can drive `App` directly.
+//! The CLI and the MCP and web servers live in the binary.
+
+use tasktui_core::{
+    activity, backup, briefing, config, dashboard, delegation, embeddings, export, filter, flow, git, history,
+    integrations, journal, links, llm, models, prioritize, quickadd, related, search, storage, tags, validate,
+};
+
+pub mod tui;
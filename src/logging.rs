@@ -0,0 +1,177 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::fmt::{self, Write as _};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+/// Directory holding the daily log files: `$XDG_STATE_HOME/tasktui/log`,
+/// falling back to `~/.local/state/tasktui/log`
+pub fn log_dir() -> PathBuf {
+    let state_home = std::env::var_os("XDG_STATE_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/state")))
+        .unwrap_or_else(|| PathBuf::from("."));
+    state_home.join("tasktui").join("log")
+}
+
+/// Most recent log file (files are named by date, so the greatest name wins)
+pub fn latest_log_file() -> Option<PathBuf> {
+    fs::read_dir(log_dir())
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("log"))
+        .max()
+}
+
+/// Map `-v` occurrences to a level: warnings by default, `-v` info, `-vv` debug, `-vvv` trace
+pub fn level_for(verbosity: u8) -> Level {
+    match verbosity {
+        0 => Level::WARN,
+        1 => Level::INFO,
+        2 => Level::DEBUG,
+        _ => Level::TRACE,
+    }
+}
+
+/// Install the file logger as the global tracing subscriber
+pub fn init(verbosity: u8) -> Result<PathBuf> {
+    let dir = log_dir();
+    fs::create_dir_all(&dir).context("Failed to create log directory")?;
+
+    let path = dir.join(format!("{}.log", Utc::now().format("%Y-%m-%d")));
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .context("Failed to open log file")?;
+
+    tracing::subscriber::set_global_default(FileSubscriber::new(file, level_for(verbosity)))
+        .context("Logger already initialized")?;
+    Ok(path)
+}
+
+/// Minimal subscriber writing one `timestamp LEVEL target: message key=value` line per event
+pub struct FileSubscriber {
+    file: Mutex<File>,
+    max_level: Level,
+    next_span: AtomicU64,
+}
+
+impl FileSubscriber {
+    pub fn new(file: File, max_level: Level) -> Self {
+        Self {
+            file: Mutex::new(file),
+            max_level,
+            next_span: AtomicU64::new(1),
+        }
+    }
+}
+
+impl Subscriber for FileSubscriber {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        *metadata.level() <= self.max_level
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        Some(LevelFilter::from_level(self.max_level))
+    }
+
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(self.next_span.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut visitor = LineVisitor::default();
+        event.record(&mut visitor);
+
+        let metadata = event.metadata();
+        let line = format!(
+            "{} {:<5} {}: {}{}\n",
+            Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+            metadata.level(),
+            metadata.target(),
+            visitor.message,
+            visitor.fields,
+        );
+
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.write_all(line.as_bytes());
+        }
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+/// Collects the `message` field and renders the rest as ` key=value` pairs
+#[derive(Default)]
+struct LineVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for LineVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else if value.is_empty() || value.contains(char::is_whitespace) || value.contains('"') {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            self.record_str(field, &format!("{:?}", value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_file_subscriber_format_and_level() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("test.log");
+        let file = File::create(&path).unwrap();
+
+        tracing::subscriber::with_default(FileSubscriber::new(file, Level::INFO), || {
+            tracing::info!(method = "tools/call", id = 7, "MCP request");
+            tracing::warn!(error = "push rejected", "Git sync failed");
+            tracing::debug!("filtered out at info level");
+        });
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let lines: Vec<_> = contents.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("INFO  tasktui::logging::tests: MCP request method=tools/call id=7"));
+        assert!(lines[1].contains("WARN  tasktui::logging::tests: Git sync failed error=\"push rejected\""));
+    }
+
+    #[test]
+    fn test_verbosity_levels() {
+        assert_eq!(level_for(0), Level::WARN);
+        assert_eq!(level_for(1), Level::INFO);
+        assert_eq!(level_for(2), Level::DEBUG);
+        assert_eq!(level_for(5), Level::TRACE);
+    }
+}
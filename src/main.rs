@@ -1,12 +1,14 @@
-mod config;
-mod llm;
-mod models;
-mod storage;
-mod tui;
-mod git;
-mod mcp;
-
-use clap::{Parser, Subcommand};
+mod commands;
+mod logging;
+mod web;
+
+use tasktui_core::{
+    backup, caldav, config, export, filter, import, integrations, llm, mcp, models, retro, seed, storage, tags, validate,
+};
+#[cfg(feature = "plugins")]
+use tasktui_core::plugins;
+use tasktui::tui;
+use clap::{ArgAction, Parser, Subcommand};
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -14,9 +16,31 @@ use std::path::PathBuf;
 #[command(about = "A CLI/TUI Task Manager with MCP support", long_about = None)]
 struct Cli {
     /// Data directory for task files
-    #[arg(short, long, default_value = "./tasks")]
+    #[arg(short, long, env = "TASKTUI_DATA_DIR", default_value = "./tasks")]
     data_dir: PathBuf,
 
+    /// Override a config setting for this run, e.g. `--set llm_limits.concurrency=2` (repeatable)
+    #[arg(long = "set", value_name = "KEY=VALUE", value_parser = parse_setting, global = true)]
+    settings: Vec<(String, String)>,
+
+    /// OpenAI API key for this run, instead of `openai_api_key`
+    #[arg(long, value_name = "KEY", global = true)]
+    openai_api_key: Option<String>,
+
+    /// Model for the first LLM provider, instead of `llm_model`
+    #[arg(long, value_name = "MODEL", global = true)]
+    llm_model: Option<String>,
+
+    /// Log more detail to the log file (-v info, -vv debug incl. MCP payloads, -vvv trace)
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// Replay the keys in this file against a copy of the data directory, then
+    /// print the final screen and tasks
+    #[cfg(feature = "replay")]
+    #[arg(long, hide = true, value_name = "KEYS")]
+    replay: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -24,19 +48,338 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Run in MCP server mode
-    Server,
+    Server {
+        /// Reject task writes with invalid dates or tags instead of saving them
+        #[arg(long)]
+        strict: bool,
+    },
+    /// Capture a thought straight into the inbox (no LLM enrichment)
+    Capture {
+        /// Text to capture
+        #[arg(required_unless_present = "popup", num_args = 1..)]
+        text: Vec<String>,
+        /// Ask for the text in a one-line prompt, for binding to a desktop hotkey
+        #[arg(long)]
+        popup: bool,
+        /// Enrich the captured task in the background after capturing
+        #[arg(long)]
+        enrich: bool,
+    },
+    /// Transcribe voice memos into inbox tasks, enriched like `capture --enrich`
+    Voice {
+        /// Audio files to transcribe
+        #[arg(required_unless_present = "watch")]
+        files: Vec<PathBuf>,
+        /// Keep transcribing memos that appear in this folder
+        #[arg(long, value_name = "DIR")]
+        watch: Option<PathBuf>,
+        /// Seconds between checks of the watched folder
+        #[arg(long, default_value_t = 10)]
+        interval: u64,
+    },
+    /// Run existing tasks through the LLM to fill in missing due dates, tags and
+    /// actionable titles, e.g. `enrich --filter status:active --missing due_date`
+    Enrich {
+        /// Enrich only this task, without confirmation (run in the background by `capture --enrich`)
+        #[arg(conflicts_with_all = ["filter", "missing", "yes"])]
+        id: Option<String>,
+        /// Filter query selecting the tasks, as in the TUI's `/` filter (default: all open tasks)
+        #[arg(long, value_name = "QUERY")]
+        filter: Option<String>,
+        /// Only tasks missing this field (repeatable)
+        #[arg(long, value_enum)]
+        missing: Vec<commands::EnrichField>,
+        /// Write the changes without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Check task files for parse errors and invalid values
+    Doctor {
+        /// Back up .tasktui-config.yaml and replace it with the default settings
+        #[arg(long)]
+        reset_config: bool,
+    },
+    /// Print the latest log file
+    Logs {
+        /// Number of trailing lines to show
+        #[arg(short = 'n', long, default_value_t = 50)]
+        lines: usize,
+        /// Keep printing new lines as they are written
+        #[arg(short, long)]
+        follow: bool,
+    },
+    /// Write a weekly retrospective to reviews/YYYY-WW.md
+    Retro {
+        /// Week to review, as YYYY-WW (default: this week)
+        #[arg(long)]
+        week: Option<String>,
+        /// Only list the facts, without an LLM-written narrative
+        #[arg(long)]
+        no_llm: bool,
+    },
+    /// Render a task or project as a standalone markdown or HTML document
+    Show {
+        /// Task id, short code or unique id prefix
+        id: String,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = export::Format::Md)]
+        format: export::Format,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Report on a project for stakeholders
+    Project {
+        #[command(subcommand)]
+        action: ProjectAction,
+    },
+    /// Create tasks from a markdown checklist, nesting indented items as subtasks
+    Import {
+        /// Format of the file
+        #[arg(long, value_enum, default_value_t = import::Source::Markdown)]
+        from: import::Source,
+        /// File to import, e.g. TODO.md
+        file: PathBuf,
+    },
+    /// Generate a synthetic vault of tasks and projects for demos and performance testing
+    Seed {
+        /// Number of tasks
+        #[arg(long, default_value_t = 500)]
+        tasks: usize,
+        /// Number of projects
+        #[arg(long, default_value_t = 10)]
+        projects: usize,
+        /// Random seed; the same seed gives the same vault
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+        /// Add to a data directory that already has tasks
+        #[arg(long)]
+        force: bool,
+    },
+    /// Post today's plan or due-soon alerts to the Slack webhook in the config
+    Notify {
+        #[arg(value_enum)]
+        message: integrations::slack::Message,
+        /// Print the message instead of posting it
+        #[arg(long)]
+        print: bool,
+    },
+    /// Serve a read-only HTML dashboard (today, board, projects) over HTTP
+    ServeWeb {
+        /// Address to listen on; use 0.0.0.0:8080 to reach it from other devices on the LAN
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        bind: String,
+    },
+    /// Manage tags across all tasks
+    Tag {
+        #[command(subcommand)]
+        action: TagAction,
+    },
+    /// Sync tasks with an external service
+    Sync {
+        #[command(subcommand)]
+        target: SyncTarget,
+    },
+    /// Manage the LLM enrichment cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    /// Snapshot the data directory to a compressed file, or restore one
+    Backup {
+        #[command(subcommand)]
+        action: BackupAction,
+    },
+    /// List or call the WASM plugins in `plugins/`
+    #[cfg(feature = "plugins")]
+    Plugin {
+        #[command(subcommand)]
+        action: PluginAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProjectAction {
+    /// Task table, milestones and a burndown sparkline, ready to paste into a status email or wiki
+    Report {
+        /// Project id, short code or unique id prefix
+        id: String,
+        /// Markdown, or the task table alone as CSV
+        #[arg(long, value_enum, default_value_t = export::ReportFormat::Md)]
+        format: export::ReportFormat,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum TagAction {
+    /// Replace a tag on every task (and in workstreams and Kanban columns), in one commit
+    Rename {
+        old: String,
+        new: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum SyncTarget {
+    /// Two-way sync with the CalDAV calendar set under `caldav` in the config
+    Caldav,
+}
+
+#[derive(Subcommand)]
+enum CacheAction {
+    /// Delete all cached enrichment results
+    Clear,
+}
+
+#[cfg(feature = "plugins")]
+#[derive(Subcommand)]
+enum PluginAction {
+    /// Show each plugin and the tools it registered
+    List,
+    /// Call a plugin tool and print the JSON it returns
+    Call {
+        tool: String,
+        /// Arguments as a JSON object
+        arguments: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum BackupAction {
+    /// Write the tasks and config to a timestamped .tar.zst
+    Create {
+        /// Encrypt with a passphrase (asked for, or read from TASKTUI_BACKUP_PASSPHRASE)
+        #[arg(long)]
+        encrypt: bool,
+        /// Folder for the backup, outside the data directory; defaults to .backups in it
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+    /// List backups, newest first
+    List {
+        /// Folder to look in; defaults to .backups in the data directory
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+    /// Replace the data directory with a backup's contents, after backing up the current state
+    Restore {
+        file: PathBuf,
+        /// Don't ask for confirmation
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+/// `key=value` from `--set`
+fn parse_setting(setting: &str) -> Result<(String, String), String> {
+    let (key, value) = setting.split_once('=').ok_or("expected KEY=VALUE")?;
+    if key.trim().is_empty() {
+        return Err("expected KEY=VALUE".to_string());
+    }
+    Ok((key.trim().to_string(), value.to_string()))
 }
 
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
+    // Flags win over TASKTUI_* variables, which win over the config file
+    let mut settings = cli.settings.clone();
+    settings.extend(cli.openai_api_key.clone().map(|key| ("openai_api_key".to_string(), key)));
+    settings.extend(cli.llm_model.clone().map(|model| ("llm_model".to_string(), model)));
+    config::set_flag_settings(settings);
+
+    // Reading logs shouldn't create a fresh (empty) log file for today
+    if !matches!(cli.command, Some(Commands::Logs { .. })) {
+        if let Err(e) = logging::init(cli.verbose) {
+            eprintln!("Warning: logging disabled: {:#}", e);
+        }
+    }
+    tracing::info!(version = env!("CARGO_PKG_VERSION"), data_dir = %cli.data_dir.display(), "Starting tasktui");
+
+    #[cfg(feature = "replay")]
+    if let Some(keys) = &cli.replay {
+        let script = std::fs::read_to_string(keys)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", keys.display(), e))?;
+        print!("{}", tui::replay(&cli.data_dir, &script)?.dump());
+        return Ok(());
+    }
+
     match cli.command {
-        Some(Commands::Server) => {
+        Some(Commands::Server { strict }) => {
             // Run MCP server mode
-            mcp::run(cli.data_dir)
+            mcp::run(cli.data_dir, strict)
+        }
+        Some(Commands::Capture { text, popup, enrich }) => {
+            commands::capture(cli.data_dir, &text.join(" "), popup, enrich)
+        }
+        Some(Commands::Voice { files, watch, interval }) => {
+            commands::voice(cli.data_dir, &files, watch.as_deref(), interval)
+        }
+        Some(Commands::Enrich { id: Some(id), .. }) => {
+            commands::enrich(cli.data_dir, &id)
+        }
+        Some(Commands::Enrich { id: None, filter, missing, yes }) => {
+            commands::enrich_bulk(cli.data_dir, filter.as_deref(), &missing, yes)
+        }
+        Some(Commands::Doctor { reset_config }) => {
+            commands::doctor(cli.data_dir, reset_config)
+        }
+        Some(Commands::Logs { lines, follow }) => {
+            commands::logs(lines, follow)
+        }
+        Some(Commands::Retro { week, no_llm }) => {
+            commands::retro(cli.data_dir, week.as_deref(), no_llm)
+        }
+        Some(Commands::Show { id, format, output }) => {
+            commands::show(cli.data_dir, &id, format, output)
+        }
+        Some(Commands::Project { action: ProjectAction::Report { id, format, output } }) => {
+            commands::project_report(cli.data_dir, &id, format, output)
+        }
+        Some(Commands::Import { from, file }) => {
+            commands::import(cli.data_dir, from, &file)
+        }
+        Some(Commands::Seed { tasks, projects, seed, force }) => {
+            commands::seed(cli.data_dir, tasks, projects, seed, force)
+        }
+        Some(Commands::Notify { message, print }) => {
+            commands::notify(cli.data_dir, message, print)
+        }
+        Some(Commands::ServeWeb { bind }) => {
+            web::serve(cli.data_dir, &bind)
+        }
+        Some(Commands::Tag { action: TagAction::Rename { old, new } }) => {
+            commands::tag_rename(cli.data_dir, &old, &new)
+        }
+        Some(Commands::Sync { target: SyncTarget::Caldav }) => {
+            commands::sync_caldav(cli.data_dir)
+        }
+        Some(Commands::Cache { action: CacheAction::Clear }) => {
+            commands::cache_clear()
+        }
+        Some(Commands::Backup { action: BackupAction::Create { encrypt, dir } }) => {
+            commands::backup_create(cli.data_dir, dir, encrypt)
+        }
+        Some(Commands::Backup { action: BackupAction::List { dir } }) => {
+            commands::backup_list(cli.data_dir, dir)
+        }
+        Some(Commands::Backup { action: BackupAction::Restore { file, yes } }) => {
+            commands::backup_restore(cli.data_dir, &file, yes)
+        }
+        #[cfg(feature = "plugins")]
+        Some(Commands::Plugin { action: PluginAction::List }) => {
+            commands::plugin_list(cli.data_dir)
+        }
+        #[cfg(feature = "plugins")]
+        Some(Commands::Plugin { action: PluginAction::Call { tool, arguments } }) => {
+            commands::plugin_call(cli.data_dir, &tool, arguments.as_deref())
         }
         None => {
             // Run TUI mode
+            commands::check_config(&cli.data_dir)?;
             tui::run(cli.data_dir)
         }
     }
@@ -1,26 +1,121 @@
-use crate::config::AppConfig;
-use crate::llm::{EnrichedTask, TaskEnricher};
-use crate::models::{ItemType, Priority, Status, TaskItem};
-use crate::storage::Storage;
+use crate::activity;
+use crate::backup::{self, Backup};
+use crate::briefing::Briefing;
+use crate::config::{AppConfig, PaneLayout, PriorityStyle};
+use crate::delegation;
+use crate::embeddings::{self, EmbeddingIndex};
+use crate::export;
+use crate::filter::{FilterExpr, SavedFilter, TagMode};
+use crate::git::{AheadBehind, GitSync};
+use crate::history::{self, Revision};
+use crate::integrations::jira;
+use crate::journal::{self, DayReview, JOURNAL_MAX_TOKENS, JOURNAL_PROMPT};
+use crate::links::LinkIndex;
+use crate::llm::{EnrichedTask, MonthlyUsage, TaskEnricher};
+use crate::models::{self, Due, FieldDef, FieldType, ItemType, Priority, Status, TaskItem};
+use crate::prioritize::{self, PriorityChange, PRIORITIZE_MAX_TOKENS, PRIORITIZE_PROMPT};
+use crate::quickadd::QuickAdd;
+use crate::related::RELATED_LIMIT;
+use crate::search::Hit;
+use crate::storage::{self, LoadBatch, Storage};
+use crate::tags;
+use crate::validate;
 use anyhow::Result;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Color,
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::time::{Duration, Instant};
 
 use uuid::Uuid;
-use super::{kanban, compact, settings, projects, project_gantt, THEME};
+use super::glyphs::{self, Glyphs};
+use super::input::TextInput;
+use super::panes::{self, Side};
+use super::state::{Drafts, UiState};
+use super::{kanban, compact, settings, projects, project_gantt, triage, people, goals, stale, shutdown, dashboard, filter_builder, status_bar, text, THEME};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ViewMode {
     Kanban,
     Compact,
     Settings,
     Projects,
     ProjectGantt,
+    Triage,
+    People,
+    Goals,
+    Stale,
+    Shutdown,
+    Dashboard,
+}
+
+/// The git history of the task last shown on the preview's History tab
+pub struct TaskHistory {
+    pub task_id: Uuid,
+    /// The task's last save when the history was read
+    pub touched: DateTime<Utc>,
+    pub revisions: Result<Vec<Revision>, String>,
+}
+
+/// Card order within a Kanban column, chosen per column with `o`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KanbanSort {
+    /// Cards moved with `J`/`K`, then the rest in load order
+    #[default]
+    Manual,
+    /// Most urgent first, then by due date
+    Priority,
+    /// Soonest deadline first, undated last
+    Due,
+    /// Oldest first
+    Age,
+}
+
+impl KanbanSort {
+    pub fn next(self) -> Self {
+        match self {
+            KanbanSort::Manual => KanbanSort::Priority,
+            KanbanSort::Priority => KanbanSort::Due,
+            KanbanSort::Due => KanbanSort::Age,
+            KanbanSort::Age => KanbanSort::Manual,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            KanbanSort::Manual => "manual",
+            KanbanSort::Priority => "priority",
+            KanbanSort::Due => "due",
+            KanbanSort::Age => "age",
+        }
+    }
+
+    fn sort(self, tasks: &mut [&TaskItem], manual: Option<&Vec<Uuid>>) {
+        let deadline = |t: &TaskItem| t.frontmatter.due_date.map(|d| d.deadline());
+        match self {
+            KanbanSort::Manual => {
+                let Some(order) = manual else { return };
+                tasks.sort_by_key(|t| order.iter().position(|id| *id == t.frontmatter.id).unwrap_or(usize::MAX));
+            }
+            KanbanSort::Priority => tasks.sort_by_key(|t| {
+                (t.frontmatter.priority.rank(), deadline(t).is_none(), deadline(t))
+            }),
+            KanbanSort::Due => tasks.sort_by_key(|t| (deadline(t).is_none(), deadline(t))),
+            KanbanSort::Age => tasks.sort_by_key(|t| t.frontmatter.created_at),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -29,13 +124,133 @@ pub enum SettingsSection {
     Workstreams,
     Goals,
     ApiKeys,
+    Sync,
+    Backups,
+}
+
+/// Chart shown under the Gantt view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectChart {
+    Burndown,
+    CumulativeFlow,
+}
+
+/// Archiving a project that still has open tasks, waiting on what to do with them
+#[derive(Debug, Clone)]
+pub struct ProjectArchive {
+    pub project_id: Uuid,
+    /// Picking the project to move the tasks to, as an index into `archive_targets()`
+    pub moving: Option<usize>,
+}
+
+/// Priority changes proposed by the LLM, each applied only once accepted
+#[derive(Debug, Clone)]
+pub struct PriorityReview {
+    pub changes: Vec<PriorityChange>,
+    /// Parallel to `changes`
+    pub accepted: Vec<bool>,
+    pub selected: usize,
+}
+
+/// Rows of Settings → Sync, top to bottom
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncSetting {
+    Remote,
+    Credentials,
+    Token,
+    Test,
+}
+
+pub const SYNC_SETTINGS: [SyncSetting; 4] = [SyncSetting::Remote, SyncSetting::Credentials, SyncSetting::Token, SyncSetting::Test];
+
+/// Tasks a git pull changed on disk while this copy of them was loaded, each
+/// reloaded or overwritten with the loaded copy once chosen
+#[derive(Debug, Clone)]
+pub struct PullConflicts {
+    pub task_ids: Vec<Uuid>,
+    pub selected: usize,
+}
+
+/// What happens to a project's open tasks when it is archived
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChildAction {
+    Archive,
+    Detach,
+    MoveTo(Uuid),
+}
+
+/// A status/tag change that may need WIP confirmation before it is applied
+#[derive(Debug, Clone)]
+pub struct TaskMove {
+    pub task_id: Uuid,
+    pub status: Option<Status>,
+    pub add_tag: Option<String>,
+    pub remove_tag: Option<String>,
+}
+
+impl TaskMove {
+    pub fn to_status(task_id: Uuid, status: Status) -> Self {
+        Self {
+            task_id,
+            status: Some(status),
+            add_tag: None,
+            remove_tag: None,
+        }
+    }
+}
+
+/// How long a transient status bar message stays visible
+const STATUS_MESSAGE_TTL: Duration = Duration::from_secs(4);
+
+/// Transient feedback shown in the status bar
+#[derive(Debug, Clone)]
+pub struct StatusMessage {
+    pub text: String,
+    pub is_error: bool,
+    pub at: Instant,
+}
+
+/// Sections of the Compact list, top to bottom
+pub const COMPACT_SECTIONS: [Status; 3] = [Status::Active, Status::Next, Status::Done];
+
+/// Done tasks shown per page in the Compact view
+pub const DONE_PAGE_SIZE: usize = 10;
+
+/// Matches listed by the `/` search
+const SEARCH_LIMIT: usize = 15;
+
+/// Task files parsed per batch when loading in the background
+const LOAD_BATCH_SIZE: usize = 250;
+
+/// Due range of the `.` quick filter, in filter query terms
+const DUE_SOON: (&str, &str) = ("today", "today+3");
+
+/// The focused task and its neighbours in the current view, captured before tasks
+/// change so the cursor can be put back on the same task afterwards
+#[derive(Debug, Default)]
+pub struct SelectionAnchor {
+    view: Option<ViewMode>,
+    focus: Option<Uuid>,
+    /// Tasks after the focused one (nearest first), then those before it
+    neighbours: Vec<Uuid>,
+}
+
+/// First visible row of each long list, kept between frames. Rendering only has
+/// `&App`, so the offsets live in cells.
+#[derive(Debug, Default)]
+pub struct ScrollOffsets {
+    pub compact: Cell<usize>,
+    pub projects: Cell<usize>,
+    /// Per Kanban column index
+    pub kanban: RefCell<HashMap<usize, usize>>,
 }
 
-/// Column indices for Kanban view
-pub const KANBAN_COL_ACTIVE: usize = 0;
-pub const KANBAN_COL_NEXT: usize = 1;
-pub const KANBAN_COL_WAITING: usize = 2;
-pub const KANBAN_COL_DONE: usize = 3;
+/// A selectable row in the Compact list
+#[derive(Debug, Clone)]
+pub enum CompactRow<'a> {
+    Header(Status),
+    Task(&'a TaskItem),
+}
 
 pub struct App {
     pub storage: Storage,
@@ -43,66 +258,411 @@ pub struct App {
     pub data_dir: PathBuf,
     pub view_mode: ViewMode,
     pub tasks: Vec<TaskItem>,
+    /// Batches from the background loader, until it finishes
+    loading: Option<Receiver<Result<LoadBatch>>>,
+    /// Task focused when the app was last closed, selected once it has loaded
+    restore_focus: Option<Uuid>,
     pub selected_index: usize,
-    pub active_filter: Option<String>,
+    pub show_preview: bool,
+    pub collapsed_sections: BTreeSet<Status>,
+    pub done_page: usize,
+    /// Order open Compact sections by most recently updated instead of by deadline
+    pub sort_recent: bool,
+    pub filter: FilterExpr,
     pub show_new_task: bool,
-    pub new_task_title: String,
+    pub new_task_title: TextInput,
     pub new_task_project_id: Option<Uuid>, // Project to assign new task to (from @project or Gantt view)
-    // Kanban navigation state
+    pub new_task_default_tags: Vec<String>, // Workstream tags from the active filter, shown as chips
+    /// Lines of a multi-line paste waiting for confirmation, one task each
+    pub new_task_batch: Vec<String>,
+    pub new_task_batch_enrich: bool,
+    // Kanban navigation state (index into config.kanban_columns)
     pub kanban_column: usize,
     pub kanban_row: usize,
     // Settings view state
     pub settings_section: SettingsSection,  // Which section (Workstreams or Goals)
     pub settings_selected: usize,
     pub settings_editing: bool,
-    pub settings_edit_text: String,
+    pub settings_edit_text: TextInput,
     pub settings_edit_area: String,  // For goal area selection
     // Projects view state
     pub projects_selected: usize,
     pub current_project_id: Option<Uuid>,
     pub gantt_selected: usize,
     pub gantt_scroll_offset: i32,
+    pub project_chart: Option<ProjectChart>,
     pub show_new_project: bool,
-    pub new_project_title: String,
+    pub new_project_title: TextInput,
+    // Inbox triage state
+    pub triage_index: usize,
+    pub triage_scheduling: bool,
+    pub triage_input: TextInput,
+    // Stale review state
+    pub stale_selected: usize,
+    pub stale_rescheduling: bool,
+    pub stale_input: TextInput,
+    // End-of-day shutdown state
+    pub shutdown_selected: usize,
+    pub shutdown_rescheduling: bool,
+    pub shutdown_input: TextInput,
+    pub shutdown_journal: Option<String>,
+    // Per-column Kanban sort and manual card order, by column name
+    pub kanban_sort: BTreeMap<String, KanbanSort>,
+    pub kanban_order: BTreeMap<String, Vec<Uuid>>,
+    /// Show done tasks older than `done_window_days` in the Done column
+    pub done_expanded: bool,
+    /// Show the selected card's full title in a popup
+    pub kanban_peek: bool,
+    // Split panes: the Gantt beside the Kanban board, and which pane has focus
+    pub kanban_split: bool,
+    pub pane_focus: Side,
+    pub preview_scroll: u16,
+    /// The preview shows the selected task's git history instead of its details
+    pub preview_history: bool,
+    pub task_history: Option<TaskHistory>,
+    // Background fetches: the last count, the check in flight, when the last
+    // fetch started and the storage sync count it saw
+    pub remote_status: Option<AheadBehind>,
+    remote_check: Option<Receiver<Result<Option<AheadBehind>>>>,
+    remote_fetched_at: Option<Instant>,
+    remote_syncs_seen: u64,
+    // The vault's `origin`, and the connection test started from Settings →
+    // Sync with how the last one went
+    pub remote_url: Option<String>,
+    sync_test: Option<Receiver<Result<()>>>,
+    pub sync_test_result: Option<Result<(), String>>,
+    // Backups listed under Settings → Backups, newest first
+    pub backups: Vec<Backup>,
+    // Tasks changed by another device's sync, shown over the view until resolved
+    pub pull_conflicts: Option<PullConflicts>,
+    // Morning briefing, shown over the view until a key is pressed
+    pub briefing: Option<Briefing>,
+    pub briefed_on: Option<NaiveDate>,
+    // Workstream dashboard: index into `config.workstreams`
+    pub dashboard_stream: usize,
+    // Delegation state
+    pub people_selected: usize,
+    pub show_delegate: bool,
+    pub delegate_input: TextInput,
+    pub delegate_task_id: Option<Uuid>,
+    // Goals view and goal picker state
+    pub goals_selected: usize,
+    pub goal_pick_task_id: Option<Uuid>,
+    pub goal_pick_selected: usize,
+    // [[links]] between tasks, rebuilt when tasks are (re)loaded or bodies change
+    pub links: LinkIndex,
+    /// Related task ids for the last previewed task
+    related_cache: RefCell<Option<(Uuid, Vec<Uuid>)>>,
+    pub show_links: bool,
+    pub links_selected: usize,
+    // `/` search state
+    pub show_search: bool,
+    pub search_input: TextInput,
+    pub search_selected: usize,
+    /// Search by meaning through the embeddings API instead of by words
+    pub search_semantic: bool,
+    /// The last semantic query answered and the tasks it found, best first
+    semantic_results: Option<(String, Vec<Uuid>)>,
+    /// Semantic searches still running, by query
+    semantic_searches: Vec<(String, Receiver<Result<Vec<Uuid>>>)>,
+    pub scroll: ScrollOffsets,
+    // Quick note state
+    pub show_note: bool,
+    pub note_input: TextInput,
+    pub note_task_id: Option<Uuid>,
+    // Custom fields editor state
+    pub fields_task_id: Option<Uuid>,
+    pub fields_selected: usize,
+    pub fields_editing: bool,
+    pub fields_input: TextInput,
+    // Filter builder state
+    pub show_filter_builder: bool,
+    pub filter_input: TextInput,
+    pub filter_saved_selected: Option<usize>,
+    pub filter_naming: bool,
+    pub filter_name_input: TextInput,
+    // Status change waiting on WIP limit confirmation
+    pub wip_pending: Option<TaskMove>,
+    // Tag rename (old, new) offered after renaming a workstream
+    pub retag_pending: Option<(String, String)>,
+    // Project archive waiting on what to do with its open tasks
+    pub project_archive: Option<ProjectArchive>,
+    pub priority_review: Option<PriorityReview>,
+    // Transient status bar message
+    pub status_message: Option<StatusMessage>,
+    /// Deadlines up to this local time have been announced
+    reminders_checked: NaiveDateTime,
+    /// Linked Jira issues fetched with `J`, by key
+    pub jira_issues: HashMap<String, jira::Issue>,
+    // Jira calls in flight, by issue key: fetches for the preview and
+    // transitions to done after marking a task done
+    jira_fetches: Vec<(String, Receiver<Result<jira::Issue>>)>,
+    jira_completions: Vec<(String, Receiver<Result<Option<String>>>)>,
     // LLM enricher for natural language task parsing
     enricher: TaskEnricher,
 }
 
 impl App {
+    /// Open without waiting for tasks; they stream in through `poll_loading`
     pub fn new(data_dir: PathBuf) -> Result<Self> {
-        let storage = Storage::new(data_dir.clone())?;
-        let config = AppConfig::load(&data_dir)?;
-        let tasks = storage.load_all_tasks()?;
+        let mut storage = Storage::new(data_dir.clone())?;
+        let (config, config_issues) = AppConfig::load_checked(&data_dir)?;
+        storage.activity_log = config.activity_log;
+        storage.hooks = config.hooks.clone();
+        storage.backups = config.backups;
+        storage.set_git_config(&config.git);
 
         // Initialize LLM enricher with API key from config (if present)
-        let enricher = TaskEnricher::new(config.openai_api_key.clone());
+        let enricher = TaskEnricher::from_config(&config, &data_dir);
+        let loading = storage::load_in_background(data_dir.clone(), LOAD_BATCH_SIZE);
+        let state = UiState::load(&data_dir);
+        if let Some(git_sync) = &storage.git_sync {
+            if let Err(err) = git_sync.exclude(UiState::FILE_NAME) {
+                tracing::warn!(error = ?err, "Failed to keep UI state out of git");
+            }
+        }
+        let kanban_column = state.kanban_column.min(config.kanban_columns.len().saturating_sub(1));
 
-        Ok(Self {
+        let mut app = Self {
             storage,
             config,
             data_dir,
-            view_mode: ViewMode::Compact,
-            tasks,
+            view_mode: state.view.unwrap_or(ViewMode::Compact),
+            tasks: Vec::new(),
+            loading: Some(loading),
+            restore_focus: state.selected,
             selected_index: 0,
-            active_filter: None,
+            show_preview: false,
+            collapsed_sections: BTreeSet::new(),
+            done_page: 0,
+            sort_recent: false,
+            filter: state.filter,
             show_new_task: false,
-            new_task_title: String::new(),
+            new_task_title: TextInput::default(),
             new_task_project_id: None,
-            kanban_column: KANBAN_COL_ACTIVE,
+            new_task_default_tags: Vec::new(),
+            new_task_batch: Vec::new(),
+            new_task_batch_enrich: false,
+            kanban_column,
             kanban_row: 0,
             settings_section: SettingsSection::default(),
             settings_selected: 0,
             settings_editing: false,
-            settings_edit_text: String::new(),
+            settings_edit_text: TextInput::default(),
             settings_edit_area: String::from("work"),
             projects_selected: 0,
-            current_project_id: None,
+            current_project_id: state.project_id,
             gantt_selected: 0,
             gantt_scroll_offset: 0,
+            project_chart: None,
             show_new_project: false,
-            new_project_title: String::new(),
+            new_project_title: TextInput::default(),
+            triage_index: 0,
+            triage_scheduling: false,
+            triage_input: TextInput::default(),
+            stale_selected: 0,
+            stale_rescheduling: false,
+            stale_input: TextInput::default(),
+            shutdown_selected: 0,
+            shutdown_rescheduling: false,
+            shutdown_input: TextInput::default(),
+            shutdown_journal: None,
+            kanban_sort: state.kanban_sort,
+            kanban_order: state.kanban_order,
+            done_expanded: false,
+            kanban_peek: false,
+            kanban_split: false,
+            pane_focus: Side::Left,
+            preview_scroll: 0,
+            preview_history: false,
+            task_history: None,
+            remote_status: None,
+            remote_check: None,
+            remote_fetched_at: None,
+            remote_syncs_seen: 0,
+            remote_url: None,
+            sync_test: None,
+            sync_test_result: None,
+            backups: Vec::new(),
+            pull_conflicts: None,
+            briefing: None,
+            briefed_on: state.briefed_on,
+            dashboard_stream: 0,
+            people_selected: 0,
+            show_delegate: false,
+            delegate_input: TextInput::default(),
+            delegate_task_id: None,
+            goals_selected: 0,
+            goal_pick_task_id: None,
+            goal_pick_selected: 0,
+            links: LinkIndex::default(),
+            related_cache: RefCell::new(None),
+            show_links: false,
+            links_selected: 0,
+            show_search: false,
+            search_input: TextInput::default(),
+            search_selected: 0,
+            search_semantic: false,
+            semantic_results: None,
+            semantic_searches: Vec::new(),
+            scroll: ScrollOffsets::default(),
+            show_note: false,
+            note_input: TextInput::multiline(),
+            note_task_id: None,
+            fields_task_id: None,
+            fields_selected: 0,
+            fields_editing: false,
+            fields_input: TextInput::default(),
+            show_filter_builder: false,
+            filter_input: TextInput::default(),
+            filter_saved_selected: None,
+            filter_naming: false,
+            filter_name_input: TextInput::default(),
+            wip_pending: None,
+            retag_pending: None,
+            project_archive: None,
+            priority_review: None,
+            status_message: None,
+            reminders_checked: models::now_local(),
+            jira_issues: HashMap::new(),
+            jira_fetches: Vec::new(),
+            jira_completions: Vec::new(),
             enricher,
-        })
+        };
+        app.recover_drafts(state.drafts);
+        if let Some(first) = config_issues.first() {
+            app.notify_error(format!(
+                "Config: {} setting(s) replaced by defaults ({}{}); original kept in .tasktui-config.yaml.bak",
+                config_issues.len(),
+                first,
+                if config_issues.len() > 1 { ", …" } else { "" },
+            ));
+        }
+        Ok(app)
+    }
+
+    /// Reopen dialogs whose text was saved when the app last stopped
+    fn recover_drafts(&mut self, drafts: Drafts) {
+        if drafts.is_empty() {
+            return;
+        }
+        if let Some(title) = drafts.new_task {
+            if self.view_mode == ViewMode::ProjectGantt {
+                self.show_new_task_dialog_for_project();
+            } else {
+                self.show_new_task_dialog();
+            }
+            self.new_task_title.set(title);
+        } else if let Some(title) = drafts.new_project {
+            self.show_new_project_dialog();
+            self.new_project_title.set(title);
+        } else if let Some((task_id, text)) = drafts.note {
+            self.show_note_dialog(task_id);
+            self.note_input.set(text);
+        }
+        self.notify("Recovered an unsaved draft from the last session");
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.loading.is_some()
+    }
+
+    /// Merge task batches the background loader has sent so far, keeping the cursor
+    /// in place. Returns true when anything changed.
+    pub fn poll_loading(&mut self) -> Result<bool> {
+        let Some(receiver) = &self.loading else {
+            return Ok(false);
+        };
+
+        let mut batches = Vec::new();
+        let finished = loop {
+            match receiver.try_recv() {
+                Ok(batch) => batches.push(batch),
+                Err(TryRecvError::Empty) => break false,
+                Err(TryRecvError::Disconnected) => break true,
+            }
+        };
+        if finished {
+            self.loading = None;
+        }
+        if batches.is_empty() && !finished {
+            return Ok(false);
+        }
+
+        let anchor = self.selection_anchor();
+        let had_focus = self.view_focused_task().is_some();
+        // Tasks created while loading may also be in a batch
+        let mut known: HashSet<Uuid> = self.tasks.iter().map(|t| t.frontmatter.id).collect();
+        for batch in batches {
+            for task in self.storage.adopt(batch?) {
+                if known.insert(task.frontmatter.id) {
+                    self.tasks.push(task);
+                }
+            }
+        }
+
+        if finished {
+            #[cfg(feature = "search-index")]
+            self.storage.sync_search_index(&self.tasks);
+            self.rebuild_links();
+            self.report_invalid_tasks();
+            self.open_briefing();
+        }
+        if had_focus {
+            self.restore_selection(anchor);
+        } else {
+            self.compact_select_first_task();
+        }
+        if self.restore_focus.is_some_and(|id| self.view_select_task(id)) {
+            self.restore_focus = None;
+        }
+        if finished {
+            self.restore_focus = None;
+            // The project open at last exit may have been deleted since
+            if self.view_mode == ViewMode::ProjectGantt && self.get_current_project().is_none() {
+                self.view_mode = ViewMode::Projects;
+            }
+        }
+        Ok(true)
+    }
+
+    /// Text in open dialogs, worth keeping if the app stops before it's confirmed
+    fn drafts(&self) -> Drafts {
+        let draft = |open: bool, text: &str| (open && !text.trim().is_empty()).then(|| text.to_string());
+        Drafts {
+            new_task: draft(self.show_new_task, &self.new_task_title),
+            new_project: draft(self.show_new_project, &self.new_project_title),
+            note: self.note_task_id
+                .zip(draft(self.show_note, &self.note_input)),
+        }
+    }
+
+    /// Remember the view, filter, cursor and open dialog drafts for the next start
+    pub fn save_ui_state(&self) -> Result<()> {
+        let state = UiState {
+            view: Some(self.view_mode),
+            project_id: self.current_project_id,
+            filter: self.filter.clone(),
+            kanban_column: self.kanban_column,
+            selected: self.view_focused_task().or(self.restore_focus),
+            kanban_sort: self.kanban_sort.clone(),
+            kanban_order: self.kanban_order.clone(),
+            briefed_on: self.briefed_on,
+            drafts: self.drafts(),
+        };
+        state.save(&self.data_dir)
+    }
+
+    /// Show the morning briefing if it's on, hasn't been shown today and has
+    /// anything to say
+    pub fn open_briefing(&mut self) {
+        let now = models::now_local();
+        if !self.config.briefing || self.briefed_on == Some(now.date()) {
+            return;
+        }
+        self.briefed_on = Some(now.date());
+        let briefing = Briefing::new(&self.tasks, &self.config, now);
+        self.briefing = (!briefing.is_empty()).then_some(briefing);
     }
 
     pub fn toggle_view(&mut self) {
@@ -112,43 +672,203 @@ impl App {
             ViewMode::Settings => ViewMode::Compact,
             ViewMode::Projects => ViewMode::Compact,
             ViewMode::ProjectGantt => ViewMode::Projects,
+            ViewMode::Triage => ViewMode::Compact,
+            ViewMode::People => ViewMode::Compact,
+            ViewMode::Goals => ViewMode::Compact,
+            ViewMode::Stale => ViewMode::Compact,
+            ViewMode::Shutdown => ViewMode::Compact,
+            ViewMode::Dashboard => ViewMode::Compact,
+        };
+    }
+
+    // === Layout ===
+
+    /// Config key for views with resizable panes
+    fn layout_key(view: ViewMode) -> Option<&'static str> {
+        match view {
+            ViewMode::Compact => Some("compact"),
+            ViewMode::Kanban => Some("kanban"),
+            ViewMode::ProjectGantt => Some("gantt"),
+            _ => None,
+        }
+    }
+
+    pub fn layout(&self, view: ViewMode) -> PaneLayout {
+        let key = Self::layout_key(view).unwrap_or("compact");
+        self.config.layouts.get(key).copied().unwrap_or_else(|| PaneLayout::default_for(key)).clamped()
+    }
+
+    /// Move the current view's pane divider left or right (Ctrl-←/→) and save it.
+    /// With a split open (the Compact preview, the Gantt beside the board) this
+    /// resizes its right pane, otherwise the sidebar.
+    pub fn resize_pane(&mut self, right: bool) -> Result<()> {
+        let Some(key) = Self::layout_key(self.view_mode) else {
+            return Ok(());
+        };
+        if self.view_mode == ViewMode::Kanban && !self.kanban_split {
+            return Ok(());
+        }
+        let mut layout = self.layout(self.view_mode);
+        if self.is_split() {
+            let (min, max) = PaneLayout::PREVIEW_RANGE;
+            let percent = if right { layout.preview_percent.saturating_sub(5) } else { layout.preview_percent + 5 };
+            layout.preview_percent = percent.clamp(min, max);
+        } else {
+            let (min, max) = PaneLayout::SIDEBAR_RANGE;
+            let width = if right { layout.sidebar_width + 2 } else { layout.sidebar_width.saturating_sub(2) };
+            layout.sidebar_width = width.clamp(min, max);
+        }
+        self.config.layouts.insert(key.to_string(), layout);
+        self.save_config()
+    }
+
+    // === Split panes ===
+
+    /// The current view has a second pane open beside it
+    pub fn is_split(&self) -> bool {
+        match self.view_mode {
+            ViewMode::Compact => self.show_preview,
+            ViewMode::Kanban => self.kanban_split,
+            _ => false,
+        }
+    }
+
+    /// Keys go to the right pane of the split (the preview, or the Gantt)
+    pub fn right_pane_focused(&self) -> bool {
+        self.is_split() && self.pane_focus == Side::Right
+    }
+
+    /// Ctrl-w: move the focus to the other pane of a split
+    pub fn switch_pane_focus(&mut self) {
+        if self.is_split() {
+            self.pane_focus = self.pane_focus.other();
+            self.preview_scroll = 0;
+        }
+    }
+
+    /// `|` on the board: show the selected card's project schedule beside it
+    pub fn toggle_kanban_split(&mut self) {
+        self.kanban_split = !self.kanban_split;
+        self.pane_focus = Side::Left;
+        self.sync_split_project();
+    }
+
+    /// The project the selected card belongs to, or is
+    fn kanban_card_project(&self) -> Option<Uuid> {
+        let task = self.kanban_selected_task()?;
+        if task.is_project() {
+            return Some(task.frontmatter.id);
+        }
+        let parent = task.frontmatter.parent_goal_id?;
+        self.tasks.iter().any(|t| t.frontmatter.id == parent && t.is_project()).then_some(parent)
+    }
+
+    /// Point the Gantt beside the board at the selected card's project. A card
+    /// outside any project leaves the last one shown.
+    pub fn sync_split_project(&mut self) {
+        if self.view_mode != ViewMode::Kanban || !self.kanban_split || self.pane_focus == Side::Right {
+            return;
+        }
+        if let Some(id) = self.kanban_card_project().filter(|id| self.current_project_id != Some(*id)) {
+            self.current_project_id = Some(id);
+            self.gantt_selected = 0;
+            self.gantt_scroll_offset = 0;
+        }
+    }
+
+    /// Scroll the focused preview pane a line up or down
+    pub fn scroll_preview(&mut self, down: bool) {
+        self.preview_scroll = if down { self.preview_scroll.saturating_add(1) } else { self.preview_scroll.saturating_sub(1) };
+    }
+
+    /// Tab in the focused preview: switch between the task's details and its history
+    pub fn toggle_preview_history(&mut self) {
+        self.preview_history = !self.preview_history;
+        self.preview_scroll = 0;
+    }
+
+    /// Read the previewed task's history from git when the History tab shows a
+    /// task it hasn't been read for, or one saved since
+    pub fn refresh_task_history(&mut self) {
+        if !self.preview_history || !self.show_preview || self.view_mode != ViewMode::Compact {
+            return;
+        }
+        let Some(task) = self.compact_selected_task() else {
+            return;
         };
+        let (id, touched) = (task.frontmatter.id, task.last_touched());
+        if self.task_history.as_ref().is_some_and(|h| h.task_id == id && h.touched == touched) {
+            return;
+        }
+        let revisions = match &self.storage.git_sync {
+            Some(git_sync) => history::task_history(git_sync, &task.file_path).map_err(|e| format!("{:#}", e)),
+            None => Err("Not a git repository".to_string()),
+        };
+        self.task_history = Some(TaskHistory { task_id: id, touched, revisions });
     }
 
     pub fn open_settings(&mut self) {
         self.view_mode = ViewMode::Settings;
+        self.remote_url = self.storage.git_sync.as_ref().and_then(|git_sync| git_sync.remote_url());
         self.settings_section = SettingsSection::Workstreams;
         self.settings_selected = 0;
         self.settings_editing = false;
         self.settings_edit_text.clear();
-        self.settings_edit_area = String::from("work");
+        self.settings_edit_area = self.default_goal_area();
     }
 
     pub fn close_settings(&mut self) {
         self.view_mode = ViewMode::Compact;
     }
 
-    pub fn settings_toggle_section(&mut self) {
-        self.settings_section = match self.settings_section {
-            SettingsSection::Workstreams => SettingsSection::Goals,
-            SettingsSection::Goals => SettingsSection::ApiKeys,
-            SettingsSection::ApiKeys => SettingsSection::Workstreams,
+    /// Switch to the next settings section (Tab), or the previous one (Shift+Tab)
+    pub fn settings_toggle_section(&mut self, forward: bool) {
+        self.settings_section = match (self.settings_section, forward) {
+            (SettingsSection::Workstreams, true) | (SettingsSection::ApiKeys, false) => SettingsSection::Goals,
+            (SettingsSection::Goals, true) | (SettingsSection::Sync, false) => SettingsSection::ApiKeys,
+            (SettingsSection::ApiKeys, true) | (SettingsSection::Backups, false) => SettingsSection::Sync,
+            (SettingsSection::Sync, true) | (SettingsSection::Workstreams, false) => SettingsSection::Backups,
+            (SettingsSection::Backups, true) | (SettingsSection::Goals, false) => SettingsSection::Workstreams,
         };
         self.settings_selected = 0;
         self.settings_editing = false;
+        if self.settings_section == SettingsSection::Backups {
+            self.refresh_backups();
+        }
+    }
+
+    /// Area a new goal starts with: the first workstream
+    fn default_goal_area(&self) -> String {
+        self.config.workstreams.first().map(|ws| ws.name.clone()).unwrap_or_else(|| String::from("work"))
     }
 
     pub fn render(&mut self, frame: &mut Frame) {
+        // Reserve the bottom line for the status bar
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(frame.area());
+
         match self.view_mode {
-            ViewMode::Kanban => kanban::render(frame, self),
-            ViewMode::Compact => compact::render(frame, self),
-            ViewMode::Settings => settings::render(frame, self),
-            ViewMode::Projects => projects::render(frame, self),
-            ViewMode::ProjectGantt => project_gantt::render(frame, self),
+            ViewMode::Kanban if self.kanban_split => self.render_kanban_split(frame, chunks[0]),
+            ViewMode::Kanban => kanban::render(frame, chunks[0], self),
+            ViewMode::Compact => compact::render(frame, chunks[0], self),
+            ViewMode::Settings => settings::render(frame, chunks[0], self),
+            ViewMode::Projects => projects::render(frame, chunks[0], self),
+            ViewMode::ProjectGantt => project_gantt::render(frame, chunks[0], self),
+            ViewMode::Triage => triage::render(frame, chunks[0], self),
+            ViewMode::People => people::render(frame, chunks[0], self),
+            ViewMode::Goals => goals::render(frame, chunks[0], self),
+            ViewMode::Stale => stale::render(frame, chunks[0], self),
+            ViewMode::Shutdown => shutdown::render(frame, chunks[0], self),
+            ViewMode::Dashboard => dashboard::render(frame, chunks[0], self),
         }
+        status_bar::render(frame, chunks[1], self);
 
-        // Render new task dialog if open
-        if self.show_new_task {
+        // Render new task dialog if open, or the tasks of a multi-line paste
+        if self.show_new_task && !self.new_task_batch.is_empty() {
+            self.render_new_task_batch_dialog(frame);
+        } else if self.show_new_task {
             self.render_new_task_dialog(frame);
         }
 
@@ -156,14 +876,81 @@ impl App {
         if self.show_new_project {
             self.render_new_project_dialog(frame);
         }
+
+        // Render delegate dialog if open
+        if self.show_delegate {
+            self.render_delegate_dialog(frame);
+        }
+
+        // Render custom fields editor if open
+        if self.fields_task_id.is_some() {
+            self.render_fields_dialog(frame);
+        }
+
+        // Render goal picker if open
+        if self.goal_pick_task_id.is_some() {
+            self.render_goal_picker(frame);
+        }
+
+        // Render link picker if open
+        if self.show_links {
+            self.render_links_dialog(frame);
+        }
+
+        // Render search if open
+        if self.show_search {
+            self.render_search_dialog(frame);
+        }
+
+        // Render quick note dialog if open
+        if self.show_note {
+            self.render_note_dialog(frame);
+        }
+
+        // Render filter builder if open
+        if self.show_filter_builder {
+            filter_builder::render(frame, self);
+        }
+
+        // Render WIP limit confirmation if pending
+        if self.wip_pending.is_some() {
+            self.render_wip_dialog(frame);
+        }
+
+        // Render retag confirmation after a workstream rename
+        if self.retag_pending.is_some() {
+            self.render_retag_dialog(frame);
+        }
+
+        // Render the choice for a project's open tasks when archiving it
+        if self.project_archive.is_some() {
+            self.render_project_archive_dialog(frame);
+        }
+
+        // Render the LLM's proposed priorities for review
+        if let Some(review) = &self.priority_review {
+            self.render_priority_review(frame, review);
+        }
+
+        // Render tasks another device changed under us until each is resolved
+        if let Some(conflicts) = &self.pull_conflicts {
+            self.render_pull_conflicts(frame, conflicts);
+        }
+
+        // Render the morning briefing over everything until dismissed
+        if let Some(briefing) = &self.briefing {
+            self.render_briefing(frame, briefing);
+        }
     }
 
     fn render_new_task_dialog(&self, frame: &mut Frame) {
         let area = frame.area();
 
+        let chips = self.new_task_chips_line();
+
         // Center the dialog
-        let dialog_width = 50.min(area.width.saturating_sub(4));
-        let dialog_height = 5;
+        let dialog_width = 60.min(area.width.saturating_sub(4));
+        let dialog_height = if chips.is_some() { 8 } else { 7 };
         let dialog_area = Rect {
             x: (area.width.saturating_sub(dialog_width)) / 2,
             y: (area.height.saturating_sub(dialog_height)) / 2,
@@ -175,14 +962,13 @@ impl App {
         frame.render_widget(Clear, dialog_area);
 
         // Create dialog content
-        let input_text = format!("{}_", self.new_task_title);
-        let content = vec![
+        let mut content = vec![
             Line::from(""),
-            Line::from(vec![
-                Span::raw(" "),
-                Span::styled(&input_text, THEME.normal_style()),
-            ]),
+            self.new_task_title.to_line(" ", THEME.normal_style()),
+            Line::from(""),
+            self.new_task_tokens_line(),
         ];
+        content.extend(chips);
 
         let dialog = Paragraph::new(content)
             .block(
@@ -196,12 +982,12 @@ impl App {
         frame.render_widget(dialog, dialog_area);
     }
 
-    fn render_new_project_dialog(&self, frame: &mut Frame) {
+    fn render_new_task_batch_dialog(&self, frame: &mut Frame) {
         let area = frame.area();
 
-        // Center the dialog
-        let dialog_width = 50.min(area.width.saturating_sub(4));
-        let dialog_height = 5;
+        // Center the dialog, growing with the list up to the screen height
+        let dialog_width = 60.min(area.width.saturating_sub(4));
+        let dialog_height = (self.new_task_batch.len() as u16 + 6).min(area.height.saturating_sub(2));
         let dialog_area = Rect {
             x: (area.width.saturating_sub(dialog_width)) / 2,
             y: (area.height.saturating_sub(dialog_height)) / 2,
@@ -212,20 +998,39 @@ impl App {
         // Clear the area behind the dialog
         frame.render_widget(Clear, dialog_area);
 
-        // Create dialog content
-        let input_text = format!("{}_", self.new_project_title);
-        let content = vec![
-            Line::from(""),
-            Line::from(vec![
-                Span::raw(" "),
-                Span::styled(&input_text, THEME.normal_style()),
-            ]),
+        let title_width = (dialog_width as usize).saturating_sub(6);
+        let mut content = vec![Line::from("")];
+        let shown = (dialog_height as usize).saturating_sub(6);
+        for line in self.new_task_batch.iter().take(shown) {
+            content.push(Line::from(vec![
+                Span::styled(" • ", THEME.dim_style()),
+                Span::styled(text::truncate(line, title_width), THEME.normal_style()),
+            ]));
+        }
+        if self.new_task_batch.len() > shown {
+            content.push(Line::from(Span::styled(
+                format!("   … and {} more", self.new_task_batch.len() - shown),
+                THEME.dim_style(),
+            )));
+        }
+        content.push(Line::from(""));
+        let mut keys = vec![
+            Span::raw(" "),
+            Span::styled("Enter", THEME.accent_style()),
+            Span::raw(" create all  "),
         ];
+        if self.enricher.is_available() {
+            keys.push(Span::styled("e", THEME.accent_style()));
+            keys.push(Span::raw(if self.new_task_batch_enrich { " enrich: on  " } else { " enrich: off  " }));
+        }
+        keys.push(Span::styled("Esc", THEME.accent_style()));
+        keys.push(Span::raw(" back"));
+        content.push(Line::from(keys));
 
         let dialog = Paragraph::new(content)
             .block(
                 Block::default()
-                    .title(" New Project ")
+                    .title(format!(" New Tasks ({}) ", self.new_task_batch.len()))
                     .title_style(THEME.accent_style())
                     .borders(Borders::ALL)
                     .border_style(THEME.border_focused_style())
@@ -234,669 +1039,4843 @@ impl App {
         frame.render_widget(dialog, dialog_area);
     }
 
-    pub fn next_task(&mut self) {
-        if !self.filtered_tasks().is_empty() {
-            self.selected_index = (self.selected_index + 1) % self.filtered_tasks().len();
+    /// Defaults from the filter or Gantt view as chips, removed with Backspace on an empty input
+    fn new_task_chips_line(&self) -> Option<Line<'static>> {
+        let project = self.new_task_project_id.and_then(|id| self.task_by_id(id));
+        if self.new_task_default_tags.is_empty() && project.is_none() {
+            return None;
         }
-    }
-
-    pub fn previous_task(&mut self) {
-        let filtered = self.filtered_tasks();
-        if !filtered.is_empty() {
-            if self.selected_index == 0 {
-                self.selected_index = filtered.len() - 1;
-            } else {
-                self.selected_index -= 1;
-            }
+        let mut spans = vec![Span::raw(" ")];
+        for tag in &self.new_task_default_tags {
+            spans.push(Span::styled(format!("[#{} ×]", tag), THEME.tag_style()));
+            spans.push(Span::raw(" "));
         }
+        if let Some(project) = project {
+            spans.push(Span::styled(format!("[→ {} ×]", project.frontmatter.title), THEME.accent_style()));
+            spans.push(Span::raw(" "));
+        }
+        if self.new_task_title.is_empty() {
+            spans.push(Span::styled(" ⌫ remove", THEME.dim_style()));
+        }
+        Some(Line::from(spans))
     }
 
-    pub fn toggle_task_selection(&mut self) {
-        // Future: expand/collapse task details
-    }
-
-    pub fn show_new_task_dialog(&mut self) {
-        self.show_new_task = true;
-        self.new_task_title.clear();
-        self.new_task_project_id = None;
-    }
-
-    pub fn show_new_task_dialog_for_project(&mut self) {
-        self.show_new_task = true;
-        self.new_task_title.clear();
-        // Pre-assign to current project when creating from Gantt view
-        self.new_task_project_id = self.current_project_id;
-    }
-
-    pub fn cancel_new_task_dialog(&mut self) {
-        self.show_new_task = false;
-        self.new_task_title.clear();
-        self.new_task_project_id = None;
-    }
-
-    pub fn create_new_task(&mut self) -> Result<()> {
-        if self.new_task_title.trim().is_empty() {
-            self.show_new_task = false;
-            self.new_task_project_id = None;
-            return Ok(());
+    /// What the inline tokens in the new-task input will set, or a syntax hint
+    fn new_task_tokens_line(&self) -> Line<'static> {
+        let parsed = QuickAdd::parse(&self.new_task_title, models::today());
+        if !parsed.has_tokens() {
+            return Line::from(Span::styled(" #tag !p1 @context due:fri +project", THEME.dim_style()));
         }
 
-        // Parse @project syntax from input (e.g., "fix bug @myproject")
-        let (input_text, project_from_at) = self.parse_project_reference(self.new_task_title.trim());
-
-        // Get goals context for LLM prioritization
-        let goals_context = self.config.goals_context();
-        let goals_ref = if goals_context.is_empty() { None } else { Some(goals_context.as_str()) };
-
-        // Use LLM to enrich the raw input (will fallback to simple task if no API key)
-        let enriched = self.enricher.enrich_sync(&input_text, goals_ref);
-
-        // Create task with enriched data
-        let mut task = TaskItem::new(enriched.title, ItemType::Task);
-
-        // Apply enriched fields
-        if let Some(due_date) = enriched.due_date {
-            task.frontmatter.due_date = Some(due_date);
+        let mut spans = vec![Span::raw(" ")];
+        for tag in parsed.all_tags() {
+            spans.push(Span::styled(format!("#{} ", tag), THEME.tag_style()));
         }
-        if let Some(priority) = enriched.priority {
-            task.frontmatter.priority = match priority.to_lowercase().as_str() {
-                "high" => Priority::High,
-                "low" => Priority::Low,
-                _ => Priority::Medium,
-            };
+        if let Some(priority) = &parsed.priority {
+            spans.push(self.priority_badge(priority));
+            spans.push(Span::raw("  "));
         }
-        if !enriched.tags.is_empty() {
-            task.frontmatter.tags = enriched.tags;
+        if let Some(due) = &parsed.due_date {
+            spans.push(Span::styled(format!("due {}  ", self.config.display.due_label(due, models::today())), THEME.normal_style()));
+            if let Some(warning) = self.holiday_warning(due) {
+                spans.push(warning);
+                spans.push(Span::raw("  "));
+            }
         }
-        if let Some(context) = enriched.context {
-            task.body = context;
+        if let Some(name) = &parsed.project {
+            match self.find_project(name).and_then(|id| self.task_by_id(id)) {
+                Some(project) => spans.push(Span::styled(format!("→ {}", project.frontmatter.title), THEME.accent_style())),
+                None => spans.push(Span::styled(format!("no project +{}", name), THEME.warning_style())),
+            }
         }
+        Line::from(spans)
+    }
 
-        // Assign to project: @project syntax takes precedence, then Gantt view context
-        task.frontmatter.parent_goal_id = project_from_at.or(self.new_task_project_id);
+    fn render_new_project_dialog(&self, frame: &mut Frame) {
+        let area = frame.area();
 
-        self.storage.write_task(&mut task)?;
-        self.tasks.push(task);
+        // Center the dialog
+        let dialog_width = 50.min(area.width.saturating_sub(4));
+        let dialog_height = 5;
+        let dialog_area = Rect {
+            x: (area.width.saturating_sub(dialog_width)) / 2,
+            y: (area.height.saturating_sub(dialog_height)) / 2,
+            width: dialog_width,
+            height: dialog_height,
+        };
 
-        // Navigate to the new task (it's the last Active task since new tasks start as Active)
-        let active_count = self.tasks.iter()
-            .filter(|t| t.frontmatter.status == Status::Active)
-            .count();
-        self.selected_index = active_count.saturating_sub(1);
+        // Clear the area behind the dialog
+        frame.render_widget(Clear, dialog_area);
 
-        // Also update Kanban view to show the new task
-        self.kanban_column = KANBAN_COL_ACTIVE;
-        let kanban_active_count = self.kanban_column_tasks().len();
-        self.kanban_row = kanban_active_count.saturating_sub(1);
+        // Create dialog content
+        let content = vec![
+            Line::from(""),
+            self.new_project_title.to_line(" ", THEME.normal_style()),
+        ];
+
+        let dialog = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title(" New Project ")
+                    .title_style(THEME.accent_style())
+                    .borders(Borders::ALL)
+                    .border_style(THEME.border_focused_style())
+            );
+
+        frame.render_widget(dialog, dialog_area);
+    }
+
+    fn render_delegate_dialog(&self, frame: &mut Frame) {
+        let area = frame.area();
+
+        // Center the dialog
+        let dialog_width = 50.min(area.width.saturating_sub(4));
+        let dialog_height = 5;
+        let dialog_area = Rect {
+            x: (area.width.saturating_sub(dialog_width)) / 2,
+            y: (area.height.saturating_sub(dialog_height)) / 2,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        // Clear the area behind the dialog
+        frame.render_widget(Clear, dialog_area);
+
+        // Create dialog content
+        let content = vec![
+            Line::from(""),
+            self.delegate_input.to_line(" ", THEME.normal_style()),
+        ];
+
+        let dialog = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title(" Delegate To ")
+                    .title_style(THEME.accent_style())
+                    .borders(Borders::ALL)
+                    .border_style(THEME.border_focused_style())
+            );
+
+        frame.render_widget(dialog, dialog_area);
+    }
+
+    fn render_note_dialog(&self, frame: &mut Frame) {
+        let area = frame.area();
+
+        // Center the dialog, growing with the note's lines
+        let dialog_width = 60.min(area.width.saturating_sub(4));
+        let dialog_height = (self.note_input.split('\n').count() as u16 + 6).min(area.height.saturating_sub(2));
+        let dialog_area = Rect {
+            x: (area.width.saturating_sub(dialog_width)) / 2,
+            y: (area.height.saturating_sub(dialog_height)) / 2,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        // Clear the area behind the dialog
+        frame.render_widget(Clear, dialog_area);
+
+        let mut content = vec![Line::from("")];
+        content.extend(self.note_input.to_lines(" ", THEME.normal_style()));
+        content.push(Line::from(""));
+        content.push(Line::from(Span::styled(" enter save  alt+enter newline  esc cancel", THEME.dim_style())));
+
+        let dialog = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title(" Add Note ")
+                    .title_style(THEME.accent_style())
+                    .borders(Borders::ALL)
+                    .border_style(THEME.border_focused_style())
+            );
+
+        frame.render_widget(dialog, dialog_area);
+    }
+
+    fn render_links_dialog(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let entries = self.selected_task_links();
+
+        // Center the dialog
+        let dialog_width = 60.min(area.width.saturating_sub(4));
+        let dialog_height = (entries.len() as u16 + 4).min(area.height.saturating_sub(2));
+        let dialog_area = Rect {
+            x: (area.width.saturating_sub(dialog_width)) / 2,
+            y: (area.height.saturating_sub(dialog_height)) / 2,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        // Clear the area behind the dialog
+        frame.render_widget(Clear, dialog_area);
+
+        let mut content = vec![Line::from("")];
+        for (idx, (outgoing, task)) in entries.iter().enumerate() {
+            let arrow = if *outgoing { "→ " } else { "← " };
+            let kind = if task.is_project() { "  (project)" } else { "" };
+            content.push(if idx == self.links_selected {
+                Line::from(vec![
+                    Span::styled(" ▸ ", THEME.accent_style()),
+                    Span::styled(arrow, THEME.accent_style()),
+                    Span::styled(task.frontmatter.title.clone(), THEME.highlight_style()),
+                    Span::styled(kind, THEME.dim_style()),
+                ])
+            } else {
+                Line::from(vec![
+                    Span::raw("   "),
+                    Span::styled(arrow, THEME.dim_style()),
+                    Span::styled(task.frontmatter.title.clone(), THEME.normal_style()),
+                    Span::styled(kind, THEME.dim_style()),
+                ])
+            });
+        }
+
+        let dialog = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title(" Links  → links to  ← referenced by ")
+                    .title_style(THEME.accent_style())
+                    .borders(Borders::ALL)
+                    .border_style(THEME.border_focused_style())
+            );
+
+        frame.render_widget(dialog, dialog_area);
+    }
+
+    fn render_search_dialog(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let hits = self.search_hits();
+
+        let dialog_width = 80.min(area.width.saturating_sub(4));
+        let dialog_height = (hits.len().max(1) as u16 + 5).min(area.height.saturating_sub(2));
+        let dialog_area = Rect {
+            x: (area.width.saturating_sub(dialog_width)) / 2,
+            y: (area.height.saturating_sub(dialog_height)) / 2,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        frame.render_widget(Clear, dialog_area);
+
+        let prefix = if self.search_semantic { " ≈ " } else { " / " };
+        let mut content = vec![Line::from(""), self.search_input.to_line(prefix, THEME.normal_style()), Line::from("")];
+        if self.is_searching() {
+            content.push(Line::from(Span::styled(" Searching…", THEME.dim_style())));
+        } else if self.search_semantic && hits.is_empty() {
+            content.push(Line::from(Span::styled(" Enter to search by meaning", THEME.dim_style())));
+        } else if hits.is_empty() && !self.search_input.trim().is_empty() {
+            content.push(Line::from(Span::styled(" No matches", THEME.dim_style())));
+        }
+        for (idx, hit) in hits.iter().enumerate() {
+            let selected = idx == self.search_selected.min(hits.len() - 1);
+            let mut spans = if selected {
+                vec![
+                    Span::styled(" ▸ ", THEME.accent_style()),
+                    Span::styled(hit.task.frontmatter.title.clone(), THEME.highlight_style()),
+                ]
+            } else {
+                vec![
+                    Span::raw("   "),
+                    Span::styled(hit.task.frontmatter.title.clone(), THEME.normal_style()),
+                ]
+            };
+            spans.push(Span::styled(format!("  {}", hit.task.frontmatter.status.as_str()), THEME.dim_style()));
+            if let Some(snippet) = &hit.snippet {
+                spans.push(Span::styled(format!("  {}", snippet), THEME.dim_style()));
+            }
+            content.push(Line::from(spans));
+        }
+
+        let dialog = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title(if self.search_semantic {
+                        " Semantic search  enter search/jump  tab words  esc close "
+                    } else {
+                        " Search  ↑↓ select  enter jump  tab meaning  esc close "
+                    })
+                    .title_style(THEME.accent_style())
+                    .borders(Borders::ALL)
+                    .border_style(THEME.border_focused_style())
+            );
+
+        frame.render_widget(dialog, dialog_area);
+    }
+
+    fn render_fields_dialog(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let Some(task) = self.fields_task_id.and_then(|id| self.task_by_id(id)) else {
+            return;
+        };
+        let rows = self.field_rows();
+
+        // Center the dialog
+        let dialog_width = 60.min(area.width.saturating_sub(4));
+        let dialog_height = (rows.len().max(1) as u16 + 6).min(area.height.saturating_sub(2));
+        let dialog_area = Rect {
+            x: (area.width.saturating_sub(dialog_width)) / 2,
+            y: (area.height.saturating_sub(dialog_height)) / 2,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        // Clear the area behind the dialog
+        frame.render_widget(Clear, dialog_area);
+
+        let mut content = vec![Line::from("")];
+        if rows.is_empty() {
+            content.push(Line::from(Span::styled(" No fields; define them on the project", THEME.dim_style())));
+        }
+        for (idx, (name, def)) in rows.iter().enumerate() {
+            let kind = def.as_ref().map_or("undefined", |d| d.kind.as_str());
+            let label = format!("{:<14} ", name);
+            let (marker, label_style) = if idx == self.fields_selected {
+                (Span::styled(" ▸ ", THEME.accent_style()), THEME.highlight_style())
+            } else {
+                (Span::raw("   "), THEME.normal_style())
+            };
+            let mut spans = vec![marker, Span::styled(label, label_style)];
+            if idx == self.fields_selected && self.fields_editing {
+                spans.extend(self.fields_input.to_line("", THEME.normal_style()).spans);
+            } else {
+                spans.push(match task.frontmatter.custom.get(name) {
+                    Some(value) => Span::styled(value.clone(), THEME.normal_style()),
+                    None => Span::styled("—", THEME.dim_style()),
+                });
+                spans.push(Span::styled(format!("  {}", kind), THEME.dim_style()));
+            }
+            content.push(Line::from(spans));
+        }
+        content.push(Line::from(""));
+        for warning in validate::custom_fields(&task.frontmatter, models::field_defs(&self.tasks, task)) {
+            content.push(Line::from(Span::styled(format!(" {}{}", self.glyphs().warning, warning.message), THEME.warning_style())));
+        }
+        let hint = if self.fields_editing { " Enter save · Esc cancel" } else { " Enter edit · ←→ choice · x clear · Esc close" };
+        content.push(Line::from(Span::styled(hint, THEME.dim_style())));
+
+        let dialog = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title(" Custom Fields ")
+                    .title_style(THEME.accent_style())
+                    .borders(Borders::ALL)
+                    .border_style(THEME.border_focused_style())
+            );
+
+        frame.render_widget(dialog, dialog_area);
+    }
+
+    fn render_goal_picker(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let choices = self.goal_choices();
+        let current = self.goal_pick_task_id
+            .and_then(|id| self.task_by_id(id))
+            .and_then(|t| t.frontmatter.goal_id);
+
+        // Center the dialog
+        let dialog_width = 60.min(area.width.saturating_sub(4));
+        let dialog_height = (choices.len() as u16 + 4).min(area.height.saturating_sub(2));
+        let dialog_area = Rect {
+            x: (area.width.saturating_sub(dialog_width)) / 2,
+            y: (area.height.saturating_sub(dialog_height)) / 2,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        // Clear the area behind the dialog
+        frame.render_widget(Clear, dialog_area);
+
+        let mut content = vec![Line::from("")];
+        for (idx, choice) in choices.iter().enumerate() {
+            let (label, area_name) = match choice.and_then(|id| self.config.goal(id)) {
+                Some(goal) => (goal.description.clone(), format!("  [{}]", goal.area)),
+                None => ("No goal".to_string(), String::new()),
+            };
+            let mark = if *choice == current { " ✓" } else { "" };
+            content.push(if idx == self.goal_pick_selected {
+                Line::from(vec![
+                    Span::styled(" ▸ ", THEME.accent_style()),
+                    Span::styled(label, THEME.highlight_style()),
+                    Span::styled(area_name, THEME.dim_style()),
+                    Span::styled(mark, THEME.accent_style()),
+                ])
+            } else {
+                Line::from(vec![
+                    Span::raw("   "),
+                    Span::styled(label, THEME.normal_style()),
+                    Span::styled(area_name, THEME.dim_style()),
+                    Span::styled(mark, THEME.accent_style()),
+                ])
+            });
+        }
+
+        let dialog = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title(" Link to Goal ")
+                    .title_style(THEME.accent_style())
+                    .borders(Borders::ALL)
+                    .border_style(THEME.border_focused_style())
+            );
+
+        frame.render_widget(dialog, dialog_area);
+    }
+
+    /// The board and the selected card's project schedule, each framed and
+    /// brighter while focused. Too narrow for both, only the focused one shows.
+    fn render_kanban_split(&self, frame: &mut Frame, area: Rect) {
+        let render_pane = |frame: &mut Frame, area: Rect, side: Side| {
+            let focused = self.pane_focus == side;
+            let title = match side {
+                Side::Left => " Board ",
+                Side::Right => " Schedule ",
+            };
+            let block = Block::default()
+                .title(title)
+                .title_style(if focused { THEME.highlight_style() } else { THEME.dim_style() })
+                .borders(Borders::ALL)
+                .border_style(panes::border_style(focused));
+            let inner = block.inner(area);
+            frame.render_widget(block, area);
+            match side {
+                Side::Left => kanban::render(frame, inner, self),
+                Side::Right if self.get_current_project().is_none() => {
+                    let hint = Line::from(Span::styled(" Select a card in a project to see its schedule", THEME.dim_style()));
+                    frame.render_widget(Paragraph::new(hint), inner);
+                }
+                Side::Right => project_gantt::render(frame, inner, self),
+            }
+        };
+
+        if area.width < panes::MIN_SPLIT_WIDTH {
+            render_pane(frame, area, self.pane_focus);
+            return;
+        }
+        let [board, schedule] = panes::split(area, self.layout(ViewMode::Kanban).preview_percent);
+        render_pane(frame, board, Side::Left);
+        render_pane(frame, schedule, Side::Right);
+    }
+
+    /// A banner across the top listing the tasks a pull changed under us
+    fn render_pull_conflicts(&self, frame: &mut Frame, conflicts: &PullConflicts) {
+        let area = frame.area();
+        let title_width = (area.width as usize).saturating_sub(8);
+
+        let mut content = Vec::new();
+        for (idx, id) in conflicts.task_ids.iter().enumerate() {
+            let Some(task) = self.tasks.iter().find(|t| t.frontmatter.id == *id) else {
+                continue;
+            };
+            let selected = idx == conflicts.selected;
+            content.push(Line::from(vec![
+                Span::styled(if selected { " ▸ " } else { "   " }, THEME.accent_style()),
+                Span::styled(
+                    text::truncate(&task.frontmatter.title, title_width),
+                    if selected { THEME.highlight_style() } else { THEME.normal_style() },
+                ),
+            ]));
+        }
+        content.push(Line::from(vec![
+            Span::raw(" "),
+            Span::styled("r", THEME.accent_style()),
+            Span::raw(" reload theirs  "),
+            Span::styled("m", THEME.accent_style()),
+            Span::raw(" keep mine  "),
+            Span::styled("Esc", THEME.accent_style()),
+            Span::raw(" reload all"),
+        ]));
+
+        let banner_height = (content.len() as u16 + 2).min(area.height.saturating_sub(2));
+        let banner_area = Rect { x: area.x, y: area.y, width: area.width, height: banner_height };
+        frame.render_widget(Clear, banner_area);
+
+        // Keep the selected task in view
+        let inner_height = banner_height.saturating_sub(3) as usize;
+        let scroll = (conflicts.selected + 1).saturating_sub(inner_height) as u16;
+        let banner = Paragraph::new(content)
+            .scroll((scroll, 0))
+            .block(
+                Block::default()
+                    .title(format!(" Changed on another device ({}) ", conflicts.task_ids.len()))
+                    .title_style(THEME.warning_style())
+                    .borders(Borders::ALL)
+                    .border_style(THEME.border_focused_style())
+            );
+        frame.render_widget(banner, banner_area);
+    }
+
+    fn render_briefing(&self, frame: &mut Frame, briefing: &Briefing) {
+        let display = &self.config.display;
+        let mut content = vec![Line::from("")];
+        if let Some(day_off) = &briefing.day_off {
+            content.push(Line::from(Span::styled(format!(" {} - enjoy the day off", day_off), THEME.accent_style())));
+            content.push(Line::from(""));
+        }
+        let sections = [
+            ("Overdue", &briefing.overdue, THEME.warning_style()),
+            ("Due today", &briefing.due_today, THEME.normal_style()),
+            ("Coming up", &briefing.coming_up, THEME.normal_style()),
+        ];
+        for (heading, tasks, style) in sections {
+            if tasks.is_empty() {
+                continue;
+            }
+            content.push(Line::from(Span::styled(format!(" {} ({})", heading, tasks.len()), THEME.title_style())));
+            for item in tasks.iter() {
+                content.push(Line::from(vec![
+                    Span::styled(format!("   {}", item.task.title), style),
+                    Span::styled(format!("  {}", display.due_label(&item.due, briefing.date)), THEME.dim_style()),
+                ]));
+            }
+            content.push(Line::from(""));
+        }
+        if let Some(suggestion) = &briefing.suggestion {
+            content.push(Line::from(Span::styled(" Suggested next", THEME.title_style())));
+            content.push(Line::from(vec![
+                Span::styled(format!("   {}", suggestion.task.title), THEME.accent_style()),
+                Span::styled(format!("  for \"{}\"", suggestion.goal), THEME.dim_style()),
+            ]));
+            content.push(Line::from(""));
+        }
+        content.push(Line::from(Span::styled(" Press any key to start", THEME.dim_style())));
+
+        let area = frame.area();
+        let dialog_width = 64.min(area.width.saturating_sub(4));
+        let dialog_height = (content.len() as u16 + 2).min(area.height.saturating_sub(2));
+        let dialog_area = Rect {
+            x: (area.width.saturating_sub(dialog_width)) / 2,
+            y: (area.height.saturating_sub(dialog_height)) / 2,
+            width: dialog_width,
+            height: dialog_height,
+        };
+        frame.render_widget(Clear, dialog_area);
+
+        let title = format!(" Good morning - {}, {} ", briefing.date.format("%A"), display.format_date(briefing.date));
+        let dialog = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title(title)
+                    .title_style(THEME.title_style())
+                    .borders(Borders::ALL)
+                    .border_style(THEME.border_focused_style())
+            );
+        frame.render_widget(dialog, dialog_area);
+    }
+
+    fn render_wip_dialog(&self, frame: &mut Frame) {
+        let Some(status) = self.wip_pending.as_ref().and_then(|m| m.status.as_ref()) else {
+            return;
+        };
+        let (count, limit) = self.wip_usage(status).unwrap_or((0, 0));
+        let area = frame.area();
+
+        // Center the dialog
+        let dialog_width = 50.min(area.width.saturating_sub(4));
+        let dialog_height = 6;
+        let dialog_area = Rect {
+            x: (area.width.saturating_sub(dialog_width)) / 2,
+            y: (area.height.saturating_sub(dialog_height)) / 2,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        // Clear the area behind the dialog
+        frame.render_widget(Clear, dialog_area);
+
+        let content = vec![
+            Line::from(""),
+            Line::from(vec![
+                Span::raw(" "),
+                Span::styled(
+                    format!("{} is at its WIP limit ({}/{}).", status.as_str(), count, limit),
+                    THEME.warning_style(),
+                ),
+            ]),
+            Line::from(vec![
+                Span::raw(" Move anyway? "),
+                Span::styled("y", THEME.accent_style()),
+                Span::raw("/"),
+                Span::styled("n", THEME.accent_style()),
+            ]),
+        ];
+
+        let dialog = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title(" WIP Limit ")
+                    .title_style(THEME.warning_style())
+                    .borders(Borders::ALL)
+                    .border_style(THEME.border_focused_style())
+            );
+
+        frame.render_widget(dialog, dialog_area);
+    }
+
+    fn render_retag_dialog(&self, frame: &mut Frame) {
+        let Some((old, new)) = &self.retag_pending else {
+            return;
+        };
+        let count = self.tasks.iter().filter(|t| t.has_tag(old)).count();
+        let area = frame.area();
+
+        let dialog_width = 50.min(area.width.saturating_sub(4));
+        let dialog_height = 6;
+        let dialog_area = Rect {
+            x: (area.width.saturating_sub(dialog_width)) / 2,
+            y: (area.height.saturating_sub(dialog_height)) / 2,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        frame.render_widget(Clear, dialog_area);
+
+        let content = vec![
+            Line::from(""),
+            Line::from(vec![
+                Span::raw(" "),
+                Span::styled(format!("{} tasks are tagged #{}.", count, old), THEME.warning_style()),
+            ]),
+            Line::from(vec![
+                Span::raw(format!(" Retag them #{}? ", new)),
+                Span::styled("y", THEME.accent_style()),
+                Span::raw("/"),
+                Span::styled("n", THEME.accent_style()),
+            ]),
+        ];
+
+        let dialog = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title(" Rename Tag ")
+                    .title_style(THEME.warning_style())
+                    .borders(Borders::ALL)
+                    .border_style(THEME.border_focused_style())
+            );
+
+        frame.render_widget(dialog, dialog_area);
+    }
+
+    fn render_project_archive_dialog(&self, frame: &mut Frame) {
+        let Some(pending) = &self.project_archive else {
+            return;
+        };
+        let Some(project) = self.tasks.iter().find(|t| t.frontmatter.id == pending.project_id) else {
+            return;
+        };
+        let count = models::open_children(&self.tasks, pending.project_id).len();
+        let targets = self.archive_targets();
+        let area = frame.area();
+
+        let mut content = vec![
+            Line::from(""),
+            Line::from(vec![
+                Span::raw(" "),
+                Span::styled(format!("{} has {} open tasks.", project.frontmatter.title, count), THEME.warning_style()),
+            ]),
+        ];
+        match pending.moving {
+            None => content.push(Line::from(vec![
+                Span::raw(" "),
+                Span::styled("a", THEME.accent_style()),
+                Span::raw(" archive them  "),
+                Span::styled("d", THEME.accent_style()),
+                Span::raw(" detach  "),
+                Span::styled("m", THEME.accent_style()),
+                Span::raw(" move  "),
+                Span::styled("Esc", THEME.accent_style()),
+                Span::raw(" cancel"),
+            ])),
+            Some(selected) => {
+                content.push(Line::from(" Move them to:"));
+                for (idx, target) in targets.iter().enumerate() {
+                    let style = if idx == selected { THEME.highlight_style() } else { THEME.normal_style() };
+                    content.push(Line::from(Span::styled(format!("   {}", target.frontmatter.title), style)));
+                }
+            }
+        }
+
+        let dialog_width = 56.min(area.width.saturating_sub(4));
+        let dialog_height = (content.len() as u16 + 2).min(area.height.saturating_sub(2));
+        let dialog_area = Rect {
+            x: (area.width.saturating_sub(dialog_width)) / 2,
+            y: (area.height.saturating_sub(dialog_height)) / 2,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        frame.render_widget(Clear, dialog_area);
+
+        let dialog = Paragraph::new(content)
+            .block(
+                Block::default()
+                    .title(" Archive Project ")
+                    .title_style(THEME.warning_style())
+                    .borders(Borders::ALL)
+                    .border_style(THEME.border_focused_style())
+            );
+
+        frame.render_widget(dialog, dialog_area);
+    }
+
+    fn render_priority_review(&self, frame: &mut Frame, review: &PriorityReview) {
+        let area = frame.area();
+        let dialog_width = 80.min(area.width.saturating_sub(4));
+        let title_width = (dialog_width as usize).saturating_sub(20);
+
+        let mut content = vec![Line::from("")];
+        for (idx, change) in review.changes.iter().enumerate() {
+            let selected = idx == review.selected;
+            let check = if review.accepted[idx] { "[x]" } else { "[ ]" };
+            content.push(Line::from(vec![
+                Span::styled(if selected { " ▸ " } else { "   " }, THEME.accent_style()),
+                Span::styled(format!("{} ", check), if review.accepted[idx] { THEME.accent_style() } else { THEME.dim_style() }),
+                self.priority_badge(&change.from),
+                Span::styled(" → ", THEME.dim_style()),
+                self.priority_badge(&change.to),
+                Span::styled(
+                    format!(" {}", text::truncate(&change.title, title_width)),
+                    if selected { THEME.highlight_style() } else { THEME.normal_style() },
+                ),
+            ]));
+            content.push(Line::from(Span::styled(
+                format!("       {}", text::truncate(&change.reason, title_width + 8)),
+                THEME.dim_style(),
+            )));
+        }
+        content.push(Line::from(""));
+        content.push(Line::from(vec![
+            Span::raw(" "),
+            Span::styled("Space", THEME.accent_style()),
+            Span::raw(" accept  "),
+            Span::styled("a", THEME.accent_style()),
+            Span::raw(" all  "),
+            Span::styled("Enter", THEME.accent_style()),
+            Span::raw(" apply accepted  "),
+            Span::styled("Esc", THEME.accent_style()),
+            Span::raw(" discard"),
+        ]));
+
+        let dialog_height = (content.len() as u16 + 2).min(area.height.saturating_sub(2));
+        let dialog_area = Rect {
+            x: (area.width.saturating_sub(dialog_width)) / 2,
+            y: (area.height.saturating_sub(dialog_height)) / 2,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        frame.render_widget(Clear, dialog_area);
+
+        // Keep the selected suggestion (two lines each) in view
+        let inner_height = dialog_height.saturating_sub(2) as usize;
+        let scroll = (1 + review.selected * 2 + 2).saturating_sub(inner_height.saturating_sub(2)) as u16;
+        let dialog = Paragraph::new(content)
+            .scroll((scroll, 0))
+            .block(
+                Block::default()
+                    .title(format!(" Suggested Priorities ({}) ", review.changes.len()))
+                    .title_style(THEME.accent_style())
+                    .borders(Borders::ALL)
+                    .border_style(THEME.border_focused_style())
+            );
+
+        frame.render_widget(dialog, dialog_area);
+    }
+
+    // === Status Bar ===
+
+    /// Show a transient confirmation in the status bar
+    pub fn notify(&mut self, text: impl Into<String>) {
+        self.status_message = Some(StatusMessage {
+            text: text.into(),
+            is_error: false,
+            at: Instant::now(),
+        });
+    }
+
+    /// Show a transient error in the status bar (and keep it in the log)
+    pub fn notify_error(&mut self, text: impl Into<String>) {
+        let text = text.into();
+        tracing::warn!(error = %text, "TUI action failed");
+        self.status_message = Some(StatusMessage {
+            text,
+            is_error: true,
+            at: Instant::now(),
+        });
+    }
+
+    /// The status message, if it hasn't expired yet
+    /// Announce timed tasks whose deadline passed since the last check
+    pub fn check_reminders(&mut self) {
+        let now = models::now_local();
+        let since = std::mem::replace(&mut self.reminders_checked, now);
+        let due: Vec<&str> = self.tasks.iter()
+            .filter(|t| !t.frontmatter.status.is_closed())
+            .filter(|t| t.frontmatter.due_date.is_some_and(|due| {
+                due.time.is_some() && (since..now).contains(&due.deadline())
+            }))
+            .map(|t| t.frontmatter.title.as_str())
+            .collect();
+
+        match due.as_slice() {
+            [] => {}
+            [title] => self.notify(format!("{}Due now: {}", self.glyphs().reminder, title)),
+            [title, rest @ ..] => self.notify(format!("{}Due now: {} and {} more", self.glyphs().reminder, title, rest.len())),
+        }
+    }
+
+    pub fn current_status_message(&self) -> Option<&StatusMessage> {
+        self.status_message.as_ref().filter(|m| m.at.elapsed() < STATUS_MESSAGE_TTL)
+    }
+
+    pub fn llm_available(&self) -> bool {
+        self.enricher.is_available()
+    }
+
+    /// The LLM providers in failover order, and the one that answered last
+    pub fn llm_providers(&self) -> (Vec<String>, Option<String>) {
+        (self.enricher.providers(), self.enricher.active_provider())
+    }
+
+    /// This month's LLM usage and the configured budget
+    pub fn llm_usage(&self) -> Option<(MonthlyUsage, Option<f64>)> {
+        self.enricher.usage()
+    }
+
+    pub fn next_task(&mut self) {
+        let count = self.compact_rows().len();
+        if count > 0 {
+            self.selected_index = (self.selected_index + 1) % count;
+        }
+    }
+
+    pub fn previous_task(&mut self) {
+        let count = self.compact_rows().len();
+        if count > 0 {
+            if self.selected_index == 0 {
+                self.selected_index = count - 1;
+            } else {
+                self.selected_index -= 1;
+            }
+        }
+    }
+
+    /// Toggle the Compact view's preview pane for the selected task
+    pub fn toggle_task_selection(&mut self) {
+        self.show_preview = !self.show_preview;
+        self.pane_focus = Side::Left;
+        self.preview_scroll = 0;
+    }
+
+    /// Enter in the Compact view: fold a section header, open the link picker for a
+    /// previewed task with links, otherwise toggle the preview
+    pub fn compact_enter(&mut self) {
+        let header = match self.compact_rows().get(self.selected_index) {
+            Some(CompactRow::Header(status)) => Some(status.clone()),
+            _ => None,
+        };
+        match header {
+            Some(status) => self.toggle_section(status),
+            None if self.show_preview && !self.selected_task_links().is_empty() => self.open_links(),
+            None => self.toggle_task_selection(),
+        }
+    }
+
+    /// Collapse or expand a Compact section
+    pub fn toggle_section(&mut self, status: Status) {
+        if !self.collapsed_sections.remove(&status) {
+            self.collapsed_sections.insert(status);
+        }
+        self.compact_clamp_selection();
+    }
+
+    /// Task under the cursor in the Compact view (None on a section header)
+    pub fn compact_selected_task(&self) -> Option<&TaskItem> {
+        match self.compact_rows().get(self.selected_index) {
+            Some(CompactRow::Task(task)) => Some(task),
+            _ => None,
+        }
+    }
+
+    /// Tasks in one Compact section; Done is ordered most recently completed first
+    pub fn compact_section_tasks(&self, status: &Status) -> Vec<&TaskItem> {
+        let mut tasks: Vec<&TaskItem> = self.filtered_tasks()
+            .into_iter()
+            .filter(|t| &t.frontmatter.status == status)
+            .collect();
+        if *status == Status::Done {
+            tasks.sort_by_key(|t| std::cmp::Reverse(t.frontmatter.completed_at));
+        } else if self.sort_recent {
+            tasks.sort_by_key(|t| std::cmp::Reverse(t.last_touched()));
+        } else {
+            // Soonest deadline first; undated tasks keep their order at the end
+            tasks.sort_by_key(|t| (t.frontmatter.due_date.is_none(), t.frontmatter.due_date.map(|d| d.deadline())));
+        }
+        tasks
+    }
+
+    /// (current page, page count) of the Done section
+    pub fn done_pages(&self) -> (usize, usize) {
+        let count = self.compact_section_tasks(&Status::Done).len();
+        let pages = count.div_ceil(DONE_PAGE_SIZE).max(1);
+        (self.done_page.min(pages - 1), pages)
+    }
+
+    /// Rows of the Compact list: section headers followed by their (visible) tasks.
+    /// Active always shows so there is somewhere to land; other sections only when non-empty.
+    pub fn compact_rows(&self) -> Vec<CompactRow<'_>> {
+        let mut rows = Vec::new();
+
+        for status in COMPACT_SECTIONS {
+            let tasks = self.compact_section_tasks(&status);
+            if tasks.is_empty() && status != Status::Active {
+                continue;
+            }
+            let collapsed = self.collapsed_sections.contains(&status);
+            let is_done = status == Status::Done;
+            rows.push(CompactRow::Header(status));
+            if collapsed {
+                continue;
+            }
+
+            if is_done {
+                let (page, _) = self.done_pages();
+                rows.extend(tasks.into_iter()
+                    .skip(page * DONE_PAGE_SIZE)
+                    .take(DONE_PAGE_SIZE)
+                    .map(CompactRow::Task));
+            } else {
+                rows.extend(tasks.into_iter().map(CompactRow::Task));
+            }
+        }
+
+        rows
+    }
+
+    /// Switch open sections between deadline order and recently updated first
+    pub fn toggle_sort_recent(&mut self) {
+        let anchor = self.selection_anchor();
+        self.sort_recent = !self.sort_recent;
+        self.restore_selection(anchor);
+        self.notify(if self.sort_recent { "Sorted by recently updated" } else { "Sorted by deadline" });
+    }
+
+    /// Move to the next (`forward`) or previous page of Done tasks
+    pub fn done_change_page(&mut self, forward: bool) {
+        let (page, pages) = self.done_pages();
+        self.done_page = if forward {
+            (page + 1).min(pages - 1)
+        } else {
+            page.saturating_sub(1)
+        };
+        self.collapsed_sections.remove(&Status::Done);
+
+        // Land on the first task of the new page
+        let rows = self.compact_rows();
+        if let Some(header) = rows.iter().position(|r| matches!(r, CompactRow::Header(Status::Done))) {
+            self.selected_index = (header + 1).min(rows.len() - 1);
+        }
+    }
+
+    /// Page of the Done section showing a task, if it's a (filtered-in) Done task
+    fn done_page_of(&self, task_id: Uuid) -> Option<usize> {
+        self.compact_section_tasks(&Status::Done)
+            .iter()
+            .position(|t| t.frontmatter.id == task_id)
+            .map(|pos| pos / DONE_PAGE_SIZE)
+    }
+
+    fn compact_select_first_task(&mut self) {
+        self.selected_index = self.compact_rows()
+            .iter()
+            .position(|r| matches!(r, CompactRow::Task(_)))
+            .unwrap_or(0);
+    }
+
+    fn compact_select_task(&mut self, task_id: Uuid) {
+        if let Some(row) = self.compact_rows()
+            .iter()
+            .position(|r| matches!(r, CompactRow::Task(t) if t.frontmatter.id == task_id))
+        {
+            self.selected_index = row;
+        }
+    }
+
+    fn compact_clamp_selection(&mut self) {
+        let count = self.compact_rows().len();
+        if self.selected_index >= count {
+            self.selected_index = count.saturating_sub(1);
+        }
+    }
+
+    /// Items whose parent is the given task or project
+    pub fn child_tasks(&self, parent_id: Uuid) -> Vec<&TaskItem> {
+        self.tasks.iter()
+            .filter(|t| t.frontmatter.parent_goal_id == Some(parent_id))
+            .collect()
+    }
+
+    /// New tasks default to the filter's workstream tags and project, so a task
+    /// created while looking at "work" doesn't vanish from the list
+    pub fn show_new_task_dialog(&mut self) {
+        self.show_new_task = true;
+        self.new_task_title.clear();
+        self.new_task_project_id = self.filter.project_id;
+        self.new_task_default_tags = match self.filter.tag_mode {
+            TagMode::All => self.filter.tags.iter()
+                .filter(|tag| self.config.workstreams.iter().any(|ws| ws.name.eq_ignore_ascii_case(tag)))
+                .cloned()
+                .collect(),
+            // "work or home" doesn't say which one the task belongs to
+            TagMode::Any => Vec::new(),
+        };
+    }
+
+    pub fn show_new_task_dialog_for_project(&mut self) {
+        self.show_new_task = true;
+        self.new_task_title.clear();
+        self.new_task_default_tags.clear();
+        // Pre-assign to current project when creating from Gantt view
+        self.new_task_project_id = self.current_project_id;
+    }
+
+    pub fn cancel_new_task_dialog(&mut self) {
+        self.show_new_task = false;
+        self.new_task_title.clear();
+        self.new_task_batch.clear();
+        self.new_task_project_id = None;
+        self.new_task_default_tags.clear();
+    }
+
+    /// Drop the last default chip: the project first, then tags from the end
+    pub fn new_task_remove_chip(&mut self) {
+        if self.new_task_project_id.take().is_none() {
+            self.new_task_default_tags.pop();
+        }
+    }
+
+    pub fn create_new_task(&mut self) -> Result<()> {
+        if self.new_task_title.trim().is_empty() {
+            self.cancel_new_task_dialog();
+            return Ok(());
+        }
+
+        let input = self.new_task_title.trim().to_string();
+        let mut task = match self.build_new_task(&input, true) {
+            Ok(task) => task,
+            Err(e) => {
+                self.notify_error(e);
+                return Ok(());
+            }
+        };
+        task.file_path = self.storage.write_task(&mut task)?;
+        let task_id = task.frontmatter.id;
+        match self.enricher.last_error() {
+            Some(e) => self.notify_error(format!("{}; created with offline rules: {}", e, task.frontmatter.title)),
+            None => self.notify(format!("Task created: {}", task.frontmatter.title)),
+        }
+        self.tasks.push(task);
+        self.rebuild_links();
+        self.select_new_task(task_id);
+
+        self.cancel_new_task_dialog();
+        Ok(())
+    }
+
+    /// A task from the new-task input, not yet saved. Inline tokens (#tag !high
+    /// @context due:fri +project) are applied locally and win over anything the
+    /// LLM suggests; without `enrich` only the offline rules run.
+    fn build_new_task(&self, input: &str, enrich: bool) -> Result<TaskItem, String> {
+        let (parsed, project_id) = self.parse_new_task(input)?;
+
+        // Get goals context for LLM prioritization
+        let goals_context = self.config.goals_context();
+        let goals_ref = if goals_context.is_empty() { None } else { Some(goals_context.as_str()) };
+
+        // Use LLM to enrich the remaining text (will fallback to simple task if no API key)
+        let enriched = if enrich {
+            self.enricher.enrich_sync(&parsed.title, goals_ref)
+        } else {
+            self.enricher.enrich_offline(&parsed.title)
+        };
+        Ok(self.finish_new_task(parsed, project_id, enriched))
+    }
+
+    /// The inline tokens of a new-task input, and the project they assign
+    fn parse_new_task(&self, input: &str) -> Result<(QuickAdd, Option<Uuid>), String> {
+        let mut parsed = QuickAdd::parse(input, models::today());
+        if parsed.title.is_empty() {
+            return Err("A task needs a title besides its tokens".to_string());
+        }
+        let project_id = match parsed.project.as_deref() {
+            Some(name) => match self.find_project(name) {
+                Some(id) => Some(id),
+                None => return Err(format!("No project matches +{}", name)),
+            },
+            // `@name` still assigns a project when it names one rather than a context
+            None => {
+                let pos = parsed.contexts.iter().position(|c| self.find_project(c).is_some());
+                pos.and_then(|pos| self.find_project(&parsed.contexts.remove(pos)))
+            }
+        };
+        Ok((parsed, project_id))
+    }
+
+    /// A new task from parsed tokens and the enrichment of its title
+    fn finish_new_task(&self, mut parsed: QuickAdd, project_id: Option<Uuid>, enriched: EnrichedTask) -> TaskItem {
+        // Create task with enriched data
+        let mut task = TaskItem::new(enriched.title, ItemType::Task);
+
+        // Apply enriched fields, with explicit tokens taking precedence
+        task.frontmatter.due_date = parsed.due_date.take()
+            .or_else(|| enriched.due_date.as_deref().and_then(Due::parse));
+        task.frontmatter.priority = match parsed.priority.take() {
+            Some(priority) => priority,
+            None => enriched.priority.as_deref().and_then(Priority::parse).unwrap_or(Priority::P2),
+        };
+        let mut tags = parsed.all_tags();
+        for tag in self.new_task_default_tags.iter().cloned().chain(enriched.tags) {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        task.frontmatter.tags = tags;
+        if let Some(context) = enriched.context {
+            task.body = context;
+        }
+
+        // Assign to project: +project token takes precedence, then Gantt view context
+        task.frontmatter.parent_goal_id = project_id.or(self.new_task_project_id);
+        task
+    }
+
+    /// Show a task just created wherever the current view lists it
+    fn select_new_task(&mut self, task_id: Uuid) {
+        // Navigate to the new task (new tasks start as Active)
+        self.collapsed_sections.remove(&Status::Active);
+        self.compact_select_task(task_id);
+
+        // Also update Kanban view to show the new task
+        self.kanban_select_task(task_id);
+
+        // Update Gantt selection if we're in that view
+        if self.view_mode == ViewMode::ProjectGantt {
+            self.gantt_selected = self.get_project_tasks().len().saturating_sub(1);
+        }
+    }
+
+    /// Turn a multi-line paste into the dialog into a list of tasks to confirm,
+    /// one per non-empty line. Text already typed becomes the first of them.
+    /// Returns false for a single line, which pastes as usual.
+    fn start_new_task_batch(&mut self, text: &str) -> bool {
+        let lines: Vec<String> = text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect();
+        if lines.len() < 2 {
+            return false;
+        }
+        let typed = self.new_task_title.trim().to_string();
+        self.new_task_batch = std::iter::once(typed).filter(|t| !t.is_empty()).chain(lines).collect();
+        self.new_task_batch_enrich = self.enricher.is_available();
+        true
+    }
+
+    /// Back to editing the single-task input
+    pub fn cancel_new_task_batch(&mut self) {
+        self.new_task_batch.clear();
+    }
+
+    /// Enrichment of a batch is a call per line, so it can be turned off
+    pub fn toggle_new_task_batch_enrich(&mut self) {
+        if self.enricher.is_available() {
+            self.new_task_batch_enrich = !self.new_task_batch_enrich;
+        }
+    }
+
+    /// Create a task per line of the confirmed batch. Lines that can't become a
+    /// task (an unknown +project, only tokens) are skipped and reported.
+    pub fn create_new_task_batch(&mut self) -> Result<()> {
+        let lines = std::mem::take(&mut self.new_task_batch);
+        let mut parsed = Vec::new();
+        let mut skipped = Vec::new();
+        for line in &lines {
+            match self.parse_new_task(line) {
+                Ok(task) => parsed.push(task),
+                Err(e) => skipped.push(format!("{} ({})", line, e)),
+            }
+        }
+
+        // The LLM calls run side by side, within the configured rate limits
+        let titles: Vec<String> = parsed.iter().map(|(p, _)| p.title.clone()).collect();
+        let enriched = if self.new_task_batch_enrich {
+            let goals_context = self.config.goals_context();
+            let goals_ref = if goals_context.is_empty() { None } else { Some(goals_context.as_str()) };
+            self.enricher.enrich_batch(&titles, goals_ref, |_, _| {})
+        } else {
+            titles.iter().map(|title| self.enricher.enrich_offline(title)).collect()
+        };
+
+        let mut created = Vec::new();
+        for ((parsed, project_id), enriched) in parsed.into_iter().zip(enriched) {
+            let mut task = self.finish_new_task(parsed, project_id, enriched);
+            task.file_path = self.storage.write_task(&mut task)?;
+            created.push(task.frontmatter.id);
+            self.tasks.push(task);
+        }
+        self.rebuild_links();
+        if let Some(&last) = created.last() {
+            self.select_new_task(last);
+        }
+
+        if skipped.is_empty() {
+            self.notify(format!("Created {} tasks", created.len()));
+        } else {
+            self.notify_error(format!("Created {} tasks, skipped {}: {}", created.len(), skipped.len(), skipped.join("; ")));
+        }
+        self.cancel_new_task_dialog();
+        Ok(())
+    }
+
+    /// Find a project whose title contains `name` (case-insensitive)
+    fn find_project(&self, name: &str) -> Option<Uuid> {
+        let name = name.to_lowercase();
+        self.tasks.iter()
+            .find(|t| t.is_project() && t.frontmatter.title.to_lowercase().contains(&name))
+            .map(|t| t.frontmatter.id)
+    }
+
+    pub fn mark_task_done(&mut self) -> Result<()> {
+        if let Some(task) = self.compact_selected_task() {
+            let task_id = task.frontmatter.id;
+            let anchor = self.selection_anchor();
+            let mut streak = None;
+            if let Some(task) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task_id) {
+                task.set_status(Status::Done);
+                self.storage.write_task(task)?;
+                streak = task.frontmatter.habit.map(|_| task.current_streak(models::today()));
+            }
+            self.restore_selection(anchor);
+            if let Some(streak) = streak {
+                self.notify(format!("Checked off, streak {}", streak));
+                return Ok(());
+            }
+            self.notify("Marked done");
+            self.complete_jira_issue(task_id);
+        }
+        Ok(())
+    }
+
+    /// Fetch the Jira issue linked to the selected task, for the preview
+    pub fn fetch_jira_issue(&mut self) {
+        let Some(key) = self.compact_selected_task().and_then(|t| t.frontmatter.jira_key.clone()) else {
+            self.notify("No Jira issue linked (set jira_key)");
+            return;
+        };
+        let Some(config) = &self.config.jira else {
+            self.notify_error("Jira isn't configured (add jira: to the config)");
+            return;
+        };
+        let receiver = jira::fetch_in_background(config, &key);
+        self.notify(format!("Fetching {}…", key));
+        self.jira_fetches.push((key, receiver));
+    }
+
+    /// Move a completed task's linked Jira issue to done, when `transition_on_done` is set
+    fn complete_jira_issue(&mut self, task_id: Uuid) {
+        let Some(config) = &self.config.jira else { return };
+        let Some(key) = self.tasks.iter()
+            .find(|t| t.frontmatter.id == task_id)
+            .and_then(|t| t.frontmatter.jira_key.clone())
+        else {
+            return;
+        };
+        if !config.transition_on_done {
+            return;
+        }
+        let receiver = jira::complete_in_background(config, &key);
+        self.jira_completions.push((key, receiver));
+    }
+
+    /// Report the Jira calls that have finished since the last tick
+    pub fn poll_jira(&mut self) {
+        for (key, result) in take_finished(&mut self.jira_fetches) {
+            match result {
+                Ok(issue) => {
+                    self.notify(format!("{}: {}", issue.key, issue.status));
+                    self.jira_issues.insert(key, issue);
+                }
+                Err(e) => self.notify_error(format!("Jira {}: {:#}", key, e)),
+            }
+        }
+        for (key, result) in take_finished(&mut self.jira_completions) {
+            match result {
+                Ok(Some(status)) => {
+                    self.notify(format!("{} moved to {}", key, status));
+                    self.jira_issues.remove(&key);
+                }
+                Ok(None) => {}
+                Err(e) => self.notify_error(format!("Marked done, but Jira {} wasn't updated: {:#}", key, e)),
+            }
+        }
+    }
+
+    /// Cycle task priority: P4 → P3 → ... → P0 → P4
+    pub fn cycle_task_priority(&mut self) -> Result<()> {
+        if let Some(task) = self.compact_selected_task() {
+            let task_id = task.frontmatter.id;
+            if let Some(task) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task_id) {
+                task.frontmatter.priority = task.frontmatter.priority.raised();
+                self.storage.write_task(task)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Cycle task priority in Kanban view
+    pub fn kanban_cycle_priority(&mut self) -> Result<()> {
+        if let Some(task) = self.kanban_selected_task() {
+            let task_id = task.frontmatter.id;
+            if let Some(task) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task_id) {
+                task.frontmatter.priority = task.frontmatter.priority.raised();
+                self.storage.write_task(task)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn archive_task(&mut self) -> Result<()> {
+        if let Some(task) = self.compact_selected_task() {
+            let task_id = task.frontmatter.id;
+            if task.is_project() {
+                return self.archive_project(task_id);
+            }
+            let anchor = self.selection_anchor();
+            if let Some(task) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task_id) {
+                task.set_status(Status::Archived);
+                self.storage.write_task(task)?;
+            }
+            self.restore_selection(anchor);
+            self.notify("Archived");
+        }
+        Ok(())
+    }
+
+    pub fn refresh_tasks(&mut self) -> Result<()> {
+        let anchor = self.selection_anchor();
+        // A full reload supersedes any background load still running
+        self.loading = None;
+        self.tasks = self.storage.load_all_tasks()?;
+        self.rebuild_links();
+        self.notify(format!("Reloaded {} tasks", self.tasks.len()));
+        self.report_invalid_tasks();
+        self.restore_selection(anchor);
+        Ok(())
+    }
+
+    /// Warn in the status bar when loaded tasks have invalid dates or tags
+    fn report_invalid_tasks(&mut self) {
+        let invalid = self.tasks.iter()
+            .filter(|t| !validate::validate(&t.frontmatter).is_empty())
+            .count();
+        if invalid > 0 {
+            self.notify_error(format!(
+                "{} task(s) have invalid fields; see the preview or run `tasktui doctor`",
+                invalid
+            ));
+        }
+    }
+
+    /// Filter by a workstream tag, keeping the `!` and `.` quick filters
+    pub fn filter_by_tag(&mut self, tag: &str) {
+        self.set_filter(FilterExpr {
+            priorities: self.filter.priorities.clone(),
+            due_from: self.filter.due_from.clone(),
+            due_to: self.filter.due_to.clone(),
+            ..FilterExpr::tag(tag)
+        });
+    }
+
+    /// Cycle the priority quick filter: all → P0–P1 → P0–P2
+    pub fn cycle_priority_filter(&mut self) {
+        let mut filter = self.filter.clone();
+        filter.priorities = match filter.priorities.as_slice() {
+            [] => vec![Priority::P0, Priority::P1],
+            [Priority::P0, Priority::P1] => vec![Priority::P0, Priority::P1, Priority::P2],
+            _ => Vec::new(),
+        };
+        self.set_filter(filter);
+    }
+
+    /// Toggle the "due soon" quick filter: due today or in the next three days
+    pub fn toggle_due_soon_filter(&mut self) {
+        let mut filter = self.filter.clone();
+        let (from, to) = DUE_SOON;
+        if filter.due_from.as_deref() == Some(from) && filter.due_to.as_deref() == Some(to) {
+            filter.due_from = None;
+            filter.due_to = None;
+        } else {
+            filter.due_from = Some(from.to_string());
+            filter.due_to = Some(to.to_string());
+        }
+        self.set_filter(filter);
+    }
+
+    pub fn clear_filters(&mut self) {
+        self.set_filter(FilterExpr::default());
+    }
+
+    /// Apply a filter, keeping the focused task selected if it still matches
+    fn set_filter(&mut self, filter: FilterExpr) {
+        let focus = self.view_focused_task();
+        self.filter = filter;
+        // Done is paged: open the page holding the focused task, if it's there
+        self.done_page = focus.and_then(|id| self.done_page_of(id)).unwrap_or(0);
+        if focus.is_some_and(|id| self.view_select_task(id)) {
+            return;
+        }
+        self.kanban_row = 0;
+        self.compact_select_first_task();
+    }
+
+    pub fn filtered_tasks(&self) -> Vec<&TaskItem> {
+        let mut tasks: Vec<&TaskItem> = self.tasks.iter().collect();
+
+        if !self.filter.is_empty() {
+            let today = models::today();
+            tasks.retain(|task| self.filter.matches(task, today));
+        }
+
+        tasks
+    }
+
+    /// Current filter rendered as query text (for headers and the builder)
+    pub fn filter_query(&self) -> String {
+        self.filter.to_query(|id| self.project_name(id))
+    }
+
+    /// Tasks shown in a Kanban column (respects the active filter), in the
+    /// column's sort order
+    pub fn tasks_in_column(&self, column: usize) -> Vec<&TaskItem> {
+        let Some(column) = self.config.kanban_columns.get(column) else {
+            return Vec::new();
+        };
+        let prune = !self.done_expanded && column.status == Some(Status::Done);
+        let mut tasks: Vec<&TaskItem> = self.filtered_tasks()
+            .into_iter()
+            .filter(|t| column.matches(t))
+            .filter(|t| !(prune && self.is_older_done(t)))
+            .collect();
+        self.column_sort(&column.name).sort(&mut tasks, self.kanban_order.get(&column.name));
+        tasks
+    }
+
+    /// Completed before the `done_window_days` window, so folded out of the Done column
+    fn is_older_done(&self, task: &TaskItem) -> bool {
+        if self.config.done_window_days == 0 {
+            return false;
+        }
+        let cutoff = models::today() - chrono::Duration::days(self.config.done_window_days as i64);
+        task.frontmatter.completed_at
+            .or(task.frontmatter.updated_at)
+            .is_some_and(|at| at.with_timezone(&chrono::Local).date_naive() < cutoff)
+    }
+
+    /// Tasks a Done column folds away when collapsed; while there are any the
+    /// column ends with a row that expands or collapses them
+    pub fn kanban_older_count(&self, column: usize) -> usize {
+        let Some(column) = self.config.kanban_columns.get(column).filter(|c| c.status == Some(Status::Done)) else {
+            return 0;
+        };
+        self.filtered_tasks()
+            .into_iter()
+            .filter(|t| column.matches(t) && self.is_older_done(t))
+            .count()
+    }
+
+    /// Cards in a column plus its "+N older" row, if any
+    fn kanban_row_count(&self, column: usize) -> usize {
+        self.tasks_in_column(column).len() + usize::from(self.kanban_older_count(column) > 0)
+    }
+
+    /// On the "+N older" row, show or hide the older done tasks
+    pub fn kanban_enter(&mut self) {
+        let tasks = self.kanban_column_tasks().len();
+        if self.kanban_row == tasks && self.kanban_older_count(self.kanban_column) > 0 {
+            self.done_expanded = !self.done_expanded;
+            self.kanban_row = if self.done_expanded { tasks } else { self.kanban_column_tasks().len() };
+        }
+    }
+
+    /// How a Kanban column is sorted, by column name
+    pub fn column_sort(&self, column: &str) -> KanbanSort {
+        self.kanban_sort.get(column).copied().unwrap_or_default()
+    }
+
+    /// Cycle the focused column's sort (manual → priority → due → age), keeping
+    /// the selected card focused
+    pub fn kanban_cycle_sort(&mut self) {
+        let Some(name) = self.config.kanban_columns.get(self.kanban_column).map(|c| c.name.clone()) else {
+            return;
+        };
+        let selected = self.kanban_selected_task().map(|t| t.frontmatter.id);
+        let sort = self.column_sort(&name).next();
+        if sort == KanbanSort::Manual {
+            self.kanban_sort.remove(&name);
+        } else {
+            self.kanban_sort.insert(name.clone(), sort);
+        }
+        if let Some(id) = selected {
+            self.kanban_select_task(id);
+        }
+        self.notify(format!("Sort {}: {}", name, sort.as_str()));
+    }
+
+    /// Move the selected card up or down its column. A sorted column switches
+    /// to manual order, starting from the order it was shown in.
+    pub fn kanban_reorder(&mut self, down: bool) {
+        let Some(name) = self.config.kanban_columns.get(self.kanban_column).map(|c| c.name.clone()) else {
+            return;
+        };
+        let mut order: Vec<Uuid> = self.kanban_column_tasks().iter().map(|t| t.frontmatter.id).collect();
+        let row = self.kanban_row;
+        let target = if down { row + 1 } else { row.wrapping_sub(1) };
+        if row >= order.len() || target >= order.len() {
+            return;
+        }
+        order.swap(row, target);
+        self.kanban_row = target;
+        self.kanban_sort.remove(&name);
+        self.kanban_order.insert(name, order);
+    }
+
+    // === Kanban Navigation Methods ===
+
+    pub fn kanban_column_tasks(&self) -> Vec<&TaskItem> {
+        self.tasks_in_column(self.kanban_column)
+    }
+
+    fn kanban_clamp_row(&mut self) {
+        let task_count = self.kanban_row_count(self.kanban_column);
+        if self.kanban_row >= task_count {
+            self.kanban_row = task_count.saturating_sub(1);
+        }
+    }
+
+    /// Focus the first column showing the given task
+    fn kanban_select_task(&mut self, task_id: Uuid) -> bool {
+        for column in 0..self.config.kanban_columns.len() {
+            if let Some(row) = self.tasks_in_column(column).iter().position(|t| t.frontmatter.id == task_id) {
+                self.kanban_column = column;
+                self.kanban_row = row;
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn kanban_move_left(&mut self) {
+        let columns = self.config.kanban_columns.len();
+        if columns == 0 {
+            return;
+        }
+        if self.kanban_column == 0 {
+            self.kanban_column = columns - 1;
+        } else {
+            self.kanban_column -= 1;
+        }
+        self.kanban_clamp_row();
+    }
+
+    pub fn kanban_move_right(&mut self) {
+        let columns = self.config.kanban_columns.len();
+        if columns == 0 {
+            return;
+        }
+        self.kanban_column = (self.kanban_column + 1) % columns;
+        self.kanban_clamp_row();
+    }
+
+    pub fn kanban_move_up(&mut self) {
+        let task_count = self.kanban_row_count(self.kanban_column);
+        if task_count > 0 {
+            if self.kanban_row == 0 {
+                self.kanban_row = task_count - 1;
+            } else {
+                self.kanban_row -= 1;
+            }
+        }
+    }
+
+    pub fn kanban_move_down(&mut self) {
+        let task_count = self.kanban_row_count(self.kanban_column);
+        if task_count > 0 {
+            self.kanban_row = (self.kanban_row + 1) % task_count;
+        }
+    }
+
+    /// Cycle the board layout override (auto → columns → scroll → grid → focus) and save it
+    pub fn cycle_kanban_layout(&mut self) -> Result<()> {
+        self.config.kanban_layout = self.config.kanban_layout.next();
+        self.save_config()?;
+        self.notify(format!("Board layout: {}", self.config.kanban_layout.as_str()));
+        Ok(())
+    }
+
+    pub fn kanban_selected_task(&self) -> Option<&TaskItem> {
+        self.kanban_column_tasks().get(self.kanban_row).copied()
+    }
+
+    pub fn kanban_mark_done(&mut self) -> Result<()> {
+        if let Some(task_id) = self.kanban_selected_task().map(|t| t.frontmatter.id) {
+            self.move_task_to_status(task_id, Status::Done)?;
+        }
+        Ok(())
+    }
+
+    /// Move the selected card one column left (`forward == false`) or right.
+    /// The card takes on the target column's status and tag, dropping the source column's tag.
+    pub fn kanban_move_task(&mut self, forward: bool) -> Result<()> {
+        let Some(task_id) = self.kanban_selected_task().map(|t| t.frontmatter.id) else {
+            return Ok(());
+        };
+        let last = self.config.kanban_columns.len().saturating_sub(1);
+        let target = if forward {
+            (self.kanban_column + 1).min(last)
+        } else {
+            self.kanban_column.saturating_sub(1)
+        };
+        if target == self.kanban_column {
+            return Ok(());
+        }
+
+        let source = &self.config.kanban_columns[self.kanban_column];
+        let dest = &self.config.kanban_columns[target];
+        let task_move = TaskMove {
+            task_id,
+            status: dest.status.clone(),
+            add_tag: dest.tag.clone(),
+            remove_tag: source.tag.clone().filter(|t| dest.tag.as_ref() != Some(t)),
+        };
+        self.request_move(task_move)
+    }
+
+    // === WIP Limit Methods ===
+
+    /// (current count, limit) for a status with a configured WIP limit
+    pub fn wip_usage(&self, status: &Status) -> Option<(usize, u32)> {
+        let limit = *self.config.wip_limits.get(status)?;
+        let count = self.tasks.iter().filter(|t| &t.frontmatter.status == status).count();
+        Some((count, limit))
+    }
+
+    fn wip_would_exceed(&self, status: &Status) -> bool {
+        self.wip_usage(status)
+            .is_some_and(|(count, limit)| count + 1 > limit as usize)
+    }
+
+    /// Change a task's status, asking for confirmation first if it would break a WIP limit
+    pub fn move_task_to_status(&mut self, task_id: Uuid, status: Status) -> Result<()> {
+        self.request_move(TaskMove::to_status(task_id, status))
+    }
+
+    fn request_move(&mut self, task_move: TaskMove) -> Result<()> {
+        let Some(current) = self.tasks.iter()
+            .find(|t| t.frontmatter.id == task_move.task_id)
+            .map(|t| t.frontmatter.status.clone())
+        else {
+            return Ok(());
+        };
+
+        if let Some(status) = &task_move.status {
+            if status != &current && self.wip_would_exceed(status) {
+                self.wip_pending = Some(task_move);
+                return Ok(());
+            }
+        }
+        self.apply_move(task_move)
+    }
+
+    pub fn confirm_wip_move(&mut self) -> Result<()> {
+        if let Some(task_move) = self.wip_pending.take() {
+            self.apply_move(task_move)?;
+        }
+        Ok(())
+    }
+
+    pub fn cancel_wip_move(&mut self) {
+        self.wip_pending = None;
+    }
+
+    fn apply_move(&mut self, task_move: TaskMove) -> Result<()> {
+        let anchor = self.selection_anchor();
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task_move.task_id) {
+            if let Some(status) = task_move.status {
+                task.set_status(status);
+            }
+            if let Some(tag) = &task_move.remove_tag {
+                task.frontmatter.tags.retain(|t| t != tag);
+            }
+            if let Some(tag) = task_move.add_tag {
+                if !task.has_tag(&tag) {
+                    task.frontmatter.tags.push(tag);
+                }
+            }
+            self.storage.write_task(task)?;
+            let message = format!("Moved to {}", task.frontmatter.status.as_str());
+            let done = task.frontmatter.status == Status::Done;
+            self.notify(message);
+            if done {
+                self.complete_jira_issue(task_move.task_id);
+            }
+        }
+
+        // Stay on the task (Kanban follows the card to its new column)
+        self.restore_selection(anchor);
+        Ok(())
+    }
+
+    pub fn kanban_archive_task(&mut self) -> Result<()> {
+        if let Some(task) = self.kanban_selected_task() {
+            let task_id = task.frontmatter.id;
+            if task.is_project() {
+                return self.archive_project(task_id);
+            }
+            let anchor = self.selection_anchor();
+            if let Some(task) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task_id) {
+                task.set_status(Status::Archived);
+                self.storage.write_task(task)?;
+            }
+            self.notify("Archived");
+            self.restore_selection(anchor);
+        }
+        Ok(())
+    }
+
+    // === Settings View Methods ===
+
+    fn settings_max_items(&self) -> usize {
+        match self.settings_section {
+            SettingsSection::Workstreams => self.config.workstreams.len() + 1, // +1 for "Add new"
+            SettingsSection::Goals => self.config.goals.len() + 1,
+            SettingsSection::ApiKeys => 1, // Just OpenAI API key for now
+            SettingsSection::Sync => SYNC_SETTINGS.len(),
+            SettingsSection::Backups => self.backups.len() + 1, // +1 for "Back up now"
+        }
+    }
+
+    pub fn settings_next(&mut self) {
+        let max_items = self.settings_max_items();
+        if max_items > 0 {
+            self.settings_selected = (self.settings_selected + 1) % max_items;
+        }
+    }
+
+    pub fn settings_prev(&mut self) {
+        let max_items = self.settings_max_items();
+        if max_items > 0 {
+            if self.settings_selected == 0 {
+                self.settings_selected = max_items - 1;
+            } else {
+                self.settings_selected -= 1;
+            }
+        }
+    }
+
+    pub fn settings_start_edit(&mut self) {
+        match self.settings_section {
+            SettingsSection::Workstreams => {
+                if self.settings_selected < self.config.workstreams.len() {
+                    self.settings_editing = true;
+                    self.settings_edit_text.set(self.config.workstreams[self.settings_selected].name.clone());
+                } else {
+                    self.settings_editing = true;
+                    self.settings_edit_text.clear();
+                }
+            }
+            SettingsSection::Goals => {
+                if self.settings_selected < self.config.goals.len() {
+                    self.settings_editing = true;
+                    self.settings_edit_text.set(self.config.goals[self.settings_selected].description.clone());
+                    self.settings_edit_area = self.config.goals[self.settings_selected].area.clone();
+                } else {
+                    self.settings_editing = true;
+                    self.settings_edit_text.clear();
+                    self.settings_edit_area = self.default_goal_area();
+                }
+            }
+            SettingsSection::ApiKeys => {
+                self.settings_editing = true;
+                self.settings_edit_text.set(self.config.openai_api_key.clone().unwrap_or_default());
+            }
+            SettingsSection::Sync => match SYNC_SETTINGS[self.settings_selected] {
+                SyncSetting::Remote => {
+                    self.settings_editing = true;
+                    self.settings_edit_text.set(self.remote_url.clone().unwrap_or_default());
+                }
+                SyncSetting::Credentials => {
+                    self.config.git.credentials = self.config.git.credentials.next();
+                    self.apply_git_config();
+                }
+                SyncSetting::Token => {
+                    self.settings_editing = true;
+                    self.settings_edit_text.set(self.config.git.token.clone().unwrap_or_default());
+                }
+                SyncSetting::Test => self.test_remote(),
+            },
+            SettingsSection::Backups => {
+                let result = match self.settings_selected {
+                    0 => self.create_backup(),
+                    n => self.restore_backup(n - 1),
+                };
+                if let Err(e) = result {
+                    self.notify_error(format!("{:#}", e));
+                }
+            }
+        }
+    }
+
+    pub fn settings_cancel_edit(&mut self) {
+        self.settings_editing = false;
+        self.settings_edit_text.clear();
+    }
+
+    pub fn settings_confirm_edit(&mut self) -> Result<()> {
+        let text = self.settings_edit_text.trim().to_string();
+
+        match self.settings_section {
+            SettingsSection::Workstreams => {
+                if text.is_empty() {
+                    self.settings_cancel_edit();
+                    return Ok(());
+                }
+                if self.settings_selected < self.config.workstreams.len() {
+                    let old_name = self.config.workstreams[self.settings_selected].name.clone();
+                    self.config.rename_workstream(&old_name, text.clone());
+                    self.offer_retag(&old_name, &text);
+                } else {
+                    self.config.add_workstream(text);
+                }
+            }
+            SettingsSection::Goals => {
+                if text.is_empty() {
+                    self.settings_cancel_edit();
+                    return Ok(());
+                }
+                if self.settings_selected < self.config.goals.len() {
+                    self.config.update_goal(self.settings_selected, text);
+                    self.config.update_goal_area(self.settings_selected, self.settings_edit_area.clone());
+                } else {
+                    self.config.add_goal(text, self.settings_edit_area.clone());
+                }
+            }
+            SettingsSection::ApiKeys => {
+                // Allow empty to clear the API key
+                if text.is_empty() {
+                    self.config.openai_api_key = None;
+                } else {
+                    self.config.openai_api_key = Some(text);
+                }
+                // Reinitialize the enricher with the new API key
+                self.enricher = TaskEnricher::from_config(&self.config, &self.data_dir);
+            }
+            SettingsSection::Sync => match SYNC_SETTINGS[self.settings_selected] {
+                SyncSetting::Remote => {
+                    self.settings_editing = false;
+                    self.settings_edit_text.clear();
+                    return self.set_remote_url(&text);
+                }
+                SyncSetting::Token => {
+                    self.config.git.token = (!text.is_empty()).then_some(text);
+                    self.storage.set_git_config(&self.config.git);
+                }
+                SyncSetting::Credentials | SyncSetting::Test => {}
+            },
+            SettingsSection::Backups => {}
+        }
+
+        self.save_config()?;
+        self.settings_editing = false;
+        self.settings_edit_text.clear();
+        Ok(())
+    }
+
+    /// Ask to carry a workstream rename over to the tasks tagged with it
+    fn offer_retag(&mut self, old_name: &str, new_name: &str) {
+        let (Ok(old), Ok(new)) = (tags::parse_tag(old_name), tags::parse_tag(new_name)) else {
+            return;
+        };
+        if old != new && self.tasks.iter().any(|t| t.has_tag(&old)) {
+            self.retag_pending = Some((old, new));
+        }
+    }
+
+    /// Rewrite the pending tag rename across all tasks, as one change
+    pub fn confirm_retag(&mut self) -> Result<()> {
+        let Some((old, new)) = self.retag_pending.take() else {
+            return Ok(());
+        };
+        let mut changed = tags::rename(&self.tasks, &old, &new);
+        self.storage.snapshot("tag-rename")?;
+        self.storage.write_tasks(&mut changed, &format!("Rename tag #{} to #{}", old, new))?;
+        if self.config.rename_tag(&old, &new) {
+            self.save_config()?;
+        }
+        let count = changed.len();
+        self.adopt_written(changed);
+        if self.filter.is_single_tag(&old) {
+            self.filter_by_tag(&new);
+        }
+        self.notify(format!("Retagged {} tasks #{}", count, new));
+        Ok(())
+    }
+
+    pub fn cancel_retag(&mut self) {
+        self.retag_pending = None;
+    }
+
+    pub fn settings_delete(&mut self) -> Result<()> {
+        match self.settings_section {
+            SettingsSection::Workstreams => {
+                if self.settings_selected < self.config.workstreams.len() {
+                    let name = self.config.workstreams[self.settings_selected].name.clone();
+                    self.config.delete_workstream(&name);
+                    self.save_config()?;
+                    if self.settings_selected >= self.config.workstreams.len() && self.settings_selected > 0 {
+                        self.settings_selected -= 1;
+                    }
+                }
+            }
+            SettingsSection::Goals => {
+                if self.settings_selected < self.config.goals.len() {
+                    self.config.delete_goal(self.settings_selected);
+                    self.save_config()?;
+                    if self.settings_selected >= self.config.goals.len() && self.settings_selected > 0 {
+                        self.settings_selected -= 1;
+                    }
+                }
+            }
+            SettingsSection::ApiKeys => {
+                // Delete clears the API key
+                self.config.openai_api_key = None;
+                self.enricher = TaskEnricher::from_config(&self.config, &self.data_dir);
+                self.save_config()?;
+            }
+            SettingsSection::Sync => match SYNC_SETTINGS[self.settings_selected] {
+                SyncSetting::Remote => self.set_remote_url("")?,
+                SyncSetting::Token => {
+                    self.config.git.token = None;
+                    self.apply_git_config();
+                }
+                SyncSetting::Credentials | SyncSetting::Test => {}
+            },
+            SettingsSection::Backups => {}
+        }
+        Ok(())
+    }
+
+    /// Reread the backups in `.backups`
+    pub fn refresh_backups(&mut self) {
+        match backup::list(&backup::default_dir(&self.data_dir)) {
+            Ok(backups) => self.backups = backups,
+            Err(e) => self.notify_error(format!("Failed to list backups: {:#}", e)),
+        }
+        self.settings_selected = self.settings_selected.min(self.backups.len());
+    }
+
+    fn create_backup(&mut self) -> Result<()> {
+        let path = backup::create(&self.data_dir, &backup::default_dir(&self.data_dir), None)?;
+        self.refresh_backups();
+        self.notify(format!("Backed up to {}", path.file_name().unwrap_or_default().to_string_lossy()));
+        Ok(())
+    }
+
+    /// Put the data directory back as it was in the `index`th backup listed,
+    /// after snapshotting the current state, and reload the config and tasks
+    fn restore_backup(&mut self, index: usize) -> Result<()> {
+        let Some(chosen) = self.backups.get(index).cloned() else {
+            return Ok(());
+        };
+        if chosen.encrypted {
+            anyhow::bail!("Encrypted backups need a passphrase: use `tasktui backup restore {}`", chosen.path.display());
+        }
+        let safety = self.storage.restore_backup(&chosen.path, None)?;
+
+        let config = AppConfig::load(&self.data_dir)?;
+        self.storage.activity_log = config.activity_log;
+        self.storage.hooks = config.hooks.clone();
+        self.storage.backups = config.backups;
+        self.storage.set_git_config(&config.git);
+        self.enricher = TaskEnricher::from_config(&config, &self.data_dir);
+        self.config = config;
+        self.refresh_tasks()?;
+        self.refresh_backups();
+        self.notify(format!(
+            "Restored {}; the previous state is in {}",
+            chosen.file_name(),
+            safety.file_name().unwrap_or_default().to_string_lossy()
+        ));
+        Ok(())
+    }
+
+    /// Point the vault at a remote, making it a git repository first if it
+    /// isn't one; an empty `url` removes the remote
+    fn set_remote_url(&mut self, url: &str) -> Result<()> {
+        if self.storage.git_sync.is_none() {
+            if url.is_empty() {
+                return Ok(());
+            }
+            let git_sync = GitSync::new(self.data_dir.clone());
+            git_sync.init_if_needed()?;
+            if let Err(err) = git_sync.exclude(UiState::FILE_NAME) {
+                tracing::warn!(error = ?err, "Failed to keep UI state out of git");
+            }
+            self.storage.git_sync = Some(git_sync);
+            self.storage.set_git_config(&self.config.git);
+        }
+        if let Some(git_sync) = &self.storage.git_sync {
+            git_sync.set_remote_url(url)?;
+            self.remote_url = git_sync.remote_url();
+        }
+        self.sync_test_result = None;
+        if url.is_empty() {
+            self.notify("Removed the remote");
+        } else {
+            self.notify("Remote set; testing the connection");
+            self.test_remote();
+        }
+        Ok(())
+    }
+
+    /// Use the edited `git` settings for the next sync and save them
+    fn apply_git_config(&mut self) {
+        self.storage.set_git_config(&self.config.git);
+        self.sync_test_result = None;
+        if let Err(e) = self.save_config() {
+            self.notify_error(format!("Failed to save settings: {:#}", e));
+        }
+    }
+
+    /// Check the remote can be reached and signed in to, in the background
+    pub fn test_remote(&mut self) {
+        match &self.storage.git_sync {
+            Some(git_sync) => {
+                self.sync_test = Some(git_sync.test_remote_in_background());
+                self.sync_test_result = None;
+            }
+            None => self.notify_error("Set a remote first"),
+        }
+    }
+
+    /// Whether a connection test is still running
+    pub fn is_testing_remote(&self) -> bool {
+        self.sync_test.is_some()
+    }
+
+    /// Cycle goal priority (only in Goals section)
+    pub fn settings_cycle_priority(&mut self) -> Result<()> {
+        if self.settings_section == SettingsSection::Goals && self.settings_selected < self.config.goals.len() {
+            self.config.cycle_goal_priority(self.settings_selected);
+            self.save_config()?;
+        }
+        Ok(())
+    }
+
+    /// Toggle goal active state (only in Goals section)
+    pub fn settings_toggle_active(&mut self) -> Result<()> {
+        if self.settings_section == SettingsSection::Goals && self.settings_selected < self.config.goals.len() {
+            self.config.toggle_goal_active(self.settings_selected);
+            self.save_config()?;
+        }
+        Ok(())
+    }
+
+    /// Cycle through areas when editing a goal
+    pub fn settings_cycle_area(&mut self) {
+        if self.settings_editing && self.settings_section == SettingsSection::Goals {
+            // Cycle through workstream names as areas
+            let areas: Vec<_> = self.config.workstreams.iter().map(|w| w.name.clone()).collect();
+            if areas.is_empty() {
+                return;
+            }
+            let current_idx = areas.iter().position(|a| a == &self.settings_edit_area).unwrap_or(0);
+            let next_idx = (current_idx + 1) % areas.len();
+            self.settings_edit_area = areas[next_idx].clone();
+        }
+    }
+
+    pub fn save_config(&self) -> Result<()> {
+        self.config.save(&self.data_dir)
+    }
+
+    // === Projects View Methods ===
+
+    pub fn open_projects(&mut self) {
+        self.view_mode = ViewMode::Projects;
+        self.projects_selected = 0;
+    }
+
+    pub fn close_projects(&mut self) {
+        self.view_mode = ViewMode::Compact;
+    }
+
+    pub fn get_projects(&self) -> Vec<&TaskItem> {
+        self.tasks.iter()
+            .filter(|t| t.is_project())
+            .collect()
+    }
+
+    pub fn projects_next(&mut self) {
+        let count = self.get_projects().len();
+        if count > 0 {
+            self.projects_selected = (self.projects_selected + 1) % count;
+        }
+    }
+
+    pub fn projects_prev(&mut self) {
+        let count = self.get_projects().len();
+        if count > 0 {
+            if self.projects_selected == 0 {
+                self.projects_selected = count - 1;
+            } else {
+                self.projects_selected -= 1;
+            }
+        }
+    }
+
+    pub fn projects_archive(&mut self) -> Result<()> {
+        let selected = self.get_projects().get(self.projects_selected).map(|p| p.frontmatter.id);
+        match selected {
+            Some(project_id) => self.archive_project(project_id),
+            None => Ok(()),
+        }
+    }
+
+    /// Archive a project, first asking what to do with its open tasks if it has any
+    pub fn archive_project(&mut self, project_id: Uuid) -> Result<()> {
+        if models::open_children(&self.tasks, project_id).is_empty() {
+            return self.finish_project_archive(project_id, ChildAction::Detach);
+        }
+        self.project_archive = Some(ProjectArchive { project_id, moving: None });
+        Ok(())
+    }
+
+    /// Open projects the pending archive's tasks can move to
+    pub fn archive_targets(&self) -> Vec<&TaskItem> {
+        let pending = self.project_archive.as_ref().map(|p| p.project_id);
+        self.get_projects()
+            .into_iter()
+            .filter(|p| Some(p.frontmatter.id) != pending)
+            .filter(|p| !p.frontmatter.status.is_closed())
+            .collect()
+    }
+
+    pub fn project_archive_start_move(&mut self) {
+        if self.archive_targets().is_empty() {
+            self.notify_error("No other open project to move them to");
+        } else if let Some(pending) = &mut self.project_archive {
+            pending.moving = Some(0);
+        }
+    }
+
+    pub fn project_archive_select(&mut self, down: bool) {
+        let count = self.archive_targets().len();
+        if let Some(ProjectArchive { moving: Some(selected), .. }) = &mut self.project_archive {
+            *selected = if down { (*selected + 1) % count } else { (*selected + count - 1) % count };
+        }
+    }
+
+    /// Archive the pending project, doing `action` to its open tasks
+    pub fn confirm_project_archive(&mut self, action: ChildAction) -> Result<()> {
+        match self.project_archive.take() {
+            Some(pending) => self.finish_project_archive(pending.project_id, action),
+            None => Ok(()),
+        }
+    }
+
+    /// Archive into the project picked from `archive_targets()`
+    pub fn confirm_project_archive_move(&mut self) -> Result<()> {
+        let target = self.project_archive.as_ref()
+            .and_then(|p| p.moving)
+            .and_then(|idx| self.archive_targets().get(idx).map(|t| t.frontmatter.id));
+        match target {
+            Some(target) => self.confirm_project_archive(ChildAction::MoveTo(target)),
+            None => Ok(()),
+        }
+    }
+
+    /// Step back from the project picker, or drop the archive altogether
+    pub fn cancel_project_archive(&mut self) {
+        match &mut self.project_archive {
+            Some(pending) if pending.moving.is_some() => pending.moving = None,
+            _ => self.project_archive = None,
+        }
+    }
+
+    fn finish_project_archive(&mut self, project_id: Uuid, action: ChildAction) -> Result<()> {
+        let Some(mut project) = self.tasks.iter().find(|t| t.frontmatter.id == project_id).cloned() else {
+            return Ok(());
+        };
+        project.set_status(Status::Archived);
+        let mut batch: Vec<TaskItem> = models::open_children(&self.tasks, project_id)
+            .into_iter()
+            .cloned()
+            .map(|mut task| {
+                match action {
+                    ChildAction::Archive => task.set_status(Status::Archived),
+                    ChildAction::Detach => task.frontmatter.parent_goal_id = None,
+                    ChildAction::MoveTo(target) => task.frontmatter.parent_goal_id = Some(target),
+                }
+                task
+            })
+            .collect();
+        let count = batch.len();
+        let title = project.frontmatter.title.clone();
+        batch.push(project);
+
+        if count > 0 {
+            self.storage.snapshot("archive-project")?;
+        }
+        let anchor = self.selection_anchor();
+        self.storage.write_tasks(&mut batch, &format!("Archive project {}", title))?;
+        self.adopt_written(batch);
+        self.restore_selection(anchor);
+        self.clamp_selections();
+        self.notify(match (action, count) {
+            (_, 0) => format!("Archived {}", title),
+            (ChildAction::Archive, _) => format!("Archived {} with {} tasks", title, count),
+            (ChildAction::Detach, _) => format!("Archived {}; {} tasks detached", title, count),
+            (ChildAction::MoveTo(_), _) => format!("Archived {}; {} tasks moved", title, count),
+        });
+        Ok(())
+    }
+
+    /// Swap freshly written copies into `self.tasks`
+    fn adopt_written(&mut self, written: Vec<TaskItem>) {
+        for task in written {
+            if let Some(slot) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task.frontmatter.id) {
+                *slot = task;
+            }
+        }
+        self.rebuild_links();
+    }
+
+    // === Remote status ===
+
+    /// Take the result of a background fetch, and start the next one every
+    /// `git.fetch_minutes`. After our own pulls and pushes the counts are
+    /// redone without a fetch.
+    pub fn poll_remote(&mut self) {
+        if let Some(receiver) = &self.sync_test {
+            match receiver.try_recv() {
+                Ok(result) => {
+                    self.sync_test_result = Some(result.map_err(|e| e.to_string().trim().to_string()));
+                    self.sync_test = None;
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => self.sync_test = None,
+            }
+        }
+        let Some(git_sync) = &self.storage.git_sync else {
+            return;
+        };
+        if let Some(receiver) = &self.remote_check {
+            match receiver.try_recv() {
+                Ok(Ok(status)) => self.remote_status = status,
+                Ok(Err(e)) => tracing::warn!(error = %e, "Background fetch failed"),
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {}
+            }
+            self.remote_check = None;
+        }
+
+        let interval = Duration::from_secs(u64::from(self.config.git.fetch_minutes) * 60);
+        let fetch = !interval.is_zero() && self.remote_fetched_at.is_none_or(|at| at.elapsed() >= interval);
+        let synced = self.storage.sync_count() != self.remote_syncs_seen;
+        if fetch || synced {
+            if fetch {
+                self.remote_fetched_at = Some(Instant::now());
+            }
+            self.remote_syncs_seen = self.storage.sync_count();
+            self.remote_check = Some(git_sync.check_remote_in_background(fetch));
+        }
+    }
+
+    // === Pull conflicts ===
+
+    /// After a git pull, compare the loaded copies of the tasks it changed
+    /// with the files now on disk
+    pub fn check_pull_conflicts(&mut self) {
+        let pulled = self.storage.take_pulled();
+        if !pulled.is_empty() {
+            self.review_pulled(pulled);
+        }
+    }
+
+    /// Take in tasks the pull added, and list those whose loaded copy differs
+    /// from the pulled file for a reload-or-keep choice
+    pub fn review_pulled(&mut self, paths: Vec<PathBuf>) {
+        let mut diverged = Vec::new();
+        for path in paths {
+            let Ok(pulled) = self.storage.parse_file(&path) else {
+                continue;
+            };
+            let Some(loaded) = self.tasks.iter().find(|t| t.frontmatter.id == pulled.frontmatter.id) else {
+                self.tasks.push(pulled);
+                continue;
+            };
+            let same = match (self.storage.serialize_task(loaded), self.storage.serialize_task(&pulled)) {
+                (Ok(loaded), Ok(pulled)) => loaded == pulled,
+                _ => false,
+            };
+            if !same {
+                diverged.push(pulled.frontmatter.id);
+            }
+        }
+        self.rebuild_links();
+        if diverged.is_empty() {
+            return;
+        }
+        let conflicts = self.pull_conflicts.get_or_insert(PullConflicts { task_ids: Vec::new(), selected: 0 });
+        for id in diverged {
+            if !conflicts.task_ids.contains(&id) {
+                conflicts.task_ids.push(id);
+            }
+        }
+    }
+
+    pub fn pull_conflicts_select(&mut self, down: bool) {
+        if let Some(conflicts) = &mut self.pull_conflicts {
+            let last = conflicts.task_ids.len().saturating_sub(1);
+            conflicts.selected = if down { (conflicts.selected + 1).min(last) } else { conflicts.selected.saturating_sub(1) };
+        }
+    }
+
+    /// Resolve the selected task: take the pulled file, or write the loaded
+    /// copy over it (`keep_mine`)
+    pub fn resolve_pull_conflict(&mut self, keep_mine: bool) -> Result<()> {
+        let Some(id) = self.pull_conflicts.as_ref().and_then(|c| c.task_ids.get(c.selected).copied()) else {
+            return Ok(());
+        };
+        let loaded = self.tasks.iter().find(|t| t.frontmatter.id == id).cloned();
+        let pulled = match &loaded {
+            Some(loaded) => Some(self.storage.parse_file(&loaded.file_path)?),
+            None => None,
+        };
+        if let Some(conflicts) = &mut self.pull_conflicts {
+            conflicts.task_ids.retain(|other| *other != id);
+            conflicts.selected = conflicts.selected.min(conflicts.task_ids.len().saturating_sub(1));
+            if conflicts.task_ids.is_empty() {
+                self.pull_conflicts = None;
+            }
+        }
+        let (Some(loaded), Some(pulled)) = (loaded, pulled) else {
+            return Ok(());
+        };
+        let anchor = self.selection_anchor();
+        if keep_mine {
+            // Our copy is older than the file now; take its stamp so the
+            // write isn't refused as a change made elsewhere
+            let mut task = loaded;
+            task.frontmatter.updated_at = task.frontmatter.updated_at.max(pulled.frontmatter.updated_at);
+            task.file_path = self.storage.write_task(&mut task)?;
+            self.notify(format!("Kept your copy of '{}'", task.frontmatter.title));
+            self.adopt_written(vec![task]);
+        } else {
+            self.notify(format!("Reloaded '{}'", pulled.frontmatter.title));
+            self.adopt_written(vec![pulled]);
+        }
+        self.restore_selection(anchor);
+        Ok(())
+    }
+
+    /// Esc on the banner: take the pulled file for every task left
+    pub fn reload_pull_conflicts(&mut self) {
+        let Some(conflicts) = self.pull_conflicts.take() else {
+            return;
+        };
+        let anchor = self.selection_anchor();
+        let pulled: Vec<TaskItem> = conflicts.task_ids.iter()
+            .filter_map(|id| self.tasks.iter().find(|t| t.frontmatter.id == *id))
+            .filter_map(|t| self.storage.parse_file(&t.file_path).ok())
+            .collect();
+        let count = pulled.len();
+        self.adopt_written(pulled);
+        self.restore_selection(anchor);
+        self.notify(format!("Reloaded {} tasks changed on another device", count));
+    }
+
+    // === Priority review ===
+
+    /// Send the open backlog and active goals to the LLM and review the
+    /// priority changes it proposes
+    pub fn open_priority_review(&mut self) {
+        if !self.enricher.is_available() {
+            self.notify_error("Suggesting priorities needs an LLM; add an OpenAI key in Settings");
+            return;
+        }
+        let prompt = prioritize::backlog_prompt(&self.tasks, &self.config);
+        let changes = self.enricher
+            .complete_sync(PRIORITIZE_PROMPT, &prompt, PRIORITIZE_MAX_TOKENS)
+            .and_then(|response| prioritize::parse_changes(&self.tasks, &response));
+        match changes {
+            Ok(changes) => self.start_priority_review(changes),
+            Err(e) => self.notify_error(format!("No priority suggestions: {}", e)),
+        }
+    }
+
+    /// Show `changes` for review, none accepted yet
+    pub fn start_priority_review(&mut self, changes: Vec<PriorityChange>) {
+        if changes.is_empty() {
+            self.notify("The LLM suggests no priority changes");
+            return;
+        }
+        let accepted = vec![false; changes.len()];
+        self.priority_review = Some(PriorityReview { changes, accepted, selected: 0 });
+    }
+
+    pub fn priority_review_select(&mut self, down: bool) {
+        if let Some(review) = &mut self.priority_review {
+            let last = review.changes.len().saturating_sub(1);
+            review.selected = if down { (review.selected + 1).min(last) } else { review.selected.saturating_sub(1) };
+        }
+    }
+
+    pub fn priority_review_toggle(&mut self) {
+        if let Some(review) = &mut self.priority_review {
+            review.accepted[review.selected] = !review.accepted[review.selected];
+        }
+    }
+
+    /// Accept every suggestion, or clear them all when all are accepted already
+    pub fn priority_review_toggle_all(&mut self) {
+        if let Some(review) = &mut self.priority_review {
+            let all = review.accepted.iter().all(|a| *a);
+            review.accepted.iter_mut().for_each(|a| *a = !all);
+        }
+    }
+
+    pub fn cancel_priority_review(&mut self) {
+        self.priority_review = None;
+    }
+
+    /// Write the accepted changes as one commit
+    pub fn apply_priority_review(&mut self) -> Result<()> {
+        let Some(review) = self.priority_review.take() else {
+            return Ok(());
+        };
+        let mut batch: Vec<TaskItem> = review.changes.iter()
+            .zip(&review.accepted)
+            .filter(|(_, accepted)| **accepted)
+            .filter_map(|(change, _)| {
+                let mut task = self.tasks.iter().find(|t| t.frontmatter.id == change.id)?.clone();
+                task.frontmatter.priority = change.to.clone();
+                Some(task)
+            })
+            .collect();
+        if batch.is_empty() {
+            self.notify("No priorities changed");
+            return Ok(());
+        }
+
+        let count = batch.len();
+        let anchor = self.selection_anchor();
+        self.storage.write_tasks(&mut batch, &format!("Reprioritize {} tasks", count))?;
+        self.adopt_written(batch);
+        self.restore_selection(anchor);
+        self.notify(format!("Changed the priority of {} tasks", count));
+        Ok(())
+    }
+
+    pub fn show_new_project_dialog(&mut self) {
+        self.show_new_project = true;
+        self.new_project_title.clear();
+    }
+
+    pub fn cancel_new_project_dialog(&mut self) {
+        self.show_new_project = false;
+        self.new_project_title.clear();
+    }
+
+    pub fn create_new_project(&mut self) -> Result<()> {
+        if self.new_project_title.trim().is_empty() {
+            self.show_new_project = false;
+            return Ok(());
+        }
+
+        let mut project = TaskItem::new_project(self.new_project_title.trim().to_string());
+        project.file_path = self.storage.write_task(&mut project)?;
+        self.notify(format!("Project created: {}", project.frontmatter.title));
+        self.tasks.push(project);
+        self.show_new_project = false;
+        self.new_project_title.clear();
+
+        // Select the new project
+        self.projects_selected = self.get_projects().len().saturating_sub(1);
+        Ok(())
+    }
+
+    pub fn open_project_gantt(&mut self) {
+        let projects = self.get_projects();
+        if let Some(project) = projects.get(self.projects_selected) {
+            self.current_project_id = Some(project.frontmatter.id);
+            self.view_mode = ViewMode::ProjectGantt;
+            self.gantt_selected = 0;
+            self.gantt_scroll_offset = 0;
+        }
+    }
+
+    /// Export the selected project (or the one open in the Gantt view) to
+    /// `exports/` as markdown and HTML for sharing
+    pub fn export_project(&mut self) {
+        let project = match self.view_mode {
+            ViewMode::ProjectGantt => self.get_current_project(),
+            _ => self.get_projects().get(self.projects_selected).copied(),
+        };
+        let Some(project) = project else {
+            return;
+        };
+        match export::write_project(&self.data_dir, project, &self.tasks, models::today()) {
+            Ok(path) => {
+                let path = path.strip_prefix(&self.data_dir).unwrap_or(&path).display().to_string();
+                self.notify(format!("Exported to {} (and .md)", path));
+            }
+            Err(e) => self.notify_error(format!("Export failed: {}", e)),
+        }
+    }
+
+    pub fn close_project_gantt(&mut self) {
+        self.view_mode = ViewMode::Projects;
+        self.current_project_id = None;
+    }
+
+    pub fn get_current_project(&self) -> Option<&TaskItem> {
+        let project_id = self.current_project_id?;
+        self.tasks.iter().find(|t| t.frontmatter.id == project_id)
+    }
+
+    pub fn get_project_tasks(&self) -> Vec<&TaskItem> {
+        let Some(project_id) = self.current_project_id else {
+            return Vec::new();
+        };
+        self.tasks.iter()
+            .filter(|t| t.frontmatter.parent_goal_id == Some(project_id))
+            .collect()
+    }
+
+    pub fn gantt_next(&mut self) {
+        let count = self.get_project_tasks().len();
+        if count > 0 {
+            self.gantt_selected = (self.gantt_selected + 1) % count;
+        }
+    }
+
+    pub fn gantt_prev(&mut self) {
+        let count = self.get_project_tasks().len();
+        if count > 0 {
+            if self.gantt_selected == 0 {
+                self.gantt_selected = count - 1;
+            } else {
+                self.gantt_selected -= 1;
+            }
+        }
+    }
+
+    /// Cycle the chart pane: burndown, cumulative flow, hidden
+    pub fn cycle_project_chart(&mut self) {
+        self.project_chart = match self.project_chart {
+            None => Some(ProjectChart::Burndown),
+            Some(ProjectChart::Burndown) => Some(ProjectChart::CumulativeFlow),
+            Some(ProjectChart::CumulativeFlow) => None,
+        };
+    }
+
+    pub fn gantt_scroll_left(&mut self) {
+        self.gantt_scroll_offset = self.gantt_scroll_offset.saturating_sub(7); // Scroll by ~1 week
+    }
+
+    pub fn gantt_scroll_right(&mut self) {
+        self.gantt_scroll_offset += 7;
+    }
+
+    /// Move the selected task's start, end and due dates by `days`, counting
+    /// only working days when the calendar skips non-working ones
+    pub fn gantt_shift_dates(&mut self, days: i64) -> Result<()> {
+        let Some(task_id) = self.get_project_tasks().get(self.gantt_selected).map(|t| t.frontmatter.id) else {
+            return Ok(());
+        };
+        let calendar = self.config.calendar.clone();
+        let Some(task) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task_id) else {
+            return Ok(());
+        };
+        let fm = &mut task.frontmatter;
+        if fm.start_date.is_none() && fm.end_date.is_none() && fm.due_date.is_none() {
+            self.notify_error("No dates to shift; set a start, end or due date first");
+            return Ok(());
+        }
+        fm.start_date = fm.start_date.map(|d| calendar.shift(d, days));
+        fm.end_date = fm.end_date.map(|d| calendar.shift(d, days));
+        if let Some(due) = &mut fm.due_date {
+            due.date = calendar.shift(due.date, days);
+        }
+        self.storage.write_task(task)?;
+        let (start, end) = task.schedule(models::today());
+        let span = format!("{} – {}", self.config.display.format_date(start), self.config.display.format_date(end));
+        self.notify(span);
+        Ok(())
+    }
+
+    /// Calculate project progress based on completed tasks
+    pub fn calculate_project_progress(&self, project_id: Uuid) -> u8 {
+        models::project_progress(&self.tasks, project_id)
+    }
+
+    /// Count tasks by status for a project
+    pub fn project_task_counts(&self, project_id: Uuid) -> (usize, usize, usize) {
+        let tasks: Vec<_> = self.tasks.iter()
+            .filter(|t| t.frontmatter.parent_goal_id == Some(project_id))
+            .collect();
+
+        let total = tasks.len();
+        let done = tasks.iter()
+            .filter(|t| t.frontmatter.status.is_closed())
+            .count();
+        let active = tasks.iter()
+            .filter(|t| matches!(t.frontmatter.status, Status::Active | Status::Next))
+            .count();
+
+        (total, done, active)
+    }
+
+    // === Inbox Triage Methods ===
+
+    pub fn open_triage(&mut self) {
+        self.view_mode = ViewMode::Triage;
+        self.triage_index = 0;
+        self.triage_scheduling = false;
+        self.triage_input.clear();
+    }
+
+    pub fn close_triage(&mut self) {
+        self.view_mode = ViewMode::Compact;
+        self.triage_scheduling = false;
+    }
+
+    /// Inbox items in capture order (oldest first)
+    pub fn inbox_tasks(&self) -> Vec<&TaskItem> {
+        let mut tasks: Vec<_> = self.tasks.iter()
+            .filter(|t| t.frontmatter.status == Status::Inbox)
+            .collect();
+        tasks.sort_by_key(|t| t.frontmatter.created_at);
+        tasks
+    }
+
+    pub fn triage_current(&self) -> Option<&TaskItem> {
+        self.inbox_tasks().get(self.triage_index).copied()
+    }
+
+    pub fn triage_next(&mut self) {
+        let count = self.inbox_tasks().len();
+        if count > 0 {
+            self.triage_index = (self.triage_index + 1) % count;
+        }
+    }
+
+    pub fn triage_prev(&mut self) {
+        let count = self.inbox_tasks().len();
+        if count > 0 {
+            if self.triage_index == 0 {
+                self.triage_index = count - 1;
+            } else {
+                self.triage_index -= 1;
+            }
+        }
+    }
+
+    /// Apply a change to the current inbox item and persist it
+    fn triage_apply(&mut self, apply: impl FnOnce(&mut TaskItem)) -> Result<()> {
+        let Some(task_id) = self.triage_current().map(|t| t.frontmatter.id) else {
+            return Ok(());
+        };
+        let anchor = self.selection_anchor();
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task_id) {
+            apply(task);
+            self.storage.write_task(task)?;
+        }
+        self.restore_selection(anchor);
+        Ok(())
+    }
+
+    fn triage_clamp_index(&mut self) {
+        let count = self.inbox_tasks().len();
+        if self.triage_index >= count {
+            self.triage_index = count.saturating_sub(1);
+        }
+    }
+
+    /// Do now: move straight to Active (subject to its WIP limit)
+    pub fn triage_do_now(&mut self) -> Result<()> {
+        if let Some(task_id) = self.triage_current().map(|t| t.frontmatter.id) {
+            self.move_task_to_status(task_id, Status::Active)?;
+        }
+        Ok(())
+    }
+
+    /// Delegate: ask who it goes to, then park it in Waiting
+    pub fn triage_delegate(&mut self) {
+        if let Some(task_id) = self.triage_current().map(|t| t.frontmatter.id) {
+            self.show_delegate_dialog(task_id);
+        }
+    }
+
+    /// Someday/maybe: keep it around as a tagged Next item
+    pub fn triage_someday(&mut self) -> Result<()> {
+        self.triage_apply(|task| {
+            task.set_status(Status::Next);
+            if !task.has_tag("someday") {
+                task.frontmatter.tags.push("someday".to_string());
+            }
+        })
+    }
+
+    /// Trash: delete the captured item entirely
+    pub fn triage_trash(&mut self) -> Result<()> {
+        let Some(task_id) = self.triage_current().map(|t| t.frontmatter.id) else {
+            return Ok(());
+        };
+        let anchor = self.selection_anchor();
+        if let Some(pos) = self.tasks.iter().position(|t| t.frontmatter.id == task_id) {
+            let task = self.tasks.remove(pos);
+            self.storage.delete_task(&task)?;
+            self.notify(format!("Trashed: {}", task.frontmatter.title));
+        }
+        self.restore_selection(anchor);
+        Ok(())
+    }
+
+    pub fn triage_start_schedule(&mut self) {
+        if self.triage_current().is_some() {
+            self.triage_scheduling = true;
+            self.triage_input.clear();
+        }
+    }
+
+    pub fn triage_cancel_schedule(&mut self) {
+        self.triage_scheduling = false;
+        self.triage_input.clear();
+    }
+
+    /// Schedule: set the due date (YYYY-MM-DD, optionally THH:MM) and move to Next.
+    /// Invalid dates keep the prompt open.
+    pub fn triage_confirm_schedule(&mut self) -> Result<()> {
+        let Some(due) = Due::parse(&self.triage_input) else {
+            return Ok(());
+        };
+
+        self.triage_apply(|task| {
+            task.set_status(Status::Next);
+            task.frontmatter.due_date = Some(due);
+        })?;
+        self.triage_scheduling = false;
+        self.triage_input.clear();
+        Ok(())
+    }
+
+    // === Stale Review Methods ===
+
+    pub fn open_stale(&mut self) {
+        self.view_mode = ViewMode::Stale;
+        self.stale_selected = 0;
+        self.stale_rescheduling = false;
+        self.stale_input.clear();
+    }
+
+    pub fn close_stale(&mut self) {
+        self.view_mode = ViewMode::Compact;
+        self.stale_rescheduling = false;
+    }
+
+    /// Open tasks not updated for more than `stale_days`, least recently updated first
+    pub fn stale_tasks(&self) -> Vec<&TaskItem> {
+        let now = Utc::now();
+        let mut tasks: Vec<_> = self.tasks.iter()
+            .filter(|t| t.is_stale(now, self.config.stale_days))
+            .collect();
+        tasks.sort_by_key(|t| t.last_touched());
+        tasks
+    }
+
+    pub fn stale_current(&self) -> Option<&TaskItem> {
+        self.stale_tasks().get(self.stale_selected).copied()
+    }
+
+    pub fn stale_next(&mut self) {
+        let count = self.stale_tasks().len();
+        if count > 0 {
+            self.stale_selected = (self.stale_selected + 1) % count;
+        }
+    }
+
+    pub fn stale_prev(&mut self) {
+        let count = self.stale_tasks().len();
+        if count > 0 {
+            if self.stale_selected == 0 {
+                self.stale_selected = count - 1;
+            } else {
+                self.stale_selected -= 1;
+            }
+        }
+    }
+
+    /// Apply a review action to the selected stale task. Reviewing counts as
+    /// touching it, so it leaves the list.
+    fn stale_apply(&mut self, message: &str, apply: impl FnOnce(&mut TaskItem)) -> Result<()> {
+        let Some(task_id) = self.stale_current().map(|t| t.frontmatter.id) else {
+            return Ok(());
+        };
+        let anchor = self.selection_anchor();
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task_id) {
+            apply(task);
+            task.frontmatter.updated_at = Some(Utc::now());
+            self.storage.write_task(task)?;
+        }
+        self.restore_selection(anchor);
+        self.notify(message);
+        Ok(())
+    }
+
+    /// Bump: still relevant as it is
+    pub fn stale_bump(&mut self) -> Result<()> {
+        self.stale_apply("Bumped", |_| {})
+    }
+
+    /// Someday: park it as a tagged Next item, out of the stale list for good
+    pub fn stale_someday(&mut self) -> Result<()> {
+        self.stale_apply("Moved to someday", |task| {
+            task.set_status(Status::Next);
+            if !task.has_tag("someday") {
+                task.frontmatter.tags.push("someday".to_string());
+            }
+        })
+    }
+
+    pub fn stale_archive(&mut self) -> Result<()> {
+        self.stale_apply("Archived", |task| task.set_status(Status::Archived))
+    }
+
+    pub fn stale_start_reschedule(&mut self) {
+        if let Some(due) = self.stale_current().map(|t| t.frontmatter.due_date) {
+            self.stale_rescheduling = true;
+            self.stale_input.set(due.map(|d| d.to_string()).unwrap_or_default());
+        }
+    }
+
+    pub fn stale_cancel_reschedule(&mut self) {
+        self.stale_rescheduling = false;
+        self.stale_input.clear();
+    }
+
+    /// Reschedule: set a new due date (YYYY-MM-DD, optionally THH:MM).
+    /// Invalid dates keep the prompt open.
+    pub fn stale_confirm_reschedule(&mut self) -> Result<()> {
+        let Some(due) = Due::parse(&self.stale_input) else {
+            return Ok(());
+        };
+        self.stale_apply(&format!("Rescheduled to {}", due), |task| {
+            task.frontmatter.due_date = Some(due);
+        })?;
+        self.stale_cancel_reschedule();
+        Ok(())
+    }
+
+    // === End-of-Day Shutdown Methods ===
+
+    pub fn open_shutdown(&mut self) {
+        self.view_mode = ViewMode::Shutdown;
+        self.shutdown_selected = 0;
+        self.shutdown_rescheduling = false;
+        self.shutdown_input.clear();
+        self.shutdown_journal = None;
+    }
+
+    pub fn close_shutdown(&mut self) {
+        self.view_mode = ViewMode::Compact;
+        self.shutdown_rescheduling = false;
+    }
+
+    /// Tasks completed today and habits checked off
+    pub fn shutdown_completed(&self) -> Vec<&TaskItem> {
+        let today = models::today();
+        self.tasks.iter().filter(|t| !t.is_project() && journal::completed_on(t, today)).collect()
+    }
+
+    /// Tasks still active, to reschedule or demote before closing the day
+    pub fn shutdown_active(&self) -> Vec<&TaskItem> {
+        let mut tasks: Vec<_> = self.tasks.iter().filter(|t| !t.is_project() && journal::carried_over(t)).collect();
+        tasks.sort_by_key(|t| t.frontmatter.priority.rank());
+        tasks
+    }
+
+    pub fn shutdown_current(&self) -> Option<&TaskItem> {
+        self.shutdown_active().get(self.shutdown_selected).copied()
+    }
+
+    pub fn shutdown_select(&mut self, forward: bool) {
+        let count = self.shutdown_active().len();
+        if count > 0 {
+            self.shutdown_selected = if forward {
+                (self.shutdown_selected + 1) % count
+            } else {
+                (self.shutdown_selected + count - 1) % count
+            };
+        }
+    }
+
+    /// Apply a change to the selected active task; it leaves the list once
+    /// it's no longer active
+    fn shutdown_apply(&mut self, message: &str, apply: impl FnOnce(&mut TaskItem)) -> Result<()> {
+        let Some(task_id) = self.shutdown_current().map(|t| t.frontmatter.id) else {
+            return Ok(());
+        };
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task_id) {
+            apply(task);
+            self.storage.write_task(task)?;
+        }
+        self.shutdown_selected = self.shutdown_selected.min(self.shutdown_active().len().saturating_sub(1));
+        self.notify(message);
+        Ok(())
+    }
+
+    /// Demote: not for tomorrow, back to Next
+    pub fn shutdown_demote(&mut self) -> Result<()> {
+        self.shutdown_apply("Moved to next", |task| task.set_status(Status::Next))
+    }
+
+    pub fn shutdown_start_reschedule(&mut self) {
+        if let Some(due) = self.shutdown_current().map(|t| t.frontmatter.due_date) {
+            self.shutdown_rescheduling = true;
+            self.shutdown_input.set(due.map(|d| d.to_string()).unwrap_or_default());
+        }
+    }
+
+    pub fn shutdown_cancel_reschedule(&mut self) {
+        self.shutdown_rescheduling = false;
+        self.shutdown_input.clear();
+    }
+
+    /// Reschedule: set a new due date and move the task to Next.
+    /// Invalid dates keep the prompt open.
+    pub fn shutdown_confirm_reschedule(&mut self) -> Result<()> {
+        let Some(due) = Due::parse(&self.shutdown_input) else {
+            return Ok(());
+        };
+        self.shutdown_apply(&format!("Rescheduled to {}", due), |task| {
+            task.set_status(Status::Next);
+            task.frontmatter.due_date = Some(due);
+        })?;
+        self.shutdown_cancel_reschedule();
+        Ok(())
+    }
+
+    /// Ask the LLM for a one-paragraph journal entry about the day
+    pub fn shutdown_write_journal(&mut self) {
+        if !self.enricher.is_available() {
+            self.notify_error("The journal needs an LLM; add an OpenAI key in Settings");
+            return;
+        }
+        let facts = DayReview::new(&self.tasks, models::today()).to_markdown();
+        match self.enricher.complete_sync(JOURNAL_PROMPT, &facts, JOURNAL_MAX_TOKENS) {
+            Ok(text) => self.shutdown_journal = Some(text.trim().to_string()),
+            Err(e) => self.notify_error(format!("No journal entry: {}", e)),
+        }
+    }
+
+    /// Close the day: save the journal entry (if one was written) above the
+    /// day's facts, then commit and push through git sync
+    pub fn finish_shutdown(&mut self) -> Result<()> {
+        let today = models::today();
+        if let Some(entry) = &self.shutdown_journal {
+            let review = DayReview::new(&self.tasks, today);
+            let path = journal::journal_path(&self.data_dir, today);
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            let content = format!("# Journal {}\n\n{}\n\n{}", today.format("%Y-%m-%d"), entry, review.to_markdown());
+            std::fs::write(&path, content)?;
+        }
+        self.storage.commit(&format!("Shutdown: {}", today.format("%Y-%m-%d")));
+        // Start the next session in the task list, not the review
+        self.close_shutdown();
+        Ok(())
+    }
+
+    // === Delegation Methods ===
+
+    pub fn show_delegate_dialog(&mut self, task_id: Uuid) {
+        self.show_delegate = true;
+        let person = self.tasks.iter()
+            .find(|t| t.frontmatter.id == task_id)
+            .and_then(|t| t.frontmatter.delegated_to.clone())
+            .unwrap_or_default();
+        self.delegate_input.set(person);
+        self.delegate_task_id = Some(task_id);
+    }
+
+    pub fn compact_delegate_task(&mut self) {
+        let task_id = self.compact_selected_task().map(|t| t.frontmatter.id);
+        if let Some(task_id) = task_id {
+            self.show_delegate_dialog(task_id);
+        }
+    }
+
+    pub fn kanban_delegate_task(&mut self) {
+        if let Some(task_id) = self.kanban_selected_task().map(|t| t.frontmatter.id) {
+            self.show_delegate_dialog(task_id);
+        }
+    }
+
+    pub fn cancel_delegate_dialog(&mut self) {
+        self.show_delegate = false;
+        self.delegate_input.clear();
+        self.delegate_task_id = None;
+    }
+
+    pub fn confirm_delegate(&mut self) -> Result<()> {
+        let person = self.delegate_input.trim().to_string();
+        let task_id = self.delegate_task_id;
+        self.cancel_delegate_dialog();
+
+        let Some(task_id) = task_id else {
+            return Ok(());
+        };
+        if person.is_empty() {
+            return Ok(());
+        }
+        let anchor = self.selection_anchor();
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task_id) {
+            task.delegate(person.clone());
+            self.storage.write_task(task)?;
+            self.notify(format!("Delegated to {}", person));
+        }
+
+        self.restore_selection(anchor);
+        Ok(())
+    }
+
+    // === Custom Field Methods ===
+
+    /// The fields editable on the open task: its project's definitions, then
+    /// any values it carries that no definition covers
+    pub fn field_rows(&self) -> Vec<(String, Option<FieldDef>)> {
+        let Some(task) = self.fields_task_id.and_then(|id| self.task_by_id(id)) else {
+            return Vec::new();
+        };
+        let defs = models::field_defs(&self.tasks, task);
+        let mut rows: Vec<_> = defs.iter().map(|d| (d.name.clone(), Some(d.clone()))).collect();
+        for name in task.frontmatter.custom.keys() {
+            if !defs.iter().any(|d| &d.name == name) {
+                rows.push((name.clone(), None));
+            }
+        }
+        rows
+    }
+
+    pub fn show_fields_dialog(&mut self, task_id: Uuid) {
+        self.fields_task_id = Some(task_id);
+        self.fields_selected = 0;
+        self.fields_editing = false;
+        self.fields_input.clear();
+    }
+
+    pub fn compact_fields_task(&mut self) {
+        if let Some(task_id) = self.compact_selected_task().map(|t| t.frontmatter.id) {
+            self.show_fields_dialog(task_id);
+        }
+    }
+
+    /// Keep the selected task out of git sync, or let it sync again
+    pub fn toggle_local_only(&mut self) {
+        let Some(task) = self.compact_selected_task().cloned() else {
+            return;
+        };
+        let local_only = !self.storage.is_local_only(&task);
+        match self.storage.set_local_only(&task, local_only) {
+            Ok(true) => self.notify(format!(
+                "Local only: {}. It was pushed before, so the remote keeps that copy",
+                task.frontmatter.title
+            )),
+            Ok(_) if local_only => self.notify(format!("Local only: {}", task.frontmatter.title)),
+            Ok(_) => self.notify(format!("Syncing again: {}", task.frontmatter.title)),
+            Err(e) => self.notify_error(format!("Failed to change sync: {}", e)),
+        }
+    }
+
+    pub fn kanban_fields_task(&mut self) {
+        if let Some(task_id) = self.kanban_selected_task().map(|t| t.frontmatter.id) {
+            self.show_fields_dialog(task_id);
+        }
+    }
+
+    pub fn close_fields_dialog(&mut self) {
+        self.fields_task_id = None;
+        self.fields_editing = false;
+        self.fields_input.clear();
+    }
+
+    pub fn fields_select(&mut self, down: bool) {
+        let count = self.field_rows().len();
+        if count == 0 {
+            return;
+        }
+        self.fields_selected = if down {
+            (self.fields_selected + 1) % count
+        } else {
+            (self.fields_selected + count - 1) % count
+        };
+    }
+
+    /// Edit the selected value as text
+    pub fn fields_start_edit(&mut self) {
+        let Some((name, _)) = self.field_rows().into_iter().nth(self.fields_selected) else {
+            return;
+        };
+        let value = self.fields_task_id
+            .and_then(|id| self.task_by_id(id))
+            .and_then(|t| t.frontmatter.custom.get(&name).cloned())
+            .unwrap_or_default();
+        self.fields_input.set(value);
+        self.fields_editing = true;
+    }
+
+    pub fn fields_cancel_edit(&mut self) {
+        self.fields_editing = false;
+        self.fields_input.clear();
+    }
+
+    /// Save the edited value; an invalid one is refused and stays in the input
+    pub fn fields_confirm_edit(&mut self) -> Result<()> {
+        let Some((name, def)) = self.field_rows().into_iter().nth(self.fields_selected) else {
+            return Ok(());
+        };
+        let value = self.fields_input.trim().to_string();
+        if value.is_empty() {
+            self.fields_cancel_edit();
+            return self.set_custom_field(&name, None);
+        }
+        if let Some(Err(reason)) = def.map(|d| d.check(&value)) {
+            self.notify_error(format!("{}: {}", name, reason));
+            return Ok(());
+        }
+        self.fields_cancel_edit();
+        self.set_custom_field(&name, Some(value))
+    }
+
+    /// Step a choice field to its next or previous allowed value
+    pub fn fields_cycle(&mut self, forward: bool) -> Result<()> {
+        let Some((name, Some(def))) = self.field_rows().into_iter().nth(self.fields_selected) else {
+            return Ok(());
+        };
+        if def.kind != FieldType::Choice || def.values.is_empty() {
+            return Ok(());
+        }
+        let current = self.fields_task_id
+            .and_then(|id| self.task_by_id(id))
+            .and_then(|t| t.frontmatter.custom.get(&name))
+            .and_then(|value| def.values.iter().position(|v| v == value));
+        let count = def.values.len();
+        let next = match (current, forward) {
+            (None, true) => 0,
+            (None, false) => count - 1,
+            (Some(i), true) => (i + 1) % count,
+            (Some(i), false) => (i + count - 1) % count,
+        };
+        self.set_custom_field(&name, Some(def.values[next].clone()))
+    }
+
+    pub fn fields_clear(&mut self) -> Result<()> {
+        let Some((name, _)) = self.field_rows().into_iter().nth(self.fields_selected) else {
+            return Ok(());
+        };
+        self.set_custom_field(&name, None)?;
+        // Clearing an undefined field removes its row
+        self.fields_selected = self.fields_selected.min(self.field_rows().len().saturating_sub(1));
+        Ok(())
+    }
+
+    fn set_custom_field(&mut self, name: &str, value: Option<String>) -> Result<()> {
+        let Some(task_id) = self.fields_task_id else {
+            return Ok(());
+        };
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task_id) {
+            let changed = match value {
+                Some(value) => task.frontmatter.custom.insert(name.to_string(), value.clone()) != Some(value),
+                None => task.frontmatter.custom.remove(name).is_some(),
+            };
+            if changed {
+                self.storage.write_task(task)?;
+            }
+        }
+        Ok(())
+    }
+
+    // === Selection Tracking ===
+
+    /// Tasks in the current view, in display order
+    fn view_task_ids(&self) -> Vec<Uuid> {
+        let tasks = match self.view_mode {
+            ViewMode::Compact => self.compact_rows()
+                .into_iter()
+                .filter_map(|row| match row {
+                    CompactRow::Task(task) => Some(task),
+                    CompactRow::Header(_) => None,
+                })
+                .collect(),
+            ViewMode::Kanban => self.kanban_column_tasks(),
+            ViewMode::Projects => self.get_projects(),
+            ViewMode::ProjectGantt => self.get_project_tasks(),
+            ViewMode::Triage => self.inbox_tasks(),
+            ViewMode::People => self.people_tasks(),
+            ViewMode::Stale => self.stale_tasks(),
+            ViewMode::Shutdown => self.shutdown_active(),
+            _ => Vec::new(),
+        };
+        tasks.iter().map(|t| t.frontmatter.id).collect()
+    }
+
+    /// Task under the cursor in the current view
+    fn view_focused_task(&self) -> Option<Uuid> {
+        let task = match self.view_mode {
+            ViewMode::Compact => self.compact_selected_task(),
+            ViewMode::Kanban => self.kanban_selected_task(),
+            ViewMode::Projects => self.get_projects().get(self.projects_selected).copied(),
+            ViewMode::ProjectGantt => self.get_project_tasks().get(self.gantt_selected).copied(),
+            ViewMode::Triage => self.triage_current(),
+            ViewMode::People => self.people_tasks().get(self.people_selected).copied(),
+            ViewMode::Stale => self.stale_current(),
+            ViewMode::Shutdown => self.shutdown_current(),
+            _ => None,
+        };
+        task.map(|t| t.frontmatter.id)
+    }
+
+    /// Put the cursor on a task if the current view shows it
+    fn view_select_task(&mut self, task_id: Uuid) -> bool {
+        if self.view_mode == ViewMode::Kanban {
+            // Cards can change column, so look at the whole board
+            return self.kanban_select_task(task_id);
+        }
+        let Some(pos) = self.view_task_ids().iter().position(|id| *id == task_id) else {
+            return false;
+        };
+        match self.view_mode {
+            ViewMode::Compact => self.compact_select_task(task_id),
+            ViewMode::Projects => self.projects_selected = pos,
+            ViewMode::ProjectGantt => self.gantt_selected = pos,
+            ViewMode::Triage => self.triage_index = pos,
+            ViewMode::People => self.people_selected = pos,
+            ViewMode::Stale => self.stale_selected = pos,
+            ViewMode::Shutdown => self.shutdown_selected = pos,
+            _ => {}
+        }
+        true
+    }
+
+    fn clamp_selections(&mut self) {
+        self.compact_clamp_selection();
+        self.kanban_clamp_row();
+        self.triage_clamp_index();
+        let projects = self.get_projects().len();
+        self.projects_selected = self.projects_selected.min(projects.saturating_sub(1));
+        let gantt = self.get_project_tasks().len();
+        self.gantt_selected = self.gantt_selected.min(gantt.saturating_sub(1));
+        let people = self.people_tasks().len();
+        self.people_selected = self.people_selected.min(people.saturating_sub(1));
+        let stale = self.stale_tasks().len();
+        self.stale_selected = self.stale_selected.min(stale.saturating_sub(1));
+    }
+
+    /// Remember the focused task before changing tasks or the filter
+    pub fn selection_anchor(&self) -> SelectionAnchor {
+        let Some(focus) = self.view_focused_task() else {
+            return SelectionAnchor { view: Some(self.view_mode), ..SelectionAnchor::default() };
+        };
+        let ids = self.view_task_ids();
+        let pos = ids.iter().position(|id| *id == focus).unwrap_or(0);
+        let neighbours = ids[pos + 1..].iter()
+            .chain(ids[..pos].iter().rev())
+            .copied()
+            .collect();
+        SelectionAnchor { view: Some(self.view_mode), focus: Some(focus), neighbours }
+    }
+
+    /// Move the cursor back to the anchored task, or the nearest neighbour still shown
+    pub fn restore_selection(&mut self, anchor: SelectionAnchor) {
+        if anchor.view.as_ref() == Some(&self.view_mode) {
+            for id in anchor.focus.into_iter().chain(anchor.neighbours) {
+                if self.view_select_task(id) {
+                    self.clamp_selections();
+                    return;
+                }
+            }
+        }
+        self.clamp_selections();
+    }
+
+    /// The text input of the open dialog, if it has one
+    pub fn active_input(&mut self) -> Option<&mut TextInput> {
+        if self.show_new_task && !self.new_task_batch.is_empty() {
+            None
+        } else if self.show_new_task {
+            Some(&mut self.new_task_title)
+        } else if self.show_new_project {
+            Some(&mut self.new_project_title)
+        } else if self.wip_pending.is_some() || self.retag_pending.is_some() || self.project_archive.is_some() || self.priority_review.is_some() || self.pull_conflicts.is_some() {
+            None
+        } else if self.show_filter_builder {
+            Some(if self.filter_naming { &mut self.filter_name_input } else { &mut self.filter_input })
+        } else if self.show_links {
+            None
+        } else if self.show_search {
+            Some(&mut self.search_input)
+        } else if self.show_note {
+            Some(&mut self.note_input)
+        } else if self.show_delegate {
+            Some(&mut self.delegate_input)
+        } else if self.fields_editing {
+            Some(&mut self.fields_input)
+        } else if self.triage_scheduling {
+            Some(&mut self.triage_input)
+        } else if self.settings_editing {
+            Some(&mut self.settings_edit_text)
+        } else {
+            None
+        }
+    }
+
+    /// Paste into the open dialog's input; ignored when no dialog takes text
+    pub fn paste(&mut self, text: &str) {
+        if self.show_new_task && self.new_task_batch.is_empty() && self.start_new_task_batch(text) {
+            return;
+        }
+        if let Some(input) = self.active_input() {
+            input.insert_str(text);
+        }
+    }
+
+    // === Links ===
+
+    pub fn rebuild_links(&mut self) {
+        self.links = LinkIndex::build(&self.tasks);
+        *self.related_cache.get_mut() = None;
+    }
+
+    /// Open tasks most like `task`, for the preview's "Related" section. Scored
+    /// once per previewed task until the tasks are reloaded or relinked.
+    pub fn related_tasks(&self, task: &TaskItem) -> Vec<&TaskItem> {
+        let task_id = task.frontmatter.id;
+        let mut cache = self.related_cache.borrow_mut();
+        let ids = match cache.as_ref() {
+            Some((cached, ids)) if *cached == task_id => ids.clone(),
+            _ => {
+                let ids: Vec<Uuid> = self.storage.related(&self.tasks, task, RELATED_LIMIT)
+                    .iter()
+                    .map(|t| t.frontmatter.id)
+                    .collect();
+                *cache = Some((task_id, ids.clone()));
+                ids
+            }
+        };
+        ids.iter()
+            .filter_map(|id| self.tasks.iter().find(|t| t.frontmatter.id == *id))
+            .filter(|t| !t.frontmatter.status.is_closed())
+            .collect()
+    }
+
+    /// A warning for a due date that falls on a configured holiday or vacation
+    pub fn holiday_warning(&self, due: &Due) -> Option<Span<'static>> {
+        let holiday = self.config.calendar.holiday_on(due.local_date())?;
+        Some(Span::styled(format!("{}{}", self.glyphs().warning, holiday.label()), THEME.warning_style()))
+    }
+
+    /// The hint under a due-date prompt, or a warning once the date typed
+    /// falls on a day off
+    pub fn due_prompt_hint(&self, input: &str) -> Line<'static> {
+        match Due::parse(input).and_then(|due| self.holiday_warning(&due)) {
+            Some(warning) => Line::from(vec![Span::raw(" "), warning]),
+            None => Line::from(Span::styled(" Due date as YYYY-MM-DD, optionally with THH:MM", THEME.dim_style())),
+        }
+    }
+
+    /// A habit's current streak, bright once it's checked off for today (or
+    /// this week)
+    pub fn streak_badge(&self, task: &TaskItem) -> Option<Span<'static>> {
+        task.frontmatter.habit?;
+        let today = models::today();
+        let style = if task.checked_off(today) { THEME.accent_style() } else { THEME.dim_style() };
+        Some(Span::styled(format!("{}{}", self.glyphs().streak, task.current_streak(today)), style))
+    }
+
+    /// Emoji or their ASCII stand-ins, per `display.ascii_only`
+    pub fn glyphs(&self) -> &'static Glyphs {
+        if self.config.display.ascii_only { &glyphs::ASCII } else { &glyphs::UNICODE }
+    }
+
+    /// A priority as shown in lists: a colored `P0`–`P4` badge, or its emoji
+    /// with `priority_style: emoji` (unless `display.ascii_only` is set). Both
+    /// are two columns wide.
+    pub fn priority_badge(&self, priority: &Priority) -> Span<'static> {
+        if self.config.priority_style == PriorityStyle::Emoji && !self.config.display.ascii_only {
+            return Span::styled(priority.emoji().to_string(), THEME.normal_style());
+        }
+        let color = self.config.priority_colors.get(priority.as_str())
+            .and_then(|color| Color::from_str(color).ok())
+            .unwrap_or(THEME.priority[priority.rank() as usize]);
+        Span::styled(priority.label().to_string(), THEME.badge_style(color))
+    }
+
+    fn task_by_id(&self, id: Uuid) -> Option<&TaskItem> {
+        self.tasks.iter().find(|t| t.frontmatter.id == id)
+    }
+
+    /// Links of the Compact selection: (true, target) for outgoing, (false, source) for backlinks
+    pub fn selected_task_links(&self) -> Vec<(bool, &TaskItem)> {
+        let Some(task) = self.compact_selected_task() else {
+            return Vec::new();
+        };
+        let id = task.frontmatter.id;
+        let outgoing = self.links.outgoing(id).iter().map(|id| (true, *id));
+        let incoming = self.links.incoming(id).iter().map(|id| (false, *id));
+        outgoing.chain(incoming)
+            .filter_map(|(out, id)| self.task_by_id(id).map(|t| (out, t)))
+            .collect()
+    }
 
-        // Update Gantt selection if we're in that view
-        if self.view_mode == ViewMode::ProjectGantt {
-            self.gantt_selected = self.get_project_tasks().len().saturating_sub(1);
+    pub fn open_links(&mut self) {
+        self.show_links = true;
+        self.links_selected = 0;
+    }
+
+    pub fn close_links(&mut self) {
+        self.show_links = false;
+    }
+
+    pub fn links_next(&mut self) {
+        let count = self.selected_task_links().len();
+        if count > 0 {
+            self.links_selected = (self.links_selected + 1) % count;
         }
+    }
 
-        self.show_new_task = false;
-        self.new_task_title.clear();
-        self.new_task_project_id = None;
-        Ok(())
+    pub fn links_prev(&mut self) {
+        let count = self.selected_task_links().len();
+        if count > 0 {
+            self.links_selected = (self.links_selected + count - 1) % count;
+        }
     }
 
-    /// Parse @project reference from input text
-    /// Returns (cleaned_input, Option<project_id>)
-    fn parse_project_reference(&self, input: &str) -> (String, Option<Uuid>) {
-        // Find @word pattern
-        let mut project_id = None;
-        let mut cleaned = input.to_string();
-
-        if let Some(at_pos) = input.find('@') {
-            // Extract the word after @
-            let after_at = &input[at_pos + 1..];
-            let project_name: String = after_at
-                .chars()
-                .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == '-')
-                .collect();
-
-            if !project_name.is_empty() {
-                // Look up project by name (case-insensitive)
-                let project_name_lower = project_name.to_lowercase();
-                if let Some(project) = self.tasks.iter().find(|t| {
-                    t.is_project() && t.frontmatter.title.to_lowercase().contains(&project_name_lower)
-                }) {
-                    project_id = Some(project.frontmatter.id);
-                    // Remove @project from input
-                    cleaned = input.replace(&format!("@{}", project_name), "").trim().to_string();
-                }
-            }
+    /// Jump to the link under the cursor in the picker
+    pub fn follow_link(&mut self) {
+        let target = self.selected_task_links()
+            .get(self.links_selected)
+            .map(|(_, t)| t.frontmatter.id);
+        self.close_links();
+        if let Some(target) = target {
+            self.jump_to_task(target);
         }
+    }
+
+    // === Search ===
 
-        (cleaned, project_id)
+    pub fn open_search(&mut self) {
+        self.show_search = true;
+        self.search_input.clear();
+        self.search_selected = 0;
     }
 
-    pub fn mark_task_done(&mut self) -> Result<()> {
-        let filtered = self.filtered_tasks();
-        if let Some(task) = filtered.get(self.selected_index) {
-            let task_id = task.frontmatter.id;
-            if let Some(task) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task_id) {
-                task.frontmatter.status = Status::Done;
-                self.storage.write_task(task)?;
-            }
+    pub fn close_search(&mut self) {
+        self.show_search = false;
+    }
+
+    /// Tasks matching the search input, best first. Semantic matches are
+    /// those of the last query run with Enter.
+    pub fn search_hits(&self) -> Vec<Hit<'_>> {
+        if !self.search_semantic {
+            return self.storage.search(&self.tasks, &self.search_input, SEARCH_LIMIT);
         }
-        Ok(())
+        let Some((_, ids)) = &self.semantic_results else {
+            return Vec::new();
+        };
+        ids.iter()
+            .filter_map(|id| self.task_by_id(*id))
+            .map(|task| Hit { task, score: 0.0, snippet: None })
+            .collect()
     }
 
-    /// Cycle task priority: Low → Medium → High → Low
-    pub fn cycle_task_priority(&mut self) -> Result<()> {
-        let filtered = self.filtered_tasks();
-        if let Some(task) = filtered.get(self.selected_index) {
-            let task_id = task.frontmatter.id;
-            if let Some(task) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task_id) {
-                task.frontmatter.priority = match task.frontmatter.priority {
-                    Priority::Low => Priority::Medium,
-                    Priority::Medium => Priority::High,
-                    Priority::High => Priority::Low,
-                };
-                self.storage.write_task(task)?;
-            }
+    /// Switch the search between words and meaning
+    pub fn toggle_search_mode(&mut self) {
+        if self.config.embeddings.is_none() {
+            self.notify_error("Semantic search needs an embeddings API: set embeddings in the config");
+            return;
         }
-        Ok(())
+        self.search_semantic = !self.search_semantic;
+        self.search_selected = 0;
     }
 
-    /// Cycle task priority in Kanban view
-    pub fn kanban_cycle_priority(&mut self) -> Result<()> {
-        if let Some(task) = self.kanban_selected_task() {
-            let task_id = task.frontmatter.id;
-            if let Some(task) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task_id) {
-                task.frontmatter.priority = match task.frontmatter.priority {
-                    Priority::Low => Priority::Medium,
-                    Priority::Medium => Priority::High,
-                    Priority::High => Priority::Low,
-                };
-                self.storage.write_task(task)?;
+    /// Whether a semantic search for the current input is still running
+    pub fn is_searching(&self) -> bool {
+        self.semantic_searches.iter().any(|(query, _)| *query == self.search_input.trim())
+    }
+
+    /// Run a semantic search for the input in the background, unless it's
+    /// already running. The embeddings of changed tasks are brought up to date
+    /// first, which the first search of a vault does for all of it.
+    fn start_semantic_search(&mut self) {
+        let query = self.search_input.trim().to_string();
+        if query.is_empty() || self.is_searching() {
+            return;
+        }
+        if let Some(git_sync) = &self.storage.git_sync {
+            if let Err(err) = git_sync.exclude(EmbeddingIndex::FILE_NAME) {
+                tracing::warn!(error = ?err, "Failed to keep embeddings out of git");
             }
         }
-        Ok(())
+        let receiver = embeddings::search_in_background(&self.data_dir, &self.config, self.tasks.clone(), &query, SEARCH_LIMIT);
+        self.semantic_searches.push((query, receiver));
     }
 
-    pub fn archive_task(&mut self) -> Result<()> {
-        let filtered = self.filtered_tasks();
-        if let Some(task) = filtered.get(self.selected_index) {
-            let task_id = task.frontmatter.id;
-            if let Some(task) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task_id) {
-                task.frontmatter.status = Status::Archived;
-                self.storage.write_task(task)?;
+    pub fn poll_search(&mut self) {
+        for (query, result) in take_finished(&mut self.semantic_searches) {
+            match result {
+                Ok(ids) => {
+                    self.semantic_results = Some((query, ids));
+                    self.search_selected = 0;
+                }
+                Err(e) => self.notify_error(format!("Semantic search failed: {:#}", e)),
             }
         }
-        Ok(())
     }
 
-    pub fn refresh_tasks(&mut self) -> Result<()> {
-        self.tasks = self.storage.load_all_tasks()?;
-        Ok(())
+    pub fn search_select(&mut self, down: bool) {
+        let count = self.search_hits().len();
+        if count > 0 {
+            let current = self.search_selected.min(count - 1);
+            self.search_selected = if down { (current + 1) % count } else { (current + count - 1) % count };
+        }
     }
 
-    pub fn filter_by_tag(&mut self, tag: &str) {
-        self.active_filter = Some(tag.to_string());
-        self.selected_index = 0;
+    /// Jump to the match under the cursor. In semantic mode a changed query
+    /// is run first.
+    pub fn confirm_search(&mut self) {
+        let answered = self.semantic_results.as_ref().is_some_and(|(query, _)| *query == self.search_input.trim());
+        if self.search_semantic && !answered {
+            self.start_semantic_search();
+            return;
+        }
+        let hits = self.search_hits();
+        let target = hits.get(self.search_selected.min(hits.len().saturating_sub(1))).map(|h| h.task.frontmatter.id);
+        self.close_search();
+        if let Some(target) = target {
+            self.jump_to_task(target);
+        }
     }
 
-    pub fn clear_filters(&mut self) {
-        self.active_filter = None;
-        self.selected_index = 0;
-    }
+    /// Show a task wherever it is visible: projects open in the Gantt view; tasks are
+    /// selected in Compact (clearing the filter if needed) or, failing that, on the board
+    pub fn jump_to_task(&mut self, task_id: Uuid) {
+        let Some(task) = self.task_by_id(task_id) else {
+            return;
+        };
+        let title = task.frontmatter.title.clone();
+        let status = task.frontmatter.status.clone();
 
-    pub fn filtered_tasks(&self) -> Vec<&TaskItem> {
-        let mut tasks: Vec<&TaskItem> = self.tasks.iter().collect();
+        if task.is_project() {
+            if let Some(pos) = self.get_projects().iter().position(|p| p.frontmatter.id == task_id) {
+                self.projects_selected = pos;
+                self.open_project_gantt();
+            }
+            return;
+        }
 
-        if let Some(tag) = &self.active_filter {
-            tasks.retain(|task| task.has_tag(tag));
+        if !self.filtered_tasks().iter().any(|t| t.frontmatter.id == task_id) {
+            self.clear_filters();
         }
+        self.collapsed_sections.remove(&status);
 
-        tasks
+        if COMPACT_SECTIONS.contains(&status) {
+            // Page the Done section to the one holding the task
+            if let Some(page) = self.done_page_of(task_id) {
+                self.done_page = page;
+            }
+            self.view_mode = ViewMode::Compact;
+            self.compact_select_task(task_id);
+        } else if (0..self.config.kanban_columns.len())
+            .any(|col| self.tasks_in_column(col).iter().any(|t| t.frontmatter.id == task_id))
+        {
+            self.view_mode = ViewMode::Kanban;
+            self.kanban_select_task(task_id);
+        } else {
+            self.notify(format!("\"{}\" is {} and not shown in this view", title, status.as_str()));
+        }
     }
 
-    pub fn tasks_by_status(&self, status: Status) -> Vec<&TaskItem> {
-        let filtered = self.filtered_tasks();
-        filtered.into_iter()
-            .filter(|t| t.frontmatter.status == status)
-            .collect()
-    }
+    // === Quick Notes ===
 
-    /// Returns tasks in display order: Active → Next → Done (excludes Archived and Waiting for compact view)
-    pub fn display_ordered_tasks(&self) -> Vec<&TaskItem> {
-        let filtered = self.filtered_tasks();
-        let mut result = Vec::new();
+    pub fn show_note_dialog(&mut self, task_id: Uuid) {
+        self.show_note = true;
+        self.note_input.clear();
+        self.note_task_id = Some(task_id);
+    }
 
-        // Active tasks first
-        result.extend(filtered.iter().filter(|t| t.frontmatter.status == Status::Active).copied());
-        // Next tasks
-        result.extend(filtered.iter().filter(|t| t.frontmatter.status == Status::Next).copied());
-        // Done tasks
-        result.extend(filtered.iter().filter(|t| t.frontmatter.status == Status::Done).copied());
+    pub fn compact_note_task(&mut self) {
+        if let Some(task_id) = self.compact_selected_task().map(|t| t.frontmatter.id) {
+            self.show_note_dialog(task_id);
+        }
+    }
 
-        result
+    pub fn kanban_note_task(&mut self) {
+        if let Some(task_id) = self.kanban_selected_task().map(|t| t.frontmatter.id) {
+            self.show_note_dialog(task_id);
+        }
     }
 
-    /// Get count of tasks by status for navigation bounds
-    pub fn task_counts(&self) -> (usize, usize, usize) {
-        let filtered = self.filtered_tasks();
-        let active = filtered.iter().filter(|t| t.frontmatter.status == Status::Active).count();
-        let next = filtered.iter().filter(|t| t.frontmatter.status == Status::Next).count();
-        let done = filtered.iter().filter(|t| t.frontmatter.status == Status::Done).count();
-        (active, next, done)
+    pub fn cancel_note_dialog(&mut self) {
+        self.show_note = false;
+        self.note_input.clear();
+        self.note_task_id = None;
     }
 
-    // === Kanban Navigation Methods ===
+    /// Append the typed note to the task's `## Notes` timeline
+    pub fn confirm_note(&mut self) -> Result<()> {
+        let text = self.note_input.trim().to_string();
+        let task_id = self.note_task_id;
+        self.cancel_note_dialog();
 
-    pub fn kanban_column_status(&self) -> Status {
-        match self.kanban_column {
-            KANBAN_COL_ACTIVE => Status::Active,
-            KANBAN_COL_NEXT => Status::Next,
-            KANBAN_COL_WAITING => Status::Waiting,
-            KANBAN_COL_DONE => Status::Done,
-            _ => Status::Active,
+        let Some(task_id) = task_id else {
+            return Ok(());
+        };
+        if text.is_empty() {
+            return Ok(());
         }
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task_id) {
+            task.body = activity::append_note(&task.body, &text, Utc::now());
+            self.storage.write_task(task)?;
+            self.notify("Note added");
+        }
+        self.rebuild_links();
+        Ok(())
     }
 
-    pub fn kanban_column_tasks(&self) -> Vec<&TaskItem> {
-        self.tasks_by_status(self.kanban_column_status())
-    }
-
-    pub fn kanban_move_left(&mut self) {
-        if self.kanban_column == 0 {
-            self.kanban_column = 3;
-        } else {
-            self.kanban_column -= 1;
+    /// Create "Ping X about Y" tasks for delegated items waiting too long.
+    /// Only run on request (`f` in the People view), naming what it created.
+    pub fn generate_follow_ups(&mut self) -> Result<usize> {
+        let follow_ups = delegation::follow_ups_due(&self.tasks, self.config.follow_up_days, Utc::now());
+        let titles: Vec<String> = follow_ups.iter().map(|t| t.frontmatter.title.clone()).collect();
+        for mut task in follow_ups {
+            task.file_path = self.storage.write_task(&mut task)?;
+            self.tasks.push(task);
         }
-        // Clamp row to new column's task count
-        let task_count = self.kanban_column_tasks().len();
-        if self.kanban_row >= task_count {
-            self.kanban_row = task_count.saturating_sub(1);
+        if titles.is_empty() {
+            self.notify("No follow-ups due");
+        } else {
+            self.notify(format!("Created {}", titles.join(", ")));
         }
+        Ok(titles.len())
     }
 
-    pub fn kanban_move_right(&mut self) {
-        self.kanban_column = (self.kanban_column + 1) % 4;
-        // Clamp row to new column's task count
-        let task_count = self.kanban_column_tasks().len();
-        if self.kanban_row >= task_count {
-            self.kanban_row = task_count.saturating_sub(1);
+    // === People View Methods ===
+
+    pub fn open_people(&mut self) {
+        self.view_mode = ViewMode::People;
+        self.people_selected = 0;
+    }
+
+    pub fn close_people(&mut self) {
+        self.view_mode = ViewMode::Compact;
+    }
+
+    /// Delegated Waiting tasks in People view order (grouped by person)
+    pub fn people_tasks(&self) -> Vec<&TaskItem> {
+        delegation::waiting_by_person(&self.tasks)
+            .into_values()
+            .flatten()
+            .collect()
+    }
+
+    pub fn people_next(&mut self) {
+        let count = self.people_tasks().len();
+        if count > 0 {
+            self.people_selected = (self.people_selected + 1) % count;
         }
     }
 
-    pub fn kanban_move_up(&mut self) {
-        let task_count = self.kanban_column_tasks().len();
-        if task_count > 0 {
-            if self.kanban_row == 0 {
-                self.kanban_row = task_count - 1;
+    pub fn people_prev(&mut self) {
+        let count = self.people_tasks().len();
+        if count > 0 {
+            if self.people_selected == 0 {
+                self.people_selected = count - 1;
             } else {
-                self.kanban_row -= 1;
+                self.people_selected -= 1;
             }
         }
     }
 
-    pub fn kanban_move_down(&mut self) {
-        let task_count = self.kanban_column_tasks().len();
-        if task_count > 0 {
-            self.kanban_row = (self.kanban_row + 1) % task_count;
+    /// Hand the selected delegated task to someone else
+    pub fn people_reassign(&mut self) {
+        let task_id = self.people_tasks()
+            .get(self.people_selected)
+            .map(|t| t.frontmatter.id);
+        if let Some(task_id) = task_id {
+            self.show_delegate_dialog(task_id);
         }
     }
 
-    pub fn kanban_selected_task(&self) -> Option<&TaskItem> {
-        self.kanban_column_tasks().get(self.kanban_row).copied()
-    }
-
-    pub fn kanban_mark_done(&mut self) -> Result<()> {
-        if let Some(task) = self.kanban_selected_task() {
-            let task_id = task.frontmatter.id;
+    /// Mark the selected delegated task as delivered
+    pub fn people_mark_done(&mut self) -> Result<()> {
+        let task_id = self.people_tasks()
+            .get(self.people_selected)
+            .map(|t| t.frontmatter.id);
+        if let Some(task_id) = task_id {
+            let anchor = self.selection_anchor();
             if let Some(task) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task_id) {
-                task.frontmatter.status = Status::Done;
+                task.set_status(Status::Done);
                 self.storage.write_task(task)?;
             }
-            // Adjust row if we removed a task from current column
-            let new_count = self.kanban_column_tasks().len();
-            if self.kanban_row >= new_count && new_count > 0 {
-                self.kanban_row = new_count - 1;
-            }
+            self.restore_selection(anchor);
+            self.complete_jira_issue(task_id);
         }
         Ok(())
     }
 
-    pub fn kanban_archive_task(&mut self) -> Result<()> {
-        if let Some(task) = self.kanban_selected_task() {
-            let task_id = task.frontmatter.id;
-            if let Some(task) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task_id) {
-                task.frontmatter.status = Status::Archived;
-                self.storage.write_task(task)?;
-            }
-            // Adjust row if we removed a task from current column
-            let new_count = self.kanban_column_tasks().len();
-            if self.kanban_row >= new_count && new_count > 0 {
-                self.kanban_row = new_count - 1;
-            }
+    // === Goal Methods ===
+
+    // === Workstream dashboard ===
+
+    /// Open the dashboard on the workstream being filtered by, or the first one
+    pub fn open_dashboard(&mut self) {
+        self.view_mode = ViewMode::Dashboard;
+        self.dashboard_stream = self.config.workstreams.iter()
+            .position(|ws| self.filter.tags.contains(&ws.name))
+            .unwrap_or(0);
+    }
+
+    pub fn close_dashboard(&mut self) {
+        self.view_mode = ViewMode::Compact;
+    }
+
+    /// Show the next (or previous) workstream
+    pub fn dashboard_cycle(&mut self, forward: bool) {
+        let count = self.config.workstreams.len();
+        if count > 0 {
+            self.dashboard_stream = if forward {
+                (self.dashboard_stream + 1) % count
+            } else {
+                (self.dashboard_stream + count - 1) % count
+            };
         }
-        Ok(())
     }
 
-    // === Settings View Methods ===
+    /// Show the workstream with this shortcut key
+    pub fn dashboard_select_key(&mut self, key: char) {
+        if let Some(idx) = self.config.workstreams.iter().position(|ws| ws.key == key) {
+            self.dashboard_stream = idx;
+        }
+    }
 
-    fn settings_max_items(&self) -> usize {
-        match self.settings_section {
-            SettingsSection::Workstreams => self.config.workstreams.len() + 1, // +1 for "Add new"
-            SettingsSection::Goals => self.config.goals.len() + 1,
-            SettingsSection::ApiKeys => 1, // Just OpenAI API key for now
+    /// Switch the lists to the shown workstream: filter by its tag and go back
+    pub fn dashboard_apply(&mut self) {
+        if let Some(name) = self.config.workstreams.get(self.dashboard_stream).map(|ws| ws.name.clone()) {
+            self.filter_by_tag(&name);
         }
+        self.close_dashboard();
     }
 
-    pub fn settings_next(&mut self) {
-        let max_items = self.settings_max_items();
-        if max_items > 0 {
-            self.settings_selected = (self.settings_selected + 1) % max_items;
+    pub fn open_goals(&mut self) {
+        self.view_mode = ViewMode::Goals;
+        self.goals_selected = 0;
+    }
+
+    pub fn close_goals(&mut self) {
+        self.view_mode = ViewMode::Compact;
+    }
+
+    pub fn goals_next(&mut self) {
+        let count = self.config.active_goals().len();
+        if count > 0 {
+            self.goals_selected = (self.goals_selected + 1) % count;
         }
     }
 
-    pub fn settings_prev(&mut self) {
-        let max_items = self.settings_max_items();
-        if max_items > 0 {
-            if self.settings_selected == 0 {
-                self.settings_selected = max_items - 1;
-            } else {
-                self.settings_selected -= 1;
-            }
+    pub fn goals_prev(&mut self) {
+        let count = self.config.active_goals().len();
+        if count > 0 {
+            self.goals_selected = (self.goals_selected + count - 1) % count;
         }
     }
 
-    pub fn settings_start_edit(&mut self) {
-        match self.settings_section {
-            SettingsSection::Workstreams => {
-                if self.settings_selected < self.config.workstreams.len() {
-                    self.settings_editing = true;
-                    self.settings_edit_text = self.config.workstreams[self.settings_selected].name.clone();
-                } else {
-                    self.settings_editing = true;
-                    self.settings_edit_text.clear();
-                }
-            }
-            SettingsSection::Goals => {
-                if self.settings_selected < self.config.goals.len() {
-                    self.settings_editing = true;
-                    self.settings_edit_text = self.config.goals[self.settings_selected].description.clone();
-                    self.settings_edit_area = self.config.goals[self.settings_selected].area.clone();
-                } else {
-                    self.settings_editing = true;
-                    self.settings_edit_text.clear();
-                    self.settings_edit_area = String::from("work");
-                }
-            }
-            SettingsSection::ApiKeys => {
-                self.settings_editing = true;
-                self.settings_edit_text = self.config.openai_api_key.clone().unwrap_or_default();
-            }
+    /// Goals offered by the picker: active goals by priority, then inactive ones,
+    /// then `None` to unlink
+    pub fn goal_choices(&self) -> Vec<Option<Uuid>> {
+        let active = self.config.active_goals();
+        let inactive = self.config.goals.iter().filter(|g| !g.active);
+        active.into_iter().chain(inactive)
+            .map(|g| Some(g.id))
+            .chain([None])
+            .collect()
+    }
+
+    /// Open the goal picker for the selected task (Compact, Kanban) or project (Projects)
+    pub fn show_goal_picker(&mut self) {
+        let task = match self.view_mode {
+            ViewMode::Compact => self.compact_selected_task(),
+            ViewMode::Kanban => self.kanban_selected_task(),
+            ViewMode::Projects => self.get_projects().get(self.projects_selected).copied(),
+            _ => None,
+        };
+        let Some((task_id, current)) = task.map(|t| (t.frontmatter.id, t.frontmatter.goal_id)) else {
+            return;
+        };
+        if self.config.goals.is_empty() {
+            self.notify("No goals yet. Add one in Settings (s, Tab to Goals)");
+            return;
         }
+        self.goal_pick_selected = self.goal_choices().iter().position(|c| *c == current).unwrap_or(0);
+        self.goal_pick_task_id = Some(task_id);
     }
 
-    pub fn settings_cancel_edit(&mut self) {
-        self.settings_editing = false;
-        self.settings_edit_text.clear();
+    pub fn close_goal_picker(&mut self) {
+        self.goal_pick_task_id = None;
     }
 
-    pub fn settings_confirm_edit(&mut self) -> Result<()> {
-        let text = self.settings_edit_text.trim().to_string();
+    pub fn goal_picker_next(&mut self) {
+        let count = self.goal_choices().len();
+        self.goal_pick_selected = (self.goal_pick_selected + 1) % count;
+    }
 
-        match self.settings_section {
-            SettingsSection::Workstreams => {
-                if text.is_empty() {
-                    self.settings_cancel_edit();
-                    return Ok(());
-                }
-                if self.settings_selected < self.config.workstreams.len() {
-                    self.config.workstreams[self.settings_selected].name = text;
-                } else {
-                    self.config.add_workstream(text);
-                }
-            }
-            SettingsSection::Goals => {
-                if text.is_empty() {
-                    self.settings_cancel_edit();
-                    return Ok(());
-                }
-                if self.settings_selected < self.config.goals.len() {
-                    self.config.update_goal(self.settings_selected, text);
-                    self.config.update_goal_area(self.settings_selected, self.settings_edit_area.clone());
-                } else {
-                    self.config.add_goal(text, self.settings_edit_area.clone());
-                }
-            }
-            SettingsSection::ApiKeys => {
-                // Allow empty to clear the API key
-                if text.is_empty() {
-                    self.config.openai_api_key = None;
-                } else {
-                    self.config.openai_api_key = Some(text);
-                }
-                // Reinitialize the enricher with the new API key
-                self.enricher = crate::llm::TaskEnricher::new(self.config.openai_api_key.clone());
+    pub fn goal_picker_prev(&mut self) {
+        let count = self.goal_choices().len();
+        self.goal_pick_selected = (self.goal_pick_selected + count - 1) % count;
+    }
+
+    /// Link the task to the goal under the cursor (or unlink it)
+    pub fn confirm_goal_picker(&mut self) -> Result<()> {
+        let choice = self.goal_choices().get(self.goal_pick_selected).copied().flatten();
+        let Some(task_id) = self.goal_pick_task_id.take() else {
+            return Ok(());
+        };
+        let label = choice.and_then(|id| self.config.goal(id)).map(|g| g.description.clone());
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.frontmatter.id == task_id) {
+            task.frontmatter.goal_id = choice;
+            self.storage.write_task(task)?;
+            match label {
+                Some(goal) => self.notify(format!("Linked to goal: {}", goal)),
+                None => self.notify("Unlinked from goal"),
             }
         }
+        Ok(())
+    }
+
+    // === Filter Builder Methods ===
+
+    /// Find a project by (case-insensitive) name; dashes match spaces
+    fn project_id_by_name(&self, name: &str) -> Option<Uuid> {
+        let needle = name.to_lowercase().replace('-', " ");
+        self.get_projects().into_iter()
+            .find(|p| p.frontmatter.title.to_lowercase().contains(&needle))
+            .map(|p| p.frontmatter.id)
+    }
+
+    fn project_name(&self, id: Uuid) -> Option<String> {
+        self.tasks.iter()
+            .find(|t| t.frontmatter.id == id)
+            .map(|t| t.frontmatter.title.clone())
+    }
+
+    pub fn open_filter_builder(&mut self) {
+        self.show_filter_builder = true;
+        self.filter_input.set(self.filter_query());
+        self.filter_saved_selected = None;
+        self.filter_naming = false;
+        self.filter_name_input.clear();
+    }
+
+    pub fn close_filter_builder(&mut self) {
+        self.show_filter_builder = false;
+        self.filter_naming = false;
+    }
+
+    /// Parse the builder input (used for the live preview and on apply)
+    pub fn filter_builder_preview(&self) -> Result<FilterExpr, String> {
+        FilterExpr::parse(&self.filter_input, |name| self.project_id_by_name(name))
+    }
+
+    /// Apply the typed filter; parse errors keep the builder open
+    pub fn apply_filter_builder(&mut self) {
+        if let Ok(filter) = self.filter_builder_preview() {
+            self.set_filter(filter);
+            self.close_filter_builder();
+        }
+    }
 
-        self.config.save(&self.data_dir)?;
-        self.settings_editing = false;
-        self.settings_edit_text.clear();
-        Ok(())
+    /// Step through saved filters, loading each into the input
+    pub fn filter_builder_select(&mut self, forward: bool) {
+        let count = self.config.saved_filters.len();
+        if count == 0 {
+            return;
+        }
+        let next = match (self.filter_saved_selected, forward) {
+            (None, true) => 0,
+            (None, false) => count - 1,
+            (Some(i), true) => (i + 1) % count,
+            (Some(i), false) => if i == 0 { count - 1 } else { i - 1 },
+        };
+        self.filter_saved_selected = Some(next);
+        self.filter_input.set(self.config.saved_filters[next].filter.to_query(|id| self.project_name(id)));
     }
 
-    pub fn settings_delete(&mut self) -> Result<()> {
-        match self.settings_section {
-            SettingsSection::Workstreams => {
-                if self.settings_selected < self.config.workstreams.len() {
-                    self.config.workstreams.remove(self.settings_selected);
-                    self.config.save(&self.data_dir)?;
-                    if self.settings_selected >= self.config.workstreams.len() && self.settings_selected > 0 {
-                        self.settings_selected -= 1;
-                    }
-                }
-            }
-            SettingsSection::Goals => {
-                if self.settings_selected < self.config.goals.len() {
-                    self.config.delete_goal(self.settings_selected);
-                    self.config.save(&self.data_dir)?;
-                    if self.settings_selected >= self.config.goals.len() && self.settings_selected > 0 {
-                        self.settings_selected -= 1;
-                    }
-                }
-            }
-            SettingsSection::ApiKeys => {
-                // Delete clears the API key
-                self.config.openai_api_key = None;
-                self.enricher = crate::llm::TaskEnricher::new(None);
-                self.config.save(&self.data_dir)?;
-            }
+    pub fn filter_builder_start_save(&mut self) {
+        if self.filter_builder_preview().is_ok() {
+            self.filter_naming = true;
+            let name = self.filter_saved_selected
+                .and_then(|i| self.config.saved_filters.get(i))
+                .map(|f| f.name.clone())
+                .unwrap_or_default();
+            self.filter_name_input.set(name);
         }
-        Ok(())
     }
 
-    /// Cycle goal priority (only in Goals section)
-    pub fn settings_cycle_priority(&mut self) -> Result<()> {
-        if self.settings_section == SettingsSection::Goals && self.settings_selected < self.config.goals.len() {
-            self.config.cycle_goal_priority(self.settings_selected);
-            self.config.save(&self.data_dir)?;
+    pub fn filter_builder_confirm_save(&mut self) -> Result<()> {
+        let name = self.filter_name_input.trim().to_string();
+        if name.is_empty() {
+            self.filter_naming = false;
+            return Ok(());
         }
+        if let Ok(filter) = self.filter_builder_preview() {
+            self.config.save_filter(SavedFilter { name: name.clone(), filter });
+            self.save_config()?;
+            self.filter_saved_selected = self.config.saved_filters.iter().position(|f| f.name == name);
+        }
+        self.filter_naming = false;
+        self.filter_name_input.clear();
         Ok(())
     }
 
-    /// Toggle goal active state (only in Goals section)
-    pub fn settings_toggle_active(&mut self) -> Result<()> {
-        if self.settings_section == SettingsSection::Goals && self.settings_selected < self.config.goals.len() {
-            self.config.toggle_goal_active(self.settings_selected);
-            self.config.save(&self.data_dir)?;
+    pub fn filter_builder_delete_saved(&mut self) -> Result<()> {
+        if let Some(index) = self.filter_saved_selected.take() {
+            self.config.delete_filter(index);
+            self.save_config()?;
         }
         Ok(())
     }
+}
 
-    /// Cycle through areas when editing a goal
-    pub fn settings_cycle_area(&mut self) {
-        if self.settings_editing && self.settings_section == SettingsSection::Goals {
-            // Cycle through workstream names as areas
-            let areas: Vec<_> = self.config.workstreams.iter().map(|w| w.name.clone()).collect();
-            if areas.is_empty() {
-                return;
-            }
-            let current_idx = areas.iter().position(|a| a == &self.settings_edit_area).unwrap_or(0);
-            let next_idx = (current_idx + 1) % areas.len();
-            self.settings_edit_area = areas[next_idx].clone();
+/// Remove the calls in `pending` that have answered, with their answers.
+/// A call whose thread died without answering is dropped.
+fn take_finished<T>(pending: &mut Vec<(String, Receiver<Result<T>>)>) -> Vec<(String, Result<T>)> {
+    let mut finished = Vec::new();
+    pending.retain(|(key, receiver)| match receiver.try_recv() {
+        Ok(result) => {
+            finished.push((key.clone(), result));
+            false
+        }
+        Err(TryRecvError::Empty) => true,
+        Err(TryRecvError::Disconnected) => false,
+    });
+    finished
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CredentialMode, EmbeddingsConfig, JiraConfig};
+    use tempfile::TempDir;
+
+    fn app_with_tasks(titles: &[&str]) -> (TempDir, App) {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+        for title in titles {
+            let mut task = TaskItem::new(title.to_string(), ItemType::Task);
+            task.frontmatter.tags = vec![title.to_lowercase()];
+            storage.write_task(&mut task).unwrap();
+        }
+        let mut app = App::new(temp_dir.path().to_path_buf()).unwrap();
+        while app.is_loading() {
+            app.poll_loading().unwrap();
+            std::thread::sleep(Duration::from_millis(1));
         }
+        (temp_dir, app)
     }
 
-    pub fn save_config(&self) -> Result<()> {
-        self.config.save(&self.data_dir)
+    fn selected_title(app: &App) -> Option<String> {
+        app.compact_selected_task().map(|t| t.frontmatter.title.clone())
     }
 
-    // === Projects View Methods ===
+    #[test]
+    fn test_history_tab_outside_git() {
+        let (_dir, mut app) = app_with_tasks(&["Alpha"]);
+        app.show_preview = true;
+        app.refresh_task_history();
+        assert!(app.task_history.is_none());
 
-    pub fn open_projects(&mut self) {
-        self.view_mode = ViewMode::Projects;
-        self.projects_selected = 0;
+        app.toggle_preview_history();
+        app.refresh_task_history();
+        let history = app.task_history.as_ref().unwrap();
+        assert_eq!(Some(history.task_id), app.compact_selected_task().map(|t| t.frontmatter.id));
+        assert_eq!(history.revisions, Err("Not a git repository".to_string()));
     }
 
-    pub fn close_projects(&mut self) {
-        self.view_mode = ViewMode::Compact;
+    #[test]
+    fn test_toggle_local_only() {
+        let (_dir, mut app) = app_with_tasks(&["Alpha"]);
+        let task = app.compact_selected_task().cloned().unwrap();
+        let ignore_file = app.storage.data_dir.join(tasktui_core::ignore::IGNORE_FILE);
+        app.toggle_local_only();
+        assert!(app.storage.is_local_only(&task));
+        assert_eq!(std::fs::read_to_string(&ignore_file).unwrap(), format!("/{}.md\n", task.frontmatter.id));
+        app.toggle_local_only();
+        assert!(!app.storage.is_local_only(&task));
+        assert_eq!(std::fs::read_to_string(&ignore_file).unwrap(), "");
     }
 
-    pub fn get_projects(&self) -> Vec<&TaskItem> {
-        self.tasks.iter()
-            .filter(|t| t.is_project())
-            .collect()
-    }
+    #[test]
+    fn test_pulled_changes_reload_or_keep_mine() {
+        let (_dir, mut app) = app_with_tasks(&["Alpha", "Beta"]);
+        let ids: Vec<Uuid> = app.tasks.iter().map(|t| t.frontmatter.id).collect();
+        let paths: Vec<PathBuf> = app.tasks.iter().map(|t| t.file_path.clone()).collect();
 
-    pub fn projects_next(&mut self) {
-        let count = self.get_projects().len();
-        if count > 0 {
-            self.projects_selected = (self.projects_selected + 1) % count;
+        // Another device retitles both tasks, and adds one
+        for id in &ids {
+            let mut theirs = app.storage.parse_file(&app.tasks.iter().find(|t| t.frontmatter.id == *id).unwrap().file_path).unwrap();
+            theirs.frontmatter.title = format!("{} (desktop)", theirs.frontmatter.title);
+            app.storage.write_task(&mut theirs).unwrap();
         }
+        let mut added = TaskItem::new("Gamma".to_string(), ItemType::Task);
+        let added_path = app.storage.write_task(&mut added).unwrap();
+        app.review_pulled(paths.iter().cloned().chain([added_path]).collect());
+        assert_eq!(app.tasks.len(), 3);
+        assert_eq!(app.pull_conflicts.as_ref().unwrap().task_ids, ids);
+
+        let title = |app: &App, id: Uuid| app.tasks.iter().find(|t| t.frontmatter.id == id).unwrap().frontmatter.title.clone();
+        let mine = title(&app, ids[0]);
+        app.resolve_pull_conflict(true).unwrap();
+        assert_eq!(title(&app, ids[0]), mine);
+        assert_eq!(app.storage.parse_file(&paths[0]).unwrap().frontmatter.title, mine);
+        app.resolve_pull_conflict(false).unwrap();
+        assert!(title(&app, ids[1]).ends_with("(desktop)"));
+        assert!(app.pull_conflicts.is_none());
+
+        // Files that match the loaded copies aren't conflicts
+        app.review_pulled(paths);
+        assert!(app.pull_conflicts.is_none());
     }
 
-    pub fn projects_prev(&mut self) {
-        let count = self.get_projects().len();
-        if count > 0 {
-            if self.projects_selected == 0 {
-                self.projects_selected = count - 1;
-            } else {
-                self.projects_selected -= 1;
-            }
-        }
+    #[test]
+    fn test_sync_settings() {
+        let (dir, mut app) = app_with_tasks(&["Alpha"]);
+        let remote_dir = TempDir::new().unwrap();
+        let git = |dir: &std::path::Path, args: &[&str]| {
+            let output = std::process::Command::new("git").args(args).current_dir(dir).output().unwrap();
+            assert!(output.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        };
+        git(remote_dir.path(), &["init", "-q", "--bare"]);
+        let remote = remote_dir.path().to_string_lossy().to_string();
+        assert!(app.storage.git_sync.is_none());
+        app.open_settings();
+        app.settings_toggle_section(false);
+        app.settings_toggle_section(false);
+        assert_eq!(app.settings_section, SettingsSection::Sync);
+
+        // Setting a remote makes the vault a repository
+        app.settings_start_edit();
+        app.settings_edit_text.set(remote.clone());
+        app.settings_confirm_edit().unwrap();
+        assert_eq!(app.remote_url.as_deref(), Some(remote.as_str()));
+        assert!(app.storage.git_sync.is_some());
+        git(dir.path(), &["config", "user.name", "Test"]);
+        git(dir.path(), &["config", "user.email", "test@example.com"]);
+
+        app.settings_next();
+        app.settings_start_edit();
+        app.settings_start_edit();
+        assert_eq!(app.config.git.credentials, CredentialMode::Token);
+        app.settings_next();
+        app.settings_start_edit();
+        app.settings_edit_text.set("s3cret".to_string());
+        app.settings_confirm_edit().unwrap();
+        assert_eq!(app.storage.git_sync.as_ref().unwrap().config.token.as_deref(), Some("s3cret"));
+        let saved = AppConfig::load(dir.path()).unwrap();
+        assert_eq!((saved.git.credentials, saved.git.token.as_deref()), (CredentialMode::Token, Some("s3cret")));
+
+        // The config is pushed with the tasks, but the token stays on this machine
+        let mut task = app.tasks[0].clone();
+        task.frontmatter.title = "Alpha renamed".to_string();
+        app.storage.write_task(&mut task).unwrap();
+        let pushed = git(remote_dir.path(), &["log", "-p", "--all"]);
+        assert!(pushed.contains("credentials: token") && pushed.contains("Alpha renamed"), "{}", pushed);
+        assert!(!pushed.contains("s3cret"), "{}", pushed);
     }
 
-    pub fn show_new_project_dialog(&mut self) {
-        self.show_new_project = true;
-        self.new_project_title.clear();
+    #[test]
+    fn test_backup_settings() {
+        let (_dir, mut app) = app_with_tasks(&["Alpha"]);
+        app.open_settings();
+        app.settings_toggle_section(false);
+        assert_eq!(app.settings_section, SettingsSection::Backups);
+        assert!(app.backups.is_empty());
+
+        // The first row backs up now; the backups follow it, newest first
+        app.settings_start_edit();
+        assert_eq!(app.backups.len(), 1);
+        app.archive_task().unwrap();
+        assert!(app.tasks.iter().all(|t| t.frontmatter.status == Status::Archived));
+
+        app.settings_next();
+        app.settings_start_edit();
+        assert_eq!(app.tasks.iter().map(|t| t.frontmatter.status.clone()).collect::<Vec<_>>(), [Status::Active]);
+        assert_eq!(app.backups.len(), 2);
+        assert_eq!(app.backups[0].reason.as_deref(), Some("restore"));
     }
 
-    pub fn cancel_new_project_dialog(&mut self) {
-        self.show_new_project = false;
-        self.new_project_title.clear();
+    #[test]
+    fn test_search_jumps_to_task() {
+        let (_dir, mut app) = app_with_tasks(&["Alpha", "Beta", "Gamma"]);
+        let mut beta = app.tasks.iter().find(|t| t.frontmatter.title == "Beta").unwrap().clone();
+        beta.body = "Ask about the contract renewal".to_string();
+        app.storage.write_task(&mut beta).unwrap();
+        app.refresh_tasks().unwrap();
+        app.filter_by_tag("alpha");
+
+        app.open_search();
+        app.search_input.set("renewal");
+        let hits = app.search_hits();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].snippet.as_deref(), Some("Ask about the contract renewal"));
+
+        app.confirm_search();
+        assert!(!app.show_search);
+        assert!(app.filter.is_empty());
+        assert_eq!(app.compact_selected_task().unwrap().frontmatter.title, "Beta");
     }
 
-    pub fn create_new_project(&mut self) -> Result<()> {
-        if self.new_project_title.trim().is_empty() {
-            self.show_new_project = false;
-            return Ok(());
+    #[test]
+    fn test_follow_ups_only_on_request() {
+        let (_dir, mut app) = app_with_tasks(&["Send contract"]);
+        let mut task = app.tasks[0].clone();
+        task.frontmatter.status = Status::Waiting;
+        task.frontmatter.delegated_to = Some("Alice".to_string());
+        task.frontmatter.delegated_at = Some(Utc::now() - chrono::Duration::days(10));
+        app.storage.write_task(&mut task).unwrap();
+
+        // Reloading leaves the overdue follow-up for the user to ask for
+        app.refresh_tasks().unwrap();
+        assert_eq!(app.tasks.len(), 1);
+
+        assert_eq!(app.generate_follow_ups().unwrap(), 1);
+        assert!(app.tasks.iter().any(|t| t.frontmatter.title == "Ping Alice about Send contract"));
+        assert_eq!(app.current_status_message().unwrap().text, "Created Ping Alice about Send contract");
+        assert_eq!(app.generate_follow_ups().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_jira_calls_dont_block_marking_done() {
+        let (_dir, mut app) = app_with_tasks(&["Alpha"]);
+        app.tasks[0].frontmatter.jira_key = Some("OPS-1".to_string());
+        // A server that takes connections and never answers
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        app.config.jira = Some(JiraConfig {
+            url: format!("http://{}", listener.local_addr().unwrap()),
+            email: "me@example.com".to_string(),
+            api_token: Some("token".to_string()),
+            transition_on_done: true,
+            done_transition: None,
+        });
+
+        let started = Instant::now();
+        app.mark_task_done().unwrap();
+        app.poll_jira();
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(app.tasks[0].frontmatter.status, Status::Done);
+        assert_eq!(app.jira_completions.len(), 1, "the transition is still in flight");
+    }
+
+    #[test]
+    fn test_selection_follows_task() {
+        let (_dir, mut app) = app_with_tasks(&["Alpha", "Beta", "Gamma"]);
+        let titles: Vec<_> = app.compact_section_tasks(&Status::Active)
+            .iter()
+            .map(|t| t.frontmatter.title.clone())
+            .collect();
+
+        // Marking done keeps the cursor on the task, now in the Done section
+        app.next_task();
+        let target = selected_title(&app);
+        app.mark_task_done().unwrap();
+        assert_eq!(selected_title(&app), target);
+        assert_eq!(app.compact_selected_task().map(|t| t.frontmatter.status.clone()), Some(Status::Done));
+
+        // Reloading from disk keeps it too
+        app.refresh_tasks().unwrap();
+        assert_eq!(selected_title(&app), target);
+
+        // Archiving moves to the task that followed it
+        app.archive_task().unwrap();
+        assert!(selected_title(&app).is_some());
+        assert_ne!(selected_title(&app), target);
+
+        // A filter that still shows the focused task keeps it selected
+        app.compact_select_task(app.tasks.iter().find(|t| t.frontmatter.title == titles[2]).unwrap().frontmatter.id);
+        app.filter_by_tag(&titles[2].to_lowercase());
+        assert_eq!(selected_title(&app).as_deref(), Some(titles[2].as_str()));
+    }
+
+    #[test]
+    fn test_settings_sections() {
+        let (dir, mut app) = app_with_tasks(&[]);
+        app.open_settings();
+        app.settings_toggle_section(false);
+        assert_eq!(app.settings_section, SettingsSection::Backups);
+        app.settings_toggle_section(true);
+        app.settings_toggle_section(true);
+        assert_eq!(app.settings_section, SettingsSection::Goals);
+
+        // With no goals the only row is "Add new"; new goals start in the first workstream
+        app.settings_start_edit();
+        assert_eq!(app.settings_edit_area, "work");
+        app.settings_cycle_area();
+        app.settings_edit_text.set("Run a marathon");
+        app.settings_confirm_edit().unwrap();
+        app.settings_cycle_priority().unwrap();
+        app.settings_toggle_active().unwrap();
+        let saved = AppConfig::load(dir.path()).unwrap();
+        let goal = &saved.goals[0];
+        assert_eq!((goal.description.as_str(), goal.area.as_str(), goal.priority, goal.active), ("Run a marathon", "personal", 4, false));
+
+        app.settings_toggle_section(true);
+        app.settings_start_edit();
+        app.settings_edit_text.set("sk-test-1234567890");
+        app.settings_confirm_edit().unwrap();
+        assert!(app.enricher.is_available());
+        // Clearing the key keeps usage metering
+        app.settings_delete().unwrap();
+        assert!(app.config.openai_api_key.is_none() && !app.enricher.is_available());
+        assert!(app.llm_usage().is_some());
+
+        app.settings_toggle_section(false);
+        app.settings_delete().unwrap();
+        assert!(app.config.goals.is_empty());
+    }
+
+    #[test]
+    fn test_edit_custom_fields() {
+        let (dir, mut app) = app_with_tasks(&["Refund"]);
+        let mut project = TaskItem::new_project("Support".to_string());
+        project.frontmatter.fields = vec![
+            FieldDef { name: "severity".to_string(), kind: FieldType::Choice, values: vec!["low".to_string(), "high".to_string()] },
+            FieldDef { name: "points".to_string(), kind: FieldType::Number, values: Vec::new() },
+        ];
+        app.storage.write_task(&mut project).unwrap();
+        let task_id = app.tasks[0].frontmatter.id;
+        app.tasks[0].frontmatter.parent_goal_id = Some(project.frontmatter.id);
+        app.storage.write_task(&mut app.tasks[0]).unwrap();
+        app.refresh_tasks().unwrap();
+
+        app.show_fields_dialog(task_id);
+        assert_eq!(app.field_rows().len(), 2);
+        app.fields_cycle(false).unwrap();
+        app.fields_select(true);
+        app.fields_start_edit();
+        app.fields_input.set("lots");
+        app.fields_confirm_edit().unwrap();
+        // An invalid number is refused and left in the input to fix
+        assert!(app.fields_editing);
+        app.fields_input.set("3");
+        app.fields_confirm_edit().unwrap();
+        app.close_fields_dialog();
+
+        let saved = app.storage.parse_file(&dir.path().join(format!("{}.md", task_id))).unwrap();
+        let custom: Vec<_> = saved.frontmatter.custom.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
+        assert_eq!(custom, [("points", "3"), ("severity", "high")]);
+
+        app.show_fields_dialog(task_id);
+        app.fields_clear().unwrap();
+        assert!(!app.task_by_id(task_id).unwrap().frontmatter.custom.contains_key("severity"));
+    }
+
+    #[test]
+    fn test_archive_project_moves_open_tasks() {
+        let (_dir, mut app) = app_with_tasks(&["Open", "Finished"]);
+        let mut old = TaskItem::new_project("Old".to_string());
+        let mut new = TaskItem::new_project("New".to_string());
+        app.storage.write_task(&mut old).unwrap();
+        app.storage.write_task(&mut new).unwrap();
+        for task in &mut app.tasks {
+            task.frontmatter.parent_goal_id = Some(old.frontmatter.id);
+            if task.frontmatter.title == "Finished" {
+                task.set_status(Status::Done);
+            }
         }
+        app.tasks.extend([old.clone(), new.clone()]);
 
-        let mut project = TaskItem::new_project(self.new_project_title.trim().to_string());
-        self.storage.write_task(&mut project)?;
-        self.tasks.push(project);
-        self.show_new_project = false;
-        self.new_project_title.clear();
+        app.archive_project(old.frontmatter.id).unwrap();
+        assert!(app.project_archive.is_some());
+        app.project_archive_start_move();
+        assert_eq!(app.archive_targets().len(), 1);
+        app.confirm_project_archive_move().unwrap();
+        assert!(app.project_archive.is_none());
 
-        // Select the new project
-        self.projects_selected = self.get_projects().len().saturating_sub(1);
-        Ok(())
+        let parent = |app: &App, title: &str| app.tasks.iter().find(|t| t.frontmatter.title == title).unwrap().frontmatter.parent_goal_id;
+        assert_eq!(parent(&app, "Open"), Some(new.frontmatter.id));
+        assert_eq!(parent(&app, "Finished"), Some(old.frontmatter.id));
+        assert!(models::orphans(&app.storage.load_all_tasks().unwrap()).is_empty());
+
+        // With no other open project left, the tasks can only be archived or detached
+        app.archive_project(new.frontmatter.id).unwrap();
+        app.project_archive_start_move();
+        assert_eq!(app.project_archive.as_ref().unwrap().moving, None);
+        app.confirm_project_archive(ChildAction::Detach).unwrap();
+        assert_eq!(parent(&app, "Open"), None);
+        assert!(app.tasks.iter().all(|t| !t.is_project() || t.frontmatter.status == Status::Archived));
     }
 
-    pub fn open_project_gantt(&mut self) {
-        let projects = self.get_projects();
-        if let Some(project) = projects.get(self.projects_selected) {
-            self.current_project_id = Some(project.frontmatter.id);
-            self.view_mode = ViewMode::ProjectGantt;
-            self.gantt_selected = 0;
-            self.gantt_scroll_offset = 0;
+    #[test]
+    fn test_semantic_search_runs_in_background() {
+        let (_dir, mut app) = app_with_tasks(&["Alpha"]);
+        app.open_search();
+        app.toggle_search_mode();
+        assert!(!app.search_semantic);
+        assert!(app.current_status_message().unwrap().text.contains("embeddings"));
+
+        // An API that takes connections and never answers
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        app.config.embeddings = Some(EmbeddingsConfig {
+            url: format!("http://{}/v1/embeddings", listener.local_addr().unwrap()),
+            model: "test".to_string(),
+            api_key: None,
+        });
+        app.toggle_search_mode();
+        app.search_input.set("the contract thing");
+        let started = Instant::now();
+        app.confirm_search();
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(app.show_search && app.is_searching());
+        assert!(app.search_hits().is_empty());
+    }
+
+    #[test]
+    fn test_workstream_rename_offers_retag() {
+        let (_dir, mut app) = app_with_tasks(&["Plan", "Ship"]);
+        for task in &mut app.tasks {
+            task.frontmatter.tags = vec!["work".to_string()];
         }
+        app.settings_selected = app.config.workstreams.iter().position(|ws| ws.name == "work").unwrap();
+        app.settings_start_edit();
+        app.settings_edit_text.set("Job");
+        app.settings_confirm_edit().unwrap();
+        assert_eq!(app.retag_pending, Some(("work".to_string(), "job".to_string())));
+
+        app.confirm_retag().unwrap();
+        assert!(app.retag_pending.is_none());
+        assert!(app.tasks.iter().all(|t| t.frontmatter.tags == ["job"]));
+        let on_disk = app.storage.load_all_tasks().unwrap();
+        assert!(on_disk.iter().filter(|t| t.has_tag("job")).count() == 2);
     }
 
-    pub fn close_project_gantt(&mut self) {
-        self.view_mode = ViewMode::Projects;
-        self.current_project_id = None;
+    #[test]
+    fn test_new_task_takes_workstream_filter_tags() {
+        let (_dir, mut app) = app_with_tasks(&["Work", "Personal"]);
+        app.filter_by_tag("work");
+        app.show_new_task_dialog();
+        assert_eq!(app.new_task_default_tags, ["work"]);
+        app.new_task_title.set("Draft plan #q3");
+        app.create_new_task().unwrap();
+        let task = app.tasks.iter().find(|t| t.frontmatter.title == "Draft plan").unwrap();
+        assert_eq!(task.frontmatter.tags, ["q3", "work"]);
+        assert!(app.new_task_default_tags.is_empty());
+
+        // Backspace on an empty input removes the chip
+        app.show_new_task_dialog();
+        app.new_task_remove_chip();
+        app.new_task_title.set("Untagged");
+        app.create_new_task().unwrap();
+        assert!(app.tasks.iter().find(|t| t.frontmatter.title == "Untagged").unwrap().frontmatter.tags.is_empty());
+
+        // Non-workstream tags aren't defaults
+        app.filter_by_tag("personal");
+        app.config.workstreams.retain(|ws| ws.name != "personal");
+        app.show_new_task_dialog();
+        assert!(app.new_task_default_tags.is_empty());
     }
 
-    pub fn get_current_project(&self) -> Option<&TaskItem> {
-        let project_id = self.current_project_id?;
-        self.tasks.iter().find(|t| t.frontmatter.id == project_id)
+    #[test]
+    fn test_priority_review_applies_accepted_changes() {
+        let (dir, mut app) = app_with_tasks(&["Launch", "Chores", "Taxes"]);
+        let change = |app: &App, title: &str, to: Priority| {
+            let task = app.tasks.iter().find(|t| t.frontmatter.title == title).unwrap();
+            PriorityChange {
+                id: task.frontmatter.id,
+                code: task.code(),
+                title: title.to_string(),
+                from: task.frontmatter.priority.clone(),
+                to,
+                reason: "Because".to_string(),
+            }
+        };
+        let changes = vec![change(&app, "Launch", Priority::P0), change(&app, "Chores", Priority::P4), change(&app, "Taxes", Priority::P1)];
+
+        // Without a key the review doesn't open
+        app.open_priority_review();
+        assert!(app.priority_review.is_none());
+
+        app.start_priority_review(changes.clone());
+        app.priority_review_toggle();
+        app.priority_review_select(true);
+        app.priority_review_select(true);
+        app.priority_review_toggle();
+        app.apply_priority_review().unwrap();
+        assert!(app.priority_review.is_none());
+        let priority = |app: &App, title: &str| app.tasks.iter().find(|t| t.frontmatter.title == title).unwrap().frontmatter.priority.clone();
+        assert_eq!(priority(&app, "Launch"), Priority::P0);
+        assert_eq!(priority(&app, "Chores"), Priority::P2);
+        assert_eq!(priority(&app, "Taxes"), Priority::P1);
+        let reloaded = Storage::new(dir.path().to_path_buf()).unwrap().load_all_tasks().unwrap();
+        assert_eq!(reloaded.iter().find(|t| t.frontmatter.title == "Launch").unwrap().frontmatter.priority, Priority::P0);
+
+        // `a` accepts everything, Esc throws it all away
+        app.start_priority_review(changes);
+        app.priority_review_toggle_all();
+        assert!(app.priority_review.as_ref().unwrap().accepted.iter().all(|a| *a));
+        app.cancel_priority_review();
+        assert_eq!(priority(&app, "Chores"), Priority::P2);
     }
 
-    pub fn get_project_tasks(&self) -> Vec<&TaskItem> {
-        let Some(project_id) = self.current_project_id else {
-            return Vec::new();
+    #[test]
+    fn test_multi_line_paste_creates_a_task_per_line() {
+        let (_dir, mut app) = app_with_tasks(&["Existing"]);
+        app.show_new_task_dialog();
+        app.new_task_title.set("Book flights");
+        app.paste("Pack bags #travel\r\n\n  Water plants !p1  \n+nowhere Lost line\n");
+        assert_eq!(app.new_task_batch, ["Book flights", "Pack bags #travel", "Water plants !p1", "+nowhere Lost line"]);
+        assert!(app.active_input().is_none());
+
+        // Esc goes back to the input, leaving it as typed
+        app.cancel_new_task_batch();
+        assert_eq!(&*app.new_task_title, "Book flights");
+        app.paste("Pack bags #travel\nWater plants !p1\n+nowhere Lost line");
+
+        app.create_new_task_batch().unwrap();
+        assert!(!app.show_new_task && app.new_task_batch.is_empty());
+        let pack = app.tasks.iter().find(|t| t.frontmatter.title == "Pack bags").unwrap();
+        assert_eq!(pack.frontmatter.tags, ["travel"]);
+        let water = app.tasks.iter().find(|t| t.frontmatter.title == "Water plants").unwrap();
+        assert_eq!(water.frontmatter.priority, Priority::P1);
+        assert!(app.tasks.iter().any(|t| t.frontmatter.title == "Book flights"));
+        assert_eq!(app.tasks.len(), 4);
+
+        // A single line pastes into the input as before
+        app.show_new_task_dialog();
+        app.paste("Just one\n");
+        assert!(app.new_task_batch.is_empty());
+        assert_eq!(app.new_task_title.trim(), "Just one");
+    }
+
+    #[test]
+    fn test_shutdown_review() {
+        let (dir, mut app) = app_with_tasks(&["Draft", "Review", "Ship"]);
+        app.tasks.iter_mut().find(|t| t.frontmatter.title == "Ship").unwrap().set_status(Status::Done);
+        app.open_shutdown();
+        let titles = |tasks: Vec<&TaskItem>| tasks.iter().map(|t| t.frontmatter.title.clone()).collect::<Vec<_>>();
+        assert_eq!(titles(app.shutdown_completed()), ["Ship"]);
+        assert_eq!(titles(app.shutdown_active()).len(), 2);
+
+        let first = app.shutdown_current().unwrap().frontmatter.title.clone();
+        app.shutdown_demote().unwrap();
+        app.shutdown_start_reschedule();
+        app.shutdown_input.set("2030-01-15");
+        app.shutdown_confirm_reschedule().unwrap();
+        assert!(app.shutdown_active().is_empty());
+        let status = |app: &App, title: &str| app.tasks.iter().find(|t| t.frontmatter.title == title).unwrap().frontmatter.status.clone();
+        assert_eq!(status(&app, &first), Status::Next);
+
+        app.shutdown_journal = Some("Shipped it.".to_string());
+        app.finish_shutdown().unwrap();
+        assert_eq!(app.view_mode, ViewMode::Compact);
+        let entry = std::fs::read_to_string(journal::journal_path(dir.path(), models::today())).unwrap();
+        assert!(entry.contains("Shipped it.\n\n### Completed (1)\n\n- Ship\n"));
+        assert!(entry.contains("### Carried over (0)"));
+    }
+
+    #[test]
+    fn test_habit_checked_off() {
+        let (_dir, mut app) = app_with_tasks(&["Stretch"]);
+        app.tasks[0].frontmatter.habit = Some(models::Habit::Daily);
+        assert_eq!(app.streak_badge(&app.tasks[0]).unwrap().content, "🔥0");
+        app.mark_task_done().unwrap();
+        assert_eq!(app.tasks[0].frontmatter.status, Status::Active);
+        assert_eq!(app.status_message.as_ref().map(|m| m.text.as_str()), Some("Checked off, streak 1"));
+        assert_eq!(app.streak_badge(&app.tasks[0]).unwrap().content, "🔥1");
+    }
+
+    #[test]
+    fn test_holiday_warning() {
+        let (_dir, mut app) = app_with_tasks(&[]);
+        app.config.calendar.holidays = vec![crate::config::Holiday {
+            name: "Summer break".to_string(),
+            from: chrono::NaiveDate::from_ymd_opt(2030, 8, 1).unwrap(),
+            to: chrono::NaiveDate::from_ymd_opt(2030, 8, 14),
+        }];
+        let hint = |input: &str| app.due_prompt_hint(input).to_string();
+        assert_eq!(hint("2030-08-05T09:00"), " ⚠ Summer break");
+        assert!(hint("2030-08-15").contains("YYYY-MM-DD"));
+        assert!(hint("next week").contains("YYYY-MM-DD"));
+    }
+
+    #[test]
+    fn test_gantt_shift_dates() {
+        let (_dir, mut app) = app_with_tasks(&["Launch", "Draft"]);
+        let project_id = app.tasks.iter().find(|t| t.frontmatter.title == "Launch").unwrap().frontmatter.id;
+        let friday = chrono::NaiveDate::from_ymd_opt(2025, 3, 7).unwrap();
+        let task = app.tasks.iter_mut().find(|t| t.frontmatter.title == "Draft").unwrap();
+        task.frontmatter.parent_goal_id = Some(project_id);
+        task.frontmatter.start_date = Some(friday);
+        task.frontmatter.due_date = Some(Due::on(friday));
+        app.current_project_id = Some(project_id);
+        app.view_mode = ViewMode::ProjectGantt;
+
+        let dates = |app: &App| {
+            let fm = &app.get_project_tasks()[0].frontmatter;
+            (fm.start_date.unwrap().to_string(), fm.due_date.unwrap().to_string())
         };
-        self.tasks.iter()
-            .filter(|t| t.frontmatter.parent_goal_id == Some(project_id))
-            .collect()
+        app.gantt_shift_dates(1).unwrap();
+        assert_eq!(dates(&app), ("2025-03-08".to_string(), "2025-03-08".to_string()));
+
+        // Skipping non-working days, a day back from Saturday is Friday and
+        // a day on from Friday is Monday
+        app.config.calendar.skip_non_working = true;
+        app.gantt_shift_dates(-1).unwrap();
+        app.gantt_shift_dates(1).unwrap();
+        assert_eq!(dates(&app), ("2025-03-10".to_string(), "2025-03-10".to_string()));
     }
 
-    pub fn gantt_next(&mut self) {
-        let count = self.get_project_tasks().len();
-        if count > 0 {
-            self.gantt_selected = (self.gantt_selected + 1) % count;
+    #[test]
+    fn test_stale_review() {
+        let (_dir, mut app) = app_with_tasks(&["Fresh", "Old", "Older"]);
+        for (title, days) in [("Old", 40), ("Older", 50)] {
+            let task = app.tasks.iter_mut().find(|t| t.frontmatter.title == title).unwrap();
+            task.frontmatter.updated_at = Some(Utc::now() - chrono::Duration::days(days));
         }
+        app.open_stale();
+        let titles = |app: &App| app.stale_tasks().iter().map(|t| t.frontmatter.title.clone()).collect::<Vec<_>>();
+        assert_eq!(titles(&app), ["Older", "Old"]);
+
+        app.stale_bump().unwrap();
+        assert_eq!(titles(&app), ["Old"]);
+
+        app.stale_start_reschedule();
+        app.stale_input.set("2030-01-15");
+        app.stale_confirm_reschedule().unwrap();
+        assert!(!app.stale_rescheduling);
+        assert!(titles(&app).is_empty());
+        let old = app.tasks.iter().find(|t| t.frontmatter.title == "Old").unwrap();
+        assert_eq!(old.frontmatter.due_date.map(|d| d.to_string()).as_deref(), Some("2030-01-15"));
+
+        // Someday tasks never go stale
+        let task = app.tasks.iter_mut().find(|t| t.frontmatter.title == "Fresh").unwrap();
+        task.frontmatter.updated_at = Some(Utc::now() - chrono::Duration::days(90));
+        app.stale_someday().unwrap();
+        assert!(app.tasks.iter().find(|t| t.frontmatter.title == "Fresh").unwrap().has_tag("someday"));
+        assert!(titles(&app).is_empty());
     }
 
-    pub fn gantt_prev(&mut self) {
-        let count = self.get_project_tasks().len();
-        if count > 0 {
-            if self.gantt_selected == 0 {
-                self.gantt_selected = count - 1;
-            } else {
-                self.gantt_selected -= 1;
-            }
+    #[test]
+    fn test_sort_recent() {
+        let (_dir, mut app) = app_with_tasks(&["First", "Second"]);
+        let titles = |app: &App| app.compact_section_tasks(&Status::Active)
+            .iter()
+            .map(|t| t.frontmatter.title.clone())
+            .collect::<Vec<_>>();
+        app.tasks.iter_mut().find(|t| t.frontmatter.title == "First").unwrap().frontmatter.due_date =
+            chrono::NaiveDate::from_ymd_opt(2030, 1, 1).map(Due::on);
+        assert_eq!(titles(&app), ["First", "Second"]);
+
+        let second = app.tasks.iter_mut().find(|t| t.frontmatter.title == "Second").unwrap();
+        second.frontmatter.updated_at = Some(Utc::now() + chrono::Duration::minutes(1));
+        app.toggle_sort_recent();
+        assert_eq!(titles(&app), ["Second", "First"]);
+    }
+
+    #[test]
+    fn test_quick_filters() {
+        let (_dir, mut app) = app_with_tasks(&["Work", "Home"]);
+        let today = models::today();
+        for task in app.tasks.iter_mut() {
+            task.frontmatter.priority = if task.frontmatter.title == "Work" { Priority::P1 } else { Priority::P2 };
+            task.frontmatter.due_date = Some(Due::on(today + chrono::Duration::days(2)));
         }
+        let titles = |app: &App| app.filtered_tasks().iter().map(|t| t.frontmatter.title.clone()).collect::<BTreeSet<_>>();
+
+        app.cycle_priority_filter();
+        assert_eq!(titles(&app), BTreeSet::from(["Work".to_string()]));
+        app.cycle_priority_filter();
+        assert_eq!(titles(&app).len(), 2);
+
+        // Combines with a workstream tag, which keeps the quick filters
+        app.toggle_due_soon_filter();
+        app.filter_by_tag("home");
+        assert_eq!(app.filter_query(), "#home priority:p0,p1,p2 due:today..today+3");
+        assert_eq!(titles(&app), BTreeSet::from(["Home".to_string()]));
+        app.tasks.iter_mut().for_each(|t| t.frontmatter.due_date = Some(Due::on(today + chrono::Duration::days(4))));
+        assert!(titles(&app).is_empty());
+
+        app.toggle_due_soon_filter();
+        app.cycle_priority_filter();
+        assert_eq!(app.filter_query(), "#home");
     }
 
-    pub fn gantt_scroll_left(&mut self) {
-        self.gantt_scroll_offset = self.gantt_scroll_offset.saturating_sub(7); // Scroll by ~1 week
+    #[test]
+    fn test_kanban_sort_and_reorder() {
+        let (dir, mut app) = app_with_tasks(&["Alpha", "Beta", "Gamma"]);
+        let titles = |app: &App| app.kanban_column_tasks().iter().map(|t| t.frontmatter.title.clone()).collect::<Vec<_>>();
+        let loaded = titles(&app);
+        let set = |app: &mut App, title: &str, priority: Priority, due: i64| {
+            let task = app.tasks.iter_mut().find(|t| t.frontmatter.title == title).unwrap();
+            task.frontmatter.priority = priority;
+            task.frontmatter.due_date = Some(Due::on(models::today() + chrono::Duration::days(due)));
+        };
+        set(&mut app, "Alpha", Priority::P3, 1);
+        set(&mut app, "Beta", Priority::P1, 5);
+        set(&mut app, "Gamma", Priority::P1, 2);
+
+        app.view_mode = ViewMode::Kanban;
+        app.kanban_cycle_sort();
+        assert_eq!(titles(&app), ["Gamma", "Beta", "Alpha"]);
+        app.kanban_cycle_sort();
+        assert_eq!(titles(&app), ["Alpha", "Gamma", "Beta"]);
+        assert_eq!(app.column_sort("ACTIVE"), KanbanSort::Due);
+
+        // Moving a card switches to manual order, starting from the shown one
+        app.kanban_row = 0;
+        app.kanban_reorder(true);
+        assert_eq!(titles(&app), ["Gamma", "Alpha", "Beta"]);
+        assert_eq!(app.kanban_row, 1);
+        assert_eq!(app.column_sort("ACTIVE"), KanbanSort::Manual);
+        app.kanban_reorder(true);
+        app.kanban_reorder(true);
+        assert_eq!(titles(&app), ["Gamma", "Beta", "Alpha"]);
+
+        app.save_ui_state().unwrap();
+        let mut reopened = App::new(dir.path().to_path_buf()).unwrap();
+        while reopened.is_loading() {
+            reopened.poll_loading().unwrap();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(titles(&reopened), ["Gamma", "Beta", "Alpha"]);
+        reopened.kanban_order.clear();
+        assert_eq!(titles(&reopened), loaded);
     }
 
-    pub fn gantt_scroll_right(&mut self) {
-        self.gantt_scroll_offset += 7;
+    #[test]
+    fn test_wip_limit_asks_before_moving() {
+        let (dir, mut app) = app_with_tasks(&["Alpha", "Beta"]);
+        let id = |app: &App, title: &str| app.tasks.iter().find(|t| t.frontmatter.title == title).unwrap().frontmatter.id;
+        let status = |app: &App, title: &str| app.tasks.iter().find(|t| t.frontmatter.title == title).unwrap().frontmatter.status.clone();
+        app.move_task_to_status(id(&app, "Beta"), Status::Next).unwrap();
+        app.config.wip_limits.insert(Status::Next, 1);
+        assert_eq!(app.wip_usage(&Status::Next), Some((1, 1)));
+
+        // Moving into the full column waits for an answer
+        app.move_task_to_status(id(&app, "Alpha"), Status::Next).unwrap();
+        assert!(app.wip_pending.is_some());
+        assert_eq!(status(&app, "Alpha"), Status::Active);
+        app.cancel_wip_move();
+        assert!(app.wip_pending.is_none());
+        assert_eq!(status(&app, "Alpha"), Status::Active);
+
+        app.move_task_to_status(id(&app, "Alpha"), Status::Next).unwrap();
+        app.confirm_wip_move().unwrap();
+        assert!(app.wip_pending.is_none());
+        assert_eq!(status(&app, "Alpha"), Status::Next);
+        assert_eq!(app.wip_usage(&Status::Next), Some((2, 1)));
+        let saved = Storage::new(dir.path().to_path_buf()).unwrap().load_all_tasks().unwrap();
+        assert!(saved.iter().all(|t| t.frontmatter.status == Status::Next));
+
+        // A move within the column isn't held up
+        app.move_task_to_status(id(&app, "Alpha"), Status::Next).unwrap();
+        assert!(app.wip_pending.is_none());
     }
 
-    /// Calculate project progress based on completed tasks
-    pub fn calculate_project_progress(&self, project_id: Uuid) -> u8 {
-        let tasks: Vec<_> = self.tasks.iter()
-            .filter(|t| t.frontmatter.parent_goal_id == Some(project_id))
-            .collect();
+    #[test]
+    fn test_done_column_prunes_older() {
+        let (_dir, mut app) = app_with_tasks(&["Old", "Recent", "Open"]);
+        for (title, days_ago) in [("Old", 10), ("Recent", 2)] {
+            let task = app.tasks.iter_mut().find(|t| t.frontmatter.title == title).unwrap();
+            task.set_status(Status::Done);
+            task.frontmatter.completed_at = Some(Utc::now() - chrono::Duration::days(days_ago));
+        }
+        app.view_mode = ViewMode::Kanban;
+        app.kanban_column = 3;
+        let titles = |app: &App| app.kanban_column_tasks().iter().map(|t| t.frontmatter.title.clone()).collect::<Vec<_>>();
+        assert_eq!(titles(&app), ["Recent"]);
+        assert_eq!(app.kanban_older_count(3), 1);
 
-        if tasks.is_empty() {
-            return 0;
+        // The "+1 older" row follows the cards and expands them
+        app.kanban_move_down();
+        assert_eq!(app.kanban_row, 1);
+        assert!(app.kanban_selected_task().is_none());
+        app.kanban_enter();
+        assert_eq!(titles(&app).len(), 2);
+        assert_eq!(app.kanban_row, 1);
+        app.kanban_move_down();
+        app.kanban_enter();
+        assert_eq!(titles(&app), ["Recent"]);
+        assert_eq!(app.kanban_row, 1);
+
+        app.config.done_window_days = 0;
+        assert_eq!(app.kanban_older_count(3), 0);
+        assert_eq!(titles(&app).len(), 2);
+    }
+
+    #[test]
+    fn test_kanban_split_follows_card() {
+        let (_dir, mut app) = app_with_tasks(&["Loose end", "Launch"]);
+        let project = TaskItem::new("Website".to_string(), ItemType::Project);
+        let project_id = project.frontmatter.id;
+        app.tasks.push(project);
+        app.tasks.iter_mut().find(|t| t.frontmatter.title == "Launch").unwrap().frontmatter.parent_goal_id = Some(project_id);
+        app.view_mode = ViewMode::Kanban;
+        app.switch_pane_focus();
+        assert!(!app.right_pane_focused());
+
+        app.toggle_kanban_split();
+        let launch = app.kanban_column_tasks().iter().position(|t| t.frontmatter.title == "Launch").unwrap();
+        app.kanban_row = launch;
+        app.sync_split_project();
+        assert_eq!(app.current_project_id, Some(project_id));
+
+        // A card outside any project keeps the schedule shown
+        app.kanban_row = 1 - launch;
+        app.sync_split_project();
+        assert_eq!(app.current_project_id, Some(project_id));
+
+        app.switch_pane_focus();
+        assert!(app.right_pane_focused());
+        app.toggle_kanban_split();
+        assert!(!app.right_pane_focused());
+    }
+
+    #[test]
+    fn test_workstream_dashboard() {
+        let (_dir, mut app) = app_with_tasks(&["Work", "Personal"]);
+        app.filter_by_tag("personal");
+        app.open_dashboard();
+        assert_eq!(app.view_mode, ViewMode::Dashboard);
+        assert_eq!(app.dashboard_stream, 1);
+        app.dashboard_cycle(true);
+        assert_eq!(app.dashboard_stream, 0);
+        app.dashboard_select_key('2');
+        assert_eq!(app.dashboard_stream, 1);
+        app.dashboard_cycle(false);
+
+        app.dashboard_apply();
+        assert_eq!(app.view_mode, ViewMode::Compact);
+        assert!(app.filter.is_single_tag("work"));
+    }
+
+    #[test]
+    fn test_related_tasks() {
+        let (_dir, mut app) = app_with_tasks(&["Renew passport", "Passport appointment", "Groceries"]);
+        let renew = app.tasks.iter().find(|t| t.frontmatter.title == "Renew passport").unwrap().clone();
+        let titles = |app: &App| app.related_tasks(&renew).iter().map(|t| t.frontmatter.title.clone()).collect::<Vec<_>>();
+        assert_eq!(titles(&app), ["Passport appointment"]);
+
+        // Finished tasks drop out without rescoring
+        app.tasks.iter_mut().find(|t| t.frontmatter.title == "Passport appointment").unwrap().set_status(Status::Done);
+        assert!(titles(&app).is_empty());
+    }
+
+    #[test]
+    fn test_briefing_once_a_day() {
+        let (dir, mut app) = app_with_tasks(&["Pay rent", "Read"]);
+        let yesterday = models::today() - chrono::Duration::days(1);
+        app.tasks[0].frontmatter.due_date = Some(Due::on(yesterday));
+        app.storage.write_task(&mut app.tasks[0]).unwrap();
+        app.briefed_on = Some(yesterday);
+        app.open_briefing();
+        let briefing = app.briefing.as_ref().unwrap();
+        assert_eq!(briefing.overdue.len(), 1);
+        assert_eq!(app.briefed_on, Some(models::today()));
+
+        // Shown once: a restart the same day skips it
+        app.save_ui_state().unwrap();
+        let mut reopened = App::new(dir.path().to_path_buf()).unwrap();
+        while reopened.is_loading() {
+            reopened.poll_loading().unwrap();
+            std::thread::sleep(Duration::from_millis(1));
         }
+        assert!(reopened.briefing.is_none());
 
-        let done = tasks.iter()
-            .filter(|t| matches!(t.frontmatter.status, Status::Done | Status::Archived))
-            .count();
+        app.briefing = None;
+        app.briefed_on = None;
+        app.config.briefing = false;
+        app.open_briefing();
+        assert!(app.briefing.is_none());
+    }
+
+    #[test]
+    fn test_ui_state_restored() {
+        let (dir, mut app) = app_with_tasks(&["Alpha", "Beta", "Gamma"]);
+        app.toggle_view();
+        assert_eq!(app.view_mode, ViewMode::Kanban);
+        app.cycle_priority_filter();
+        app.cycle_priority_filter();
+        let target = app.tasks.iter().find(|t| t.frontmatter.title == "Beta").unwrap().frontmatter.id;
+        assert!(app.view_select_task(target));
+        let column = app.kanban_column;
+        app.save_ui_state().unwrap();
 
-        ((done as f64 / tasks.len() as f64) * 100.0) as u8
+        let reopen = || {
+            let mut app = App::new(dir.path().to_path_buf()).unwrap();
+            while app.is_loading() {
+                app.poll_loading().unwrap();
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            app
+        };
+        let reopened = reopen();
+        assert_eq!(reopened.view_mode, ViewMode::Kanban);
+        assert_eq!(reopened.filter, app.filter);
+        assert_eq!(reopened.kanban_column, column);
+        assert_eq!(reopened.kanban_selected_task().map(|t| t.frontmatter.id), Some(target));
+
+        // A Gantt view whose project is gone falls back to the project list
+        let state = UiState { view: Some(ViewMode::ProjectGantt), project_id: Some(Uuid::new_v4()), ..UiState::default() };
+        state.save(dir.path()).unwrap();
+        assert_eq!(reopen().view_mode, ViewMode::Projects);
     }
 
-    /// Count tasks by status for a project
-    pub fn project_task_counts(&self, project_id: Uuid) -> (usize, usize, usize) {
-        let tasks: Vec<_> = self.tasks.iter()
-            .filter(|t| t.frontmatter.parent_goal_id == Some(project_id))
-            .collect();
+    #[test]
+    fn test_drafts_recovered() {
+        let (dir, mut app) = app_with_tasks(&["Alpha"]);
+        app.show_new_task_dialog();
+        app.new_task_title.set("Half-typed task");
+        app.save_ui_state().unwrap();
 
-        let total = tasks.len();
-        let done = tasks.iter()
-            .filter(|t| matches!(t.frontmatter.status, Status::Done | Status::Archived))
-            .count();
-        let active = tasks.iter()
-            .filter(|t| matches!(t.frontmatter.status, Status::Active | Status::Next))
-            .count();
+        let mut reopened = App::new(dir.path().to_path_buf()).unwrap();
+        assert!(reopened.show_new_task);
+        assert_eq!(&*reopened.new_task_title, "Half-typed task");
 
-        (total, done, active)
+        // Closed or blank dialogs leave nothing to recover
+        reopened.cancel_new_task_dialog();
+        let task_id = app.tasks[0].frontmatter.id;
+        reopened.show_note_dialog(task_id);
+        reopened.note_input.set("   ");
+        reopened.save_ui_state().unwrap();
+        assert!(UiState::load(dir.path()).drafts.is_empty());
+
+        reopened.note_input.set("Called back");
+        reopened.save_ui_state().unwrap();
+        let reopened = App::new(dir.path().to_path_buf()).unwrap();
+        assert!(reopened.show_note);
+        assert_eq!(reopened.note_task_id, Some(task_id));
+        assert_eq!(&*reopened.note_input, "Called back");
     }
 }
@@ -10,6 +10,11 @@ pub struct Theme {
     pub text_dim: Color,
     pub border: Color,
     pub border_focused: Color,
+    pub warning: Color,
+    /// Background of non-working days in the Gantt header
+    pub weekend: Color,
+    /// Priority badge backgrounds, P0 to P4
+    pub priority: [Color; 5],
 }
 
 pub const THEME: Theme = Theme {
@@ -21,6 +26,15 @@ pub const THEME: Theme = Theme {
     text_dim: Color::Rgb(128, 128, 128),     // Gray
     border: Color::Rgb(64, 64, 64),          // Dark gray
     border_focused: Color::Rgb(255, 215, 0), // Gold
+    warning: Color::Rgb(255, 85, 85),        // Red
+    weekend: Color::Rgb(32, 32, 40),         // Near-black slate
+    priority: [
+        Color::Rgb(255, 85, 85),   // P0 red
+        Color::Rgb(255, 140, 0),   // P1 orange
+        Color::Rgb(255, 215, 0),   // P2 gold
+        Color::Rgb(110, 150, 190), // P3 steel blue
+        Color::Rgb(96, 96, 96),    // P4 gray
+    ],
 };
 
 impl Theme {
@@ -59,6 +73,26 @@ impl Theme {
             .add_modifier(Modifier::BOLD)
     }
 
+    pub fn warning_style(&self) -> Style {
+        Style::default()
+            .fg(self.warning)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    pub fn weekend_style(&self) -> Style {
+        Style::default()
+            .fg(self.text_dim)
+            .bg(self.weekend)
+    }
+
+    /// A priority badge: dark text on the level's color
+    pub fn badge_style(&self, color: Color) -> Style {
+        Style::default()
+            .fg(self.background)
+            .bg(color)
+            .add_modifier(Modifier::BOLD)
+    }
+
     pub fn tag_style(&self) -> Style {
         Style::default().fg(self.secondary)
     }
@@ -1,15 +1,19 @@
-use super::{app::App, THEME};
-use crate::models::Status;
+use super::{app::{App, CompactRow, TaskHistory, ViewMode, DONE_PAGE_SIZE}, panes, text, virtual_list, THEME};
+use crate::activity::{self, NOTES_HEADING};
+use crate::config::TitleMode;
+use crate::history::{Change, Revision};
+use crate::links;
+use crate::models::{self, Due, Status, TaskItem};
+use crate::validate;
+use chrono::{DateTime, Local, NaiveDateTime, Utc};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
     Frame,
 };
 
-pub fn render(frame: &mut Frame, app: &App) {
-    let size = frame.area();
-
+pub fn render(frame: &mut Frame, size: Rect, app: &App) {
     // Main layout: header, content, footer
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -46,18 +50,366 @@ fn render_header(frame: &mut Frame, area: Rect, _app: &App) {
     frame.render_widget(header, area);
 }
 
+/// Below this width the sidebar collapses into a one-line filter indicator
+const NARROW_WIDTH: u16 = 60;
+
 fn render_content(frame: &mut Frame, area: Rect, app: &App) {
+    let layout = app.layout(ViewMode::Compact);
+
+    if area.width < NARROW_WIDTH {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Filter indicator
+                Constraint::Min(0),    // Main
+            ])
+            .split(area);
+        render_filter_line(frame, rows[0], app);
+
+        // Stack the preview under the list when there's no room beside it
+        if app.show_preview {
+            let panes = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Percentage(100u16.saturating_sub(layout.preview_percent)),
+                    Constraint::Percentage(layout.preview_percent),
+                ])
+                .split(rows[1]);
+            render_task_list(frame, panes[0], app);
+            render_preview(frame, panes[1], app);
+        } else {
+            render_task_list(frame, rows[1], app);
+        }
+        return;
+    }
+
     // Split into sidebar and main content
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Length(12), // Sidebar
-            Constraint::Min(0),     // Main
+            Constraint::Length(layout.sidebar_width), // Sidebar
+            Constraint::Min(0),                       // Main
         ])
         .split(area);
 
     render_sidebar(frame, chunks[0], app);
-    render_task_list(frame, chunks[1], app);
+
+    if app.show_preview {
+        let [list, preview] = panes::split(chunks[1], layout.preview_percent);
+        render_task_list(frame, list, app);
+        render_preview(frame, preview, app);
+    } else {
+        render_task_list(frame, chunks[1], app);
+    }
+}
+
+/// Narrow-terminal stand-in for the sidebar: the active filter on one line
+fn render_filter_line(frame: &mut Frame, area: Rect, app: &App) {
+    let line = if app.filter.is_empty() {
+        Line::from(vec![
+            Span::styled(" All tasks  ", THEME.dim_style()),
+            Span::styled("f", THEME.accent_style()),
+            Span::styled(" filter", THEME.dim_style()),
+        ])
+    } else {
+        Line::from(vec![
+            Span::styled(" ● ", THEME.accent_style()),
+            Span::styled(text::truncate(&app.filter_query(), area.width.saturating_sub(3) as usize), THEME.highlight_style()),
+        ])
+    };
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+fn render_preview(frame: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .title(if app.preview_history { " History " } else { " Preview " })
+        .title_style(THEME.accent_style())
+        .borders(Borders::LEFT)
+        .border_style(panes::border_style(app.right_pane_focused()));
+
+    let Some(task) = app.compact_selected_task() else {
+        let empty = Paragraph::new(Line::from(Span::styled(" No task selected", THEME.dim_style())))
+            .block(block);
+        frame.render_widget(empty, area);
+        return;
+    };
+
+    if app.preview_history {
+        let lines = match app.task_history.as_ref().filter(|h| h.task_id == task.frontmatter.id) {
+            Some(TaskHistory { revisions: Ok(revisions), .. }) => history_lines(app, revisions),
+            Some(TaskHistory { revisions: Err(e), .. }) => {
+                vec![Line::from(Span::styled(format!(" {}", e), THEME.dim_style()))]
+            }
+            None => vec![Line::from(Span::styled(" Reading history…", THEME.dim_style()))],
+        };
+        let history = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((app.preview_scroll, 0))
+            .block(block);
+        frame.render_widget(history, area);
+        return;
+    }
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::raw(" "),
+            app.priority_badge(&task.frontmatter.priority),
+            Span::styled(format!(" {}", task.frontmatter.title), THEME.title_style()),
+        ]),
+        Line::from(vec![
+            Span::styled(" Status: ", THEME.dim_style()),
+            Span::styled(task.frontmatter.status.as_str().to_string(), THEME.normal_style()),
+            Span::styled("  Priority: ", THEME.dim_style()),
+            Span::styled(task.frontmatter.priority.label().to_string(), THEME.normal_style()),
+        ]),
+    ];
+
+    if app.storage.is_local_only(task) {
+        lines.push(Line::from(Span::styled(" Local only: kept out of git sync", THEME.dim_style())));
+    }
+
+    let field_defs = models::field_defs(&app.tasks, task);
+    for warning in validate::validate(&task.frontmatter).into_iter()
+        .chain(validate::custom_fields(&task.frontmatter, field_defs))
+    {
+        lines.push(Line::from(Span::styled(format!(" {}{}", app.glyphs().warning, warning), THEME.warning_style())));
+    }
+
+    if !task.frontmatter.tags.is_empty() {
+        let tags = task.frontmatter.tags
+            .iter()
+            .map(|t| format!("#{}", t))
+            .collect::<Vec<_>>()
+            .join(" ");
+        lines.push(Line::from(Span::styled(format!(" {}", tags), THEME.tag_style())));
+    }
+
+    // Custom fields, padded like the date labels below but never run together
+    for (name, value) in &task.frontmatter.custom {
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {:<9} ", name), THEME.dim_style()),
+            Span::styled(value.clone(), THEME.normal_style()),
+        ]));
+    }
+
+    // Dates and history
+    lines.push(Line::from(""));
+    let mut date_line = |label: &str, value: String| {
+        lines.push(Line::from(vec![
+            Span::styled(format!(" {:<10}", label), THEME.dim_style()),
+            Span::styled(value, THEME.normal_style()),
+        ]));
+    };
+    // The full date, with the relative form lists show alongside
+    let display = &app.config.display;
+    let today = models::today();
+    if let Some(due) = &task.frontmatter.due_date {
+        let time = due.time.map(|_| format!(" {}", due.deadline().format("%H:%M"))).unwrap_or_default();
+        date_line("Due", format!(
+            "{}{} ({})",
+            display.format_date(due.local_date()),
+            time,
+            display.due_label(&Due::on(due.local_date()), today)
+        ));
+    }
+    if let Some(start) = task.frontmatter.start_date {
+        date_line("Start", format!("{} ({})", display.format_date(start), display.date_label(start, today)));
+    }
+    if let Some(end) = task.frontmatter.end_date {
+        date_line("End", format!("{} ({})", display.format_date(end), display.date_label(end, today)));
+    }
+    if let Some(minutes) = task.frontmatter.estimate_minutes {
+        let estimate = match (minutes / 60, minutes % 60) {
+            (0, m) => format!("{}m", m),
+            (h, 0) => format!("{}h", h),
+            (h, m) => format!("{}h {}m", h, m),
+        };
+        date_line("Estimate", estimate);
+    }
+    if let Some(habit) = task.frontmatter.habit {
+        date_line("Habit", format!(
+            "{}, streak {} (best {})",
+            habit.as_str(),
+            task.current_streak(today),
+            task.best_streak()
+        ));
+    }
+    date_line("Code", task.code());
+    let timestamp = |at: DateTime<Utc>| {
+        let at = at.with_timezone(&Local);
+        format!("{} {}", display.format_date(at.date_naive()), at.format("%H:%M"))
+    };
+    date_line("Created", timestamp(task.frontmatter.created_at));
+    let updated = task.last_touched();
+    let days = (Utc::now() - updated).num_days();
+    date_line("Updated", match days {
+        0 => format!("{} (today)", timestamp(updated)),
+        1 => format!("{} (yesterday)", timestamp(updated)),
+        _ => format!("{} ({} days ago)", timestamp(updated), days),
+    });
+    if let Some(person) = &task.frontmatter.delegated_to {
+        let since = task.frontmatter.delegated_at
+            .map(|d| format!(" since {}", display.format_date(d.with_timezone(&Local).date_naive())))
+            .unwrap_or_default();
+        date_line("Delegated", format!("{}{}", person, since));
+    }
+    if let Some(parent) = task.frontmatter.parent_goal_id
+        .and_then(|id| app.tasks.iter().find(|t| t.frontmatter.id == id))
+    {
+        date_line("Project", parent.frontmatter.title.clone());
+    }
+    if let Some(goal) = task.frontmatter.goal_id.and_then(|id| app.config.goal(id)) {
+        date_line("Goal", goal.description.clone());
+    }
+    if let Some(key) = &task.frontmatter.jira_key {
+        date_line("Jira", match app.jira_issues.get(key) {
+            Some(issue) => format!("{} · {} · {}", issue.key, issue.status, issue.summary),
+            None => format!("{} (J to fetch)", key),
+        });
+    }
+
+    // Subtasks
+    let children = app.child_tasks(task.frontmatter.id);
+    if !children.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(format!(" Subtasks ({})", children.len()), THEME.accent_style())));
+        for child in children {
+            lines.push(subtask_line(child));
+        }
+    }
+
+    // Links from the body, with unresolved targets dimmed
+    let refs = links::references(&task.body);
+    if !refs.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(format!(" Links ({})", refs.len()), THEME.accent_style())));
+        for target in refs {
+            lines.push(match links::resolve(target, &app.tasks).and_then(|id| app.tasks.iter().find(|t| t.frontmatter.id == id)) {
+                Some(linked) => Line::from(vec![
+                    Span::styled(" → ", THEME.accent_style()),
+                    Span::styled(linked.frontmatter.title.as_str(), THEME.normal_style()),
+                ]),
+                None => Line::from(Span::styled(format!(" ? [[{}]]", target), THEME.dim_style())),
+            });
+        }
+    }
+
+    // Backlinks
+    let referenced_by = app.links.incoming(task.frontmatter.id);
+    if !referenced_by.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(format!(" Referenced by ({})", referenced_by.len()), THEME.accent_style())));
+        for source in referenced_by.iter().filter_map(|id| app.tasks.iter().find(|t| t.frontmatter.id == *id)) {
+            lines.push(Line::from(vec![
+                Span::styled(" ← ", THEME.accent_style()),
+                Span::styled(source.frontmatter.title.as_str(), THEME.normal_style()),
+            ]));
+        }
+    }
+
+    // Similar open tasks, for prior context and duplicates
+    let related = app.related_tasks(task);
+    if !related.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(format!(" Related ({})", related.len()), THEME.accent_style())));
+        for other in related {
+            lines.push(Line::from(vec![
+                Span::styled(" ~ ", THEME.accent_style()),
+                Span::styled(other.frontmatter.title.as_str(), THEME.normal_style()),
+                Span::styled(format!("  {}", other.frontmatter.status.as_str()), THEME.dim_style()),
+            ]));
+        }
+    }
+
+    // Notes timeline
+    let notes = activity::notes(&task.body);
+    if !notes.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(format!(" Notes ({})", notes.len()), THEME.accent_style())));
+        for note in notes {
+            lines.push(Line::from(vec![
+                Span::styled(" ● ", THEME.accent_style()),
+                Span::styled(note.at, THEME.dim_style()),
+            ]));
+            for text_line in note.text.lines() {
+                lines.push(Line::from(vec![
+                    Span::styled(" │ ", THEME.border_style()),
+                    Span::styled(text_line.to_string(), THEME.normal_style()),
+                ]));
+            }
+        }
+    }
+
+    // Body (notes are shown above as a timeline)
+    let body = activity::without_section(&task.body, NOTES_HEADING);
+    if !body.is_empty() {
+        lines.push(Line::from(""));
+        for body_line in body.lines() {
+            lines.push(Line::from(Span::styled(format!(" {}", body_line), THEME.normal_style())));
+        }
+    }
+
+    let preview = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((app.preview_scroll, 0))
+        .block(block);
+
+    frame.render_widget(preview, area);
+}
+
+/// One entry per commit, newest first: who and when, then what changed
+fn history_lines<'a>(app: &App, revisions: &'a [Revision]) -> Vec<Line<'a>> {
+    if revisions.is_empty() {
+        return vec![Line::from(Span::styled(" Not committed yet", THEME.dim_style()))];
+    }
+    let mut lines = Vec::new();
+    for revision in revisions {
+        let when = revision.at
+            .map(|at| at.with_timezone(&Local))
+            .map(|at| format!("{} {}", app.config.display.format_date(at.date_naive()), at.format("%H:%M")))
+            .unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(" ● ", THEME.accent_style()),
+            Span::styled(format!("{} · {}", when, revision.author), THEME.dim_style()),
+            Span::styled(format!("  {}", revision.commit), THEME.border_style()),
+        ]));
+        let mut change_line = |text: String| {
+            lines.push(Line::from(vec![
+                Span::styled(" │ ", THEME.border_style()),
+                Span::styled(text, THEME.normal_style()),
+            ]));
+        };
+        if revision.changes.is_empty() {
+            change_line(revision.subject.clone());
+        }
+        for change in &revision.changes {
+            change_line(match change {
+                Change::Created(title) => format!("Created \"{}\"", title),
+                Change::Field { name, from: Some(from), to: Some(to) } => format!("{}: {} → {}", name, from, to),
+                Change::Field { name, from: None, to: Some(to) } => format!("{}: set to {}", name, to),
+                Change::Field { name, from: Some(from), to: None } => format!("{}: cleared (was {})", name, from),
+                Change::Field { name, .. } => format!("{}: changed", name),
+                Change::Note(note) => format!("Note: {}", note),
+                Change::Body { added, removed } => match (added, removed) {
+                    (a, 0) => format!("Body: {} lines added", a),
+                    (0, r) => format!("Body: {} lines removed", r),
+                    (a, r) => format!("Body: {} lines added, {} removed", a, r),
+                },
+            });
+        }
+    }
+    lines
+}
+
+fn subtask_line(task: &TaskItem) -> Line<'_> {
+    let done = task.frontmatter.status.is_closed();
+    Line::from(vec![
+        Span::styled(if done { " ✓ " } else { " ○ " }, THEME.accent_style()),
+        Span::styled(
+            task.frontmatter.title.as_str(),
+            if done { THEME.dim_style() } else { THEME.normal_style() },
+        ),
+    ])
 }
 
 fn render_sidebar(frame: &mut Frame, area: Rect, app: &App) {
@@ -67,7 +419,7 @@ fn render_sidebar(frame: &mut Frame, area: Rect, app: &App) {
             Span::raw("ilters"),
         ])),
         ListItem::new(""),
-        ListItem::new(if app.active_filter.is_none() {
+        ListItem::new(if app.filter.is_empty() {
             Line::from(Span::styled("● All", THEME.accent_style()))
         } else {
             Line::from(Span::raw("○ All"))
@@ -76,11 +428,9 @@ fn render_sidebar(frame: &mut Frame, area: Rect, app: &App) {
 
     // Add dynamic workstream filters
     for ws in &app.config.workstreams {
-        let is_active = app.active_filter.as_deref() == Some(&ws.name);
+        let is_active = app.filter.is_single_tag(&ws.name);
         // Capitalize first letter for display
-        let display_name = ws.name.chars().next()
-            .map(|c| c.to_uppercase().to_string() + &ws.name[1..])
-            .unwrap_or_else(|| ws.name.clone());
+        let display_name = text::capitalize(&ws.name);
 
         if is_active {
             items.push(ListItem::new(Line::from(Span::styled(
@@ -92,6 +442,21 @@ fn render_sidebar(frame: &mut Frame, area: Rect, app: &App) {
         }
     }
 
+    // Anything beyond a single workstream tag is a custom filter
+    let is_custom = !app.filter.is_empty()
+        && !app.config.workstreams.iter().any(|ws| app.filter.is_single_tag(&ws.name));
+    if is_custom {
+        items.push(ListItem::new(Line::from(Span::styled("● Custom", THEME.accent_style()))));
+    }
+
+    if !app.config.workstreams.is_empty() {
+        items.push(ListItem::new(""));
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled("W", THEME.accent_style()),
+            Span::raw(" dashboard"),
+        ])));
+    }
+
     let sidebar = List::new(items)
         .block(
             Block::default()
@@ -103,105 +468,122 @@ fn render_sidebar(frame: &mut Frame, area: Rect, app: &App) {
 }
 
 fn render_task_list(frame: &mut Frame, area: Rect, app: &App) {
-    let filtered = app.filtered_tasks();
-
-    // Group tasks by status
-    let active_tasks: Vec<_> = filtered.iter()
-        .filter(|t| t.frontmatter.status == Status::Active)
-        .collect();
-    let next_tasks: Vec<_> = filtered.iter()
-        .filter(|t| t.frontmatter.status == Status::Next)
-        .collect();
-    let done_tasks: Vec<_> = filtered.iter()
-        .filter(|t| t.frontmatter.status == Status::Done)
-        .collect();
-
-    let mut items = Vec::new();
-    let mut current_offset: usize = 0;
-
-    // Active section
-    items.push(ListItem::new(Line::from(vec![
-        Span::styled("  Active Tasks", THEME.accent_style()),
-        Span::styled(format!(" ({})", active_tasks.len()), THEME.dim_style()),
-    ])));
-
-    for (idx, task) in active_tasks.iter().enumerate() {
-        let is_selected = current_offset + idx == app.selected_index;
-        items.push(create_task_item(task, is_selected));
-    }
-    current_offset += active_tasks.len();
-
-    // Next section
-    if !next_tasks.is_empty() {
-        items.push(ListItem::new(""));
-        items.push(ListItem::new(Line::from(vec![
-            Span::styled("  Next Tasks", THEME.dim_style()),
-            Span::styled(format!(" ({})", next_tasks.len()), THEME.dim_style()),
-        ])));
-
-        for (idx, task) in next_tasks.iter().enumerate() {
-            let is_selected = current_offset + idx == app.selected_index;
-            items.push(create_task_item(task, is_selected));
-        }
-        current_offset += next_tasks.len();
-    }
-
-    // Done section (show up to 10)
-    if !done_tasks.is_empty() {
-        items.push(ListItem::new(""));
-        let showing = done_tasks.len().min(10);
-        let remaining = done_tasks.len().saturating_sub(10);
-        let label = if remaining > 0 {
-            format!("  Done ({} shown, +{} more)", showing, remaining)
-        } else {
-            format!("  Done ({})", done_tasks.len())
-        };
-        items.push(ListItem::new(Line::from(vec![
-            Span::styled(label, THEME.dim_style()),
-        ])));
+    let rows = app.compact_rows();
+    let now = models::now_local();
+    virtual_list::render(
+        frame,
+        area,
+        None,
+        &app.scroll.compact,
+        Some(app.selected_index),
+        rows.len(),
+        |idx| {
+            let is_selected = idx == app.selected_index;
+            match &rows[idx] {
+                // Sections after the first get a blank line above them
+                CompactRow::Header(status) if idx > 0 => {
+                    ListItem::new(vec![Line::from(""), section_header(app, status, is_selected)])
+                }
+                CompactRow::Header(status) => ListItem::new(section_header(app, status, is_selected)),
+                CompactRow::Task(task) => create_task_item(app, task, is_selected, area.width as usize, now),
+            }
+        },
+    );
+}
 
-        for (idx, task) in done_tasks.iter().take(10).enumerate() {
-            let is_selected = current_offset + idx == app.selected_index;
-            items.push(create_task_item(task, is_selected));
+fn section_header<'a>(app: &App, status: &Status, is_selected: bool) -> Line<'a> {
+    let count = app.compact_section_tasks(status).len();
+    let collapsed = app.collapsed_sections.contains(status);
+
+    let name = match status {
+        Status::Active => "Active Tasks",
+        Status::Next => "Next Tasks",
+        _ => "Done",
+    };
+    let detail = match status {
+        Status::Done if !collapsed && count > DONE_PAGE_SIZE => {
+            let (page, pages) = app.done_pages();
+            let first = page * DONE_PAGE_SIZE + 1;
+            let last = (first + DONE_PAGE_SIZE - 1).min(count);
+            format!(" ({}-{} of {}, page {}/{}  [ ] page)", first, last, count, page + 1, pages)
         }
-    }
+        _ => format!(" ({})", count),
+    };
 
-    let list = List::new(items);
-    frame.render_widget(list, area);
+    let name_style = if is_selected {
+        THEME.highlight_style()
+    } else if *status == Status::Active {
+        THEME.accent_style()
+    } else {
+        THEME.dim_style()
+    };
+
+    Line::from(vec![
+        Span::styled(if is_selected { " ▸" } else { "  " }, THEME.accent_style()),
+        Span::styled(if collapsed { "▶ " } else { "▼ " }, THEME.dim_style()),
+        Span::styled(name, name_style),
+        Span::styled(detail, THEME.dim_style()),
+    ])
 }
 
-fn create_task_item(task: &crate::models::TaskItem, is_selected: bool) -> ListItem {
+fn create_task_item<'a>(app: &App, task: &'a TaskItem, is_selected: bool, width: usize, now: NaiveDateTime) -> ListItem<'a> {
     // Single line with title, tags, and due date
+    let tags = task.frontmatter.tags
+        .iter()
+        .map(|t| format!("#{}", t))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let due = task.frontmatter.due_date.map(|d| format!("{}{}", app.glyphs().due, app.config.display.due_label(&d, now.date())));
+
+    let streak = app.streak_badge(task);
+
+    // Shorten the title so tags, streak and due date stay visible, but keep at least a few words
+    let streak_text = streak.as_ref().map(|s| s.content.to_string()).unwrap_or_default();
+    let suffix_width = [&tags, &streak_text, due.as_deref().unwrap_or("")]
+        .iter()
+        .filter(|s| !s.is_empty())
+        .map(|s| text::width(s) + 2)
+        .sum::<usize>();
+    let title_width = width.saturating_sub(6 + suffix_width).max(16);
+    let wrap = app.config.display.titles.compact == TitleMode::Wrap;
+    let mut title_lines = if wrap {
+        text::wrap(&task.frontmatter.title, title_width)
+    } else {
+        vec![text::truncate(&task.frontmatter.title, title_width)]
+    };
+    let continued = title_lines.split_off(1);
+    let title_style = if is_selected { THEME.highlight_style() } else { THEME.normal_style() };
+
     let mut spans = Vec::new();
+    let badge = app.priority_badge(&task.frontmatter.priority);
+    // Wrapped lines line up under the title's first character
+    let indent = " ".repeat(4 + badge.width());
 
-    if is_selected {
-        spans.push(Span::styled(" ▸ ", THEME.accent_style()));
-        spans.push(Span::styled(task.frontmatter.priority.emoji(), THEME.normal_style()));
-        spans.push(Span::styled(format!(" {}", task.frontmatter.title), THEME.highlight_style()));
-    } else {
-        spans.push(Span::raw("   "));
-        spans.push(Span::styled(task.frontmatter.priority.emoji(), THEME.normal_style()));
-        spans.push(Span::styled(format!(" {}", task.frontmatter.title), THEME.normal_style()));
-    }
+    spans.push(if is_selected { Span::styled(" ▸ ", THEME.accent_style()) } else { Span::raw("   ") });
+    spans.push(badge);
+    spans.push(Span::styled(format!(" {}", title_lines[0]), title_style));
 
     // Add tags inline
-    if !task.frontmatter.tags.is_empty() {
-        let tags = task.frontmatter.tags
-            .iter()
-            .map(|t| format!("#{}", t))
-            .collect::<Vec<_>>()
-            .join(" ");
+    if !tags.is_empty() {
         spans.push(Span::raw("  "));
         spans.push(Span::styled(tags, THEME.tag_style()));
     }
 
+    if let Some(streak) = streak {
+        spans.push(Span::raw("  "));
+        spans.push(streak);
+    }
+
     // Add due date inline
-    if let Some(due) = &task.frontmatter.due_date {
+    if let Some(due) = due {
+        let overdue = task.is_overdue(now);
         spans.push(Span::raw("  "));
-        spans.push(Span::styled(format!("📅 {}", due), THEME.dim_style()));
+        spans.push(Span::styled(due, if overdue { THEME.warning_style() } else { THEME.dim_style() }));
     }
 
-    ListItem::new(Line::from(spans))
+    let mut lines = vec![Line::from(spans)];
+    lines.extend(continued.into_iter().map(|rest| Line::from(Span::styled(format!("{}{}", indent, rest), title_style))));
+    ListItem::new(lines)
 }
 
 fn render_footer(frame: &mut Frame, area: Rect, app: &App) {
@@ -214,7 +596,24 @@ fn render_footer(frame: &mut Frame, area: Rect, app: &App) {
         Span::raw(" done  "),
         Span::styled("P", THEME.accent_style()),
         Span::raw(" priority  "),
+        Span::styled("D", THEME.accent_style()),
+        Span::raw(" delegate  "),
+        Span::styled("N", THEME.accent_style()),
+        Span::raw(" note  "),
+        Span::styled("G", THEME.accent_style()),
+        Span::raw(" goal  "),
+        Span::styled("space", THEME.accent_style()),
+        Span::raw(" preview  "),
     ];
+    if app.show_preview {
+        help_items.extend([Span::styled("^w", THEME.accent_style()), Span::raw(" focus  ")]);
+    }
+    help_items.extend([
+        Span::styled("enter", THEME.accent_style()),
+        Span::raw(" fold  "),
+        Span::styled("u", THEME.accent_style()),
+        Span::raw(if app.sort_recent { " by due  " } else { " recent  " }),
+    ]);
 
     // Add dynamic workstream shortcuts
     for ws in &app.config.workstreams {
@@ -225,8 +624,20 @@ fn render_footer(frame: &mut Frame, area: Rect, app: &App) {
     help_items.extend([
         Span::styled("0", THEME.accent_style()),
         Span::raw(" all  "),
+        Span::styled("f", THEME.accent_style()),
+        Span::raw(" filter  "),
+        Span::styled("!", THEME.accent_style()),
+        Span::raw(" priority  "),
+        Span::styled(".", THEME.accent_style()),
+        Span::raw(" due soon  "),
         Span::styled("p", THEME.accent_style()),
         Span::raw(" projects  "),
+        Span::styled("w", THEME.accent_style()),
+        Span::raw(" people  "),
+        Span::styled("g", THEME.accent_style()),
+        Span::raw(" goals  "),
+        Span::styled("S", THEME.accent_style()),
+        Span::raw(" stale  "),
         Span::styled("s", THEME.accent_style()),
         Span::raw(" settings  "),
         Span::styled("tab", THEME.accent_style()),
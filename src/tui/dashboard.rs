@@ -0,0 +1,157 @@
+use super::{app::App, THEME};
+use crate::dashboard::WorkstreamSummary;
+use crate::models::{self, TaskItem};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+
+/// Width of the progress bar next to each goal
+const BAR_WIDTH: usize = 20;
+
+pub fn render(frame: &mut Frame, size: Rect, app: &App) {
+    // Main layout: header, content, footer
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // Header
+            Constraint::Min(0),     // Content
+            Constraint::Length(3),  // Footer
+        ])
+        .split(size);
+
+    render_header(frame, chunks[0], app);
+    render_content(frame, chunks[1], app);
+    render_footer(frame, chunks[2]);
+}
+
+/// Title and a tab per workstream, the shown one highlighted
+fn render_header(frame: &mut Frame, area: Rect, app: &App) {
+    let mut tabs = vec![Span::styled("  WORKSTREAM  ", THEME.title_style())];
+    for (idx, ws) in app.config.workstreams.iter().enumerate() {
+        let style = if idx == app.dashboard_stream { THEME.highlight_style() } else { THEME.dim_style() };
+        tabs.push(Span::styled(format!(" {} {} ", ws.key, ws.name), style));
+        tabs.push(Span::raw(" "));
+    }
+
+    let header = Paragraph::new(vec![Line::from(tabs)])
+        .block(Block::default().borders(Borders::BOTTOM).border_style(THEME.border_style()));
+
+    frame.render_widget(header, area);
+}
+
+fn heading(text: String) -> ListItem<'static> {
+    ListItem::new(Line::from(Span::styled(format!("  {}", text), THEME.title_style())))
+}
+
+fn task_row<'a>(app: &App, task: &'a TaskItem, now: chrono::NaiveDateTime) -> ListItem<'a> {
+    let mut spans = vec![
+        Span::raw("    "),
+        app.priority_badge(&task.frontmatter.priority),
+        Span::styled(format!(" {}", task.frontmatter.title), THEME.normal_style()),
+        Span::styled(format!("  {}", task.frontmatter.status.as_str()), THEME.dim_style()),
+    ];
+    if let Some(due) = &task.frontmatter.due_date {
+        let style = if task.is_overdue(now) { THEME.warning_style() } else { THEME.dim_style() };
+        spans.push(Span::styled(format!("  {}", app.config.display.due_label(due, now.date())), style));
+    }
+    ListItem::new(Line::from(spans))
+}
+
+fn render_content(frame: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(THEME.border_style());
+
+    let Some(ws) = app.config.workstreams.get(app.dashboard_stream) else {
+        let empty = Paragraph::new(Line::from(Span::styled(
+            "  No workstreams. Add one in Settings (s).",
+            THEME.dim_style(),
+        )))
+        .block(block);
+        frame.render_widget(empty, area);
+        return;
+    };
+
+    let now = models::now_local();
+    let summary = WorkstreamSummary::new(&app.tasks, &app.config, &ws.name, now);
+    let mut items = Vec::new();
+
+    let mut counts = vec![Span::raw("  ")];
+    for (status, count) in &summary.counts {
+        counts.push(Span::styled(count.to_string(), THEME.accent_style()));
+        counts.push(Span::styled(format!(" {}   ", status.as_str()), THEME.dim_style()));
+    }
+    if summary.counts.is_empty() {
+        counts.push(Span::styled(format!("No tasks tagged #{} yet.", summary.name), THEME.dim_style()));
+    }
+    items.push(ListItem::new(Line::from(counts)));
+
+    let trend = match summary.done_this_week.cmp(&summary.done_last_week) {
+        std::cmp::Ordering::Greater => "up from",
+        std::cmp::Ordering::Less => "down from",
+        std::cmp::Ordering::Equal => "same as",
+    };
+    items.push(ListItem::new(Line::from(vec![
+        Span::raw("  "),
+        Span::styled(summary.done_this_week.to_string(), THEME.accent_style()),
+        Span::styled(
+            format!(" done in the last 7 days, {} {} the week before", trend, summary.done_last_week),
+            THEME.dim_style(),
+        ),
+    ])));
+    items.push(ListItem::new(""));
+
+    if !summary.overdue.is_empty() {
+        items.push(heading(format!("{}Overdue ({})", app.glyphs().warning, summary.overdue.len())));
+        items.extend(summary.overdue.iter().map(|t| task_row(app, t, now)));
+        items.push(ListItem::new(""));
+    }
+
+    items.push(heading("Top priorities".to_string()));
+    if summary.top.is_empty() {
+        items.push(ListItem::new(Span::styled("    Nothing open.", THEME.dim_style())));
+    }
+    items.extend(summary.top.iter().map(|t| task_row(app, t, now)));
+    items.push(ListItem::new(""));
+
+    items.push(heading("Goals".to_string()));
+    if summary.goals.is_empty() {
+        items.push(ListItem::new(Span::styled(
+            format!("    No active goals in the {} area.", summary.name),
+            THEME.dim_style(),
+        )));
+    }
+    for (goal, progress) in &summary.goals {
+        let filled = *progress as usize * BAR_WIDTH / 100;
+        items.push(ListItem::new(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(app.glyphs().bar_full.to_string().repeat(filled), THEME.accent_style()),
+            Span::styled(app.glyphs().bar_empty.to_string().repeat(BAR_WIDTH - filled), THEME.dim_style()),
+            Span::styled(format!(" {:>3}%  ", progress), THEME.dim_style()),
+            Span::styled(goal.description.clone(), THEME.normal_style()),
+        ])));
+    }
+
+    frame.render_widget(List::new(items).block(block), area);
+}
+
+fn render_footer(frame: &mut Frame, area: Rect) {
+    let help_items = vec![
+        Span::styled("←→", THEME.accent_style()),
+        Span::raw(" workstream  "),
+        Span::styled("Enter", THEME.accent_style()),
+        Span::raw(" filter by it  "),
+        Span::styled("Esc", THEME.accent_style()),
+        Span::raw(" back  "),
+        Span::styled("q", THEME.accent_style()),
+        Span::raw(" quit"),
+    ];
+
+    let footer = Paragraph::new(Line::from(help_items))
+        .block(Block::default().borders(Borders::TOP).border_style(THEME.border_style()));
+
+    frame.render_widget(footer, area);
+}
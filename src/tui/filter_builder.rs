@@ -0,0 +1,100 @@
+use super::{app::App, THEME};
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+
+    // Center the dialog
+    let dialog_width = 70.min(area.width.saturating_sub(4));
+    let dialog_height = (15 + app.config.saved_filters.len() as u16).min(area.height.saturating_sub(2));
+    let dialog_area = Rect {
+        x: (area.width.saturating_sub(dialog_width)) / 2,
+        y: (area.height.saturating_sub(dialog_height)) / 2,
+        width: dialog_width,
+        height: dialog_height,
+    };
+
+    // Clear the area behind the dialog
+    frame.render_widget(Clear, dialog_area);
+
+    let mut content = vec![Line::from("")];
+
+    if app.filter_naming {
+        content.push(app.filter_name_input.to_line(" Name: ", THEME.normal_style()));
+        content.push(Line::from(vec![
+            Span::raw("       "),
+            Span::styled(app.filter_input.to_string(), THEME.dim_style()),
+        ]));
+    } else {
+        content.push(app.filter_input.to_line(" ", THEME.normal_style()));
+
+        // Live preview of the parsed filter
+        let preview = match app.filter_builder_preview() {
+            Ok(filter) if filter.is_empty() => Span::styled(" No filter (all tasks)", THEME.dim_style()),
+            Ok(filter) => {
+                let today = crate::models::today();
+                let count = app.tasks.iter().filter(|t| filter.matches(t, today)).count();
+                Span::styled(format!(" ✓ {} matching tasks", count), THEME.accent_style())
+            }
+            Err(e) => Span::styled(format!(" ✗ {}", e), THEME.dim_style()),
+        };
+        content.push(Line::from(preview));
+    }
+
+    content.push(Line::from(""));
+    content.push(Line::from(Span::styled(" #a #b  all tags   #a|#b  any tag   status:active,next", THEME.dim_style())));
+    content.push(Line::from(Span::styled(" priority:high   due:today..today+7   due:..2025-12-31   @project", THEME.dim_style())));
+    content.push(Line::from(Span::styled(" completed:today-7..   completed:2025-11-01..   severity=high,low", THEME.dim_style())));
+
+    // Saved filters
+    content.push(Line::from(""));
+    content.push(Line::from(Span::styled(" Saved filters", THEME.accent_style())));
+    if app.config.saved_filters.is_empty() {
+        content.push(Line::from(Span::styled("   (none yet - Tab to save the current query)", THEME.dim_style())));
+    }
+    for (idx, saved) in app.config.saved_filters.iter().enumerate() {
+        let is_selected = app.filter_saved_selected == Some(idx);
+        content.push(if is_selected {
+            Line::from(vec![
+                Span::styled(" ▸ ", THEME.accent_style()),
+                Span::styled(saved.name.clone(), THEME.highlight_style()),
+            ])
+        } else {
+            Line::from(vec![
+                Span::raw("   "),
+                Span::styled(saved.name.clone(), THEME.normal_style()),
+            ])
+        });
+    }
+
+    content.push(Line::from(""));
+    content.push(Line::from(vec![
+        Span::styled(" Enter", THEME.accent_style()),
+        Span::raw(if app.filter_naming { " save  " } else { " apply  " }),
+        Span::styled("↑↓", THEME.accent_style()),
+        Span::raw(" saved  "),
+        Span::styled("Tab", THEME.accent_style()),
+        Span::raw(" save as  "),
+        Span::styled("Del", THEME.accent_style()),
+        Span::raw(" delete  "),
+        Span::styled("Esc", THEME.accent_style()),
+        Span::raw(" close"),
+    ]));
+
+    let title = if app.filter_naming { " Save Filter As " } else { " Filter " };
+    let dialog = Paragraph::new(content)
+        .block(
+            Block::default()
+                .title(title)
+                .title_style(THEME.accent_style())
+                .borders(Borders::ALL)
+                .border_style(THEME.border_focused_style())
+        );
+
+    frame.render_widget(dialog, dialog_area);
+}
@@ -0,0 +1,39 @@
+//! Symbols that change with `display.ascii_only`. Emoji like 📅 are double
+//! width, and terminals that draw them as boxes or single-width glyphs shift
+//! everything after them out of line; the ASCII set never does.
+
+pub struct Glyphs {
+    /// Before a due date label
+    pub due: &'static str,
+    /// Before a validation warning
+    pub warning: &'static str,
+    /// Before a "due now" reminder in the status bar
+    pub reminder: &'static str,
+    /// Before a habit's streak
+    pub streak: &'static str,
+    /// Progress bars in the Projects, Gantt and Goals views
+    pub bar_full: char,
+    pub bar_empty: char,
+    /// The today line in the Gantt view
+    pub today: char,
+}
+
+pub const UNICODE: Glyphs = Glyphs {
+    due: "📅 ",
+    warning: "⚠ ",
+    reminder: "⏰ ",
+    streak: "🔥",
+    bar_full: '█',
+    bar_empty: '░',
+    today: '│',
+};
+
+pub const ASCII: Glyphs = Glyphs {
+    due: "due: ",
+    warning: "! ",
+    reminder: "",
+    streak: "*",
+    bar_full: '#',
+    bar_empty: '-',
+    today: '|',
+};
@@ -0,0 +1,135 @@
+use super::{app::App, THEME};
+use crate::models::{self, GoalLinks};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+
+/// Width of the progress bar next to each goal
+const BAR_WIDTH: usize = 20;
+
+pub fn render(frame: &mut Frame, size: Rect, app: &App) {
+    // Main layout: header, content, footer
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // Header
+            Constraint::Min(0),     // Content
+            Constraint::Length(3),  // Footer
+        ])
+        .split(size);
+
+    render_header(frame, chunks[0]);
+    render_content(frame, chunks[1], app);
+    render_footer(frame, chunks[2]);
+}
+
+fn render_header(frame: &mut Frame, area: Rect) {
+    let title = vec![
+        Line::from(vec![
+            Span::styled("  GOALS - Active Goals and Linked Work", THEME.title_style()),
+        ]),
+    ];
+
+    let header = Paragraph::new(title)
+        .block(Block::default().borders(Borders::BOTTOM).border_style(THEME.border_style()));
+
+    frame.render_widget(header, area);
+}
+
+fn render_content(frame: &mut Frame, area: Rect, app: &App) {
+    let goals = app.config.active_goals();
+    let mut items = Vec::new();
+
+    if goals.is_empty() {
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled("  No active goals. Add one in Settings (s, Tab to Goals).", THEME.dim_style()),
+        ])));
+    }
+
+    for (idx, goal) in goals.iter().enumerate() {
+        let is_selected = idx == app.goals_selected;
+        let links = GoalLinks::new(&app.tasks, goal.id);
+        let progress = links.progress();
+        let filled = progress as usize * BAR_WIDTH / 100;
+
+        items.push(ListItem::new(Line::from(vec![
+            if is_selected {
+                Span::styled("  ▸ ", THEME.accent_style())
+            } else {
+                Span::raw("    ")
+            },
+            Span::styled("★".repeat(6 - goal.priority as usize), THEME.accent_style()),
+            Span::raw(" "),
+            Span::styled(
+                goal.description.clone(),
+                if is_selected { THEME.highlight_style() } else { THEME.normal_style() },
+            ),
+            Span::styled(format!("  [{}]", goal.area), THEME.dim_style()),
+        ])));
+
+        let done = links.tasks.iter()
+            .filter(|t| t.frontmatter.status.is_closed())
+            .count();
+        let next_actions = links.next_actions();
+        items.push(ListItem::new(Line::from(vec![
+            Span::raw("      "),
+            Span::styled(app.glyphs().bar_full.to_string().repeat(filled), THEME.accent_style()),
+            Span::styled(app.glyphs().bar_empty.to_string().repeat(BAR_WIDTH - filled), THEME.dim_style()),
+            Span::styled(format!(" {}%  {}/{} tasks done  ", progress, done, links.tasks.len()), THEME.dim_style()),
+            if next_actions == 0 {
+                Span::styled(format!("{}no next actions", app.glyphs().warning), THEME.warning_style())
+            } else {
+                Span::styled(format!("{} next actions", next_actions), THEME.dim_style())
+            },
+        ])));
+
+        for project in &links.projects {
+            items.push(ListItem::new(Line::from(vec![
+                Span::styled("      ◆ ", THEME.accent_style()),
+                Span::styled(project.frontmatter.title.clone(), THEME.normal_style()),
+                Span::styled(
+                    format!("  {}%", models::project_progress(&app.tasks, project.frontmatter.id)),
+                    THEME.dim_style(),
+                ),
+            ])));
+        }
+
+        // Open work only; finished tasks are already counted in the bar
+        for task in links.tasks.iter().filter(|t| !t.frontmatter.status.is_closed()) {
+            items.push(ListItem::new(Line::from(vec![
+                Span::raw("      "),
+                app.priority_badge(&task.frontmatter.priority),
+                Span::styled(format!(" {}", task.frontmatter.title), THEME.normal_style()),
+                Span::styled(format!("  {}", task.frontmatter.status.as_str()), THEME.dim_style()),
+            ])));
+        }
+        items.push(ListItem::new(""));
+    }
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(THEME.border_style()),
+    );
+
+    frame.render_widget(list, area);
+}
+
+fn render_footer(frame: &mut Frame, area: Rect) {
+    let help_items = vec![
+        Span::styled("↑↓", THEME.accent_style()),
+        Span::raw(" nav  "),
+        Span::styled("Esc", THEME.accent_style()),
+        Span::raw(" back  "),
+        Span::styled("q", THEME.accent_style()),
+        Span::raw(" quit"),
+    ];
+
+    let footer = Paragraph::new(Line::from(help_items))
+        .block(Block::default().borders(Borders::TOP).border_style(THEME.border_style()));
+
+    frame.render_widget(footer, area);
+}
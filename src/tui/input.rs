@@ -0,0 +1,257 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+use std::ops::Deref;
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::text;
+
+/// Editable text for dialog inputs: a cursor, word/line editing and paste.
+/// Multi-line inputs take Alt+Enter for a newline and Up/Down between lines.
+#[derive(Debug, Clone, Default)]
+pub struct TextInput {
+    text: String,
+    /// Byte offset into `text`, always on a grapheme boundary
+    cursor: usize,
+    multiline: bool,
+}
+
+impl TextInput {
+    pub fn multiline() -> Self {
+        Self { multiline: true, ..Self::default() }
+    }
+
+    /// Replace the contents and put the cursor at the end
+    pub fn set(&mut self, text: impl Into<String>) {
+        self.text.clear();
+        self.cursor = 0;
+        self.insert_str(&text.into());
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.cursor = 0;
+    }
+
+    /// Insert at the cursor; single-line inputs turn newlines into spaces
+    pub fn insert_str(&mut self, text: &str) {
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        let text = if self.multiline { text } else { text.replace('\n', " ") };
+        self.text.insert_str(self.cursor, &text);
+        self.cursor += text.len();
+    }
+
+    /// Apply an editing key. Returns false for keys the input doesn't use.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+
+        match key.code {
+            KeyCode::Char('w') if ctrl && !alt => self.delete_to(self.word_start()),
+            KeyCode::Backspace if ctrl || alt => self.delete_to(self.word_start()),
+            KeyCode::Char('u') if ctrl && !alt => self.delete_to(self.line_start()),
+            KeyCode::Char('k') if ctrl && !alt => self.delete_to(self.line_end()),
+            KeyCode::Char('a') if ctrl && !alt => self.cursor = self.line_start(),
+            KeyCode::Char('e') if ctrl && !alt => self.cursor = self.line_end(),
+            // Ctrl+Alt is AltGr on some layouts, so it still types
+            KeyCode::Char(c) if ctrl == alt => {
+                self.text.insert(self.cursor, c);
+                self.cursor += c.len_utf8();
+            }
+            KeyCode::Enter if alt && self.multiline => self.insert_str("\n"),
+            KeyCode::Backspace => self.delete_to(self.prev_boundary()),
+            KeyCode::Delete => self.delete_to(self.next_boundary()),
+            KeyCode::Left if ctrl || alt => self.cursor = self.word_start(),
+            KeyCode::Right if ctrl || alt => self.cursor = self.word_end(),
+            KeyCode::Left => self.cursor = self.prev_boundary(),
+            KeyCode::Right => self.cursor = self.next_boundary(),
+            KeyCode::Home => self.cursor = self.line_start(),
+            KeyCode::End => self.cursor = self.line_end(),
+            KeyCode::Up if self.multiline => self.move_line(false),
+            KeyCode::Down if self.multiline => self.move_line(true),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Render with a block cursor; continuation lines are indented to match `prefix`
+    pub fn to_lines<'a>(&'a self, prefix: &'a str, style: Style) -> Vec<Line<'a>> {
+        let cursor_style = style.add_modifier(Modifier::REVERSED);
+        let indent = " ".repeat(text::width(prefix));
+        let mut lines = Vec::new();
+        let mut start = 0;
+
+        for (idx, text_line) in self.text.split('\n').enumerate() {
+            let end = start + text_line.len();
+            let mut spans = vec![if idx == 0 { Span::raw(prefix) } else { Span::raw(indent.clone()) }];
+            if (start..=end).contains(&self.cursor) {
+                let (before, rest) = text_line.split_at(self.cursor - start);
+                let mut after = rest.graphemes(true);
+                let under = after.next().unwrap_or(" ");
+                spans.push(Span::styled(before, style));
+                spans.push(Span::styled(under, cursor_style));
+                spans.push(Span::styled(after.as_str(), style));
+            } else {
+                spans.push(Span::styled(text_line, style));
+            }
+            lines.push(Line::from(spans));
+            start = end + 1;
+        }
+
+        lines
+    }
+
+    /// Single-line rendering (the first line of `to_lines`)
+    pub fn to_line<'a>(&'a self, prefix: &'a str, style: Style) -> Line<'a> {
+        self.to_lines(prefix, style).into_iter().next().unwrap_or_default()
+    }
+
+    /// Single-line rendering for secrets: characters show as `•`, except the last
+    /// four of a long value so it can be told apart
+    pub fn to_masked_line<'a>(&self, prefix: &'a str, style: Style) -> Line<'a> {
+        let graphemes: Vec<(usize, &str)> = self.text.grapheme_indices(true).collect();
+        let hidden = if graphemes.len() > 8 { graphemes.len() - 4 } else { graphemes.len() };
+        let mut masked = Self::default();
+        for (idx, (offset, grapheme)) in graphemes.iter().enumerate() {
+            if *offset == self.cursor {
+                masked.cursor = masked.text.len();
+            }
+            masked.text.push_str(if idx < hidden { "•" } else { grapheme });
+        }
+        if self.cursor == self.text.len() {
+            masked.cursor = masked.text.len();
+        }
+        let line = masked.to_line(prefix, style);
+        Line::from(line.spans.into_iter()
+            .map(|span| Span::styled(span.content.into_owned(), span.style))
+            .collect::<Vec<_>>())
+    }
+
+    fn delete_to(&mut self, pos: usize) {
+        let (from, to) = if pos < self.cursor { (pos, self.cursor) } else { (self.cursor, pos) };
+        self.text.replace_range(from..to, "");
+        self.cursor = from;
+    }
+
+    fn prev_boundary(&self) -> usize {
+        self.text[..self.cursor].grapheme_indices(true).next_back().map(|(i, _)| i).unwrap_or(0)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.text[self.cursor..].graphemes(true).next().map(|g| self.cursor + g.len()).unwrap_or(self.cursor)
+    }
+
+    fn line_start(&self) -> usize {
+        self.text[..self.cursor].rfind('\n').map(|i| i + 1).unwrap_or(0)
+    }
+
+    fn line_end(&self) -> usize {
+        self.text[self.cursor..].find('\n').map(|i| self.cursor + i).unwrap_or(self.text.len())
+    }
+
+    /// Start of the word before the cursor (skipping whitespace first)
+    fn word_start(&self) -> usize {
+        let before = self.text[..self.cursor].trim_end();
+        before.rfind(char::is_whitespace).map(|i| i + 1).unwrap_or(0)
+    }
+
+    /// End of the word after the cursor (skipping whitespace first)
+    fn word_end(&self) -> usize {
+        let after = &self.text[self.cursor..];
+        let skipped = after.len() - after.trim_start().len();
+        let word = &after[skipped..];
+        self.cursor + skipped + word.find(char::is_whitespace).unwrap_or(word.len())
+    }
+
+    /// Move to the same column on the previous/next line
+    fn move_line(&mut self, down: bool) {
+        let start = self.line_start();
+        let column = self.text[start..self.cursor].graphemes(true).count();
+        let target = if down {
+            match self.text[self.cursor..].find('\n') {
+                Some(i) => self.cursor + i + 1,
+                None => return,
+            }
+        } else if start == 0 {
+            return;
+        } else {
+            self.text[..start - 1].rfind('\n').map(|i| i + 1).unwrap_or(0)
+        };
+        let line = self.text[target..].split('\n').next().unwrap_or("");
+        self.cursor = target + line.graphemes(true).take(column).map(str::len).sum::<usize>();
+    }
+}
+
+impl Deref for TextInput {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(input: &mut TextInput, code: KeyCode, modifiers: KeyModifiers) {
+        input.handle_key(KeyEvent::new(code, modifiers));
+    }
+
+    #[test]
+    fn test_cursor_editing() {
+        let mut input = TextInput::default();
+        input.set("fix the bug");
+        press(&mut input, KeyCode::Home, KeyModifiers::NONE);
+        press(&mut input, KeyCode::Right, KeyModifiers::CONTROL);
+        press(&mut input, KeyCode::Char('é'), KeyModifiers::NONE);
+        assert_eq!(&*input, "fixé the bug");
+
+        press(&mut input, KeyCode::Left, KeyModifiers::NONE);
+        press(&mut input, KeyCode::Delete, KeyModifiers::NONE);
+        assert_eq!(&*input, "fix the bug");
+
+        press(&mut input, KeyCode::End, KeyModifiers::NONE);
+        press(&mut input, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(&*input, "fix the ");
+
+        input.insert_str("login\nflow");
+        assert_eq!(&*input, "fix the login flow");
+
+        // Backspace removes a whole grapheme, not half of it
+        input.insert_str(" cafe\u{301}");
+        press(&mut input, KeyCode::Backspace, KeyModifiers::NONE);
+        assert_eq!(&*input, "fix the login flow caf");
+    }
+
+    #[test]
+    fn test_multiline() {
+        let mut input = TextInput::multiline();
+        input.insert_str("first line\r\nsecond");
+        press(&mut input, KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(input.cursor, "first ".len());
+        press(&mut input, KeyCode::Enter, KeyModifiers::ALT);
+        assert_eq!(&*input, "first \nline\nsecond");
+        assert_eq!(input.to_lines(" ", Style::default()).len(), 3);
+
+        press(&mut input, KeyCode::Down, KeyModifiers::NONE);
+        press(&mut input, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        assert_eq!(&*input, "first \nline\nsecond");
+        press(&mut input, KeyCode::End, KeyModifiers::NONE);
+        press(&mut input, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        assert_eq!(&*input, "first \nline\n");
+    }
+
+    #[test]
+    fn test_masked_line() {
+        let text = |line: Line| line.spans.iter().map(|s| s.content.as_ref()).collect::<String>();
+        let mut input = TextInput::default();
+        input.set("sk-abcdef123456");
+        assert_eq!(text(input.to_masked_line(" ", Style::default())), " •••••••••••3456 ");
+        input.set("short");
+        press(&mut input, KeyCode::Home, KeyModifiers::NONE);
+        assert_eq!(text(input.to_masked_line(" ", Style::default())), " •••••");
+    }
+}
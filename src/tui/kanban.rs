@@ -1,15 +1,15 @@
-use super::{app::{App, KANBAN_COL_ACTIVE, KANBAN_COL_NEXT, KANBAN_COL_WAITING, KANBAN_COL_DONE}, THEME};
-use crate::models::Status;
+use super::{app::{App, KanbanSort}, text, virtual_list, THEME};
+use crate::config::{KanbanColumn, KanbanLayout, TitleMode};
+use crate::models;
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Clear, ListItem, Paragraph},
     Frame,
 };
+use std::cell::Cell;
 
-pub fn render(frame: &mut Frame, app: &App) {
-    let size = frame.area();
-
+pub fn render(frame: &mut Frame, size: Rect, app: &App) {
     // Main layout: header, board, footer
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -28,6 +28,48 @@ pub fn render(frame: &mut Frame, app: &App) {
 
     // Render footer
     render_footer(frame, chunks[2], app);
+
+    if app.kanban_peek {
+        render_card_popup(frame, chunks[1], app);
+    }
+}
+
+/// The selected card's full title over the bottom of the board, following
+/// the selection like a hover
+fn render_card_popup(frame: &mut Frame, board: Rect, app: &App) {
+    let Some(task) = app.kanban_selected_task() else {
+        return;
+    };
+    let width = board.width.saturating_sub(4).min(80);
+    let mut lines: Vec<Line> = text::wrap(&task.frontmatter.title, width.saturating_sub(4) as usize)
+        .into_iter()
+        .map(|line| Line::from(Span::styled(format!(" {}", line), THEME.highlight_style())))
+        .collect();
+    let mut details = vec![Span::styled(format!(" {}", task.frontmatter.status.as_str()), THEME.dim_style())];
+    if !task.frontmatter.tags.is_empty() {
+        let tags: Vec<String> = task.frontmatter.tags.iter().map(|t| format!("#{}", t)).collect();
+        details.push(Span::styled(format!("  {}", tags.join(" ")), THEME.tag_style()));
+    }
+    if let Some(due) = &task.frontmatter.due_date {
+        let label = app.config.display.due_label(due, models::today());
+        details.push(Span::styled(format!("  {}{}", app.glyphs().due, label), THEME.dim_style()));
+    }
+    lines.push(Line::from(details));
+
+    let height = (lines.len() as u16 + 2).min(board.height);
+    let area = Rect {
+        x: board.x + (board.width.saturating_sub(width)) / 2,
+        y: board.y + board.height.saturating_sub(height),
+        width,
+        height,
+    };
+    frame.render_widget(Clear, area);
+    let popup = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(THEME.border_focused_style()),
+    );
+    frame.render_widget(popup, area);
 }
 
 fn render_header(frame: &mut Frame, area: Rect, _app: &App) {
@@ -46,77 +88,180 @@ fn render_header(frame: &mut Frame, area: Rect, _app: &App) {
     frame.render_widget(header, area);
 }
 
+/// Narrowest a column can be and still show readable titles
+const MIN_COLUMN_WIDTH: u16 = 24;
+
+/// Height below which a 2x2 grid leaves too little room per column
+const MIN_GRID_HEIGHT: u16 = 24;
+
+/// Resolve `auto` to a concrete layout for the board area
+fn effective_layout(layout: KanbanLayout, area: Rect, count: usize) -> KanbanLayout {
+    if layout != KanbanLayout::Auto {
+        return layout;
+    }
+    let wanted = MIN_COLUMN_WIDTH.saturating_mul(count as u16);
+    if area.width >= wanted {
+        KanbanLayout::Columns
+    } else if area.width >= 2 * MIN_COLUMN_WIDTH && area.height >= MIN_GRID_HEIGHT {
+        KanbanLayout::Grid
+    } else if area.width >= 2 * MIN_COLUMN_WIDTH {
+        KanbanLayout::Scroll
+    } else {
+        KanbanLayout::Focus
+    }
+}
+
+/// Split an area into `count` equal parts
+fn split_even(area: Rect, direction: Direction, count: usize) -> Vec<Rect> {
+    let count = count.max(1) as u32;
+    Layout::default()
+        .direction(direction)
+        .constraints((0..count).map(|_| Constraint::Ratio(1, count)))
+        .split(area)
+        .to_vec()
+}
+
 fn render_board(frame: &mut Frame, area: Rect, app: &App) {
-    // Split into 4 columns
-    let columns = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-            Constraint::Percentage(25),
-        ])
-        .split(area);
+    let count = app.config.kanban_columns.len();
+    let layout = effective_layout(app.config.kanban_layout, area, count);
+
+    // Scroll and focus show a subset of columns, with a peek line for the hidden ones
+    let (board, peek) = match layout {
+        KanbanLayout::Scroll | KanbanLayout::Focus => {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(1)])
+                .split(area);
+            (rows[0], Some(rows[1]))
+        }
+        _ => (area, None),
+    };
 
-    render_column(frame, columns[0], "ACTIVE", Status::Active, KANBAN_COL_ACTIVE, app);
-    render_column(frame, columns[1], "NEXT", Status::Next, KANBAN_COL_NEXT, app);
-    render_column(frame, columns[2], "WAITING", Status::Waiting, KANBAN_COL_WAITING, app);
-    render_column(frame, columns[3], "DONE", Status::Done, KANBAN_COL_DONE, app);
+    let visible: Vec<(usize, Rect)> = match layout {
+        KanbanLayout::Scroll => {
+            let fit = ((board.width / MIN_COLUMN_WIDTH) as usize).clamp(1, count.max(1));
+            // Keep the selected column in view
+            let start = (app.kanban_column + 1).saturating_sub(fit).min(count.saturating_sub(fit));
+            (start..start + fit).zip(split_even(board, Direction::Horizontal, fit)).collect()
+        }
+        KanbanLayout::Focus => vec![(app.kanban_column, board)],
+        KanbanLayout::Grid if count > 2 => {
+            let rows = split_even(board, Direction::Vertical, count.div_ceil(2));
+            rows.into_iter()
+                .flat_map(|row| split_even(row, Direction::Horizontal, 2))
+                .enumerate()
+                .take(count)
+                .collect()
+        }
+        _ => split_even(board, Direction::Horizontal, count).into_iter().enumerate().collect(),
+    };
+
+    for (col_index, rect) in &visible {
+        if let Some(column) = app.config.kanban_columns.get(*col_index) {
+            render_column(frame, *rect, column, *col_index, app);
+        }
+    }
+
+    if let (Some(peek), Some(first), Some(last)) = (peek, visible.first(), visible.last()) {
+        render_peek(frame, peek, app, first.0, last.0);
+    }
+}
+
+/// "◀ NEXT (3)" / "DONE (2) +1 ▶" hints for columns scrolled out of view
+fn render_peek(frame: &mut Frame, area: Rect, app: &App, first: usize, last: usize) {
+    let columns = &app.config.kanban_columns;
+    let label = |idx: usize| format!("{} ({})", columns[idx].name, app.tasks_in_column(idx).len());
+
+    let halves = split_even(area, Direction::Horizontal, 2);
+    if first > 0 {
+        let more = if first > 1 { format!(" +{}", first - 1) } else { String::new() };
+        let left = Line::from(vec![
+            Span::styled(" ◀ ", THEME.accent_style()),
+            Span::styled(format!("{}{}", label(first - 1), more), THEME.dim_style()),
+        ]);
+        frame.render_widget(Paragraph::new(left), halves[0]);
+    }
+    if last + 1 < columns.len() {
+        let hidden = columns.len() - last - 1;
+        let more = if hidden > 1 { format!(" +{}", hidden - 1) } else { String::new() };
+        let right = Line::from(vec![
+            Span::styled(format!("{}{}", label(last + 1), more), THEME.dim_style()),
+            Span::styled(" ▶ ", THEME.accent_style()),
+        ]);
+        frame.render_widget(Paragraph::new(right).alignment(Alignment::Right), halves[1]);
+    }
 }
 
-fn render_column(frame: &mut Frame, area: Rect, title: &str, status: Status, col_index: usize, app: &App) {
-    let tasks = app.tasks_by_status(status);
+fn render_column(frame: &mut Frame, area: Rect, column: &KanbanColumn, col_index: usize, app: &App) {
+    let tasks = app.tasks_in_column(col_index);
     let is_selected_column = app.kanban_column == col_index;
 
-    let items: Vec<ListItem> = tasks
-        .iter()
-        .enumerate()
-        .map(|(idx, task)| {
-            let is_selected = is_selected_column && idx == app.kanban_row;
-
-            let mut lines = vec![];
-
-            // Title line with selection indicator
-            if is_selected {
-                lines.push(Line::from(vec![
-                    Span::styled("▸ ", THEME.accent_style()),
-                    Span::styled(task.frontmatter.priority.emoji(), THEME.normal_style()),
-                    Span::styled(format!(" {}", task.frontmatter.title), THEME.highlight_style()),
-                ]));
-            } else {
-                lines.push(Line::from(vec![
-                    Span::raw("  "),
-                    Span::styled(task.frontmatter.priority.emoji(), THEME.normal_style()),
-                    Span::styled(format!(" {}", task.frontmatter.title), THEME.normal_style()),
-                ]));
-            }
+    // Borders, selection marker and priority emoji take 6 columns
+    let title_width = (area.width as usize).saturating_sub(6);
+    let now = models::now_local();
 
-            // Add tags
-            if !task.frontmatter.tags.is_empty() {
-                let tags = task.frontmatter.tags
-                    .iter()
-                    .map(|t| format!("#{}", t))
-                    .collect::<Vec<_>>()
-                    .join(" ");
-                lines.push(Line::from(vec![
-                    Span::raw("  "),
-                    Span::styled(tags, THEME.tag_style()),
-                ]));
+    let older = app.kanban_older_count(col_index);
+    let task_item = |idx: usize| {
+        if idx == tasks.len() {
+            return older_row(older, is_selected_column && idx == app.kanban_row, app.done_expanded);
+        }
+        let task = tasks[idx];
+        let is_selected = is_selected_column && idx == app.kanban_row;
+        let badge = app.priority_badge(&task.frontmatter.priority);
+        // Wrapped lines line up under the title's first character and stay
+        // inside the card's borders
+        let indent = " ".repeat(3 + badge.width());
+        let title_lines = match app.config.display.titles.kanban {
+            TitleMode::Wrap => {
+                let width = (area.width as usize).saturating_sub(2 + indent.len()).max(1);
+                text::wrap(&task.frontmatter.title, width)
             }
+            TitleMode::Truncate => vec![text::truncate(&task.frontmatter.title, title_width)],
+        };
+        let title_style = if is_selected { THEME.highlight_style() } else { THEME.normal_style() };
 
-            // Add due date
-            if let Some(due) = &task.frontmatter.due_date {
-                lines.push(Line::from(vec![
-                    Span::raw("  "),
-                    Span::styled(format!("📅 {}", due), THEME.dim_style()),
-                ]));
-            }
+        let mut lines = vec![];
 
-            lines.push(Line::from(""));
+        // Title line with selection indicator
+        lines.push(Line::from(vec![
+            if is_selected { Span::styled("▸ ", THEME.accent_style()) } else { Span::raw("  ") },
+            badge,
+            Span::styled(format!(" {}", title_lines[0]), title_style),
+        ]));
+        for rest in &title_lines[1..] {
+            lines.push(Line::from(Span::styled(format!("{}{}", indent, rest), title_style)));
+        }
 
-            ListItem::new(lines)
-        })
-        .collect();
+        // Add tags
+        if !task.frontmatter.tags.is_empty() {
+            let tags = task.frontmatter.tags
+                .iter()
+                .map(|t| format!("#{}", t))
+                .collect::<Vec<_>>()
+                .join(" ");
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(tags, THEME.tag_style()),
+            ]));
+        }
+
+        if let Some(streak) = app.streak_badge(task) {
+            lines.push(Line::from(vec![Span::raw("  "), streak]));
+        }
+
+        // Add due date
+        if let Some(due) = &task.frontmatter.due_date {
+            let due_style = if task.is_overdue(now) { THEME.warning_style() } else { THEME.dim_style() };
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(format!("{}{}", app.glyphs().due, app.config.display.due_label(due, now.date())), due_style),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+
+        ListItem::new(lines)
+    };
 
     // Highlight selected column with different border style
     let border_style = if is_selected_column {
@@ -125,21 +270,50 @@ fn render_column(frame: &mut Frame, area: Rect, title: &str, status: Status, col
         THEME.border_style()
     };
 
-    let title_style = if is_selected_column {
+    // WIP limits count every task in the status, regardless of the active filter
+    let wip = column.status.as_ref().and_then(|status| app.wip_usage(status));
+    let over_limit = wip.is_some_and(|(count, limit)| count > limit as usize);
+
+    let title_style = if over_limit {
+        THEME.warning_style()
+    } else if is_selected_column {
         THEME.highlight_style()
     } else {
         THEME.accent_style()
     };
 
-    let list = List::new(items).block(
-        Block::default()
-            .title(format!("{} ({})", title, tasks.len()))
-            .title_style(title_style)
-            .borders(Borders::ALL)
-            .border_style(border_style),
-    );
+    let mut title = match wip {
+        Some((count, limit)) => format!("{} ({}) WIP {}/{}", column.name, tasks.len(), count, limit),
+        None => format!("{} ({})", column.name, tasks.len()),
+    };
+    let sort = app.column_sort(&column.name);
+    if sort != KanbanSort::Manual {
+        title.push_str(&format!(" by {}", sort.as_str()));
+    }
+
+    let block = Block::default()
+        .title(title)
+        .title_style(title_style)
+        .borders(Borders::ALL)
+        .border_style(border_style);
 
-    frame.render_widget(list, area);
+    // Each column keeps its own scroll position; only the focused one follows a selection
+    let offset = Cell::new(app.scroll.kanban.borrow().get(&col_index).copied().unwrap_or(0));
+    let selected = is_selected_column.then_some(app.kanban_row);
+    let rows = tasks.len() + usize::from(older > 0);
+    virtual_list::render(frame, area, Some(block), &offset, selected, rows, task_item);
+    app.scroll.kanban.borrow_mut().insert(col_index, offset.get());
+}
+
+/// The Done column's last row: "+N older" folded away, or the way to fold them again
+fn older_row(older: usize, is_selected: bool, expanded: bool) -> ListItem<'static> {
+    let label = if expanded { format!(" hide {} older", older) } else { format!(" +{} older", older) };
+    let line = if is_selected {
+        Line::from(vec![Span::styled("▸ ", THEME.accent_style()), Span::styled(label, THEME.highlight_style())])
+    } else {
+        Line::from(vec![Span::raw("  "), Span::styled(label, THEME.dim_style())])
+    };
+    ListItem::new(line)
 }
 
 fn render_footer(frame: &mut Frame, area: Rect, app: &App) {
@@ -148,6 +322,12 @@ fn render_footer(frame: &mut Frame, area: Rect, app: &App) {
         Span::raw(" col  "),
         Span::styled("↑↓", THEME.accent_style()),
         Span::raw(" row  "),
+        Span::styled("HL", THEME.accent_style()),
+        Span::raw(" move  "),
+        Span::styled("JK", THEME.accent_style()),
+        Span::raw(" reorder  "),
+        Span::styled("o", THEME.accent_style()),
+        Span::raw(" sort  "),
         Span::styled("n", THEME.accent_style()),
         Span::raw(" new  "),
         Span::styled("d", THEME.accent_style()),
@@ -156,14 +336,35 @@ fn render_footer(frame: &mut Frame, area: Rect, app: &App) {
         Span::raw(" archive  "),
         Span::styled("P", THEME.accent_style()),
         Span::raw(" priority  "),
+        Span::styled("N", THEME.accent_style()),
+        Span::raw(" note  "),
+        Span::styled("G", THEME.accent_style()),
+        Span::raw(" goal  "),
+        Span::styled("!", THEME.accent_style()),
+        Span::raw(" priority  "),
+        Span::styled(".", THEME.accent_style()),
+        Span::raw(" due soon  "),
+        Span::styled("v", THEME.accent_style()),
+        Span::raw(format!(" layout ({})  ", app.config.kanban_layout.as_str())),
+        Span::styled("space", THEME.accent_style()),
+        Span::raw(" full title  "),
+        Span::styled("|", THEME.accent_style()),
+        Span::raw(if app.kanban_split { " board only  " } else { " schedule  " }),
         Span::styled("tab", THEME.accent_style()),
         Span::raw(" view  "),
         Span::styled("q", THEME.accent_style()),
         Span::raw(" quit"),
     ];
 
-    if let Some(filter) = &app.active_filter {
-        help_items.insert(0, Span::styled(format!(" Filter: {} ", filter), THEME.highlight_style()));
+    if app.kanban_split {
+        help_items.splice(help_items.len() - 4..help_items.len() - 4, [
+            Span::styled("^w", THEME.accent_style()),
+            Span::raw(" focus  "),
+        ]);
+    }
+
+    if !app.filter.is_empty() {
+        help_items.insert(0, Span::styled(format!(" Filter: {} ", app.filter_query()), THEME.highlight_style()));
         help_items.insert(1, Span::raw("  "));
     }
 
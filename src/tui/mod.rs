@@ -1,17 +1,44 @@
 mod app;
 mod colors;
+mod glyphs;
 mod kanban;
 mod compact;
 mod settings;
 mod projects;
 mod project_gantt;
+mod project_chart;
+mod triage;
+mod people;
+mod goals;
+mod stale;
+mod shutdown;
+mod dashboard;
+mod filter_builder;
+mod status_bar;
+mod input;
+mod text;
+mod virtual_list;
+mod panes;
+mod popup;
+mod state;
+#[cfg(test)]
+mod snapshots;
+#[cfg(any(test, feature = "replay"))]
+mod replay;
 
-pub use app::{App, ViewMode, SettingsSection};
+pub use app::{App, ChildAction, ViewMode, SettingsSection};
 pub use colors::THEME;
+pub use popup::capture_prompt;
+#[cfg(feature = "replay")]
+pub use replay::replay;
 
 use anyhow::Result;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, Event, KeyCode,
+        KeyEvent, KeyEventKind, KeyModifiers,
+    },
+    cursor::Show,
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -20,150 +47,483 @@ use ratatui::{
     Terminal,
 };
 use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Duration;
+
+const IDLE_POLL: Duration = Duration::from_millis(500);
+const LOADING_POLL: Duration = Duration::from_millis(30);
 
 /// Run the TUI application
 pub fn run(data_dir: std::path::PathBuf) -> Result<()> {
+    install_panic_hook();
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create app state
+    // Create app state; tasks load in the background so the first frame isn't delayed
     let mut app = App::new(data_dir)?;
 
-    // Run app loop
-    let res = run_app(&mut terminal, &mut app);
+    // Run app loop. A panic unwinds to here so dialog drafts can still be saved.
+    let res = panic::catch_unwind(AssertUnwindSafe(|| run_app(&mut terminal, &mut app, read_terminal)));
+    if let Err(err) = app.save_ui_state() {
+        tracing::warn!(error = ?err, "Failed to save UI state");
+    }
 
-    // Restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
+    restore_terminal()?;
     terminal.show_cursor()?;
 
-    if let Err(err) = res {
-        eprintln!("Error: {:?}", err);
+    match res {
+        Ok(Err(err)) => {
+            tracing::error!(error = ?err, "TUI exited with error");
+            eprintln!("Error: {:?}", err);
+        }
+        Ok(Ok(())) => {}
+        Err(panic) => panic::resume_unwind(panic),
     }
 
     Ok(())
 }
 
+fn restore_terminal() -> Result<()> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, DisableBracketedPaste, Show)?;
+    Ok(())
+}
+
+/// Leave raw mode before the panic message is printed, so it's readable and
+/// the shell isn't left unusable
+fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        tracing::error!(panic = %info, "TUI panicked");
+        default_hook(info);
+    }));
+}
+
+/// What the event loop got from its input source
+enum Input {
+    Event(Event),
+    /// Nothing yet; redraw and ask again
+    Idle,
+    /// The source is exhausted (end of a replay script)
+    #[cfg_attr(not(any(test, feature = "replay")), allow(dead_code))]
+    End,
+}
+
+/// Wait for a terminal event. Polls so transient status messages expire
+/// without a key press, and more often while tasks are still loading.
+fn read_terminal(app: &App) -> Result<Input> {
+    let timeout = if app.is_loading() { LOADING_POLL } else { IDLE_POLL };
+    if !event::poll(timeout)? {
+        return Ok(Input::Idle);
+    }
+    Ok(Input::Event(event::read()?))
+}
+
 fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
+    mut next_input: impl FnMut(&App) -> Result<Input>,
 ) -> Result<()> {
     loop {
+        if let Err(e) = app.poll_loading() {
+            app.notify_error(format!("Failed to load tasks: {:#}", e));
+        }
+        app.check_reminders();
+        app.poll_remote();
+        app.poll_jira();
+        app.poll_search();
+        app.check_pull_conflicts();
+        app.refresh_task_history();
         terminal.draw(|f| app.render(f))?;
 
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
-                // Handle dialog inputs first
-                if app.show_new_task {
-                    match key.code {
-                        KeyCode::Esc => app.cancel_new_task_dialog(),
-                        KeyCode::Enter => app.create_new_task()?,
-                        KeyCode::Backspace => { app.new_task_title.pop(); }
-                        KeyCode::Char(c) => app.new_task_title.push(c),
-                        _ => {}
-                    }
-                } else if app.show_new_project {
-                    match key.code {
-                        KeyCode::Esc => app.cancel_new_project_dialog(),
-                        KeyCode::Enter => app.create_new_project()?,
-                        KeyCode::Backspace => { app.new_project_title.pop(); }
-                        KeyCode::Char(c) => app.new_project_title.push(c),
-                        _ => {}
-                    }
-                } else if app.settings_editing {
-                    match key.code {
-                        KeyCode::Esc => app.settings_cancel_edit(),
-                        KeyCode::Enter => app.settings_confirm_edit()?,
-                        KeyCode::Backspace => { app.settings_edit_text.pop(); }
-                        KeyCode::Tab => {
-                            // In Goals section, Tab cycles through areas
-                            if app.settings_section == SettingsSection::Goals {
-                                app.settings_cycle_area();
-                            }
-                        }
-                        KeyCode::Char(c) => app.settings_edit_text.push(c),
-                        _ => {}
-                    }
-                } else {
-                    // View-specific handling
-                    match app.view_mode {
-                        ViewMode::Settings => match key.code {
-                            KeyCode::Char('q') | KeyCode::Esc => app.close_settings(),
-                            KeyCode::Tab => app.settings_toggle_section(),
-                            KeyCode::Up | KeyCode::Char('k') => app.settings_prev(),
-                            KeyCode::Down | KeyCode::Char('j') => app.settings_next(),
-                            KeyCode::Enter => app.settings_start_edit(),
-                            KeyCode::Char('x') | KeyCode::Delete => app.settings_delete()?,
-                            KeyCode::Char('P') => {
-                                // Cycle priority in Goals section
-                                if app.settings_section == SettingsSection::Goals {
-                                    app.settings_cycle_priority()?;
-                                }
-                            }
-                            KeyCode::Char(' ') => {
-                                // Toggle active state in Goals section
-                                if app.settings_section == SettingsSection::Goals {
-                                    app.settings_toggle_active()?;
-                                }
-                            }
-                            _ => {}
-                        },
-                        ViewMode::Projects => match key.code {
-                            KeyCode::Char('q') => return Ok(()),
-                            KeyCode::Esc => app.close_projects(),
-                            KeyCode::Up | KeyCode::Char('k') => app.projects_prev(),
-                            KeyCode::Down | KeyCode::Char('j') => app.projects_next(),
-                            KeyCode::Enter => app.open_project_gantt(),
-                            KeyCode::Char('n') => app.show_new_project_dialog(),
-                            _ => {}
-                        },
-                        ViewMode::ProjectGantt => match key.code {
-                            KeyCode::Char('q') => return Ok(()),
-                            KeyCode::Esc => app.close_project_gantt(),
-                            KeyCode::Up | KeyCode::Char('k') => app.gantt_prev(),
-                            KeyCode::Down | KeyCode::Char('j') => app.gantt_next(),
-                            KeyCode::Left | KeyCode::Char('h') => app.gantt_scroll_left(),
-                            KeyCode::Right | KeyCode::Char('l') => app.gantt_scroll_right(),
-                            KeyCode::Char('n') => app.show_new_task_dialog_for_project(),
-                            _ => {}
-                        },
-                        _ => {
-                            // Global keys for Compact and Kanban views
-                            match key.code {
-                                KeyCode::Char('q') => return Ok(()),
-                                KeyCode::Tab => app.toggle_view(),
-                                KeyCode::Char('n') => app.show_new_task_dialog(),
-                                KeyCode::Char('r') => app.refresh_tasks()?,
-                                KeyCode::Char('s') => app.open_settings(),
-                                KeyCode::Char('p') => app.open_projects(),
-                                KeyCode::Char('0') => app.clear_filters(),
-                                _ => {
-                                    // Check for dynamic workstream shortcuts
-                                    if let KeyCode::Char(c) = key.code {
-                                        if let Some(ws) = app.config.get_workstream_by_key(c) {
-                                            app.filter_by_tag(&ws.name.clone());
-                                        } else {
-                                            handle_view_keys(app, key.code)?;
-                                        }
-                                    } else {
-                                        handle_view_keys(app, key.code)?;
-                                    }
-                                }
+        let event = match next_input(app)? {
+            Input::Event(event) => event,
+            Input::Idle => continue,
+            Input::End => return Ok(()),
+        };
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => {
+                // Errors go to the status bar instead of tearing down the UI
+                match handle_key(app, key) {
+                    Ok(true) => return Ok(()),
+                    Ok(false) => {}
+                    Err(e) => app.notify_error(format!("Error: {:#}", e)),
+                }
+            }
+            Event::Paste(text) => app.paste(&text),
+            _ => {}
+        }
+    }
+}
+
+/// Handle one key press. Returns `Ok(true)` when the app should quit.
+fn handle_key(app: &mut App, key: KeyEvent) -> Result<bool> {
+    // Handle dialog inputs first; any key dismisses the morning briefing
+    if app.briefing.is_some() {
+        app.briefing = None;
+    } else if app.pull_conflicts.is_some() {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => app.pull_conflicts_select(false),
+            KeyCode::Down | KeyCode::Char('j') => app.pull_conflicts_select(true),
+            KeyCode::Char('r') => app.resolve_pull_conflict(false)?,
+            KeyCode::Char('m') => app.resolve_pull_conflict(true)?,
+            KeyCode::Esc => app.reload_pull_conflicts(),
+            _ => {}
+        }
+    } else if app.show_new_task && !app.new_task_batch.is_empty() {
+        match key.code {
+            KeyCode::Esc => app.cancel_new_task_batch(),
+            KeyCode::Enter => app.create_new_task_batch()?,
+            KeyCode::Char('e') => app.toggle_new_task_batch_enrich(),
+            _ => {}
+        }
+    } else if app.show_new_task {
+        match key.code {
+            KeyCode::Esc => app.cancel_new_task_dialog(),
+            KeyCode::Enter => app.create_new_task()?,
+            KeyCode::Backspace if app.new_task_title.is_empty() => app.new_task_remove_chip(),
+            _ => { app.new_task_title.handle_key(key); }
+        }
+    } else if app.show_new_project {
+        match key.code {
+            KeyCode::Esc => app.cancel_new_project_dialog(),
+            KeyCode::Enter => app.create_new_project()?,
+            _ => { app.new_project_title.handle_key(key); }
+        }
+    } else if app.wip_pending.is_some() {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => app.confirm_wip_move()?,
+            KeyCode::Char('n') | KeyCode::Esc => app.cancel_wip_move(),
+            _ => {}
+        }
+    } else if app.retag_pending.is_some() {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Enter => app.confirm_retag()?,
+            KeyCode::Char('n') | KeyCode::Esc => app.cancel_retag(),
+            _ => {}
+        }
+    } else if app.priority_review.is_some() {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => app.priority_review_select(false),
+            KeyCode::Down | KeyCode::Char('j') => app.priority_review_select(true),
+            KeyCode::Char(' ') => app.priority_review_toggle(),
+            KeyCode::Char('a') => app.priority_review_toggle_all(),
+            KeyCode::Enter => app.apply_priority_review()?,
+            KeyCode::Esc => app.cancel_priority_review(),
+            _ => {}
+        }
+    } else if app.project_archive.as_ref().is_some_and(|p| p.moving.is_some()) {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => app.project_archive_select(false),
+            KeyCode::Down | KeyCode::Char('j') => app.project_archive_select(true),
+            KeyCode::Enter => app.confirm_project_archive_move()?,
+            KeyCode::Esc => app.cancel_project_archive(),
+            _ => {}
+        }
+    } else if app.project_archive.is_some() {
+        match key.code {
+            KeyCode::Char('a') => app.confirm_project_archive(ChildAction::Archive)?,
+            KeyCode::Char('d') => app.confirm_project_archive(ChildAction::Detach)?,
+            KeyCode::Char('m') => app.project_archive_start_move(),
+            KeyCode::Esc => app.cancel_project_archive(),
+            _ => {}
+        }
+    } else if app.show_filter_builder {
+        if app.filter_naming {
+            match key.code {
+                KeyCode::Esc => app.filter_naming = false,
+                KeyCode::Enter => app.filter_builder_confirm_save()?,
+                _ => { app.filter_name_input.handle_key(key); }
+            }
+        } else {
+            match key.code {
+                KeyCode::Esc => app.close_filter_builder(),
+                KeyCode::Enter => app.apply_filter_builder(),
+                KeyCode::Up => app.filter_builder_select(false),
+                KeyCode::Down => app.filter_builder_select(true),
+                KeyCode::Tab => app.filter_builder_start_save(),
+                KeyCode::Delete => app.filter_builder_delete_saved()?,
+                _ => { app.filter_input.handle_key(key); }
+            }
+        }
+    } else if app.show_links {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => app.close_links(),
+            KeyCode::Enter => app.follow_link(),
+            KeyCode::Down | KeyCode::Char('j') => app.links_next(),
+            KeyCode::Up | KeyCode::Char('k') => app.links_prev(),
+            _ => {}
+        }
+    } else if app.show_search {
+        match key.code {
+            KeyCode::Esc => app.close_search(),
+            KeyCode::Enter => app.confirm_search(),
+            KeyCode::Tab => app.toggle_search_mode(),
+            KeyCode::Down => app.search_select(true),
+            KeyCode::Up => app.search_select(false),
+            // Typing moves the cursor back to the best match
+            _ => if app.search_input.handle_key(key) { app.search_selected = 0; },
+        }
+    } else if app.goal_pick_task_id.is_some() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => app.close_goal_picker(),
+            KeyCode::Enter => app.confirm_goal_picker()?,
+            KeyCode::Down | KeyCode::Char('j') => app.goal_picker_next(),
+            KeyCode::Up | KeyCode::Char('k') => app.goal_picker_prev(),
+            _ => {}
+        }
+    } else if app.show_note {
+        match key.code {
+            KeyCode::Esc => app.cancel_note_dialog(),
+            KeyCode::Enter if !key.modifiers.contains(KeyModifiers::ALT) => app.confirm_note()?,
+            _ => { app.note_input.handle_key(key); }
+        }
+    } else if app.show_delegate {
+        match key.code {
+            KeyCode::Esc => app.cancel_delegate_dialog(),
+            KeyCode::Enter => app.confirm_delegate()?,
+            _ => { app.delegate_input.handle_key(key); }
+        }
+    } else if app.fields_task_id.is_some() {
+        if app.fields_editing {
+            match key.code {
+                KeyCode::Esc => app.fields_cancel_edit(),
+                KeyCode::Enter => app.fields_confirm_edit()?,
+                _ => { app.fields_input.handle_key(key); }
+            }
+        } else {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => app.close_fields_dialog(),
+                KeyCode::Enter => app.fields_start_edit(),
+                KeyCode::Down | KeyCode::Char('j') => app.fields_select(true),
+                KeyCode::Up | KeyCode::Char('k') => app.fields_select(false),
+                KeyCode::Right | KeyCode::Char('l') => app.fields_cycle(true)?,
+                KeyCode::Left | KeyCode::Char('h') => app.fields_cycle(false)?,
+                KeyCode::Char('x') => app.fields_clear()?,
+                _ => {}
+            }
+        }
+    } else if app.triage_scheduling {
+        match key.code {
+            KeyCode::Esc => app.triage_cancel_schedule(),
+            KeyCode::Enter => app.triage_confirm_schedule()?,
+            _ => { app.triage_input.handle_key(key); }
+        }
+    } else if app.stale_rescheduling {
+        match key.code {
+            KeyCode::Esc => app.stale_cancel_reschedule(),
+            KeyCode::Enter => app.stale_confirm_reschedule()?,
+            _ => { app.stale_input.handle_key(key); }
+        }
+    } else if app.shutdown_rescheduling {
+        match key.code {
+            KeyCode::Esc => app.shutdown_cancel_reschedule(),
+            KeyCode::Enter => app.shutdown_confirm_reschedule()?,
+            _ => { app.shutdown_input.handle_key(key); }
+        }
+    } else if app.right_pane_focused() {
+        if handle_pane_keys(app, key)? {
+            return Ok(true);
+        }
+    } else if app.settings_editing {
+        match key.code {
+            KeyCode::Esc => app.settings_cancel_edit(),
+            KeyCode::Enter => app.settings_confirm_edit()?,
+            // In Goals section, Tab cycles through areas
+            KeyCode::Tab if app.settings_section == SettingsSection::Goals => {
+                app.settings_cycle_area();
+            }
+            _ => { app.settings_edit_text.handle_key(key); }
+        }
+    } else {
+        // View-specific handling
+        match app.view_mode {
+            ViewMode::Settings => match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => app.close_settings(),
+                KeyCode::Tab => app.settings_toggle_section(true),
+                KeyCode::BackTab => app.settings_toggle_section(false),
+                KeyCode::Up | KeyCode::Char('k') => app.settings_prev(),
+                KeyCode::Down | KeyCode::Char('j') => app.settings_next(),
+                KeyCode::Enter => app.settings_start_edit(),
+                KeyCode::Char('x') | KeyCode::Delete => app.settings_delete()?,
+                // Cycle priority in Goals section
+                KeyCode::Char('P') if app.settings_section == SettingsSection::Goals => {
+                    app.settings_cycle_priority()?;
+                }
+                // Toggle active state in Goals section
+                KeyCode::Char(' ') if app.settings_section == SettingsSection::Goals => {
+                    app.settings_toggle_active()?;
+                }
+                _ => {}
+            },
+            ViewMode::Projects => match key.code {
+                KeyCode::Char('q') => return Ok(true),
+                KeyCode::Esc => app.close_projects(),
+                KeyCode::Up | KeyCode::Char('k') => app.projects_prev(),
+                KeyCode::Down | KeyCode::Char('j') => app.projects_next(),
+                KeyCode::Enter => app.open_project_gantt(),
+                KeyCode::Char('n') => app.show_new_project_dialog(),
+                KeyCode::Char('a') => app.projects_archive()?,
+                KeyCode::Char('G') => app.show_goal_picker(),
+                KeyCode::Char('E') => app.export_project(),
+                _ => {}
+            },
+            ViewMode::ProjectGantt => match key.code {
+                KeyCode::Char('q') => return Ok(true),
+                KeyCode::Esc => app.close_project_gantt(),
+                KeyCode::Up | KeyCode::Char('k') => app.gantt_prev(),
+                KeyCode::Down | KeyCode::Char('j') => app.gantt_next(),
+                KeyCode::Left if key.modifiers.contains(KeyModifiers::CONTROL) => app.resize_pane(false)?,
+                KeyCode::Right if key.modifiers.contains(KeyModifiers::CONTROL) => app.resize_pane(true)?,
+                KeyCode::Left | KeyCode::Char('h') => app.gantt_scroll_left(),
+                KeyCode::Right | KeyCode::Char('l') => app.gantt_scroll_right(),
+                KeyCode::Char('[') => app.gantt_shift_dates(-1)?,
+                KeyCode::Char(']') => app.gantt_shift_dates(1)?,
+                KeyCode::Char('n') => app.show_new_task_dialog_for_project(),
+                KeyCode::Char('c') => app.cycle_project_chart(),
+                KeyCode::Char('E') => app.export_project(),
+                _ => {}
+            },
+            ViewMode::Triage => match key.code {
+                KeyCode::Char('q') => return Ok(true),
+                KeyCode::Esc => app.close_triage(),
+                KeyCode::Up | KeyCode::Char('k') => app.triage_prev(),
+                KeyCode::Down | KeyCode::Char('j') => app.triage_next(),
+                KeyCode::Char('n') => app.triage_do_now()?,
+                KeyCode::Char('s') => app.triage_start_schedule(),
+                KeyCode::Char('d') => app.triage_delegate(),
+                KeyCode::Char('m') => app.triage_someday()?,
+                KeyCode::Char('x') => app.triage_trash()?,
+                _ => {}
+            },
+            ViewMode::People => match key.code {
+                KeyCode::Char('q') => return Ok(true),
+                KeyCode::Esc => app.close_people(),
+                KeyCode::Up | KeyCode::Char('k') => app.people_prev(),
+                KeyCode::Down | KeyCode::Char('j') => app.people_next(),
+                KeyCode::Char('d') => app.people_mark_done()?,
+                KeyCode::Char('D') => app.people_reassign(),
+                KeyCode::Char('f') => { app.generate_follow_ups()?; }
+                _ => {}
+            },
+            ViewMode::Stale => match key.code {
+                KeyCode::Char('q') => return Ok(true),
+                KeyCode::Esc => app.close_stale(),
+                KeyCode::Up | KeyCode::Char('k') => app.stale_prev(),
+                KeyCode::Down | KeyCode::Char('j') => app.stale_next(),
+                KeyCode::Char('b') => app.stale_bump()?,
+                KeyCode::Char('s') => app.stale_start_reschedule(),
+                KeyCode::Char('m') => app.stale_someday()?,
+                KeyCode::Char('a') => app.stale_archive()?,
+                _ => {}
+            },
+            ViewMode::Shutdown => match key.code {
+                KeyCode::Char('q') => return Ok(true),
+                KeyCode::Esc => app.close_shutdown(),
+                KeyCode::Up | KeyCode::Char('k') => app.shutdown_select(false),
+                KeyCode::Down | KeyCode::Char('j') => app.shutdown_select(true),
+                KeyCode::Char('s') => app.shutdown_start_reschedule(),
+                KeyCode::Char('n') => app.shutdown_demote()?,
+                KeyCode::Char('J') => app.shutdown_write_journal(),
+                KeyCode::Enter => {
+                    app.finish_shutdown()?;
+                    return Ok(true);
+                }
+                _ => {}
+            },
+            ViewMode::Dashboard => match key.code {
+                KeyCode::Char('q') => return Ok(true),
+                KeyCode::Esc => app.close_dashboard(),
+                KeyCode::Left | KeyCode::Char('h') | KeyCode::BackTab => app.dashboard_cycle(false),
+                KeyCode::Right | KeyCode::Char('l') | KeyCode::Tab => app.dashboard_cycle(true),
+                KeyCode::Enter => app.dashboard_apply(),
+                KeyCode::Char(c) => app.dashboard_select_key(c),
+                _ => {}
+            },
+            ViewMode::Goals => match key.code {
+                KeyCode::Char('q') => return Ok(true),
+                KeyCode::Esc => app.close_goals(),
+                KeyCode::Up | KeyCode::Char('k') => app.goals_prev(),
+                KeyCode::Down | KeyCode::Char('j') => app.goals_next(),
+                _ => {}
+            },
+            _ => {
+                // Global keys for Compact and Kanban views
+                match key.code {
+                    KeyCode::Char('q') => return Ok(true),
+                    KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => app.open_shutdown(),
+                    KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => app.switch_pane_focus(),
+                    KeyCode::Tab => app.toggle_view(),
+                    KeyCode::Char('n') => app.show_new_task_dialog(),
+                    KeyCode::Char('r') => app.refresh_tasks()?,
+                    KeyCode::Char('s') => app.open_settings(),
+                    KeyCode::Char('p') => app.open_projects(),
+                    KeyCode::Char('i') => app.open_triage(),
+                    KeyCode::Char('S') => app.open_stale(),
+                    KeyCode::Char('w') => app.open_people(),
+                    KeyCode::Char('g') => app.open_goals(),
+                    KeyCode::Char('W') => app.open_dashboard(),
+                    KeyCode::Char('G') => app.show_goal_picker(),
+                    KeyCode::Char('R') => app.open_priority_review(),
+                    KeyCode::Char('0') => app.clear_filters(),
+                    KeyCode::Char('f') => app.open_filter_builder(),
+                    KeyCode::Char('/') => app.open_search(),
+                    KeyCode::Char('!') => app.cycle_priority_filter(),
+                    KeyCode::Char('.') => app.toggle_due_soon_filter(),
+                    KeyCode::Left if key.modifiers.contains(KeyModifiers::CONTROL) => app.resize_pane(false)?,
+                    KeyCode::Right if key.modifiers.contains(KeyModifiers::CONTROL) => app.resize_pane(true)?,
+                    _ => {
+                        // Check for dynamic workstream shortcuts
+                        if let KeyCode::Char(c) = key.code {
+                            if let Some(ws) = app.config.get_workstream_by_key(c) {
+                                app.filter_by_tag(&ws.name.clone());
+                            } else {
+                                handle_view_keys(app, key.code)?;
                             }
+                        } else {
+                            handle_view_keys(app, key.code)?;
                         }
                     }
                 }
             }
         }
     }
+    // Keep the Gantt beside the board on the selected card's project
+    app.sync_split_project();
+    Ok(false)
+}
+
+/// Keys for the focused right pane of a split: the Compact preview, or the
+/// Gantt beside the Kanban board. Returns true to quit.
+fn handle_pane_keys(app: &mut App, key: KeyEvent) -> Result<bool> {
+    match key.code {
+        KeyCode::Char('q') => return Ok(true),
+        KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => app.switch_pane_focus(),
+        KeyCode::Esc => app.switch_pane_focus(),
+        _ if app.view_mode == ViewMode::Compact => match key.code {
+            KeyCode::Up | KeyCode::Char('k') => app.scroll_preview(false),
+            KeyCode::Down | KeyCode::Char('j') => app.scroll_preview(true),
+            KeyCode::Tab => app.toggle_preview_history(),
+            _ => {}
+        },
+        _ => match key.code {
+            KeyCode::Up | KeyCode::Char('k') => app.gantt_prev(),
+            KeyCode::Down | KeyCode::Char('j') => app.gantt_next(),
+            KeyCode::Left | KeyCode::Char('h') => app.gantt_scroll_left(),
+            KeyCode::Right | KeyCode::Char('l') => app.gantt_scroll_right(),
+            KeyCode::Char('[') => app.gantt_shift_dates(-1)?,
+            KeyCode::Char(']') => app.gantt_shift_dates(1)?,
+            KeyCode::Char('c') => app.cycle_project_chart(),
+            _ => {}
+        },
+    }
+    Ok(false)
 }
 
 fn handle_view_keys(app: &mut App, code: KeyCode) -> Result<()> {
@@ -171,10 +531,19 @@ fn handle_view_keys(app: &mut App, code: KeyCode) -> Result<()> {
         ViewMode::Compact => match code {
             KeyCode::Up | KeyCode::Char('k') => app.previous_task(),
             KeyCode::Down | KeyCode::Char('j') => app.next_task(),
-            KeyCode::Enter => app.toggle_task_selection(),
+            KeyCode::Enter => app.compact_enter(),
+            KeyCode::Char(' ') => app.toggle_task_selection(),
+            KeyCode::Char('[') => app.done_change_page(false),
+            KeyCode::Char(']') => app.done_change_page(true),
             KeyCode::Char('d') => app.mark_task_done()?,
             KeyCode::Char('a') => app.archive_task()?,
             KeyCode::Char('P') => app.cycle_task_priority()?,
+            KeyCode::Char('D') => app.compact_delegate_task(),
+            KeyCode::Char('N') => app.compact_note_task(),
+            KeyCode::Char('F') => app.compact_fields_task(),
+            KeyCode::Char('u') => app.toggle_sort_recent(),
+            KeyCode::Char('J') => app.fetch_jira_issue(),
+            KeyCode::Char('L') => app.toggle_local_only(),
             _ => {}
         },
         ViewMode::Kanban => match code {
@@ -182,9 +551,21 @@ fn handle_view_keys(app: &mut App, code: KeyCode) -> Result<()> {
             KeyCode::Down | KeyCode::Char('j') => app.kanban_move_down(),
             KeyCode::Left | KeyCode::Char('h') => app.kanban_move_left(),
             KeyCode::Right | KeyCode::Char('l') => app.kanban_move_right(),
+            KeyCode::Char('H') => app.kanban_move_task(false)?,
+            KeyCode::Char('L') => app.kanban_move_task(true)?,
             KeyCode::Char('d') => app.kanban_mark_done()?,
             KeyCode::Char('a') => app.kanban_archive_task()?,
             KeyCode::Char('P') => app.kanban_cycle_priority()?,
+            KeyCode::Char('D') => app.kanban_delegate_task(),
+            KeyCode::Char('N') => app.kanban_note_task(),
+            KeyCode::Char('F') => app.kanban_fields_task(),
+            KeyCode::Char('v') => app.cycle_kanban_layout()?,
+            KeyCode::Char('o') => app.kanban_cycle_sort(),
+            KeyCode::Char('K') => app.kanban_reorder(false),
+            KeyCode::Char('J') => app.kanban_reorder(true),
+            KeyCode::Enter => app.kanban_enter(),
+            KeyCode::Char('|') => app.toggle_kanban_split(),
+            KeyCode::Char(' ') => app.kanban_peek = !app.kanban_peek,
             _ => {}
         },
         _ => {} // Other views handled above
@@ -0,0 +1,50 @@
+//! Two panes side by side with one of them focused: the Compact list and its
+//! preview, or the Kanban board and the Gantt of the selected card's project.
+//! `Ctrl-w` moves the focus; keys go to the focused pane.
+
+use super::THEME;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+};
+
+/// Below this width a split shows only its focused pane
+pub const MIN_SPLIT_WIDTH: u16 = 120;
+
+/// Which pane of a split has the keyboard
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Side {
+    #[default]
+    Left,
+    Right,
+}
+
+impl Side {
+    pub fn other(self) -> Self {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+}
+
+/// `area` cut into a left and right pane, the right one taking `right_percent`
+pub fn split(area: Rect, right_percent: u16) -> [Rect; 2] {
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(100u16.saturating_sub(right_percent)),
+            Constraint::Percentage(right_percent),
+        ])
+        .split(area);
+    [panes[0], panes[1]]
+}
+
+/// Border of a pane, brighter while it has focus
+pub fn border_style(focused: bool) -> Style {
+    if focused {
+        THEME.border_focused_style()
+    } else {
+        THEME.border_style()
+    }
+}
@@ -0,0 +1,119 @@
+use super::{app::App, THEME};
+use crate::delegation;
+use chrono::Utc;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, size: Rect, app: &App) {
+    // Main layout: header, content, footer
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // Header
+            Constraint::Min(0),     // Content
+            Constraint::Length(3),  // Footer
+        ])
+        .split(size);
+
+    render_header(frame, chunks[0], app);
+    render_content(frame, chunks[1], app);
+    render_footer(frame, chunks[2]);
+}
+
+fn render_header(frame: &mut Frame, area: Rect, app: &App) {
+    let mut spans = vec![Span::styled("  PEOPLE - Waiting On", THEME.title_style())];
+    let due = delegation::follow_ups_due(&app.tasks, app.config.follow_up_days, Utc::now()).len();
+    if due > 0 {
+        spans.push(Span::styled(format!("  {} follow-ups due, f to create", due), THEME.accent_style()));
+    }
+    let title = vec![Line::from(spans)];
+
+    let header = Paragraph::new(title)
+        .block(Block::default().borders(Borders::BOTTOM).border_style(THEME.border_style()));
+
+    frame.render_widget(header, area);
+}
+
+fn render_content(frame: &mut Frame, area: Rect, app: &App) {
+    let people = delegation::waiting_by_person(&app.tasks);
+    let now = Utc::now();
+    let follow_up_days = app.config.follow_up_days as i64;
+    let mut items = Vec::new();
+
+    if people.is_empty() {
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled("  Nothing delegated. Press 'D' on a task to hand it off.", THEME.dim_style()),
+        ])));
+    }
+
+    let mut offset = 0;
+    for (person, tasks) in &people {
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(format!("  {}", person), THEME.accent_style()),
+            Span::styled(format!(" ({})", tasks.len()), THEME.dim_style()),
+        ])));
+
+        for (idx, task) in tasks.iter().enumerate() {
+            let is_selected = offset + idx == app.people_selected;
+            let days = task.days_waiting(now).unwrap_or(0);
+            let overdue = follow_up_days > 0 && days >= follow_up_days;
+
+            let title_span = if is_selected {
+                Span::styled(format!(" {}", task.frontmatter.title), THEME.highlight_style())
+            } else {
+                Span::styled(format!(" {}", task.frontmatter.title), THEME.normal_style())
+            };
+
+            items.push(ListItem::new(Line::from(vec![
+                if is_selected {
+                    Span::styled("   ▸ ", THEME.accent_style())
+                } else {
+                    Span::raw("     ")
+                },
+                app.priority_badge(&task.frontmatter.priority),
+                title_span,
+                Span::raw("  "),
+                Span::styled(
+                    format!("waiting {}d", days),
+                    if overdue { THEME.accent_style() } else { THEME.dim_style() },
+                ),
+            ])));
+        }
+        offset += tasks.len();
+        items.push(ListItem::new(""));
+    }
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(THEME.border_style()),
+    );
+
+    frame.render_widget(list, area);
+}
+
+fn render_footer(frame: &mut Frame, area: Rect) {
+    let help_items = vec![
+        Span::styled("↑↓", THEME.accent_style()),
+        Span::raw(" nav  "),
+        Span::styled("d", THEME.accent_style()),
+        Span::raw(" delivered  "),
+        Span::styled("D", THEME.accent_style()),
+        Span::raw(" reassign  "),
+        Span::styled("f", THEME.accent_style()),
+        Span::raw(" follow-ups  "),
+        Span::styled("Esc", THEME.accent_style()),
+        Span::raw(" back  "),
+        Span::styled("q", THEME.accent_style()),
+        Span::raw(" quit"),
+    ];
+
+    let footer = Paragraph::new(Line::from(help_items))
+        .block(Block::default().borders(Borders::TOP).border_style(THEME.border_style()));
+
+    frame.render_widget(footer, area);
+}
@@ -0,0 +1,82 @@
+use super::input::TextInput;
+use super::THEME;
+use anyhow::Result;
+use crossterm::{
+    event::{self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame, Terminal,
+};
+use std::io;
+
+/// A single-input prompt filling a small terminal window, as opened by a desktop
+/// hotkey. Returns the entered text, or `None` if cancelled.
+pub fn capture_prompt() -> Result<Option<String>> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let res = prompt_loop(&mut terminal);
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableBracketedPaste)?;
+    terminal.show_cursor()?;
+    res
+}
+
+fn prompt_loop<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>) -> Result<Option<String>> {
+    let mut input = TextInput::default();
+    loop {
+        terminal.draw(|f| render(f, &input))?;
+
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+                KeyCode::Esc => return Ok(None),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(None),
+                KeyCode::Enter => return Ok(Some(input.trim().to_string())),
+                _ => { input.handle_key(key); }
+            },
+            Event::Paste(text) => input.insert_str(&text),
+            _ => {}
+        }
+    }
+}
+
+fn render(frame: &mut Frame, input: &TextInput) {
+    let area = frame.area();
+    let width = 70.min(area.width);
+    let prompt_area = Rect {
+        x: (area.width - width) / 2,
+        y: area.height.saturating_sub(4) / 2,
+        width,
+        height: 3.min(area.height),
+    };
+
+    let prompt = Paragraph::new(input.to_line(" ", THEME.normal_style()))
+        .block(
+            Block::default()
+                .title(" Capture to inbox ")
+                .title_style(THEME.accent_style())
+                .borders(Borders::ALL)
+                .border_style(THEME.border_focused_style())
+        );
+    frame.render_widget(prompt, prompt_area);
+
+    let help_area = Rect { y: prompt_area.y + prompt_area.height, height: 1, ..prompt_area };
+    if help_area.bottom() <= area.height {
+        let help = Line::from(vec![
+            Span::styled(" Enter", THEME.accent_style()),
+            Span::raw(" save  "),
+            Span::styled("Esc", THEME.accent_style()),
+            Span::raw(" cancel"),
+        ]);
+        frame.render_widget(Paragraph::new(help), help_area);
+    }
+}
@@ -0,0 +1,107 @@
+use super::{app::{App, ProjectChart}, THEME};
+use crate::flow::{ProjectFlow, FLOW_STATUSES};
+use crate::models::{self, Status};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Style},
+    symbols,
+    text::Span,
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+    Frame,
+};
+
+/// Legend name, colour and points of one line
+type Series<'a> = (&'a str, Color, Vec<(f64, f64)>);
+
+/// Burndown or cumulative flow chart for the project open in the Gantt view
+pub fn render(frame: &mut Frame, area: Rect, app: &App) {
+    let (Some(chart), Some(project)) = (app.project_chart, app.get_current_project()) else {
+        return;
+    };
+    let flow = ProjectFlow::new(&app.tasks, project, models::today());
+
+    let title = match chart {
+        ProjectChart::Burndown => " Burndown ",
+        ProjectChart::CumulativeFlow => " Cumulative Flow ",
+    };
+    let block = Block::default()
+        .title(title)
+        .title_style(THEME.accent_style())
+        .borders(Borders::ALL)
+        .border_style(THEME.border_style());
+
+    if flow.days.len() < 2 {
+        let empty = Paragraph::new(Span::styled("  Not enough history yet; check back tomorrow.", THEME.dim_style()))
+            .block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let points = |series: &[usize]| -> Vec<(f64, f64)> {
+        series.iter().enumerate().map(|(x, y)| (x as f64, *y as f64)).collect()
+    };
+    // Later entries are drawn on top
+    let series: Vec<Series> = match chart {
+        ProjectChart::Burndown => vec![
+            (
+                "ideal",
+                THEME.text_dim,
+                flow.ideal.iter().enumerate().map(|(x, y)| (x as f64, *y)).collect(),
+            ),
+            ("open", THEME.accent, points(&flow.remaining)),
+        ],
+        // Top band first so its legend entry lines up with the stack
+        ProjectChart::CumulativeFlow => FLOW_STATUSES.iter()
+            .zip(flow.stacked())
+            .rev()
+            .map(|(status, band)| (status.as_str(), status_color(status), points(&band)))
+            .collect(),
+    };
+
+    let max_y = series.iter()
+        .flat_map(|(_, _, points)| points.iter().map(|(_, y)| *y))
+        .fold(1.0, f64::max)
+        .ceil();
+    let last_x = (flow.days.len() - 1) as f64;
+    let date = |idx: usize| flow.days[idx].format("%b %d").to_string();
+
+    let datasets = series.iter()
+        .map(|(name, color, points)| {
+            Dataset::default()
+                .name(*name)
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(*color))
+                .data(points)
+        })
+        .collect();
+
+    let chart = Chart::new(datasets)
+        .block(block)
+        // Keep the legend in short panes too
+        .hidden_legend_constraints((Constraint::Ratio(1, 3), Constraint::Ratio(2, 3)))
+        .x_axis(
+            Axis::default()
+                .style(THEME.dim_style())
+                .bounds([0.0, last_x])
+                .labels([date(0), date(flow.days.len() / 2), date(flow.days.len() - 1)]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(THEME.dim_style())
+                .bounds([0.0, max_y])
+                .labels(["0".to_string(), format!("{}", max_y / 2.0), format!("{}", max_y)]),
+        );
+
+    frame.render_widget(chart, area);
+}
+
+fn status_color(status: &Status) -> Color {
+    match status {
+        Status::Done | Status::Archived => THEME.primary,
+        Status::Active => THEME.accent,
+        Status::Next => THEME.secondary,
+        Status::Waiting => THEME.warning,
+        Status::Inbox => THEME.foreground,
+    }
+}
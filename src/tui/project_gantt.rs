@@ -1,5 +1,7 @@
-use super::{app::App, THEME};
-use chrono::{NaiveDate, Utc, Duration};
+use super::{app::{App, ViewMode}, glyphs::Glyphs, project_chart, text, THEME};
+use crate::config::CalendarConfig;
+use crate::models;
+use chrono::{NaiveDate, Duration};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     text::{Line, Span},
@@ -7,13 +9,7 @@ use ratatui::{
     Frame,
 };
 
-const TASK_NAME_WIDTH: usize = 20;
-const BAR_FULL: &str = "█";
-const BAR_EMPTY: &str = "░";
-
-pub fn render(frame: &mut Frame, app: &App) {
-    let size = frame.area();
-
+pub fn render(frame: &mut Frame, size: Rect, app: &App) {
     // Main layout: header, content, footer
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -25,7 +21,16 @@ pub fn render(frame: &mut Frame, app: &App) {
         .split(size);
 
     render_header(frame, chunks[0], app);
-    render_gantt(frame, chunks[1], app);
+    if app.project_chart.is_some() {
+        let panes = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Percentage(45)])
+            .split(chunks[1]);
+        render_gantt(frame, panes[0], app);
+        project_chart::render(frame, panes[1], app);
+    } else {
+        render_gantt(frame, chunks[1], app);
+    }
     render_footer(frame, chunks[2]);
 }
 
@@ -48,18 +53,22 @@ fn render_header(frame: &mut Frame, area: Rect, app: &App) {
 
 fn render_gantt(frame: &mut Frame, area: Rect, app: &App) {
     let tasks = app.get_project_tasks();
-    let timeline_width = (area.width as usize).saturating_sub(TASK_NAME_WIDTH + 4);
+    // Task-name column width is resizable with Ctrl-←/→
+    let name_width = app.layout(ViewMode::ProjectGantt).sidebar_width as usize;
+    let timeline_width = (area.width as usize).saturating_sub(name_width + 4);
 
     // Calculate date range
-    let today = Utc::now().date_naive();
+    let today = models::today();
     let (min_date, max_date) = calculate_date_range(&tasks, today, app.gantt_scroll_offset);
     let total_days = (max_date - min_date).num_days().max(1) as usize;
     let days_per_char = (total_days as f64 / timeline_width as f64).max(1.0);
 
     let mut items = Vec::new();
 
-    // Month header
-    items.push(ListItem::new(create_month_header(min_date, max_date, timeline_width)));
+    // Month and week headers, with non-working days shaded
+    let calendar = &app.config.calendar;
+    items.push(ListItem::new(create_month_header(min_date, max_date, timeline_width, name_width, calendar)));
+    items.push(ListItem::new(create_week_header(min_date, days_per_char, timeline_width, name_width, calendar)));
 
     // Today marker position
     let today_col = date_to_col(today, min_date, days_per_char, timeline_width);
@@ -72,45 +81,30 @@ fn render_gantt(frame: &mut Frame, area: Rect, app: &App) {
         for (idx, task) in tasks.iter().enumerate() {
             let is_selected = idx == app.gantt_selected;
 
-            // Task name (truncated)
-            let mut name = task.frontmatter.title.clone();
-            if name.len() > TASK_NAME_WIDTH - 3 {
-                name.truncate(TASK_NAME_WIDTH - 6);
-                name.push_str("...");
-            }
+            // Task name, truncated and padded to the name column
+            let name = text::fit(&task.frontmatter.title, name_width.saturating_sub(3));
 
             // Get task dates
-            let start = parse_date(task.frontmatter.start_date.as_deref())
-                .or_else(|| parse_date(task.frontmatter.due_date.as_deref()))
-                .unwrap_or(today);
-
-            let end = parse_date(task.frontmatter.end_date.as_deref())
-                .or_else(|| parse_date(task.frontmatter.due_date.as_deref()))
-                .unwrap_or(start + Duration::days(7));
+            let (start, end) = task.schedule(today);
 
             // Calculate bar position
             let start_col = date_to_col(start, min_date, days_per_char, timeline_width);
             let end_col = date_to_col(end, min_date, days_per_char, timeline_width);
-
-            // Progress
-            let progress = match task.frontmatter.status {
-                crate::models::Status::Done | crate::models::Status::Archived => 100,
-                _ => task.frontmatter.progress.unwrap_or(0) as usize,
-            };
+            let progress = task.percent_done() as usize;
 
             // Render bar
-            let bar = render_bar(start_col, end_col, progress, timeline_width, Some(today_col));
+            let bar = render_bar(app.glyphs(), start_col, end_col, progress, timeline_width, Some(today_col));
 
             // Selection indicator
             let name_span = if is_selected {
                 vec![
                     Span::styled(" ▸ ", THEME.accent_style()),
-                    Span::styled(format!("{:<width$}", name, width = TASK_NAME_WIDTH - 3), THEME.highlight_style()),
+                    Span::styled(name, THEME.highlight_style()),
                 ]
             } else {
                 vec![
                     Span::raw("   "),
-                    Span::styled(format!("{:<width$}", name, width = TASK_NAME_WIDTH - 3), THEME.normal_style()),
+                    Span::styled(name, THEME.normal_style()),
                 ]
             };
 
@@ -124,7 +118,7 @@ fn render_gantt(frame: &mut Frame, area: Rect, app: &App) {
 
     // Today indicator line
     let mut today_line = vec![
-        Span::raw(" ".repeat(TASK_NAME_WIDTH)),
+        Span::raw(" ".repeat(name_width)),
         Span::raw("│"),
     ];
     if today_col < timeline_width {
@@ -149,8 +143,14 @@ fn render_footer(frame: &mut Frame, area: Rect) {
         Span::raw(" nav  "),
         Span::styled("←→", THEME.accent_style()),
         Span::raw(" scroll  "),
+        Span::styled("[]", THEME.accent_style()),
+        Span::raw(" shift dates  "),
         Span::styled("n", THEME.accent_style()),
         Span::raw(" new task  "),
+        Span::styled("c", THEME.accent_style()),
+        Span::raw(" charts  "),
+        Span::styled("E", THEME.accent_style()),
+        Span::raw(" export  "),
         Span::styled("Esc", THEME.accent_style()),
         Span::raw(" back  "),
         Span::styled("q", THEME.accent_style()),
@@ -168,14 +168,12 @@ fn calculate_date_range(tasks: &[&crate::models::TaskItem], today: NaiveDate, sc
     let mut max_date = today + Duration::days(30);
 
     for task in tasks {
-        if let Some(start) = parse_date(task.frontmatter.start_date.as_deref()) {
+        if let Some(start) = task.frontmatter.start_date {
             if start < min_date {
                 min_date = start;
             }
         }
-        if let Some(end) = parse_date(task.frontmatter.end_date.as_deref())
-            .or_else(|| parse_date(task.frontmatter.due_date.as_deref()))
-        {
+        if let Some(end) = task.frontmatter.end_date.or(task.frontmatter.due_date.map(|d| d.local_date())) {
             if end > max_date {
                 max_date = end;
             }
@@ -183,23 +181,19 @@ fn calculate_date_range(tasks: &[&crate::models::TaskItem], today: NaiveDate, sc
     }
 
     // Apply scroll offset
-    min_date = min_date + Duration::days(scroll_offset as i64);
-    max_date = max_date + Duration::days(scroll_offset as i64);
+    min_date += Duration::days(scroll_offset as i64);
+    max_date += Duration::days(scroll_offset as i64);
 
     (min_date, max_date)
 }
 
-fn parse_date(date_str: Option<&str>) -> Option<NaiveDate> {
-    date_str.and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
-}
-
 fn date_to_col(date: NaiveDate, min_date: NaiveDate, days_per_char: f64, max_col: usize) -> usize {
     let days = (date - min_date).num_days().max(0) as f64;
     let col = (days / days_per_char) as usize;
     col.min(max_col.saturating_sub(1))
 }
 
-fn render_bar(start_col: usize, end_col: usize, progress: usize, total_width: usize, today_col: Option<usize>) -> String {
+fn render_bar(glyphs: &Glyphs, start_col: usize, end_col: usize, progress: usize, total_width: usize, today_col: Option<usize>) -> String {
     let mut result = vec![' '; total_width];
 
     let bar_length = end_col.saturating_sub(start_col).max(1);
@@ -208,28 +202,33 @@ fn render_bar(start_col: usize, end_col: usize, progress: usize, total_width: us
     for i in 0..bar_length {
         let col = start_col + i;
         if col < total_width {
-            result[col] = if i < filled { '█' } else { '░' };
+            result[col] = if i < filled { glyphs.bar_full } else { glyphs.bar_empty };
         }
     }
 
     // Insert today marker if it's in range
     if let Some(today) = today_col {
         if today < total_width && result[today] == ' ' {
-            result[today] = '│';
+            result[today] = glyphs.today;
         }
     }
 
     result.iter().collect()
 }
 
-fn create_month_header(min_date: NaiveDate, max_date: NaiveDate, width: usize) -> Line<'static> {
+fn create_month_header(
+    min_date: NaiveDate,
+    max_date: NaiveDate,
+    width: usize,
+    name_width: usize,
+    calendar: &CalendarConfig,
+) -> Line<'static> {
     let total_days = (max_date - min_date).num_days().max(1) as usize;
     let days_per_char = (total_days as f64 / width as f64).max(1.0);
 
-    let mut header = " ".repeat(TASK_NAME_WIDTH);
+    let mut header = " ".repeat(name_width);
     header.push('│');
 
-    let mut current = min_date;
     let mut last_month = None;
     let mut result = String::new();
 
@@ -262,9 +261,75 @@ fn create_month_header(min_date: NaiveDate, max_date: NaiveDate, width: usize) -
 
     header.push_str(&result);
 
-    Line::from(vec![
-        Span::raw(" ".repeat(TASK_NAME_WIDTH)),
+    let mut spans = vec![
+        Span::raw(" ".repeat(name_width)),
         Span::styled("│", THEME.border_style()),
-        Span::styled(result, THEME.dim_style()),
-    ])
+    ];
+    spans.extend(shade_non_working(&result, min_date, days_per_char, calendar));
+    Line::from(spans)
+}
+
+/// The day of the month at the start of each week (`week_start` in the
+/// calendar config)
+fn create_week_header(
+    min_date: NaiveDate,
+    days_per_char: f64,
+    width: usize,
+    name_width: usize,
+    calendar: &CalendarConfig,
+) -> Line<'static> {
+    let mut result = vec![' '; width];
+    let mut free_from = 0;
+    let mut last_week = None;
+    for col in 0..width {
+        let date = min_date + Duration::days((col as f64 * days_per_char) as i64);
+        let week = calendar.week_start_of(date);
+        if last_week.replace(week) == Some(week) || col < free_from {
+            continue;
+        }
+        // The first column only gets a label when a week starts right there
+        if col == 0 && week != date {
+            continue;
+        }
+        let label = week.format("%-d").to_string();
+        for (i, c) in label.chars().enumerate() {
+            if let Some(cell) = result.get_mut(col + i) {
+                *cell = c;
+            }
+        }
+        free_from = col + label.len() + 1;
+    }
+
+    let mut spans = vec![
+        Span::raw(" ".repeat(name_width)),
+        Span::styled("│", THEME.border_style()),
+    ];
+    spans.extend(shade_non_working(&result.iter().collect::<String>(), min_date, days_per_char, calendar));
+    Line::from(spans)
+}
+
+/// Split a header row into runs, shading the columns that fall on
+/// non-working days. Once a column spans two or more days, weekends would
+/// only be noise and just holidays are shaded.
+fn shade_non_working(row: &str, min_date: NaiveDate, days_per_char: f64, calendar: &CalendarConfig) -> Vec<Span<'static>> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut run = String::new();
+    let mut run_shaded = false;
+    for (col, c) in row.chars().enumerate() {
+        let date = min_date + Duration::days((col as f64 * days_per_char) as i64);
+        let shaded = calendar.holiday_on(date).is_some() || (days_per_char < 2.0 && !calendar.is_working_day(date));
+        if shaded != run_shaded && !run.is_empty() {
+            spans.push(header_span(std::mem::take(&mut run), run_shaded));
+        }
+        run_shaded = shaded;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        spans.push(header_span(run, run_shaded));
+    }
+    spans
+}
+
+fn header_span(text: String, shaded: bool) -> Span<'static> {
+    Span::styled(text, if shaded { THEME.weekend_style() } else { THEME.dim_style() })
 }
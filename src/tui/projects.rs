@@ -1,4 +1,5 @@
-use super::{app::App, THEME};
+use super::{app::App, virtual_list, THEME};
+use crate::models;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     text::{Line, Span},
@@ -6,9 +7,7 @@ use ratatui::{
     Frame,
 };
 
-pub fn render(frame: &mut Frame, app: &App) {
-    let size = frame.area();
-
+pub fn render(frame: &mut Frame, size: Rect, app: &App) {
     // Main layout: header, content, footer
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -39,78 +38,86 @@ fn render_header(frame: &mut Frame, area: Rect) {
 
 fn render_content(frame: &mut Frame, area: Rect, app: &App) {
     let projects = app.get_projects();
-    let mut items = Vec::new();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(THEME.border_style());
 
     if projects.is_empty() {
-        items.push(ListItem::new(Line::from(vec![
+        let empty = List::new(vec![ListItem::new(Line::from(vec![
             Span::styled("  No projects yet. Press 'n' to create one.", THEME.dim_style()),
-        ])));
-    } else {
-        for (idx, project) in projects.iter().enumerate() {
-            let is_selected = idx == app.projects_selected;
-            let project_id = project.frontmatter.id;
-
-            // Calculate progress
-            let progress = app.calculate_project_progress(project_id);
-            let (total, done, active) = app.project_task_counts(project_id);
-
-            // Progress bar (10 chars)
-            let filled = (progress as usize) / 10;
-            let empty = 10 - filled;
-            let progress_bar = format!(
-                "[{}{}]",
-                "█".repeat(filled),
-                "░".repeat(empty)
-            );
-
-            // Due date
-            let due = project.frontmatter.end_date.as_deref()
-                .or(project.frontmatter.due_date.as_deref())
-                .unwrap_or("No due date");
-
-            // Selection indicator and title
-            let title_line = if is_selected {
-                Line::from(vec![
-                    Span::styled(" ▸ ", THEME.accent_style()),
-                    Span::styled(&project.frontmatter.title, THEME.highlight_style()),
-                ])
-            } else {
-                Line::from(vec![
-                    Span::raw("   "),
-                    Span::styled(&project.frontmatter.title, THEME.normal_style()),
-                ])
-            };
-
-            // Info line with progress bar
-            let info_line = Line::from(vec![
-                Span::raw("     "),
-                Span::styled(progress_bar, if progress >= 100 { THEME.accent_style() } else { THEME.dim_style() }),
-                Span::styled(format!(" {}%", progress), THEME.dim_style()),
-                Span::raw("   "),
-                Span::styled(format!("Due: {}", due), THEME.dim_style()),
-            ]);
-
-            // Stats line
-            let stats_line = Line::from(vec![
-                Span::raw("     "),
-                Span::styled(format!("{} tasks", total), THEME.dim_style()),
-                Span::raw("  •  "),
-                Span::styled(format!("{} done", done), THEME.dim_style()),
-                Span::raw("  •  "),
-                Span::styled(format!("{} active", active), THEME.dim_style()),
-            ]);
-
-            items.push(ListItem::new(vec![title_line, info_line, stats_line, Line::from("")]));
-        }
+        ]))]);
+        frame.render_widget(empty.block(block), area);
+        return;
     }
 
-    let list = List::new(items).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(THEME.border_style()),
+    // Progress and counts scan every task, so only compute them for visible projects
+    let project_item = |idx: usize| {
+        let project = projects[idx];
+        let is_selected = idx == app.projects_selected;
+        let project_id = project.frontmatter.id;
+
+        // Calculate progress
+        let progress = app.calculate_project_progress(project_id);
+        let (total, done, active) = app.project_task_counts(project_id);
+
+        // Progress bar (10 chars)
+        let filled = (progress as usize) / 10;
+        let empty = 10 - filled;
+        let progress_bar = format!(
+            "[{}{}]",
+            app.glyphs().bar_full.to_string().repeat(filled),
+            app.glyphs().bar_empty.to_string().repeat(empty)
+        );
+
+        // Due date
+        let due = project.frontmatter.end_date
+            .or(project.frontmatter.due_date.map(|d| d.local_date()))
+            .map_or("No due date".to_string(), |d| app.config.display.date_label(d, models::today()));
+
+        // Selection indicator and title
+        let title_line = if is_selected {
+            Line::from(vec![
+                Span::styled(" ▸ ", THEME.accent_style()),
+                Span::styled(&project.frontmatter.title, THEME.highlight_style()),
+            ])
+        } else {
+            Line::from(vec![
+                Span::raw("   "),
+                Span::styled(&project.frontmatter.title, THEME.normal_style()),
+            ])
+        };
+
+        // Info line with progress bar
+        let info_line = Line::from(vec![
+            Span::raw("     "),
+            Span::styled(progress_bar, if progress >= 100 { THEME.accent_style() } else { THEME.dim_style() }),
+            Span::styled(format!(" {}%", progress), THEME.dim_style()),
+            Span::raw("   "),
+            Span::styled(format!("Due: {}", due), THEME.dim_style()),
+        ]);
+
+        // Stats line
+        let stats_line = Line::from(vec![
+            Span::raw("     "),
+            Span::styled(format!("{} tasks", total), THEME.dim_style()),
+            Span::raw("  •  "),
+            Span::styled(format!("{} done", done), THEME.dim_style()),
+            Span::raw("  •  "),
+            Span::styled(format!("{} active", active), THEME.dim_style()),
+        ]);
+
+        ListItem::new(vec![title_line, info_line, stats_line, Line::from("")])
+    };
+
+    virtual_list::render(
+        frame,
+        area,
+        Some(block),
+        &app.scroll.projects,
+        Some(app.projects_selected),
+        projects.len(),
+        project_item,
     );
-
-    frame.render_widget(list, area);
 }
 
 fn render_footer(frame: &mut Frame, area: Rect) {
@@ -121,6 +128,10 @@ fn render_footer(frame: &mut Frame, area: Rect) {
         Span::raw(" gantt  "),
         Span::styled("n", THEME.accent_style()),
         Span::raw(" new project  "),
+        Span::styled("G", THEME.accent_style()),
+        Span::raw(" goal  "),
+        Span::styled("E", THEME.accent_style()),
+        Span::raw(" export  "),
         Span::styled("Esc", THEME.accent_style()),
         Span::raw(" back  "),
         Span::styled("q", THEME.accent_style()),
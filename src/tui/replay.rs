@@ -0,0 +1,222 @@
+//! Scripted key replay for end-to-end tests (`tasktui --replay keys.txt`,
+//! behind the `replay` feature).
+//!
+//! A script is typed line by line: plain characters are key presses, and
+//! `<Name>` is a named key, optionally with modifiers (`<C-Right>`, `<A-Enter>`).
+//! Blank lines and lines starting with `#` are skipped.
+//!
+//! ```text
+//! # Create a task, move it to Next on the board and complete it
+//! nBuy milk<Enter>
+//! <Tab>L
+//! d
+//! ```
+
+use super::{run_app, App, Input};
+use crate::models::TaskItem;
+use crate::storage::Storage;
+use anyhow::{bail, Context, Result};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use tempfile::TempDir;
+
+const WIDTH: u16 = 100;
+const HEIGHT: u16 = 30;
+
+/// Where a replay ended up
+pub struct Outcome {
+    /// The last frame drawn, one line per row
+    pub screen: String,
+    /// Every task on disk afterwards, by title
+    pub tasks: Vec<TaskItem>,
+}
+
+impl Outcome {
+    /// The screen followed by one line per task, for `--replay` output
+    pub fn dump(&self) -> String {
+        let mut out = self.screen.clone();
+        out.push_str(&"─".repeat(WIDTH as usize));
+        out.push('\n');
+        for task in &self.tasks {
+            let fm = &task.frontmatter;
+            let tags: String = fm.tags.iter().map(|t| format!("  #{}", t)).collect();
+            out.push_str(&format!("{:<8} {:<6} {}{}\n", fm.status.as_str(), fm.priority.as_str(), fm.title, tags));
+        }
+        out
+    }
+}
+
+/// Run `script` through the event loop against a copy of `data_dir` (or an
+/// empty vault if it doesn't exist). The original directory is never written.
+pub fn replay(data_dir: &Path, script: &str) -> Result<Outcome> {
+    let mut keys: VecDeque<KeyEvent> = parse_script(script)?.into();
+    let vault = TempDir::new().context("Failed to create a temporary vault")?;
+    if data_dir.exists() {
+        copy_vault(data_dir, vault.path())?;
+    }
+
+    let mut app = App::new(vault.path().to_path_buf())?;
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT))?;
+    run_app(&mut terminal, &mut app, |app| {
+        // Like a user, wait for tasks to load before typing
+        if app.is_loading() {
+            std::thread::sleep(Duration::from_millis(1));
+            return Ok(Input::Idle);
+        }
+        Ok(keys.pop_front().map_or(Input::End, |key| Input::Event(Event::Key(key))))
+    })?;
+    terminal.draw(|frame| app.render(frame))?;
+
+    let mut tasks = Storage::new(vault.path().to_path_buf())?.load_all_tasks()?;
+    tasks.sort_by(|a, b| a.frontmatter.title.cmp(&b.frontmatter.title));
+    let screen = screen_text(terminal.backend().buffer(), &vault.path().display().to_string());
+    Ok(Outcome { screen, tasks })
+}
+
+fn parse_script(script: &str) -> Result<Vec<KeyEvent>> {
+    let mut keys = Vec::new();
+    for (n, line) in script.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut rest = line;
+        while let Some(c) = rest.chars().next() {
+            if c == '<' {
+                let Some(end) = rest.find('>') else {
+                    bail!("line {}: unclosed '<' (use <lt> for a literal one)", n + 1);
+                };
+                keys.push(parse_key(&rest[1..end]).with_context(|| format!("line {}", n + 1))?);
+                rest = &rest[end + 1..];
+            } else {
+                keys.push(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    Ok(keys)
+}
+
+/// `Enter`, `C-z`, `A-S-Tab`, ...
+fn parse_key(name: &str) -> Result<KeyEvent> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut rest = name;
+    while let Some((prefix, tail)) = rest.split_once('-').filter(|(_, tail)| !tail.is_empty()) {
+        modifiers |= match prefix {
+            "C" => KeyModifiers::CONTROL,
+            "A" => KeyModifiers::ALT,
+            "S" => KeyModifiers::SHIFT,
+            _ => break,
+        };
+        rest = tail;
+    }
+    let code = match rest {
+        "Enter" => KeyCode::Enter,
+        "Esc" => KeyCode::Esc,
+        "Tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+        "Tab" => KeyCode::Tab,
+        "BackTab" => KeyCode::BackTab,
+        "Backspace" | "BS" => KeyCode::Backspace,
+        "Delete" | "Del" => KeyCode::Delete,
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "Home" => KeyCode::Home,
+        "End" => KeyCode::End,
+        "PageUp" => KeyCode::PageUp,
+        "PageDown" => KeyCode::PageDown,
+        "Space" => KeyCode::Char(' '),
+        "lt" => KeyCode::Char('<'),
+        _ if rest.chars().count() == 1 => KeyCode::Char(rest.chars().next().unwrap_or_default()),
+        _ => bail!("unknown key <{}>", name),
+    };
+    Ok(KeyEvent::new(code, modifiers))
+}
+
+/// Copy a vault's files, leaving out its git history so nothing is committed
+/// or pushed on the original's behalf
+fn copy_vault(from: &Path, to: &Path) -> Result<()> {
+    for entry in fs::read_dir(from).with_context(|| format!("Failed to read {}", from.display()))? {
+        let entry = entry?;
+        if entry.file_name() == ".git" {
+            continue;
+        }
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            fs::create_dir_all(&target)?;
+            copy_vault(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// The frame as text, with the temporary vault's path masked so output is
+/// the same from run to run
+fn screen_text(buffer: &Buffer, vault: &str) -> String {
+    let area = buffer.area;
+    let mask = format!("{:<1$}", "<vault>", vault.chars().count());
+    (area.top()..area.bottom())
+        .map(|y| {
+            let line: String = (area.left()..area.right()).map(|x| buffer[(x, y)].symbol()).collect();
+            line.replace(vault, &mask).trim_end().to_string() + "\n"
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Status;
+
+    #[test]
+    fn test_parse_script() {
+        let keys = parse_script("# comment\nab<Enter>\n\n<C-Right><lt><S-Tab>").unwrap();
+        let codes: Vec<_> = keys.iter().map(|k| (k.code, k.modifiers)).collect();
+        assert_eq!(codes, vec![
+            (KeyCode::Char('a'), KeyModifiers::NONE),
+            (KeyCode::Char('b'), KeyModifiers::NONE),
+            (KeyCode::Enter, KeyModifiers::NONE),
+            (KeyCode::Right, KeyModifiers::CONTROL),
+            (KeyCode::Char('<'), KeyModifiers::NONE),
+            (KeyCode::BackTab, KeyModifiers::SHIFT),
+        ]);
+        assert!(parse_script("<Nope>").is_err());
+        assert!(parse_script("a<Enter").is_err());
+    }
+
+    #[test]
+    fn test_create_move_complete_reopen() {
+        let missing = Path::new("/nonexistent/tasktui-replay");
+        let script = "\
+# Create two tasks in the compact view
+nBuy milk<Enter>
+nCall the plumber !high<Enter>
+# On the board, move the selected card to Next, then complete it
+<Tab>
+L
+d
+";
+        let outcome = replay(missing, script).unwrap();
+        let status = |title: &str| {
+            outcome.tasks.iter().find(|t| t.frontmatter.title == title).map(|t| t.frontmatter.status.clone())
+        };
+        assert_eq!(outcome.tasks.len(), 2);
+        assert_eq!(status("Call the plumber"), Some(Status::Done));
+        assert_eq!(status("Buy milk"), Some(Status::Active));
+        assert!(outcome.screen.contains("Buy milk"));
+
+        // The cursor follows the card into Done, so moving it back reopens it
+        let reopened = replay(missing, &format!("{}H\n", script)).unwrap();
+        let task = reopened.tasks.iter().find(|t| t.frontmatter.title == "Call the plumber").unwrap();
+        assert_eq!(task.frontmatter.status, Status::Waiting);
+        assert!(task.frontmatter.completed_at.is_none());
+        assert!(reopened.dump().contains("waiting  p1     Call the plumber"));
+        assert!(reopened.screen.contains("<vault>"));
+    }
+}
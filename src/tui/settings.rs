@@ -1,4 +1,6 @@
-use super::{app::{App, SettingsSection}, THEME};
+use super::{app::{App, SettingsSection, SyncSetting, SYNC_SETTINGS}, THEME};
+use crate::backup;
+use crate::config::CredentialMode;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     text::{Line, Span},
@@ -6,9 +8,7 @@ use ratatui::{
     Frame,
 };
 
-pub fn render(frame: &mut Frame, app: &App) {
-    let size = frame.area();
-
+pub fn render(frame: &mut Frame, size: Rect, app: &App) {
     // Main layout: header, tabs, content, footer
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -45,11 +45,13 @@ fn render_header(frame: &mut Frame, area: Rect) {
 }
 
 fn render_tabs(frame: &mut Frame, area: Rect, app: &App) {
-    let titles = vec!["Workstreams", "Goals & Priorities", "API Keys"];
+    let titles = vec!["Workstreams", "Goals & Priorities", "API Keys", "Sync", "Backups"];
     let selected = match app.settings_section {
         SettingsSection::Workstreams => 0,
         SettingsSection::Goals => 1,
         SettingsSection::ApiKeys => 2,
+        SettingsSection::Sync => 3,
+        SettingsSection::Backups => 4,
     };
 
     let tabs = Tabs::new(titles)
@@ -65,6 +67,8 @@ fn render_content(frame: &mut Frame, area: Rect, app: &App) {
         SettingsSection::Workstreams => render_workstreams(frame, area, app),
         SettingsSection::Goals => render_goals(frame, area, app),
         SettingsSection::ApiKeys => render_api_keys(frame, area, app),
+        SettingsSection::Sync => render_sync(frame, area, app),
+        SettingsSection::Backups => render_backups(frame, area, app),
     }
 }
 
@@ -216,8 +220,11 @@ fn render_api_keys(frame: &mut Frame, area: Rect, app: &App) {
     let has_key = app.config.openai_api_key.is_some();
 
     let key_display = if let Some(key) = &app.config.openai_api_key {
-        if key.len() > 8 {
-            format!("{}...{}", &key[..4], &key[key.len()-4..])
+        let chars: Vec<char> = key.chars().collect();
+        if chars.len() > 8 {
+            let head: String = chars[..4].iter().collect();
+            let tail: String = chars[chars.len() - 4..].iter().collect();
+            format!("{}...{}", head, tail)
         } else {
             "****".to_string()
         }
@@ -258,6 +265,173 @@ fn render_api_keys(frame: &mut Frame, area: Rect, app: &App) {
         Span::styled("https://platform.openai.com/api-keys", THEME.accent_style()),
     ])));
 
+    // Failover chain, marking the provider that answered last
+    let (providers, active) = app.llm_providers();
+    if providers.len() > 1 || !app.config.llm_providers.is_empty() {
+        items.push(ListItem::new(""));
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled("  Providers (llm_providers, tried in order):", THEME.normal_style()),
+        ])));
+        for (i, name) in providers.iter().enumerate() {
+            let is_active = active.as_ref() == Some(name);
+            let marker = if is_active { "● " } else { "  " };
+            let style = if is_active { THEME.accent_style() } else { THEME.dim_style() };
+            items.push(ListItem::new(Line::from(vec![
+                Span::styled(format!("   {}{}. {}", marker, i + 1, name), style),
+                Span::styled(if is_active { "  (active)" } else { "" }, THEME.dim_style()),
+            ])));
+        }
+        if providers.is_empty() {
+            items.push(ListItem::new(Line::from(vec![
+                Span::styled("     (none usable: add API keys)", THEME.warning_style()),
+            ])));
+        }
+    } else if let Some(active) = active {
+        items.push(ListItem::new(""));
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled("  Active provider: ", THEME.normal_style()),
+            Span::styled(active, THEME.accent_style()),
+        ])));
+    }
+
+    // Usage this month, against the budget when one is set
+    if let Some((month, budget)) = app.llm_usage() {
+        items.push(ListItem::new(""));
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(format!("  Usage in {}: ", month.month), THEME.normal_style()),
+            Span::styled(
+                format!(
+                    "{} calls, {} tokens in / {} out",
+                    month.calls, month.prompt_tokens, month.completion_tokens
+                ),
+                THEME.dim_style(),
+            ),
+        ])));
+        let (spend, style) = match budget {
+            Some(budget) if month.cost_usd >= budget => {
+                (format!("${:.4} of ${:.2} budget — enrichment paused", month.cost_usd, budget), THEME.warning_style())
+            }
+            Some(budget) => (format!("${:.4} of ${:.2} budget", month.cost_usd, budget), THEME.accent_style()),
+            None => (format!("${:.4} (no budget: set llm_monthly_budget)", month.cost_usd), THEME.dim_style()),
+        };
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled("  Estimated cost: ", THEME.normal_style()),
+            Span::styled(spend, style),
+        ])));
+    }
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(THEME.border_style()),
+    );
+
+    frame.render_widget(list, area);
+}
+
+fn render_sync(frame: &mut Frame, area: Rect, app: &App) {
+    let mut items = Vec::new();
+
+    items.push(ListItem::new(Line::from(vec![
+        Span::styled("  Git remote the task files sync with:", THEME.dim_style()),
+    ])));
+    items.push(ListItem::new(""));
+
+    let git = &app.config.git;
+    for (idx, setting) in SYNC_SETTINGS.iter().enumerate() {
+        let is_selected = idx == app.settings_selected;
+        let (label, value) = match setting {
+            SyncSetting::Remote => ("Remote URL: ", app.remote_url.clone().unwrap_or_else(|| "(not set)".to_string())),
+            SyncSetting::Credentials => ("Credentials: ", git.credentials.as_str().to_string()),
+            SyncSetting::Token if git.credentials != CredentialMode::Token => ("Access token: ", "(not used)".to_string()),
+            SyncSetting::Token => ("Access token: ", if git.token.is_some() { "****".to_string() } else { "(not set)".to_string() }),
+            SyncSetting::Test => ("Test connection", String::new()),
+        };
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(if is_selected { " ▸ " } else { "   " }, THEME.accent_style()),
+            Span::styled(label, if is_selected { THEME.highlight_style() } else { THEME.normal_style() }),
+            Span::styled(value, THEME.dim_style()),
+        ])));
+    }
+
+    // What the credential mode means
+    items.push(ListItem::new(""));
+    let help = match git.credentials {
+        CredentialMode::System => "  system: git's own credential helpers and SSH config",
+        CredentialMode::Ssh => "  ssh: keys from ssh-agent or ~/.ssh, for git@host:user/repo remotes",
+        CredentialMode::Token => "  token: an access token for an https:// remote (or TASKTUI_GIT__TOKEN)",
+    };
+    items.push(ListItem::new(Line::from(Span::styled(help, THEME.dim_style()))));
+
+    // The last test, or else the last sync, with the fix for a refused sign-in
+    let (heading, outcome) = if app.is_testing_remote() {
+        ("  Testing the connection…", None)
+    } else if let Some(result) = &app.sync_test_result {
+        match result {
+            Ok(()) => ("  ✓ Connected and signed in", None),
+            Err(e) => ("  Connection test failed:", Some(e.clone())),
+        }
+    } else if let Some(e) = app.storage.last_sync_error() {
+        ("  Last sync failed:", Some(e))
+    } else {
+        ("", None)
+    };
+    if !heading.is_empty() {
+        items.push(ListItem::new(""));
+        let style = if outcome.is_some() { THEME.warning_style() } else { THEME.accent_style() };
+        items.push(ListItem::new(Line::from(Span::styled(heading, style))));
+    }
+    for line in outcome.iter().flat_map(|e| e.lines()) {
+        items.push(ListItem::new(Line::from(Span::styled(format!("    {}", line), THEME.normal_style()))));
+    }
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(THEME.border_style()),
+    );
+
+    frame.render_widget(list, area);
+}
+
+fn render_backups(frame: &mut Frame, area: Rect, app: &App) {
+    let mut items = vec![
+        ListItem::new(Line::from(Span::styled(
+            "  Snapshots in .backups, newest first. Restoring backs up the current state first:",
+            THEME.dim_style(),
+        ))),
+        ListItem::new(""),
+    ];
+
+    let row = |idx: usize, label: String, detail: String| {
+        let is_selected = idx == app.settings_selected;
+        ListItem::new(Line::from(vec![
+            Span::styled(if is_selected { " ▸ " } else { "   " }, THEME.accent_style()),
+            Span::styled(label, if is_selected { THEME.highlight_style() } else { THEME.normal_style() }),
+            Span::styled(detail, THEME.dim_style()),
+        ]))
+    };
+    items.push(row(0, "Back up now".to_string(), String::new()));
+    for (idx, backup) in app.backups.iter().enumerate() {
+        let reason = match &backup.reason {
+            Some(reason) => format!("before {}", reason.replace('-', " ")),
+            None => "by hand".to_string(),
+        };
+        let encrypted = if backup.encrypted { ", encrypted" } else { "" };
+        items.push(row(
+            idx + 1,
+            backup.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            format!("  {}  ({}{})", reason, backup::format_size(backup.size), encrypted),
+        ));
+    }
+    if app.backups.is_empty() {
+        items.push(ListItem::new(""));
+        items.push(ListItem::new(Line::from(Span::styled(
+            "  No backups yet. One is taken before each bulk archive, import, tag rename and bulk enrichment.",
+            THEME.dim_style(),
+        ))));
+    }
+
     let list = List::new(items).block(
         Block::default()
             .borders(Borders::ALL)
@@ -270,7 +444,7 @@ fn render_api_keys(frame: &mut Frame, area: Rect, app: &App) {
 fn render_footer(frame: &mut Frame, area: Rect, app: &App) {
     let help_items = match app.settings_section {
         SettingsSection::Workstreams => vec![
-            Span::styled("Tab", THEME.accent_style()),
+            Span::styled("Tab/⇧Tab", THEME.accent_style()),
             Span::raw(" section  "),
             Span::styled("↑↓", THEME.accent_style()),
             Span::raw(" nav  "),
@@ -282,7 +456,7 @@ fn render_footer(frame: &mut Frame, area: Rect, app: &App) {
             Span::raw(" back"),
         ],
         SettingsSection::Goals => vec![
-            Span::styled("Tab", THEME.accent_style()),
+            Span::styled("Tab/⇧Tab", THEME.accent_style()),
             Span::raw(" section  "),
             Span::styled("↑↓", THEME.accent_style()),
             Span::raw(" nav  "),
@@ -298,7 +472,7 @@ fn render_footer(frame: &mut Frame, area: Rect, app: &App) {
             Span::raw(" back"),
         ],
         SettingsSection::ApiKeys => vec![
-            Span::styled("Tab", THEME.accent_style()),
+            Span::styled("Tab/⇧Tab", THEME.accent_style()),
             Span::raw(" section  "),
             Span::styled("Enter", THEME.accent_style()),
             Span::raw(" edit  "),
@@ -307,6 +481,28 @@ fn render_footer(frame: &mut Frame, area: Rect, app: &App) {
             Span::styled("Esc", THEME.accent_style()),
             Span::raw(" back"),
         ],
+        SettingsSection::Sync => vec![
+            Span::styled("Tab/⇧Tab", THEME.accent_style()),
+            Span::raw(" section  "),
+            Span::styled("↑↓", THEME.accent_style()),
+            Span::raw(" nav  "),
+            Span::styled("Enter", THEME.accent_style()),
+            Span::raw(" edit/change/test  "),
+            Span::styled("x", THEME.accent_style()),
+            Span::raw(" clear  "),
+            Span::styled("Esc", THEME.accent_style()),
+            Span::raw(" back"),
+        ],
+        SettingsSection::Backups => vec![
+            Span::styled("Tab/⇧Tab", THEME.accent_style()),
+            Span::raw(" section  "),
+            Span::styled("↑↓", THEME.accent_style()),
+            Span::raw(" nav  "),
+            Span::styled("Enter", THEME.accent_style()),
+            Span::raw(" back up/restore  "),
+            Span::styled("Esc", THEME.accent_style()),
+            Span::raw(" back"),
+        ],
     };
 
     let footer = Paragraph::new(Line::from(help_items))
@@ -340,13 +536,9 @@ fn render_edit_dialog(frame: &mut Frame, app: &App) {
                 " New Workstream "
             };
 
-            let input_text = format!("{}_", app.settings_edit_text);
             let content = vec![
                 Line::from(""),
-                Line::from(vec![
-                    Span::raw(" "),
-                    Span::styled(&input_text, THEME.normal_style()),
-                ]),
+                app.settings_edit_text.to_line(" ", THEME.normal_style()),
             ];
 
             let dialog = Paragraph::new(content)
@@ -367,7 +559,6 @@ fn render_edit_dialog(frame: &mut Frame, app: &App) {
                 " New Goal "
             };
 
-            let input_text = format!("{}_", app.settings_edit_text);
             let content = vec![
                 Line::from(""),
                 Line::from(vec![
@@ -376,10 +567,7 @@ fn render_edit_dialog(frame: &mut Frame, app: &App) {
                     Span::styled(" (press Tab to change)", THEME.dim_style()),
                 ]),
                 Line::from(""),
-                Line::from(vec![
-                    Span::raw(" Goal: "),
-                    Span::styled(&input_text, THEME.normal_style()),
-                ]),
+                app.settings_edit_text.to_line(" Goal: ", THEME.normal_style()),
             ];
 
             let dialog = Paragraph::new(content)
@@ -396,13 +584,10 @@ fn render_edit_dialog(frame: &mut Frame, app: &App) {
         SettingsSection::ApiKeys => {
             let title = " Edit OpenAI API Key ";
 
-            let input_text = format!("{}_", app.settings_edit_text);
+            // Masked so the key isn't readable over a shoulder or in a screen share
             let content = vec![
                 Line::from(""),
-                Line::from(vec![
-                    Span::raw(" "),
-                    Span::styled(&input_text, THEME.normal_style()),
-                ]),
+                app.settings_edit_text.to_masked_line(" ", THEME.normal_style()),
                 Line::from(""),
                 Line::from(vec![
                     Span::styled(" Leave empty to clear the key", THEME.dim_style()),
@@ -430,5 +615,34 @@ fn render_edit_dialog(frame: &mut Frame, app: &App) {
 
             frame.render_widget(dialog, api_dialog_area);
         }
+        SettingsSection::Sync => {
+            let token = SYNC_SETTINGS.get(app.settings_selected) == Some(&SyncSetting::Token);
+            let (title, input, hint) = if token {
+                (" Access Token ", app.settings_edit_text.to_masked_line(" ", THEME.normal_style()), " Leave empty to clear the token")
+            } else {
+                (" Remote URL ", app.settings_edit_text.to_line(" ", THEME.normal_style()), " git@host:user/tasks.git or https://… ; empty removes it")
+            };
+            let content = vec![
+                Line::from(""),
+                input,
+                Line::from(""),
+                Line::from(Span::styled(hint, THEME.dim_style())),
+            ];
+            let sync_dialog_area = Rect { height: 7, ..dialog_area };
+            frame.render_widget(Clear, sync_dialog_area);
+
+            let dialog = Paragraph::new(content)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .title_style(THEME.accent_style())
+                        .borders(Borders::ALL)
+                        .border_style(THEME.border_focused_style())
+                );
+
+            frame.render_widget(dialog, sync_dialog_area);
+        }
+        // Nothing to edit: Enter backs up or restores straight away
+        SettingsSection::Backups => {}
     }
 }
@@ -0,0 +1,174 @@
+use super::{app::App, THEME};
+use crate::models;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, size: Rect, app: &App) {
+    // Main layout: header, content, footer
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // Header
+            Constraint::Min(0),     // Content
+            Constraint::Length(3),  // Footer
+        ])
+        .split(size);
+
+    render_header(frame, chunks[0], app);
+    match &app.shutdown_journal {
+        Some(journal) => {
+            let panes = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(8)])
+                .split(chunks[1]);
+            render_content(frame, panes[0], app);
+            let entry = Paragraph::new(journal.as_str())
+                .style(THEME.normal_style())
+                .wrap(Wrap { trim: true })
+                .block(Block::default().title(" Journal ").borders(Borders::ALL).border_style(THEME.border_style()));
+            frame.render_widget(entry, panes[1]);
+        }
+        None => render_content(frame, chunks[1], app),
+    }
+    render_footer(frame, chunks[2]);
+
+    if app.shutdown_rescheduling {
+        render_reschedule_dialog(frame, app);
+    }
+}
+
+fn render_header(frame: &mut Frame, area: Rect, app: &App) {
+    let title = vec![
+        Line::from(vec![
+            Span::styled("  SHUTDOWN - End of Day", THEME.title_style()),
+            Span::styled(format!(" ({})", app.config.display.format_date(models::today())), THEME.dim_style()),
+        ]),
+    ];
+
+    let header = Paragraph::new(title)
+        .block(Block::default().borders(Borders::BOTTOM).border_style(THEME.border_style()));
+
+    frame.render_widget(header, area);
+}
+
+fn render_content(frame: &mut Frame, area: Rect, app: &App) {
+    let completed = app.shutdown_completed();
+    let active = app.shutdown_active();
+    let mut items = Vec::new();
+
+    items.push(ListItem::new(Line::from(Span::styled(
+        format!("  Completed today ({})", completed.len()),
+        THEME.accent_style(),
+    ))));
+    if completed.is_empty() {
+        items.push(ListItem::new(Line::from(Span::styled("    Nothing yet.", THEME.dim_style()))));
+    }
+    for task in completed {
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled("    ✓ ", THEME.accent_style()),
+            Span::styled(task.frontmatter.title.clone(), THEME.normal_style()),
+        ])));
+    }
+
+    items.push(ListItem::new(Line::from("")));
+    items.push(ListItem::new(Line::from(Span::styled(
+        format!("  Still active ({})", active.len()),
+        THEME.accent_style(),
+    ))));
+    if active.is_empty() {
+        items.push(ListItem::new(Line::from(Span::styled(
+            "    Nothing left active. Press Enter to close the day.",
+            THEME.dim_style(),
+        ))));
+    }
+    for (idx, task) in active.iter().enumerate() {
+        let is_selected = idx == app.shutdown_selected;
+        let mut spans = vec![
+            if is_selected {
+                Span::styled("  ▸ ", THEME.accent_style())
+            } else {
+                Span::raw("    ")
+            },
+            app.priority_badge(&task.frontmatter.priority),
+            Span::styled(
+                format!(" {}", task.frontmatter.title),
+                if is_selected { THEME.highlight_style() } else { THEME.normal_style() },
+            ),
+        ];
+        if let Some(due) = task.frontmatter.due_date {
+            spans.push(Span::styled(
+                format!("  {}{}", app.glyphs().due, app.config.display.due_label(&due, models::today())),
+                if task.is_overdue(models::now_local()) { THEME.warning_style() } else { THEME.dim_style() },
+            ));
+        }
+        items.push(ListItem::new(Line::from(spans)));
+    }
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(THEME.border_style()),
+    );
+
+    frame.render_widget(list, area);
+}
+
+fn render_footer(frame: &mut Frame, area: Rect) {
+    let help_items = vec![
+        Span::styled("s", THEME.accent_style()),
+        Span::raw(" reschedule  "),
+        Span::styled("n", THEME.accent_style()),
+        Span::raw(" demote to next  "),
+        Span::styled("J", THEME.accent_style()),
+        Span::raw(" journal  "),
+        Span::styled("↑↓", THEME.accent_style()),
+        Span::raw(" nav  "),
+        Span::styled("Enter", THEME.accent_style()),
+        Span::raw(" sync and quit  "),
+        Span::styled("Esc", THEME.accent_style()),
+        Span::raw(" back"),
+    ];
+
+    let footer = Paragraph::new(Line::from(help_items))
+        .block(Block::default().borders(Borders::TOP).border_style(THEME.border_style()));
+
+    frame.render_widget(footer, area);
+}
+
+fn render_reschedule_dialog(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+
+    // Center the dialog
+    let dialog_width = 50.min(area.width.saturating_sub(4));
+    let dialog_height = 6;
+    let dialog_area = Rect {
+        x: (area.width.saturating_sub(dialog_width)) / 2,
+        y: (area.height.saturating_sub(dialog_height)) / 2,
+        width: dialog_width,
+        height: dialog_height,
+    };
+
+    // Clear the area behind the dialog
+    frame.render_widget(Clear, dialog_area);
+
+    let content = vec![
+        Line::from(""),
+        app.shutdown_input.to_line(" ", THEME.normal_style()),
+        app.due_prompt_hint(&app.shutdown_input),
+    ];
+
+    let dialog = Paragraph::new(content)
+        .block(
+            Block::default()
+                .title(" Reschedule ")
+                .title_style(THEME.accent_style())
+                .borders(Borders::ALL)
+                .border_style(THEME.border_focused_style())
+        );
+
+    frame.render_widget(dialog, dialog_area);
+}
@@ -0,0 +1,234 @@
+//! Rendering snapshots: each main view drawn from a fixture vault into a
+//! `TestBackend` and compared with `src/tui/snapshots/<name>.txt`.
+//! Run with `UPDATE_SNAPSHOTS=1 cargo test snapshot` to accept changes, then
+//! review the diff of the snapshot files.
+
+use super::app::{App, ViewMode};
+use crate::config::TitleMode;
+use crate::models::{self, Due, ItemType, Priority, Status, TaskItem};
+use crate::storage::Storage;
+use chrono::{NaiveDate, NaiveTime, TimeZone, Utc};
+use ratatui::{backend::TestBackend, Terminal};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+/// The fixed "now" every snapshot is rendered at
+fn today() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2025, 3, 12).unwrap()
+}
+
+/// A small vault covering each status, priority, due-date label and a project
+/// with dated tasks. Ids are fixed so codes and load order never change.
+fn fixture() -> (TempDir, App) {
+    models::freeze_clock(today().and_hms_opt(9, 0, 0).unwrap());
+    let day = |offset: i64| today() + chrono::Duration::days(offset);
+
+    let dir = TempDir::new().unwrap();
+    let storage = Storage::new(dir.path().to_path_buf()).unwrap();
+    let item = |n: u128, title: &str, item_type: ItemType| {
+        let mut task = TaskItem::new(title.to_string(), item_type);
+        task.frontmatter.id = Uuid::from_u128(0x5eed_0000_0000_4000_8000_0000_0000_0000 + n);
+        task.frontmatter.code = None;
+        task.frontmatter.created_at = Utc.from_utc_datetime(&day(-14 + n as i64).and_hms_opt(9, 0, 0).unwrap());
+        task
+    };
+
+    let mut project = item(1, "Website relaunch", ItemType::Project);
+    project.frontmatter.start_date = Some(day(-10));
+    project.frontmatter.end_date = Some(day(12));
+    let project_id = project.frontmatter.id;
+    let mut items = vec![project];
+
+    let mut task = item(2, "Write launch post", ItemType::Task);
+    task.frontmatter.priority = Priority::P1;
+    task.frontmatter.tags = vec!["work".to_string()];
+    task.frontmatter.due_date = Some(Due::on(day(0)));
+    task.frontmatter.parent_goal_id = Some(project_id);
+    task.frontmatter.start_date = Some(day(-3));
+    task.frontmatter.end_date = Some(day(2));
+    items.push(task);
+
+    let mut task = item(3, "Fix signup form", ItemType::Task);
+    task.frontmatter.tags = vec!["work".to_string()];
+    task.frontmatter.due_date = Some(Due { date: day(1), time: NaiveTime::from_hms_opt(15, 0, 0), offset: None });
+    task.frontmatter.parent_goal_id = Some(project_id);
+    task.frontmatter.status = Status::Next;
+    items.push(task);
+
+    let mut task = item(4, "Renew passport", ItemType::Task);
+    task.frontmatter.tags = vec!["personal".to_string()];
+    task.frontmatter.due_date = Some(Due::on(day(-2)));
+    task.frontmatter.priority = Priority::P1;
+    items.push(task);
+
+    let mut task = item(5, "Ask Ana for the logo files", ItemType::Task);
+    task.frontmatter.tags = vec!["work".to_string()];
+    task.frontmatter.status = Status::Waiting;
+    task.frontmatter.delegated_to = Some("Ana".to_string());
+    task.frontmatter.parent_goal_id = Some(project_id);
+    items.push(task);
+
+    let mut task = item(6, "Idea: podcast", ItemType::Task);
+    task.frontmatter.status = Status::Inbox;
+    task.frontmatter.priority = Priority::P3;
+    items.push(task);
+
+    let mut task = item(7, "Set up analytics", ItemType::Task);
+    task.frontmatter.tags = vec!["work".to_string()];
+    task.frontmatter.parent_goal_id = Some(project_id);
+    task.frontmatter.status = Status::Done;
+    task.frontmatter.completed_at = Some(Utc.from_utc_datetime(&day(-1).and_hms_opt(16, 0, 0).unwrap()));
+    items.push(task);
+
+    storage.write_tasks(&mut items, "Fixture").unwrap();
+    let mut app = App::new(dir.path().to_path_buf()).unwrap();
+    while app.is_loading() {
+        app.poll_loading().unwrap();
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    app.status_message = None;
+    app.briefing = None;
+    (dir, app)
+}
+
+/// The screen as text, one line per row with trailing blanks trimmed. The
+/// temporary vault path is masked to a same-width placeholder.
+fn render(app: &mut App, width: u16, height: u16) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|frame| app.render(frame)).unwrap();
+    let buffer = terminal.backend().buffer();
+    let vault = app.data_dir.display().to_string();
+    let mask = format!("{:<1$}", "<vault>", vault.chars().count());
+    (0..height)
+        .map(|y| {
+            let line: String = (0..width).map(|x| buffer[(x, y)].symbol()).collect();
+            line.replace(&vault, &mask).trim_end().to_string() + "\n"
+        })
+        .collect()
+}
+
+/// Compare with the stored snapshot, or store it when `UPDATE_SNAPSHOTS` is set
+fn assert_snapshot(name: &str, screen: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/tui/snapshots").join(format!("{}.txt", name));
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, screen).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("No snapshot {}; run with UPDATE_SNAPSHOTS=1 to create it", path.display()));
+    if expected != screen {
+        let diff: String = expected.lines().zip(screen.lines())
+            .enumerate()
+            .filter(|(_, (old, new))| old != new)
+            .map(|(row, (old, new))| format!("row {}:\n  - {}\n  + {}\n", row, old, new))
+            .collect();
+        panic!("{} changed; rerun with UPDATE_SNAPSHOTS=1 if intended\n{}\n{}", name, diff, screen);
+    }
+}
+
+#[test]
+fn snapshot_compact() {
+    let (_dir, mut app) = fixture();
+    assert_snapshot("compact", &render(&mut app, 100, 24));
+    assert_snapshot("compact_narrow", &render(&mut app, 56, 20));
+}
+
+#[test]
+fn snapshot_kanban() {
+    let (_dir, mut app) = fixture();
+    app.view_mode = ViewMode::Kanban;
+    assert_snapshot("kanban", &render(&mut app, 110, 24));
+}
+
+#[test]
+fn snapshot_kanban_split() {
+    let (_dir, mut app) = fixture();
+    app.view_mode = ViewMode::Kanban;
+    app.kanban_move_down();
+    app.toggle_kanban_split();
+    assert_snapshot("kanban_split", &render(&mut app, 160, 24));
+}
+
+#[test]
+fn snapshot_projects() {
+    let (_dir, mut app) = fixture();
+    app.open_projects();
+    assert_snapshot("projects", &render(&mut app, 100, 20));
+}
+
+#[test]
+fn snapshot_gantt() {
+    let (_dir, mut app) = fixture();
+    app.open_projects();
+    app.open_project_gantt();
+    assert_snapshot("gantt", &render(&mut app, 110, 24));
+}
+
+#[test]
+fn snapshot_dashboard() {
+    let (_dir, mut app) = fixture();
+    app.open_dashboard();
+    assert_snapshot("dashboard", &render(&mut app, 100, 24));
+}
+
+#[test]
+fn snapshot_settings() {
+    let (_dir, mut app) = fixture();
+    app.open_settings();
+    assert_snapshot("settings", &render(&mut app, 100, 20));
+}
+
+#[test]
+fn snapshot_briefing() {
+    let (_dir, mut app) = fixture();
+    app.briefed_on = None;
+    app.open_briefing();
+    assert_snapshot("briefing", &render(&mut app, 100, 24));
+}
+
+#[test]
+fn snapshot_wrapped_titles() {
+    let (_dir, mut app) = fixture();
+    let task = app.tasks.iter_mut().find(|t| t.frontmatter.title == "Write launch post").unwrap();
+    task.frontmatter.title = "Write the launch post announcing the relaunch to newsletter subscribers".to_string();
+    app.config.display.titles.compact = TitleMode::Wrap;
+    app.config.display.titles.kanban = TitleMode::Wrap;
+    assert_snapshot("compact_wrapped", &render(&mut app, 80, 20));
+    app.view_mode = ViewMode::Kanban;
+    app.kanban_peek = true;
+    assert_snapshot("kanban_wrapped", &render(&mut app, 110, 24));
+}
+
+#[test]
+fn snapshot_ascii() {
+    let (_dir, mut app) = fixture();
+    app.config.display.ascii_only = true;
+    app.config.priority_style = crate::config::PriorityStyle::Emoji;
+    assert_snapshot("compact_ascii", &render(&mut app, 100, 24));
+    app.view_mode = ViewMode::Kanban;
+    assert_snapshot("kanban_ascii", &render(&mut app, 110, 24));
+    app.open_projects();
+    app.open_project_gantt();
+    assert_snapshot("gantt_ascii", &render(&mut app, 110, 24));
+}
+
+#[test]
+fn hand_edited_layouts_still_render() {
+    let (_dir, mut app) = fixture();
+    let layout = crate::config::PaneLayout { sidebar_width: 0, preview_percent: 150 };
+    for view in ["compact", "gantt"] {
+        app.config.layouts.insert(view.to_string(), layout);
+    }
+    assert_eq!(app.layout(ViewMode::Compact), crate::config::PaneLayout { sidebar_width: 8, preview_percent: 80 });
+
+    app.show_preview = true;
+    render(&mut app, 56, 20);
+    render(&mut app, 160, 24);
+    app.open_projects();
+    app.open_project_gantt();
+    render(&mut app, 110, 24);
+}
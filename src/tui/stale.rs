@@ -0,0 +1,153 @@
+use super::{app::App, THEME};
+use crate::models;
+use chrono::Utc;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, size: Rect, app: &App) {
+    // Main layout: header, content, footer
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // Header
+            Constraint::Min(0),     // Content
+            Constraint::Length(3),  // Footer
+        ])
+        .split(size);
+
+    render_header(frame, chunks[0], app);
+    render_content(frame, chunks[1], app);
+    render_footer(frame, chunks[2]);
+
+    if app.stale_rescheduling {
+        render_reschedule_dialog(frame, app);
+    }
+}
+
+fn render_header(frame: &mut Frame, area: Rect, app: &App) {
+    let title = vec![
+        Line::from(vec![
+            Span::styled("  STALE - Untouched Tasks", THEME.title_style()),
+            Span::styled(
+                format!(" (not updated in {}+ days)", app.config.stale_days),
+                THEME.dim_style(),
+            ),
+        ]),
+    ];
+
+    let header = Paragraph::new(title)
+        .block(Block::default().borders(Borders::BOTTOM).border_style(THEME.border_style()));
+
+    frame.render_widget(header, area);
+}
+
+fn render_content(frame: &mut Frame, area: Rect, app: &App) {
+    let tasks = app.stale_tasks();
+    let now = Utc::now();
+    let mut items = Vec::new();
+
+    if app.config.stale_days == 0 {
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled("  Stale detection is off (stale_days: 0).", THEME.dim_style()),
+        ])));
+    } else if tasks.is_empty() {
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled("  Nothing stale. Every open task was updated recently.", THEME.accent_style()),
+        ])));
+    }
+
+    for (idx, task) in tasks.iter().enumerate() {
+        let is_selected = idx == app.stale_selected;
+        let mut spans = vec![
+            if is_selected {
+                Span::styled("  ▸ ", THEME.accent_style())
+            } else {
+                Span::raw("    ")
+            },
+            app.priority_badge(&task.frontmatter.priority),
+            Span::styled(
+                format!(" {}", task.frontmatter.title),
+                if is_selected { THEME.highlight_style() } else { THEME.normal_style() },
+            ),
+            Span::styled(format!("  {}", task.frontmatter.status.as_str()), THEME.dim_style()),
+            Span::styled(
+                format!("  updated {} days ago", (now - task.last_touched()).num_days()),
+                THEME.warning_style(),
+            ),
+        ];
+        if let Some(due) = task.frontmatter.due_date {
+            spans.push(Span::styled(format!("  {}{}", app.glyphs().due, app.config.display.due_label(&due, models::today())), THEME.dim_style()));
+        }
+        items.push(ListItem::new(Line::from(spans)));
+    }
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(THEME.border_style()),
+    );
+
+    frame.render_widget(list, area);
+}
+
+fn render_footer(frame: &mut Frame, area: Rect) {
+    let help_items = vec![
+        Span::styled("b", THEME.accent_style()),
+        Span::raw(" bump  "),
+        Span::styled("s", THEME.accent_style()),
+        Span::raw(" reschedule  "),
+        Span::styled("m", THEME.accent_style()),
+        Span::raw(" someday  "),
+        Span::styled("a", THEME.accent_style()),
+        Span::raw(" archive  "),
+        Span::styled("↑↓", THEME.accent_style()),
+        Span::raw(" nav  "),
+        Span::styled("Esc", THEME.accent_style()),
+        Span::raw(" back  "),
+        Span::styled("q", THEME.accent_style()),
+        Span::raw(" quit"),
+    ];
+
+    let footer = Paragraph::new(Line::from(help_items))
+        .block(Block::default().borders(Borders::TOP).border_style(THEME.border_style()));
+
+    frame.render_widget(footer, area);
+}
+
+fn render_reschedule_dialog(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+
+    // Center the dialog
+    let dialog_width = 50.min(area.width.saturating_sub(4));
+    let dialog_height = 6;
+    let dialog_area = Rect {
+        x: (area.width.saturating_sub(dialog_width)) / 2,
+        y: (area.height.saturating_sub(dialog_height)) / 2,
+        width: dialog_width,
+        height: dialog_height,
+    };
+
+    // Clear the area behind the dialog
+    frame.render_widget(Clear, dialog_area);
+
+    let content = vec![
+        Line::from(""),
+        app.stale_input.to_line(" ", THEME.normal_style()),
+        app.due_prompt_hint(&app.stale_input),
+    ];
+
+    let dialog = Paragraph::new(content)
+        .block(
+            Block::default()
+                .title(" Reschedule ")
+                .title_style(THEME.accent_style())
+                .borders(Borders::ALL)
+                .border_style(THEME.border_focused_style())
+        );
+
+    frame.render_widget(dialog, dialog_area);
+}
@@ -0,0 +1,72 @@
+use super::app::{KanbanSort, ViewMode};
+use crate::filter::FilterExpr;
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Where the TUI was left, kept in `.tasktui-state.json` so the next start
+/// reopens the same view. Separate from the config because it changes on
+/// every quit and is local to this machine.
+/// Also written after a panic, so unsaved dialog text survives the crash.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiState {
+    pub view: Option<ViewMode>,
+    /// Project open in the Gantt view
+    pub project_id: Option<Uuid>,
+    pub filter: FilterExpr,
+    pub kanban_column: usize,
+    /// Sort of each Kanban column that isn't in manual order, by column name
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub kanban_sort: BTreeMap<String, KanbanSort>,
+    /// Cards moved by hand, top first, by column name
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub kanban_order: BTreeMap<String, Vec<Uuid>>,
+    /// Task under the cursor in `view`
+    pub selected: Option<Uuid>,
+    /// Day the morning briefing was last shown
+    pub briefed_on: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Drafts::is_empty")]
+    pub drafts: Drafts,
+}
+
+/// Text typed into dialogs that were still open when the app stopped
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Drafts {
+    pub new_task: Option<String>,
+    pub new_project: Option<String>,
+    /// Quick note and the task it belongs to
+    pub note: Option<(Uuid, String)>,
+}
+
+impl Drafts {
+    pub fn is_empty(&self) -> bool {
+        self.new_task.is_none() && self.new_project.is_none() && self.note.is_none()
+    }
+}
+
+impl UiState {
+    pub const FILE_NAME: &'static str = ".tasktui-state.json";
+
+    pub fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join(Self::FILE_NAME)
+    }
+
+    /// The saved state; a missing or unreadable file starts from the defaults
+    pub fn load(data_dir: &Path) -> Self {
+        fs::read_to_string(Self::path(data_dir))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, data_dir: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(Self::path(data_dir), content).context("Failed to write UI state")
+    }
+}
@@ -0,0 +1,106 @@
+use super::{app::App, text, THEME};
+use crate::models::Status;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+/// One-line status bar: transient message (or active filter) on the left,
+/// task counts, data dir, git sync state and LLM availability on the right
+pub fn render(frame: &mut Frame, area: Rect, app: &App) {
+    let right = Line::from(right_spans(app));
+    let right_width = (right.width() as u16).min(area.width);
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Min(0),              // Message / filter
+            Constraint::Length(right_width), // Counts and state
+        ])
+        .split(area);
+
+    frame.render_widget(Paragraph::new(left_line(app)), chunks[0]);
+    frame.render_widget(Paragraph::new(right).alignment(Alignment::Right), chunks[1]);
+}
+
+fn left_line(app: &App) -> Line<'_> {
+    if let Some(message) = app.current_status_message() {
+        let style = if message.is_error { THEME.warning_style() } else { THEME.accent_style() };
+        return Line::from(Span::styled(format!(" {}", message.text), style));
+    }
+
+    if app.is_loading() {
+        return Line::from(Span::styled(format!(" Loading tasks… {}", app.tasks.len()), THEME.accent_style()));
+    }
+
+    if app.filter.is_empty() {
+        Line::from(Span::styled(" All tasks", THEME.dim_style()))
+    } else {
+        Line::from(vec![
+            Span::styled(" Filter: ", THEME.dim_style()),
+            Span::styled(app.filter_query(), THEME.tag_style()),
+        ])
+    }
+}
+
+fn right_spans(app: &App) -> Vec<Span<'_>> {
+    let filtered = app.filtered_tasks();
+    let count = |status: Status| filtered.iter().filter(|t| t.frontmatter.status == status).count();
+
+    let mut spans = Vec::new();
+    for (status, label) in [
+        (Status::Inbox, "inbox"),
+        (Status::Active, "active"),
+        (Status::Next, "next"),
+        (Status::Waiting, "waiting"),
+    ] {
+        spans.push(Span::styled(count(status).to_string(), THEME.normal_style()));
+        spans.push(Span::styled(format!(" {}  ", label), THEME.dim_style()));
+    }
+
+    spans.push(Span::styled("│ ", THEME.border_style()));
+    spans.push(Span::styled(format!("{}  ", display_path(app)), THEME.dim_style()));
+
+    spans.push(Span::styled("│ ", THEME.border_style()));
+    if app.storage.git_sync.is_none() {
+        spans.push(Span::styled("no git  ", THEME.dim_style()));
+    } else if let Some(error) = app.storage.last_sync_error() {
+        let first_line = error.lines().next().unwrap_or_default().to_string();
+        spans.push(Span::styled(format!("sync failed: {}  ", text::truncate(&first_line, 40)), THEME.warning_style()));
+    } else {
+        spans.push(Span::styled("git ✓ ", THEME.dim_style()));
+        match app.remote_status {
+            Some(status) if status.ahead + status.behind > 0 => {
+                let mut counts = Vec::new();
+                if status.behind > 0 {
+                    counts.push(format!("↓{}", status.behind));
+                }
+                if status.ahead > 0 {
+                    counts.push(format!("↑{}", status.ahead));
+                }
+                spans.push(Span::styled(format!("{}  ", counts.join(" ")), THEME.accent_style()));
+            }
+            _ => spans.push(Span::raw(" ")),
+        }
+    }
+
+    spans.push(Span::styled("│ ", THEME.border_style()));
+    if app.llm_available() {
+        spans.push(Span::styled("LLM on ", THEME.accent_style()));
+    } else {
+        spans.push(Span::styled("LLM off ", THEME.dim_style()));
+    }
+
+    spans
+}
+
+/// Data dir with the home directory shortened to `~`
+fn display_path(app: &App) -> String {
+    let path = app.data_dir.display().to_string();
+    match std::env::var("HOME") {
+        Ok(home) if !home.is_empty() && path.starts_with(&home) => format!("~{}", &path[home.len()..]),
+        _ => path,
+    }
+}
@@ -0,0 +1,106 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Display width in terminal columns (wide CJK/emoji count as 2)
+pub fn width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// Cut `text` to at most `max` columns on grapheme boundaries, ending in "…" when shortened
+pub fn truncate(text: &str, max: usize) -> String {
+    if width(text) <= max {
+        return text.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+
+    let mut result = String::new();
+    let mut used = 0;
+    for grapheme in text.graphemes(true) {
+        let w = width(grapheme);
+        if used + w > max - 1 {
+            break;
+        }
+        result.push_str(grapheme);
+        used += w;
+    }
+    result.push('…');
+    result
+}
+
+/// Break `text` into lines of at most `max` columns, between words where it
+/// can and inside a word only when it alone is longer than a line
+pub fn wrap(text: &str, max: usize) -> Vec<String> {
+    let max = max.max(1);
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let gap = usize::from(!line.is_empty());
+        if width(&line) + gap + width(word) <= max {
+            if gap == 1 {
+                line.push(' ');
+            }
+            line.push_str(word);
+            continue;
+        }
+        if !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+        }
+        for grapheme in word.graphemes(true) {
+            if width(&line) + width(grapheme) > max && !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            line.push_str(grapheme);
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// Truncate, then pad with spaces to exactly `columns` wide
+pub fn fit(text: &str, columns: usize) -> String {
+    let mut result = truncate(text, columns);
+    let used = width(&result);
+    result.push_str(&" ".repeat(columns.saturating_sub(used)));
+    result
+}
+
+/// Uppercase the first grapheme ("work" → "Work")
+pub fn capitalize(text: &str) -> String {
+    let mut graphemes = text.graphemes(true);
+    match graphemes.next() {
+        Some(first) => first.to_uppercase() + graphemes.as_str(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_wide_and_combining() {
+        assert_eq!(truncate("plain", 10), "plain");
+        assert_eq!(truncate("Ship the release", 8), "Ship th…");
+        // Wide characters take two columns each
+        assert_eq!(truncate("日本語のタスク", 7), "日本語…");
+        // A combining accent stays with its base letter
+        assert_eq!(truncate("cafe\u{301} au lait", 5), "cafe\u{301}…");
+        assert_eq!(truncate("🚀🚀🚀", 4), "🚀…");
+        assert_eq!(width(&fit("日本語のタスク", 8)), 8);
+        assert_eq!(capitalize("éclair"), "Éclair");
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("Ship the release notes", 10), ["Ship the", "release", "notes"]);
+        assert_eq!(wrap("short", 10), ["short"]);
+        assert_eq!(wrap("", 10), [""]);
+        // A word longer than a line is split, wide characters by their width
+        assert_eq!(wrap("Donaudampfschiff", 6), ["Donaud", "ampfsc", "hiff"]);
+        assert_eq!(wrap("日本語のタスク", 6), ["日本語", "のタス", "ク"]);
+    }
+}
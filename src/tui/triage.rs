@@ -0,0 +1,177 @@
+use super::{app::App, THEME};
+use chrono::Local;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+pub fn render(frame: &mut Frame, size: Rect, app: &App) {
+    // Main layout: header, content, footer
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // Header
+            Constraint::Min(0),     // Content
+            Constraint::Length(3),  // Footer
+        ])
+        .split(size);
+
+    render_header(frame, chunks[0], app);
+    render_content(frame, chunks[1], app);
+    render_footer(frame, chunks[2]);
+
+    // Render schedule prompt if active
+    if app.triage_scheduling {
+        render_schedule_dialog(frame, app);
+    }
+}
+
+fn render_header(frame: &mut Frame, area: Rect, app: &App) {
+    let count = app.inbox_tasks().len();
+    let position = if count > 0 {
+        format!(" ({}/{})", app.triage_index + 1, count)
+    } else {
+        String::new()
+    };
+
+    let title = vec![
+        Line::from(vec![
+            Span::styled("  INBOX TRIAGE", THEME.title_style()),
+            Span::styled(position, THEME.dim_style()),
+        ]),
+    ];
+
+    let header = Paragraph::new(title)
+        .block(Block::default().borders(Borders::BOTTOM).border_style(THEME.border_style()));
+
+    frame.render_widget(header, area);
+}
+
+fn render_content(frame: &mut Frame, area: Rect, app: &App) {
+    let Some(task) = app.triage_current() else {
+        let empty = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(Span::styled("  Inbox zero. Nothing left to triage.", THEME.accent_style())),
+            Line::from(""),
+            Line::from(Span::styled("  Capture more with: tasktui capture \"...\"", THEME.dim_style())),
+        ])
+        .block(Block::default().borders(Borders::ALL).border_style(THEME.border_style()));
+        frame.render_widget(empty, area);
+        return;
+    };
+
+    let captured = task.frontmatter.created_at.with_timezone(&Local);
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  "),
+            app.priority_badge(&task.frontmatter.priority),
+            Span::styled(format!(" {}", task.frontmatter.title), THEME.highlight_style()),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                format!(
+                    "Captured {} {}",
+                    app.config.display.format_date(captured.date_naive()),
+                    captured.format("%H:%M")
+                ),
+                THEME.dim_style(),
+            ),
+        ]),
+    ];
+
+    if !task.frontmatter.tags.is_empty() {
+        let tags = task.frontmatter.tags
+            .iter()
+            .map(|t| format!("#{}", t))
+            .collect::<Vec<_>>()
+            .join(" ");
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(tags, THEME.tag_style()),
+        ]));
+    }
+
+    if !task.body.is_empty() {
+        lines.push(Line::from(""));
+        for body_line in task.body.lines() {
+            lines.push(Line::from(Span::styled(format!("  {}", body_line), THEME.normal_style())));
+        }
+    }
+
+    let card = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .title(" What is it? What's the next action? ")
+                .title_style(THEME.accent_style())
+                .borders(Borders::ALL)
+                .border_style(THEME.border_focused_style()),
+        );
+
+    frame.render_widget(card, area);
+}
+
+fn render_footer(frame: &mut Frame, area: Rect) {
+    let help_items = vec![
+        Span::styled("n", THEME.accent_style()),
+        Span::raw(" do now  "),
+        Span::styled("s", THEME.accent_style()),
+        Span::raw(" schedule  "),
+        Span::styled("d", THEME.accent_style()),
+        Span::raw(" delegate  "),
+        Span::styled("m", THEME.accent_style()),
+        Span::raw(" someday  "),
+        Span::styled("x", THEME.accent_style()),
+        Span::raw(" trash  "),
+        Span::styled("↑↓", THEME.accent_style()),
+        Span::raw(" skip  "),
+        Span::styled("Esc", THEME.accent_style()),
+        Span::raw(" back  "),
+        Span::styled("q", THEME.accent_style()),
+        Span::raw(" quit"),
+    ];
+
+    let footer = Paragraph::new(Line::from(help_items))
+        .block(Block::default().borders(Borders::TOP).border_style(THEME.border_style()));
+
+    frame.render_widget(footer, area);
+}
+
+fn render_schedule_dialog(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+
+    // Center the dialog
+    let dialog_width = 50.min(area.width.saturating_sub(4));
+    let dialog_height = 6;
+    let dialog_area = Rect {
+        x: (area.width.saturating_sub(dialog_width)) / 2,
+        y: (area.height.saturating_sub(dialog_height)) / 2,
+        width: dialog_width,
+        height: dialog_height,
+    };
+
+    // Clear the area behind the dialog
+    frame.render_widget(Clear, dialog_area);
+
+    let content = vec![
+        Line::from(""),
+        app.triage_input.to_line(" ", THEME.normal_style()),
+        app.due_prompt_hint(&app.triage_input),
+    ];
+
+    let dialog = Paragraph::new(content)
+        .block(
+            Block::default()
+                .title(" Schedule ")
+                .title_style(THEME.accent_style())
+                .borders(Borders::ALL)
+                .border_style(THEME.border_focused_style())
+        );
+
+    frame.render_widget(dialog, dialog_area);
+}
@@ -0,0 +1,123 @@
+use ratatui::{
+    layout::Rect,
+    widgets::{Block, List, ListItem},
+    Frame,
+};
+use std::cell::Cell;
+use std::collections::VecDeque;
+
+/// Rows built past the bottom of the viewport
+const OVERSCAN: usize = 3;
+
+/// Render a list building only the rows around the viewport.
+/// `offset` remembers the first visible row between frames and moves just enough to
+/// keep `selected` in view; `row` builds the item at an index.
+pub fn render<'a>(
+    frame: &mut Frame,
+    area: Rect,
+    block: Option<Block<'a>>,
+    offset: &Cell<usize>,
+    selected: Option<usize>,
+    total: usize,
+    row: impl FnMut(usize) -> ListItem<'a>,
+) {
+    let inner = block.as_ref().map(|b| b.inner(area)).unwrap_or(area);
+    let items = window(offset, selected, total, inner.height as usize, row);
+    let list = match block {
+        Some(block) => List::new(items).block(block),
+        None => List::new(items),
+    };
+    frame.render_widget(list, area);
+}
+
+/// Build the visible rows (plus overscan), updating `offset`
+fn window<'a>(
+    offset: &Cell<usize>,
+    selected: Option<usize>,
+    total: usize,
+    height: usize,
+    mut row: impl FnMut(usize) -> ListItem<'a>,
+) -> Vec<ListItem<'a>> {
+    if total == 0 || height == 0 {
+        offset.set(0);
+        return Vec::new();
+    }
+
+    let mut start = offset.get().min(total - 1);
+    let mut items = VecDeque::new();
+    let mut used = 0;
+
+    if let Some(selected) = selected.map(|s| s.min(total - 1)) {
+        // Every row is at least one line, so rows further up than `height` can't be visible
+        start = start.min(selected).max((selected + 1).saturating_sub(height));
+        for idx in start..=selected {
+            let item = row(idx);
+            used += item.height();
+            items.push_back(item);
+        }
+        // Scroll down until the selected row fits
+        while used > height && items.len() > 1 {
+            if let Some(item) = items.pop_front() {
+                used -= item.height();
+                start += 1;
+            }
+        }
+    }
+
+    // Fill the rest of the viewport, then a few more
+    let mut end = start + items.len();
+    let mut overscan = 0;
+    while end < total && (used < height || overscan < OVERSCAN) {
+        if used >= height {
+            overscan += 1;
+        }
+        let item = row(end);
+        used += item.height();
+        items.push_back(item);
+        end += 1;
+    }
+
+    offset.set(start);
+    items.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_builds_only_visible_rows() {
+        let offset = Cell::new(0);
+        let mut built = 0;
+        let mut row = |idx: usize| {
+            built += 1;
+            ListItem::new(format!("row {}", idx))
+        };
+
+        // Top of a large list: viewport plus overscan
+        let items = window(&offset, Some(0), 5000, 20, &mut row);
+        assert_eq!(items.len(), 20 + OVERSCAN);
+
+        // Jumping to the end scrolls so the selection is the last visible row
+        let items = window(&offset, Some(4999), 5000, 20, &mut row);
+        assert_eq!(offset.get(), 4980);
+        assert_eq!(items.len(), 20);
+
+        // Moving up inside the viewport keeps the offset
+        window(&offset, Some(4990), 5000, 20, &mut row);
+        assert_eq!(offset.get(), 4980);
+        window(&offset, Some(4970), 5000, 20, &mut row);
+        assert_eq!(offset.get(), 4970);
+
+        assert!(built < 100, "built {} rows", built);
+    }
+
+    #[test]
+    fn test_window_multi_line_rows() {
+        let offset = Cell::new(0);
+        let row = |idx: usize| ListItem::new(vec![format!("row {}", idx).into(), "".into()]);
+        window(&offset, Some(9), 100, 10, row);
+        // Two lines per row: rows 5..=9 fill the viewport
+        assert_eq!(offset.get(), 5);
+    }
+}
@@ -0,0 +1,262 @@
+use crate::config::{AppConfig, DisplayConfig};
+use crate::export::{self, escape};
+use crate::models::{self, Status, TaskItem};
+use crate::storage::Storage;
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, NaiveDateTime};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Cards shown per Kanban column; Done in particular grows without bound
+const COLUMN_LIMIT: usize = 20;
+/// Seconds between automatic page reloads
+const REFRESH_SECS: u32 = 60;
+/// How long a client may take to send its request or read the response
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:1rem;color:#222;background:#fafafa}\
+h1{font-size:1.3rem}h2{font-size:1.1rem;margin-top:1.5rem}a{color:inherit}\
+ul{padding-left:1.2rem}li{margin:.2rem 0}.dim{color:#888}.overdue{color:#c0392b}\
+.p{font-size:.7rem;font-weight:bold;padding:0 .25rem;border-radius:3px;color:#fff}\
+.p0{background:#e74c3c}.p1{background:#e67e22}.p2{background:#d4ac0d}.p3{background:#6e96be}.p4{background:#999}\
+.board{display:flex;gap:.8rem;overflow-x:auto}.column{flex:1 0 12rem;background:#fff;border:1px solid #ddd;border-radius:6px;padding:.5rem}\
+.column h3{font-size:.9rem;margin:.2rem 0 .5rem}.card{border-top:1px solid #eee;padding:.3rem 0;font-size:.9rem}\
+.bar{display:inline-block;width:8rem;height:.6rem;background:#eee;border-radius:3px;vertical-align:middle}\
+.bar span{display:block;height:100%;background:#3c9;border-radius:3px}";
+
+/// Serve the read-only dashboard on `addr` until the process is stopped.
+/// Each connection gets its own thread, with timeouts so an idle client can't
+/// hold one forever. Pages are built one at a time from a single `Storage`, so
+/// each load only reparses files that changed since the last one.
+pub fn serve(data_dir: PathBuf, addr: &str) -> Result<()> {
+    let listener = TcpListener::bind(addr).with_context(|| format!("Failed to listen on {}", addr))?;
+    println!("Serving the dashboard on http://{} (Ctrl-C to stop)", listener.local_addr()?);
+    serve_on(listener, data_dir)
+}
+
+fn serve_on(listener: TcpListener, data_dir: PathBuf) -> Result<()> {
+    let storage = Arc::new(Mutex::new(Storage::new(data_dir.clone())?));
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                tracing::warn!(error = ?e, "Failed to accept connection");
+                continue;
+            }
+        };
+        let (storage, data_dir) = (Arc::clone(&storage), data_dir.clone());
+        thread::spawn(move || {
+            if let Err(e) = handle(stream, &storage, &data_dir) {
+                tracing::warn!(error = ?e, "Dashboard request failed");
+            }
+        });
+    }
+    Ok(())
+}
+
+fn handle(mut stream: TcpStream, storage: &Mutex<Storage>, data_dir: &Path) -> Result<()> {
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Headers aren't needed, but have to be read before responding
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or("/"));
+    tracing::debug!(method, path, "Dashboard request");
+    let (status, body) = match method {
+        "GET" | "HEAD" => {
+            // A panic elsewhere leaves the storage as usable as before
+            let storage = storage.lock().unwrap_or_else(|e| e.into_inner());
+            route(path, &storage, data_dir)?
+        }
+        _ => ("405 Method Not Allowed", page("Not allowed", "<p>The dashboard is read-only.</p>")),
+    };
+
+    let head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        body.len()
+    );
+    stream.write_all(head.as_bytes())?;
+    if method != "HEAD" {
+        stream.write_all(body.as_bytes())?;
+    }
+    Ok(())
+}
+
+fn route(path: &str, storage: &Storage, data_dir: &Path) -> Result<(&'static str, String)> {
+    let path = path.split('?').next().unwrap_or(path);
+    let tasks = storage.load_all_tasks()?;
+    let today = models::today();
+
+    if path == "/" {
+        let config = AppConfig::load(data_dir)?;
+        return Ok(("200 OK", dashboard(&tasks, &config, today, models::now_local())));
+    }
+    let project = path.strip_prefix("/project/")
+        .and_then(|code| models::resolve_id(&tasks, code).ok())
+        .and_then(|id| tasks.iter().find(|t| t.frontmatter.id == id && t.is_project()));
+    match project {
+        Some(project) => Ok(("200 OK", export::project_document(project, &tasks, today).to_html())),
+        None => Ok(("404 Not Found", page("Not found", "<p>No such page. <a href=\"/\">Back to the dashboard</a></p>"))),
+    }
+}
+
+/// The dashboard: today's list, the Kanban board and project progress
+pub fn dashboard(tasks: &[TaskItem], config: &AppConfig, today: NaiveDate, now: NaiveDateTime) -> String {
+    let open: Vec<&TaskItem> = tasks.iter()
+        .filter(|t| t.is_open())
+        .collect();
+
+    let mut body = format!(
+        "<h1>tasktui</h1>\n<p class=\"dim\">{} {}</p>\n",
+        today.format("%A"),
+        config.display.format_date(today)
+    );
+
+    // Today: overdue or due today, then what's active
+    let mut due: Vec<&TaskItem> = open.iter()
+        .copied()
+        .filter(|t| t.frontmatter.due_date.is_some_and(|d| d.local_date() <= today))
+        .collect();
+    due.sort_by_key(|t| t.frontmatter.due_date.map(|d| d.deadline()));
+    let active = open.iter()
+        .copied()
+        .filter(|t| t.frontmatter.status == Status::Active && !due.iter().any(|d| d.frontmatter.id == t.frontmatter.id));
+    let today_list: Vec<&TaskItem> = due.iter().copied().chain(active).collect();
+    body.push_str(&format!("<h2>Today ({})</h2>\n", today_list.len()));
+    if today_list.is_empty() {
+        body.push_str("<p class=\"dim\">Nothing due and nothing active.</p>\n");
+    } else {
+        body.push_str("<ul>\n");
+        for task in today_list {
+            body.push_str(&format!("<li>{}</li>\n", task_html(task, &config.display, today, now)));
+        }
+        body.push_str("</ul>\n");
+    }
+
+    body.push_str("<h2>Board</h2>\n<div class=\"board\">\n");
+    for column in &config.kanban_columns {
+        let mut cards: Vec<&TaskItem> = tasks.iter().filter(|t| !t.is_project() && column.matches(t)).collect();
+        if column.status == Some(Status::Done) {
+            cards.sort_by_key(|t| std::cmp::Reverse(t.frontmatter.completed_at));
+        }
+        body.push_str(&format!("<div class=\"column\"><h3>{} ({})</h3>\n", escape(&column.name), cards.len()));
+        for task in cards.iter().take(COLUMN_LIMIT) {
+            body.push_str(&format!("<div class=\"card\">{}</div>\n", task_html(task, &config.display, today, now)));
+        }
+        if cards.len() > COLUMN_LIMIT {
+            body.push_str(&format!("<div class=\"card dim\">…and {} more</div>\n", cards.len() - COLUMN_LIMIT));
+        }
+        body.push_str("</div>\n");
+    }
+    body.push_str("</div>\n");
+
+    let projects: Vec<&TaskItem> = tasks.iter()
+        .filter(|t| t.is_project() && !t.frontmatter.status.is_closed())
+        .collect();
+    if !projects.is_empty() {
+        body.push_str("<h2>Projects</h2>\n<ul>\n");
+        for project in projects {
+            let progress = models::project_progress(tasks, project.frontmatter.id);
+            body.push_str(&format!(
+                "<li><a href=\"/project/{}\">{}</a> <span class=\"bar\"><span style=\"width:{}%\"></span></span> {}%</li>\n",
+                project.code(),
+                escape(&project.frontmatter.title),
+                progress,
+                progress
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+
+    page("tasktui", &body)
+}
+
+/// A task as one line: priority, title, due label and short code
+fn task_html(task: &TaskItem, display: &DisplayConfig, today: NaiveDate, now: NaiveDateTime) -> String {
+    let due = match task.frontmatter.due_date {
+        Some(due) if task.is_overdue(now) => {
+            format!(" <span class=\"overdue\">{}</span>", escape(&display.due_label(&due, today)))
+        }
+        Some(due) => format!(" <span class=\"dim\">{}</span>", escape(&display.due_label(&due, today))),
+        None => String::new(),
+    };
+    format!(
+        "<span class=\"p {}\">{}</span> {}{} <span class=\"dim\">{}</span>",
+        task.frontmatter.priority.as_str(),
+        task.frontmatter.priority.label(),
+        escape(&task.frontmatter.title),
+        due,
+        task.code()
+    )
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <meta http-equiv=\"refresh\" content=\"{}\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        REFRESH_SECS,
+        escape(title),
+        STYLE,
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Due, ItemType};
+    use std::io::Read;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_dashboard() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 4).unwrap();
+        let now = today.and_hms_opt(9, 0, 0).unwrap();
+        let project = TaskItem::new_project("Launch".to_string());
+        let mut late = TaskItem::new("Pay <rent>".to_string(), ItemType::Task);
+        late.frontmatter.status = Status::Next;
+        late.frontmatter.due_date = Due::parse("2025-03-01");
+        late.frontmatter.parent_goal_id = Some(project.frontmatter.id);
+        let mut done = TaskItem::new("Shipped".to_string(), ItemType::Task);
+        done.set_status(Status::Done);
+        let tasks = [project.clone(), late.clone(), done];
+
+        let html = dashboard(&tasks, &AppConfig::default(), today, now);
+        assert!(html.contains("<h2>Today (1)</h2>"));
+        assert!(html.contains("Pay &lt;rent&gt; <span class=\"overdue\">3d overdue</span>"));
+        assert!(html.contains("<h3>NEXT (1)</h3>") && html.contains("<h3>DONE (1)</h3>"));
+        assert!(html.contains(&format!("<a href=\"/project/{}\">Launch</a>", project.code())));
+        assert!(html.contains("width:0%"));
+    }
+
+    #[test]
+    fn test_idle_client_doesnt_stall_others() {
+        let temp_dir = TempDir::new().unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let data_dir = temp_dir.path().to_path_buf();
+        thread::spawn(move || serve_on(listener, data_dir));
+
+        // Connected but never sending a request
+        let _idle = TcpStream::connect(addr).unwrap();
+        let mut client = TcpStream::connect(addr).unwrap();
+        client.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        client.write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+    }
+}
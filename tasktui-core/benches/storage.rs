@@ -0,0 +1,137 @@
+//! `Storage` timings on vaults from `seed` with 100, 1k and 10k tasks, checked
+//! against the per-task budgets in the README's Performance section. Run with
+//! `cargo bench -p tasktui-core --bench storage`; the run fails when a median
+//! is over its budget.
+
+use criterion::{BenchmarkId, Criterion};
+use std::cell::RefCell;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use tasktui_core::filter::FilterExpr;
+use tasktui_core::models::{self, Status, TaskFilter};
+use tasktui_core::seed;
+use tasktui_core::storage::{self, Storage};
+use tempfile::TempDir;
+
+const SIZES: [usize; 3] = [100, 1_000, 10_000];
+
+/// Budget per task for each bench, for a release build
+const BUDGETS: &[(&str, Duration)] = &[
+    ("load_all_tasks (cold)", Duration::from_micros(100)),
+    ("load_all_tasks (warm)", Duration::from_micros(15)),
+    ("list_tasks status", Duration::from_micros(10)),
+    ("list_tasks tag", Duration::from_micros(10)),
+    ("list_tasks query", Duration::from_micros(10)),
+    ("serialize + parse", Duration::from_micros(60)),
+];
+
+thread_local! {
+    /// Time per iteration of every sample, by bench and vault size
+    static SAMPLES: RefCell<Vec<(&'static str, usize, Duration)>> = const { RefCell::new(Vec::new()) };
+}
+
+fn vault(tasks: usize) -> (TempDir, Storage) {
+    let dir = TempDir::new().unwrap();
+    let storage = Storage::new(dir.path().to_path_buf()).unwrap();
+    let mut items = seed::generate(tasks, tasks / 50, 1, models::today());
+    storage.write_tasks(&mut items, "Seed").unwrap();
+    (dir, storage)
+}
+
+/// Time `f` for criterion and keep each sample's time per iteration for the
+/// budget check
+fn timed(criterion: &mut Criterion, name: &'static str, tasks: usize, mut f: impl FnMut()) {
+    criterion.bench_with_input(BenchmarkId::new(name, tasks), &tasks, |b, _| {
+        b.iter_custom(|iters| {
+            let start = Instant::now();
+            for _ in 0..iters {
+                f();
+            }
+            let took = start.elapsed();
+            SAMPLES.with(|samples| samples.borrow_mut().push((name, tasks, took / iters as u32)));
+            took
+        })
+    });
+}
+
+fn load_all_tasks(criterion: &mut Criterion) {
+    for tasks in SIZES {
+        let (dir, _) = vault(tasks);
+        timed(criterion, "load_all_tasks (cold)", tasks, || {
+            black_box(Storage::new(dir.path().to_path_buf()).unwrap().load_all_tasks().unwrap());
+        });
+
+        let storage = Storage::new(dir.path().to_path_buf()).unwrap();
+        storage.load_all_tasks().unwrap();
+        timed(criterion, "load_all_tasks (warm)", tasks, || {
+            black_box(storage.load_all_tasks().unwrap());
+        });
+    }
+}
+
+fn list_tasks(criterion: &mut Criterion) {
+    let query = FilterExpr::parse("#work status:active,next due:..today+7", |_| None).unwrap();
+    let filters = [
+        ("list_tasks status", TaskFilter { status: Some(Status::Next), ..Default::default() }),
+        ("list_tasks tag", TaskFilter { tags: vec!["work".to_string()], ..Default::default() }),
+        ("list_tasks query", TaskFilter { expr: Some(query), ..Default::default() }),
+    ];
+    for tasks in SIZES {
+        let (_dir, storage) = vault(tasks);
+        storage.load_all_tasks().unwrap();
+        for (name, filter) in &filters {
+            timed(criterion, name, tasks, || {
+                black_box(storage.list_tasks(filter).unwrap());
+            });
+        }
+    }
+}
+
+fn round_trip(criterion: &mut Criterion) {
+    for tasks in SIZES {
+        let (_dir, storage) = vault(tasks);
+        let items = storage.load_all_tasks().unwrap();
+        timed(criterion, "serialize + parse", tasks, || {
+            for item in &items {
+                let content = storage.serialize_task(item).unwrap();
+                black_box(storage::parse_task(&content, &item.file_path).unwrap());
+            }
+        });
+    }
+}
+
+/// Fail if any bench's median sample is over its budget
+fn check_budgets() {
+    let mut over = Vec::new();
+    for (name, per_task) in BUDGETS {
+        for tasks in SIZES {
+            let mut times: Vec<Duration> = SAMPLES.with(|samples| {
+                samples.borrow().iter().filter(|s| s.0 == *name && s.1 == tasks).map(|s| s.2).collect()
+            });
+            if times.is_empty() {
+                continue;
+            }
+            times.sort();
+            let median = times[times.len() / 2];
+            let budget = *per_task * tasks as u32;
+            println!("{:<24} {:>6} tasks {:>10.2?} (budget {:.2?})", name, tasks, median, budget);
+            if median > budget {
+                over.push(format!("{} at {} tasks: {:?} > {:?}", name, tasks, median, budget));
+            }
+        }
+    }
+    assert!(over.is_empty(), "over budget:\n{}", over.join("\n"));
+}
+
+fn main() {
+    let mut criterion = Criterion::default()
+        .sample_size(10)
+        .warm_up_time(Duration::from_millis(500))
+        .measurement_time(Duration::from_secs(2))
+        .configure_from_args();
+    load_all_tasks(&mut criterion);
+    list_tasks(&mut criterion);
+    round_trip(&mut criterion);
+    criterion.final_summary();
+    check_budgets();
+}
@@ -0,0 +1,267 @@
+use crate::models::{Frontmatter, Status};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+/// Heading of the body section that carries the activity trail
+pub const LOG_HEADING: &str = "## Log";
+
+/// Heading of the body section holding timestamped notes
+pub const NOTES_HEADING: &str = "## Notes";
+
+/// Format of the timestamp that starts every log and note entry
+const STAMP_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// One timestamped entry from the `## Notes` section
+#[derive(Debug, Clone, PartialEq)]
+pub struct Note {
+    pub at: String,
+    pub text: String,
+}
+
+/// A status change recorded in the `## Log` section
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusChange {
+    pub on: NaiveDate,
+    pub from: Status,
+    pub to: Status,
+}
+
+/// Describe tracked field changes between two versions of a task's frontmatter
+pub fn changes(before: &Frontmatter, after: &Frontmatter) -> Vec<String> {
+    let mut entries = Vec::new();
+
+    if before.status != after.status {
+        entries.push(format!("status: {} → {}", before.status.as_str(), after.status.as_str()));
+    }
+    if before.priority != after.priority {
+        entries.push(format!("priority: {} → {}", before.priority.as_str(), after.priority.as_str()));
+    }
+    let text = |value: Option<String>| value.unwrap_or_else(|| "none".to_string());
+    for (field, old, new) in [
+        ("due", before.due_date.map(|d| d.to_string()), after.due_date.map(|d| d.to_string())),
+        ("start", before.start_date.map(|d| d.to_string()), after.start_date.map(|d| d.to_string())),
+        ("end", before.end_date.map(|d| d.to_string()), after.end_date.map(|d| d.to_string())),
+    ] {
+        if old != new {
+            entries.push(format!("{}: {} → {}", field, text(old), text(new)));
+        }
+    }
+
+    entries
+}
+
+/// Append timestamped entries to the body's `## Log` section, creating it at the end if missing
+pub fn append_log(body: &str, entries: &[String], now: DateTime<Utc>) -> String {
+    let stamp = now.format(STAMP_FORMAT);
+    let lines: Vec<String> = entries.iter().map(|e| format!("- {} {}", stamp, e)).collect();
+    append_to_section(body, LOG_HEADING, lines)
+}
+
+/// Append a timestamped note under `## Notes`. Extra lines of a multi-line note are
+/// indented under its bullet so they stay part of the same entry.
+pub fn append_note(body: &str, text: &str, now: DateTime<Utc>) -> String {
+    let text = text.trim();
+    if text.is_empty() {
+        return body.to_string();
+    }
+
+    let mut text_lines = text.lines();
+    let mut lines = vec![format!("- {} {}", now.format(STAMP_FORMAT), text_lines.next().unwrap_or_default())];
+    lines.extend(text_lines.map(|l| format!("  {}", l)));
+    append_to_section(body, NOTES_HEADING, lines)
+}
+
+/// Parse the `## Notes` section into entries, oldest first
+pub fn notes(body: &str) -> Vec<Note> {
+    let mut notes: Vec<Note> = Vec::new();
+
+    for line in section_lines(body, NOTES_HEADING) {
+        if let Some(entry) = line.strip_prefix("- ") {
+            // "YYYY-MM-DD HH:MM text"; entries without a stamp keep an empty `at`
+            let (at, text) = match entry.get(..16) {
+                Some(stamp) if NaiveDateTime::parse_from_str(stamp, STAMP_FORMAT).is_ok() => {
+                    (stamp.to_string(), entry[16..].trim_start().to_string())
+                }
+                _ => (String::new(), entry.to_string()),
+            };
+            notes.push(Note { at, text });
+        } else if let Some(note) = notes.last_mut() {
+            if !line.trim().is_empty() {
+                note.text.push('\n');
+                note.text.push_str(line.trim());
+            }
+        }
+    }
+
+    notes
+}
+
+/// Status changes from the `## Log` section, oldest first
+pub fn status_changes(body: &str) -> Vec<StatusChange> {
+    section_lines(body, LOG_HEADING)
+        .iter()
+        .filter_map(|line| {
+            let entry = line.strip_prefix("- ")?;
+            let at = NaiveDateTime::parse_from_str(entry.get(..16)?, STAMP_FORMAT).ok()?;
+            let (from, to) = entry[16..].trim_start().strip_prefix("status: ")?.split_once(" → ")?;
+            Some(StatusChange { on: at.date(), from: Status::parse(from)?, to: Status::parse(to)? })
+        })
+        .collect()
+}
+
+/// Body with the given section (heading included) removed
+pub fn without_section(body: &str, heading: &str) -> String {
+    let lines: Vec<&str> = body.lines().collect();
+    let Some((start, mut end)) = section_bounds(&lines, heading) else {
+        return body.to_string();
+    };
+    // Drop the blank lines that separated it from the next section too
+    while end < lines.len() && lines[end].trim().is_empty() {
+        end += 1;
+    }
+    let mut kept: Vec<&str> = lines[..start].to_vec();
+    kept.extend(&lines[end..]);
+    kept.join("\n").trim().to_string()
+}
+
+/// Lines inside a section, excluding the heading
+fn section_lines<'a>(body: &'a str, heading: &str) -> Vec<&'a str> {
+    let lines: Vec<&str> = body.lines().collect();
+    match section_bounds(&lines, heading) {
+        Some((start, end)) => lines[start + 1..end].to_vec(),
+        None => Vec::new(),
+    }
+}
+
+/// (heading index, end index) of a section. It runs until the next heading of the
+/// same or higher level, with trailing blank lines left outside it.
+fn section_bounds<S: AsRef<str>>(lines: &[S], heading: &str) -> Option<(usize, usize)> {
+    let start = lines.iter().position(|l| l.as_ref().trim() == heading)?;
+    let mut end = lines[start + 1..]
+        .iter()
+        .position(|l| l.as_ref().starts_with("# ") || l.as_ref().starts_with("## "))
+        .map(|offset| start + 1 + offset)
+        .unwrap_or(lines.len());
+    while end > start + 1 && lines[end - 1].as_ref().trim().is_empty() {
+        end -= 1;
+    }
+    Some((start, end))
+}
+
+/// Append lines to the end of a section. A missing section is created at the end of the
+/// body, or just before `## Log` so the activity trail stays last.
+fn append_to_section(body: &str, heading: &str, new_lines: Vec<String>) -> String {
+    if new_lines.is_empty() {
+        return body.to_string();
+    }
+
+    let mut lines: Vec<String> = body.lines().map(String::from).collect();
+    if let Some((_, end)) = section_bounds(&lines, heading) {
+        lines.splice(end..end, new_lines);
+        return lines.join("\n");
+    }
+
+    let mut section = vec![heading.to_string(), String::new()];
+    section.extend(new_lines);
+
+    match lines.iter().position(|l| l.trim() == LOG_HEADING).filter(|_| heading != LOG_HEADING) {
+        Some(log) => {
+            section.push(String::new());
+            lines.splice(log..log, section);
+        }
+        None => {
+            while lines.last().is_some_and(|l| l.trim().is_empty()) {
+                lines.pop();
+            }
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            lines.extend(section);
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Due, ItemType, Priority, Status, TaskItem};
+    use chrono::{NaiveDate, TimeZone};
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 11, 24, 10, 5, 0).unwrap()
+    }
+
+    #[test]
+    fn test_changes() {
+        let before = TaskItem::new("Task".to_string(), ItemType::Task);
+        let mut after = before.clone();
+        after.set_status(Status::Done);
+        after.frontmatter.priority = Priority::P1;
+        after.frontmatter.due_date = NaiveDate::from_ymd_opt(2025, 11, 30).map(Due::on);
+        after.frontmatter.title = "Renamed".to_string();
+
+        assert_eq!(
+            changes(&before.frontmatter, &after.frontmatter),
+            vec![
+                "status: active → done",
+                "priority: p2 → p1",
+                "due: none → 2025-11-30",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_append_creates_section() {
+        let body = append_log("Some notes\n", &["status: active → done".to_string()], now());
+        assert_eq!(body, "Some notes\n\n## Log\n\n- 2025-11-24 10:05 status: active → done");
+
+        let empty = append_log("", &["priority: low → high".to_string()], now());
+        assert_eq!(empty, "## Log\n\n- 2025-11-24 10:05 priority: low → high");
+    }
+
+    #[test]
+    fn test_notes_round_trip() {
+        let body = append_log("Context", &["status: next → active".to_string()], now());
+        let body = append_note(&body, "Called the vendor", now());
+        let body = append_note(&body, "Quote received\nWaiting on approval", now());
+
+        assert_eq!(
+            body,
+            "Context\n\n## Notes\n\n- 2025-11-24 10:05 Called the vendor\n- 2025-11-24 10:05 Quote received\n  Waiting on approval\n\n## Log\n\n- 2025-11-24 10:05 status: next → active"
+        );
+        assert_eq!(
+            notes(&body),
+            vec![
+                Note { at: "2025-11-24 10:05".to_string(), text: "Called the vendor".to_string() },
+                Note { at: "2025-11-24 10:05".to_string(), text: "Quote received\nWaiting on approval".to_string() },
+            ]
+        );
+        assert_eq!(
+            without_section(&body, NOTES_HEADING),
+            "Context\n\n## Log\n\n- 2025-11-24 10:05 status: next → active"
+        );
+    }
+
+    #[test]
+    fn test_status_changes() {
+        let body = "## Log\n\n- 2025-11-20 09:00 status: next → active\n- 2025-11-21 09:00 priority: low → high\n- 2025-11-24 10:05 status: active → done";
+        assert_eq!(
+            status_changes(body),
+            vec![
+                StatusChange { on: NaiveDate::from_ymd_opt(2025, 11, 20).unwrap(), from: Status::Next, to: Status::Active },
+                StatusChange { on: NaiveDate::from_ymd_opt(2025, 11, 24).unwrap(), from: Status::Active, to: Status::Done },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_append_to_existing_section() {
+        let body = "Notes\n\n## Log\n\n- 2025-11-20 09:00 status: next → active\n\n## Links\n\n- spec";
+        let updated = append_log(body, &["status: active → done".to_string()], now());
+        assert_eq!(
+            updated,
+            "Notes\n\n## Log\n\n- 2025-11-20 09:00 status: next → active\n- 2025-11-24 10:05 status: active → done\n\n## Links\n\n- spec"
+        );
+    }
+}
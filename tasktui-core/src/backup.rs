@@ -0,0 +1,361 @@
+use crate::git::GitSync;
+use anyhow::{Context, Result};
+use chrono::{Local, NaiveDateTime};
+use std::fs::{self, File};
+use std::io::{BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+pub use age::secrecy::SecretString;
+
+/// Where backups go unless told otherwise, inside the data directory
+pub const BACKUP_DIR: &str = ".backups";
+
+/// Passphrase for encrypted backups, so scripts needn't answer a prompt
+pub const PASSPHRASE_ENV: &str = "TASKTUI_BACKUP_PASSPHRASE";
+
+/// Left out of backups, and left alone by restores: git's own store, the
+/// backups themselves and a restore in progress
+const SKIPPED: &[&str] = &[".git", BACKUP_DIR, RESTORING];
+
+/// Where a restore unpacks before anything in the data directory is touched
+const RESTORING: &str = ".tasktui-restoring";
+
+const PREFIX: &str = "tasktui-";
+/// Marks automatic snapshots in file names, ahead of what they were taken before
+const AUTO: &str = "-auto-";
+const STAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+const EXTENSION: &str = ".tar.zst";
+const ENCRYPTED_EXTENSION: &str = ".tar.zst.age";
+
+/// A backup file found by `list`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backup {
+    pub path: PathBuf,
+    /// When it was made, from its name
+    pub created_at: NaiveDateTime,
+    /// Size in bytes
+    pub size: u64,
+    pub encrypted: bool,
+    /// What an automatic snapshot was taken before, such as `import`; `None`
+    /// for backups made by hand
+    pub reason: Option<String>,
+}
+
+impl Backup {
+    pub fn file_name(&self) -> String {
+        self.path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default()
+    }
+}
+
+/// What's inside a backup
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Contents {
+    /// Task files at the top of the data directory
+    pub tasks: usize,
+    /// The config, reviews, exports and the rest
+    pub other_files: usize,
+}
+
+/// Bytes as `812 B`, `14.2 KB` or `3.1 MB`
+pub fn format_size(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{} B", bytes),
+        1024..=1_048_575 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+/// The default backup folder of `data_dir`
+pub fn default_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join(BACKUP_DIR)
+}
+
+/// Whether the backup at `path` needs a passphrase
+pub fn is_encrypted(path: &Path) -> bool {
+    path.to_string_lossy().ends_with(".age")
+}
+
+/// Write everything in `data_dir` (but `.git` and backups) to a timestamped
+/// `tar.zst` in `dir`, encrypted with `passphrase` when given. The file only
+/// appears under its name once complete.
+pub fn create(data_dir: &Path, dir: &Path, passphrase: Option<&SecretString>) -> Result<PathBuf> {
+    write(data_dir, dir, passphrase, None)
+}
+
+/// Take an automatic snapshot into `.backups` before `reason`, a slug like
+/// `import`
+pub fn snapshot(data_dir: &Path, reason: &str) -> Result<PathBuf> {
+    write(data_dir, &default_dir(data_dir), None, Some(reason))
+}
+
+/// Remove all but the newest `keep` automatic snapshots
+pub fn prune(data_dir: &Path, keep: usize) -> Result<()> {
+    for old in list(&default_dir(data_dir))?.iter().filter(|b| b.reason.is_some()).skip(keep) {
+        fs::remove_file(&old.path).with_context(|| format!("Failed to remove {}", old.path.display()))?;
+    }
+    Ok(())
+}
+
+fn write(data_dir: &Path, dir: &Path, passphrase: Option<&SecretString>, reason: Option<&str>) -> Result<PathBuf> {
+    // Anywhere else in the data directory, backups would be archived into
+    // the next backup and deleted by the next restore
+    let in_default = resolve(dir).starts_with(resolve(&default_dir(data_dir)));
+    if !in_default && resolve(dir).starts_with(resolve(data_dir)) {
+        anyhow::bail!(
+            "{} is inside the data directory: back up to {} there, or to a folder outside it",
+            dir.display(),
+            BACKUP_DIR
+        );
+    }
+    fs::create_dir_all(dir).context("Failed to create the backup folder")?;
+    if in_default {
+        let git_sync = GitSync::new(data_dir.to_path_buf());
+        if git_sync.is_git_repo() {
+            if let Err(err) = git_sync.exclude(&format!("/{}/", BACKUP_DIR)) {
+                tracing::warn!(error = ?err, "Failed to keep backups out of git");
+            }
+        }
+    }
+
+    let stamp = Local::now().format(STAMP_FORMAT).to_string();
+    let extension = if passphrase.is_some() { ENCRYPTED_EXTENSION } else { EXTENSION };
+    let reason = reason.map(|reason| format!("{}{}", AUTO, reason)).unwrap_or_default();
+    // Backups made within the same second are told apart, and kept in order,
+    // by a sequence number whatever their reason
+    let n = fs::read_dir(dir)
+        .context("Failed to read the backup folder")?
+        .filter_map(|entry| parse_name(&entry.ok()?.file_name().to_string_lossy()))
+        .filter(|(created_at, _, _)| created_at.format(STAMP_FORMAT).to_string() == stamp)
+        .map(|(_, sequence, _)| sequence + 1)
+        .max()
+        .unwrap_or(1);
+    let sequence = if n == 1 { String::new() } else { format!("-{}", n) };
+    let path = dir.join(format!("{}{}{}{}{}", PREFIX, stamp, sequence, reason, extension));
+    let partial = path.with_extension("part");
+
+    let file = File::create(&partial).context("Failed to create the backup file")?;
+    let written = match passphrase {
+        Some(passphrase) => {
+            let encryptor = age::Encryptor::with_user_passphrase(passphrase.clone());
+            write_archive(data_dir, encryptor.wrap_output(file)?).and_then(|output| Ok(output.finish()?))
+        }
+        None => write_archive(data_dir, file),
+    };
+    if let Err(e) = written.and_then(|file| Ok(file.sync_all()?)) {
+        let _ = fs::remove_file(&partial);
+        return Err(e.context("Failed to write the backup"));
+    }
+    fs::rename(&partial, &path).context("Failed to name the backup file")?;
+    Ok(path)
+}
+
+/// `path` made absolute, with symlinks resolved as far as it exists
+fn resolve(path: &Path) -> PathBuf {
+    let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    for ancestor in path.ancestors() {
+        if let (Ok(real), Ok(rest)) = (ancestor.canonicalize(), path.strip_prefix(ancestor)) {
+            return real.join(rest);
+        }
+    }
+    path
+}
+
+/// Backups in `dir`, newest first; none when the folder doesn't exist
+pub fn list(dir: &Path) -> Result<Vec<Backup>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(dir).context("Failed to read the backup folder")? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let Some((created_at, sequence, reason)) = parse_name(&name) else {
+            continue;
+        };
+        let backup = Backup {
+            path: entry.path(),
+            created_at,
+            size: entry.metadata()?.len(),
+            encrypted: name.ends_with(ENCRYPTED_EXTENSION),
+            reason,
+        };
+        backups.push((sequence, backup));
+    }
+    backups.sort_by(|(a_seq, a), (b_seq, b)| {
+        b.created_at.cmp(&a.created_at).then(b_seq.cmp(a_seq)).then_with(|| b.path.cmp(&a.path))
+    });
+    Ok(backups.into_iter().map(|(_, backup)| backup).collect())
+}
+
+/// The time in a backup's name, its place among backups made that second and
+/// what an automatic one was taken before
+fn parse_name(name: &str) -> Option<(NaiveDateTime, u32, Option<String>)> {
+    let stem = name.strip_suffix(ENCRYPTED_EXTENSION).or_else(|| name.strip_suffix(EXTENSION))?;
+    let rest = stem.strip_prefix(PREFIX)?;
+    let (stamp, rest) = (rest.get(..15)?, rest.get(15..)?);
+    let created_at = NaiveDateTime::parse_from_str(stamp, STAMP_FORMAT).ok()?;
+    let (sequence, reason) = match rest.find(AUTO) {
+        Some(at) => (&rest[..at], Some(rest[at + AUTO.len()..].to_string())),
+        None => (rest, None),
+    };
+    let sequence = match sequence.strip_prefix('-') {
+        Some(n) => n.parse().ok()?,
+        None if sequence.is_empty() => 1,
+        None => return None,
+    };
+    Some((created_at, sequence, reason))
+}
+
+/// Count what's in the backup at `path`, reading it through: a wrong
+/// passphrase or a damaged file fails here
+pub fn contents(path: &Path, passphrase: Option<&SecretString>) -> Result<Contents> {
+    let mut archive = tar::Archive::new(open(path, passphrase)?);
+    let mut contents = Contents::default();
+    for entry in archive.entries()? {
+        let mut entry = entry.context("The backup is damaged")?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry.path()?.into_owned();
+        if name.components().count() == 1 && name.extension().is_some_and(|e| e == "md") {
+            contents.tasks += 1;
+        } else {
+            contents.other_files += 1;
+        }
+        std::io::copy(&mut entry, &mut std::io::sink()).context("The backup is damaged")?;
+    }
+    Ok(contents)
+}
+
+/// Put `data_dir` back as it was in the backup at `path`: files made since are
+/// removed and changed ones replaced, leaving `.git` and backups alone. The
+/// backup is unpacked in full first, so a bad one changes nothing.
+pub fn restore(data_dir: &Path, path: &Path, passphrase: Option<&SecretString>) -> Result<()> {
+    let staging = data_dir.join(RESTORING);
+    if staging.exists() {
+        fs::remove_dir_all(&staging).context("Failed to clear an unfinished restore")?;
+    }
+    let unpacked = open(path, passphrase).and_then(|reader| Ok(tar::Archive::new(reader).unpack(&staging)?));
+    if let Err(e) = unpacked {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e.context("Failed to unpack the backup"));
+    }
+
+    for entry in top_level(data_dir)? {
+        if entry.is_dir() {
+            fs::remove_dir_all(&entry)
+        } else {
+            fs::remove_file(&entry)
+        }
+        .with_context(|| format!("Failed to remove {}", entry.display()))?;
+    }
+    for entry in top_level(&staging)? {
+        let target = data_dir.join(entry.file_name().unwrap_or_default());
+        fs::rename(&entry, &target).with_context(|| format!("Failed to restore {}", target.display()))?;
+    }
+    fs::remove_dir_all(&staging).context("Failed to clean up after restoring")
+}
+
+/// Entries at the top of `dir` that backups cover
+fn top_level(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if !SKIPPED.contains(&entry.file_name().to_string_lossy().as_ref()) {
+            entries.push(entry.path());
+        }
+    }
+    entries.sort();
+    Ok(entries)
+}
+
+fn write_archive<W: Write>(data_dir: &Path, output: W) -> Result<W> {
+    let mut builder = tar::Builder::new(zstd::Encoder::new(output, 0)?);
+    builder.follow_symlinks(false);
+    for entry in top_level(data_dir)? {
+        let name = entry.strip_prefix(data_dir)?;
+        if entry.is_dir() {
+            builder.append_dir_all(name, &entry)?;
+        } else {
+            builder.append_path_with_name(&entry, name)?;
+        }
+    }
+    Ok(builder.into_inner()?.finish()?)
+}
+
+/// The backup's tar stream, decrypted when it's encrypted
+fn open(path: &Path, passphrase: Option<&SecretString>) -> Result<Box<dyn Read>> {
+    let file = BufReader::new(File::open(path).with_context(|| format!("Failed to open {}", path.display()))?);
+    if !is_encrypted(path) {
+        return Ok(Box::new(zstd::Decoder::new(file)?));
+    }
+    let passphrase = passphrase.context("The backup is encrypted and needs its passphrase")?;
+    let identity = age::scrypt::Identity::new(passphrase.clone());
+    let reader = age::Decryptor::new_buffered(file)
+        .and_then(|decryptor| decryptor.decrypt(std::iter::once(&identity as &dyn age::Identity)))
+        .context("Failed to decrypt the backup: wrong passphrase?")?;
+    Ok(Box::new(zstd::Decoder::new(reader)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_backup_and_restore() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_dir = temp_dir.path().join("tasks");
+        fs::create_dir_all(data_dir.join("reviews")).unwrap();
+        fs::write(data_dir.join("a.md"), "alpha").unwrap();
+        fs::write(data_dir.join("b.md"), "beta").unwrap();
+        fs::write(data_dir.join(".tasktui-config.yaml"), "stale_days: 9\n").unwrap();
+        fs::write(data_dir.join("reviews/2025-W10.md"), "review").unwrap();
+
+        let first = create(&data_dir, &default_dir(&data_dir), None).unwrap();
+        let second = create(&data_dir, &default_dir(&data_dir), None).unwrap();
+        assert_ne!(first, second, "backups in the same second get their own names");
+        let backups = list(&default_dir(&data_dir)).unwrap();
+        assert_eq!(backups.iter().map(|b| b.path.clone()).collect::<Vec<_>>(), [second.clone(), first]);
+        assert!(!backups[0].encrypted && backups[0].size > 0);
+        assert_eq!(format_size(14_540), "14.2 KB");
+        assert_eq!(contents(&second, None).unwrap(), Contents { tasks: 2, other_files: 2 });
+
+        // A fat-fingered delete, an edit and a new file, all undone
+        fs::remove_file(data_dir.join("a.md")).unwrap();
+        fs::write(data_dir.join("b.md"), "changed").unwrap();
+        fs::write(data_dir.join("c.md"), "new").unwrap();
+        restore(&data_dir, &second, None).unwrap();
+        assert_eq!(fs::read_to_string(data_dir.join("a.md")).unwrap(), "alpha");
+        assert_eq!(fs::read_to_string(data_dir.join("b.md")).unwrap(), "beta");
+        assert_eq!(fs::read_to_string(data_dir.join("reviews/2025-W10.md")).unwrap(), "review");
+        assert!(!data_dir.join("c.md").exists() && !data_dir.join(RESTORING).exists());
+        assert_eq!(list(&default_dir(&data_dir)).unwrap().len(), 2, "backups survive a restore");
+
+        // Automatic snapshots are pruned to the newest few; backups made by hand stay
+        for _ in 0..3 {
+            snapshot(&data_dir, "import").unwrap();
+        }
+        prune(&data_dir, 2).unwrap();
+        let backups = list(&default_dir(&data_dir)).unwrap();
+        assert_eq!(backups.len(), 4);
+        assert_eq!(backups.iter().filter(|b| b.reason.as_deref() == Some("import")).count(), 2);
+        assert!(backups[0].file_name().ends_with("-auto-import.tar.zst"));
+
+        // Encrypted backups need the passphrase back
+        let passphrase = SecretString::from("correct horse".to_string());
+        let encrypted = create(&data_dir, &temp_dir.path().join("elsewhere"), Some(&passphrase)).unwrap();
+        assert!(encrypted.to_string_lossy().ends_with(".tar.zst.age") && is_encrypted(&encrypted));
+        assert!(contents(&encrypted, None).is_err());
+        assert!(contents(&encrypted, Some(&SecretString::from("wrong".to_string()))).is_err());
+        fs::remove_file(data_dir.join("b.md")).unwrap();
+        restore(&data_dir, &encrypted, Some(&passphrase)).unwrap();
+        assert_eq!(fs::read_to_string(data_dir.join("b.md")).unwrap(), "beta");
+
+        // Nowhere else in the data directory, where restores would delete them
+        assert!(create(&data_dir, &data_dir.join("archive"), None).is_err());
+        assert!(create(&data_dir, &data_dir.join("reviews/../archive"), None).is_err());
+        assert!(!data_dir.join("archive").exists());
+        assert!(create(&data_dir, &default_dir(&data_dir).join("weekly"), None).is_ok());
+    }
+}
@@ -0,0 +1,146 @@
+use crate::config::AppConfig;
+use crate::models::{Due, GoalLinks, Status, TaskItem};
+use crate::retro::TaskRef;
+use chrono::{NaiveDate, NaiveDateTime};
+
+/// A dated task in the briefing
+#[derive(Debug)]
+pub struct DueTask {
+    pub task: TaskRef,
+    pub due: Due,
+}
+
+/// The next step toward the most important active goal
+#[derive(Debug)]
+pub struct Suggestion {
+    pub goal: String,
+    pub task: TaskRef,
+}
+
+/// What the day starts with: shown once on the first start of each day
+#[derive(Debug)]
+pub struct Briefing {
+    pub date: NaiveDate,
+    /// Past their deadline, most overdue first
+    pub overdue: Vec<DueTask>,
+    /// Due later today
+    pub due_today: Vec<DueTask>,
+    /// Due after today up to the next working day, so a Friday briefing
+    /// covers the weekend and Monday
+    pub coming_up: Vec<DueTask>,
+    /// The holiday or vacation `date` falls in
+    pub day_off: Option<String>,
+    pub suggestion: Option<Suggestion>,
+}
+
+fn task_ref(task: &TaskItem) -> TaskRef {
+    TaskRef { id: task.frontmatter.id, title: task.frontmatter.title.clone() }
+}
+
+impl Briefing {
+    pub fn new(tasks: &[TaskItem], config: &AppConfig, now: NaiveDateTime) -> Self {
+        let today = now.date();
+        let horizon = config.calendar.next_working_day(today);
+        let mut dated: Vec<(&TaskItem, Due)> = tasks.iter()
+            .filter(|t| t.is_open())
+            .filter_map(|t| t.frontmatter.due_date.map(|due| (t, due)))
+            .collect();
+        dated.sort_by_key(|(_, due)| due.deadline());
+        let due_tasks = |keep: &dyn Fn(&Due) -> bool| {
+            dated.iter()
+                .filter(|(_, due)| keep(due))
+                .map(|(task, due)| DueTask { task: task_ref(task), due: *due })
+                .collect()
+        };
+
+        Self {
+            date: today,
+            overdue: due_tasks(&|due| due.deadline() < now),
+            due_today: due_tasks(&|due| due.date == today && due.deadline() >= now),
+            coming_up: due_tasks(&|due| due.date > today && due.date <= horizon),
+            day_off: config.calendar.holiday_on(today).map(|h| h.label().to_string()),
+            suggestion: suggestion(tasks, config),
+        }
+    }
+
+    /// Nothing to report, so there's no point showing it
+    pub fn is_empty(&self) -> bool {
+        self.overdue.is_empty() && self.due_today.is_empty() && self.coming_up.is_empty()
+            && self.day_off.is_none() && self.suggestion.is_none()
+    }
+}
+
+/// The most urgent active or next task linked to the highest-priority active
+/// goal that has one
+fn suggestion(tasks: &[TaskItem], config: &AppConfig) -> Option<Suggestion> {
+    config.active_goals().into_iter().find_map(|goal| {
+        let links = GoalLinks::new(tasks, goal.id);
+        links.tasks.into_iter()
+            .filter(|t| matches!(t.frontmatter.status, Status::Active | Status::Next))
+            .min_by_key(|t| {
+                let due = t.frontmatter.due_date.map(|d| d.deadline());
+                (t.frontmatter.priority.rank(), due.is_none(), due)
+            })
+            .map(|task| Suggestion { goal: goal.description.clone(), task: task_ref(task) })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Goal, Holiday};
+    use crate::models::{ItemType, Priority};
+
+    #[test]
+    fn test_briefing() {
+        // A Friday morning
+        let today = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+        let now = today.and_hms_opt(9, 0, 0).unwrap();
+        let day = |offset: i64| today + chrono::Duration::days(offset);
+        let task = |title: &str, due: Option<NaiveDate>| {
+            let mut task = TaskItem::new(title.to_string(), ItemType::Task);
+            task.frontmatter.due_date = due.map(Due::on);
+            task
+        };
+
+        let mut config = AppConfig::default();
+        let mut goal = Goal::new("Get fit".to_string(), "personal".to_string());
+        goal.active = true;
+        config.goals.push(goal.clone());
+
+        let mut run = task("Go for a run", None);
+        run.frontmatter.goal_id = Some(goal.id);
+        let mut gym = task("Book the gym", Some(day(5)));
+        gym.frontmatter.goal_id = Some(goal.id);
+        gym.frontmatter.priority = Priority::P1;
+        let mut done = task("Filed taxes", Some(day(-1)));
+        done.set_status(Status::Done);
+        let tasks = vec![
+            task("Pay rent", Some(day(-1))),
+            task("Call mum", Some(day(0))),
+            task("Plan the week", Some(day(3))),
+            task("Water plants", Some(day(1))),
+            task("Someday", Some(day(4))),
+            done,
+            run,
+            gym,
+        ];
+
+        let briefing = Briefing::new(&tasks, &config, now);
+        let titles = |due: &[DueTask]| due.iter().map(|d| d.task.title.clone()).collect::<Vec<_>>();
+        assert_eq!(titles(&briefing.overdue), ["Pay rent"]);
+        assert_eq!(titles(&briefing.due_today), ["Call mum"]);
+        // Through Monday, the next working day
+        assert_eq!(titles(&briefing.coming_up), ["Water plants", "Plan the week"]);
+        assert!(briefing.day_off.is_none());
+        let suggestion = briefing.suggestion.unwrap();
+        assert_eq!((suggestion.goal.as_str(), suggestion.task.title.as_str()), ("Get fit", "Book the gym"));
+
+        config.calendar.holidays.push(Holiday { name: "Spring break".to_string(), from: day(0), to: Some(day(3)) });
+        let briefing = Briefing::new(&tasks, &config, now);
+        assert_eq!(briefing.day_off.as_deref(), Some("Spring break"));
+        assert_eq!(titles(&briefing.coming_up), ["Water plants", "Plan the week", "Someday"]);
+
+        assert!(Briefing::new(&[], &AppConfig::default(), now).is_empty());
+    }
+}
@@ -0,0 +1,205 @@
+use super::ical::Vtodo;
+use crate::config::CaldavConfig;
+use anyhow::{Context, Result};
+use reqwest::header::{CONTENT_TYPE, ETAG, IF_MATCH, IF_NONE_MATCH};
+use reqwest::{Client, Method, RequestBuilder, StatusCode, Url};
+
+/// REPORT body asking for every VTODO with its etag and data (RFC 4791 §7.8)
+const CALENDAR_QUERY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop><d:getetag/><c:calendar-data/></d:prop>
+  <c:filter><c:comp-filter name="VCALENDAR"><c:comp-filter name="VTODO"/></c:comp-filter></c:filter>
+</c:calendar-query>"#;
+
+/// A VTODO on the server, with where it lives and its current etag
+#[derive(Debug, Clone)]
+pub struct Remote {
+    pub href: String,
+    pub etag: Option<String>,
+    pub todo: Vtodo,
+}
+
+pub struct CaldavClient {
+    client: Client,
+    calendar: Url,
+    username: String,
+    password: Option<String>,
+}
+
+impl CaldavClient {
+    pub fn new(config: &CaldavConfig) -> Result<Self> {
+        // Relative hrefs resolve against the collection, which needs its trailing slash
+        let mut url = config.url.clone();
+        if !url.ends_with('/') {
+            url.push('/');
+        }
+        Ok(Self {
+            client: Client::new(),
+            calendar: Url::parse(&url).with_context(|| format!("Invalid CalDAV url '{}'", config.url))?,
+            username: config.username.clone(),
+            password: config.password.clone(),
+        })
+    }
+
+    fn request(&self, method: Method, url: Url) -> RequestBuilder {
+        self.client.request(method, url).basic_auth(&self.username, self.password.as_ref())
+    }
+
+    /// Where a task created by us lives: `<calendar>/<id>.ics`
+    pub fn href_for(&self, uid: &str) -> String {
+        self.calendar.path().to_string() + uid + ".ics"
+    }
+
+    /// Every VTODO in the calendar
+    pub async fn list(&self) -> Result<Vec<Remote>> {
+        let method = Method::from_bytes(b"REPORT").context("Invalid method")?;
+        let response = self
+            .request(method, self.calendar.clone())
+            .header("Depth", "1")
+            .header(CONTENT_TYPE, "application/xml; charset=utf-8")
+            .body(CALENDAR_QUERY)
+            .send()
+            .await
+            .context("CalDAV request failed")?;
+        if !response.status().is_success() {
+            anyhow::bail!("CalDAV REPORT failed: {}", response.status());
+        }
+        let body = response.text().await.context("Failed to read CalDAV response")?;
+        Ok(parse_multistatus(&body))
+    }
+
+    /// Create or replace the VTODO at `href`. With an etag the write only
+    /// succeeds if the server copy is unchanged; without one, only if it's new.
+    /// Returns the new etag when the server reports it.
+    pub async fn put(&self, href: &str, etag: Option<&str>, ics: String) -> Result<Option<String>> {
+        let request = self
+            .request(Method::PUT, self.url(href)?)
+            .header(CONTENT_TYPE, "text/calendar; charset=utf-8")
+            .body(ics);
+        let request = match etag {
+            Some(etag) => request.header(IF_MATCH, etag),
+            None => request.header(IF_NONE_MATCH, "*"),
+        };
+        let response = request.send().await.context("CalDAV request failed")?;
+        match response.status() {
+            StatusCode::PRECONDITION_FAILED => anyhow::bail!("{} changed on the server since it was fetched", href),
+            status if status.is_success() => Ok(response
+                .headers()
+                .get(ETAG)
+                .and_then(|etag| etag.to_str().ok())
+                .map(str::to_string)),
+            status => anyhow::bail!("CalDAV PUT {} failed: {}", href, status),
+        }
+    }
+
+    /// Delete the VTODO at `href` if it's unchanged since `etag`
+    pub async fn delete(&self, href: &str, etag: Option<&str>) -> Result<()> {
+        let mut request = self.request(Method::DELETE, self.url(href)?);
+        if let Some(etag) = etag {
+            request = request.header(IF_MATCH, etag);
+        }
+        let response = request.send().await.context("CalDAV request failed")?;
+        match response.status() {
+            StatusCode::PRECONDITION_FAILED => anyhow::bail!("{} changed on the server since it was fetched", href),
+            status if status.is_success() || status == StatusCode::NOT_FOUND => Ok(()),
+            status => anyhow::bail!("CalDAV DELETE {} failed: {}", href, status),
+        }
+    }
+
+    fn url(&self, href: &str) -> Result<Url> {
+        self.calendar.join(href).with_context(|| format!("Invalid href '{}'", href))
+    }
+}
+
+/// The VTODOs in a `multistatus` response. Namespace prefixes vary between
+/// servers, so elements are matched by local name.
+fn parse_multistatus(xml: &str) -> Vec<Remote> {
+    elements(xml, "response")
+        .into_iter()
+        .filter_map(|response| {
+            let href = elements(response, "href").into_iter().next()?;
+            let etag = elements(response, "getetag").into_iter().next().map(xml_text);
+            let data = elements(response, "calendar-data").into_iter().next()?;
+            Some(Remote {
+                href: xml_text(href),
+                etag: etag.filter(|e| !e.is_empty()),
+                todo: Vtodo::parse(&xml_text(data))?,
+            })
+        })
+        .collect()
+}
+
+/// Contents of every `<name>` / `<prefix:name>` element, outermost first
+fn elements<'a>(xml: &'a str, name: &str) -> Vec<&'a str> {
+    let mut found = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        let tag_end = rest.find(['>', ' ', '\t', '\r', '\n', '/']).unwrap_or(rest.len());
+        let tag = &rest[..tag_end];
+        if tag.rsplit(':').next() != Some(name) {
+            continue;
+        }
+        let Some(open_end) = rest.find('>') else { break };
+        if rest[..open_end].ends_with('/') {
+            found.push("");
+            continue;
+        }
+        let content = &rest[open_end + 1..];
+        let close = format!("</{}>", tag);
+        let Some(end) = content.find(&close) else { break };
+        found.push(&content[..end]);
+        rest = &content[end + close.len()..];
+    }
+    found
+}
+
+/// Element text with CDATA and entities resolved
+fn xml_text(text: &str) -> String {
+    let text = text.trim();
+    if let Some(cdata) = text.strip_prefix("<![CDATA[").and_then(|t| t.strip_suffix("]]>")) {
+        return cdata.to_string();
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#13;", "\r")
+        .replace("&#xD;", "\r")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_multistatus() {
+        let xml = r#"<?xml version="1.0"?>
+<D:multistatus xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
+  <D:response>
+    <D:href>/cal/tasks/a.ics</D:href>
+    <D:propstat><D:prop>
+      <D:getetag>"1"</D:getetag>
+      <C:calendar-data>BEGIN:VCALENDAR&#13;
+BEGIN:VTODO&#13;
+UID:a&#13;
+SUMMARY:Fish &amp; chips&#13;
+END:VTODO&#13;
+END:VCALENDAR&#13;
+</C:calendar-data>
+    </D:prop></D:propstat>
+  </D:response>
+  <D:response>
+    <D:href>/cal/tasks/</D:href>
+    <D:propstat><D:prop><D:getetag/></D:prop></D:propstat>
+  </D:response>
+</D:multistatus>"#;
+
+        let remotes = parse_multistatus(xml);
+        assert_eq!(remotes.len(), 1);
+        assert_eq!(remotes[0].href, "/cal/tasks/a.ics");
+        assert_eq!(remotes[0].etag.as_deref(), Some("\"1\""));
+        assert_eq!(remotes[0].todo.summary, "Fish & chips");
+    }
+}
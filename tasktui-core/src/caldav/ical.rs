@@ -0,0 +1,310 @@
+use crate::models::{Due, Priority, Status, TaskItem};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Utc};
+
+/// Longest content line before it's folded, in bytes (RFC 5545 §3.1)
+const FOLD_WIDTH: usize = 75;
+const TIMESTAMP: &str = "%Y%m%dT%H%M%SZ";
+
+/// The parts of a VTODO that map onto task fields
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Vtodo {
+    pub uid: String,
+    pub summary: String,
+    /// `NEEDS-ACTION`, `IN-PROCESS`, `COMPLETED` or `CANCELLED`
+    pub status: Option<String>,
+    /// 1 (highest) to 9 (lowest); 0 or missing means undefined
+    pub priority: Option<u8>,
+    pub due: Option<Due>,
+    pub categories: Vec<String>,
+    pub last_modified: Option<DateTime<Utc>>,
+}
+
+impl Vtodo {
+    /// The VTODO for a task; the UID is the task id
+    pub fn from_task(task: &TaskItem) -> Self {
+        let fm = &task.frontmatter;
+        Self {
+            uid: fm.id.to_string(),
+            summary: fm.title.clone(),
+            status: Some(status_value(&fm.status).to_string()),
+            priority: Some(match fm.priority {
+                Priority::P0 => 1,
+                Priority::P1 => 3,
+                Priority::P2 => 5,
+                Priority::P3 => 7,
+                Priority::P4 => 9,
+            }),
+            due: fm.due_date,
+            categories: fm.tags.clone(),
+            last_modified: Some(task.last_touched()),
+        }
+    }
+
+    /// Copy the remote fields onto a task. A status in the same VTODO bucket as
+    /// the task's (e.g. `next` for `NEEDS-ACTION`) is left alone.
+    pub fn apply(&self, task: &mut TaskItem) {
+        let fm = &mut task.frontmatter;
+        fm.title = self.summary.clone();
+        if let Some(priority) = self.priority.and_then(priority_from) {
+            fm.priority = priority;
+        }
+        fm.due_date = self.due;
+        fm.tags = self.categories.clone();
+
+        let status = self.status.as_deref().unwrap_or("NEEDS-ACTION");
+        if status_value(&task.frontmatter.status) != status {
+            task.set_status(status_from(status));
+        }
+    }
+
+    /// Whether the VTODO is finished, so not worth importing as a new task
+    pub fn is_closed(&self) -> bool {
+        matches!(self.status.as_deref(), Some("COMPLETED" | "CANCELLED"))
+    }
+
+    /// A complete `VCALENDAR` object holding this VTODO
+    pub fn to_ics(&self, completed_at: Option<DateTime<Utc>>) -> String {
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            "PRODID:-//tasktui//EN".to_string(),
+            "BEGIN:VTODO".to_string(),
+            format!("UID:{}", escape(&self.uid)),
+            format!("DTSTAMP:{}", Utc::now().format(TIMESTAMP)),
+            format!("SUMMARY:{}", escape(&self.summary)),
+        ];
+        if let Some(status) = &self.status {
+            lines.push(format!("STATUS:{}", status));
+        }
+        if let Some(priority) = self.priority {
+            lines.push(format!("PRIORITY:{}", priority));
+        }
+        if let Some(due) = self.due {
+            lines.push(due_property(due));
+        }
+        if !self.categories.is_empty() {
+            let categories: Vec<_> = self.categories.iter().map(|c| escape(c)).collect();
+            lines.push(format!("CATEGORIES:{}", categories.join(",")));
+        }
+        if let Some(completed) = completed_at {
+            lines.push(format!("COMPLETED:{}", completed.format(TIMESTAMP)));
+        }
+        if let Some(modified) = self.last_modified {
+            lines.push(format!("LAST-MODIFIED:{}", modified.format(TIMESTAMP)));
+        }
+        lines.push("END:VTODO".to_string());
+        lines.push("END:VCALENDAR".to_string());
+
+        lines.iter().map(|line| fold(line)).collect::<Vec<_>>().join("\r\n") + "\r\n"
+    }
+
+    /// The first VTODO in a calendar object, if it has one
+    pub fn parse(ics: &str) -> Option<Self> {
+        let mut todo: Option<Vtodo> = None;
+        for line in unfold(ics) {
+            let Some((name, value)) = line.split_once(':') else { continue };
+            let (name, params) = name.split_once(';').unwrap_or((name, ""));
+            match (name.to_ascii_uppercase().as_str(), todo.as_mut()) {
+                ("BEGIN", None) if value.eq_ignore_ascii_case("VTODO") => todo = Some(Vtodo::default()),
+                ("END", Some(_)) if value.eq_ignore_ascii_case("VTODO") => break,
+                ("UID", Some(t)) => t.uid = unescape(value),
+                ("SUMMARY", Some(t)) => t.summary = unescape(value),
+                ("STATUS", Some(t)) => t.status = Some(value.trim().to_ascii_uppercase()),
+                ("PRIORITY", Some(t)) => t.priority = value.trim().parse().ok(),
+                ("DUE", Some(t)) => t.due = parse_due(value.trim(), params),
+                ("CATEGORIES", Some(t)) => t.categories.extend(
+                    split_list(value).into_iter().map(|c| c.trim().to_string()).filter(|c| !c.is_empty()),
+                ),
+                ("LAST-MODIFIED", Some(t)) => t.last_modified = parse_timestamp(value.trim()),
+                _ => {}
+            }
+        }
+        todo
+    }
+}
+
+fn status_value(status: &Status) -> &'static str {
+    match status {
+        Status::Inbox | Status::Next | Status::Waiting => "NEEDS-ACTION",
+        Status::Active => "IN-PROCESS",
+        Status::Done => "COMPLETED",
+        Status::Archived => "CANCELLED",
+    }
+}
+
+fn status_from(value: &str) -> Status {
+    match value {
+        "IN-PROCESS" => Status::Active,
+        "COMPLETED" => Status::Done,
+        "CANCELLED" => Status::Archived,
+        _ => Status::Inbox,
+    }
+}
+
+fn priority_from(value: u8) -> Option<Priority> {
+    match value {
+        1 => Some(Priority::P0),
+        2..=4 => Some(Priority::P1),
+        5 => Some(Priority::P2),
+        6..=8 => Some(Priority::P3),
+        9 => Some(Priority::P4),
+        _ => None,
+    }
+}
+
+/// `DUE;VALUE=DATE:20250302` for a date, UTC for a time pinned to an offset,
+/// and floating local time otherwise
+fn due_property(due: Due) -> String {
+    match (due.time, due.offset) {
+        (None, _) => format!("DUE;VALUE=DATE:{}", due.date.format("%Y%m%d")),
+        (Some(time), Some(offset)) => {
+            let utc = due.date.and_time(time) - offset;
+            format!("DUE:{}", utc.format(TIMESTAMP))
+        }
+        (Some(time), None) => format!("DUE:{}", due.date.and_time(time).format("%Y%m%dT%H%M%S")),
+    }
+}
+
+/// A DUE value. Times in a named timezone (`TZID=`) are taken as local time.
+fn parse_due(value: &str, params: &str) -> Option<Due> {
+    let params = params.to_ascii_uppercase();
+    if params.split(';').any(|p| p == "VALUE=DATE") || value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d").ok().map(Due::on);
+    }
+    let (local, utc) = match value.strip_suffix(['Z', 'z']) {
+        Some(local) => (local, true),
+        None => (value, false),
+    };
+    let at = NaiveDateTime::parse_from_str(local, "%Y%m%dT%H%M%S").ok()?;
+    let offset = if utc { FixedOffset::east_opt(0) } else { None };
+    Some(Due { date: at.date(), time: Some(at.time()), offset })
+}
+
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value, TIMESTAMP).ok().map(|at| at.and_utc())
+}
+
+/// Escape TEXT values (RFC 5545 §3.3.11)
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Split a comma-separated list on unescaped commas, unescaping each item
+fn split_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut start = 0;
+    let mut escaped = false;
+    for (idx, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            ',' => {
+                items.push(unescape(&value[start..idx]));
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(unescape(&value[start..]));
+    items
+}
+
+/// Fold a content line at 75 bytes, never inside a character
+fn fold(line: &str) -> String {
+    let mut out = String::with_capacity(line.len() + line.len() / FOLD_WIDTH * 3);
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > FOLD_WIDTH {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out
+}
+
+/// Content lines with folded continuations joined back on
+fn unfold(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in ics.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ItemType;
+
+    #[test]
+    fn test_round_trip() {
+        let mut task = TaskItem::new("Call Ana; then, bill \\ invoice".to_string(), ItemType::Task);
+        task.frontmatter.priority = Priority::P0;
+        task.frontmatter.due_date = Due::parse("2025-03-02T15:00Z");
+        task.frontmatter.tags = vec!["work".to_string(), "a,b".to_string()];
+        task.set_status(Status::Done);
+
+        let todo = Vtodo::from_task(&task);
+        let ics = todo.to_ics(task.frontmatter.completed_at);
+        assert!(ics.contains("DUE:20250302T150000Z\r\n"));
+        assert!(ics.contains("STATUS:COMPLETED\r\n"));
+        let parsed = Vtodo::parse(&ics).unwrap();
+        assert_eq!(parsed.uid, todo.uid);
+        assert_eq!(parsed.summary, task.frontmatter.title);
+        assert_eq!(parsed.categories, task.frontmatter.tags);
+        assert_eq!(parsed.due, task.frontmatter.due_date);
+        assert_eq!(parsed.priority, Some(1));
+
+        // Long lines are folded, and unfolded again on parse
+        task.frontmatter.title = "é".repeat(60);
+        let ics = Vtodo::from_task(&task).to_ics(None);
+        assert!(ics.lines().all(|line| line.len() <= FOLD_WIDTH));
+        assert_eq!(Vtodo::parse(&ics).unwrap().summary, task.frontmatter.title);
+    }
+
+    #[test]
+    fn test_apply_keeps_status_bucket() {
+        let ics = "BEGIN:VCALENDAR\r\nBEGIN:VTODO\r\nUID:abc\r\nSUMMARY:Buy milk\r\nSTATUS:NEEDS-ACTION\r\n\
+                   PRIORITY:7\r\nDUE;VALUE=DATE:20250401\r\nEND:VTODO\r\nEND:VCALENDAR\r\n";
+        let todo = Vtodo::parse(ics).unwrap();
+
+        let mut task = TaskItem::new("Milk".to_string(), ItemType::Task);
+        task.frontmatter.status = Status::Next;
+        todo.apply(&mut task);
+        assert_eq!(task.frontmatter.title, "Buy milk");
+        assert_eq!(task.frontmatter.status, Status::Next);
+        assert_eq!(task.frontmatter.priority, Priority::P3);
+        assert_eq!(task.frontmatter.due_date.map(|d| d.to_string()).as_deref(), Some("2025-04-01"));
+
+        let done = Vtodo { status: Some("COMPLETED".to_string()), ..todo };
+        done.apply(&mut task);
+        assert_eq!(task.frontmatter.status, Status::Done);
+        assert!(task.frontmatter.completed_at.is_some());
+    }
+}
@@ -0,0 +1,342 @@
+mod client;
+mod ical;
+
+pub use client::{CaldavClient, Remote};
+pub use ical::Vtodo;
+
+use crate::hooks::HookEvent;
+use crate::models::{ItemType, Status, TaskItem};
+use crate::storage::Storage;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// What the last sync saw of one task: where it lives on the server, the
+/// server's etag and the task's `updated_at`. A change to either side since
+/// then is what gets synced.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Synced {
+    pub href: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Sync bookkeeping by task id, kept in `.tasktui-caldav.json`. Local to this
+/// machine, like the UI state: another machine starts fresh and matches tasks by UID.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncState {
+    pub tasks: BTreeMap<Uuid, Synced>,
+}
+
+impl SyncState {
+    pub const FILE_NAME: &'static str = ".tasktui-caldav.json";
+
+    pub fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join(Self::FILE_NAME)
+    }
+
+    /// The saved state; a missing or unreadable file starts a fresh sync
+    pub fn load(data_dir: &Path) -> Self {
+        fs::read_to_string(Self::path(data_dir))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, data_dir: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(Self::path(data_dir), content).context("Failed to write CalDAV sync state")
+    }
+}
+
+/// One step of a sync
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    /// Upload the task, creating it on the server if it has no href yet
+    Push(Uuid),
+    /// Copy the remote VTODO at this index onto the task, or into a new task
+    Pull { remote: usize, task: Option<Uuid> },
+    /// The task was deleted here; delete it on the server too
+    DeleteRemote(Uuid),
+    /// The VTODO was deleted on the server; archive the task
+    Archive(Uuid),
+}
+
+/// Tasks that take part in the sync: plain tasks, not projects or notes
+fn syncable(task: &TaskItem) -> bool {
+    task.frontmatter.item_type == ItemType::Task
+}
+
+/// Decide what to do for every task and VTODO. A side changed if its
+/// `updated_at` or etag differs from the state; when both did, the later
+/// modification wins. Tasks are matched to VTODOs by href, then by UID.
+pub fn plan(tasks: &[TaskItem], remotes: &[Remote], state: &SyncState) -> Vec<Action> {
+    let mut actions = Vec::new();
+    let mut matched = vec![false; remotes.len()];
+    let by_href: HashMap<&str, usize> = remotes.iter().enumerate().map(|(i, r)| (r.href.as_str(), i)).collect();
+    let by_uid: HashMap<&str, usize> = remotes.iter().enumerate().map(|(i, r)| (r.todo.uid.as_str(), i)).collect();
+
+    for task in tasks.iter().filter(|t| syncable(t)) {
+        let id = task.frontmatter.id;
+        let synced = state.tasks.get(&id);
+        let remote = match synced {
+            Some(synced) => by_href.get(synced.href.as_str()),
+            None => by_uid.get(id.to_string().as_str()),
+        };
+
+        match (remote, synced) {
+            (Some(&idx), Some(synced)) => {
+                matched[idx] = true;
+                let local_changed = task.frontmatter.updated_at != synced.updated_at;
+                let remote_changed = remotes[idx].etag != synced.etag;
+                match (local_changed, remote_changed) {
+                    (false, false) => {}
+                    (true, false) => actions.push(Action::Push(id)),
+                    (false, true) => actions.push(Action::Pull { remote: idx, task: Some(id) }),
+                    (true, true) => actions.push(newer(task, &remotes[idx], idx)),
+                }
+            }
+            // Already on the server from an earlier sync elsewhere
+            (Some(&idx), None) => {
+                matched[idx] = true;
+                actions.push(newer(task, &remotes[idx], idx));
+            }
+            // Deleted on the server: recreate it if it changed here since, else archive
+            (None, Some(synced)) => {
+                if task.frontmatter.updated_at != synced.updated_at {
+                    actions.push(Action::Push(id));
+                } else if task.frontmatter.status != Status::Archived {
+                    actions.push(Action::Archive(id));
+                }
+            }
+            // New here; finished tasks stay off the server
+            (None, None) => {
+                if !task.frontmatter.status.is_closed() {
+                    actions.push(Action::Push(id));
+                }
+            }
+        }
+    }
+
+    for (id, synced) in &state.tasks {
+        if tasks.iter().any(|t| t.frontmatter.id == *id) {
+            continue;
+        }
+        if let Some(&idx) = by_href.get(synced.href.as_str()) {
+            matched[idx] = true;
+            if remotes[idx].etag == synced.etag {
+                actions.push(Action::DeleteRemote(*id));
+            } else {
+                actions.push(Action::Pull { remote: idx, task: None });
+            }
+        }
+    }
+
+    for (idx, remote) in remotes.iter().enumerate() {
+        if !matched[idx] && !remote.todo.is_closed() && !remote.todo.summary.trim().is_empty() {
+            actions.push(Action::Pull { remote: idx, task: None });
+        }
+    }
+    actions
+}
+
+fn newer(task: &TaskItem, remote: &Remote, idx: usize) -> Action {
+    match remote.todo.last_modified {
+        Some(modified) if modified > task.last_touched() => Action::Pull { remote: idx, task: Some(task.frontmatter.id) },
+        _ => Action::Push(task.frontmatter.id),
+    }
+}
+
+/// Counts for the summary line
+#[derive(Debug, Default)]
+pub struct Report {
+    pub pushed: usize,
+    pub pulled: usize,
+    pub deleted: usize,
+    pub archived: usize,
+    pub errors: Vec<String>,
+}
+
+/// Sync the tasks in `storage` with the calendar. Local changes are written as
+/// one git commit; failures on single items are reported and retried next time.
+pub async fn sync(storage: &Storage, client: &CaldavClient, data_dir: &Path) -> Result<Report> {
+    let tasks = storage.load_all_tasks()?;
+    let remotes = client.list().await?;
+    let mut state = SyncState::load(data_dir);
+    let mut report = Report::default();
+
+    let mut changed: Vec<(TaskItem, Option<Synced>)> = Vec::new();
+    for action in plan(&tasks, &remotes, &state) {
+        let task = |id: Uuid| tasks.iter().find(|t| t.frontmatter.id == id);
+        match action {
+            Action::Push(id) => {
+                let Some(task) = task(id) else { continue };
+                let existing = match state.tasks.get(&id) {
+                    Some(synced) => remotes.iter().find(|r| r.href == synced.href),
+                    None => remotes.iter().find(|r| r.todo.uid == id.to_string()),
+                };
+                let mut todo = Vtodo::from_task(task);
+                let (href, etag) = match existing {
+                    // Keep the UID of VTODOs created on another client
+                    Some(remote) => {
+                        todo.uid = remote.todo.uid.clone();
+                        (remote.href.clone(), remote.etag.clone())
+                    }
+                    None => (client.href_for(&todo.uid), None),
+                };
+                let ics = todo.to_ics(task.frontmatter.completed_at);
+                match client.put(&href, etag.as_deref(), ics).await {
+                    Ok(etag) => {
+                        state.tasks.insert(id, Synced { href, etag, updated_at: task.frontmatter.updated_at });
+                        report.pushed += 1;
+                    }
+                    Err(e) => report.errors.push(format!("'{}': {:#}", task.frontmatter.title, e)),
+                }
+            }
+            Action::Pull { remote, task: id } => {
+                let remote = &remotes[remote];
+                let mut item = match id.and_then(task) {
+                    Some(task) => task.clone(),
+                    None => {
+                        let mut item = TaskItem::new(remote.todo.summary.clone(), ItemType::Task);
+                        item.frontmatter.status = Status::Inbox;
+                        item
+                    }
+                };
+                remote.todo.apply(&mut item);
+                let synced = Synced { href: remote.href.clone(), etag: remote.etag.clone(), updated_at: None };
+                changed.push((item, Some(synced)));
+                report.pulled += 1;
+            }
+            Action::DeleteRemote(id) => {
+                let Some(synced) = state.tasks.get(&id) else { continue };
+                match client.delete(&synced.href, synced.etag.as_deref()).await {
+                    Ok(()) => {
+                        state.tasks.remove(&id);
+                        report.deleted += 1;
+                    }
+                    Err(e) => report.errors.push(format!("{:#}", e)),
+                }
+            }
+            Action::Archive(id) => {
+                let Some(task) = task(id) else { continue };
+                let mut item = task.clone();
+                item.set_status(Status::Archived);
+                changed.push((item, None));
+                report.archived += 1;
+            }
+        }
+    }
+
+    if !changed.is_empty() {
+        let (mut items, synced): (Vec<_>, Vec<_>) = changed.into_iter().unzip();
+        storage.write_tasks(&mut items, "CalDAV sync")?;
+        for (item, synced) in items.iter().zip(synced) {
+            let id = item.frontmatter.id;
+            match synced {
+                Some(synced) => {
+                    state.tasks.insert(id, Synced { updated_at: item.frontmatter.updated_at, ..synced });
+                }
+                None => {
+                    state.tasks.remove(&id);
+                }
+            }
+        }
+    }
+
+    state.save(data_dir)?;
+    storage.run_hooks(HookEvent::Sync, &json!({
+        "target": "caldav",
+        "pushed": report.pushed,
+        "pulled": report.pulled,
+        "deleted": report.deleted,
+        "archived": report.archived,
+    }));
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote(task: &TaskItem, etag: &str) -> Remote {
+        Remote {
+            href: format!("/cal/{}.ics", task.frontmatter.id),
+            etag: Some(etag.to_string()),
+            todo: Vtodo::from_task(task),
+        }
+    }
+
+    fn synced(task: &TaskItem, etag: &str) -> Synced {
+        Synced {
+            href: format!("/cal/{}.ics", task.frontmatter.id),
+            etag: Some(etag.to_string()),
+            updated_at: task.frontmatter.updated_at,
+        }
+    }
+
+    #[test]
+    fn test_plan() {
+        let now = Utc::now();
+        let mut tasks: Vec<TaskItem> = ["Unchanged", "Edited here", "Edited there", "Deleted there", "New", "Done"]
+            .into_iter()
+            .map(|title| {
+                let mut task = TaskItem::new(title.to_string(), ItemType::Task);
+                task.frontmatter.updated_at = Some(now);
+                task
+            })
+            .collect();
+        tasks[5].set_status(Status::Done);
+
+        let mut state = SyncState::default();
+        for task in &tasks[..4] {
+            state.tasks.insert(task.frontmatter.id, synced(task, "1"));
+        }
+        let gone = TaskItem::new("Deleted here".to_string(), ItemType::Task);
+        state.tasks.insert(gone.frontmatter.id, synced(&gone, "1"));
+
+        let mut remotes = vec![remote(&tasks[0], "1"), remote(&tasks[1], "1"), remote(&tasks[2], "2"), remote(&gone, "1")];
+        let mut foreign = Vtodo { uid: "apple-1".to_string(), summary: "From phone".to_string(), ..Default::default() };
+        remotes.push(Remote { href: "/cal/apple-1.ics".to_string(), etag: None, todo: foreign.clone() });
+        foreign.status = Some("COMPLETED".to_string());
+        remotes.push(Remote { href: "/cal/apple-2.ics".to_string(), etag: None, todo: foreign });
+
+        tasks[1].frontmatter.updated_at = Some(now + chrono::Duration::seconds(1));
+
+        let actions = plan(&tasks, &remotes, &state);
+        assert_eq!(actions, vec![
+            Action::Push(tasks[1].frontmatter.id),
+            Action::Pull { remote: 2, task: Some(tasks[2].frontmatter.id) },
+            Action::Archive(tasks[3].frontmatter.id),
+            Action::Push(tasks[4].frontmatter.id),
+            Action::DeleteRemote(gone.frontmatter.id),
+            Action::Pull { remote: 4, task: None },
+        ]);
+    }
+
+    #[test]
+    fn test_plan_conflict_newer_wins() {
+        let mut task = TaskItem::new("Both".to_string(), ItemType::Task);
+        task.frontmatter.updated_at = Some(Utc::now());
+        let mut state = SyncState::default();
+        state.tasks.insert(task.frontmatter.id, synced(&task, "1"));
+
+        let mut theirs = remote(&task, "2");
+        theirs.todo.last_modified = Some(Utc::now() + chrono::Duration::minutes(5));
+        task.frontmatter.updated_at = Some(Utc::now() + chrono::Duration::minutes(1));
+
+        let actions = plan(std::slice::from_ref(&task), std::slice::from_ref(&theirs), &state);
+        assert_eq!(actions, vec![Action::Pull { remote: 0, task: Some(task.frontmatter.id) }]);
+
+        theirs.todo.last_modified = task.frontmatter.updated_at.map(|at| at - chrono::Duration::minutes(2));
+        let actions = plan(std::slice::from_ref(&task), std::slice::from_ref(&theirs), &state);
+        assert_eq!(actions, vec![Action::Push(task.frontmatter.id)]);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::config::{CalendarConfig, Holiday};
+use chrono::{Datelike, Duration, NaiveDate};
+
+impl Holiday {
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.from <= date && date <= self.to.unwrap_or(self.from)
+    }
+
+    /// The name, or "Day off" for an unnamed one
+    pub fn label(&self) -> &str {
+        if self.name.is_empty() {
+            "Day off"
+        } else {
+            &self.name
+        }
+    }
+}
+
+impl CalendarConfig {
+    /// A configured weekday that isn't a holiday
+    pub fn is_working_day(&self, date: NaiveDate) -> bool {
+        self.working_days.contains(&date.weekday()) && self.holiday_on(date).is_none()
+    }
+
+    /// The holiday or vacation covering `date`
+    pub fn holiday_on(&self, date: NaiveDate) -> Option<&Holiday> {
+        self.holidays.iter().find(|h| h.contains(date))
+    }
+
+    /// The first working day after `date`, or the next day when no day of
+    /// the week is a working one
+    pub fn next_working_day(&self, date: NaiveDate) -> NaiveDate {
+        date.iter_days()
+            .skip(1)
+            .take(366)
+            .find(|d| self.is_working_day(*d))
+            .unwrap_or(date + Duration::days(1))
+    }
+
+    /// The first day of the week `date` falls in
+    pub fn week_start_of(&self, date: NaiveDate) -> NaiveDate {
+        date - Duration::days(date.weekday().days_since(self.week_start) as i64)
+    }
+
+    /// `date` moved by `days`, counting only working days when
+    /// `skip_non_working` is set. A move then always lands on a working day:
+    /// shifting a Friday by one gives the Monday after.
+    pub fn shift(&self, date: NaiveDate, days: i64) -> NaiveDate {
+        if !self.skip_non_working || self.working_days.is_empty() {
+            return date + Duration::days(days);
+        }
+        let step = Duration::days(days.signum());
+        let mut date = date;
+        let mut left = days.abs();
+        while left > 0 {
+            date += step;
+            if self.is_working_day(date) {
+                left -= 1;
+            }
+        }
+        date
+    }
+
+    /// Working days from `start` to `end`, both included
+    pub fn working_days_between(&self, start: NaiveDate, end: NaiveDate) -> i64 {
+        start.iter_days()
+            .take_while(|d| *d <= end)
+            .filter(|d| self.is_working_day(*d))
+            .count() as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Weekday;
+
+    fn date(day: u32) -> NaiveDate {
+        // 2025-03-07 is a Friday
+        NaiveDate::from_ymd_opt(2025, 3, day).unwrap()
+    }
+
+    #[test]
+    fn test_week_start() {
+        let mut calendar = CalendarConfig::default();
+        assert_eq!(calendar.week_start_of(date(7)), date(3));
+        assert_eq!(calendar.week_start_of(date(3)), date(3));
+        calendar.week_start = Weekday::Sun;
+        assert_eq!(calendar.week_start_of(date(7)), date(2));
+        assert_eq!(calendar.week_start_of(date(9)), date(9));
+    }
+
+    #[test]
+    fn test_shift_skips_weekends() {
+        let mut calendar = CalendarConfig::default();
+        assert_eq!(calendar.shift(date(7), 1), date(8));
+        calendar.skip_non_working = true;
+        assert_eq!(calendar.shift(date(7), 1), date(10));
+        assert_eq!(calendar.shift(date(10), -1), date(7));
+        assert_eq!(calendar.shift(date(7), 5), date(14));
+        assert_eq!(calendar.shift(date(8), 1), date(10));
+        assert_eq!(calendar.working_days_between(date(3), date(14)), 10);
+    }
+
+    #[test]
+    fn test_calendar_config() {
+        let calendar: CalendarConfig = serde_yaml::from_str("week_start: sunday\nworking_days: [sun, mon, tue, wed, thu]").unwrap();
+        assert_eq!(calendar.week_start, Weekday::Sun);
+        assert!(calendar.is_working_day(date(9)));
+        assert!(!calendar.is_working_day(date(7)));
+    }
+
+    #[test]
+    fn test_holidays() {
+        let calendar: CalendarConfig = serde_yaml::from_str(
+            "holidays:\n- name: Carnival\n  date: 2025-03-04\n- from: 2025-03-10\n  to: 2025-03-14",
+        ).unwrap();
+        assert_eq!(calendar.holiday_on(date(4)).map(Holiday::label), Some("Carnival"));
+        assert_eq!(calendar.holiday_on(date(12)).map(Holiday::label), Some("Day off"));
+        assert!(calendar.holiday_on(date(15)).is_none());
+        assert!(!calendar.is_working_day(date(4)));
+        // A week's vacation and a weekend
+        assert_eq!(calendar.next_working_day(date(7)), date(17));
+        assert_eq!(calendar.working_days_between(date(3), date(14)), 4);
+    }
+}
@@ -0,0 +1,1507 @@
+use crate::filter::SavedFilter;
+use crate::models::{Due, Status, TaskItem};
+use anyhow::Result;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+/// A workstream (tag category) with a keyboard shortcut
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workstream {
+    pub name: String,
+    pub key: char, // '1'-'9'
+}
+
+/// A Kanban board column: tasks matching every given predicate appear in it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KanbanColumn {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+}
+
+impl KanbanColumn {
+    fn for_status(name: &str, status: Status) -> Self {
+        Self {
+            name: name.to_string(),
+            status: Some(status),
+            tag: None,
+        }
+    }
+
+    pub fn matches(&self, task: &TaskItem) -> bool {
+        if self.status.is_none() && self.tag.is_none() {
+            return false;
+        }
+        if let Some(status) = &self.status {
+            if &task.frontmatter.status != status {
+                return false;
+            }
+        }
+        if let Some(tag) = &self.tag {
+            if !task.has_tag(tag) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+fn default_kanban_columns() -> Vec<KanbanColumn> {
+    vec![
+        KanbanColumn::for_status("ACTIVE", Status::Active),
+        KanbanColumn::for_status("NEXT", Status::Next),
+        KanbanColumn::for_status("WAITING", Status::Waiting),
+        KanbanColumn::for_status("DONE", Status::Done),
+    ]
+}
+
+/// How the Kanban board arranges its columns; `auto` picks by terminal size
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KanbanLayout {
+    #[default]
+    Auto,
+    /// All columns side by side
+    Columns,
+    /// As many columns as fit, scrolling to follow the selection
+    Scroll,
+    /// Two columns per row
+    Grid,
+    /// One column at a time
+    Focus,
+}
+
+impl KanbanLayout {
+    pub fn as_str(&self) -> &str {
+        match self {
+            KanbanLayout::Auto => "auto",
+            KanbanLayout::Columns => "columns",
+            KanbanLayout::Scroll => "scroll",
+            KanbanLayout::Grid => "grid",
+            KanbanLayout::Focus => "focus",
+        }
+    }
+
+    /// Next layout in the manual override cycle
+    pub fn next(self) -> Self {
+        match self {
+            KanbanLayout::Auto => KanbanLayout::Columns,
+            KanbanLayout::Columns => KanbanLayout::Scroll,
+            KanbanLayout::Scroll => KanbanLayout::Grid,
+            KanbanLayout::Grid => KanbanLayout::Focus,
+            KanbanLayout::Focus => KanbanLayout::Auto,
+        }
+    }
+}
+
+/// How the TUI shows a task's priority
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PriorityStyle {
+    /// `P0`–`P4` in the level's color
+    #[default]
+    Badge,
+    /// 🚨 🔴 🟠 ⚪ 💤
+    Emoji,
+}
+
+/// What a view does with a title longer than the room it has
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TitleMode {
+    /// Cut to one line, ending in "…"
+    #[default]
+    Truncate,
+    /// Continue on the lines below
+    Wrap,
+}
+
+/// Long-title handling per view
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TitleDisplay {
+    #[serde(default)]
+    pub compact: TitleMode,
+    #[serde(default)]
+    pub kanban: TitleMode,
+}
+
+impl TitleDisplay {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// How the TUI draws things
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisplayConfig {
+    /// ASCII in place of emoji and block characters, for terminals that draw
+    /// them at the wrong width
+    #[serde(default)]
+    pub ascii_only: bool,
+    /// strftime format for dates, e.g. `%d.%m.%Y` or `%a %-d %b`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_format: Option<String>,
+    #[serde(default, skip_serializing_if = "TitleDisplay::is_default")]
+    pub titles: TitleDisplay,
+}
+
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Days ahead that still read as `in 3d` rather than a date
+const RELATIVE_DAYS: i64 = 6;
+
+impl DisplayConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// `date_format`, or the default when it's unset or not a valid format
+    pub fn date_format(&self) -> &str {
+        match &self.date_format {
+            Some(format) if is_valid_date_format(format) => format,
+            _ => DEFAULT_DATE_FORMAT,
+        }
+    }
+
+    pub fn format_date(&self, date: NaiveDate) -> String {
+        date.format(self.date_format()).to_string()
+    }
+
+    /// A due date for lists: `today`, `tomorrow` or `in 3d` for the week
+    /// ahead, `2d overdue` once it has passed, otherwise the formatted date.
+    /// A time of day follows: `today 15:00`.
+    pub fn due_label(&self, due: &Due, today: NaiveDate) -> String {
+        let date = due.local_date();
+        let day = match (date - today).num_days() {
+            days if days < 0 => format!("{}d overdue", -days),
+            _ => self.date_label(date, today),
+        };
+        match due.time {
+            Some(_) => format!("{} {}", day, due.deadline().format("%H:%M")),
+            None => day,
+        }
+    }
+
+    /// Any other date: `today`, `tomorrow`, `in 3d` or `3d ago` within a
+    /// week, otherwise the formatted date
+    pub fn date_label(&self, date: NaiveDate, today: NaiveDate) -> String {
+        match (date - today).num_days() {
+            0 => "today".to_string(),
+            1 => "tomorrow".to_string(),
+            days @ 2..=RELATIVE_DAYS => format!("in {}d", days),
+            days if (-RELATIVE_DAYS..0).contains(&days) => format!("{}d ago", -days),
+            _ => self.format_date(date),
+        }
+    }
+}
+
+/// Whether chrono can format with `format`; an invalid one would panic when
+/// a date is printed with it
+pub fn is_valid_date_format(format: &str) -> bool {
+    StrftimeItems::new(format).all(|item| !matches!(item, Item::Error))
+}
+
+/// Pane sizes for one view, adjusted with Ctrl-←/→ and saved per view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaneLayout {
+    /// Sidebar (Compact) or task-name column (Gantt) width, in columns
+    pub sidebar_width: u16,
+    /// Share of the content width given to the preview pane, in percent
+    #[serde(default = "default_preview_percent")]
+    pub preview_percent: u16,
+}
+
+impl PaneLayout {
+    pub const SIDEBAR_RANGE: (u16, u16) = (8, 60);
+    pub const PREVIEW_RANGE: (u16, u16) = (20, 80);
+
+    /// Layout used until the view is resized
+    pub fn default_for(view: &str) -> Self {
+        let sidebar_width = match view {
+            "gantt" => 20,
+            _ => 12,
+        };
+        Self { sidebar_width, preview_percent: default_preview_percent() }
+    }
+
+    /// Within `SIDEBAR_RANGE` and `PREVIEW_RANGE`, whatever the config file says
+    pub fn clamped(self) -> Self {
+        let (sidebar_min, sidebar_max) = Self::SIDEBAR_RANGE;
+        let (preview_min, preview_max) = Self::PREVIEW_RANGE;
+        Self {
+            sidebar_width: self.sidebar_width.clamp(sidebar_min, sidebar_max),
+            preview_percent: self.preview_percent.clamp(preview_min, preview_max),
+        }
+    }
+}
+
+fn default_preview_percent() -> u16 {
+    45
+}
+
+/// A high-level goal or priority (GTD "Horizons of Focus")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Goal {
+    /// Tasks and projects link to a goal by this id; older configs get one on load
+    #[serde(default = "Uuid::nil", skip_serializing_if = "Uuid::is_nil")]
+    pub id: Uuid,
+    pub description: String,
+    pub area: String,        // e.g., "work", "personal" - links to workstream
+    pub priority: u8,        // 1-5, where 1 is highest priority
+    #[serde(default)]
+    pub active: bool,        // Whether this goal is currently active/relevant
+}
+
+impl Goal {
+    pub fn new(description: String, area: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            description,
+            area,
+            priority: 3,     // Default to medium priority
+            active: true,
+        }
+    }
+}
+
+/// A CalDAV calendar collection that `tasktui sync caldav` keeps tasks in step with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaldavConfig {
+    /// Calendar collection URL, e.g. `https://dav.example.com/calendars/me/tasks/`
+    pub url: String,
+    pub username: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+}
+
+/// Jira Cloud site that tasks link to through `jira_key`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraConfig {
+    /// Site URL, e.g. `https://example.atlassian.net`
+    pub url: String,
+    /// Account email the API token belongs to
+    pub email: String,
+    /// API token; `JIRA_API_TOKEN` is used when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_token: Option<String>,
+    /// Move the linked issue to done when its task is completed
+    #[serde(default)]
+    pub transition_on_done: bool,
+    /// Transition (or target status) to use, by name; default: the first into a done status
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub done_transition: Option<String>,
+}
+
+/// A service that serves LLM completions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderKind {
+    Openai,
+    Anthropic,
+    /// A local Ollama server, through its OpenAI-compatible API
+    Ollama,
+}
+
+impl ProviderKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ProviderKind::Openai => "openai",
+            ProviderKind::Anthropic => "anthropic",
+            ProviderKind::Ollama => "ollama",
+        }
+    }
+}
+
+/// One link of the `llm_providers` chain
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LlmProvider {
+    pub provider: ProviderKind,
+    /// Not needed for ollama; an openai entry without one uses `openai_api_key`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    /// Model name, when not the provider's default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Base URL, for a server elsewhere or a compatible proxy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// USD per million prompt tokens, when `model` isn't priced like the default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_usd_per_mtok: Option<f64>,
+    /// USD per million completion tokens, likewise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_usd_per_mtok: Option<f64>,
+}
+
+impl LlmProvider {
+    /// The provider's default model and URL
+    pub fn new(provider: ProviderKind, api_key: Option<String>) -> Self {
+        Self { provider, api_key, model: None, url: None, input_usd_per_mtok: None, output_usd_per_mtok: None }
+    }
+}
+
+/// How fast LLM calls may go, so batch jobs stay under provider rate limits
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LlmLimits {
+    /// Calls in flight at once
+    #[serde(default = "default_llm_concurrency")]
+    pub concurrency: usize,
+    /// Calls started per minute; unlimited when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_per_minute: Option<u32>,
+}
+
+fn default_llm_concurrency() -> usize {
+    4
+}
+
+impl Default for LlmLimits {
+    fn default() -> Self {
+        Self { concurrency: default_llm_concurrency(), requests_per_minute: None }
+    }
+}
+
+impl LlmLimits {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Git sync of the vault, when it's a repository. The remote itself is
+/// kept in the repository (`origin`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GitConfig {
+    /// Minutes between background fetches of the remote (0 disables)
+    #[serde(default = "default_fetch_minutes")]
+    pub fetch_minutes: u32,
+    /// How git signs in to the remote
+    #[serde(default)]
+    pub credentials: CredentialMode,
+    /// User name sent with `token`; most hosts accept any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// Access token for an HTTPS remote, with `credentials: token`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+fn default_fetch_minutes() -> u32 {
+    5
+}
+
+impl Default for GitConfig {
+    fn default() -> Self {
+        Self {
+            fetch_minutes: default_fetch_minutes(),
+            credentials: CredentialMode::default(),
+            username: None,
+            token: None,
+        }
+    }
+}
+
+/// How git authenticates to the vault's remote
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialMode {
+    /// Whatever git is set up with: credential helpers, SSH config
+    #[default]
+    System,
+    /// SSH keys from the agent or `~/.ssh`, never stopping to ask for a passphrase
+    Ssh,
+    /// An HTTPS access token from `git.token`
+    Token,
+}
+
+impl CredentialMode {
+    pub fn as_str(&self) -> &str {
+        match self {
+            CredentialMode::System => "system",
+            CredentialMode::Ssh => "ssh",
+            CredentialMode::Token => "token",
+        }
+    }
+
+    /// The mode after this one, for cycling through them in Settings
+    pub fn next(self) -> Self {
+        match self {
+            CredentialMode::System => CredentialMode::Ssh,
+            CredentialMode::Ssh => CredentialMode::Token,
+            CredentialMode::Token => CredentialMode::System,
+        }
+    }
+}
+
+impl GitConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Automatic snapshots to `.backups` before batch changes: bulk archives,
+/// imports, tag renames and bulk enrichment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupConfig {
+    /// Automatic snapshots kept, newest first (0 turns them off). Backups
+    /// made with `tasktui backup create` are never removed.
+    #[serde(default = "default_backups_keep")]
+    pub keep: usize,
+}
+
+fn default_backups_keep() -> usize {
+    10
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self { keep: default_backups_keep() }
+    }
+}
+
+impl BackupConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Speech-to-text API that `tasktui voice` sends audio files to. Any endpoint
+/// compatible with OpenAI's `/v1/audio/transcriptions` works.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptionConfig {
+    #[serde(default = "default_transcription_url")]
+    pub url: String,
+    #[serde(default = "default_transcription_model")]
+    pub model: String,
+    /// API key for `url`; `openai_api_key` is used when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+fn default_transcription_url() -> String {
+    "https://api.openai.com/v1/audio/transcriptions".to_string()
+}
+
+fn default_transcription_model() -> String {
+    "whisper-1".to_string()
+}
+
+impl Default for TranscriptionConfig {
+    fn default() -> Self {
+        Self { url: default_transcription_url(), model: default_transcription_model(), api_key: None }
+    }
+}
+
+impl TranscriptionConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Embeddings API behind semantic search. Any endpoint compatible with
+/// OpenAI's `/v1/embeddings` works, Ollama's included.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmbeddingsConfig {
+    #[serde(default = "default_embeddings_url")]
+    pub url: String,
+    #[serde(default = "default_embeddings_model")]
+    pub model: String,
+    /// API key for `url`; `openai_api_key` is used when unset, and a local
+    /// server needs neither
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+}
+
+fn default_embeddings_url() -> String {
+    "https://api.openai.com/v1/embeddings".to_string()
+}
+
+fn default_embeddings_model() -> String {
+    "text-embedding-3-small".to_string()
+}
+
+/// Slack incoming webhook that `tasktui notify` posts to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlackConfig {
+    /// Kept in the credentials file once saved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+    /// How far ahead `notify due-soon` looks, in hours
+    #[serde(default = "default_due_soon_hours")]
+    pub due_soon_hours: u32,
+}
+
+fn default_due_soon_hours() -> u32 {
+    2
+}
+
+/// Which MCP tools clients may call, and how often
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct McpPermissions {
+    /// Only these tools are enabled; empty enables all of them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    /// Tools that are refused even if allowed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+    /// Most calls per minute, by tool name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub rate_limits: BTreeMap<String, u32>,
+}
+
+/// Shell commands run on task events, each with a JSON payload on stdin
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Hooks {
+    /// After a task is created; stdin is the task
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on_create: Vec<String>,
+    /// After a task is marked done; stdin is the task
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on_complete: Vec<String>,
+    /// After a git push or CalDAV sync; stdin says which and what changed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on_sync: Vec<String>,
+}
+
+impl Hooks {
+    pub fn is_empty(&self) -> bool {
+        self.on_create.is_empty() && self.on_complete.is_empty() && self.on_sync.is_empty()
+    }
+}
+
+/// The working week: the day weeks start on and the days that count as work
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalendarConfig {
+    #[serde(default = "default_week_start")]
+    pub week_start: Weekday,
+    #[serde(default = "default_working_days")]
+    pub working_days: Vec<Weekday>,
+    /// Count only working days when shifting dates in the Gantt view
+    #[serde(default)]
+    pub skip_non_working: bool,
+    /// Days off, which never count as working days
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub holidays: Vec<Holiday>,
+}
+
+/// A day off (`date`), or a vacation from `from` to `to`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Holiday {
+    #[serde(default)]
+    pub name: String,
+    #[serde(alias = "date")]
+    pub from: NaiveDate,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<NaiveDate>,
+}
+
+fn default_week_start() -> Weekday {
+    Weekday::Mon
+}
+
+fn default_working_days() -> Vec<Weekday> {
+    vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri]
+}
+
+impl Default for CalendarConfig {
+    fn default() -> Self {
+        Self {
+            week_start: default_week_start(),
+            working_days: default_working_days(),
+            skip_non_working: false,
+            holidays: Vec::new(),
+        }
+    }
+}
+
+impl CalendarConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A config setting that couldn't be read and was replaced by its default
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    /// The top-level key that was dropped
+    pub key: String,
+    /// 1-based line in `.tasktui-config.yaml`
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}: {}", line, self.key, self.message),
+            None => write!(f, "{}: {}", self.key, self.message),
+        }
+    }
+}
+
+/// Application configuration. Unknown keys are rejected, so that typos are
+/// reported (and skipped) by `load_checked` instead of silently ignored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AppConfig {
+    #[serde(default = "default_workstreams")]
+    pub workstreams: Vec<Workstream>,
+    #[serde(default)]
+    pub goals: Vec<Goal>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub openai_api_key: Option<String>,
+    /// LLM services to try in order, falling back to the next when one fails
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub llm_providers: Vec<LlmProvider>,
+    /// Model for the first provider of the chain, replacing its own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub llm_model: Option<String>,
+    /// Concurrency and requests-per-minute caps for LLM calls
+    #[serde(default, skip_serializing_if = "LlmLimits::is_default")]
+    pub llm_limits: LlmLimits,
+    /// Monthly LLM spend limit in USD; enrichment is refused once it's reached
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub llm_monthly_budget: Option<f64>,
+    /// Reuse enrichment results for repeated inputs instead of calling the API again
+    #[serde(default = "default_llm_cache")]
+    pub llm_cache: bool,
+    /// Days a delegated task may wait before a follow-up task is generated (0 disables)
+    #[serde(default = "default_follow_up_days")]
+    pub follow_up_days: u32,
+    /// Days without an update before an open task shows as stale (0 disables)
+    #[serde(default = "default_stale_days")]
+    pub stale_days: u32,
+    /// Days a completed task stays in the Kanban Done column before it's folded
+    /// into a "+N older" row (0 shows all)
+    #[serde(default = "default_done_window_days")]
+    pub done_window_days: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub saved_filters: Vec<SavedFilter>,
+    /// Optional work-in-progress limits per status (e.g. `active: 3`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub wip_limits: BTreeMap<Status, u32>,
+    /// Kanban board columns, left to right
+    #[serde(default = "default_kanban_columns")]
+    pub kanban_columns: Vec<KanbanColumn>,
+    #[serde(default)]
+    pub kanban_layout: KanbanLayout,
+    #[serde(default)]
+    pub priority_style: PriorityStyle,
+    /// Badge colors by level (`p0: red`, `p2: "#ff8c00"`), overriding the theme's
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub priority_colors: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "DisplayConfig::is_default")]
+    pub display: DisplayConfig,
+    #[serde(default, skip_serializing_if = "CalendarConfig::is_default")]
+    pub calendar: CalendarConfig,
+    /// Append status/priority/date changes to a `## Log` section in each task's body
+    #[serde(default = "default_activity_log")]
+    pub activity_log: bool,
+    /// Show the morning briefing on the first start of each day
+    #[serde(default = "default_briefing")]
+    pub briefing: bool,
+    /// Resized pane layouts, keyed by view ("compact", "gantt")
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub layouts: BTreeMap<String, PaneLayout>,
+    #[serde(default, skip_serializing_if = "GitConfig::is_default")]
+    pub git: GitConfig,
+    #[serde(default, skip_serializing_if = "BackupConfig::is_default")]
+    pub backups: BackupConfig,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub caldav: Option<CaldavConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jira: Option<JiraConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slack: Option<SlackConfig>,
+    #[serde(default, skip_serializing_if = "TranscriptionConfig::is_default")]
+    pub transcription: TranscriptionConfig,
+    /// Turns on semantic search
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embeddings: Option<EmbeddingsConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<McpPermissions>,
+    #[serde(default, skip_serializing_if = "Hooks::is_empty")]
+    pub hooks: Hooks,
+    /// Top-level settings replaced by environment variables or flags, with
+    /// their values from the file, which `save` writes back instead
+    #[serde(skip)]
+    overridden: Vec<(String, Option<serde_yaml::Value>)>,
+}
+
+fn default_follow_up_days() -> u32 {
+    3
+}
+
+fn default_stale_days() -> u32 {
+    30
+}
+
+fn default_done_window_days() -> u32 {
+    7
+}
+
+fn default_activity_log() -> bool {
+    true
+}
+
+fn default_briefing() -> bool {
+    true
+}
+
+fn default_llm_cache() -> bool {
+    true
+}
+
+fn default_workstreams() -> Vec<Workstream> {
+    vec![
+        Workstream {
+            name: "work".to_string(),
+            key: '1',
+        },
+        Workstream {
+            name: "personal".to_string(),
+            key: '2',
+        },
+    ]
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            workstreams: default_workstreams(),
+            goals: Vec::new(),
+            openai_api_key: None,
+            llm_providers: Vec::new(),
+            llm_model: None,
+            llm_limits: LlmLimits::default(),
+            llm_monthly_budget: None,
+            llm_cache: default_llm_cache(),
+            follow_up_days: default_follow_up_days(),
+            stale_days: default_stale_days(),
+            done_window_days: default_done_window_days(),
+            saved_filters: Vec::new(),
+            wip_limits: BTreeMap::new(),
+            kanban_columns: default_kanban_columns(),
+            kanban_layout: KanbanLayout::default(),
+            priority_style: PriorityStyle::default(),
+            priority_colors: BTreeMap::new(),
+            display: DisplayConfig::default(),
+            calendar: CalendarConfig::default(),
+            activity_log: default_activity_log(),
+            briefing: default_briefing(),
+            layouts: BTreeMap::new(),
+            git: GitConfig::default(),
+            backups: BackupConfig::default(),
+            caldav: None,
+            jira: None,
+            slack: None,
+            transcription: TranscriptionConfig::default(),
+            embeddings: None,
+            permissions: None,
+            hooks: Hooks::default(),
+            overridden: Vec::new(),
+        }
+    }
+}
+
+impl AppConfig {
+    /// Passwords, tokens and API keys, kept out of the config file since that
+    /// is committed and pushed with the vault
+    pub const CREDENTIALS_FILE: &'static str = ".tasktui-credentials.yaml";
+    /// The previous config file, which may still hold secrets
+    pub const BACKUP_FILE: &'static str = ".tasktui-config.yaml.bak";
+
+    /// Get the config file path for a data directory
+    pub fn config_path(data_dir: &Path) -> PathBuf {
+        data_dir.join(".tasktui-config.yaml")
+    }
+
+    pub fn credentials_path(data_dir: &Path) -> PathBuf {
+        data_dir.join(Self::CREDENTIALS_FILE)
+    }
+
+    /// Where `load_checked` and `reset` keep the previous config file
+    pub fn backup_path(data_dir: &Path) -> PathBuf {
+        data_dir.join(Self::BACKUP_FILE)
+    }
+
+    /// Load config from data directory, or create default if not found.
+    /// Settings that can't be read fall back to their defaults (logged).
+    pub fn load(data_dir: &Path) -> Result<Self> {
+        let (config, issues) = Self::load_checked(data_dir)?;
+        for issue in &issues {
+            tracing::warn!(issue = %issue, "Invalid config setting, using its default");
+        }
+        Ok(config)
+    }
+
+    /// Load config, replacing each top-level setting that can't be read with
+    /// its default and listing what was replaced. The file is first copied to
+    /// `backup_path`, so saving the repaired config loses nothing. Secrets
+    /// found in the file are moved to `credentials_path` right away. Fails
+    /// only when the file isn't readable YAML at all.
+    pub fn load_checked(data_dir: &Path) -> Result<(Self, Vec<ConfigIssue>)> {
+        let config_path = Self::config_path(data_dir);
+
+        if config_path.exists() {
+            let content = fs::read_to_string(&config_path)?;
+            let (mut config, mut issues) = Self::parse_lenient(&content).map_err(|e| {
+                anyhow::anyhow!(
+                    "Invalid {}: {}. Fix it, or run `tasktui doctor --reset-config` to back it up and start from defaults",
+                    config_path.display(),
+                    e
+                )
+            })?;
+            let has_secrets = config.secrets_mut().iter().any(|(_, value)| value.is_some());
+            config.load_credentials(data_dir)?;
+            if !issues.is_empty() {
+                fs::copy(&config_path, Self::backup_path(data_dir))?;
+            }
+            // Written before credentials were kept apart; the next push mustn't carry them
+            if has_secrets {
+                config.save(data_dir)?;
+            }
+            if config.kanban_columns.is_empty() {
+                config.kanban_columns = default_kanban_columns();
+            }
+            // Goals written before goal ids existed get one, saved so task links stay valid
+            let unnamed: Vec<_> = config.goals.iter_mut().filter(|g| g.id.is_nil()).collect();
+            if !unnamed.is_empty() {
+                unnamed.into_iter().for_each(|g| g.id = Uuid::new_v4());
+                config.save(data_dir)?;
+            }
+            config.apply_overrides(&mut issues);
+            Ok((config, issues))
+        } else {
+            // Create default config
+            let mut config = AppConfig::default();
+            config.load_credentials(data_dir)?;
+            config.save(data_dir)?;
+            let mut issues = Vec::new();
+            config.apply_overrides(&mut issues);
+            Ok((config, issues))
+        }
+    }
+
+    /// The layers above the file: `TASKTUI_*` environment variables, then
+    /// the command line's settings (see `set_flag_settings`)
+    fn apply_overrides(&mut self, issues: &mut Vec<ConfigIssue>) {
+        for (source, key, value) in env_settings(std::env::vars()) {
+            self.apply_setting(&source, &key, &value, issues);
+        }
+        for (key, value) in FLAG_SETTINGS.get().into_iter().flatten() {
+            self.apply_setting(&format!("--set {}", key), key, value, issues);
+        }
+    }
+
+    /// Set the dotted `key` (e.g. `llm_limits.concurrency`) to `value`, read as
+    /// YAML, or as plain text when the setting wants a string. A setting that
+    /// doesn't fit is reported as coming from `source` and left alone.
+    fn apply_setting(&mut self, source: &str, key: &str, value: &str, issues: &mut Vec<ConfigIssue>) {
+        let root = match serde_yaml::to_value(&*self) {
+            Ok(root) => root,
+            Err(e) => {
+                issues.push(ConfigIssue { key: source.to_string(), line: None, message: e.to_string() });
+                return;
+            }
+        };
+        let with = |value: serde_yaml::Value| {
+            let mut root = root.clone();
+            set_path(&mut root, key, Some(value));
+            serde_yaml::from_value::<AppConfig>(root)
+        };
+        let text = serde_yaml::Value::String(value.to_string());
+        let parsed = serde_yaml::from_str(value).unwrap_or_else(|_| text.clone());
+        let result = match with(parsed.clone()) {
+            Err(e) if parsed != text => with(text).map_err(|_| e),
+            result => result,
+        };
+        match result {
+            Ok(mut config) => {
+                // The whole top-level setting is restored, so that a section
+                // the file didn't have isn't written half-empty
+                let top = key.split('.').next().unwrap_or(key);
+                let mut overridden = std::mem::take(&mut self.overridden);
+                if !overridden.iter().any(|(k, _)| k == top) {
+                    overridden.push((top.to_string(), root.get(top).cloned()));
+                }
+                config.overridden = overridden;
+                *self = config;
+            }
+            Err(e) => {
+                let message = e.to_string();
+                let message = message.split(", expected one of").next().unwrap_or(&message).to_string();
+                issues.push(ConfigIssue { key: source.to_string(), line: None, message });
+            }
+        }
+    }
+
+    /// Parse config text, dropping each top-level key whose value doesn't
+    /// deserialize (or that isn't a setting at all) until the rest does. The
+    /// error names the line and column when no key can be blamed.
+    fn parse_lenient(content: &str) -> Result<(Self, Vec<ConfigIssue>), String> {
+        let mut lines: Vec<&str> = content.lines().collect();
+        let mut issues: Vec<ConfigIssue> = Vec::new();
+        let mut previous = None;
+        loop {
+            let error = match serde_yaml::from_str::<AppConfig>(&lines.join("\n")) {
+                Ok(config) => return Ok((config, issues)),
+                Err(e) => e,
+            };
+            let location = error.location();
+            let message = error.to_string();
+            let message = message.split(" at line ").next().unwrap_or(&message);
+            let message = message.split(", expected one of").next().unwrap_or(message).to_string();
+            let fatal = || match &location {
+                Some(at) => format!("line {}, column {}: {}", at.line(), at.column(), message),
+                None => message.clone(),
+            };
+            // Dropping a key didn't help, so the file itself is broken
+            let current = (location.as_ref().map(|at| at.line()), message.clone());
+            if previous.replace(current.clone()) == Some(current) {
+                return Err(fatal());
+            }
+            // The top-level key whose block holds the error line; blank its lines
+            // so the line numbers of later errors stay right
+            let line = location.as_ref().map(|at| at.line()).ok_or_else(fatal)?;
+            let start = (0..line.min(lines.len())).rev().find(|&i| top_level_key(lines[i]).is_some()).ok_or_else(fatal)?;
+            let end = (start + 1..lines.len()).find(|&i| top_level_key(lines[i]).is_some()).unwrap_or(lines.len());
+            let key = top_level_key(lines[start]).unwrap_or_default().to_string();
+            let message = message.strip_prefix(&format!("{}: ", key))
+                .or_else(|| message.strip_prefix(&format!("{}.", key)))
+                .unwrap_or(&message)
+                .to_string();
+            issues.push(ConfigIssue { key, line: Some(line), message });
+            lines[start..end].iter_mut().for_each(|line| *line = "");
+        }
+    }
+
+    /// Back up the config file and replace it with the defaults, keeping
+    /// the credentials the defaults have a place for
+    pub fn reset(data_dir: &Path) -> Result<PathBuf> {
+        let backup = Self::backup_path(data_dir);
+        fs::copy(Self::config_path(data_dir), &backup)?;
+        let mut config = AppConfig::default();
+        config.load_credentials(data_dir)?;
+        config.save(data_dir)?;
+        Ok(backup)
+    }
+
+    /// Save config to data directory, the credentials to `credentials_path`
+    pub fn save(&self, data_dir: &Path) -> Result<()> {
+        let config_path = Self::config_path(data_dir);
+        let mut root = serde_yaml::to_value(self)?;
+        for (key, file_value) in &self.overridden {
+            set_path(&mut root, key, file_value.clone());
+        }
+        // What the file holds, so a secret given by a variable isn't saved either
+        let mut saved: AppConfig = serde_yaml::from_value(root.clone())?;
+        let mut credentials = BTreeMap::new();
+        for (key, value) in saved.secrets_mut() {
+            set_path(&mut root, &key, None);
+            if let Some(value) = value.take() {
+                credentials.insert(key, value);
+            }
+        }
+        let content = serde_yaml::to_string(&root)?;
+        fs::write(config_path, content)?;
+        Self::save_credentials(data_dir, &credentials)
+    }
+
+    /// The settings holding secrets, by dotted key
+    fn secrets_mut(&mut self) -> Vec<(String, &mut Option<String>)> {
+        let mut secrets = vec![
+            ("openai_api_key".to_string(), &mut self.openai_api_key),
+            ("git.token".to_string(), &mut self.git.token),
+            ("transcription.api_key".to_string(), &mut self.transcription.api_key),
+        ];
+        for (i, provider) in self.llm_providers.iter_mut().enumerate() {
+            secrets.push((format!("llm_providers.{}.api_key", i), &mut provider.api_key));
+        }
+        if let Some(caldav) = &mut self.caldav {
+            secrets.push(("caldav.password".to_string(), &mut caldav.password));
+        }
+        if let Some(jira) = &mut self.jira {
+            secrets.push(("jira.api_token".to_string(), &mut jira.api_token));
+        }
+        if let Some(slack) = &mut self.slack {
+            secrets.push(("slack.webhook_url".to_string(), &mut slack.webhook_url));
+        }
+        if let Some(embeddings) = &mut self.embeddings {
+            secrets.push(("embeddings.api_key".to_string(), &mut embeddings.api_key));
+        }
+        secrets
+    }
+
+    /// Fill in the secrets from `credentials_path`. Ones still in the config
+    /// file, from before they were kept apart, are used when it has none.
+    fn load_credentials(&mut self, data_dir: &Path) -> Result<()> {
+        let path = Self::credentials_path(data_dir);
+        if !path.exists() {
+            return Ok(());
+        }
+        let content = fs::read_to_string(&path)?;
+        let credentials: BTreeMap<String, String> = serde_yaml::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Invalid {}: {}", path.display(), e))?;
+        for (key, value) in self.secrets_mut() {
+            if let Some(saved) = credentials.get(&key) {
+                *value = Some(saved.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the credentials file, readable only by the user, or remove it
+    /// when there's nothing to keep
+    fn save_credentials(data_dir: &Path, credentials: &BTreeMap<String, String>) -> Result<()> {
+        let path = Self::credentials_path(data_dir);
+        if credentials.is_empty() {
+            if path.exists() {
+                fs::remove_file(&path)?;
+            }
+            return Ok(());
+        }
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&path)?;
+        file.write_all(serde_yaml::to_string(credentials)?.as_bytes())?;
+        Ok(())
+    }
+
+    /// Add a new workstream with auto-assigned key
+    pub fn add_workstream(&mut self, name: String) -> Option<char> {
+        // Find next available key (3-9)
+        let used_keys: Vec<char> = self.workstreams.iter().map(|w| w.key).collect();
+        let next_key = ('3'..='9').find(|k| !used_keys.contains(k))?;
+
+        self.workstreams.push(Workstream {
+            name,
+            key: next_key,
+        });
+
+        Some(next_key)
+    }
+
+    /// Rename a workstream
+    pub fn rename_workstream(&mut self, old_name: &str, new_name: String) -> bool {
+        if let Some(ws) = self.workstreams.iter_mut().find(|w| w.name == old_name) {
+            ws.name = new_name;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Point workstreams and Kanban columns using tag `old` at `new`.
+    /// Returns whether anything changed.
+    pub fn rename_tag(&mut self, old: &str, new: &str) -> bool {
+        let mut changed = false;
+        for ws in self.workstreams.iter_mut().filter(|w| w.name == old) {
+            ws.name = new.to_string();
+            changed = true;
+        }
+        for column in self.kanban_columns.iter_mut().filter(|c| c.tag.as_deref() == Some(old)) {
+            column.tag = Some(new.to_string());
+            changed = true;
+        }
+        changed
+    }
+
+    /// Delete a workstream
+    pub fn delete_workstream(&mut self, name: &str) -> bool {
+        let initial_len = self.workstreams.len();
+        self.workstreams.retain(|w| w.name != name);
+        self.workstreams.len() < initial_len
+    }
+
+    /// Save a named filter, replacing any existing filter with the same name
+    pub fn save_filter(&mut self, saved: SavedFilter) {
+        if let Some(existing) = self.saved_filters.iter_mut().find(|f| f.name == saved.name) {
+            *existing = saved;
+        } else {
+            self.saved_filters.push(saved);
+        }
+    }
+
+    /// Delete a saved filter
+    pub fn delete_filter(&mut self, index: usize) {
+        if index < self.saved_filters.len() {
+            self.saved_filters.remove(index);
+        }
+    }
+
+    /// Get workstream by key
+    pub fn get_workstream_by_key(&self, key: char) -> Option<&Workstream> {
+        self.workstreams.iter().find(|w| w.key == key)
+    }
+
+    /// Add a new goal
+    pub fn add_goal(&mut self, description: String, area: String) {
+        self.goals.push(Goal::new(description, area));
+    }
+
+    /// Update a goal's description
+    pub fn update_goal(&mut self, index: usize, description: String) {
+        if let Some(goal) = self.goals.get_mut(index) {
+            goal.description = description;
+        }
+    }
+
+    /// Update a goal's area
+    pub fn update_goal_area(&mut self, index: usize, area: String) {
+        if let Some(goal) = self.goals.get_mut(index) {
+            goal.area = area;
+        }
+    }
+
+    /// Cycle a goal's priority (1→2→3→4→5→1)
+    pub fn cycle_goal_priority(&mut self, index: usize) {
+        if let Some(goal) = self.goals.get_mut(index) {
+            goal.priority = if goal.priority >= 5 { 1 } else { goal.priority + 1 };
+        }
+    }
+
+    /// Toggle a goal's active state
+    pub fn toggle_goal_active(&mut self, index: usize) {
+        if let Some(goal) = self.goals.get_mut(index) {
+            goal.active = !goal.active;
+        }
+    }
+
+    /// Delete a goal
+    pub fn delete_goal(&mut self, index: usize) {
+        if index < self.goals.len() {
+            self.goals.remove(index);
+        }
+    }
+
+    /// Look up a goal by id
+    pub fn goal(&self, id: Uuid) -> Option<&Goal> {
+        self.goals.iter().find(|g| g.id == id)
+    }
+
+    /// Get active goals sorted by priority
+    pub fn active_goals(&self) -> Vec<&Goal> {
+        let mut goals: Vec<_> = self.goals.iter().filter(|g| g.active).collect();
+        goals.sort_by_key(|g| g.priority);
+        goals
+    }
+
+    /// The providers enrichment tries, in order: `llm_providers`, or just
+    /// OpenAI with `openai_api_key` when none are listed. Entries that need a
+    /// key and have none are left out.
+    pub fn provider_chain(&self) -> Vec<LlmProvider> {
+        let mut chain: Vec<LlmProvider> = if self.llm_providers.is_empty() {
+            self.openai_api_key.iter()
+                .map(|key| LlmProvider::new(ProviderKind::Openai, Some(key.clone())))
+                .collect()
+        } else {
+            self.llm_providers.iter()
+                .cloned()
+                .map(|mut p| {
+                    if p.provider == ProviderKind::Openai && p.api_key.is_none() {
+                        p.api_key = self.openai_api_key.clone();
+                    }
+                    p
+                })
+                .filter(|p| p.provider == ProviderKind::Ollama || p.api_key.is_some())
+                .collect()
+        };
+        if let (Some(first), Some(model)) = (chain.first_mut(), &self.llm_model) {
+            first.model = Some(model.clone());
+        }
+        chain
+    }
+
+    /// Format goals for LLM context
+    pub fn goals_context(&self) -> String {
+        let active = self.active_goals();
+        if active.is_empty() {
+            return String::new();
+        }
+
+        let mut context = String::from("Current priorities and goals:\n");
+        for goal in active {
+            let priority_stars = "★".repeat(6 - goal.priority as usize);
+            context.push_str(&format!("- [{}] {}: {}\n", goal.area, priority_stars, goal.description));
+        }
+        context
+    }
+}
+
+/// Settings from the command line, which win over the environment and the file
+static FLAG_SETTINGS: OnceLock<Vec<(String, String)>> = OnceLock::new();
+
+/// Environment variables that aren't settings: the data directory is a flag's
+/// default, hooks are given the event, and backups read their passphrase
+const RESERVED_ENV: &[&str] = &["TASKTUI_DATA_DIR", "TASKTUI_EVENT", crate::backup::PASSPHRASE_ENV];
+
+/// Record the `(key, value)` settings given on the command line, applied to
+/// every config loaded afterwards. Only the first call counts.
+pub fn set_flag_settings(settings: Vec<(String, String)>) {
+    let _ = FLAG_SETTINGS.set(settings);
+}
+
+/// `TASKTUI_*` variables as `(variable, key, value)`: `TASKTUI_STALE_DAYS` sets
+/// `stale_days`, and a double underscore reaches into a section, as in
+/// `TASKTUI_LLM_LIMITS__CONCURRENCY`
+fn env_settings(vars: impl Iterator<Item = (String, String)>) -> Vec<(String, String, String)> {
+    let mut settings: Vec<_> = vars
+        .filter(|(name, _)| !RESERVED_ENV.contains(&name.as_str()))
+        .filter_map(|(name, value)| {
+            let key = name.strip_prefix("TASKTUI_")?.to_lowercase().replace("__", ".");
+            Some((name, key, value))
+        })
+        .collect();
+    settings.sort();
+    settings
+}
+
+/// Set (or with `None` remove) the dotted `key`, creating sections on the
+/// way. A number picks an existing list entry, as in `llm_providers.0.api_key`.
+fn set_path(root: &mut serde_yaml::Value, key: &str, value: Option<serde_yaml::Value>) {
+    let mut parts: Vec<&str> = key.split('.').collect();
+    let Some(last) = parts.pop() else {
+        return;
+    };
+    let mut node = root;
+    for part in parts {
+        if node.is_sequence() {
+            match part.parse::<usize>().ok().and_then(|i| node.get_mut(i)) {
+                Some(next) => node = next,
+                None => return,
+            }
+            continue;
+        }
+        if !node.get(part).is_some_and(|v| v.is_mapping() || v.is_sequence()) {
+            if value.is_none() {
+                return;
+            }
+            if let Some(map) = node.as_mapping_mut() {
+                map.insert(part.into(), serde_yaml::Value::Mapping(Default::default()));
+            }
+        }
+        match node.get_mut(part) {
+            Some(next) => node = next,
+            None => return,
+        }
+    }
+    if let Some(map) = node.as_mapping_mut() {
+        match value {
+            Some(value) => map.insert(last.into(), value),
+            None => map.remove(last),
+        };
+    }
+}
+
+/// The key a line starts a top-level mapping entry for, e.g. `hooks` in `hooks:`
+fn top_level_key(line: &str) -> Option<&str> {
+    let (key, _) = line.split_once(':')?;
+    let key = key.trim_end();
+    let is_key = !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    is_key.then_some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ItemType;
+
+    #[test]
+    fn test_kanban_column_matches() {
+        let column: KanbanColumn = serde_yaml::from_str("name: BLOCKED\nstatus: waiting\ntag: blocked").unwrap();
+
+        let mut task = TaskItem::new("Task".to_string(), ItemType::Task);
+        task.frontmatter.status = Status::Waiting;
+        assert!(!column.matches(&task));
+
+        task.frontmatter.tags.push("blocked".to_string());
+        assert!(column.matches(&task));
+    }
+
+    #[test]
+    fn test_missing_columns_use_defaults() {
+        let config: AppConfig = serde_yaml::from_str("workstreams: []").unwrap();
+        let names: Vec<_> = config.kanban_columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["ACTIVE", "NEXT", "WAITING", "DONE"]);
+    }
+
+    #[test]
+    fn test_goals_get_ids_on_load() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::write(
+            AppConfig::config_path(temp_dir.path()),
+            "workstreams: []\ngoals:\n- description: Ship v2\n  area: work\n  priority: 1\n  active: true\n",
+        ).unwrap();
+
+        let id = AppConfig::load(temp_dir.path()).unwrap().goals[0].id;
+        assert!(!id.is_nil());
+        // Saved, so links made against it survive the next load
+        assert_eq!(AppConfig::load(temp_dir.path()).unwrap().goals[0].id, id);
+    }
+
+    #[test]
+    fn test_date_labels() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+        let day = |offset: i64| today + chrono::Duration::days(offset);
+        let mut display = DisplayConfig::default();
+
+        assert_eq!(display.due_label(&Due::on(day(0)), today), "today");
+        assert_eq!(display.due_label(&Due::on(day(1)), today), "tomorrow");
+        assert_eq!(display.due_label(&Due::on(day(3)), today), "in 3d");
+        assert_eq!(display.due_label(&Due::on(day(-2)), today), "2d overdue");
+        assert_eq!(display.due_label(&Due::on(day(-30)), today), "30d overdue");
+        assert_eq!(display.due_label(&Due::on(day(10)), today), "2025-03-22");
+        let timed = Due { date: day(1), time: chrono::NaiveTime::from_hms_opt(15, 0, 0), offset: None };
+        assert_eq!(display.due_label(&timed, today), "tomorrow 15:00");
+
+        assert_eq!(display.date_label(day(-2), today), "2d ago");
+        assert_eq!(display.date_label(day(-30), today), "2025-02-10");
+
+        display.date_format = Some("%d.%m.%Y".to_string());
+        assert_eq!(display.due_label(&Due::on(day(10)), today), "22.03.2025");
+        assert_eq!(display.format_date(today), "12.03.2025");
+
+        // An invalid format falls back instead of panicking when printed
+        display.date_format = Some("%Q".to_string());
+        assert!(!is_valid_date_format("%Q"));
+        assert_eq!(display.format_date(today), "2025-03-12");
+    }
+
+    #[test]
+    fn test_provider_chain() {
+        let mut config = AppConfig::default();
+        assert!(config.provider_chain().is_empty());
+        config.openai_api_key = Some("sk-openai".to_string());
+        assert_eq!(config.provider_chain().len(), 1);
+
+        config.llm_providers = serde_yaml::from_str(
+            "- provider: anthropic\n  api_key: sk-ant\n- provider: openai\n- provider: anthropic\n- provider: ollama\n  model: llama3.2",
+        ).unwrap();
+        let chain = config.provider_chain();
+        let kinds: Vec<_> = chain.iter().map(|p| p.provider.as_str()).collect();
+        // The second anthropic entry has no key, so it's skipped
+        assert_eq!(kinds, ["anthropic", "openai", "ollama"]);
+        assert_eq!(chain[1].api_key.as_deref(), Some("sk-openai"));
+        assert_eq!(chain[2].model.as_deref(), Some("llama3.2"));
+    }
+
+    #[test]
+    fn test_invalid_settings_fall_back_to_defaults() {
+        let content = "\
+follow_up_days: 5
+llm_limits:
+  concurrency: lots
+stale_days: 9
+folow_up_days: 3
+";
+        let (config, issues) = AppConfig::parse_lenient(content).unwrap();
+        assert_eq!((config.follow_up_days, config.stale_days), (5, 9));
+        assert!(config.llm_limits.is_default());
+        let found: Vec<_> = issues.iter().map(|i| (i.key.as_str(), i.line)).collect();
+        assert_eq!(found, [("llm_limits", Some(3)), ("folow_up_days", Some(5))]);
+        assert!(issues[0].to_string().starts_with("line 3: llm_limits: concurrency: invalid type"));
+        assert_eq!(issues[1].message, "unknown field `folow_up_days`");
+
+        let error = AppConfig::parse_lenient("\tstale_days: 9\n").unwrap_err();
+        assert!(error.starts_with("line "), "{}", error);
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::write(AppConfig::config_path(temp_dir.path()), content).unwrap();
+        let (_, issues) = AppConfig::load_checked(temp_dir.path()).unwrap();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(fs::read_to_string(AppConfig::backup_path(temp_dir.path())).unwrap(), content);
+        AppConfig::reset(temp_dir.path()).unwrap();
+        assert!(AppConfig::load_checked(temp_dir.path()).unwrap().1.is_empty());
+    }
+
+    #[test]
+    fn test_settings_layer_over_the_file() {
+        let vars = [
+            ("TASKTUI_STALE_DAYS", "9"),
+            ("TASKTUI_LLM_LIMITS__CONCURRENCY", "2"),
+            ("TASKTUI_EVENT", "on_create"),
+            ("HOME", "/home/sam"),
+        ];
+        let settings = env_settings(vars.iter().map(|(k, v)| (k.to_string(), v.to_string())));
+        let keys: Vec<_> = settings.iter().map(|(_, key, value)| (key.as_str(), value.as_str())).collect();
+        assert_eq!(keys, [("llm_limits.concurrency", "2"), ("stale_days", "9")]);
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::write(AppConfig::config_path(temp_dir.path()), "openai_api_key: sk-file\nstale_days: 20\n").unwrap();
+        let (mut config, _) = AppConfig::load_checked(temp_dir.path()).unwrap();
+        let mut issues = Vec::new();
+        for (source, key, value) in &settings {
+            config.apply_setting(source, key, value, &mut issues);
+        }
+        // Flags come last, so they win; a number is still text where text is wanted
+        config.apply_setting("--set stale_days", "stale_days", "12", &mut issues);
+        config.apply_setting("--set openai_api_key", "openai_api_key", "12345", &mut issues);
+        config.apply_setting("--set llm_model", "llm_model", "gpt-4.1", &mut issues);
+        config.apply_setting("--set follow_up_days", "follow_up_days", "soon", &mut issues);
+        assert_eq!((config.stale_days, config.llm_limits.concurrency), (12, 2));
+        assert_eq!(config.openai_api_key.as_deref(), Some("12345"));
+        assert_eq!(config.provider_chain()[0].model.as_deref(), Some("gpt-4.1"));
+        let sources: Vec<_> = issues.iter().map(|i| i.key.as_str()).collect();
+        assert_eq!(sources, ["--set follow_up_days"]);
+
+        // Saving keeps the file's own values for overridden settings
+        config.follow_up_days = 4;
+        config.save(temp_dir.path()).unwrap();
+        let saved = fs::read_to_string(AppConfig::config_path(temp_dir.path())).unwrap();
+        assert!(saved.contains("stale_days: 20"), "{}", saved);
+        let credentials = fs::read_to_string(AppConfig::credentials_path(temp_dir.path())).unwrap();
+        assert_eq!(credentials, "openai_api_key: sk-file\n");
+        assert!(saved.contains("follow_up_days: 4"));
+        assert!(!saved.contains("llm_model") && !saved.contains("llm_limits"));
+    }
+
+    #[test]
+    fn test_credentials_stay_out_of_the_config_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        // Written before credentials were kept apart
+        fs::write(
+            AppConfig::config_path(temp_dir.path()),
+            "git:\n  credentials: token\n  token: ghp-secret\njira:\n  url: https://jira.example.com\n  email: me@example.com\n  api_token: jira-secret\n\
+             llm_providers:\n  - provider: ollama\n  - provider: anthropic\n    api_key: sk-ant-secret\n\
+             slack:\n  webhook_url: https://hooks.slack.com/services/secret\n",
+        ).unwrap();
+
+        // Loading moves them over, before anything could push the file
+        let config = AppConfig::load(temp_dir.path()).unwrap();
+        assert_eq!(config.git.token.as_deref(), Some("ghp-secret"));
+        let saved = fs::read_to_string(AppConfig::config_path(temp_dir.path())).unwrap();
+        assert!(!saved.contains("secret"), "{}", saved);
+        assert!(saved.contains("credentials: token") && saved.contains("email: me@example.com"));
+        assert!(saved.contains("provider: anthropic") && saved.contains("slack:"));
+        let credentials = fs::read_to_string(AppConfig::credentials_path(temp_dir.path())).unwrap();
+        assert!(credentials.contains("llm_providers.1.api_key: sk-ant-secret"), "{}", credentials);
+
+        let mut config = AppConfig::load(temp_dir.path()).unwrap();
+        assert_eq!(config.git.token.as_deref(), Some("ghp-secret"));
+        assert_eq!(config.jira.as_ref().unwrap().api_token.as_deref(), Some("jira-secret"));
+        assert_eq!(config.llm_providers[1].api_key.as_deref(), Some("sk-ant-secret"));
+        assert_eq!(
+            config.slack.as_ref().unwrap().webhook_url.as_deref(),
+            Some("https://hooks.slack.com/services/secret")
+        );
+
+        config.git.token = None;
+        config.jira = None;
+        config.llm_providers.clear();
+        config.slack = None;
+        config.save(temp_dir.path()).unwrap();
+        assert!(!AppConfig::credentials_path(temp_dir.path()).exists());
+    }
+}
@@ -0,0 +1,122 @@
+use crate::config::{AppConfig, Goal};
+use crate::models::{GoalLinks, Status, TaskItem};
+use chrono::{Duration, Local, NaiveDateTime};
+
+/// Open tasks listed under "Top priorities"
+pub const TOP_PRIORITIES: usize = 5;
+
+/// One workstream at a glance: everything tagged with its name
+pub struct WorkstreamSummary<'a> {
+    pub name: String,
+    /// Tasks per status, leaving out archived ones and statuses with none
+    pub counts: Vec<(Status, usize)>,
+    /// Past their deadline, most overdue first
+    pub overdue: Vec<&'a TaskItem>,
+    /// Most urgent open tasks, then soonest due
+    pub top: Vec<&'a TaskItem>,
+    /// Active goals in the workstream's area with their progress, by priority
+    pub goals: Vec<(&'a Goal, u8)>,
+    /// Tasks completed in the last 7 days
+    pub done_this_week: usize,
+    /// Tasks completed in the 7 days before that
+    pub done_last_week: usize,
+}
+
+impl<'a> WorkstreamSummary<'a> {
+    /// `now` is local wall-clock time, as from `models::now_local`
+    pub fn new(tasks: &'a [TaskItem], config: &'a AppConfig, name: &str, now: NaiveDateTime) -> Self {
+        let stream: Vec<&TaskItem> = tasks.iter()
+            .filter(|t| !t.is_project() && t.has_tag(name))
+            .collect();
+
+        let counts = [Status::Inbox, Status::Active, Status::Next, Status::Waiting, Status::Done]
+            .into_iter()
+            .map(|status| {
+                let count = stream.iter().filter(|t| t.frontmatter.status == status).count();
+                (status, count)
+            })
+            .filter(|(_, count)| *count > 0)
+            .collect();
+
+        let mut overdue: Vec<&TaskItem> = stream.iter().copied().filter(|t| t.is_overdue(now)).collect();
+        overdue.sort_by_key(|t| t.frontmatter.due_date.map(|d| d.deadline()));
+
+        let mut top: Vec<&TaskItem> = stream.iter().copied().filter(|t| t.is_open()).collect();
+        top.sort_by_key(|t| {
+            let due = t.frontmatter.due_date.map(|d| d.deadline());
+            (t.frontmatter.priority.rank(), due.is_none(), due)
+        });
+        top.truncate(TOP_PRIORITIES);
+
+        let goals = config.active_goals()
+            .into_iter()
+            .filter(|g| g.area == name)
+            .map(|goal| (goal, GoalLinks::new(tasks, goal.id).progress()))
+            .collect();
+
+        let completed_within = |from: Duration, to: Duration| {
+            stream.iter()
+                .filter_map(|t| t.frontmatter.completed_at)
+                .map(|at| at.with_timezone(&Local).naive_local())
+                .filter(|at| *at > now - from && *at <= now - to)
+                .count()
+        };
+
+        Self {
+            name: name.to_string(),
+            counts,
+            overdue,
+            top,
+            goals,
+            done_this_week: completed_within(Duration::days(7), Duration::zero()),
+            done_last_week: completed_within(Duration::days(14), Duration::days(7)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Due, ItemType, Priority};
+    use chrono::Utc;
+
+    #[test]
+    fn test_workstream_summary() {
+        let now = Local::now().naive_local();
+        let task = |title: &str, tag: &str| {
+            let mut task = TaskItem::new(title.to_string(), ItemType::Task);
+            task.frontmatter.tags = vec![tag.to_string()];
+            task
+        };
+        let mut config = AppConfig::default();
+        let mut goal = Goal::new("Ship v2".to_string(), "work".to_string());
+        goal.active = true;
+        config.goals.push(goal.clone());
+        config.goals.push(Goal { active: true, ..Goal::new("Run a 10k".to_string(), "personal".to_string()) });
+
+        let mut late = task("Send invoice", "work");
+        late.frontmatter.due_date = Some(Due::on(now.date() - Duration::days(3)));
+        let mut urgent = task("Fix outage", "work");
+        urgent.frontmatter.priority = Priority::P0;
+        urgent.frontmatter.goal_id = Some(goal.id);
+        let mut shipped = task("Release notes", "work");
+        shipped.set_status(Status::Done);
+        shipped.frontmatter.completed_at = Some(Utc::now() - Duration::days(2));
+        shipped.frontmatter.goal_id = Some(goal.id);
+        let mut earlier = shipped.clone();
+        earlier.frontmatter.completed_at = Some(Utc::now() - Duration::days(9));
+        let mut waiting = task("Contract review", "work");
+        waiting.frontmatter.status = Status::Waiting;
+        let tasks = vec![late, urgent, shipped, earlier, waiting, task("Groceries", "personal")];
+
+        let summary = WorkstreamSummary::new(&tasks, &config, "work", now);
+        assert_eq!(summary.counts, vec![(Status::Active, 2), (Status::Waiting, 1), (Status::Done, 2)]);
+        assert_eq!(summary.overdue.len(), 1);
+        assert_eq!(summary.overdue[0].frontmatter.title, "Send invoice");
+        let top: Vec<_> = summary.top.iter().map(|t| t.frontmatter.title.as_str()).collect();
+        assert_eq!(top, ["Fix outage", "Send invoice", "Contract review"]);
+        assert_eq!(summary.goals.len(), 1);
+        assert_eq!((summary.goals[0].0.description.as_str(), summary.goals[0].1), ("Ship v2", 66));
+        assert_eq!((summary.done_this_week, summary.done_last_week), (1, 1));
+    }
+}
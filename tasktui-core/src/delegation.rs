@@ -0,0 +1,120 @@
+use crate::models::{ItemType, Status, TaskItem};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+/// Group delegated Waiting tasks by person (sorted by name, oldest hand-off first)
+pub fn waiting_by_person(tasks: &[TaskItem]) -> BTreeMap<String, Vec<&TaskItem>> {
+    let mut people: BTreeMap<String, Vec<&TaskItem>> = BTreeMap::new();
+
+    for task in tasks {
+        if task.frontmatter.status != Status::Waiting {
+            continue;
+        }
+        if let Some(person) = &task.frontmatter.delegated_to {
+            people.entry(person.clone()).or_default().push(task);
+        }
+    }
+
+    for tasks in people.values_mut() {
+        tasks.sort_by_key(|t| t.frontmatter.delegated_at.unwrap_or(t.frontmatter.created_at));
+    }
+
+    people
+}
+
+/// Build "Ping X about Y" tasks for delegated items that have waited longer than `after_days`.
+/// A new ping is only generated when there is no open follow-up and the last one is older
+/// than `after_days`, so completing a ping restarts the clock.
+pub fn follow_ups_due(tasks: &[TaskItem], after_days: u32, now: DateTime<Utc>) -> Vec<TaskItem> {
+    if after_days == 0 {
+        return Vec::new();
+    }
+    let after_days = after_days as i64;
+
+    let mut follow_ups = Vec::new();
+
+    for task in tasks {
+        let Some(days) = task.days_waiting(now) else {
+            continue;
+        };
+        if days < after_days {
+            continue;
+        }
+
+        let recently_pinged = tasks.iter()
+            .filter(|t| t.frontmatter.follow_up_of == Some(task.frontmatter.id))
+            .any(|t| {
+                !t.frontmatter.status.is_closed()
+                    || (now - t.frontmatter.created_at).num_days() < after_days
+            });
+        if recently_pinged {
+            continue;
+        }
+
+        let person = task.frontmatter.delegated_to.clone().unwrap_or_default();
+        let mut ping = TaskItem::new(
+            format!("Ping {} about {}", person, task.frontmatter.title),
+            ItemType::Task,
+        );
+        ping.frontmatter.status = Status::Next;
+        ping.frontmatter.tags = task.frontmatter.tags.clone();
+        ping.frontmatter.follow_up_of = Some(task.frontmatter.id);
+        ping.frontmatter.parent_goal_id = task.frontmatter.parent_goal_id;
+        ping.body = format!(
+            "Delegated to {} {} days ago and still waiting.\n\nOriginal task: {}",
+            person, days, task.frontmatter.id
+        );
+        follow_ups.push(ping);
+    }
+
+    follow_ups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn delegated(title: &str, person: &str, days_ago: i64) -> TaskItem {
+        let mut task = TaskItem::new(title.to_string(), ItemType::Task);
+        task.delegate(person.to_string());
+        task.frontmatter.delegated_at = Some(Utc::now() - Duration::days(days_ago));
+        task
+    }
+
+    #[test]
+    fn test_follow_up_generated_after_threshold() {
+        let tasks = vec![
+            delegated("Send contract", "Alice", 5),
+            delegated("Review slides", "Bob", 1),
+        ];
+
+        let follow_ups = follow_ups_due(&tasks, 3, Utc::now());
+        assert_eq!(follow_ups.len(), 1);
+        assert_eq!(follow_ups[0].frontmatter.title, "Ping Alice about Send contract");
+        assert_eq!(follow_ups[0].frontmatter.follow_up_of, Some(tasks[0].frontmatter.id));
+    }
+
+    #[test]
+    fn test_no_duplicate_follow_up_while_open() {
+        let mut tasks = vec![delegated("Send contract", "Alice", 5)];
+        let first = follow_ups_due(&tasks, 3, Utc::now());
+        tasks.extend(first);
+
+        assert!(follow_ups_due(&tasks, 3, Utc::now()).is_empty());
+    }
+
+    #[test]
+    fn test_waiting_by_person() {
+        let tasks = vec![
+            delegated("A", "Bob", 1),
+            delegated("B", "Alice", 2),
+            delegated("C", "Bob", 3),
+        ];
+
+        let people = waiting_by_person(&tasks);
+        let names: Vec<_> = people.keys().cloned().collect();
+        assert_eq!(names, vec!["Alice", "Bob"]);
+        assert_eq!(people["Bob"][0].frontmatter.title, "C");
+    }
+}
@@ -0,0 +1,267 @@
+use crate::activity::LOG_HEADING;
+use crate::config::{AppConfig, EmbeddingsConfig};
+use crate::llm::cache::fnv1a;
+use crate::models::TaskItem;
+use crate::runtime::block_on;
+use crate::search::Hit;
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+use uuid::Uuid;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Texts sent per embeddings request
+const BATCH_SIZE: usize = 64;
+
+/// Characters of a task embedded; the rest of a long body is left out
+const MAX_TEXT_CHARS: usize = 4000;
+
+/// Below this cosine similarity a task has little to do with the query
+const MIN_SIMILARITY: f32 = 0.2;
+
+/// A vector and the hash of the task text it was computed from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Embedded {
+    pub hash: String,
+    pub vector: Vec<f32>,
+}
+
+/// Vectors per task, kept in `.tasktui-embeddings.json` so only tasks whose
+/// text changed are sent to the API again
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EmbeddingIndex {
+    /// Model the vectors came from; vectors of another model don't compare
+    pub model: String,
+    pub tasks: HashMap<Uuid, Embedded>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+impl EmbeddingIndex {
+    pub const FILE_NAME: &'static str = ".tasktui-embeddings.json";
+
+    pub fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join(Self::FILE_NAME)
+    }
+
+    /// The saved index, or an empty one when missing or made with another model
+    pub fn load(data_dir: &Path, model: &str) -> Self {
+        fs::read_to_string(Self::path(data_dir))
+            .ok()
+            .and_then(|content| serde_json::from_str::<Self>(&content).ok())
+            .filter(|index| index.model == model)
+            .unwrap_or_else(|| Self { model: model.to_string(), tasks: HashMap::new() })
+    }
+
+    pub fn save(&self, data_dir: &Path) -> Result<()> {
+        let content = serde_json::to_string(self)?;
+        fs::write(Self::path(data_dir), content).context("Failed to write the embeddings file")
+    }
+
+    /// Embed the tasks that are new or changed and forget deleted ones.
+    /// Returns how many were embedded; after an error, the batches that
+    /// went through are kept.
+    pub fn update(&mut self, config: &EmbeddingsConfig, api_key: Option<&str>, tasks: &[TaskItem]) -> Result<usize> {
+        let ids: HashSet<Uuid> = tasks.iter().map(|t| t.frontmatter.id).collect();
+        self.tasks.retain(|id, _| ids.contains(id));
+        let stale: Vec<(Uuid, String, String)> = tasks.iter()
+            .map(|task| {
+                let text = task_text(task);
+                (task.frontmatter.id, format!("{:016x}", fnv1a(&text)), text)
+            })
+            .filter(|(id, hash, _)| self.tasks.get(id).is_none_or(|e| e.hash != *hash))
+            .collect();
+
+        for batch in stale.chunks(BATCH_SIZE) {
+            let texts: Vec<String> = batch.iter().map(|(_, _, text)| text.clone()).collect();
+            let vectors = embed(config, api_key, &texts)?;
+            for ((id, hash, _), vector) in batch.iter().zip(vectors) {
+                self.tasks.insert(*id, Embedded { hash: hash.clone(), vector });
+            }
+        }
+        Ok(stale.len())
+    }
+
+    /// Up to `limit` of `tasks` closest in meaning to `query`, closest first
+    pub fn nearest<'a>(&self, tasks: &'a [TaskItem], query: &[f32], limit: usize) -> Vec<Hit<'a>> {
+        let mut hits: Vec<Hit> = tasks.iter()
+            .filter_map(|task| {
+                let embedded = self.tasks.get(&task.frontmatter.id)?;
+                let score = cosine(&embedded.vector, query);
+                (score >= MIN_SIMILARITY).then_some(Hit { task, score: score as f64, snippet: None })
+            })
+            .collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(limit);
+        hits
+    }
+}
+
+/// What's embedded of a task: its title, tags and body without the activity log
+fn task_text(task: &TaskItem) -> String {
+    let body = crate::activity::without_section(&task.body, LOG_HEADING);
+    let text = format!("{}\n{}\n{}", task.frontmatter.title, task.frontmatter.tags.join(" "), body.trim());
+    text.chars().take(MAX_TEXT_CHARS).collect()
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norms = norm(a) * norm(b);
+    if norms == 0.0 { 0.0 } else { dot / norms }
+}
+
+/// One vector per text, in order
+pub fn embed(config: &EmbeddingsConfig, api_key: Option<&str>, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+    let client = Client::builder().timeout(TIMEOUT).build().context("Failed to set up the embeddings client")?;
+    let body = json!({ "model": config.model, "input": texts });
+    let send = async {
+        let mut request = client.post(&config.url).json(&body);
+        if let Some(key) = api_key {
+            request = request.bearer_auth(key);
+        }
+        let response = request.send().await.context("Embeddings request failed")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Embeddings API error {}: {}", status, body);
+        }
+        let mut parsed: EmbeddingsResponse = response.json().await.context("Unexpected embeddings response")?;
+        parsed.data.sort_by_key(|d| d.index);
+        if parsed.data.len() != texts.len() {
+            anyhow::bail!("Embeddings API returned {} vectors for {} texts", parsed.data.len(), texts.len());
+        }
+        Ok(parsed.data.into_iter().map(|d| d.embedding).collect())
+    };
+    block_on(send).context("Failed to start the async runtime")?
+}
+
+/// Tasks closest in meaning to `query`, best first. Brings the index in
+/// `data_dir` up to date first, so the first search of a vault embeds all of it.
+pub fn semantic_search<'a>(data_dir: &Path, config: &AppConfig, tasks: &'a [TaskItem], query: &str, limit: usize) -> Result<Vec<Hit<'a>>> {
+    let embeddings = config.embeddings.as_ref()
+        .context("Semantic search needs an embeddings API: set embeddings in the config")?;
+    if query.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let api_key = embeddings.api_key.as_deref().or(config.openai_api_key.as_deref());
+
+    let mut index = EmbeddingIndex::load(data_dir, &embeddings.model);
+    let embedded = index.update(embeddings, api_key, tasks);
+    if !matches!(embedded, Ok(0)) {
+        index.save(data_dir)?;
+    }
+    embedded?;
+    let query = embed(embeddings, api_key, &[query.to_string()])?.pop().unwrap_or_default();
+    Ok(index.nearest(tasks, &query, limit))
+}
+
+/// `semantic_search` on its own thread, returning the ids of the hits
+pub fn search_in_background(data_dir: &Path, config: &AppConfig, tasks: Vec<TaskItem>, query: &str, limit: usize) -> Receiver<Result<Vec<Uuid>>> {
+    let (sender, receiver) = mpsc::channel();
+    let (data_dir, config, query) = (data_dir.to_path_buf(), config.clone(), query.to_string());
+    thread::spawn(move || {
+        let hits = semantic_search(&data_dir, &config, &tasks, &query, limit)
+            .map(|hits| hits.iter().map(|h| h.task.frontmatter.id).collect());
+        let _ = sender.send(hits);
+    });
+    receiver
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ItemType;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    /// An embeddings API whose vectors say whether each text is about
+    /// contracts or about food, counting the texts it's sent
+    fn fake_api() -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/v1/embeddings", listener.local_addr().unwrap());
+        let embedded = Arc::new(AtomicUsize::new(0));
+        let counter = embedded.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                let inputs = request["input"].as_array().unwrap();
+                counter.fetch_add(inputs.len(), Ordering::SeqCst);
+                let data: Vec<serde_json::Value> = inputs.iter().enumerate().map(|(index, text)| {
+                    let text = text.as_str().unwrap().to_lowercase();
+                    let about = |words: &[&str]| if words.iter().any(|w| text.contains(w)) { 1.0 } else { 0.0 };
+                    json!({ "index": index, "embedding": [about(&["contract", "agreement", "lease"]), about(&["milk", "groceries", "dinner"]), 0.1] })
+                }).collect();
+                let body = json!({ "data": data }).to_string();
+                write!(stream, "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body).unwrap();
+            }
+        });
+        (url, embedded)
+    }
+
+    #[test]
+    fn test_semantic_search() {
+        let (url, embedded) = fake_api();
+        let dir = TempDir::new().unwrap();
+        let mut config = AppConfig::default();
+        config.embeddings = Some(EmbeddingsConfig { url, model: "fake".to_string(), api_key: None });
+        let mut tasks = vec![
+            TaskItem::new("Review the vendor agreement".to_string(), ItemType::Task),
+            TaskItem::new("Buy milk".to_string(), ItemType::Task),
+        ];
+
+        // No keyword in common, but close in meaning
+        let hits = semantic_search(dir.path(), &config, &tasks, "that thing about the lease renewal", 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].task.frontmatter.title, "Review the vendor agreement");
+        assert_eq!(embedded.load(Ordering::SeqCst), 3);
+
+        // Unchanged tasks aren't embedded again; an edited one is
+        tasks[1].frontmatter.title = "Plan dinner".to_string();
+        let hits = semantic_search(dir.path(), &config, &tasks, "groceries", 10).unwrap();
+        assert_eq!(hits[0].task.frontmatter.title, "Plan dinner");
+        assert_eq!(embedded.load(Ordering::SeqCst), 5);
+        assert_eq!(EmbeddingIndex::load(dir.path(), "fake").tasks.len(), 2);
+        assert!(EmbeddingIndex::load(dir.path(), "other").tasks.is_empty());
+
+        assert!(semantic_search(dir.path(), &AppConfig::default(), &tasks, "lease", 10).is_err());
+    }
+}
@@ -0,0 +1,555 @@
+use crate::flow::ProjectFlow;
+use crate::models::{self, TaskItem};
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Width of the ASCII Gantt chart's timeline, in characters
+const GANTT_WIDTH: usize = 48;
+/// Longest task title shown in front of a Gantt bar
+const GANTT_LABEL_WIDTH: usize = 24;
+/// Most days drawn in the burndown sparkline; longer histories are sampled
+const SPARKLINE_WIDTH: usize = 30;
+/// Sparkline bars, lowest first
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// Tag that makes a project's task one of its milestones in the status report
+pub const MILESTONE_TAG: &str = "milestone";
+/// Columns of the status report's task table
+const REPORT_COLUMNS: [&str; 5] = ["Task", "Owner", "Status", "Due", "Progress"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Md,
+    Html,
+}
+
+impl Format {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Md => "md",
+            Format::Html => "html",
+        }
+    }
+}
+
+/// Output of `project report`: markdown for a status email or wiki, or the
+/// task table alone as CSV for a spreadsheet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    Md,
+    Csv,
+}
+
+/// A standalone document, rendered to markdown or HTML
+pub struct Document {
+    pub title: String,
+    blocks: Vec<Block>,
+}
+
+enum Block {
+    Heading(String),
+    Paragraph(String),
+    Table(Vec<&'static str>, Vec<Vec<String>>),
+    Pre(String),
+    /// A task body: markdown already, converted for HTML
+    Body(String),
+}
+
+/// The document for an item: the project report for projects, the task sheet otherwise
+pub fn document(item: &TaskItem, tasks: &[TaskItem], today: NaiveDate) -> Document {
+    if item.is_project() {
+        project_document(item, tasks, today)
+    } else {
+        task_document(item, tasks)
+    }
+}
+
+/// Write a project as `exports/<slug>.md` and `.html` in the data dir,
+/// returning the HTML path
+pub fn write_project(data_dir: &Path, project: &TaskItem, tasks: &[TaskItem], today: NaiveDate) -> Result<PathBuf> {
+    let dir = data_dir.join("exports");
+    fs::create_dir_all(&dir)?;
+    let doc = project_document(project, tasks, today);
+    let mut html_path = PathBuf::new();
+    for format in [Format::Md, Format::Html] {
+        let path = dir.join(format!("{}.{}", slug(&doc.title), format.extension()));
+        fs::write(&path, doc.render(format)).with_context(|| format!("Failed to write {}", path.display()))?;
+        html_path = path;
+    }
+    Ok(html_path)
+}
+
+/// A task with its fields and notes
+pub fn task_document(task: &TaskItem, tasks: &[TaskItem]) -> Document {
+    let fm = &task.frontmatter;
+    let mut fields = vec![
+        vec!["Code".to_string(), task.code()],
+        vec!["Status".to_string(), fm.status.as_str().to_string()],
+        vec!["Priority".to_string(), fm.priority.as_str().to_string()],
+    ];
+    if let Some(due) = &fm.due_date {
+        fields.push(vec!["Due".to_string(), due.to_string()]);
+    }
+    if !fm.tags.is_empty() {
+        fields.push(vec!["Tags".to_string(), fm.tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" ")]);
+    }
+    if let Some(project) = fm.parent_goal_id.and_then(|id| tasks.iter().find(|t| t.frontmatter.id == id)) {
+        fields.push(vec!["Project".to_string(), project.frontmatter.title.clone()]);
+    }
+    if let Some(person) = &fm.delegated_to {
+        fields.push(vec!["Delegated to".to_string(), person.clone()]);
+    }
+    fields.push(vec!["Created".to_string(), fm.created_at.format("%Y-%m-%d").to_string()]);
+    if let Some(completed) = fm.completed_at {
+        fields.push(vec!["Completed".to_string(), completed.format("%Y-%m-%d").to_string()]);
+    }
+
+    let mut blocks = vec![Block::Table(vec!["Field", "Value"], fields)];
+    if !task.body.trim().is_empty() {
+        blocks.push(Block::Body(task.body.trim().to_string()));
+    }
+    Document { title: fm.title.clone(), blocks }
+}
+
+/// A project for stakeholders: progress, its task table, an ASCII Gantt chart
+/// and the project notes
+pub fn project_document(project: &TaskItem, tasks: &[TaskItem], today: NaiveDate) -> Document {
+    let id = project.frontmatter.id;
+    let children: Vec<&TaskItem> = tasks.iter().filter(|t| t.frontmatter.parent_goal_id == Some(id)).collect();
+    let done = children.iter().filter(|t| t.percent_done() == 100).count();
+    let progress = models::project_progress(tasks, id);
+    let spans: Vec<_> = children.iter().map(|t| t.schedule(today)).collect();
+    let (start, end) = project.schedule(today);
+
+    let filled = progress as usize / 5;
+    let summary = format!(
+        "Progress: {}{} {}% ({} of {} tasks done) · {} → {} · {}",
+        "█".repeat(filled),
+        "░".repeat(20 - filled),
+        progress,
+        done,
+        children.len(),
+        start,
+        end,
+        project.frontmatter.status.as_str(),
+    );
+
+    let rows = children.iter().zip(&spans)
+        .map(|(t, (start, end))| {
+            vec![
+                t.code(),
+                t.frontmatter.title.clone(),
+                t.frontmatter.status.as_str().to_string(),
+                t.frontmatter.priority.as_str().to_string(),
+                start.to_string(),
+                end.to_string(),
+                format!("{}%", t.percent_done()),
+            ]
+        })
+        .collect();
+
+    let mut blocks = vec![
+        Block::Paragraph(summary),
+        Block::Heading("Tasks".to_string()),
+        Block::Table(vec!["Code", "Task", "Status", "Priority", "Start", "End", "Done"], rows),
+    ];
+    if !children.is_empty() {
+        blocks.push(Block::Heading("Timeline".to_string()));
+        blocks.push(Block::Pre(ascii_gantt(&children, today)));
+    }
+    if !project.body.trim().is_empty() {
+        blocks.push(Block::Heading("Notes".to_string()));
+        blocks.push(Block::Body(project.body.trim().to_string()));
+    }
+    blocks.push(Block::Paragraph(format!("Exported {} from tasktui", today)));
+    Document { title: project.frontmatter.title.clone(), blocks }
+}
+
+/// A project's status for stakeholders: a summary line, its tasks with owner,
+/// status, due date and progress, its milestones (tasks tagged `milestone`) and
+/// a burndown sparkline of the open tasks
+pub fn project_report(project: &TaskItem, tasks: &[TaskItem], today: NaiveDate) -> Document {
+    let id = project.frontmatter.id;
+    let children: Vec<&TaskItem> = tasks.iter().filter(|t| t.frontmatter.parent_goal_id == Some(id)).collect();
+    let done = children.iter().filter(|t| t.frontmatter.status.is_closed()).count();
+    let overdue = children.iter().filter(|t| is_late(t, today)).count();
+    let (start, end) = project.schedule(today);
+
+    let mut summary = format!(
+        "Status: {} · {} of {} tasks done ({}%) · {} → {}",
+        project.frontmatter.status.as_str(),
+        done,
+        children.len(),
+        models::project_progress(tasks, id),
+        start,
+        end,
+    );
+    if overdue > 0 {
+        summary.push_str(&format!(" · {} overdue", overdue));
+    }
+
+    let mut blocks = vec![
+        Block::Paragraph(summary),
+        Block::Heading("Tasks".to_string()),
+        Block::Table(REPORT_COLUMNS.to_vec(), report_rows(&children)),
+    ];
+
+    let milestones: Vec<&TaskItem> = children.iter()
+        .copied()
+        .filter(|t| t.frontmatter.tags.iter().any(|tag| tag == MILESTONE_TAG))
+        .collect();
+    if !milestones.is_empty() {
+        let reached = milestones.iter().filter(|t| t.frontmatter.status.is_closed()).count();
+        let mut line = format!("{} of {} reached", reached, milestones.len());
+        let next = milestones.iter()
+            .filter(|t| !t.frontmatter.status.is_closed())
+            .min_by_key(|t| t.frontmatter.due_date.map(|d| d.local_date()).unwrap_or(NaiveDate::MAX));
+        if let Some(next) = next {
+            line.push_str(&format!("; next: {}", next.frontmatter.title));
+            if let Some(due) = next.frontmatter.due_date {
+                line.push_str(&format!(", due {}", due.local_date()));
+            }
+        }
+        let rows = milestones.iter()
+            .map(|t| {
+                let state = if t.frontmatter.status.is_closed() {
+                    match t.frontmatter.completed_at {
+                        Some(at) => format!("reached {}", at.date_naive()),
+                        None => "reached".to_string(),
+                    }
+                } else if is_late(t, today) {
+                    "overdue".to_string()
+                } else {
+                    t.frontmatter.status.as_str().to_string()
+                };
+                let due = t.frontmatter.due_date.map(|d| d.local_date().to_string()).unwrap_or_default();
+                vec![t.frontmatter.title.clone(), due, state]
+            })
+            .collect();
+        blocks.push(Block::Heading("Milestones".to_string()));
+        blocks.push(Block::Paragraph(line));
+        blocks.push(Block::Table(vec!["Milestone", "Due", "Status"], rows));
+    }
+
+    let flow = ProjectFlow::new(tasks, project, today);
+    if let (Some(first), Some(last)) = (flow.remaining.first(), flow.remaining.last()) {
+        blocks.push(Block::Heading("Burndown".to_string()));
+        blocks.push(Block::Paragraph(format!(
+            "{} {} → {} open tasks, {} to {}",
+            sparkline(&flow.remaining),
+            first,
+            last,
+            flow.days[0],
+            today,
+        )));
+    }
+    blocks.push(Block::Paragraph(format!("Reported {} from tasktui", today)));
+    Document { title: format!("{} status report", project.frontmatter.title), blocks }
+}
+
+/// The status report's task table as CSV
+pub fn project_report_csv(project: &TaskItem, tasks: &[TaskItem]) -> String {
+    let id = project.frontmatter.id;
+    let children: Vec<&TaskItem> = tasks.iter().filter(|t| t.frontmatter.parent_goal_id == Some(id)).collect();
+    let mut csv = format!("{}\n", REPORT_COLUMNS.join(","));
+    for row in report_rows(&children) {
+        let cells: Vec<String> = row.iter().map(|cell| csv_field(cell)).collect();
+        csv.push_str(&format!("{}\n", cells.join(",")));
+    }
+    csv
+}
+
+/// Title, owner (whoever it's delegated to, else "me"), status, due date and
+/// percent done of each task
+fn report_rows(tasks: &[&TaskItem]) -> Vec<Vec<String>> {
+    tasks.iter()
+        .map(|t| {
+            vec![
+                t.frontmatter.title.clone(),
+                t.frontmatter.delegated_to.clone().unwrap_or_else(|| "me".to_string()),
+                t.frontmatter.status.as_str().to_string(),
+                t.frontmatter.due_date.map(|d| d.local_date().to_string()).unwrap_or_default(),
+                format!("{}%", t.percent_done()),
+            ]
+        })
+        .collect()
+}
+
+/// Open and due before `today`
+fn is_late(task: &TaskItem, today: NaiveDate) -> bool {
+    !task.frontmatter.status.is_closed() && task.frontmatter.due_date.is_some_and(|due| due.local_date() < today)
+}
+
+/// One bar per day, scaled to the highest value; long series are sampled down
+/// to `SPARKLINE_WIDTH` days, keeping the first and last
+fn sparkline(values: &[usize]) -> String {
+    let max = values.iter().copied().max().unwrap_or(0).max(1);
+    let points = values.len().min(SPARKLINE_WIDTH);
+    (0..points)
+        .map(|i| {
+            let value = if points == 1 { values[0] } else { values[i * (values.len() - 1) / (points - 1)] };
+            SPARKS[value * (SPARKS.len() - 1) / max]
+        })
+        .collect()
+}
+
+/// A CSV cell, quoted when it holds a comma, quote or line break
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// One bar per task over the span of all tasks: `█` done, `░` remaining, `│` today
+fn ascii_gantt(tasks: &[&TaskItem], today: NaiveDate) -> String {
+    let spans: Vec<_> = tasks.iter().map(|t| t.schedule(today)).collect();
+    let first = spans.iter().map(|(start, _)| *start).min().unwrap_or(today);
+    let last = spans.iter().map(|(_, end)| *end).max().unwrap_or(today).max(first);
+    let days = (last - first).num_days() as usize + 1;
+    let column = |day: NaiveDate| ((day - first).num_days().max(0) as usize * GANTT_WIDTH / days).min(GANTT_WIDTH);
+    let today_col = (first..=last).contains(&today).then(|| column(today));
+
+    let mut out = format!(
+        "{:label$} {}{:>pad$}\n",
+        "",
+        first,
+        last.to_string(),
+        label = GANTT_LABEL_WIDTH,
+        pad = GANTT_WIDTH.saturating_sub(10),
+    );
+    for (task, (start, end)) in tasks.iter().zip(&spans) {
+        let from = column(*start);
+        let to = column(*end + chrono::Duration::days(1)).max(from + 1).min(GANTT_WIDTH);
+        let done_to = from + (to - from) * task.percent_done() as usize / 100;
+        let bar: String = (0..GANTT_WIDTH)
+            .map(|col| match col {
+                _ if (from..done_to).contains(&col) => '█',
+                _ if (from..to).contains(&col) => '░',
+                _ if Some(col) == today_col => '│',
+                _ => ' ',
+            })
+            .collect();
+        out.push_str(&format!("{} {}\n", label(&task.frontmatter.title), bar.trim_end()));
+    }
+    out
+}
+
+/// A title cut or padded to the Gantt label width
+fn label(title: &str) -> String {
+    let mut label: String = title.chars().take(GANTT_LABEL_WIDTH).collect();
+    if title.chars().count() > GANTT_LABEL_WIDTH {
+        label.pop();
+        label.push('…');
+    }
+    format!("{:width$}", label, width = GANTT_LABEL_WIDTH)
+}
+
+/// A file name for the document: its title lowercased with dashes
+pub fn slug(title: &str) -> String {
+    let slug = title.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() { "export".to_string() } else { slug }
+}
+
+impl Document {
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Md => self.to_markdown(),
+            Format::Html => self.to_html(),
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!("# {}\n", self.title);
+        for block in &self.blocks {
+            md.push('\n');
+            match block {
+                Block::Heading(text) => md.push_str(&format!("## {}\n", text)),
+                Block::Paragraph(text) | Block::Body(text) => md.push_str(&format!("{}\n", text)),
+                Block::Table(header, rows) => {
+                    md.push_str(&format!("| {} |\n", header.join(" | ")));
+                    md.push_str(&format!("|{}\n", "---|".repeat(header.len())));
+                    for row in rows {
+                        let cells: Vec<_> = row.iter().map(|c| c.replace('|', "\\|")).collect();
+                        md.push_str(&format!("| {} |\n", cells.join(" | ")));
+                    }
+                }
+                Block::Pre(text) => md.push_str(&format!("```\n{}```\n", text)),
+            }
+        }
+        md
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut body = format!("<h1>{}</h1>\n", escape(&self.title));
+        for block in &self.blocks {
+            match block {
+                Block::Heading(text) => body.push_str(&format!("<h2>{}</h2>\n", escape(text))),
+                Block::Paragraph(text) => body.push_str(&format!("<p>{}</p>\n", escape(text))),
+                Block::Table(header, rows) => {
+                    body.push_str("<table>\n<tr>");
+                    for cell in header {
+                        body.push_str(&format!("<th>{}</th>", escape(cell)));
+                    }
+                    body.push_str("</tr>\n");
+                    for row in rows {
+                        body.push_str("<tr>");
+                        for cell in row {
+                            body.push_str(&format!("<td>{}</td>", escape(cell)));
+                        }
+                        body.push_str("</tr>\n");
+                    }
+                    body.push_str("</table>\n");
+                }
+                Block::Pre(text) => body.push_str(&format!("<pre>{}</pre>\n", escape(text))),
+                Block::Body(text) => body.push_str(&markdown_to_html(text)),
+            }
+        }
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+            escape(&self.title),
+            STYLE,
+            body
+        )
+    }
+}
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem;color:#222}\
+table{border-collapse:collapse;margin:1rem 0}th,td{border:1px solid #ccc;padding:.3rem .6rem;text-align:left}\
+th{background:#f4f4f4}pre{background:#f8f8f8;padding:1rem;overflow-x:auto;line-height:1.2}";
+
+/// The markdown subset task bodies use: headings, bullet lists and paragraphs
+fn markdown_to_html(text: &str) -> String {
+    let mut html = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut in_list = false;
+    let flush = |html: &mut String, paragraph: &mut Vec<&str>| {
+        if !paragraph.is_empty() {
+            html.push_str(&format!("<p>{}</p>\n", escape(&paragraph.join(" "))));
+            paragraph.clear();
+        }
+    };
+
+    for line in text.lines() {
+        let line = line.trim_end();
+        let item = line.strip_prefix("- ").or_else(|| line.strip_prefix("* "));
+        if item.is_none() && in_list {
+            html.push_str("</ul>\n");
+            in_list = false;
+        }
+        if let Some(item) = item {
+            flush(&mut html, &mut paragraph);
+            if !in_list {
+                html.push_str("<ul>\n");
+                in_list = true;
+            }
+            html.push_str(&format!("<li>{}</li>\n", escape(item)));
+        } else if let Some(heading) = line.strip_prefix('#') {
+            flush(&mut html, &mut paragraph);
+            let level = 2 + heading.chars().take_while(|c| *c == '#').count().min(3);
+            html.push_str(&format!("<h{0}>{1}</h{0}>\n", level, escape(heading.trim_start_matches('#').trim())));
+        } else if line.is_empty() {
+            flush(&mut html, &mut paragraph);
+        } else {
+            paragraph.push(line);
+        }
+    }
+    flush(&mut html, &mut paragraph);
+    if in_list {
+        html.push_str("</ul>\n");
+    }
+    html
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ItemType, Status};
+
+    #[test]
+    fn test_project_document() {
+        let today = NaiveDate::from_ymd_opt(2025, 11, 24).unwrap();
+        let mut project = TaskItem::new_project("Website <relaunch>".to_string());
+        project.body = "## Scope\n\n- New design\n- Blog".to_string();
+        let mut tasks = vec![project.clone()];
+        for (title, start, done) in [("Wireframes", 24, true), ("Build pages", 28, false)] {
+            let mut task = TaskItem::new(title.to_string(), ItemType::Task);
+            task.frontmatter.parent_goal_id = Some(project.frontmatter.id);
+            task.frontmatter.start_date = NaiveDate::from_ymd_opt(2025, 11, start);
+            if done {
+                task.set_status(Status::Done);
+            }
+            tasks.push(task);
+        }
+
+        let doc = project_document(&project, &tasks, today);
+        let md = doc.to_markdown();
+        assert!(md.starts_with("# Website <relaunch>\n"));
+        assert!(md.contains("50% (1 of 2 tasks done)"));
+        assert!(md.contains("| Wireframes | done | p2 | 2025-11-24 | 2025-12-01 | 100% |"));
+        let gantt: Vec<_> = md.lines().filter(|l| l.starts_with("Wireframes") || l.starts_with("Build pages")).collect();
+        assert!(gantt[0].contains('█') && !gantt[0].contains('░'));
+        assert!(gantt[1].contains('░') && !gantt[1].contains('█'));
+
+        let html = doc.to_html();
+        assert!(html.contains("<h1>Website &lt;relaunch&gt;</h1>"));
+        assert!(html.contains("<ul>\n<li>New design</li>\n<li>Blog</li>\n</ul>"));
+        assert_eq!(slug(&project.frontmatter.title), "website-relaunch");
+    }
+
+    #[test]
+    fn test_project_report() {
+        let today = NaiveDate::from_ymd_opt(2025, 11, 24).unwrap();
+        let mut project = TaskItem::new_project("Launch".to_string());
+        project.frontmatter.start_date = NaiveDate::from_ymd_opt(2025, 11, 20);
+        let mut tasks = vec![project.clone()];
+        for (title, due, milestone, done) in [
+            ("Write copy, v2", "2025-11-21", false, true),
+            ("Beta ships", "2025-11-22", true, false),
+            ("GA", "2025-12-01", true, false),
+        ] {
+            let mut task = TaskItem::new(title.to_string(), ItemType::Task);
+            task.frontmatter.parent_goal_id = Some(project.frontmatter.id);
+            task.frontmatter.due_date = models::Due::parse(due);
+            task.frontmatter.created_at = "2025-11-20T09:00:00Z".parse().unwrap();
+            if milestone {
+                task.frontmatter.tags.push(MILESTONE_TAG.to_string());
+            }
+            if done {
+                task.set_status(Status::Done);
+                task.frontmatter.completed_at = Some("2025-11-22T09:00:00Z".parse().unwrap());
+            }
+            tasks.push(task);
+        }
+        tasks[2].frontmatter.delegated_to = Some("Sam".to_string());
+        tasks[2].frontmatter.progress = Some(60);
+
+        let md = project_report(&project, &tasks, today).to_markdown();
+        assert!(md.starts_with("# Launch status report\n"));
+        assert!(md.contains("1 of 3 tasks done (33%) · 2025-11-20 → 2025-11-27 · 1 overdue"));
+        assert!(md.contains("| Beta ships | Sam | active | 2025-11-22 | 60% |"));
+        assert!(md.contains("0 of 2 reached; next: Beta ships, due 2025-11-22"));
+        assert!(md.contains("| Beta ships | 2025-11-22 | overdue |"));
+        assert!(md.contains("| GA | 2025-12-01 | active |"));
+        let burndown = md.lines().find(|l| l.contains("open tasks")).unwrap();
+        // Done on the 22nd, so three are open for two days and then two
+        assert!(burndown.starts_with("██▅▅▅ 3 → 2 open tasks, 2025-11-20 to 2025-11-24"));
+
+        assert_eq!(sparkline(&[4, 2, 0]), "█▄▁");
+        assert_eq!(sparkline(&(0..100).rev().collect::<Vec<_>>()).chars().count(), SPARKLINE_WIDTH);
+        let csv = project_report_csv(&project, &tasks);
+        assert_eq!(csv.lines().next(), Some("Task,Owner,Status,Due,Progress"));
+        assert!(csv.contains("\"Write copy, v2\",me,done,2025-11-21,100%\n"));
+    }
+}
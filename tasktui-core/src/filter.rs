@@ -0,0 +1,368 @@
+use crate::models::{Priority, Status, TaskItem};
+use chrono::{Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// How multiple tags combine
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TagMode {
+    #[default]
+    All,
+    Any,
+}
+
+/// Compound filter expression shared by the TUI and saved filters.
+/// Empty fields don't constrain; non-empty fields must all match.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FilterExpr {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub tag_mode: TagMode,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub statuses: Vec<Status>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub priorities: Vec<Priority>,
+    /// Inclusive lower due bound: YYYY-MM-DD, "today", or "today+N"/"today-N"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_from: Option<String>,
+    /// Inclusive upper due bound, same forms as `due_from`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_to: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<Uuid>,
+    /// Inclusive completion-date bounds, same forms as `due_from`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_from: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_to: Option<String>,
+    /// Custom field values: a task matches when each named field holds one of the values
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, Vec<String>>,
+}
+
+/// A named filter persisted in config
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedFilter {
+    pub name: String,
+    pub filter: FilterExpr,
+}
+
+impl FilterExpr {
+    /// Filter on a single tag (workstream shortcuts)
+    pub fn tag(tag: &str) -> Self {
+        Self {
+            tags: vec![tag.to_string()],
+            ..Default::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// True if this filter is exactly a single-tag filter for `tag`
+    pub fn is_single_tag(&self, tag: &str) -> bool {
+        *self == Self::tag(tag)
+    }
+
+    pub fn matches(&self, item: &TaskItem, today: NaiveDate) -> bool {
+        if !self.tags.is_empty() {
+            let matched = match self.tag_mode {
+                TagMode::All => self.tags.iter().all(|t| item.has_tag(t)),
+                TagMode::Any => self.tags.iter().any(|t| item.has_tag(t)),
+            };
+            if !matched {
+                return false;
+            }
+        }
+
+        if !self.statuses.is_empty() && !self.statuses.contains(&item.frontmatter.status) {
+            return false;
+        }
+
+        if !self.priorities.is_empty() && !self.priorities.contains(&item.frontmatter.priority) {
+            return false;
+        }
+
+        if (self.due_from.is_some() || self.due_to.is_some())
+            && !in_range(item.frontmatter.due_date.map(|d| d.local_date()), &self.due_from, &self.due_to, today)
+        {
+            return false;
+        }
+
+        if self.completed_from.is_some() || self.completed_to.is_some() {
+            let completed = item.frontmatter.completed_at.map(|c| c.with_timezone(&Local).date_naive());
+            if !in_range(completed, &self.completed_from, &self.completed_to, today) {
+                return false;
+            }
+        }
+
+        if let Some(project_id) = &self.project_id {
+            if item.frontmatter.parent_goal_id.as_ref() != Some(project_id) {
+                return false;
+            }
+        }
+
+        for (name, values) in &self.fields {
+            let Some(value) = item.frontmatter.custom.get(name) else {
+                return false;
+            };
+            if !values.iter().any(|v| v.eq_ignore_ascii_case(value)) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Parse a filter query such as `#work|#home status:active,next priority:high due:today..today+7 @launch`.
+    /// `name=value[,value]` matches a custom field, e.g. `severity=high,critical`.
+    /// `project_lookup` resolves a project name to its id.
+    pub fn parse(query: &str, project_lookup: impl Fn(&str) -> Option<Uuid>) -> Result<Self, String> {
+        let mut expr = Self::default();
+
+        for token in query.split_whitespace() {
+            if let Some(project) = token.strip_prefix('@').or_else(|| token.strip_prefix("project:")) {
+                expr.project_id = Some(
+                    project_lookup(project).ok_or_else(|| format!("Unknown project: {}", project))?,
+                );
+            } else if token.starts_with('#') || token.starts_with("tag:") {
+                let tags = token.strip_prefix("tag:").unwrap_or(token);
+                if tags.contains('|') {
+                    expr.tag_mode = TagMode::Any;
+                }
+                for tag in tags.split('|') {
+                    let tag = tag.trim_start_matches('#');
+                    if !tag.is_empty() {
+                        expr.tags.push(tag.to_string());
+                    }
+                }
+            } else if let Some(statuses) = token.strip_prefix("status:") {
+                for status in statuses.split(',') {
+                    expr.statuses.push(parse_status(status)?);
+                }
+            } else if let Some(priorities) = token.strip_prefix("priority:") {
+                for priority in priorities.split(',') {
+                    expr.priorities.push(parse_priority(priority)?);
+                }
+            } else if let Some(range) = token.strip_prefix("due:") {
+                (expr.due_from, expr.due_to) = parse_range(range)?;
+            } else if let Some(range) = token.strip_prefix("completed:") {
+                (expr.completed_from, expr.completed_to) = parse_range(range)?;
+            } else if let Some((name, values)) = token.split_once('=').filter(|(name, _)| !name.is_empty()) {
+                let values: Vec<String> = values.split(',').filter(|v| !v.is_empty()).map(str::to_string).collect();
+                if values.is_empty() {
+                    return Err(format!("No value for field {}", name));
+                }
+                expr.fields.entry(name.to_string()).or_default().extend(values);
+            } else {
+                return Err(format!("Unknown filter term: {}", token));
+            }
+        }
+
+        Ok(expr)
+    }
+
+    /// Render back to query syntax (inverse of `parse`)
+    pub fn to_query(&self, project_name: impl Fn(Uuid) -> Option<String>) -> String {
+        let mut parts = Vec::new();
+
+        if !self.tags.is_empty() {
+            let tags: Vec<_> = self.tags.iter().map(|t| format!("#{}", t)).collect();
+            match self.tag_mode {
+                TagMode::All => parts.extend(tags),
+                TagMode::Any => parts.push(tags.join("|")),
+            }
+        }
+        if !self.statuses.is_empty() {
+            let statuses: Vec<_> = self.statuses.iter().map(|s| s.as_str()).collect();
+            parts.push(format!("status:{}", statuses.join(",")));
+        }
+        if !self.priorities.is_empty() {
+            let priorities: Vec<_> = self.priorities.iter().map(|p| p.as_str()).collect();
+            parts.push(format!("priority:{}", priorities.join(",")));
+        }
+        if let Some(range) = range_query(&self.due_from, &self.due_to) {
+            parts.push(format!("due:{}", range));
+        }
+        if let Some(range) = range_query(&self.completed_from, &self.completed_to) {
+            parts.push(format!("completed:{}", range));
+        }
+        if let Some(project_id) = self.project_id {
+            let name = project_name(project_id).unwrap_or_else(|| project_id.to_string());
+            parts.push(format!("project:{}", name.replace(' ', "-")));
+        }
+        for (name, values) in &self.fields {
+            parts.push(format!("{}={}", name, values.join(",")));
+        }
+
+        parts.join(" ")
+    }
+}
+
+/// Resolve a date bound: YYYY-MM-DD, "today", or "today+N"/"today-N" (days)
+pub fn resolve_date(value: &str, today: NaiveDate) -> Option<NaiveDate> {
+    if let Some(offset) = value.strip_prefix("today") {
+        if offset.is_empty() {
+            return Some(today);
+        }
+        let days: i64 = offset.strip_prefix('+').unwrap_or(offset).parse().ok()?;
+        return today.checked_add_signed(Duration::days(days));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
+/// Check an optional date against optional bounds; a missing date never matches
+fn in_range(date: Option<NaiveDate>, from: &Option<String>, to: &Option<String>, today: NaiveDate) -> bool {
+    let Some(date) = date else {
+        return false;
+    };
+    if let Some(from) = from.as_deref().and_then(|s| resolve_date(s, today)) {
+        if date < from {
+            return false;
+        }
+    }
+    if let Some(to) = to.as_deref().and_then(|s| resolve_date(s, today)) {
+        if date > to {
+            return false;
+        }
+    }
+    true
+}
+
+/// Parse `from..to`, `from..`, `..to` or a single date into validated bounds
+fn parse_range(range: &str) -> Result<(Option<String>, Option<String>), String> {
+    let (from, to) = range.split_once("..").unwrap_or((range, range));
+    // Validate against an arbitrary mid-range day so negative offsets can't underflow
+    let reference = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap_or_default();
+    for bound in [from, to] {
+        if !bound.is_empty() && resolve_date(bound, reference).is_none() {
+            return Err(format!("Invalid date: {}", bound));
+        }
+    }
+    Ok((
+        (!from.is_empty()).then(|| from.to_string()),
+        (!to.is_empty()).then(|| to.to_string()),
+    ))
+}
+
+/// Render bounds back to range syntax (inverse of `parse_range`)
+fn range_query(from: &Option<String>, to: &Option<String>) -> Option<String> {
+    if from.is_none() && to.is_none() {
+        return None;
+    }
+    let from = from.as_deref().unwrap_or("");
+    let to = to.as_deref().unwrap_or("");
+    if from == to {
+        Some(from.to_string())
+    } else {
+        Some(format!("{}..{}", from, to))
+    }
+}
+
+fn parse_status(value: &str) -> Result<Status, String> {
+    Status::parse(value).ok_or_else(|| format!("Invalid status: {}", value))
+}
+
+fn parse_priority(value: &str) -> Result<Priority, String> {
+    Priority::parse(value).ok_or_else(|| format!("Invalid priority: {}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Due, ItemType};
+    use chrono::{TimeZone, Utc};
+
+    fn task(tags: &[&str], status: Status, due: Option<&str>) -> TaskItem {
+        let mut task = TaskItem::new("Task".to_string(), ItemType::Task);
+        task.frontmatter.tags = tags.iter().map(|t| t.to_string()).collect();
+        task.frontmatter.status = status;
+        task.frontmatter.due_date = due.and_then(Due::parse);
+        task
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 11, 24).unwrap()
+    }
+
+    #[test]
+    fn test_tag_modes() {
+        let all = FilterExpr::parse("#work #urgent", |_| None).unwrap();
+        let any = FilterExpr::parse("#work|#home", |_| None).unwrap();
+
+        let work = task(&["work"], Status::Active, None);
+        let urgent_work = task(&["work", "urgent"], Status::Active, None);
+        let home = task(&["home"], Status::Active, None);
+
+        assert!(!all.matches(&work, today()));
+        assert!(all.matches(&urgent_work, today()));
+        assert!(any.matches(&work, today()));
+        assert!(any.matches(&home, today()));
+    }
+
+    #[test]
+    fn test_status_and_due_range() {
+        let filter = FilterExpr::parse("status:active,next due:today..today+7", |_| None).unwrap();
+
+        assert!(filter.matches(&task(&[], Status::Next, Some("2025-11-30")), today()));
+        assert!(!filter.matches(&task(&[], Status::Next, Some("2025-12-05")), today()));
+        assert!(!filter.matches(&task(&[], Status::Done, Some("2025-11-25")), today()));
+        assert!(!filter.matches(&task(&[], Status::Active, None), today()));
+    }
+
+    #[test]
+    fn test_completed_range() {
+        let filter = FilterExpr::parse("completed:today-7..", |_| None).unwrap();
+
+        let mut recent = task(&[], Status::Active, None);
+        recent.set_status(Status::Done);
+        recent.frontmatter.completed_at = Some(Utc.with_ymd_and_hms(2025, 11, 20, 9, 0, 0).unwrap());
+        let mut old = recent.clone();
+        old.frontmatter.completed_at = Some(Utc.with_ymd_and_hms(2025, 10, 1, 9, 0, 0).unwrap());
+
+        assert!(filter.matches(&recent, today()));
+        assert!(!filter.matches(&old, today()));
+        assert!(!filter.matches(&task(&[], Status::Done, None), today()));
+        assert_eq!(filter.to_query(|_| None), "completed:today-7..");
+    }
+
+    #[test]
+    fn test_query_round_trip() {
+        let query = "#work|#home status:active priority:p0,p1 due:..2025-12-01";
+        let filter = FilterExpr::parse(query, |_| None).unwrap();
+        assert_eq!(filter.to_query(|_| None), query);
+        assert_eq!(FilterExpr::parse(&filter.to_query(|_| None), |_| None).unwrap(), filter);
+
+        // Queries saved before priorities were numbered still parse
+        let old = FilterExpr::parse("priority:high,low", |_| None).unwrap();
+        assert_eq!(old.priorities, [Priority::P1, Priority::P3]);
+    }
+
+    #[test]
+    fn test_custom_fields() {
+        let filter = FilterExpr::parse("severity=high,critical client=acme", |_| None).unwrap();
+        assert_eq!(filter.to_query(|_| None), "client=acme severity=high,critical");
+
+        let mut bug = task(&[], Status::Active, None);
+        bug.frontmatter.custom.insert("client".to_string(), "Acme".to_string());
+        bug.frontmatter.custom.insert("severity".to_string(), "critical".to_string());
+        assert!(filter.matches(&bug, today()));
+
+        bug.frontmatter.custom.insert("severity".to_string(), "low".to_string());
+        assert!(!filter.matches(&bug, today()));
+        bug.frontmatter.custom.remove("severity");
+        assert!(!filter.matches(&bug, today()));
+        assert!(FilterExpr::parse("client=", |_| None).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(FilterExpr::parse("status:someday", |_| None).is_err());
+        assert!(FilterExpr::parse("due:tomorrowish", |_| None).is_err());
+        assert!(FilterExpr::parse("@nope", |_| None).is_err());
+    }
+}
@@ -0,0 +1,159 @@
+use crate::activity;
+use crate::models::{Status, TaskItem};
+use chrono::{Duration, NaiveDate};
+use serde_json::{json, Value};
+
+/// Longest history charted, in days
+const MAX_DAYS: i64 = 365;
+
+/// Statuses stacked in the cumulative flow diagram, bottom band first.
+/// Archived tasks count as done.
+pub const FLOW_STATUSES: [Status; 5] = [Status::Done, Status::Waiting, Status::Active, Status::Next, Status::Inbox];
+
+/// Daily series for a project's burndown and cumulative flow charts, replayed
+/// from the status changes in each task's `## Log`
+pub struct ProjectFlow {
+    pub days: Vec<NaiveDate>,
+    /// Open (not done or archived) tasks at the end of each day
+    pub remaining: Vec<usize>,
+    /// Straight line from the first day's open tasks down to zero at the project end
+    pub ideal: Vec<f64>,
+    /// Task counts per day for each of `FLOW_STATUSES`
+    pub by_status: Vec<Vec<usize>>,
+}
+
+impl ProjectFlow {
+    /// Series from the project start (or its first task, if earlier) up to today
+    pub fn new(tasks: &[TaskItem], project: &TaskItem, today: NaiveDate) -> Self {
+        let id = project.frontmatter.id;
+        let children: Vec<(&TaskItem, Vec<activity::StatusChange>)> = tasks.iter()
+            .filter(|t| t.frontmatter.parent_goal_id == Some(id))
+            .map(|t| (t, activity::status_changes(&t.body)))
+            .collect();
+
+        let (start, end) = project.schedule(today);
+        let first = children.iter()
+            .map(|(t, _)| t.frontmatter.created_at.date_naive())
+            .chain([start])
+            .min()
+            .unwrap_or(today)
+            .clamp(today - Duration::days(MAX_DAYS), today);
+        let days: Vec<NaiveDate> = first.iter_days().take_while(|d| *d <= today).collect();
+
+        let mut by_status = vec![Vec::with_capacity(days.len()); FLOW_STATUSES.len()];
+        for day in &days {
+            let mut counts = [0; FLOW_STATUSES.len()];
+            for (task, changes) in &children {
+                let status = match status_on(task, changes, *day) {
+                    Some(Status::Archived) => Status::Done,
+                    Some(status) => status,
+                    None => continue,
+                };
+                if let Some(idx) = FLOW_STATUSES.iter().position(|s| *s == status) {
+                    counts[idx] += 1;
+                }
+            }
+            for (series, count) in by_status.iter_mut().zip(counts) {
+                series.push(count);
+            }
+        }
+
+        let total: Vec<usize> = (0..days.len()).map(|i| by_status.iter().map(|s| s[i]).sum()).collect();
+        let remaining: Vec<usize> = total.iter().zip(&by_status[0]).map(|(total, done)| total - done).collect();
+        let planned = (end - first).num_days().max(1) as f64;
+        let ideal = days.iter()
+            .map(|day| {
+                let left = 1.0 - (*day - first).num_days() as f64 / planned;
+                remaining.first().copied().unwrap_or(0) as f64 * left.max(0.0)
+            })
+            .collect();
+
+        Self { days, remaining, ideal, by_status }
+    }
+
+    /// Cumulative counts per status: each band sits on top of the ones before it
+    pub fn stacked(&self) -> Vec<Vec<usize>> {
+        let mut stacked: Vec<Vec<usize>> = Vec::with_capacity(self.by_status.len());
+        for series in &self.by_status {
+            let band = match stacked.last() {
+                Some(below) => series.iter().zip(below).map(|(count, below)| count + below).collect(),
+                None => series.clone(),
+            };
+            stacked.push(band);
+        }
+        stacked
+    }
+
+    pub fn to_json(&self) -> Value {
+        let flow: serde_json::Map<String, Value> = FLOW_STATUSES.iter()
+            .zip(&self.by_status)
+            .map(|(status, series)| (status.as_str().to_string(), json!(series)))
+            .collect();
+        json!({
+            "days": self.days,
+            "burndown": { "remaining": self.remaining, "ideal": self.ideal },
+            "cumulative_flow": flow,
+        })
+    }
+}
+
+/// A task's status at the end of `day`, or `None` if it didn't exist yet.
+/// Before its first logged change a task had that change's old status; tasks
+/// finished without a log count as active until `completed_at`.
+fn status_on(task: &TaskItem, changes: &[activity::StatusChange], day: NaiveDate) -> Option<Status> {
+    let fm = &task.frontmatter;
+    if day < fm.created_at.date_naive() {
+        return None;
+    }
+    if let Some(change) = changes.iter().rev().find(|c| c.on <= day) {
+        return Some(change.to.clone());
+    }
+    if let Some(change) = changes.first() {
+        return Some(change.from.clone());
+    }
+    match fm.completed_at {
+        Some(completed) if day < completed.date_naive() => Some(Status::Active),
+        _ => Some(fm.status.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ItemType;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_project_flow() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 11, d).unwrap();
+        let created = Utc.with_ymd_and_hms(2025, 11, 20, 9, 0, 0).unwrap();
+        let mut project = TaskItem::new_project("Launch".to_string());
+        project.frontmatter.start_date = Some(day(20));
+        project.frontmatter.end_date = Some(day(24));
+        project.frontmatter.created_at = created;
+
+        let mut tasks = vec![project.clone()];
+        for body in [
+            "## Log\n\n- 2025-11-21 10:00 status: next → active\n- 2025-11-22 10:00 status: active → done",
+            "## Log\n\n- 2025-11-23 10:00 status: inbox → next",
+        ] {
+            let mut task = TaskItem::new("Task".to_string(), ItemType::Task);
+            task.frontmatter.parent_goal_id = Some(project.frontmatter.id);
+            task.frontmatter.created_at = created;
+            task.body = body.to_string();
+            tasks.push(task);
+        }
+
+        let flow = ProjectFlow::new(&tasks, &project, day(24));
+        assert_eq!(flow.days, (20..=24).map(day).collect::<Vec<_>>());
+        assert_eq!(flow.remaining, [2, 2, 1, 1, 1]);
+        assert_eq!(flow.ideal, [2.0, 1.5, 1.0, 0.5, 0.0]);
+        // Done, Waiting, Active, Next, Inbox
+        assert_eq!(flow.by_status[0], [0, 0, 1, 1, 1]);
+        assert_eq!(flow.by_status[2], [0, 1, 0, 0, 0]);
+        assert_eq!(flow.by_status[3], [1, 0, 0, 1, 1]);
+        assert_eq!(flow.by_status[4], [1, 1, 1, 0, 0]);
+        assert_eq!(flow.stacked()[4], [2, 2, 2, 2, 2]);
+        assert_eq!(flow.to_json()["cumulative_flow"]["done"], json!([0, 0, 1, 1, 1]));
+    }
+}
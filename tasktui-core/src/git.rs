@@ -0,0 +1,653 @@
+use crate::config::{AppConfig, CredentialMode, GitConfig};
+use crate::ignore::IgnoreRules;
+use crate::llm::MonthlyUsage;
+use anyhow::{Context, Result};
+use std::fmt;
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+/// Stands in for git's interactive credential prompt when `credentials: token`;
+/// the secret reaches it through the environment, never the command line
+const TOKEN_HELPER: &str =
+    "!f() { test \"$1\" = get && printf 'username=%s\\npassword=%s\\n' \"$TASKTUI_GIT_USERNAME\" \"$TASKTUI_GIT_TOKEN\"; }; f";
+
+/// How far the local branch and its upstream have moved apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AheadBehind {
+    /// Local commits not pushed yet
+    pub ahead: usize,
+    /// Fetched commits not pulled yet
+    pub behind: usize,
+}
+
+/// The remote turned down our credentials; `fix` says what to do about it
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthError {
+    pub operation: String,
+    pub fix: String,
+    /// What git said
+    pub detail: String,
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Git {} failed: the remote refused the credentials\n{}\n{}", self.operation, self.fix, self.detail.trim())
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+/// Git sync manager
+#[derive(Debug, Clone)]
+pub struct GitSync {
+    repo_path: std::path::PathBuf,
+    /// Credentials for commands that talk to the remote
+    pub config: GitConfig,
+}
+
+impl GitSync {
+    pub fn new(repo_path: std::path::PathBuf) -> Self {
+        Self { repo_path, config: GitConfig::default() }
+    }
+
+    /// A git command that may reach the remote. It never prompts, since there
+    /// is no terminal to answer in the TUI; missing credentials fail instead.
+    fn remote_command(&self) -> Command {
+        let mut command = Command::new("git");
+        command.current_dir(&self.repo_path).env("GIT_TERMINAL_PROMPT", "0");
+        match self.config.credentials {
+            CredentialMode::System => {}
+            CredentialMode::Ssh => {
+                if std::env::var_os("GIT_SSH_COMMAND").is_none() {
+                    command.env("GIT_SSH_COMMAND", "ssh -o BatchMode=yes");
+                }
+            }
+            CredentialMode::Token => {
+                // An empty helper first clears the ones from git's own config
+                command
+                    .args(["-c", "credential.helper=", "-c"])
+                    .arg(format!("credential.helper={}", TOKEN_HELPER))
+                    .env("TASKTUI_GIT_USERNAME", self.config.username.as_deref().unwrap_or("git"))
+                    .env("TASKTUI_GIT_TOKEN", self.config.token.as_deref().unwrap_or_default());
+            }
+        }
+        command
+    }
+
+    /// The error for a failed remote command, telling a refused sign-in apart
+    /// from the rest
+    fn remote_error(&self, operation: &str, stderr: &str) -> anyhow::Error {
+        match auth_fix(&self.config, self.remote_url().as_deref(), stderr) {
+            Some(fix) => AuthError { operation: operation.to_string(), fix, detail: stderr.to_string() }.into(),
+            None => anyhow::anyhow!("Git {} failed: {}", operation, stderr),
+        }
+    }
+
+    /// Execute git pull --rebase --autostash
+    pub fn pull(&self) -> Result<()> {
+        let output = self.remote_command()
+            .arg("pull")
+            .arg("--rebase")
+            .arg("--autostash")
+            .output()
+            .context("Failed to execute git pull")?;
+
+        if !output.status.success() {
+            return Err(self.remote_error("pull", &String::from_utf8_lossy(&output.stderr)));
+        }
+
+        Ok(())
+    }
+
+    /// Execute git fetch, never stopping to ask for credentials
+    pub fn fetch(&self) -> Result<()> {
+        let output = self.remote_command()
+            .arg("fetch")
+            .arg("--quiet")
+            .output()
+            .context("Failed to execute git fetch")?;
+
+        if !output.status.success() {
+            return Err(self.remote_error("fetch", &String::from_utf8_lossy(&output.stderr)));
+        }
+
+        Ok(())
+    }
+
+    /// Commits ahead of and behind the upstream branch as of the last fetch,
+    /// or `None` when the branch has no upstream
+    pub fn ahead_behind(&self) -> Result<Option<AheadBehind>> {
+        let output = Command::new("git")
+            .arg("rev-list")
+            .arg("--left-right")
+            .arg("--count")
+            .arg("HEAD...@{upstream}")
+            .current_dir(&self.repo_path)
+            .output()
+            .context("Failed to execute git rev-list")?;
+
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        let counts = String::from_utf8_lossy(&output.stdout);
+        let mut counts = counts.split_whitespace().map(|n| n.parse::<usize>().unwrap_or(0));
+        Ok(Some(AheadBehind { ahead: counts.next().unwrap_or(0), behind: counts.next().unwrap_or(0) }))
+    }
+
+    /// Fetch (when `fetch` is set) and count `ahead_behind` on a background
+    /// thread, so the network never holds up the caller
+    pub fn check_remote_in_background(&self, fetch: bool) -> Receiver<Result<Option<AheadBehind>>> {
+        let (sender, receiver) = mpsc::channel();
+        let git_sync = self.clone();
+        thread::spawn(move || {
+            let result = if fetch { git_sync.fetch() } else { Ok(()) }.and_then(|_| git_sync.ahead_behind());
+            let _ = sender.send(result);
+        });
+        receiver
+    }
+
+    /// The commit checked out, or `None` before the first commit
+    pub fn head(&self) -> Option<String> {
+        let output = Command::new("git")
+            .arg("rev-parse")
+            .arg("HEAD")
+            .current_dir(&self.repo_path)
+            .output()
+            .ok()?;
+        output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Files under the repository path that differ between two commits
+    pub fn changed_files(&self, from: &str, to: &str) -> Result<Vec<std::path::PathBuf>> {
+        let output = Command::new("git")
+            .arg("diff")
+            .arg("--name-only")
+            .arg("--relative")
+            .arg(from)
+            .arg(to)
+            .current_dir(&self.repo_path)
+            .output()
+            .context("Failed to execute git diff")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Git diff failed: {}", stderr);
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| self.repo_path.join(line))
+            .collect())
+    }
+
+    /// Execute git add, commit, and push
+    pub fn commit_and_push(&self, message: &str) -> Result<()> {
+        // However the repository was set up, credentials never go in it
+        self.exclude(AppConfig::CREDENTIALS_FILE)?;
+        self.exclude(AppConfig::BACKUP_FILE)?;
+        // Each machine counts its own LLM spending; a synced total would conflict on every pull
+        self.exclude(MonthlyUsage::FILE_NAME)?;
+
+        // Git add
+        let output = Command::new("git")
+            .arg("add")
+            .arg(".")
+            .current_dir(&self.repo_path)
+            .output()
+            .context("Failed to execute git add")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Git add failed: {}", stderr);
+        }
+        self.unstage_ignored()?;
+
+        // Git commit
+        let output = Command::new("git")
+            .arg("commit")
+            .arg("-m")
+            .arg(message)
+            .current_dir(&self.repo_path)
+            .output()
+            .context("Failed to execute git commit")?;
+
+        // It's ok if commit fails (nothing to commit)
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            // Check if it's just "nothing to commit"
+            if !stderr.contains("nothing to commit") && !stderr.contains("no changes added") {
+                tracing::warn!(error = %stderr.trim(), "Git commit had issues");
+            }
+        }
+
+        // Git push, setting the upstream on the first push to a new remote
+        let mut output = self.remote_command()
+            .arg("push")
+            .output()
+            .context("Failed to execute git push")?;
+        if !output.status.success() && String::from_utf8_lossy(&output.stderr).contains("has no upstream branch") {
+            output = self.remote_command()
+                .args(["push", "--set-upstream", "origin", "HEAD"])
+                .output()
+                .context("Failed to execute git push")?;
+        }
+
+        if !output.status.success() {
+            return Err(self.remote_error("push", &String::from_utf8_lossy(&output.stderr)));
+        }
+
+        Ok(())
+    }
+
+    /// Take the files `.tasktuiignore` keeps local back out of the index, so
+    /// they're never committed. Files committed before stay in the repository
+    /// as they were, rather than being deleted from the remote and from every
+    /// other clone; only their local changes are held back.
+    fn unstage_ignored(&self) -> Result<()> {
+        let rules = IgnoreRules::load(&self.repo_path);
+        if rules.is_empty() {
+            return Ok(());
+        }
+        let output = Command::new("git")
+            .args(["ls-files", "-z"])
+            .current_dir(&self.repo_path)
+            .output()
+            .context("Failed to execute git ls-files")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Git ls-files failed: {}", stderr);
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let ignored: Vec<&str> = stdout.split('\0')
+            .filter(|path| !path.is_empty() && rules.is_ignored(std::path::Path::new(path)))
+            .collect();
+        if ignored.is_empty() {
+            return Ok(());
+        }
+        let committed = self.committed_files()?;
+        let (committed, new): (Vec<&str>, Vec<&str>) = ignored.into_iter().partition(|path| committed.contains(*path));
+
+        if !new.is_empty() {
+            let output = Command::new("git")
+                .args(["rm", "--cached", "--quiet", "--"])
+                .args(&new)
+                .current_dir(&self.repo_path)
+                .output()
+                .context("Failed to execute git rm")?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                anyhow::bail!("Git rm failed: {}", stderr);
+            }
+        }
+        if !committed.is_empty() {
+            let output = Command::new("git")
+                .args(["reset", "--quiet", "HEAD", "--"])
+                .args(&committed)
+                .current_dir(&self.repo_path)
+                .output()
+                .context("Failed to execute git reset")?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                anyhow::bail!("Git reset failed: {}", stderr);
+            }
+        }
+        Ok(())
+    }
+
+    /// Paths in the last commit, none before the first
+    fn committed_files(&self) -> Result<std::collections::HashSet<String>> {
+        if self.head().is_none() {
+            return Ok(Default::default());
+        }
+        let output = Command::new("git")
+            .args(["ls-tree", "-r", "-z", "--name-only", "HEAD"])
+            .current_dir(&self.repo_path)
+            .output()
+            .context("Failed to execute git ls-tree")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Git ls-tree failed: {}", stderr);
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).split('\0').filter(|p| !p.is_empty()).map(str::to_string).collect())
+    }
+
+    /// Whether `path`, relative to the repository, is in the last commit
+    pub fn is_committed(&self, path: &std::path::Path) -> bool {
+        self.committed_files().is_ok_and(|files| files.contains(&path.to_string_lossy().replace('\\', "/")))
+    }
+
+    /// The URL of the `origin` remote, if there is one
+    pub fn remote_url(&self) -> Option<String> {
+        let output = Command::new("git")
+            .args(["remote", "get-url", "origin"])
+            .current_dir(&self.repo_path)
+            .output()
+            .ok()?;
+        output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Point `origin` at `url`, adding the remote if needed; an empty `url`
+    /// removes it
+    pub fn set_remote_url(&self, url: &str) -> Result<()> {
+        let args: &[&str] = match (self.remote_url().is_some(), url.is_empty()) {
+            (true, true) => &["remote", "remove", "origin"],
+            (true, false) => &["remote", "set-url", "origin", url],
+            (false, false) => &["remote", "add", "origin", url],
+            (false, true) => return Ok(()),
+        };
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(&self.repo_path)
+            .output()
+            .context("Failed to execute git remote")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Git remote failed: {}", stderr);
+        }
+
+        Ok(())
+    }
+
+    /// Reach `origin` and sign in, without changing anything
+    pub fn test_remote(&self) -> Result<()> {
+        if self.remote_url().is_none() {
+            anyhow::bail!("No remote set");
+        }
+        let output = self.remote_command()
+            .args(["ls-remote", "--heads", "origin"])
+            .output()
+            .context("Failed to execute git ls-remote")?;
+
+        if !output.status.success() {
+            return Err(self.remote_error("connection test", &String::from_utf8_lossy(&output.stderr)));
+        }
+
+        Ok(())
+    }
+
+    /// `test_remote` on a background thread
+    pub fn test_remote_in_background(&self) -> Receiver<Result<()>> {
+        let (sender, receiver) = mpsc::channel();
+        let git_sync = self.clone();
+        thread::spawn(move || {
+            let _ = sender.send(git_sync.test_remote());
+        });
+        receiver
+    }
+
+    /// `git log -p --follow` of one file, newest commit first, with every
+    /// diff carrying the whole file so each line's section is known. Each
+    /// commit starts with a record separator (0x1e) and a header of hash,
+    /// author, ISO date and subject split by unit separators (0x1f).
+    pub fn file_log(&self, path: &std::path::Path, max_commits: usize) -> Result<String> {
+        let output = Command::new("git")
+            .arg("log")
+            .arg("-p")
+            .arg("--follow")
+            .arg("--no-color")
+            .arg("--no-ext-diff")
+            .arg("--unified=100000")
+            .arg(format!("--max-count={}", max_commits))
+            .arg("--format=%x1e%h%x1f%an%x1f%aI%x1f%s")
+            .arg("--")
+            .arg(path)
+            .current_dir(&self.repo_path)
+            .output()
+            .context("Failed to execute git log")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Git log failed: {}", stderr);
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Check if we're in a git repository
+    pub fn is_git_repo(&self) -> bool {
+        let output = Command::new("git")
+            .arg("rev-parse")
+            .arg("--git-dir")
+            .current_dir(&self.repo_path)
+            .output();
+
+        matches!(output, Ok(output) if output.status.success())
+    }
+
+    /// Add a pattern to the repository's local exclude file, so files that
+    /// only matter on this machine are never committed
+    pub fn exclude(&self, pattern: &str) -> Result<()> {
+        let output = Command::new("git")
+            .arg("rev-parse")
+            .arg("--git-path")
+            .arg("info/exclude")
+            .current_dir(&self.repo_path)
+            .output()
+            .context("Failed to execute git rev-parse")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Git rev-parse failed: {}", stderr);
+        }
+
+        let path = self.repo_path.join(String::from_utf8_lossy(&output.stdout).trim());
+        let existing = std::fs::read_to_string(&path).unwrap_or_default();
+        if existing.lines().any(|line| line.trim() == pattern) {
+            return Ok(());
+        }
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let separator = if existing.is_empty() || existing.ends_with('\n') { "" } else { "\n" };
+        std::fs::write(&path, format!("{}{}{}\n", existing, separator, pattern))
+            .context("Failed to write git exclude file")
+    }
+
+    /// Initialize a git repository if it doesn't exist
+    pub fn init_if_needed(&self) -> Result<()> {
+        if !self.is_git_repo() {
+            let output = Command::new("git")
+                .arg("init")
+                .current_dir(&self.repo_path)
+                .output()
+                .context("Failed to execute git init")?;
+
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                anyhow::bail!("Git init failed: {}", stderr);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What to do about `stderr` from a remote command when it says the sign-in
+/// failed; `None` for any other failure
+fn auth_fix(config: &GitConfig, url: Option<&str>, stderr: &str) -> Option<String> {
+    let error = stderr.to_lowercase();
+    if error.contains("host key verification failed") {
+        return Some("The host's SSH key isn't trusted yet: connect once from a terminal (`ssh -T git@<host>`) and accept it".to_string());
+    }
+    let refused = [
+        "authentication failed",
+        "permission denied (publickey",
+        "could not read username",
+        "could not read password",
+        "terminal prompts disabled",
+        "invalid username or password",
+        "the requested url returned error: 401",
+        "the requested url returned error: 403",
+    ];
+    if !refused.iter().any(|pattern| error.contains(pattern)) {
+        return None;
+    }
+    let ssh_url = url.is_some_and(|url| url.starts_with("ssh://") || (url.contains('@') && !url.contains("://")));
+    Some(match config.credentials {
+        CredentialMode::Token if !ssh_url && config.token.as_deref().unwrap_or_default().is_empty() => {
+            "Paste an access token in Settings → Sync, or set TASKTUI_GIT__TOKEN".to_string()
+        }
+        CredentialMode::Token if !ssh_url => {
+            "The access token was turned down: check it hasn't expired and may write to the repository".to_string()
+        }
+        _ if ssh_url => {
+            "Load your SSH key with `ssh-add` and add its public half to your account on the host; `ssh -T git@<host>` tests it".to_string()
+        }
+        _ => "Git has no saved credentials for this remote: choose `token` credentials in Settings → Sync and paste an access token, \
+              or set up a credential helper and push once from a terminal"
+            .to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_git_init() {
+        let temp_dir = TempDir::new().unwrap();
+        let git_sync = GitSync::new(temp_dir.path().to_path_buf());
+
+        assert!(!git_sync.is_git_repo());
+        git_sync.init_if_needed().unwrap();
+        assert!(git_sync.is_git_repo());
+    }
+
+    #[test]
+    fn test_exclude() {
+        let temp_dir = TempDir::new().unwrap();
+        let git_sync = GitSync::new(temp_dir.path().to_path_buf());
+        git_sync.init_if_needed().unwrap();
+
+        git_sync.exclude(".local-state").unwrap();
+        git_sync.exclude(".local-state").unwrap();
+        let exclude = std::fs::read_to_string(temp_dir.path().join(".git/info/exclude")).unwrap();
+        assert_eq!(exclude.lines().filter(|l| *l == ".local-state").count(), 1);
+
+        std::fs::write(temp_dir.path().join(".local-state"), "{}").unwrap();
+        let status = Command::new("git")
+            .args(["status", "--porcelain"])
+            .current_dir(temp_dir.path())
+            .output()
+            .unwrap();
+        assert!(String::from_utf8_lossy(&status.stdout).is_empty());
+    }
+
+    #[test]
+    fn test_ahead_behind() {
+        let temp_dir = TempDir::new().unwrap();
+        let git = |dir: &std::path::Path, args: &[&str]| {
+            let output = Command::new("git").args(args).current_dir(dir).output().unwrap();
+            assert!(output.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+        };
+        git(temp_dir.path(), &["init", "--bare", "remote.git"]);
+        let clone = |name: &str| {
+            git(temp_dir.path(), &["clone", "-q", "remote.git", name]);
+            let dir = temp_dir.path().join(name);
+            git(&dir, &["config", "user.name", "Test"]);
+            git(&dir, &["config", "user.email", "test@example.com"]);
+            GitSync::new(dir)
+        };
+        let (laptop, desktop) = (clone("laptop"), clone("desktop"));
+        let commit = |git_sync: &GitSync, name: &str| {
+            std::fs::write(git_sync.repo_path.join(name), name).unwrap();
+            git_sync.commit_and_push(name).unwrap();
+        };
+        commit(&laptop, "first.md");
+        git(&desktop.repo_path, &["pull", "-q"]);
+        assert_eq!(desktop.ahead_behind().unwrap(), Some(AheadBehind::default()));
+
+        // The laptop pushes twice; the desktop only sees it after a fetch
+        commit(&laptop, "second.md");
+        commit(&laptop, "third.md");
+        std::fs::write(desktop.repo_path.join("local.md"), "local").unwrap();
+        git(&desktop.repo_path, &["add", "."]);
+        git(&desktop.repo_path, &["commit", "-qm", "Local"]);
+        assert_eq!(desktop.ahead_behind().unwrap(), Some(AheadBehind { ahead: 1, behind: 0 }));
+        let status = desktop.check_remote_in_background(true).recv().unwrap().unwrap();
+        assert_eq!(status, Some(AheadBehind { ahead: 1, behind: 2 }));
+    }
+
+    #[test]
+    fn test_ignoring_a_pushed_file_keeps_it_on_other_clones() {
+        let temp_dir = TempDir::new().unwrap();
+        let git = |dir: &std::path::Path, args: &[&str]| {
+            let output = Command::new("git").args(args).current_dir(dir).output().unwrap();
+            assert!(output.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+        };
+        git(temp_dir.path(), &["init", "--bare", "remote.git"]);
+        let clone = |name: &str| {
+            git(temp_dir.path(), &["clone", "-q", "remote.git", name]);
+            let dir = temp_dir.path().join(name);
+            git(&dir, &["config", "user.name", "Test"]);
+            git(&dir, &["config", "user.email", "test@example.com"]);
+            GitSync::new(dir)
+        };
+        let (laptop, desktop) = (clone("laptop"), clone("desktop"));
+        std::fs::write(laptop.repo_path.join("shared.md"), "first").unwrap();
+        laptop.commit_and_push("Add shared").unwrap();
+        desktop.pull().unwrap();
+
+        // The laptop keeps the pushed file local, and a new one too
+        std::fs::write(laptop.repo_path.join(crate::ignore::IGNORE_FILE), "shared.md\nscratch.md\n").unwrap();
+        std::fs::write(laptop.repo_path.join("shared.md"), "edited on the laptop").unwrap();
+        std::fs::write(laptop.repo_path.join("scratch.md"), "scratch").unwrap();
+        // Secrets, the config backup that may hold them and the LLM usage are never pushed
+        std::fs::write(laptop.repo_path.join(AppConfig::CREDENTIALS_FILE), "git.token: s3cret\n").unwrap();
+        std::fs::write(laptop.repo_path.join(AppConfig::BACKUP_FILE), "git:\n  token: s3cret\n").unwrap();
+        std::fs::write(laptop.repo_path.join(MonthlyUsage::FILE_NAME), "calls: 3\n").unwrap();
+        laptop.commit_and_push("Keep local").unwrap();
+        assert!(laptop.is_committed(std::path::Path::new("shared.md")));
+        assert!(!laptop.is_committed(std::path::Path::new("scratch.md")));
+        assert!(!laptop.is_committed(std::path::Path::new(AppConfig::CREDENTIALS_FILE)));
+        assert!(!laptop.is_committed(std::path::Path::new(AppConfig::BACKUP_FILE)));
+        assert!(!laptop.is_committed(std::path::Path::new(MonthlyUsage::FILE_NAME)));
+
+        // The desktop still has the file as it was pushed
+        desktop.pull().unwrap();
+        assert_eq!(std::fs::read_to_string(desktop.repo_path.join("shared.md")).unwrap(), "first");
+        assert!(!desktop.repo_path.join("scratch.md").exists());
+        assert_eq!(std::fs::read_to_string(laptop.repo_path.join("shared.md")).unwrap(), "edited on the laptop");
+    }
+
+    #[test]
+    fn test_credentials_and_auth_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut git_sync = GitSync::new(temp_dir.path().to_path_buf());
+        git_sync.init_if_needed().unwrap();
+        git_sync.config.credentials = CredentialMode::Token;
+        git_sync.config.token = Some("s3cret".to_string());
+
+        // Git asks the token helper in place of a prompt
+        let mut fill = git_sync.remote_command()
+            .args(["credential", "fill"])
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        std::io::Write::write_all(fill.stdin.as_mut().unwrap(), b"protocol=https\nhost=example.com\n\n").unwrap();
+        let filled = String::from_utf8(fill.wait_with_output().unwrap().stdout).unwrap();
+        assert!(filled.contains("username=git\n") && filled.contains("password=s3cret\n"), "{}", filled);
+
+        git_sync.set_remote_url("https://example.com/tasks.git").unwrap();
+        assert_eq!(git_sync.remote_url().as_deref(), Some("https://example.com/tasks.git"));
+        let refused = "remote: Invalid username or password.\nfatal: Authentication failed for 'https://example.com/tasks.git/'";
+        let error = git_sync.remote_error("push", refused);
+        let auth = error.downcast_ref::<AuthError>().unwrap();
+        assert!(auth.fix.contains("token was turned down"), "{}", auth.fix);
+        assert!(error.to_string().starts_with("Git push failed: the remote refused the credentials\n"));
+
+        git_sync.config.credentials = CredentialMode::System;
+        git_sync.set_remote_url("git@example.com:me/tasks.git").unwrap();
+        let error = git_sync.remote_error("pull", "git@example.com: Permission denied (publickey).");
+        assert!(error.downcast_ref::<AuthError>().unwrap().fix.contains("ssh-add"));
+        let error = git_sync.remote_error("pull", "fatal: couldn't find remote ref main");
+        assert!(error.downcast_ref::<AuthError>().is_none());
+
+        git_sync.set_remote_url("").unwrap();
+        assert_eq!(git_sync.remote_url(), None);
+    }
+}
@@ -0,0 +1,138 @@
+use crate::models::{Habit, TaskItem};
+use chrono::{Datelike, NaiveDate, Weekday};
+
+impl Habit {
+    /// A running number for the period `date` falls in, so consecutive
+    /// periods differ by one. Weekends have none for a weekdays habit.
+    fn period(self, date: NaiveDate) -> Option<i64> {
+        let day = date.num_days_from_ce() as i64;
+        let weekday = date.weekday().num_days_from_monday() as i64;
+        match self {
+            Habit::Daily => Some(day),
+            Habit::Weekly => Some((day - weekday).div_euclid(7)),
+            Habit::Weekdays if weekday >= 5 => None,
+            Habit::Weekdays => Some((day - weekday).div_euclid(7) * 5 + weekday),
+        }
+    }
+
+    /// The period that's current on `today`; on a weekend, a weekdays habit
+    /// is still on Friday's
+    fn current_period(self, today: NaiveDate) -> i64 {
+        let mut date = today;
+        while matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && self == Habit::Weekdays {
+            date = date.pred_opt().unwrap_or(date);
+        }
+        self.period(date).unwrap_or_default()
+    }
+}
+
+impl TaskItem {
+    /// Record a habit as done on `date`. Checking off twice is harmless.
+    pub fn check_off(&mut self, date: NaiveDate) {
+        let log = &mut self.frontmatter.habit_log;
+        if let Err(pos) = log.binary_search(&date) {
+            log.insert(pos, date);
+        }
+    }
+
+    /// Distinct periods with a check-off, in order
+    fn habit_periods(&self) -> Vec<i64> {
+        let Some(habit) = self.frontmatter.habit else {
+            return Vec::new();
+        };
+        let mut periods: Vec<i64> = self.frontmatter.habit_log.iter().filter_map(|d| habit.period(*d)).collect();
+        periods.sort_unstable();
+        periods.dedup();
+        periods
+    }
+
+    /// Whether the habit is already checked off for the current period
+    pub fn checked_off(&self, today: NaiveDate) -> bool {
+        self.frontmatter.habit.is_some_and(|habit| self.habit_periods().contains(&habit.current_period(today)))
+    }
+
+    /// Consecutive periods checked off up to now. A streak stays alive through
+    /// the current period until it's over, so it doesn't drop to 0 each morning.
+    pub fn current_streak(&self, today: NaiveDate) -> usize {
+        let Some(habit) = self.frontmatter.habit else {
+            return 0;
+        };
+        let periods = self.habit_periods();
+        let current = habit.current_period(today);
+        let last = if periods.contains(&current) { current } else { current - 1 };
+        let mut streak = 0;
+        while periods.contains(&(last - streak as i64)) {
+            streak += 1;
+        }
+        streak
+    }
+
+    /// The longest run of consecutive periods ever checked off
+    pub fn best_streak(&self) -> usize {
+        let periods = self.habit_periods();
+        let mut best = 0;
+        let mut run = 0;
+        for (i, period) in periods.iter().enumerate() {
+            run = if i > 0 && periods[i - 1] + 1 == *period { run + 1 } else { 1 };
+            best = best.max(run);
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ItemType, Status};
+
+    fn date(day: u32) -> NaiveDate {
+        // 2025-03-03 is a Monday
+        NaiveDate::from_ymd_opt(2025, 3, day).unwrap()
+    }
+
+    fn habit(kind: Habit, days: &[u32]) -> TaskItem {
+        let mut task = TaskItem::new("Stretch".to_string(), ItemType::Task);
+        task.frontmatter.habit = Some(kind);
+        for day in days {
+            task.check_off(date(*day));
+        }
+        task
+    }
+
+    #[test]
+    fn test_daily_streaks() {
+        let task = habit(Habit::Daily, &[3, 4, 5, 7, 8, 9, 10]);
+        assert_eq!(task.best_streak(), 4);
+        assert_eq!(task.current_streak(date(10)), 4);
+        // Not done yet today: the streak holds until the day is over
+        assert_eq!(task.current_streak(date(11)), 4);
+        assert!(!task.checked_off(date(11)));
+        assert_eq!(task.current_streak(date(12)), 0);
+    }
+
+    #[test]
+    fn test_weekday_and_weekly_streaks() {
+        // Friday to Monday skips the weekend
+        let task = habit(Habit::Weekdays, &[6, 7, 10]);
+        assert_eq!(task.current_streak(date(10)), 3);
+        let task = habit(Habit::Weekdays, &[6, 7]);
+        assert_eq!(task.current_streak(date(9)), 2);
+        assert!(task.checked_off(date(9)));
+
+        let task = habit(Habit::Weekly, &[3, 5, 12, 24]);
+        assert_eq!(task.best_streak(), 2);
+        assert_eq!(task.current_streak(date(26)), 1);
+    }
+
+    #[test]
+    fn test_done_checks_off_habit() {
+        let mut task = habit(Habit::Daily, &[]);
+        task.frontmatter.status = Status::Active;
+        task.set_status(Status::Done);
+        assert_eq!(task.frontmatter.status, Status::Active);
+        assert!(task.frontmatter.completed_at.is_none());
+        assert_eq!(task.frontmatter.habit_log, vec![crate::models::today()]);
+        task.set_status(Status::Done);
+        assert_eq!(task.frontmatter.habit_log.len(), 1);
+    }
+}
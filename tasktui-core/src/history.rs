@@ -0,0 +1,222 @@
+use crate::activity::{LOG_HEADING, NOTES_HEADING};
+use crate::git::GitSync;
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset};
+use std::path::Path;
+
+/// Commits read for one task's history
+pub const MAX_COMMITS: usize = 200;
+
+/// Frontmatter fields that change on every save and would drown the rest
+const NOISY_FIELDS: &[&str] = &["updated_at", "code"];
+
+/// One commit that touched a task's file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Revision {
+    pub commit: String,
+    pub author: String,
+    pub at: Option<DateTime<FixedOffset>>,
+    pub subject: String,
+    pub changes: Vec<Change>,
+}
+
+/// What a commit changed in the task
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// The file was added, with this title
+    Created(String),
+    /// A frontmatter field: its old and new value, `None` when absent
+    Field { name: String, from: Option<String>, to: Option<String> },
+    /// A note added under `## Notes`
+    Note(String),
+    /// Other body lines added and removed
+    Body { added: usize, removed: usize },
+}
+
+/// The history of the task file at `path`, newest first
+pub fn task_history(git: &GitSync, path: &Path) -> Result<Vec<Revision>> {
+    Ok(parse_log(&git.file_log(path, MAX_COMMITS)?))
+}
+
+/// Parse the output of `GitSync::file_log`
+pub fn parse_log(log: &str) -> Vec<Revision> {
+    log.split('\u{1e}').filter(|record| !record.trim().is_empty()).filter_map(parse_revision).collect()
+}
+
+fn parse_revision(record: &str) -> Option<Revision> {
+    let (header, patch) = record.split_once('\n').unwrap_or((record, ""));
+    let mut fields = header.split('\u{1f}');
+    let commit = fields.next()?.trim().to_string();
+    let author = fields.next().unwrap_or_default().to_string();
+    let at = fields.next().and_then(|at| DateTime::parse_from_rfc3339(at.trim()).ok());
+    let subject = fields.next().unwrap_or_default().trim().to_string();
+    Some(Revision { commit, author, at, subject, changes: parse_patch(patch) })
+}
+
+/// Changes in a whole-file diff: lines are ` ` (kept), `+` or `-`
+fn parse_patch(patch: &str) -> Vec<Change> {
+    let lines: Vec<&str> = patch.lines()
+        .skip_while(|line| !line.starts_with("@@"))
+        .skip(1)
+        .filter(|line| !line.is_empty() && !line.starts_with("@@") && !line.starts_with('\\'))
+        .collect();
+    if lines.is_empty() {
+        return Vec::new();
+    }
+
+    let created = lines.iter().all(|line| line.starts_with('+'));
+    let mut fields: Vec<(String, Vec<String>, Vec<String>)> = Vec::new();
+    let mut notes = Vec::new();
+    let (mut added, mut removed) = (0, 0);
+    let mut delimiters = 0;
+    let mut key = String::new();
+    let mut section = String::new();
+    let mut title = String::new();
+
+    for line in lines {
+        let (mark, text) = line.split_at(1);
+        if text.trim_end() == "---" && delimiters < 2 {
+            if mark != "-" {
+                delimiters += 1;
+            }
+            continue;
+        }
+        if delimiters == 1 {
+            // Frontmatter: a top-level `key: value`, or a nested line under the last key
+            let nested = text.starts_with([' ', '\t', '-']);
+            if !nested {
+                if let Some((name, _)) = text.split_once(':') {
+                    key = name.trim().to_string();
+                }
+            }
+            if key == "title" && mark != "-" {
+                title = text.split_once(':').map(|(_, v)| unquote(v)).unwrap_or_default();
+            }
+            if mark == " " || NOISY_FIELDS.contains(&key.as_str()) {
+                continue;
+            }
+            let value = if nested {
+                text.trim().to_string()
+            } else {
+                text.split_once(':').map(|(_, v)| v.trim().to_string()).unwrap_or_default()
+            };
+            let index = match fields.iter().position(|(name, _, _)| *name == key) {
+                Some(index) => index,
+                None => {
+                    fields.push((key.clone(), Vec::new(), Vec::new()));
+                    fields.len() - 1
+                }
+            };
+            let entry = &mut fields[index];
+            let side = if mark == "-" { &mut entry.1 } else { &mut entry.2 };
+            if !value.is_empty() {
+                side.push(value);
+            }
+        } else if delimiters >= 2 {
+            if text.starts_with("## ") {
+                section = text.trim().to_string();
+            }
+            if mark == " " || text.trim().is_empty() || section == LOG_HEADING || text.trim() == NOTES_HEADING {
+                // `## Log` entries repeat the field changes
+                continue;
+            }
+            match (mark, section == NOTES_HEADING, text.strip_prefix("- ")) {
+                ("+", true, Some(note)) => notes.push(note.to_string()),
+                (_, true, _) if text.starts_with("  ") => {}
+                ("+", _, _) => added += 1,
+                _ => removed += 1,
+            }
+        }
+    }
+
+    if created {
+        return vec![Change::Created(title)];
+    }
+    let mut changes: Vec<Change> = fields.into_iter()
+        .filter(|(_, from, to)| from != to)
+        .map(|(name, from, to)| {
+            let join = |values: Vec<String>| (!values.is_empty()).then(|| values.join(", "));
+            Change::Field { name, from: join(from), to: join(to) }
+        })
+        .collect();
+    changes.extend(notes.into_iter().map(Change::Note));
+    if added + removed > 0 {
+        changes.push(Change::Body { added, removed });
+    }
+    changes
+}
+
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    let quoted = value.len() >= 2
+        && ((value.starts_with('"') && value.ends_with('"')) || (value.starts_with('\'') && value.ends_with('\'')));
+    if quoted { value[1..value.len() - 1].to_string() } else { value.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log() {
+        let log = [
+            "\u{1e}b2c3d4e\u{1f}Sam\u{1f}2025-03-12T09:30:00+01:00\u{1f}Update: Renew passport",
+            "",
+            "diff --git a/t.md b/t.md",
+            "--- a/t.md",
+            "+++ b/t.md",
+            "@@ -1,14 +1,17 @@",
+            " ---",
+            " title: Renew passport",
+            "-status: next",
+            "+status: active",
+            " tags:",
+            " - admin",
+            "+- travel",
+            "-updated_at: 2025-03-10T08:00:00Z",
+            "+updated_at: 2025-03-12T08:30:00Z",
+            "+due_date: 2025-04-01",
+            " ---",
+            " ",
+            " Bring photos.",
+            "+Two of them.",
+            " ",
+            " ## Notes",
+            "+- 2025-03-12 09:30 Booked the appointment",
+            "+",
+            "+## Log",
+            "+- 2025-03-12 09:30 status: next → active",
+            "\u{1e}a1b2c3d\u{1f}Alex\u{1f}2025-03-10T09:00:00+01:00\u{1f}Update: Renew passport",
+            "",
+            "diff --git a/t.md b/t.md",
+            "new file mode 100644",
+            "--- /dev/null",
+            "+++ b/t.md",
+            "@@ -0,0 +1,5 @@",
+            "+---",
+            "+title: \"Renew passport\"",
+            "+status: next",
+            "+---",
+            "+Bring photos.",
+        ]
+        .join("\n");
+
+        let history = parse_log(&log);
+        assert_eq!(history.len(), 2);
+        assert_eq!((history[0].commit.as_str(), history[0].author.as_str()), ("b2c3d4e", "Sam"));
+        assert_eq!(history[0].at.unwrap().to_rfc3339(), "2025-03-12T09:30:00+01:00");
+        let field = |name: &str, from: Option<&str>, to: Option<&str>| Change::Field {
+            name: name.to_string(),
+            from: from.map(str::to_string),
+            to: to.map(str::to_string),
+        };
+        assert_eq!(history[0].changes, [
+            field("status", Some("next"), Some("active")),
+            field("tags", None, Some("- travel")),
+            field("due_date", None, Some("2025-04-01")),
+            Change::Note("2025-03-12 09:30 Booked the appointment".to_string()),
+            Change::Body { added: 1, removed: 0 },
+        ]);
+        assert_eq!(history[1].changes, [Change::Created("Renew passport".to_string())]);
+    }
+}
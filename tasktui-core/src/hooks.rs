@@ -0,0 +1,197 @@
+use crate::config::Hooks;
+use crate::models::{Status, TaskItem};
+use serde_json::{json, Value};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// What happened, choosing which of the configured hooks run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    Create,
+    Complete,
+    Sync,
+}
+
+impl HookEvent {
+    /// The config key, also passed to commands as `TASKTUI_EVENT`
+    pub fn key(self) -> &'static str {
+        match self {
+            HookEvent::Create => "on_create",
+            HookEvent::Complete => "on_complete",
+            HookEvent::Sync => "on_sync",
+        }
+    }
+}
+
+impl Hooks {
+    pub fn commands(&self, event: HookEvent) -> &[String] {
+        match event {
+            HookEvent::Create => &self.on_create,
+            HookEvent::Complete => &self.on_complete,
+            HookEvent::Sync => &self.on_sync,
+        }
+    }
+}
+
+/// The task events a write of `item` over `previous` (the version on disk)
+/// amounts to. A task that's new but already done, such as an imported one,
+/// only counts as created.
+pub fn events(previous: Option<&TaskItem>, item: &TaskItem) -> Vec<HookEvent> {
+    match previous {
+        None => vec![HookEvent::Create],
+        Some(previous) if previous.frontmatter.status != Status::Done && item.frontmatter.status == Status::Done => {
+            vec![HookEvent::Complete]
+        }
+        Some(_) => Vec::new(),
+    }
+}
+
+/// A task as hook commands see it: its frontmatter plus code, body and path
+pub fn task_payload(item: &TaskItem) -> Value {
+    let mut payload = serde_json::to_value(&item.frontmatter).unwrap_or_else(|_| json!({}));
+    payload["code"] = json!(item.code());
+    payload["body"] = json!(item.body);
+    payload["path"] = json!(item.file_path);
+    payload
+}
+
+/// Start the commands configured for `event` in `data_dir`, writing `payload`
+/// to their stdin. Commands run through the shell and aren't waited for, so a
+/// slow hook never holds up a write; failures are only logged.
+pub fn run(hooks: &Hooks, event: HookEvent, data_dir: &Path, payload: &Value) {
+    for command in hooks.commands(event) {
+        if let Err(e) = spawn(command, event, data_dir, payload) {
+            tracing::warn!(hook = event.key(), command = %command, error = %e, "Hook failed to start");
+        }
+    }
+}
+
+fn spawn(command: &str, event: HookEvent, data_dir: &Path, payload: &Value) -> std::io::Result<()> {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    let mut child = shell
+        .arg(command)
+        .current_dir(data_dir)
+        .env("TASKTUI_EVENT", event.key())
+        .env("TASKTUI_DATA_DIR", data_dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let stdin = child.stdin.take();
+    let (command, payload) = (command.to_string(), payload.to_string());
+    std::thread::spawn(move || {
+        // Written here, as a hook that doesn't read its input would otherwise
+        // block the write once the pipe fills; one that exits early just
+        // closes the pipe
+        if let Some(mut stdin) = stdin {
+            let _ = stdin.write_all(payload.as_bytes());
+        }
+        report(event, &command, child.wait_with_output());
+    });
+    Ok(())
+}
+
+fn report(event: HookEvent, command: &str, output: std::io::Result<std::process::Output>) {
+    match output {
+        Ok(output) if !output.status.success() => tracing::warn!(
+            hook = event.key(),
+            command = %command,
+            status = %output.status,
+            stderr = %String::from_utf8_lossy(&output.stderr).trim(),
+            "Hook failed"
+        ),
+        Ok(_) => tracing::debug!(hook = event.key(), command = %command, "Hook finished"),
+        Err(e) => tracing::warn!(hook = event.key(), command = %command, error = %e, "Hook failed"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ItemType;
+    use crate::storage::Storage;
+    use std::time::{Duration, Instant};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_events() {
+        let task = TaskItem::new("Water plants".to_string(), ItemType::Task);
+        assert_eq!(events(None, &task), vec![HookEvent::Create]);
+        assert!(events(Some(&task), &task).is_empty());
+
+        let mut done = task.clone();
+        done.set_status(Status::Done);
+        assert_eq!(events(Some(&task), &done), vec![HookEvent::Complete]);
+        assert!(events(Some(&done), &done).is_empty());
+        assert_eq!(events(None, &done), vec![HookEvent::Create]);
+    }
+
+    /// Wait for a hook's output, since hooks run in the background. The test
+    /// hooks write a temporary file and move it into place once it's complete.
+    fn read_eventually(path: &Path) -> String {
+        let start = Instant::now();
+        loop {
+            match std::fs::read_to_string(path) {
+                Ok(text) => return text,
+                _ if start.elapsed() > Duration::from_secs(5) => panic!("{} never appeared", path.display()),
+                _ => std::thread::sleep(Duration::from_millis(10)),
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hooks_run_on_write() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+        storage.hooks = Hooks {
+            on_create: vec!["cat > created.tmp && mv created.tmp created.json".to_string()],
+            on_complete: vec!["(echo $TASKTUI_EVENT; cat) > completed.tmp && mv completed.tmp completed.txt".to_string()],
+            on_sync: Vec::new(),
+        };
+
+        let mut task = TaskItem::new("Water plants".to_string(), ItemType::Task);
+        task.frontmatter.tags = vec!["home".to_string()];
+        storage.write_task(&mut task).unwrap();
+        let created: Value = serde_json::from_str(&read_eventually(&temp_dir.path().join("created.json"))).unwrap();
+        assert_eq!(created["id"], json!(task.frontmatter.id));
+        assert_eq!(created["title"], "Water plants");
+        assert_eq!(created["tags"], json!(["home"]));
+        assert_eq!(created["code"], json!(task.code()));
+        assert!(created["path"].as_str().unwrap().ends_with(&format!("{}.md", task.frontmatter.id)));
+        assert!(!temp_dir.path().join("completed.txt").exists());
+
+        task.set_status(Status::Done);
+        storage.write_task(&mut task).unwrap();
+        let completed = read_eventually(&temp_dir.path().join("completed.txt"));
+        let (event, payload) = completed.split_once('\n').unwrap();
+        assert_eq!(event, "on_complete");
+        let payload: Value = serde_json::from_str(payload).unwrap();
+        assert_eq!(payload["status"], "done");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hook_not_reading_stdin_doesnt_block() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+        storage.hooks = Hooks { on_create: vec!["sleep 2".to_string()], ..Hooks::default() };
+
+        // Far more than a pipe holds, which nothing reads until the hook exits
+        let mut task = TaskItem::new("Big".to_string(), ItemType::Task);
+        task.body = "x".repeat(1 << 20);
+        let started = Instant::now();
+        storage.write_task(&mut task).unwrap();
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,156 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Component, Path};
+
+/// Rules for files that stay on this machine, in the vault's root
+pub const IGNORE_FILE: &str = ".tasktuiignore";
+
+/// One line of `.tasktuiignore`
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    pattern: Vec<char>,
+    /// `!pattern` takes a path back in
+    negated: bool,
+    /// `pattern/` only matches folders
+    dir_only: bool,
+    /// A pattern with a `/` is matched from the vault root; one without
+    /// matches a file or folder name at any depth
+    anchored: bool,
+}
+
+/// The paths `.tasktuiignore` keeps local: gitignore's syntax with comments,
+/// `!`, trailing and leading `/`, `*`, `?` and `**`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IgnoreRules {
+    rules: Vec<Rule>,
+}
+
+impl IgnoreRules {
+    /// The rules in `dir`'s `.tasktuiignore`; none when there isn't one
+    pub fn load(dir: &Path) -> Self {
+        Self::parse(&fs::read_to_string(dir.join(IGNORE_FILE)).unwrap_or_default())
+    }
+
+    pub fn parse(text: &str) -> Self {
+        let rules = text.lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let (negated, line) = match line.strip_prefix('!') {
+                    Some(rest) => (true, rest),
+                    None => (false, line),
+                };
+                let (dir_only, line) = match line.strip_suffix('/') {
+                    Some(rest) => (true, rest),
+                    None => (false, line),
+                };
+                Rule {
+                    pattern: line.trim_start_matches('/').chars().collect(),
+                    negated,
+                    dir_only,
+                    anchored: line.contains('/'),
+                }
+            })
+            .filter(|rule| !rule.pattern.is_empty())
+            .collect();
+        Self { rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether `path`, relative to the vault root, is kept local. As in git,
+    /// nothing inside an ignored folder can be taken back in.
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let parts: Vec<String> = path.components()
+            .filter_map(|c| match c {
+                Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect();
+        (1..parts.len()).any(|depth| self.matches(&parts[..depth], true) == Some(true))
+            || self.matches(&parts, false) == Some(true)
+    }
+
+    /// Whether the last rule matching `parts` ignores it; `None` when none match
+    fn matches(&self, parts: &[String], is_dir: bool) -> Option<bool> {
+        let path: Vec<char> = parts.join("/").chars().collect();
+        let name: Vec<char> = parts.last()?.chars().collect();
+        self.rules.iter()
+            .rev()
+            .find(|rule| {
+                (is_dir || !rule.dir_only) && glob(&rule.pattern, if rule.anchored { &path } else { &name })
+            })
+            .map(|rule| !rule.negated)
+    }
+}
+
+/// Keep the file at `path` (relative to `dir`) local or let it sync again,
+/// editing `dir`'s `.tasktuiignore`. Its own `/path` and `!/path` lines are
+/// replaced; a line is only added when the other rules don't already agree.
+pub fn set_ignored(dir: &Path, path: &Path, ignored: bool) -> Result<()> {
+    let file = dir.join(IGNORE_FILE);
+    let existing = fs::read_to_string(&file).unwrap_or_default();
+    let relative = path.to_string_lossy().replace('\\', "/");
+    let (ignore, unignore) = (format!("/{}", relative), format!("!/{}", relative));
+    let mut lines: Vec<&str> = existing.lines().filter(|line| *line != ignore && *line != unignore).collect();
+    if IgnoreRules::parse(&lines.join("\n")).is_ignored(path) != ignored {
+        lines.push(if ignored { &ignore } else { &unignore });
+    }
+    if lines.is_empty() && existing.is_empty() {
+        return Ok(());
+    }
+    let text: String = lines.iter().map(|line| format!("{}\n", line)).collect();
+    fs::write(&file, text).with_context(|| format!("Failed to write {}", IGNORE_FILE))
+}
+
+/// `*` and `?` stop at `/`; `**` crosses it, and `**/` also matches no folder at all
+fn glob(pattern: &[char], text: &[char]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        ['*', '*', '/', rest @ ..] => {
+            glob(rest, text) || (0..text.len()).any(|i| text[i] == '/' && glob(rest, &text[i + 1..]))
+        }
+        ['*', '*', rest @ ..] => (0..=text.len()).any(|i| glob(rest, &text[i..])),
+        ['*', rest @ ..] => (0..=text.len())
+            .take_while(|&i| i == 0 || text[i - 1] != '/')
+            .any(|i| glob(rest, &text[i..])),
+        ['?', rest @ ..] => text.first().is_some_and(|&c| c != '/') && glob(rest, &text[1..]),
+        [c, rest @ ..] => text.first() == Some(c) && glob(rest, &text[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_ignore_rules() {
+        let rules = IgnoreRules::parse("# scratch\ninbox-private/\n*.draft.md\n/notes/**/*.md\n!notes/keep.md\n\n");
+        let ignored = |path: &str| rules.is_ignored(Path::new(path));
+        assert!(ignored("inbox-private/a.md"));
+        assert!(ignored("sub/inbox-private/b.md"));
+        assert!(!ignored("inbox-private"), "a folder-only rule doesn't match a file");
+        assert!(ignored("x.draft.md") && ignored("deep/x.draft.md"));
+        assert!(ignored("notes/a.md") && ignored("notes/2025/a.md"));
+        assert!(!ignored("notes/keep.md"));
+        assert!(!ignored("task.md") && !ignored("other/notes/a.md"));
+
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        set_ignored(dir, Path::new("a.md"), false).unwrap();
+        assert!(!dir.join(IGNORE_FILE).exists(), "nothing to write");
+        set_ignored(dir, Path::new("a.md"), true).unwrap();
+        set_ignored(dir, Path::new("a.md"), true).unwrap();
+        assert_eq!(fs::read_to_string(dir.join(IGNORE_FILE)).unwrap(), "/a.md\n");
+        assert!(IgnoreRules::load(dir).is_ignored(Path::new("a.md")));
+
+        // A broader rule still matching needs a negation to let the file sync
+        fs::write(dir.join(IGNORE_FILE), "*.md\n/a.md\n").unwrap();
+        set_ignored(dir, Path::new("a.md"), false).unwrap();
+        assert_eq!(fs::read_to_string(dir.join(IGNORE_FILE)).unwrap(), "*.md\n!/a.md\n");
+        assert!(!IgnoreRules::load(dir).is_ignored(Path::new("a.md")));
+    }
+}
@@ -0,0 +1,123 @@
+use crate::models::{ItemType, Status, TaskItem};
+use crate::quickadd::QuickAdd;
+use anyhow::Result;
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use uuid::Uuid;
+
+/// Columns a tab counts for when comparing indentation
+const TAB_WIDTH: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Source {
+    /// `- [ ]` checklist items, as in a TODO.md
+    Markdown,
+}
+
+/// Tasks for the `- [ ]` / `- [x]` items of a markdown checklist. Items take the
+/// new-task tokens (`#tag`, `@context`, `!high`, `due:fri`, `+project`); an item
+/// indented under another becomes its subtask. Other lines are skipped.
+/// Open items land in the inbox, checked ones are done.
+pub fn from_markdown(markdown: &str, projects: &[TaskItem], today: NaiveDate) -> Result<Vec<TaskItem>> {
+    let mut tasks: Vec<TaskItem> = Vec::new();
+    // Indentation and id of the items enclosing the current line
+    let mut parents: Vec<(usize, Uuid)> = Vec::new();
+
+    for (line_no, line) in markdown.lines().enumerate() {
+        let Some((indent, done, text)) = checklist_item(line) else {
+            continue;
+        };
+        let parsed = QuickAdd::parse(text, today);
+        if parsed.title.is_empty() {
+            anyhow::bail!("Line {}: checklist item has no title besides its tokens", line_no + 1);
+        }
+
+        while parents.last().is_some_and(|(parent_indent, _)| *parent_indent >= indent) {
+            parents.pop();
+        }
+        let parent_id = match (parents.last(), parsed.project.as_deref()) {
+            (Some((_, id)), _) => Some(*id),
+            (None, Some(name)) => Some(find_project(projects, name).ok_or_else(|| {
+                anyhow::anyhow!("Line {}: no project matches +{}", line_no + 1, name)
+            })?),
+            (None, None) => None,
+        };
+
+        let mut task = TaskItem::new(parsed.title.clone(), ItemType::Task);
+        task.frontmatter.tags = parsed.all_tags();
+        task.frontmatter.due_date = parsed.due_date;
+        if let Some(priority) = parsed.priority {
+            task.frontmatter.priority = priority;
+        }
+        task.frontmatter.parent_goal_id = parent_id;
+        task.set_status(if done { Status::Done } else { Status::Inbox });
+
+        parents.push((indent, task.frontmatter.id));
+        tasks.push(task);
+    }
+    Ok(tasks)
+}
+
+/// Indentation, checked state and text of a `- [ ] text` line
+fn checklist_item(line: &str) -> Option<(usize, bool, &str)> {
+    let rest = line.trim_start();
+    let indent = line[..line.len() - rest.len()]
+        .chars()
+        .map(|c| if c == '\t' { TAB_WIDTH } else { 1 })
+        .sum();
+    let rest = rest.strip_prefix(['-', '*', '+'])?.strip_prefix(' ')?.trim_start();
+    let done = match rest.get(..3)? {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+    let text = rest[3..].trim();
+    (!text.is_empty()).then_some((indent, done, text))
+}
+
+fn find_project(projects: &[TaskItem], name: &str) -> Option<Uuid> {
+    let name = name.to_lowercase();
+    projects.iter()
+        .find(|t| t.is_project() && t.frontmatter.title.to_lowercase().contains(&name))
+        .map(|t| t.frontmatter.id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Due, Priority};
+
+    #[test]
+    fn test_from_markdown() {
+        let today = NaiveDate::from_ymd_opt(2025, 11, 25).unwrap();
+        let project = TaskItem::new_project("Website launch".to_string());
+        let markdown = "\
+# TODO
+
+- [ ] Plan the move #home due:2025-12-01
+  - [x] Book the van !high
+  - [ ] Pack
+\t- [ ] Kitchen
+- Not a checklist item
+* [ ] Write copy +launch @desk
+";
+        let tasks = from_markdown(markdown, std::slice::from_ref(&project), today).unwrap();
+        let titles: Vec<_> = tasks.iter().map(|t| t.frontmatter.title.as_str()).collect();
+        assert_eq!(titles, ["Plan the move", "Book the van", "Pack", "Kitchen", "Write copy"]);
+
+        let id = |idx: usize| Some(tasks[idx].frontmatter.id);
+        let parents: Vec<_> = tasks.iter().map(|t| t.frontmatter.parent_goal_id).collect();
+        assert_eq!(parents, [None, id(0), id(0), id(2), Some(project.frontmatter.id)]);
+
+        assert_eq!(tasks[0].frontmatter.tags, ["home"]);
+        assert_eq!(tasks[0].frontmatter.due_date, NaiveDate::from_ymd_opt(2025, 12, 1).map(Due::on));
+        assert_eq!(tasks[0].frontmatter.status, Status::Inbox);
+        assert_eq!(tasks[1].frontmatter.status, Status::Done);
+        assert!(tasks[1].frontmatter.completed_at.is_some());
+        assert_eq!(tasks[1].frontmatter.priority, Priority::P1);
+        assert_eq!(tasks[4].frontmatter.tags, ["@desk"]);
+
+        let err = from_markdown("- [ ] Ship +nowhere", &[project], today).unwrap_err();
+        assert_eq!(err.to_string(), "Line 1: no project matches +nowhere");
+    }
+}
@@ -0,0 +1,190 @@
+use crate::config::JiraConfig;
+use crate::runtime::block_on;
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+/// Environment variable holding the API token when the config has none
+const TOKEN_ENV: &str = "JIRA_API_TOKEN";
+/// How long a Jira request may take before it's given up on
+const TIMEOUT: Duration = Duration::from_secs(15);
+
+/// What the TUI and MCP show of a linked issue
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    pub key: String,
+    pub summary: String,
+    pub status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Transition {
+    id: String,
+    name: String,
+    to: TransitionTarget,
+}
+
+#[derive(Debug, Deserialize)]
+struct TransitionTarget {
+    name: String,
+    #[serde(rename = "statusCategory")]
+    category: Option<StatusCategory>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StatusCategory {
+    key: String,
+}
+
+/// Jira Cloud REST client, authenticated with an account email and API token
+pub struct JiraClient {
+    client: Client,
+    base_url: String,
+    email: String,
+    token: String,
+    done_transition: Option<String>,
+}
+
+impl JiraClient {
+    /// A client for the configured site. The token comes from the config, or
+    /// from `JIRA_API_TOKEN` so it can live in a keychain-backed environment.
+    pub fn new(config: &JiraConfig) -> Result<Self> {
+        let token = config.api_token.clone()
+            .or_else(|| std::env::var(TOKEN_ENV).ok())
+            .filter(|t| !t.trim().is_empty())
+            .with_context(|| format!("No Jira API token: set jira.api_token or {}", TOKEN_ENV))?;
+        let client = Client::builder().timeout(TIMEOUT).build().context("Failed to set up the Jira client")?;
+        Ok(Self {
+            client,
+            base_url: config.url.trim_end_matches('/').to_string(),
+            email: config.email.clone(),
+            token,
+            done_transition: config.done_transition.clone(),
+        })
+    }
+
+    async fn get(&self, path: &str) -> Result<Value> {
+        let response = self.client
+            .get(format!("{}/rest/api/2/{}", self.base_url, path))
+            .basic_auth(&self.email, Some(&self.token))
+            .send()
+            .await
+            .context("Jira request failed")?;
+        if !response.status().is_success() {
+            anyhow::bail!("Jira API error {}", response.status());
+        }
+        response.json().await.context("Failed to parse Jira response")
+    }
+
+    /// Summary and status of an issue
+    pub async fn issue(&self, key: &str) -> Result<Issue> {
+        let value = self.get(&format!("issue/{}?fields=summary,status", key)).await?;
+        parse_issue(&value).with_context(|| format!("Unexpected Jira response for {}", key))
+    }
+
+    /// Move an issue to done: through `done_transition` if configured, else the
+    /// first available transition into a done status. Returns the new status.
+    pub async fn complete(&self, key: &str) -> Result<String> {
+        let value = self.get(&format!("issue/{}/transitions", key)).await?;
+        let transitions: Vec<Transition> = serde_json::from_value(value["transitions"].clone())
+            .context("Failed to parse Jira transitions")?;
+        let transition = pick_done(&transitions, self.done_transition.as_deref())
+            .with_context(|| format!("{} has no transition to done", key))?;
+
+        let response = self.client
+            .post(format!("{}/rest/api/2/issue/{}/transitions", self.base_url, key))
+            .basic_auth(&self.email, Some(&self.token))
+            .json(&json!({ "transition": { "id": transition.id } }))
+            .send()
+            .await
+            .context("Jira request failed")?;
+        if !response.status().is_success() {
+            anyhow::bail!("Jira transition failed: {}", response.status());
+        }
+        Ok(transition.to.name.clone())
+    }
+}
+
+/// Fetch a linked issue from synchronous code
+pub fn fetch(config: &JiraConfig, key: &str) -> Result<Issue> {
+    let client = JiraClient::new(config)?;
+    block_on(client.issue(key)).context("Failed to start the async runtime")?
+}
+
+/// Transition a linked issue to done from synchronous code, if the config asks for it.
+/// Returns the new status when the issue was moved.
+pub fn complete(config: &JiraConfig, key: &str) -> Result<Option<String>> {
+    if !config.transition_on_done {
+        return Ok(None);
+    }
+    let client = JiraClient::new(config)?;
+    block_on(client.complete(key)).context("Failed to start the async runtime")?.map(Some)
+}
+
+/// `fetch` on its own thread, so a slow or unreachable Jira doesn't hold up the caller
+pub fn fetch_in_background(config: &JiraConfig, key: &str) -> Receiver<Result<Issue>> {
+    let (sender, receiver) = mpsc::channel();
+    let (config, key) = (config.clone(), key.to_string());
+    thread::spawn(move || {
+        let _ = sender.send(fetch(&config, &key));
+    });
+    receiver
+}
+
+/// `complete` on its own thread
+pub fn complete_in_background(config: &JiraConfig, key: &str) -> Receiver<Result<Option<String>>> {
+    let (sender, receiver) = mpsc::channel();
+    let (config, key) = (config.clone(), key.to_string());
+    thread::spawn(move || {
+        let _ = sender.send(complete(&config, &key));
+    });
+    receiver
+}
+
+fn parse_issue(value: &Value) -> Option<Issue> {
+    Some(Issue {
+        key: value["key"].as_str()?.to_string(),
+        summary: value["fields"]["summary"].as_str()?.to_string(),
+        status: value["fields"]["status"]["name"].as_str()?.to_string(),
+    })
+}
+
+fn pick_done<'a>(transitions: &'a [Transition], name: Option<&str>) -> Option<&'a Transition> {
+    match name {
+        Some(name) => transitions.iter().find(|t| t.name.eq_ignore_ascii_case(name) || t.to.name.eq_ignore_ascii_case(name)),
+        None => transitions.iter().find(|t| t.to.category.as_ref().is_some_and(|c| c.key == "done")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_issue() {
+        let value = json!({ "key": "OPS-142", "fields": { "summary": "Rotate certs", "status": { "name": "In Progress" } } });
+        assert_eq!(parse_issue(&value), Some(Issue {
+            key: "OPS-142".to_string(),
+            summary: "Rotate certs".to_string(),
+            status: "In Progress".to_string(),
+        }));
+        assert_eq!(parse_issue(&json!({ "errorMessages": ["Issue does not exist"] })), None);
+    }
+
+    #[test]
+    fn test_pick_done() {
+        let transitions: Vec<Transition> = serde_json::from_value(json!([
+            { "id": "11", "name": "Start", "to": { "name": "In Progress", "statusCategory": { "key": "indeterminate" } } },
+            { "id": "31", "name": "Resolve", "to": { "name": "Done", "statusCategory": { "key": "done" } } },
+            { "id": "41", "name": "Won't do", "to": { "name": "Closed", "statusCategory": { "key": "done" } } },
+        ])).unwrap();
+
+        assert_eq!(pick_done(&transitions, None).map(|t| t.id.as_str()), Some("31"));
+        assert_eq!(pick_done(&transitions, Some("closed")).map(|t| t.id.as_str()), Some("41"));
+        assert!(pick_done(&transitions, Some("Reopen")).is_none());
+    }
+}
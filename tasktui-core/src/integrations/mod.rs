@@ -0,0 +1,3 @@
+pub mod jira;
+pub mod slack;
+pub mod transcribe;
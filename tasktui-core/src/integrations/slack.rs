@@ -0,0 +1,200 @@
+use crate::models::{Status, TaskItem};
+use crate::runtime::block_on;
+use anyhow::{Context, Result};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Most tasks listed per digest section
+const SECTION_LIMIT: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Message {
+    /// Today's plan: overdue, due today and active tasks
+    Digest,
+    /// Tasks coming due within `due_soon_hours` that weren't announced yet
+    DueSoon,
+}
+
+/// Tasks already announced as due soon, with the deadline they were announced
+/// for, kept in `.tasktui-notify.json`. A rescheduled task is announced again.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Alerted {
+    pub tasks: BTreeMap<Uuid, String>,
+}
+
+impl Alerted {
+    pub const FILE_NAME: &'static str = ".tasktui-notify.json";
+
+    pub fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join(Self::FILE_NAME)
+    }
+
+    pub fn load(data_dir: &Path) -> Self {
+        fs::read_to_string(Self::path(data_dir))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, data_dir: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(Self::path(data_dir), content).context("Failed to write notification state")
+    }
+}
+
+/// A task as a digest line: short code first, so it can be pasted into `tasktui show`
+fn task_line(task: &TaskItem, today: NaiveDate) -> String {
+    let due = task.frontmatter.due_date
+        .map(|due| format!(" _(due {})_", due.label(today)))
+        .unwrap_or_default();
+    format!("• `{}` {} {}{}", task.code(), task.frontmatter.priority.emoji(), escape(&task.frontmatter.title), due)
+}
+
+/// Slack mrkdwn treats `&`, `<` and `>` as control characters
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// The morning digest: overdue tasks, tasks due today and the rest of what's active
+pub fn digest(tasks: &[TaskItem], today: NaiveDate, now: NaiveDateTime) -> String {
+    let mut open: Vec<&TaskItem> = tasks.iter().filter(|t| t.is_open()).collect();
+    open.sort_by(|a, b| {
+        a.frontmatter.priority.rank().cmp(&b.frontmatter.priority.rank())
+            .then_with(|| a.frontmatter.due_date.map(|d| d.deadline()).cmp(&b.frontmatter.due_date.map(|d| d.deadline())))
+    });
+
+    let is_due_today = |t: &TaskItem| t.frontmatter.due_date.is_some_and(|due| due.local_date() == today);
+    let overdue: Vec<_> = open.iter().filter(|t| t.is_overdue(now) && !is_due_today(t)).collect();
+    let due_today: Vec<_> = open.iter().filter(|t| is_due_today(t)).collect();
+    let active: Vec<_> = open.iter()
+        .filter(|t| t.frontmatter.status == Status::Active && !is_due_today(t) && !t.is_overdue(now))
+        .collect();
+
+    let mut text = format!("*Today's plan, {}*", today.format("%A %Y-%m-%d"));
+    for (heading, section) in [("Overdue", &overdue), ("Due today", &due_today), ("Active", &active)] {
+        if section.is_empty() {
+            continue;
+        }
+        text.push_str(&format!("\n\n*{}* ({})", heading, section.len()));
+        for task in section.iter().take(SECTION_LIMIT) {
+            text.push('\n');
+            text.push_str(&task_line(task, today));
+        }
+        if section.len() > SECTION_LIMIT {
+            text.push_str(&format!("\n…and {} more", section.len() - SECTION_LIMIT));
+        }
+    }
+    if overdue.is_empty() && due_today.is_empty() && active.is_empty() {
+        text.push_str("\n\nNothing due and nothing active.");
+    }
+    text
+}
+
+/// An alert for open tasks whose deadline falls within `hours` from `now`, skipping
+/// those already announced for the same deadline. Records what it announces.
+pub fn due_soon(tasks: &[TaskItem], today: NaiveDate, now: NaiveDateTime, hours: u32, alerted: &mut Alerted) -> Option<String> {
+    let until = now + Duration::hours(hours as i64);
+    let soon: Vec<&TaskItem> = tasks.iter()
+        .filter(|t| t.is_open())
+        .filter(|t| t.frontmatter.due_date.is_some_and(|due| (now..=until).contains(&due.deadline())))
+        .collect();
+
+    // Forget tasks that are done, rescheduled or past, so they can alert again
+    alerted.tasks.retain(|id, due| {
+        soon.iter().any(|t| t.frontmatter.id == *id && t.frontmatter.due_date.map(|d| d.to_string()).as_ref() == Some(due))
+    });
+    let new: Vec<&TaskItem> = soon.into_iter()
+        .filter(|t| !alerted.tasks.contains_key(&t.frontmatter.id))
+        .collect();
+    if new.is_empty() {
+        return None;
+    }
+
+    let mut text = format!("⏰ *Due within {} hours*", hours);
+    for task in new {
+        text.push('\n');
+        text.push_str(&task_line(task, today));
+        let due = task.frontmatter.due_date.map(|d| d.to_string()).unwrap_or_default();
+        alerted.tasks.insert(task.frontmatter.id, due);
+    }
+    Some(text)
+}
+
+/// Post a message to an incoming webhook
+pub fn post(webhook_url: &str, text: &str) -> Result<()> {
+    let send = async {
+        let response = reqwest::Client::new()
+            .post(webhook_url)
+            .json(&json!({ "text": text }))
+            .send()
+            .await
+            .context("Slack request failed")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Slack webhook error {}: {}", status, body);
+        }
+        Ok(())
+    };
+    block_on(send).context("Failed to start the async runtime")?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Due, ItemType, Priority};
+
+    fn task(title: &str, status: Status, due: Option<&str>) -> TaskItem {
+        let mut task = TaskItem::new(title.to_string(), ItemType::Task);
+        task.frontmatter.status = status;
+        task.frontmatter.due_date = due.and_then(Due::parse);
+        task
+    }
+
+    #[test]
+    fn test_digest_sections() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 4).unwrap();
+        let now = today.and_hms_opt(8, 0, 0).unwrap();
+        let mut urgent = task("Ship <beta>", Status::Next, Some("2025-03-04T15:00"));
+        urgent.frontmatter.priority = Priority::P1;
+        let tasks = [
+            task("Pay rent", Status::Next, Some("2025-03-01")),
+            urgent,
+            task("Write report", Status::Active, None),
+            task("Old", Status::Done, Some("2025-03-04")),
+        ];
+
+        let text = digest(&tasks, today, now);
+        let overdue = text.find("*Overdue* (1)").unwrap();
+        let due_today = text.find("*Due today* (1)").unwrap();
+        let active = text.find("*Active* (1)").unwrap();
+        assert!(overdue < due_today && due_today < active);
+        assert!(text.contains(&format!("`{}` 🔴 Ship &lt;beta&gt; _(due today 15:00)_", tasks[1].code())));
+        assert!(!text.contains("Old"));
+    }
+
+    #[test]
+    fn test_due_soon_alerts_once() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 4).unwrap();
+        let now = today.and_hms_opt(13, 30, 0).unwrap();
+        let mut tasks = vec![
+            task("Standup", Status::Next, Some("2025-03-04T14:00")),
+            task("Later", Status::Next, Some("2025-03-04T18:00")),
+        ];
+        let mut alerted = Alerted::default();
+
+        let text = due_soon(&tasks, today, now, 2, &mut alerted).unwrap();
+        assert!(text.contains("Standup") && !text.contains("Later"));
+        assert_eq!(due_soon(&tasks, today, now, 2, &mut alerted), None);
+
+        // Rescheduling within the window announces it again
+        tasks[0].frontmatter.due_date = Due::parse("2025-03-04T15:00");
+        assert!(due_soon(&tasks, today, now, 2, &mut alerted).is_some());
+    }
+}
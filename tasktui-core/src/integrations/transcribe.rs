@@ -0,0 +1,91 @@
+use crate::config::TranscriptionConfig;
+use crate::runtime::block_on;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// File types the transcription API accepts
+pub const AUDIO_EXTENSIONS: &[&str] = &["flac", "m4a", "mp3", "mp4", "mpeg", "mpga", "oga", "ogg", "wav", "webm"];
+
+/// Largest upload the OpenAI endpoint takes
+pub const MAX_FILE_BYTES: u64 = 25 * 1024 * 1024;
+
+const BOUNDARY: &str = "tasktui-voice-memo-boundary";
+
+#[derive(Deserialize)]
+struct Transcription {
+    text: String,
+}
+
+/// Whether `path` has one of the `AUDIO_EXTENSIONS`, ignoring case
+pub fn is_audio(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| AUDIO_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// A `multipart/form-data` body with the model name and the audio file
+fn multipart_body(model: &str, file_name: &str, audio: &[u8]) -> Vec<u8> {
+    let mut body = Vec::with_capacity(audio.len() + 512);
+    body.extend_from_slice(format!(
+        "--{b}\r\nContent-Disposition: form-data; name=\"model\"\r\n\r\n{model}\r\n\
+         --{b}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{name}\"\r\n\
+         Content-Type: application/octet-stream\r\n\r\n",
+        b = BOUNDARY,
+        model = model,
+        name = file_name.replace('"', "'"),
+    ).as_bytes());
+    body.extend_from_slice(audio);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", BOUNDARY).as_bytes());
+    body
+}
+
+/// The text spoken in the audio file at `path`
+pub fn transcribe(config: &TranscriptionConfig, api_key: &str, path: &Path) -> Result<String> {
+    let size = fs::metadata(path).with_context(|| format!("Failed to read {}", path.display()))?.len();
+    if size > MAX_FILE_BYTES {
+        anyhow::bail!("{} is {} MB; the limit is 25 MB", path.display(), size / (1024 * 1024));
+    }
+    let audio = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("memo");
+    let body = multipart_body(&config.model, file_name, &audio);
+
+    let send = async {
+        let response = reqwest::Client::new()
+            .post(&config.url)
+            .bearer_auth(api_key)
+            .header("Content-Type", format!("multipart/form-data; boundary={}", BOUNDARY))
+            .body(body)
+            .send()
+            .await
+            .context("Transcription request failed")?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Transcription API error {}: {}", status, body);
+        }
+        let transcription: Transcription = response.json().await.context("Unexpected transcription response")?;
+        Ok(transcription.text.trim().to_string())
+    };
+    block_on(send).context("Failed to start the async runtime")?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multipart_body() {
+        assert!(is_audio(Path::new("memos/Groceries.M4A")));
+        assert!(!is_audio(Path::new("memos/notes.txt")));
+        assert!(!is_audio(Path::new("memos/m4a")));
+
+        let body = multipart_body("whisper-1", "memo \"1\".m4a", b"RIFF");
+        let text = String::from_utf8(body).unwrap();
+        assert!(text.starts_with(&format!("--{}\r\n", BOUNDARY)));
+        assert!(text.contains("name=\"model\"\r\n\r\nwhisper-1\r\n"));
+        assert!(text.contains("filename=\"memo '1'.m4a\""));
+        assert!(text.ends_with(&format!("RIFF\r\n--{}--\r\n", BOUNDARY)));
+    }
+}
@@ -0,0 +1,107 @@
+use crate::models::{Status, TaskItem};
+use crate::retro::TaskRef;
+use chrono::{Local, NaiveDate};
+use std::path::{Path, PathBuf};
+
+/// One day at shutdown: what got done and what's still active
+#[derive(Debug)]
+pub struct DayReview {
+    pub date: NaiveDate,
+    /// Tasks completed that day, and habits checked off
+    pub completed: Vec<TaskRef>,
+    /// Active tasks carried over to the next day
+    pub carried_over: Vec<TaskRef>,
+}
+
+/// Where a day's journal entry is saved: `journal/YYYY-MM-DD.md` in the data directory
+pub fn journal_path(data_dir: &Path, date: NaiveDate) -> PathBuf {
+    data_dir.join("journal").join(format!("{}.md", date.format("%Y-%m-%d")))
+}
+
+/// Finished on `date`: a task completed that local day, or a habit checked off
+pub fn completed_on(task: &TaskItem, date: NaiveDate) -> bool {
+    let fm = &task.frontmatter;
+    fm.completed_at.is_some_and(|at| at.with_timezone(&Local).date_naive() == date)
+        || (fm.habit.is_some() && fm.habit_log.contains(&date))
+}
+
+/// Still active at the end of the day. Habits aren't carried over; they
+/// come back by themselves.
+pub fn carried_over(task: &TaskItem) -> bool {
+    task.frontmatter.status == Status::Active && task.frontmatter.habit.is_none()
+}
+
+impl DayReview {
+    pub fn new(tasks: &[TaskItem], date: NaiveDate) -> Self {
+        let refs = |keep: &dyn Fn(&TaskItem) -> bool| {
+            tasks.iter()
+                .filter(|t| !t.is_project() && keep(t))
+                .map(|t| TaskRef { id: t.frontmatter.id, title: t.frontmatter.title.clone() })
+                .collect()
+        };
+        Self {
+            date,
+            completed: refs(&|t| completed_on(t, date)),
+            carried_over: refs(&carried_over),
+        }
+    }
+
+    /// The facts as markdown, written under the journal entry and sent to the LLM
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        for (heading, tasks) in [("Completed", &self.completed), ("Carried over", &self.carried_over)] {
+            md.push_str(&format!("### {} ({})\n\n", heading, tasks.len()));
+            for task in tasks.iter() {
+                md.push_str(&format!("- {}\n", task.title));
+            }
+            if tasks.is_empty() {
+                md.push_str("None.\n");
+            }
+            md.push('\n');
+        }
+        md.trim_end().to_string() + "\n"
+    }
+}
+
+/// One paragraph
+pub const JOURNAL_MAX_TOKENS: u32 = 250;
+
+/// System prompt for turning a day's facts into a journal entry
+pub const JOURNAL_PROMPT: &str = "You write end-of-day journal entries for a personal task manager. \
+Given what was completed and what carries over, write one short first-person paragraph: what got \
+done, what's left and where to pick up tomorrow. Use only the facts given; don't invent tasks. \
+No heading, no lists.";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Habit, ItemType};
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_day_review() {
+        let date = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+        let noon = Local.from_local_datetime(&date.and_hms_opt(12, 0, 0).unwrap()).unwrap().to_utc();
+
+        let mut shipped = TaskItem::new("Ship release".to_string(), ItemType::Task);
+        shipped.set_status(Status::Done);
+        shipped.frontmatter.completed_at = Some(noon);
+        let mut earlier = shipped.clone();
+        earlier.frontmatter.title = "Earlier win".to_string();
+        earlier.frontmatter.completed_at = Some(noon - chrono::Duration::days(1));
+        let mut stretch = TaskItem::new("Stretch".to_string(), ItemType::Task);
+        stretch.frontmatter.habit = Some(Habit::Daily);
+        stretch.check_off(date);
+        let open = TaskItem::new("Write tests".to_string(), ItemType::Task);
+
+        let review = DayReview::new(&[shipped, earlier, stretch, open], date);
+        let titles = |refs: &[TaskRef]| refs.iter().map(|t| t.title.clone()).collect::<Vec<_>>();
+        assert_eq!(titles(&review.completed), ["Ship release", "Stretch"]);
+        assert_eq!(titles(&review.carried_over), ["Write tests"]);
+        assert_eq!(
+            review.to_markdown(),
+            "### Completed (2)\n\n- Ship release\n- Stretch\n\n### Carried over (1)\n\n- Write tests\n"
+        );
+        assert!(journal_path(Path::new("/vault"), date).ends_with("journal/2025-03-12.md"));
+    }
+}
@@ -0,0 +1,102 @@
+//! The task store behind `tasktui`: markdown task files with YAML frontmatter,
+//! the vault config, LLM enrichment and the MCP server. The `tasktui` binary
+//! is a CLI/TUI on top of this crate, and other tools can build on the same
+//! vault through it.
+//!
+//! ```no_run
+//! use tasktui_core::models::{ItemType, Status, TaskFilter, TaskItem};
+//! use tasktui_core::storage::Storage;
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let storage = Storage::new("./tasks".into())?;
+//!
+//! let mut task = TaskItem::new("Renew passport".to_string(), ItemType::Task);
+//! task.frontmatter.tags.push("personal".to_string());
+//! storage.write_task(&mut task)?;
+//!
+//! let filter = TaskFilter { status: Some(Status::Active), ..TaskFilter::default() };
+//! for task in storage.list_tasks(&filter)? {
+//!     println!("{} {}", task.code(), task.frontmatter.title);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Writes go through [`storage::Storage`], which validates frontmatter, stamps
+//! `updated_at`, refuses to overwrite edits made since a task was read and
+//! commits to git when the vault is a repository.
+
+/// Status change history parsed from and appended to task bodies
+pub mod activity;
+/// Compressed, optionally encrypted snapshots of the data directory
+pub mod backup;
+/// The morning briefing: overdue, due-soon and goal-aligned tasks
+pub mod briefing;
+/// Working days and week boundaries
+pub mod calendar;
+/// Two-way CalDAV sync of tasks as VTODOs
+pub mod caldav;
+/// The vault config (`.tasktui-config.yaml`)
+pub mod config;
+/// Per-workstream summaries: status counts, overdue and top tasks, goals, throughput
+pub mod dashboard;
+/// Who is waiting on what: delegated tasks grouped by person
+pub mod delegation;
+/// Task vectors from an embeddings API, for semantic search
+pub mod embeddings;
+/// Markdown/HTML documents for single tasks and projects, and project status reports
+pub mod export;
+/// The filter expression language and saved filters
+pub mod filter;
+/// Burndown and cumulative flow series from task status logs
+pub mod flow;
+/// Git commits and sync for the vault
+pub mod git;
+/// Habit check-offs and streaks
+pub mod habits;
+/// A task's field changes and notes, commit by commit, from git
+pub mod history;
+/// External commands run when tasks are created, completed or synced
+pub mod hooks;
+/// `.tasktuiignore`: files kept on this machine and out of git sync
+pub mod ignore;
+/// Tasks from markdown checklists
+pub mod import;
+/// End-of-day reviews and journal entries
+pub mod journal;
+/// Jira issue links, Slack reminders and voice memo transcription
+pub mod integrations;
+/// Backlinks between tasks
+pub mod links;
+/// LLM enrichment with a response cache, usage tracking and local rules
+pub mod llm;
+/// Model Context Protocol server over stdio
+pub mod mcp;
+/// Tasks, their frontmatter and the clock everything is dated by
+pub mod models;
+/// WASM plugins that register MCP tools, loaded from `plugins/`
+#[cfg(feature = "plugins")]
+pub mod plugins;
+/// LLM-proposed priority changes for the open backlog
+pub mod prioritize;
+/// Inline tokens in a quick-add line (`#tag !high due:fri +project`)
+pub mod quickadd;
+/// Similar open tasks by shared tags and words
+pub mod related;
+/// Weekly retrospectives
+pub mod retro;
+/// Ranked text search over titles, tags and bodies
+pub mod search;
+/// A tantivy full-text index kept in `.tasktui-index/`
+#[cfg(feature = "search-index")]
+pub mod search_index;
+/// Blocking on async work from synchronous code
+pub mod runtime;
+/// Synthetic sample vaults
+pub mod seed;
+/// Reading, writing and caching task files
+pub mod storage;
+/// Tag renames across tasks
+pub mod tags;
+/// Frontmatter checks for dates, tags and references
+pub mod validate;
@@ -0,0 +1,102 @@
+use crate::models::TaskItem;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// `[[target]]` references in a body, in order of appearance
+pub fn references(body: &str) -> Vec<&str> {
+    let mut refs = Vec::new();
+    let mut rest = body;
+
+    while let Some(start) = rest.find("[[") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("]]") else {
+            break;
+        };
+        let target = after[..end].trim();
+        if !target.is_empty() && !target.contains('\n') {
+            refs.push(target);
+        }
+        rest = &after[end + 2..];
+    }
+
+    refs
+}
+
+/// Resolve a link target: a full id, a title (case-insensitive), or a short code
+pub fn resolve(target: &str, tasks: &[TaskItem]) -> Option<Uuid> {
+    if let Ok(id) = Uuid::parse_str(target) {
+        return tasks.iter().find(|t| t.frontmatter.id == id).map(|t| t.frontmatter.id);
+    }
+    tasks.iter()
+        .find(|t| t.frontmatter.title.eq_ignore_ascii_case(target))
+        .or_else(|| tasks.iter().find(|t| t.code().eq_ignore_ascii_case(target.trim_start_matches('#'))))
+        .map(|t| t.frontmatter.id)
+}
+
+/// Resolved links between tasks, in both directions
+#[derive(Debug, Default)]
+pub struct LinkIndex {
+    outgoing: HashMap<Uuid, Vec<Uuid>>,
+    incoming: HashMap<Uuid, Vec<Uuid>>,
+}
+
+impl LinkIndex {
+    pub fn build(tasks: &[TaskItem]) -> Self {
+        let mut index = Self::default();
+
+        for task in tasks {
+            let from = task.frontmatter.id;
+            for target in references(&task.body) {
+                let Some(to) = resolve(target, tasks) else {
+                    continue;
+                };
+                let outgoing = index.outgoing.entry(from).or_default();
+                if to == from || outgoing.contains(&to) {
+                    continue;
+                }
+                outgoing.push(to);
+                index.incoming.entry(to).or_default().push(from);
+            }
+        }
+
+        index
+    }
+
+    /// Tasks this task links to
+    pub fn outgoing(&self, id: Uuid) -> &[Uuid] {
+        self.outgoing.get(&id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Tasks linking to this task ("referenced by")
+    pub fn incoming(&self, id: Uuid) -> &[Uuid] {
+        self.incoming.get(&id).map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ItemType;
+
+    #[test]
+    fn test_references() {
+        let body = "See [[Launch plan]] and [[ 1234 ]].\n[[]] and [[unclosed";
+        assert_eq!(references(body), vec!["Launch plan", "1234"]);
+    }
+
+    #[test]
+    fn test_link_index() {
+        let plan = TaskItem::new("Launch plan".to_string(), ItemType::Project);
+        let mut a = TaskItem::new("Write copy".to_string(), ItemType::Task);
+        a.body = "Part of [[launch PLAN]]".to_string();
+        let mut b = TaskItem::new("Review copy".to_string(), ItemType::Task);
+        b.body = format!("After [[{}]], see [[Launch plan]] and [[missing]]", a.frontmatter.id);
+
+        let tasks = vec![plan.clone(), a.clone(), b.clone()];
+        let index = LinkIndex::build(&tasks);
+
+        assert_eq!(index.outgoing(b.frontmatter.id), &[a.frontmatter.id, plan.frontmatter.id]);
+        assert_eq!(index.incoming(plan.frontmatter.id), &[a.frontmatter.id, b.frontmatter.id]);
+        assert!(index.incoming(b.frontmatter.id).is_empty());
+    }
+}
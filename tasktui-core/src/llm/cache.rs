@@ -0,0 +1,157 @@
+use super::prompt::PROMPT_VERSION;
+use super::EnrichedTask;
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Entries kept before the least recently used are dropped
+const CAPACITY: usize = 500;
+
+/// Enrichment results by input, kept on disk between runs
+pub struct Cache {
+    path: PathBuf,
+    capacity: usize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    /// Increments on every use; entries remember when they were last used
+    clock: u64,
+    entries: HashMap<String, Entry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    task: EnrichedTask,
+    /// Day the result was produced, since relative dates ("tomorrow") depend on it
+    created: NaiveDate,
+    last_used: u64,
+}
+
+/// The cache file: `$XDG_CACHE_HOME/tasktui/llm-cache.json`, falling back to
+/// `~/.cache/tasktui/llm-cache.json`. It lives outside the data directory so
+/// git sync doesn't pick it up.
+pub fn default_path() -> PathBuf {
+    let cache_home = std::env::var_os("XDG_CACHE_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(|| PathBuf::from("."));
+    cache_home.join("tasktui").join("llm-cache.json")
+}
+
+/// Remove the cache file, returning how many entries it held
+pub fn clear(path: &Path) -> Result<usize> {
+    if !path.exists() {
+        return Ok(0);
+    }
+    let count = read(path).entries.len();
+    fs::remove_file(path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    Ok(count)
+}
+
+impl Cache {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, capacity: CAPACITY }
+    }
+
+    /// A stored result for this input. Results carrying a due date are only reused
+    /// on the day they were made.
+    pub fn get(&self, raw_input: &str, goals_context: Option<&str>, today: NaiveDate) -> Option<EnrichedTask> {
+        let mut file = read(&self.path);
+        file.clock += 1;
+        let clock = file.clock;
+        let entry = file.entries.get_mut(&key(raw_input, goals_context))?;
+        if entry.task.due_date.is_some() && entry.created != today {
+            return None;
+        }
+        entry.last_used = clock;
+        let task = entry.task.clone();
+        self.write(&file);
+        Some(task)
+    }
+
+    pub fn put(&self, raw_input: &str, goals_context: Option<&str>, today: NaiveDate, task: &EnrichedTask) {
+        let mut file = read(&self.path);
+        file.clock += 1;
+        let entry = Entry { task: task.clone(), created: today, last_used: file.clock };
+        file.entries.insert(key(raw_input, goals_context), entry);
+
+        while file.entries.len() > self.capacity {
+            let oldest = file.entries.iter().min_by_key(|(_, e)| e.last_used).map(|(k, _)| k.clone());
+            match oldest {
+                Some(oldest) => file.entries.remove(&oldest),
+                None => break,
+            };
+        }
+        self.write(&file);
+    }
+
+    /// Cache failures only cost an API call, so they're logged rather than returned
+    fn write(&self, file: &CacheFile) {
+        let result = self.path.parent().map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&self.path, serde_json::to_string(file).unwrap_or_default()));
+        if let Err(e) = result {
+            tracing::warn!(error = %e, path = %self.path.display(), "Failed to write LLM cache");
+        }
+    }
+}
+
+fn read(path: &Path) -> CacheFile {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Prompt version, a hash of the goals context (it changes the answer), and the
+/// input lowercased with whitespace collapsed
+fn key(raw_input: &str, goals_context: Option<&str>) -> String {
+    let input = raw_input.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    format!("v{}:{:016x}:{}", PROMPT_VERSION, fnv1a(goals_context.unwrap_or("")), input)
+}
+
+/// FNV-1a, stable across builds unlike `DefaultHasher`
+pub(crate) fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_cache_hits_and_eviction() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("cache.json");
+        let cache = Cache { path: path.clone(), capacity: 2 };
+        let day = NaiveDate::from_ymd_opt(2025, 11, 25).unwrap();
+        let next_day = day.succ_opt().unwrap();
+
+        let report = EnrichedTask::simple("Write weekly report".to_string());
+        cache.put("weekly report", None, day, &report);
+        assert_eq!(cache.get("  Weekly   REPORT ", None, next_day).map(|t| t.title), Some(report.title.clone()));
+        assert!(cache.get("weekly report", Some("Goals: ship v2"), day).is_none());
+
+        // Relative dates go stale overnight
+        let mut call = EnrichedTask::simple("Call Mom".to_string());
+        call.due_date = Some("2025-11-26".to_string());
+        cache.put("call mom tomorrow", None, day, &call);
+        assert!(cache.get("call mom tomorrow", None, day).is_some());
+        assert!(cache.get("call mom tomorrow", None, next_day).is_none());
+
+        // Using "weekly report" leaves "call mom tomorrow" least recently used
+        cache.get("weekly report", None, day);
+        cache.put("buy milk", None, day, &EnrichedTask::simple("Buy milk".to_string()));
+        assert!(cache.get("call mom tomorrow", None, day).is_none());
+        assert!(cache.get("weekly report", None, day).is_some());
+
+        assert_eq!(clear(&path).unwrap(), 2);
+        assert!(!path.exists());
+        assert_eq!(clear(&path).unwrap(), 0);
+    }
+}
@@ -0,0 +1,218 @@
+use super::usage::{Price, Usage};
+use crate::config::{LlmProvider, ProviderKind};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+const OPENAI_API_URL: &str = "https://api.openai.com/v1";
+const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1";
+const OLLAMA_API_URL: &str = "http://localhost:11434/v1";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+#[derive(Debug, Serialize)]
+struct ChatRequest {
+    model: String,
+    messages: Vec<Message>,
+    temperature: f32,
+    max_tokens: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct Message {
+    role: String,
+    content: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatResponse {
+    choices: Vec<Choice>,
+    #[serde(default)]
+    usage: Usage,
+}
+
+#[derive(Debug, Deserialize)]
+struct Choice {
+    message: ResponseMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseMessage {
+    content: String,
+}
+
+/// Anthropic's Messages API takes the system prompt separately
+#[derive(Debug, Serialize)]
+struct MessagesRequest {
+    model: String,
+    system: String,
+    messages: Vec<Message>,
+    temperature: f32,
+    max_tokens: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessagesResponse {
+    content: Vec<ContentBlock>,
+    #[serde(default)]
+    usage: MessagesUsage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContentBlock {
+    #[serde(default)]
+    text: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct MessagesUsage {
+    input_tokens: u64,
+    output_tokens: u64,
+}
+
+/// Completions from one provider of the chain
+pub struct LlmClient {
+    client: Client,
+    kind: ProviderKind,
+    api_key: Option<String>,
+    model: String,
+    base_url: String,
+    price: Price,
+}
+
+impl LlmClient {
+    /// OpenAI with its default model
+    pub fn new(api_key: String) -> Self {
+        Self::from_provider(&LlmProvider::new(ProviderKind::Openai, Some(api_key)))
+    }
+
+    pub fn from_provider(provider: &LlmProvider) -> Self {
+        let (model, base_url) = match provider.provider {
+            ProviderKind::Openai => ("gpt-4o-mini", OPENAI_API_URL),
+            ProviderKind::Anthropic => ("claude-3-5-haiku-latest", ANTHROPIC_API_URL),
+            ProviderKind::Ollama => ("llama3.2", OLLAMA_API_URL),
+        };
+        let default_price = Price::default_for(provider.provider);
+        let price = Price {
+            input_usd_per_mtok: provider.input_usd_per_mtok.unwrap_or(default_price.input_usd_per_mtok),
+            output_usd_per_mtok: provider.output_usd_per_mtok.unwrap_or(default_price.output_usd_per_mtok),
+        };
+        Self {
+            client: Client::new(),
+            kind: provider.provider,
+            api_key: provider.api_key.clone(),
+            model: provider.model.clone().unwrap_or_else(|| model.to_string()),
+            base_url: provider.url.clone().unwrap_or_else(|| base_url.to_string()).trim_end_matches('/').to_string(),
+            price,
+        }
+    }
+
+    /// What its tokens cost
+    pub fn price(&self) -> Price {
+        self.price
+    }
+
+    /// Provider and model, e.g. `anthropic (claude-3-5-haiku-latest)`
+    pub fn name(&self) -> String {
+        format!("{} ({})", self.kind.as_str(), self.model)
+    }
+
+    /// Runs on this machine, so its tokens cost nothing
+    pub fn is_local(&self) -> bool {
+        self.kind == ProviderKind::Ollama
+    }
+
+    /// Send a chat completion, returning the reply and the tokens it used
+    pub async fn complete(&self, system_prompt: &str, user_prompt: &str, max_tokens: u32) -> Result<(String, Usage), String> {
+        match self.kind {
+            ProviderKind::Anthropic => self.complete_anthropic(system_prompt, user_prompt, max_tokens).await,
+            ProviderKind::Openai | ProviderKind::Ollama => self.complete_openai(system_prompt, user_prompt, max_tokens).await,
+        }
+    }
+
+    async fn complete_openai(&self, system_prompt: &str, user_prompt: &str, max_tokens: u32) -> Result<(String, Usage), String> {
+        let request = ChatRequest {
+            model: self.model.clone(),
+            messages: vec![
+                Message {
+                    role: "system".to_string(),
+                    content: system_prompt.to_string(),
+                },
+                Message {
+                    role: "user".to_string(),
+                    content: user_prompt.to_string(),
+                },
+            ],
+            temperature: 0.1,
+            max_tokens,
+        };
+
+        let mut builder = self.client
+            .post(format!("{}/chat/completions", self.base_url))
+            .header("Content-Type", "application/json");
+        if let Some(key) = &self.api_key {
+            builder = builder.header("Authorization", format!("Bearer {}", key));
+        }
+        let response = builder
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("API error {}: {}", status, body));
+        }
+
+        let chat_response: ChatResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+        chat_response
+            .choices
+            .first()
+            .map(|c| (c.message.content.clone(), chat_response.usage))
+            .ok_or_else(|| "No response from API".to_string())
+    }
+
+    async fn complete_anthropic(&self, system_prompt: &str, user_prompt: &str, max_tokens: u32) -> Result<(String, Usage), String> {
+        let request = MessagesRequest {
+            model: self.model.clone(),
+            system: system_prompt.to_string(),
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: user_prompt.to_string(),
+            }],
+            temperature: 0.1,
+            max_tokens,
+        };
+
+        let response = self.client
+            .post(format!("{}/messages", self.base_url))
+            .header("x-api-key", self.api_key.as_deref().unwrap_or_default())
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("API error {}: {}", status, body));
+        }
+
+        let reply: MessagesResponse = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+        let usage = Usage { prompt_tokens: reply.usage.input_tokens, completion_tokens: reply.usage.output_tokens };
+
+        reply.content
+            .into_iter()
+            .map(|block| block.text)
+            .find(|text| !text.is_empty())
+            .map(|text| (text, usage))
+            .ok_or_else(|| "No response from API".to_string())
+    }
+}
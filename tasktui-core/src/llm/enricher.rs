@@ -0,0 +1,307 @@
+use super::cache::{self, Cache};
+use super::client::LlmClient;
+use super::queue::{self, RequestQueue};
+use super::prompt::{build_system_prompt, build_user_prompt};
+use super::rules;
+use super::usage::{Meter, MonthlyUsage};
+use super::EnrichedTask;
+use crate::config::{AppConfig, LlmLimits};
+use crate::models;
+use crate::runtime::block_on;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Enrichment replies are a small JSON object
+const ENRICH_MAX_TOKENS: u32 = 256;
+
+pub struct TaskEnricher {
+    /// Providers in the order they're tried
+    clients: Vec<LlmClient>,
+    /// The provider that answered the most recent call
+    active_provider: Mutex<Option<String>>,
+    /// Token and cost accounting; without one calls are neither counted nor capped
+    meter: Option<Meter>,
+    /// Why the most recent call fell back to the rules, when the user should know
+    last_error: Mutex<Option<String>>,
+    /// Words the offline rules turn into tags
+    known_tags: Vec<String>,
+    /// Results of earlier calls, so repeated inputs skip the API
+    cache: Option<Cache>,
+    /// Keeps calls within `llm_limits`
+    queue: RequestQueue,
+}
+
+impl TaskEnricher {
+    /// Create a new enricher with optional API key
+    pub fn new(api_key: Option<String>) -> Self {
+        Self {
+            clients: api_key.map(LlmClient::new).into_iter().collect(),
+            active_provider: Mutex::new(None),
+            meter: None,
+            last_error: Mutex::new(None),
+            known_tags: Vec::new(),
+            cache: None,
+            queue: RequestQueue::new(&LlmLimits::default()),
+        }
+    }
+
+    /// An enricher using the configured provider chain, metering usage in `data_dir`
+    /// against `llm_monthly_budget` and caching results when `llm_cache` is on.
+    /// Workstream names and goal areas are the offline rules' known tags.
+    pub fn from_config(config: &AppConfig, data_dir: &Path) -> Self {
+        let mut known_tags: Vec<String> = config.workstreams.iter()
+            .map(|ws| &ws.name)
+            .chain(config.goals.iter().map(|g| &g.area))
+            .map(|name| name.to_lowercase())
+            .collect();
+        known_tags.sort();
+        known_tags.dedup();
+        Self {
+            meter: Some(Meter::new(data_dir.to_path_buf(), config.llm_monthly_budget)),
+            known_tags,
+            cache: config.llm_cache.then(|| Cache::new(cache::default_path())),
+            clients: config.provider_chain().iter().map(LlmClient::from_provider).collect(),
+            queue: RequestQueue::new(&config.llm_limits),
+            ..Self::new(None)
+        }
+    }
+
+    /// Keyword-rule enrichment, used without an API key or when the call fails
+    fn fallback(&self, raw_input: &str) -> EnrichedTask {
+        rules::enrich(raw_input, models::today(), &self.known_tags)
+    }
+
+    /// Only the offline rules, skipping the LLM even when a key is configured
+    pub fn enrich_offline(&self, raw_input: &str) -> EnrichedTask {
+        self.fallback(raw_input)
+    }
+
+    /// This month's usage and the budget, when metered
+    pub fn usage(&self) -> Option<(MonthlyUsage, Option<f64>)> {
+        self.meter.as_ref().map(|meter| (meter.month(), meter.budget()))
+    }
+
+    /// Why the most recent enrichment was skipped (e.g. the budget was reached)
+    pub fn last_error(&self) -> Option<String> {
+        self.last_error.lock().ok().and_then(|e| e.clone())
+    }
+
+    fn set_last_error(&self, error: Option<String>) {
+        if let Ok(mut last) = self.last_error.lock() {
+            *last = error;
+        }
+    }
+
+    /// Check if enrichment is available
+    pub fn is_available(&self) -> bool {
+        !self.clients.is_empty()
+    }
+
+    /// The configured providers, in failover order
+    pub fn providers(&self) -> Vec<String> {
+        self.clients.iter().map(LlmClient::name).collect()
+    }
+
+    /// The provider that answered the most recent call
+    pub fn active_provider(&self) -> Option<String> {
+        self.active_provider.lock().ok().and_then(|p| p.clone())
+    }
+
+    /// Enrich a raw task input using LLM
+    /// Falls back to the offline rules if LLM unavailable or fails
+    pub async fn enrich(&self, raw_input: &str, goals_context: Option<&str>) -> EnrichedTask {
+        // If no API key, apply the offline rules
+        if self.clients.is_empty() {
+            return self.fallback(raw_input);
+        }
+
+        self.set_last_error(None);
+        let day = models::today();
+        if let Some(task) = self.cache.as_ref().and_then(|c| c.get(raw_input, goals_context, day)) {
+            tracing::debug!("LLM cache hit");
+            return task;
+        }
+
+        // Get today's date for the prompt
+        let today = day.format("%Y-%m-%d").to_string();
+        let system_prompt = build_system_prompt(&today, goals_context);
+        let user_prompt = build_user_prompt(raw_input);
+
+        // Try to get enriched response
+        match self.complete(&system_prompt, &user_prompt, ENRICH_MAX_TOKENS).await {
+            Ok(response) => {
+                // Try to parse JSON response
+                match parse_llm_response(&response) {
+                    Ok(task) => {
+                        if let Some(cache) = &self.cache {
+                            cache.put(raw_input, goals_context, day, &task);
+                        }
+                        task
+                    }
+                    Err(e) => {
+                        // Fallback: keyword rules on the raw input
+                        tracing::warn!(error = %e, "Unparseable LLM response, using offline rules");
+                        self.fallback(raw_input)
+                    }
+                }
+            }
+            Err(e) => {
+                // API error or budget reached: fallback to keyword rules
+                tracing::warn!(error = %e, "LLM request failed, using offline rules");
+                self.fallback(raw_input)
+            }
+        }
+    }
+
+    /// Synchronous version for non-async contexts
+    /// Uses tokio runtime to block on the async call
+    pub fn enrich_sync(&self, raw_input: &str, goals_context: Option<&str>) -> EnrichedTask {
+        // If no API key, apply the offline rules immediately
+        if self.clients.is_empty() {
+            return self.fallback(raw_input);
+        }
+
+        block_on(self.enrich(raw_input, goals_context)).unwrap_or_else(|| self.fallback(raw_input))
+    }
+
+    /// Enrich several inputs at once, as fast as `llm_limits` allows, calling
+    /// `progress(done, total)` as each finishes. Results are in input order.
+    pub fn enrich_batch(
+        &self,
+        inputs: &[String],
+        goals_context: Option<&str>,
+        progress: impl FnMut(usize, usize) + Send,
+    ) -> Vec<EnrichedTask> {
+        if self.clients.is_empty() {
+            return inputs.iter().map(|input| self.fallback(input)).collect();
+        }
+        let enrich_all = queue::run_all(inputs.iter().map(|input| self.enrich(input, goals_context)).collect(), progress);
+        block_on(enrich_all).unwrap_or_else(|| inputs.iter().map(|input| self.fallback(input)).collect())
+    }
+
+    /// Send one completion, metered: refused once the month's budget is reached
+    /// (also reported through `last_error`), otherwise its tokens are recorded.
+    /// Providers are tried in order until one answers.
+    pub async fn complete(&self, system_prompt: &str, user_prompt: &str, max_tokens: u32) -> Result<String, String> {
+        if self.clients.is_empty() {
+            return Err("No LLM provider configured".to_string());
+        }
+        if let Some(Err(e)) = self.meter.as_ref().map(Meter::check) {
+            self.set_last_error(Some(e.clone()));
+            return Err(e);
+        }
+
+        let _permit = self.queue.acquire().await;
+        let mut errors = Vec::new();
+        for client in &self.clients {
+            let (response, usage) = match client.complete(system_prompt, user_prompt, max_tokens).await {
+                Ok(reply) => reply,
+                Err(e) => {
+                    tracing::warn!(provider = %client.name(), error = %e, "LLM provider failed, trying the next");
+                    errors.push(format!("{}: {}", client.name(), e));
+                    continue;
+                }
+            };
+            if let Ok(mut active) = self.active_provider.lock() {
+                *active = Some(client.name());
+            }
+            // Local models cost nothing, so don't count them against the budget
+            if let Some(meter) = self.meter.as_ref().filter(|_| !client.is_local()) {
+                match meter.record(usage, client.price()) {
+                    Ok(month) => tracing::info!(
+                        provider = %client.name(),
+                        prompt_tokens = usage.prompt_tokens,
+                        completion_tokens = usage.completion_tokens,
+                        month_cost_usd = month.cost_usd,
+                        "LLM usage"
+                    ),
+                    Err(e) => tracing::warn!(error = %e, "Failed to record LLM usage"),
+                }
+            }
+            return Ok(response);
+        }
+        Err(errors.join("; "))
+    }
+
+    /// Synchronous `complete`
+    pub fn complete_sync(&self, system_prompt: &str, user_prompt: &str, max_tokens: u32) -> Result<String, String> {
+        block_on(self.complete(system_prompt, user_prompt, max_tokens))
+            .unwrap_or_else(|| Err("Failed to start the async runtime".to_string()))
+    }
+}
+
+/// Parse the LLM JSON response into an EnrichedTask
+fn parse_llm_response(response: &str) -> Result<EnrichedTask, String> {
+    // Try to find JSON in the response (it might have markdown code blocks)
+    let json_str = extract_json(response)?;
+
+    serde_json::from_str(&json_str)
+        .map_err(|e| format!("JSON parse error: {}", e))
+}
+
+/// Extract JSON from a response that might have markdown formatting
+pub(crate) fn extract_json(response: &str) -> Result<String, String> {
+    let trimmed = response.trim();
+
+    // If it starts with {, assume it's raw JSON
+    if trimmed.starts_with('{') {
+        return Ok(trimmed.to_string());
+    }
+
+    // Try to find JSON in code blocks
+    if let Some(start) = trimmed.find("```json") {
+        let after_marker = &trimmed[start + 7..];
+        if let Some(end) = after_marker.find("```") {
+            return Ok(after_marker[..end].trim().to_string());
+        }
+    }
+
+    // Try generic code block
+    if let Some(start) = trimmed.find("```") {
+        let after_marker = &trimmed[start + 3..];
+        // Skip optional language identifier
+        let content_start = after_marker.find('\n').unwrap_or(0);
+        let after_newline = &after_marker[content_start..];
+        if let Some(end) = after_newline.find("```") {
+            return Ok(after_newline[..end].trim().to_string());
+        }
+    }
+
+    // Try to find { and } directly
+    if let (Some(start), Some(end)) = (trimmed.find('{'), trimmed.rfind('}')) {
+        if start < end {
+            return Ok(trimmed[start..=end].to_string());
+        }
+    }
+
+    Err("No JSON found in response".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_json_raw() {
+        let response = r#"{"title": "Test", "tags": []}"#;
+        assert!(extract_json(response).is_ok());
+    }
+
+    #[test]
+    fn test_extract_json_code_block() {
+        let response = r#"```json
+{"title": "Test", "tags": []}
+```"#;
+        assert!(extract_json(response).is_ok());
+    }
+
+    #[test]
+    fn test_parse_llm_response() {
+        let response = r#"{"title": "Call mom", "due_date": "2024-12-25", "priority": "high", "tags": ["personal"], "context": null}"#;
+        let task = parse_llm_response(response).unwrap();
+        assert_eq!(task.title, "Call mom");
+        assert_eq!(task.due_date, Some("2024-12-25".to_string()));
+        assert_eq!(task.priority, Some("high".to_string()));
+        assert_eq!(task.tags, vec!["personal"]);
+    }
+}
@@ -1,8 +1,14 @@
+pub mod cache;
 mod client;
 mod prompt;
 mod enricher;
+mod queue;
+mod rules;
+mod usage;
 
 pub use enricher::TaskEnricher;
+pub(crate) use enricher::extract_json;
+pub use usage::MonthlyUsage;
 
 use serde::{Deserialize, Serialize};
 
@@ -21,7 +27,7 @@ pub struct EnrichedTask {
 }
 
 impl EnrichedTask {
-    /// Create a simple task with just a title (fallback when LLM unavailable)
+    /// Create a simple task with just a title
     pub fn simple(title: String) -> Self {
         Self {
             title,
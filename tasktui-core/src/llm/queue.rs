@@ -0,0 +1,126 @@
+use crate::config::LlmLimits;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::task::Poll;
+use std::time::{Duration, Instant};
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Admits LLM calls no faster than the configured limits: at most
+/// `concurrency` in flight, and at most `requests_per_minute` started in any
+/// sliding minute
+pub struct RequestQueue {
+    permits: Semaphore,
+    requests_per_minute: Option<u32>,
+    /// When the calls of the last minute started, oldest first
+    started: Mutex<VecDeque<Instant>>,
+}
+
+impl RequestQueue {
+    pub fn new(limits: &LlmLimits) -> Self {
+        Self {
+            permits: Semaphore::new(limits.concurrency.max(1)),
+            requests_per_minute: limits.requests_per_minute.filter(|&rpm| rpm > 0),
+            started: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Wait for a turn; the call may go ahead while the permit is held
+    pub async fn acquire(&self) -> Option<SemaphorePermit<'_>> {
+        let permit = self.permits.acquire().await.ok()?;
+        let Some(rpm) = self.requests_per_minute else {
+            return Some(permit);
+        };
+        loop {
+            let wait = {
+                let mut started = self.started.lock().ok()?;
+                let now = Instant::now();
+                let wait = wait_time(&mut started, now, rpm);
+                if wait.is_none() {
+                    started.push_back(now);
+                }
+                wait
+            };
+            match wait {
+                None => return Some(permit),
+                Some(wait) => {
+                    tracing::debug!(wait_ms = wait.as_millis() as u64, "LLM rate limit reached, waiting");
+                    tokio::time::sleep(wait).await;
+                }
+            }
+        }
+    }
+}
+
+/// How long until another call may start, after forgetting calls older than
+/// a minute; `None` when one may start now
+fn wait_time(started: &mut VecDeque<Instant>, now: Instant, rpm: u32) -> Option<Duration> {
+    while started.front().is_some_and(|&t| now.duration_since(t) >= WINDOW) {
+        started.pop_front();
+    }
+    if started.len() < rpm as usize {
+        return None;
+    }
+    started.front().map(|&oldest| WINDOW - now.duration_since(oldest))
+}
+
+/// Drive all `futures` at once, calling `progress(done, total)` as each
+/// finishes; results come back in the input order
+pub async fn run_all<F: Future>(futures: Vec<F>, mut progress: impl FnMut(usize, usize)) -> Vec<F::Output> {
+    let total = futures.len();
+    let mut pending: Vec<Option<Pin<Box<F>>>> = futures.into_iter().map(|f| Some(Box::pin(f))).collect();
+    let mut results: Vec<Option<F::Output>> = (0..total).map(|_| None).collect();
+    let mut done = 0;
+    std::future::poll_fn(|cx| {
+        for (slot, result) in pending.iter_mut().zip(results.iter_mut()) {
+            let Some(future) = slot else {
+                continue;
+            };
+            if let Poll::Ready(output) = future.as_mut().poll(cx) {
+                *result = Some(output);
+                *slot = None;
+                done += 1;
+                progress(done, total);
+            }
+        }
+        if done == total { Poll::Ready(()) } else { Poll::Pending }
+    })
+    .await;
+    results.into_iter().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wait_time_and_run_all() {
+        let start = Instant::now();
+        let mut started = VecDeque::from([start, start + Duration::from_secs(10)]);
+        // Two calls in the last minute: a third must wait for the oldest to age out
+        assert_eq!(wait_time(&mut started, start + Duration::from_secs(20), 2), Some(Duration::from_secs(40)));
+        assert_eq!(wait_time(&mut started, start + Duration::from_secs(20), 3), None);
+        assert_eq!(wait_time(&mut started, start + Duration::from_secs(60), 2), None);
+        assert_eq!(started.len(), 1);
+
+        let queue = RequestQueue::new(&LlmLimits { concurrency: 2, requests_per_minute: Some(100) });
+        let mut seen = Vec::new();
+        let squares = tokio::runtime::Runtime::new().unwrap().block_on(run_all(
+            (1..=5u64).map(|n| {
+                let queue = &queue;
+                async move {
+                    let _permit = queue.acquire().await;
+                    tokio::time::sleep(Duration::from_millis(10 * (5 - n))).await;
+                    n * n
+                }
+            }).collect(),
+            |done, total| seen.push((done, total)),
+        ));
+        assert_eq!(squares, [1, 4, 9, 16, 25]);
+        assert_eq!(seen.last(), Some(&(5, 5)));
+        assert_eq!(queue.started.lock().unwrap().len(), 5);
+    }
+}
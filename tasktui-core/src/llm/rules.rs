@@ -0,0 +1,194 @@
+use super::EnrichedTask;
+use crate::quickadd::parse_day;
+use chrono::{Duration, NaiveDate, Weekday};
+
+/// Words that may introduce a date ("call Sam by friday") and go with it
+const DATE_CONNECTORS: &[&str] = &["by", "on", "due", "before", "until", "this"];
+
+/// Leading verbs and the tag they imply
+const VERB_TAGS: &[(&[&str], &str)] = &[
+    (&["call", "phone", "ring"], "@phone"),
+    (&["email", "mail", "reply", "respond"], "@email"),
+    (&["buy", "order", "pick"], "errands"),
+    (&["read"], "reading"),
+];
+
+/// Deterministic stand-in for the LLM: pulls a due date, priority and tags out of
+/// `raw_input` with keyword rules. `known_tags` are tag names (workstreams, goal
+/// areas) that are tagged when they appear as a word.
+pub fn enrich(raw_input: &str, today: NaiveDate, known_tags: &[String]) -> EnrichedTask {
+    let words: Vec<&str> = raw_input.split_whitespace().collect();
+    let normalized: Vec<String> = words.iter().map(|w| normalize(w)).collect();
+    let mut keep = vec![true; words.len()];
+    let mut task = EnrichedTask::simple(String::new());
+
+    let mut i = 0;
+    while i < words.len() {
+        let rest = &normalized[i..];
+
+        if task.due_date.is_none() {
+            let connector = i > 0 && DATE_CONNECTORS.contains(&normalized[i - 1].as_str());
+            if let Some((date, len)) = match_date(rest, today, connector) {
+                task.due_date = Some(date.format("%Y-%m-%d").to_string());
+                keep[i..i + len].fill(false);
+                if connector {
+                    keep[i - 1] = false;
+                }
+                i += len;
+                continue;
+            }
+        }
+
+        match rest.iter().map(String::as_str).take(2).collect::<Vec<_>>()[..] {
+            ["high" | "top", "priority", ..] => {
+                task.priority = Some("p1".to_string());
+                keep[i..i + 2].fill(false);
+                i += 2;
+                continue;
+            }
+            ["low", "priority", ..] => {
+                task.priority = Some("p3".to_string());
+                keep[i..i + 2].fill(false);
+                i += 2;
+                continue;
+            }
+            ["urgent" | "urgently" | "asap", ..] => {
+                task.priority = Some("p1".to_string());
+                keep[i] = false;
+            }
+            ["whenever", ..] => {
+                task.priority.get_or_insert_with(|| "p3".to_string());
+                keep[i] = false;
+            }
+            ["critical", ..] => {
+                task.priority = Some("p0".to_string());
+            }
+            ["important", ..] => {
+                task.priority.get_or_insert_with(|| "p1".to_string());
+            }
+            ["someday" | "eventually", ..] => {
+                task.priority.get_or_insert_with(|| "p4".to_string());
+            }
+            [word, ..] if known_tags.iter().any(|t| t == word) => {
+                push_tag(&mut task.tags, word);
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    if let Some(first) = normalized.first() {
+        if let Some((_, tag)) = VERB_TAGS.iter().find(|(verbs, _)| verbs.contains(&first.as_str())) {
+            push_tag(&mut task.tags, tag);
+        }
+    }
+
+    let title: Vec<&str> = words.iter().zip(&keep).filter(|(_, keep)| **keep).map(|(w, _)| *w).collect();
+    let title = title.join(" ");
+    let title = title.trim_end_matches([',', ';', ':', '-', '!']).trim();
+    task.title = if title.is_empty() { raw_input.trim().to_string() } else { capitalize(title) };
+    task
+}
+
+/// A date phrase at the start of `words` and how many words it spans.
+/// Weekday abbreviations only count after a connector ("by fri"), so "buy sun
+/// cream" stays dateless.
+fn match_date(words: &[String], today: NaiveDate, after_connector: bool) -> Option<(NaiveDate, usize)> {
+    let word = |i: usize| words.get(i).map(String::as_str);
+    match (word(0)?, word(1), word(2)) {
+        ("next", Some("week"), _) => Some((next_monday(today)?, 2)),
+        // "next friday" is the friday of next week
+        ("next", Some(day), _) if day.parse::<Weekday>().is_ok() => {
+            Some((parse_day(day, next_monday(today)?)?, 2))
+        }
+        ("in", Some(n), Some(unit)) => {
+            let n: i64 = if n == "a" { 1 } else { n.parse().ok()? };
+            let days = match unit.trim_end_matches('s') {
+                "day" => n,
+                "week" => n * 7,
+                _ => return None,
+            };
+            Some((today + Duration::days(days), 3))
+        }
+        ("end", Some("of"), Some("week")) => Some((parse_day("fri", today)?, 3)),
+        ("eow", _, _) => Some((parse_day("fri", today)?, 1)),
+        ("tonight", _, _) => Some((today, 1)),
+        ("tmrw", _, _) => Some((today + Duration::days(1), 1)),
+        ("today" | "tomorrow", _, _) => Some((parse_day(word(0)?, today)?, 1)),
+        (day, _, _) if is_iso_date(day) => Some((parse_day(day, today)?, 1)),
+        (day, _, _) if day.parse::<Weekday>().is_ok() && (day.len() > 4 || after_connector) => {
+            Some((parse_day(day, today)?, 1))
+        }
+        _ => None,
+    }
+}
+
+fn next_monday(today: NaiveDate) -> Option<NaiveDate> {
+    parse_day("mon", today + Duration::days(1))
+}
+
+fn is_iso_date(word: &str) -> bool {
+    NaiveDate::parse_from_str(word, "%Y-%m-%d").is_ok()
+}
+
+/// Lowercase and drop surrounding punctuation ("Friday," → "friday")
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric() && c != '-' && c != '+')
+        .trim_start_matches('#')
+        .to_lowercase()
+}
+
+fn push_tag(tags: &mut Vec<String>, tag: &str) {
+    if !tags.iter().any(|t| t == tag) {
+        tags.push(tag.to_string());
+    }
+}
+
+fn capitalize(title: &str) -> String {
+    let mut chars = title.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        // A Tuesday
+        NaiveDate::from_ymd_opt(2025, 11, 25).unwrap()
+    }
+
+    fn enrich(input: &str) -> EnrichedTask {
+        super::enrich(input, today(), &["work".to_string(), "home".to_string()])
+    }
+
+    #[test]
+    fn test_rules() {
+        let task = enrich("call Sam about the contract by Friday, urgent");
+        assert_eq!(task.title, "Call Sam about the contract");
+        assert_eq!(task.due_date.as_deref(), Some("2025-11-28"));
+        assert_eq!(task.priority.as_deref(), Some("p1"));
+        assert_eq!(task.tags, ["@phone"]);
+
+        let task = enrich("buy paint for home next week");
+        assert_eq!(task.title, "Buy paint for home");
+        assert_eq!(task.due_date.as_deref(), Some("2025-12-01"));
+        assert_eq!(task.tags, ["home", "errands"]);
+
+        assert_eq!(enrich("review budget in 3 days").due_date.as_deref(), Some("2025-11-28"));
+        assert_eq!(enrich("review budget next tue").due_date.as_deref(), Some("2025-12-02"));
+        assert_eq!(enrich("plan offsite next friday").due_date.as_deref(), Some("2025-12-05"));
+        assert_eq!(enrich("submit report on 2025-12-15").due_date.as_deref(), Some("2025-12-15"));
+        assert_eq!(enrich("learn piano someday").priority.as_deref(), Some("p4"));
+        assert_eq!(enrich("fix critical outage").priority.as_deref(), Some("p0"));
+        assert_eq!(enrich("call Tom about sun cream").due_date, None);
+
+        // Nothing recognized: the input is the title
+        let task = enrich("Think about Q4 plans");
+        assert_eq!(task.title, "Think about Q4 plans");
+        assert!(task.due_date.is_none() && task.priority.is_none() && task.tags.is_empty());
+    }
+}
@@ -0,0 +1,157 @@
+use crate::config::ProviderKind;
+use crate::models;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// What a provider charges, in USD per million tokens
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Price {
+    pub input_usd_per_mtok: f64,
+    pub output_usd_per_mtok: f64,
+}
+
+impl Price {
+    /// List prices of the provider's default model (gpt-4o-mini, Claude 3.5
+    /// Haiku); a local model is free
+    pub fn default_for(kind: ProviderKind) -> Self {
+        let (input_usd_per_mtok, output_usd_per_mtok) = match kind {
+            ProviderKind::Openai => (0.15, 0.60),
+            ProviderKind::Anthropic => (0.80, 4.00),
+            ProviderKind::Ollama => (0.0, 0.0),
+        };
+        Self { input_usd_per_mtok, output_usd_per_mtok }
+    }
+}
+
+/// Tokens used by one completion, as reported by the API
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+pub struct Usage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+}
+
+impl Usage {
+    /// Estimated cost in USD
+    pub fn cost(&self, price: Price) -> f64 {
+        (self.prompt_tokens as f64 * price.input_usd_per_mtok + self.completion_tokens as f64 * price.output_usd_per_mtok)
+            / 1_000_000.0
+    }
+}
+
+/// Running totals for one calendar month, kept in `.tasktui-usage.yaml`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MonthlyUsage {
+    /// `YYYY-MM`
+    pub month: String,
+    pub calls: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub cost_usd: f64,
+}
+
+impl MonthlyUsage {
+    /// Not synced: each machine counts the calls it made
+    pub const FILE_NAME: &'static str = ".tasktui-usage.yaml";
+
+    pub fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join(Self::FILE_NAME)
+    }
+
+    /// Totals for `month`; a missing file or one from an earlier month starts at zero
+    pub fn load(data_dir: &Path, month: &str) -> Self {
+        fs::read_to_string(Self::path(data_dir))
+            .ok()
+            .and_then(|content| serde_yaml::from_str::<MonthlyUsage>(&content).ok())
+            .filter(|usage| usage.month == month)
+            .unwrap_or_else(|| MonthlyUsage { month: month.to_string(), ..Default::default() })
+    }
+
+    pub fn save(&self, data_dir: &Path) -> Result<()> {
+        let content = serde_yaml::to_string(self)?;
+        fs::write(Self::path(data_dir), content).context("Failed to write usage file")
+    }
+
+    pub fn add(&mut self, usage: Usage, price: Price) {
+        self.calls += 1;
+        self.prompt_tokens += usage.prompt_tokens;
+        self.completion_tokens += usage.completion_tokens;
+        self.cost_usd += usage.cost(price);
+    }
+}
+
+/// Records usage in the data directory and enforces the monthly budget
+pub struct Meter {
+    data_dir: PathBuf,
+    budget_usd: Option<f64>,
+}
+
+impl Meter {
+    pub fn new(data_dir: PathBuf, budget_usd: Option<f64>) -> Self {
+        Self { data_dir, budget_usd }
+    }
+
+    /// This month's totals, read fresh so other processes' calls count too
+    pub fn month(&self) -> MonthlyUsage {
+        MonthlyUsage::load(&self.data_dir, &current_month())
+    }
+
+    pub fn budget(&self) -> Option<f64> {
+        self.budget_usd
+    }
+
+    /// Refuse once this month's spend has reached the budget
+    pub fn check(&self) -> Result<(), String> {
+        let Some(budget) = self.budget_usd else {
+            return Ok(());
+        };
+        let month = self.month();
+        if month.cost_usd >= budget {
+            return Err(format!(
+                "LLM budget reached: ${:.2} of ${:.2} spent in {} (raise llm_monthly_budget to continue)",
+                month.cost_usd, budget, month.month
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn record(&self, usage: Usage, price: Price) -> Result<MonthlyUsage> {
+        let mut month = self.month();
+        month.add(usage, price);
+        month.save(&self.data_dir)?;
+        Ok(month)
+    }
+}
+
+fn current_month() -> String {
+    models::today().format("%Y-%m").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_meter_totals_and_budget() {
+        let temp_dir = TempDir::new().unwrap();
+        let meter = Meter::new(temp_dir.path().to_path_buf(), Some(0.001));
+        let usage = Usage { prompt_tokens: 2_000, completion_tokens: 500 };
+        let price = Price::default_for(ProviderKind::Openai);
+        assert!((usage.cost(price) - 0.0006).abs() < 1e-12);
+        assert!((usage.cost(Price::default_for(ProviderKind::Anthropic)) - 0.0036).abs() < 1e-12);
+
+        assert!(meter.check().is_ok());
+        meter.record(usage, price).unwrap();
+        assert!(meter.check().is_ok());
+        let month = meter.record(usage, price).unwrap();
+        assert_eq!((month.calls, month.prompt_tokens, month.completion_tokens), (2, 4_000, 1_000));
+        assert!(meter.check().unwrap_err().contains("LLM budget reached"));
+
+        // Totals from an earlier month don't count
+        MonthlyUsage { month: "1999-01".to_string(), ..month }.save(temp_dir.path()).unwrap();
+        assert_eq!(meter.month().calls, 0);
+        assert!(meter.check().is_ok());
+    }
+}
@@ -0,0 +1,43 @@
+mod permissions;
+mod prompts;
+mod protocol;
+mod tools;
+
+pub use protocol::McpServer;
+
+use crate::config::AppConfig;
+use crate::llm::TaskEnricher;
+use crate::storage::Storage;
+use anyhow::Result;
+use std::path::PathBuf;
+
+/// Run MCP server mode. With `strict`, writes with invalid frontmatter are rejected.
+pub fn run(data_dir: PathBuf, strict: bool) -> Result<()> {
+    let config = AppConfig::load(&data_dir)?;
+    let mut storage = open_storage(&data_dir, &config)?;
+    storage.strict = strict;
+    let enricher = TaskEnricher::from_config(&config, &data_dir);
+    #[cfg(feature = "plugins")]
+    let server = {
+        let builtin = tools::list_tools().map_err(anyhow::Error::msg)?;
+        let reserved: Vec<&str> = builtin["tools"].as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|tool| tool["name"].as_str())
+            .collect();
+        let plugins = crate::plugins::Plugins::load(open_storage(&data_dir, &config)?, &reserved)?;
+        McpServer::new(storage, enricher, config).with_plugins(plugins)
+    };
+    #[cfg(not(feature = "plugins"))]
+    let server = McpServer::new(storage, enricher, config);
+    server.run()
+}
+
+fn open_storage(data_dir: &std::path::Path, config: &AppConfig) -> Result<Storage> {
+    let mut storage = Storage::new(data_dir.to_path_buf())?;
+    storage.activity_log = config.activity_log;
+    storage.hooks = config.hooks.clone();
+    storage.backups = config.backups;
+    storage.set_git_config(&config.git);
+    Ok(storage)
+}
@@ -0,0 +1,127 @@
+use crate::config::McpPermissions;
+use serde_json::{json, Value};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Window the `rate_limits` are counted over
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// The `permissions` config, plus the recent calls its rate limits count
+pub struct Policy {
+    rules: McpPermissions,
+    calls: RefCell<HashMap<String, VecDeque<Instant>>>,
+}
+
+/// Why a tool call was refused
+#[derive(Debug, PartialEq)]
+pub enum Refusal {
+    Denied { tool: String },
+    RateLimited { tool: String, limit: u32, retry_after: Duration },
+}
+
+impl Policy {
+    pub fn new(rules: Option<McpPermissions>) -> Self {
+        Self { rules: rules.unwrap_or_default(), calls: RefCell::new(HashMap::new()) }
+    }
+
+    pub fn is_enabled(&self, tool: &str) -> bool {
+        let allowed = self.rules.allow.is_empty() || self.rules.allow.iter().any(|t| t == tool);
+        allowed && !self.rules.deny.iter().any(|t| t == tool)
+    }
+
+    /// Check a call against the policy, counting it toward the tool's rate limit if it's let through
+    pub fn check(&self, tool: &str, now: Instant) -> Result<(), Refusal> {
+        if !self.is_enabled(tool) {
+            return Err(Refusal::Denied { tool: tool.to_string() });
+        }
+        let Some(&limit) = self.rules.rate_limits.get(tool) else {
+            return Ok(());
+        };
+
+        let mut calls = self.calls.borrow_mut();
+        let recent = calls.entry(tool.to_string()).or_default();
+        while recent.front().is_some_and(|at| now.duration_since(*at) >= RATE_WINDOW) {
+            recent.pop_front();
+        }
+        if recent.len() >= limit as usize {
+            let retry_after = recent.front().map_or(RATE_WINDOW, |oldest| RATE_WINDOW - now.duration_since(*oldest));
+            return Err(Refusal::RateLimited { tool: tool.to_string(), limit, retry_after });
+        }
+        recent.push_back(now);
+        Ok(())
+    }
+
+    /// A `tools/list` result without the disabled tools
+    pub fn filter_tools(&self, mut list: Value) -> Value {
+        if let Some(tools) = list.get_mut("tools").and_then(Value::as_array_mut) {
+            tools.retain(|tool| tool.get("name").and_then(Value::as_str).is_some_and(|name| self.is_enabled(name)));
+        }
+        list
+    }
+}
+
+impl Refusal {
+    /// JSON-RPC error code, in the range reserved for servers
+    pub fn code(&self) -> i32 {
+        match self {
+            Refusal::Denied { .. } => -32001,
+            Refusal::RateLimited { .. } => -32002,
+        }
+    }
+
+    /// Error `data` for clients to explain the refusal
+    pub fn data(&self) -> Value {
+        match self {
+            Refusal::Denied { tool } => json!({ "reason": "denied", "tool": tool }),
+            Refusal::RateLimited { tool, limit, retry_after } => json!({
+                "reason": "rate_limited",
+                "tool": tool,
+                "limit_per_minute": limit,
+                "retry_after_secs": retry_after.as_secs().max(1),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::Denied { tool } => write!(f, "Tool '{}' is disabled by the permissions config", tool),
+            Refusal::RateLimited { tool, limit, .. } => {
+                write!(f, "Tool '{}' is limited to {} calls per minute", tool, limit)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_policy() {
+        let policy = Policy::new(Some(McpPermissions {
+            allow: vec!["create_task".to_string(), "list_tasks".to_string(), "bulk_update".to_string()],
+            deny: vec!["bulk_update".to_string()],
+            rate_limits: BTreeMap::from([("create_task".to_string(), 2)]),
+        }));
+        let start = Instant::now();
+
+        assert!(policy.check("list_tasks", start).is_ok());
+        assert_eq!(policy.check("bulk_update", start), Err(Refusal::Denied { tool: "bulk_update".to_string() }));
+        assert!(matches!(policy.check("update_task", start), Err(Refusal::Denied { .. })));
+
+        assert!(policy.check("create_task", start).is_ok());
+        assert!(policy.check("create_task", start + Duration::from_secs(20)).is_ok());
+        let refusal = policy.check("create_task", start + Duration::from_secs(30)).unwrap_err();
+        assert_eq!(refusal.data()["retry_after_secs"], 30);
+        // The first call drops out of the window
+        assert!(policy.check("create_task", start + Duration::from_secs(61)).is_ok());
+
+        let listed = policy.filter_tools(json!({ "tools": [{ "name": "list_tasks" }, { "name": "bulk_update" }] }));
+        assert_eq!(listed, json!({ "tools": [{ "name": "list_tasks" }] }));
+    }
+}
@@ -0,0 +1,213 @@
+use crate::config::{AppConfig, CalendarConfig};
+use crate::models::{self, Priority, Status, TaskItem};
+use crate::storage::Storage;
+use chrono::{Duration, Utc};
+use serde_json::{json, Value};
+
+/// List available prompts
+pub fn list_prompts() -> Result<Value, String> {
+    Ok(json!({
+        "prompts": [
+            {
+                "name": "weekly_review",
+                "description": "Review the past week: what got done, what's waiting on others, and what's overdue",
+                "arguments": []
+            },
+            {
+                "name": "plan_my_day",
+                "description": "Plan today from overdue, due and active tasks, weighed against your goals",
+                "arguments": [
+                    {
+                        "name": "hours",
+                        "description": "Hours available for focused work today",
+                        "required": false
+                    }
+                ]
+            },
+            {
+                "name": "triage_inbox",
+                "description": "Clarify each inbox item into a next action, a delegation, a someday item or trash",
+                "arguments": []
+            }
+        ]
+    }))
+}
+
+/// Build a prompt filled in with the current tasks
+pub fn get_prompt(storage: &Storage, config: &AppConfig, params: Value) -> Result<Value, String> {
+    let name = params
+        .get("name")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing prompt name")?;
+    let arguments = params.get("arguments").cloned().unwrap_or(Value::Null);
+
+    let tasks = storage
+        .load_all_tasks()
+        .map_err(|e| format!("Failed to load tasks: {}", e))?;
+    let tasks: Vec<_> = tasks.into_iter().filter(|t| !t.is_project()).collect();
+
+    let (description, text) = match name {
+        "weekly_review" => ("Weekly review", weekly_review(&tasks)),
+        "plan_my_day" => {
+            let hours = arguments.get("hours").and_then(|v| v.as_str());
+            ("Plan my day", plan_my_day(&tasks, &config.calendar, hours))
+        }
+        "triage_inbox" => ("Triage inbox", triage_inbox(&tasks)),
+        _ => return Err(format!("Unknown prompt: {}", name)),
+    };
+    let text = match config.goals_context() {
+        goals if goals.is_empty() => text,
+        goals => format!("{}\n{}", text, goals),
+    };
+
+    Ok(json!({
+        "description": description,
+        "messages": [
+            {
+                "role": "user",
+                "content": { "type": "text", "text": text }
+            }
+        ]
+    }))
+}
+
+fn weekly_review(tasks: &[TaskItem]) -> String {
+    let since = Utc::now() - Duration::days(7);
+    let done = tasks.iter().filter(|t| t.frontmatter.completed_at.is_some_and(|at| at >= since));
+    let waiting = tasks.iter().filter(|t| t.frontmatter.status == Status::Waiting);
+    let now = models::now_local();
+    let overdue = tasks.iter().filter(|t| t.is_overdue(now));
+    let open = tasks.iter().filter(|t| matches!(t.frontmatter.status, Status::Active | Status::Next));
+
+    let mut text = String::from(
+        "Run my weekly review. Summarize what I finished, flag anything waiting too long on \
+         someone else, suggest what to drop or reschedule among overdue tasks, and propose the \
+         three most important tasks for next week. Use the tasktui tools to apply changes I agree to.\n",
+    );
+    section(&mut text, "Completed in the last 7 days", done);
+    section(&mut text, "Waiting on others", waiting);
+    section(&mut text, "Overdue", overdue);
+    section(&mut text, "Active and next", open);
+    text
+}
+
+/// Holidays and vacations count as days with no time for planned work
+fn plan_my_day(tasks: &[TaskItem], calendar: &CalendarConfig, hours: Option<&str>) -> String {
+    let today = models::today();
+    let now = models::now_local();
+    let overdue = tasks.iter().filter(|t| t.is_overdue(now));
+    let due_today = tasks.iter().filter(|t| t.is_due_today() && !t.is_overdue(now));
+    let mut active: Vec<_> = tasks.iter()
+        .filter(|t| t.frontmatter.status == Status::Active && !t.is_due_today() && !t.is_overdue(now))
+        .collect();
+    active.sort_by_key(|t| t.frontmatter.priority.rank());
+
+    let mut text = format!("Plan my day for {}. ", today.format("%A %Y-%m-%d"));
+    match calendar.holiday_on(today) {
+        Some(holiday) => text.push_str(&format!(
+            "It's a day off ({}), so there is no time for planned work. Name only what can't wait, \
+             and suggest new dates for the rest from {} on.\n",
+            holiday.label(),
+            calendar.next_working_day(today).format("%A %Y-%m-%d")
+        )),
+        None => {
+            if let Some(hours) = hours {
+                text.push_str(&format!("I have about {} hours for focused work. ", hours));
+            }
+            text.push_str(
+                "Pick what fits, in order, starting with anything overdue or due today, and say what \
+                 should move to another day.\n",
+            );
+        }
+    }
+    section(&mut text, "Overdue", overdue);
+    section(&mut text, "Due today", due_today);
+    section(&mut text, "Active", active.into_iter());
+    text
+}
+
+fn triage_inbox(tasks: &[TaskItem]) -> String {
+    let inbox = tasks.iter().filter(|t| t.frontmatter.status == Status::Inbox);
+    let mut text = String::from(
+        "Help me triage my inbox. For each item suggest one of: do now (status active), \
+         schedule (status next with a due_date), delegate (delegated_to a person), someday \
+         (status next with a someday tag) or trash. Ask before applying anything with update_task.\n",
+    );
+    section(&mut text, "Inbox", inbox);
+    text
+}
+
+/// Append a heading and one line per task, or "(none)"
+fn section<'a>(text: &mut String, heading: &str, tasks: impl Iterator<Item = &'a TaskItem>) {
+    text.push_str(&format!("\n## {}\n", heading));
+    let before = text.len();
+    for task in tasks {
+        text.push_str(&task_line(task));
+        text.push('\n');
+    }
+    if text.len() == before {
+        text.push_str("(none)\n");
+    }
+}
+
+fn task_line(task: &TaskItem) -> String {
+    let fm = &task.frontmatter;
+    let mut line = format!("- {} [{}]", fm.title, fm.id);
+    if fm.priority.rank() <= Priority::P1.rank() {
+        line.push_str(&format!(" !{}", fm.priority.as_str()));
+    }
+    if let Some(due) = fm.due_date {
+        line.push_str(&format!(" due {}", due.label(models::today())));
+    }
+    if let Some(person) = &fm.delegated_to {
+        line.push_str(&format!(" waiting on {}", person));
+    }
+    for tag in &fm.tags {
+        line.push_str(&format!(" #{}", tag));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Holiday;
+    use crate::models::{Due, ItemType};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_prompts_include_tasks() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut captured = TaskItem::new("Call the plumber".to_string(), ItemType::Task);
+        captured.frontmatter.status = Status::Inbox;
+        storage.write_task(&mut captured).unwrap();
+        let mut late = TaskItem::new("File taxes".to_string(), ItemType::Task);
+        late.frontmatter.due_date = Some(Due::on(models::today() - Duration::days(2)));
+        storage.write_task(&mut late).unwrap();
+
+        let config = AppConfig::default();
+        let get = |name: &str, arguments: Value| {
+            let prompt = get_prompt(&storage, &config, json!({ "name": name, "arguments": arguments })).unwrap();
+            prompt["messages"][0]["content"]["text"].as_str().unwrap().to_string()
+        };
+
+        let triage = get("triage_inbox", Value::Null);
+        assert!(triage.contains(&format!("- Call the plumber [{}]", captured.frontmatter.id)));
+        assert!(!triage.contains("File taxes"));
+
+        let plan = get("plan_my_day", json!({ "hours": "4" }));
+        assert!(plan.contains("about 4 hours"));
+        assert!(plan.contains("## Overdue\n- File taxes"));
+        assert!(plan.contains("## Due today\n(none)"));
+
+        let mut config = config.clone();
+        config.calendar.holidays.push(Holiday { name: "Founders' Day".to_string(), from: models::today(), to: None });
+        let prompt = get_prompt(&storage, &config, json!({ "name": "plan_my_day", "arguments": { "hours": "4" } })).unwrap();
+        let plan = prompt["messages"][0]["content"]["text"].as_str().unwrap();
+        assert!(plan.contains("a day off (Founders' Day)") && !plan.contains("about 4 hours"));
+
+        assert!(get("weekly_review", Value::Null).contains("## Waiting on others\n(none)"));
+        assert!(get_prompt(&storage, &config, json!({ "name": "nope" })).is_err());
+    }
+}
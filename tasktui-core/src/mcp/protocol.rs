@@ -0,0 +1,218 @@
+use crate::config::AppConfig;
+use crate::llm::TaskEnricher;
+use crate::storage::Storage;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+#[cfg(feature = "plugins")]
+use std::cell::RefCell;
+use std::io::{self, BufRead, Write};
+use std::time::Instant;
+
+use super::permissions::Policy;
+use super::{prompts, tools};
+
+/// JSON-RPC 2.0 Request
+#[derive(Debug, Deserialize)]
+struct JsonRpcRequest {
+    jsonrpc: String,
+    id: Option<Value>,
+    method: String,
+    params: Option<Value>,
+}
+
+/// JSON-RPC 2.0 Response
+#[derive(Debug, Serialize)]
+struct JsonRpcResponse {
+    jsonrpc: String,
+    id: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<JsonRpcError>,
+}
+
+/// JSON-RPC 2.0 Error
+#[derive(Debug, Serialize)]
+struct JsonRpcError {
+    code: i32,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<Value>,
+}
+
+pub struct McpServer {
+    storage: Storage,
+    enricher: TaskEnricher,
+    config: AppConfig,
+    policy: Policy,
+    #[cfg(feature = "plugins")]
+    plugins: RefCell<crate::plugins::Plugins>,
+}
+
+impl McpServer {
+    pub fn new(storage: Storage, enricher: TaskEnricher, config: AppConfig) -> Self {
+        let policy = Policy::new(config.permissions.clone());
+        Self {
+            storage,
+            enricher,
+            config,
+            policy,
+            #[cfg(feature = "plugins")]
+            plugins: RefCell::default(),
+        }
+    }
+
+    /// Serve the tools `plugins` registered alongside the built-in ones
+    #[cfg(feature = "plugins")]
+    pub fn with_plugins(mut self, plugins: crate::plugins::Plugins) -> Self {
+        self.plugins = RefCell::new(plugins);
+        self
+    }
+
+    pub fn run(&self) -> Result<()> {
+        let stdin = io::stdin();
+        let stdout = io::stdout();
+        let mut stdout = stdout.lock();
+
+        tracing::info!("MCP server started, listening on stdio");
+
+        for line in stdin.lock().lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            tracing::debug!(payload = %line, "MCP request");
+
+            let response = match serde_json::from_str::<JsonRpcRequest>(&line) {
+                Ok(request) => {
+                    tracing::info!(method = %request.method, id = %request.id.clone().unwrap_or(serde_json::Value::Null), "MCP request");
+                    self.handle_request(request)
+                }
+                Err(e) => JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id: None,
+                    result: None,
+                    error: Some(JsonRpcError {
+                        code: -32700,
+                        message: format!("Parse error: {}", e),
+                        data: None,
+                    }),
+                },
+            };
+
+            if let Some(error) = &response.error {
+                tracing::warn!(id = %response.id.clone().unwrap_or(serde_json::Value::Null), code = error.code, error = %error.message, "MCP error response");
+            }
+            let response_json = serde_json::to_string(&response)?;
+            tracing::debug!(payload = %response_json, "MCP response");
+            writeln!(stdout, "{}", response_json)?;
+            stdout.flush()?;
+        }
+
+        Ok(())
+    }
+
+    fn handle_request(&self, request: JsonRpcRequest) -> JsonRpcResponse {
+        if request.jsonrpc != "2.0" {
+            return JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id: request.id,
+                result: None,
+                error: Some(JsonRpcError {
+                    code: -32600,
+                    message: format!("Invalid Request: unsupported jsonrpc version {}", request.jsonrpc),
+                    data: None,
+                }),
+            };
+        }
+
+        if request.method == "tools/call" {
+            let tool = request.params.as_ref().and_then(|p| p.get("name")).and_then(|n| n.as_str()).unwrap_or("");
+            if let Err(refusal) = self.policy.check(tool, Instant::now()) {
+                return JsonRpcResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id: request.id,
+                    result: None,
+                    error: Some(JsonRpcError {
+                        code: refusal.code(),
+                        message: refusal.to_string(),
+                        data: Some(refusal.data()),
+                    }),
+                };
+            }
+        }
+
+        let result = match request.method.as_str() {
+            "initialize" => tools::initialize(),
+            "tools/list" => self.list_tools().map(|list| self.policy.filter_tools(list)),
+            "tools/call" => {
+                let params = request.params.unwrap_or(serde_json::Value::Null);
+                self.call_tool(params)
+            }
+            "resources/list" => tools::list_resources(),
+            "resources/templates/list" => tools::list_resource_templates(),
+            "resources/read" => {
+                let params = request.params.unwrap_or(serde_json::Value::Null);
+                tools::read_resource(&self.storage, &self.config, params)
+            }
+            "prompts/list" => prompts::list_prompts(),
+            "prompts/get" => {
+                let params = request.params.unwrap_or(serde_json::Value::Null);
+                prompts::get_prompt(&self.storage, &self.config, params)
+            }
+            _ => Err(format!("Method not found: {}", request.method)),
+        };
+
+        match result {
+            Ok(result) => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id: request.id,
+                result: Some(result),
+                error: None,
+            },
+            Err(e) => JsonRpcResponse {
+                jsonrpc: "2.0".to_string(),
+                id: request.id,
+                result: None,
+                error: Some(JsonRpcError {
+                    code: -32603,
+                    message: e,
+                    data: None,
+                }),
+            },
+        }
+    }
+
+    #[cfg(not(feature = "plugins"))]
+    fn list_tools(&self) -> Result<Value, String> {
+        tools::list_tools()
+    }
+
+    #[cfg(feature = "plugins")]
+    fn list_tools(&self) -> Result<Value, String> {
+        let mut list = tools::list_tools()?;
+        if let Some(tools) = list["tools"].as_array_mut() {
+            tools.extend(self.plugins.borrow().tools().cloned());
+        }
+        Ok(list)
+    }
+
+    #[cfg(not(feature = "plugins"))]
+    fn call_tool(&self, params: Value) -> Result<Value, String> {
+        tools::call_tool(&self.storage, &self.enricher, &self.config, params)
+    }
+
+    #[cfg(feature = "plugins")]
+    fn call_tool(&self, params: Value) -> Result<Value, String> {
+        let name = params.get("name").and_then(|n| n.as_str()).unwrap_or("");
+        let mut plugins = self.plugins.borrow_mut();
+        if !plugins.has_tool(name) {
+            return tools::call_tool(&self.storage, &self.enricher, &self.config, params);
+        }
+        let arguments = params.get("arguments").cloned().unwrap_or(Value::Null);
+        plugins.call(name, arguments).map_err(|e| format!("{:#}", e))
+    }
+
+}
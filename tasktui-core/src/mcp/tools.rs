@@ -0,0 +1,1705 @@
+use crate::activity;
+use crate::config::AppConfig;
+use crate::embeddings::{self, EmbeddingIndex};
+use crate::filter::FilterExpr;
+use crate::flow::ProjectFlow;
+use crate::integrations::jira;
+use crate::llm::TaskEnricher;
+use crate::models::{self, Due, FieldDef, Habit, ItemType, Priority, Status, TaskFilter, TaskItem};
+use crate::prioritize;
+use crate::retro::{self, WeekReview};
+use crate::storage::Storage;
+use crate::validate;
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::fs;
+
+/// Handle initialize request
+pub fn initialize() -> Result<Value, String> {
+    Ok(json!({
+        "protocolVersion": "0.1.0",
+        "serverInfo": {
+            "name": "tasktui",
+            "version": "0.1.0"
+        },
+        "capabilities": {
+            "tools": true,
+            "resources": true,
+            "prompts": true
+        }
+    }))
+}
+
+/// List available tools
+pub fn list_tools() -> Result<Value, String> {
+    Ok(json!({
+        "tools": [
+            {
+                "name": "create_task",
+                "description": "Create a new task. Use raw_input for natural language (e.g., 'call mom tomorrow high priority') which will be parsed by LLM, or provide structured fields directly.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "dry_run": {
+                            "type": "boolean",
+                            "description": "Return the changes this call would make without writing anything"
+                        },
+                        "raw_input": {
+                            "type": "string",
+                            "description": "Natural language task description (e.g., 'call mom tomorrow high priority'). If provided, LLM will parse it to extract title, due_date, priority, and tags."
+                        },
+                        "title": {
+                            "type": "string",
+                            "description": "Task title (used if raw_input not provided)"
+                        },
+                        "context": {
+                            "type": "string",
+                            "description": "Task context/notes"
+                        },
+                        "due_date": {
+                            "type": "string",
+                            "description": "Due date in YYYY-MM-DD format"
+                        },
+                        "priority": {
+                            "type": "string",
+                            "enum": ["p0", "p1", "p2", "p3", "p4", "high", "medium", "low"],
+                            "description": "Task priority, p0 (most urgent) to p4; high, medium and low mean p1, p2 and p3"
+                        },
+                        "tags": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Task tags"
+                        },
+                        "status": {
+                            "type": "string",
+                            "enum": ["inbox", "active", "next", "waiting"],
+                            "description": "Starting status (default active)"
+                        },
+                        "parent_project_id": {
+                            "type": "string",
+                            "description": "UUID or short code of an open project to create the task in"
+                        },
+                        "start_date": {
+                            "type": "string",
+                            "description": "Start date in YYYY-MM-DD format"
+                        },
+                        "estimate_minutes": {
+                            "type": "number",
+                            "description": "Expected effort in minutes"
+                        }
+                    }
+                }
+            },
+            {
+                "name": "enrich_text",
+                "description": "Parse natural language (e.g., 'call mom tomorrow high priority') into structured task fields the way create_task's raw_input does, without creating anything. The result's fields can be adjusted and passed to create_task.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "text": {
+                            "type": "string",
+                            "description": "Natural language task description"
+                        }
+                    },
+                    "required": ["text"]
+                }
+            },
+            {
+                "name": "suggest_priorities",
+                "description": "Ask the LLM to review the priorities of all open tasks against the active goals. Returns proposed changes, each with a one-line reason; nothing is changed. Apply the ones the user accepts with update_task or bulk_update.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {}
+                }
+            },
+            {
+                "name": "update_task",
+                "description": "Update a task field, or append a timestamped entry to the task's ## Notes timeline (field \"notes\")",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "dry_run": {
+                            "type": "boolean",
+                            "description": "Return the changes this call would make without writing anything"
+                        },
+                        "id": {
+                            "type": "string",
+                            "description": "Task UUID or short code (e.g. 3fa85f)"
+                        },
+                        "field": {
+                            "type": "string",
+                            "enum": ["title", "status", "priority", "tags", "due_date", "notes", "delegated_to", "goal_id", "jira_key", "custom", "fields", "habit"],
+                            "description": "Field to update"
+                        },
+                        "value": {
+                            "description": "New value. tags takes an array (or a single tag), due_date takes YYYY-MM-DD or YYYY-MM-DDTHH:MM, goal_id takes a goal id from the config, jira_key takes an issue key like OPS-142; null clears tags, due_date, goal_id or jira_key. custom takes an object of custom field values to set (null removes one), checked against the project's field definitions. fields (projects only) takes the definitions: [{\"name\", \"type\": text|number|date|choice, \"values\": [...]}]. habit takes daily, weekdays or weekly (null stops it being a habit); complete_task then checks off the day instead of closing it"
+                        },
+                        "mode": {
+                            "type": "string",
+                            "enum": ["replace", "add", "remove"],
+                            "description": "For tags: replace the list (default), or add/remove the given tags"
+                        }
+                    },
+                    "required": ["id", "field", "value"]
+                }
+            },
+            {
+                "name": "list_tasks",
+                "description": "List tasks with optional filtering",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "status": {
+                            "type": "string",
+                            "enum": ["inbox", "active", "next", "waiting", "done", "archived"],
+                            "description": "Filter by status"
+                        },
+                        "tag": {
+                            "type": "string",
+                            "description": "Filter by tag"
+                        },
+                        "query": {
+                            "type": "string",
+                            "description": "Compound filter, e.g. '#work|#home status:active,next priority:high due:today..today+7 @project'"
+                        },
+                        "limit": {
+                            "type": "number",
+                            "description": "Maximum number of results"
+                        },
+                        "offset": {
+                            "type": "number",
+                            "description": "Number of results to skip"
+                        },
+                        "cursor": {
+                            "type": "string",
+                            "description": "next_cursor from a previous call, to fetch the following page"
+                        },
+                        "sort_by": {
+                            "type": "string",
+                            "enum": ["priority", "due", "created", "updated"],
+                            "description": "priority (default, P0 first), due (soonest first), created or updated (newest first)"
+                        },
+                        "fields": {
+                            "type": "array",
+                            "items": {
+                                "type": "string",
+                                "enum": LIST_FIELDS
+                            },
+                            "description": "Fields to return for each task (default: title, status, priority, tags, due_date, delegated_to); id and code are always included"
+                        }
+                    }
+                }
+            },
+            {
+                "name": "search_tasks",
+                "description": "Find tasks by words in their title, tags or body, best matches first. Quote a phrase to match it exactly",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "query": {
+                            "type": "string",
+                            "description": "Words every match must contain, e.g. 'contract renewal' or '\"quarterly report\" finance'"
+                        },
+                        "limit": {
+                            "type": "number",
+                            "description": "Maximum number of results (default 20)"
+                        },
+                        "mode": {
+                            "type": "string",
+                            "enum": ["text", "semantic"],
+                            "description": "text (default) matches words; semantic finds tasks close in meaning, e.g. 'that thing about the contract renewal', and needs an embeddings API in the config"
+                        }
+                    },
+                    "required": ["query"]
+                }
+            },
+            {
+                "name": "bulk_update",
+                "description": "Apply one operation to every task matching a filter in a single atomic write, e.g. archive all tasks completed before a date",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "dry_run": {
+                            "type": "boolean",
+                            "description": "Return the changes this call would make without writing anything"
+                        },
+                        "filter": {
+                            "type": "object",
+                            "description": "Which tasks to change; at least one criterion is required",
+                            "properties": {
+                                "status": {
+                                    "type": "string",
+                                    "enum": ["inbox", "active", "next", "waiting", "done", "archived"]
+                                },
+                                "tag": { "type": "string" },
+                                "ids": {
+                                    "type": "array",
+                                    "items": { "type": "string" },
+                                    "description": "Task UUIDs or short codes"
+                                },
+                                "query": {
+                                    "type": "string",
+                                    "description": "Compound filter as in list_tasks, e.g. 'completed:..2025-02-28'"
+                                }
+                            }
+                        },
+                        "operation": {
+                            "type": "string",
+                            "enum": ["set_status", "add_tag", "set_priority", "archive"]
+                        },
+                        "value": {
+                            "type": "string",
+                            "description": "Status, tag or priority for set_status, add_tag and set_priority"
+                        }
+                    },
+                    "required": ["filter", "operation"]
+                }
+            },
+            {
+                "name": "reparent_tasks",
+                "description": "Move tasks to another project, or detach them from their project, in a single atomic write. Use orphaned to pick up open tasks whose project was deleted or archived.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "dry_run": {
+                            "type": "boolean",
+                            "description": "Return the changes this call would make without writing anything"
+                        },
+                        "ids": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Task UUIDs or short codes"
+                        },
+                        "from_project": {
+                            "type": "string",
+                            "description": "Move every open task in this project (UUID or short code)"
+                        },
+                        "orphaned": {
+                            "type": "boolean",
+                            "description": "Move every open task whose project no longer exists or is archived"
+                        },
+                        "to_project": {
+                            "type": ["string", "null"],
+                            "description": "Project UUID or short code to move them to; omit or null to detach them"
+                        }
+                    }
+                }
+            },
+            {
+                "name": "read_task_details",
+                "description": "Get full details of a specific task",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "id": {
+                            "type": "string",
+                            "description": "Task UUID or short code (e.g. 3fa85f)"
+                        }
+                    },
+                    "required": ["id"]
+                }
+            },
+            {
+                "name": "complete_task",
+                "description": "Mark a task as done",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "dry_run": {
+                            "type": "boolean",
+                            "description": "Return the changes this call would make without writing anything"
+                        },
+                        "id": {
+                            "type": "string",
+                            "description": "Task UUID or short code (e.g. 3fa85f)"
+                        }
+                    },
+                    "required": ["id"]
+                }
+            }
+        ]
+    }))
+}
+
+/// Call a tool
+pub fn call_tool(storage: &Storage, enricher: &TaskEnricher, config: &AppConfig, params: Value) -> Result<Value, String> {
+    let tool_name = params
+        .get("name")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing tool name")?;
+
+    let arguments = params.get("arguments").cloned().unwrap_or(Value::Null);
+
+    match tool_name {
+        "create_task" => create_task(storage, enricher, config, arguments),
+        "enrich_text" => enrich_text(enricher, config, arguments),
+        "suggest_priorities" => suggest_priorities(storage, enricher, config),
+        "update_task" => update_task(storage, config, arguments),
+        "list_tasks" => list_tasks(storage, arguments),
+        "search_tasks" => search_tasks(storage, config, arguments),
+        "bulk_update" => bulk_update(storage, arguments),
+        "reparent_tasks" => reparent_tasks(storage, arguments),
+        "read_task_details" => read_task_details(storage, arguments),
+        "complete_task" => complete_task(storage, config, arguments),
+        _ => Err(format!("Unknown tool: {}", tool_name)),
+    }
+}
+
+fn create_task(storage: &Storage, enricher: &TaskEnricher, config: &AppConfig, args: Value) -> Result<Value, String> {
+    // Get goals context for LLM prioritization
+    let goals_context = config.goals_context();
+    let goals_ref = if goals_context.is_empty() { None } else { Some(goals_context.as_str()) };
+
+    // Check if raw_input is provided (natural language mode)
+    let (title, enriched_due_date, enriched_priority, enriched_tags, enriched_context) =
+        if let Some(raw_input) = args.get("raw_input").and_then(|v| v.as_str()) {
+            // Use LLM to parse the natural language input
+            let enriched = enricher.enrich_sync(raw_input, goals_ref);
+            (
+                enriched.title,
+                enriched.due_date,
+                enriched.priority,
+                enriched.tags,
+                enriched.context,
+            )
+        } else if let Some(title) = args.get("title").and_then(|v| v.as_str()) {
+            // Structured mode - use provided title directly
+            (title.to_string(), None, None, Vec::new(), None)
+        } else {
+            return Err("Missing raw_input or title".to_string());
+        };
+
+    let mut task = TaskItem::new(title, ItemType::Task);
+
+    // Apply enriched fields first, then override with explicit args
+    if let Some(due_date) = enriched_due_date.as_deref().and_then(Due::parse) {
+        task.frontmatter.due_date = Some(due_date);
+    }
+    if let Some(priority) = enriched_priority {
+        task.frontmatter.priority = Priority::parse(&priority).unwrap_or(Priority::P2);
+    }
+    if !enriched_tags.is_empty() {
+        task.frontmatter.tags = enriched_tags;
+    }
+    if let Some(context) = enriched_context {
+        task.body = context;
+    }
+
+    // Override with explicit arguments if provided
+    if let Some(context) = args.get("context").and_then(|v| v.as_str()) {
+        task.body = context.to_string();
+    }
+
+    if let Some(due_date) = args.get("due_date").and_then(|v| v.as_str()) {
+        let due_date = Due::parse(due_date)
+            .ok_or_else(|| format!("Invalid due_date '{}', expected YYYY-MM-DD or YYYY-MM-DDTHH:MM", due_date))?;
+        task.frontmatter.due_date = Some(due_date);
+    }
+
+    if let Some(priority) = args.get("priority").and_then(|v| v.as_str()) {
+        task.frontmatter.priority = Priority::parse(priority).ok_or("Invalid priority value")?;
+    }
+
+    if let Some(tags) = args.get("tags").and_then(|v| v.as_array()) {
+        task.frontmatter.tags = tags
+            .iter()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect();
+    }
+
+    if let Some(status) = args.get("status").and_then(|v| v.as_str()) {
+        // A new task starts out open; done and archived go through complete_task and update_task
+        match Status::parse(status) {
+            Some(status @ (Status::Inbox | Status::Active | Status::Next | Status::Waiting)) => task.frontmatter.status = status,
+            _ => return Err(format!("Invalid status '{}', expected inbox, active, next or waiting", status)),
+        }
+    }
+
+    if let Some(start_date) = args.get("start_date").and_then(|v| v.as_str()) {
+        let start_date = models::parse_date(start_date)
+            .ok_or_else(|| format!("Invalid start_date '{}', expected YYYY-MM-DD", start_date))?;
+        task.frontmatter.start_date = Some(start_date);
+    }
+
+    if let Some(estimate) = args.get("estimate_minutes") {
+        let minutes = estimate.as_u64()
+            .and_then(|m| u32::try_from(m).ok())
+            .filter(|m| *m > 0)
+            .ok_or("estimate_minutes must be a positive whole number")?;
+        task.frontmatter.estimate_minutes = Some(minutes);
+    }
+
+    if let Some(project) = args.get("parent_project_id").and_then(|v| v.as_str()) {
+        let tasks = storage.load_all_tasks().map_err(|e| format!("Failed to load tasks: {}", e))?;
+        let id = models::resolve_id(&tasks, project)?;
+        let project = tasks.iter()
+            .find(|t| t.frontmatter.id == id && t.is_project())
+            .ok_or_else(|| format!("'{}' is not a project", project))?;
+        if project.frontmatter.status.is_closed() {
+            return Err(format!("Project '{}' is {}", project.frontmatter.title, project.frontmatter.status.as_str()));
+        }
+        task.frontmatter.parent_goal_id = Some(id);
+    }
+
+    if is_dry_run(&args) {
+        return dry_run(storage, &[], &mut [task]);
+    }
+
+    storage
+        .write_task(&mut task)
+        .map_err(|e| format!("Failed to write task: {}", e))?;
+    warn_on_sync_error(storage);
+
+    let mut result = json!({
+        "id": task.frontmatter.id,
+        "code": task.code(),
+        "title": task.frontmatter.title,
+        "status": "created"
+    });
+    if args.get("raw_input").is_some() {
+        if let Some(e) = enricher.last_error() {
+            result["warning"] = json!(format!("{}; enriched with offline rules instead", e));
+        }
+    }
+    Ok(result)
+}
+
+/// The fields create_task would take from `raw_input`: title, due_date,
+/// priority, tags and context, named as create_task's arguments
+fn enrich_text(enricher: &TaskEnricher, config: &AppConfig, args: Value) -> Result<Value, String> {
+    let text = args.get("text").and_then(|v| v.as_str()).ok_or("Missing text")?;
+    if text.trim().is_empty() {
+        return Err("text is empty".to_string());
+    }
+
+    let goals_context = config.goals_context();
+    let goals_ref = if goals_context.is_empty() { None } else { Some(goals_context.as_str()) };
+    let enriched = enricher.enrich_sync(text, goals_ref);
+
+    let mut result = serde_json::to_value(&enriched).map_err(|e| e.to_string())?;
+    result["llm"] = json!(enricher.is_available());
+    if let Some(e) = enricher.last_error() {
+        result["warning"] = json!(format!("{}; enriched with offline rules instead", e));
+    }
+    Ok(result)
+}
+
+fn suggest_priorities(storage: &Storage, enricher: &TaskEnricher, config: &AppConfig) -> Result<Value, String> {
+    if !enricher.is_available() {
+        return Err("suggest_priorities needs an LLM; no OpenAI API key is configured".to_string());
+    }
+    let tasks = storage
+        .load_all_tasks()
+        .map_err(|e| format!("Failed to load tasks: {}", e))?;
+    let prompt = prioritize::backlog_prompt(&tasks, config);
+    let response = enricher.complete_sync(prioritize::PRIORITIZE_PROMPT, &prompt, prioritize::PRIORITIZE_MAX_TOKENS)?;
+    let changes = prioritize::parse_changes(&tasks, &response)?;
+    Ok(json!({ "suggestions": changes }))
+}
+
+fn update_task(storage: &Storage, config: &AppConfig, args: Value) -> Result<Value, String> {
+    let id_str = args
+        .get("id")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing id")?;
+
+    let field = args
+        .get("field")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing field")?;
+
+    let value = args.get("value").ok_or("Missing value")?;
+
+    let mut tasks = storage
+        .load_all_tasks()
+        .map_err(|e| format!("Failed to load tasks: {}", e))?;
+    let id = models::resolve_id(&tasks, id_str)?;
+    let defs = tasks.iter()
+        .find(|t| t.frontmatter.id == id)
+        .map(|task| models::field_defs(&tasks, task).to_vec())
+        .unwrap_or_default();
+
+    let task = tasks
+        .iter_mut()
+        .find(|t| t.frontmatter.id == id)
+        .ok_or("Task not found")?;
+    let before = task.clone();
+
+    match field {
+        "title" => {
+            task.frontmatter.title = value.as_str().ok_or("Invalid title")?.to_string();
+        }
+        "status" => {
+            let status_str = value.as_str().ok_or("Invalid status")?;
+            task.set_status(Status::parse(status_str).ok_or("Invalid status value")?);
+        }
+        "priority" => {
+            let priority_str = value.as_str().ok_or("Invalid priority")?;
+            task.frontmatter.priority = Priority::parse(priority_str).ok_or("Invalid priority value")?;
+        }
+        "notes" => {
+            let note = value.as_str().ok_or("Invalid notes")?;
+            task.body = activity::append_note(&task.body, note, Utc::now());
+        }
+        "delegated_to" => {
+            let person = value.as_str().ok_or("Invalid delegated_to")?;
+            task.delegate(person.to_string());
+        }
+        "tags" => {
+            let tags = match value {
+                Value::Null => Vec::new(),
+                Value::String(tag) => vec![tag.clone()],
+                Value::Array(values) => values
+                    .iter()
+                    .map(|v| v.as_str().map(String::from).ok_or("Tags must be strings"))
+                    .collect::<Result<_, _>>()?,
+                _ => return Err("Invalid tags: expected an array of strings".to_string()),
+            };
+            let mode = args.get("mode").and_then(|v| v.as_str()).unwrap_or("replace");
+            let current = &mut task.frontmatter.tags;
+            match mode {
+                _ if value.is_null() => current.clear(),
+                "replace" => *current = tags,
+                "add" => current.extend(tags),
+                "remove" => current.retain(|t| !tags.iter().any(|r| r.trim_start_matches('#').eq_ignore_ascii_case(t))),
+                _ => return Err(format!("Invalid mode: {}", mode)),
+            }
+        }
+        "due_date" => {
+            task.frontmatter.due_date = match value {
+                Value::Null => None,
+                Value::String(due) => Some(Due::parse(due).ok_or_else(|| {
+                    format!("Invalid due_date '{}', expected YYYY-MM-DD or YYYY-MM-DDTHH:MM", due)
+                })?),
+                _ => return Err("Invalid due_date: expected a string or null".to_string()),
+            };
+        }
+        "goal_id" => {
+            task.frontmatter.goal_id = match value {
+                Value::Null => None,
+                Value::String(goal) => {
+                    let goal_id = uuid::Uuid::parse_str(goal).map_err(|e| format!("Invalid goal_id: {}", e))?;
+                    config.goal(goal_id).ok_or_else(|| format!("No goal with id {}", goal_id))?;
+                    Some(goal_id)
+                }
+                _ => return Err("Invalid goal_id: expected a string or null".to_string()),
+            };
+        }
+        "jira_key" => {
+            task.frontmatter.jira_key = match value {
+                Value::Null => None,
+                Value::String(key) if !key.trim().is_empty() => Some(key.trim().to_uppercase()),
+                _ => return Err("Invalid jira_key: expected an issue key or null".to_string()),
+            };
+        }
+        "custom" => {
+            let values = value.as_object().ok_or("Invalid custom: expected an object of field values")?;
+            for (name, value) in values {
+                let text = match value {
+                    Value::Null => {
+                        task.frontmatter.custom.remove(name);
+                        continue;
+                    }
+                    Value::String(text) => text.clone(),
+                    Value::Number(_) | Value::Bool(_) => value.to_string(),
+                    _ => return Err(format!("Invalid value for custom field {}: expected a string, number or null", name)),
+                };
+                task.frontmatter.custom.insert(name.clone(), text);
+            }
+            let warnings = validate::custom_fields(&task.frontmatter, &defs);
+            if !warnings.is_empty() {
+                return Err(warnings.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "));
+            }
+        }
+        "fields" => {
+            if !task.is_project() {
+                return Err("Only projects define fields".to_string());
+            }
+            task.frontmatter.fields = match value {
+                Value::Null => Vec::new(),
+                _ => serde_json::from_value::<Vec<FieldDef>>(value.clone())
+                    .map_err(|e| format!("Invalid fields: {}", e))?,
+            };
+        }
+        "habit" => {
+            task.frontmatter.habit = match value {
+                Value::Null => None,
+                Value::String(habit) => Some(Habit::parse(habit).ok_or_else(|| {
+                    format!("Invalid habit '{}', expected daily, weekdays or weekly", habit)
+                })?),
+                _ => return Err("Invalid habit: expected a string or null".to_string()),
+            };
+        }
+        _ => return Err(format!("Unknown field: {}", field)),
+    }
+
+    if is_dry_run(&args) {
+        return dry_run(storage, &[before], std::slice::from_mut(task));
+    }
+
+    storage
+        .write_task(task)
+        .map_err(|e| format!("Failed to write task: {}", e))?;
+    warn_on_sync_error(storage);
+
+    Ok(json!({ "status": "updated" }))
+}
+
+fn list_tasks(storage: &Storage, args: Value) -> Result<Value, String> {
+    let filter = task_filter(storage, &args)?;
+
+    let mut tasks = storage
+        .list_tasks(&filter)
+        .map_err(|e| format!("Failed to list tasks: {}", e))?;
+
+    // Storage already orders by priority, then newest first
+    match args.get("sort_by").and_then(|v| v.as_str()).unwrap_or("priority") {
+        "priority" => {}
+        "due" => tasks.sort_by_key(|t| {
+            let deadline = t.frontmatter.due_date.map(|due| due.deadline());
+            (deadline.is_none(), deadline)
+        }),
+        "created" => tasks.sort_by_key(|t| std::cmp::Reverse(t.frontmatter.created_at)),
+        "updated" => tasks.sort_by_key(|t| std::cmp::Reverse(t.last_touched())),
+        other => return Err(format!("Invalid sort_by: {}", other)),
+    }
+
+    let offset = match args.get("cursor").and_then(|v| v.as_str()) {
+        Some(cursor) => cursor.parse::<usize>().map_err(|_| format!("Invalid cursor: {}", cursor))?,
+        None => args.get("offset").and_then(|v| v.as_u64()).unwrap_or(0) as usize,
+    };
+    let limit = args.get("limit").and_then(|v| v.as_u64()).map(|l| l as usize);
+    let fields = match args.get("fields") {
+        Some(Value::Array(values)) => values
+            .iter()
+            .map(|v| match v.as_str() {
+                Some(field) if LIST_FIELDS.contains(&field) => Ok(field),
+                _ => Err(format!("Invalid field: {}", v)),
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        Some(_) => return Err("fields must be an array".to_string()),
+        None => DEFAULT_FIELDS.to_vec(),
+    };
+
+    let total = tasks.len();
+    let page: Vec<Value> = tasks
+        .iter()
+        .skip(offset)
+        .take(limit.unwrap_or(usize::MAX))
+        .map(|task| {
+            let mut entry = serde_json::Map::new();
+            entry.insert("id".to_string(), json!(task.frontmatter.id));
+            entry.insert("code".to_string(), json!(task.code()));
+            for field in &fields {
+                entry.insert(field.to_string(), list_field(task, field));
+            }
+            Value::Object(entry)
+        })
+        .collect();
+
+    let end = offset + page.len();
+    let next_cursor = (end < total).then(|| end.to_string());
+    Ok(json!({ "tasks": page, "total": total, "next_cursor": next_cursor }))
+}
+
+fn search_tasks(storage: &Storage, config: &AppConfig, args: Value) -> Result<Value, String> {
+    let query = args.get("query").and_then(|v| v.as_str()).ok_or("Missing query")?;
+    let limit = args.get("limit").and_then(|v| v.as_u64()).unwrap_or(SEARCH_LIMIT as u64) as usize;
+    let tasks = storage.load_all_tasks().map_err(|e| format!("Failed to load tasks: {}", e))?;
+
+    let hits = match args.get("mode").and_then(|v| v.as_str()).unwrap_or("text") {
+        "text" => storage.search(&tasks, query, limit),
+        "semantic" => {
+            if let Some(git_sync) = &storage.git_sync {
+                if let Err(err) = git_sync.exclude(EmbeddingIndex::FILE_NAME) {
+                    tracing::warn!(error = ?err, "Failed to keep embeddings out of git");
+                }
+            }
+            embeddings::semantic_search(&storage.data_dir, config, &tasks, query, limit)
+                .map_err(|e| format!("{:#}", e))?
+        }
+        other => return Err(format!("Invalid mode: {}", other)),
+    };
+    let results: Vec<Value> = hits
+        .iter()
+        .map(|hit| {
+            let mut entry = json!({ "id": hit.task.frontmatter.id, "code": hit.task.code() });
+            for field in DEFAULT_FIELDS {
+                entry[field] = list_field(hit.task, field);
+            }
+            entry["snippet"] = json!(hit.snippet);
+            entry
+        })
+        .collect();
+    Ok(json!({ "tasks": results }))
+}
+
+/// Results `search_tasks` returns when `limit` isn't given
+const SEARCH_LIMIT: usize = 20;
+
+/// Fields `list_tasks` returns when `fields` isn't given
+const DEFAULT_FIELDS: [&str; 6] = ["title", "status", "priority", "tags", "due_date", "delegated_to"];
+
+/// Fields `list_tasks` can return besides `id`
+const LIST_FIELDS: [&str; 10] = ["title", "status", "priority", "tags", "due_date", "delegated_to", "created_at", "completed_at", "updated_at", "custom"];
+
+fn list_field(task: &TaskItem, field: &str) -> Value {
+    let fm = &task.frontmatter;
+    match field {
+        "title" => json!(fm.title),
+        "status" => json!(fm.status.as_str()),
+        "priority" => json!(fm.priority.as_str()),
+        "tags" => json!(fm.tags),
+        "due_date" => json!(fm.due_date),
+        "delegated_to" => json!(fm.delegated_to),
+        "created_at" => json!(fm.created_at),
+        "completed_at" => json!(fm.completed_at),
+        "updated_at" => json!(task.last_touched()),
+        "custom" => json!(fm.custom),
+        _ => Value::Null,
+    }
+}
+
+/// Build a filter from `status`, `tag` and `query` arguments
+fn task_filter(storage: &Storage, args: &Value) -> Result<TaskFilter, String> {
+    let mut filter = TaskFilter::default();
+
+    if let Some(status_str) = args.get("status").and_then(|v| v.as_str()) {
+        filter.status = Some(Status::parse(status_str).ok_or("Invalid status")?);
+    }
+
+    if let Some(tag) = args.get("tag").and_then(|v| v.as_str()) {
+        filter.tags.push(tag.to_string());
+    }
+
+    if let Some(query) = args.get("query").and_then(|v| v.as_str()) {
+        let projects = storage
+            .list_tasks(&TaskFilter { item_type: Some(ItemType::Project), ..Default::default() })
+            .map_err(|e| format!("Failed to list projects: {}", e))?;
+        let expr = FilterExpr::parse(query, |name| {
+            let needle = name.to_lowercase().replace('-', " ");
+            projects.iter()
+                .find(|p| p.frontmatter.title.to_lowercase().contains(&needle))
+                .map(|p| p.frontmatter.id)
+        })?;
+        filter.expr = Some(expr);
+    }
+
+    Ok(filter)
+}
+
+fn bulk_update(storage: &Storage, args: Value) -> Result<Value, String> {
+    let criteria = args.get("filter").ok_or("Missing filter")?;
+    if ["status", "tag", "ids", "query"].iter().all(|key| criteria.get(key).is_none()) {
+        return Err("Filter needs at least one of status, tag, ids or query".to_string());
+    }
+    let filter = task_filter(storage, criteria)?;
+
+    let ids = match criteria.get("ids") {
+        Some(Value::Array(values)) => Some(
+            values
+                .iter()
+                .map(|v| {
+                    let id = v.as_str().ok_or("ids must be strings")?;
+                    storage.resolve_id(id).map_err(|e| e.to_string())
+                })
+                .collect::<Result<Vec<_>, String>>()?,
+        ),
+        Some(_) => return Err("ids must be an array".to_string()),
+        None => None,
+    };
+
+    let operation = args.get("operation").and_then(|v| v.as_str()).ok_or("Missing operation")?;
+    let value = args.get("value").and_then(|v| v.as_str());
+    let require_value = || value.ok_or_else(|| format!("{} needs a value", operation));
+    let apply: Box<dyn Fn(&mut TaskItem)> = match operation {
+        "set_status" => {
+            let status = Status::parse(require_value()?).ok_or("Invalid status value")?;
+            Box::new(move |task| task.set_status(status.clone()))
+        }
+        "archive" => Box::new(|task| task.set_status(Status::Archived)),
+        "add_tag" => {
+            let tag = require_value()?.to_string();
+            Box::new(move |task| task.frontmatter.tags.push(tag.clone()))
+        }
+        "set_priority" => {
+            let priority = Priority::parse(require_value()?).ok_or("Invalid priority value")?;
+            Box::new(move |task| task.frontmatter.priority = priority.clone())
+        }
+        _ => return Err(format!("Unknown operation: {}", operation)),
+    };
+
+    let mut tasks = storage
+        .list_tasks(&filter)
+        .map_err(|e| format!("Failed to list tasks: {}", e))?;
+    if let Some(ids) = &ids {
+        tasks.retain(|t| ids.contains(&t.frontmatter.id));
+    }
+    let before = tasks.clone();
+
+    // Only write tasks the operation actually changes
+    let mut changed: Vec<TaskItem> = tasks
+        .into_iter()
+        .filter_map(|mut task| {
+            let fields = |t: &TaskItem| (t.frontmatter.status.clone(), t.frontmatter.priority.clone(), t.frontmatter.tags.clone());
+            let before = fields(&task);
+            apply(&mut task);
+            validate::normalize_tags(&mut task.frontmatter.tags);
+            (fields(&task) != before).then_some(task)
+        })
+        .collect();
+
+    if is_dry_run(&args) {
+        return dry_run(storage, &before, &mut changed);
+    }
+
+    if !changed.is_empty() {
+        storage.snapshot("bulk-update").map_err(|e| format!("{:#}", e))?;
+        let message = format!("Bulk update: {} on {} task(s)", operation, changed.len());
+        storage
+            .write_tasks(&mut changed, &message)
+            .map_err(|e| format!("Failed to write tasks, nothing was changed: {:#}", e))?;
+        warn_on_sync_error(storage);
+    }
+
+    Ok(json!({
+        "updated": changed.len(),
+        "ids": changed.iter().map(|t| t.frontmatter.id).collect::<Vec<_>>(),
+    }))
+}
+
+fn reparent_tasks(storage: &Storage, args: Value) -> Result<Value, String> {
+    let tasks = storage.load_all_tasks().map_err(|e| format!("Failed to load tasks: {}", e))?;
+    let resolve = |text: &str| models::resolve_id(&tasks, text);
+    let project = |text: &str| -> Result<&TaskItem, String> {
+        let id = resolve(text)?;
+        tasks.iter()
+            .find(|t| t.frontmatter.id == id && t.is_project())
+            .ok_or_else(|| format!("'{}' is not a project", text))
+    };
+
+    let mut selected: Vec<uuid::Uuid> = Vec::new();
+    match args.get("ids") {
+        Some(Value::Array(values)) => {
+            for value in values {
+                selected.push(resolve(value.as_str().ok_or("ids must be strings")?)?);
+            }
+        }
+        Some(_) => return Err("ids must be an array".to_string()),
+        None => {}
+    }
+    if let Some(from) = args.get("from_project").and_then(|v| v.as_str()) {
+        let from = project(from)?.frontmatter.id;
+        selected.extend(models::open_children(&tasks, from).iter().map(|t| t.frontmatter.id));
+    }
+    if args.get("orphaned").and_then(|v| v.as_bool()).unwrap_or(false) {
+        selected.extend(models::orphans(&tasks).iter().map(|(t, _)| t.frontmatter.id));
+    }
+    if ["ids", "from_project", "orphaned"].iter().all(|key| args.get(key).is_none()) {
+        return Err("Give ids, from_project or orphaned".to_string());
+    }
+
+    let target = match args.get("to_project") {
+        None | Some(Value::Null) => None,
+        Some(Value::String(text)) => {
+            let target = project(text)?;
+            if target.frontmatter.status.is_closed() {
+                return Err(format!("Project '{}' is {}", target.frontmatter.title, target.frontmatter.status.as_str()));
+            }
+            Some(target.frontmatter.id)
+        }
+        Some(_) => return Err("to_project must be a string or null".to_string()),
+    };
+
+    let mut changed: Vec<TaskItem> = tasks.iter()
+        .filter(|t| selected.contains(&t.frontmatter.id))
+        .filter(|t| t.frontmatter.parent_goal_id != target)
+        .cloned()
+        .collect();
+    if changed.iter().any(|t| Some(t.frontmatter.id) == target) {
+        return Err("A project can't be moved into itself".to_string());
+    }
+    for task in &mut changed {
+        task.frontmatter.parent_goal_id = target;
+    }
+
+    if is_dry_run(&args) {
+        return dry_run(storage, &tasks, &mut changed);
+    }
+
+    if !changed.is_empty() {
+        let message = match target {
+            Some(_) => format!("Move {} task(s) to another project", changed.len()),
+            None => format!("Detach {} task(s) from their project", changed.len()),
+        };
+        storage
+            .write_tasks(&mut changed, &message)
+            .map_err(|e| format!("Failed to write tasks, nothing was changed: {:#}", e))?;
+        warn_on_sync_error(storage);
+    }
+
+    Ok(json!({
+        "updated": changed.len(),
+        "ids": changed.iter().map(|t| t.frontmatter.id).collect::<Vec<_>>(),
+        "project_id": target,
+    }))
+}
+
+fn read_task_details(storage: &Storage, args: Value) -> Result<Value, String> {
+    let id_str = args
+        .get("id")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing id")?;
+
+    let tasks = storage
+        .load_all_tasks()
+        .map_err(|e| format!("Failed to load tasks: {}", e))?;
+    let id = models::resolve_id(&tasks, id_str)?;
+
+    let task = tasks
+        .iter()
+        .find(|t| t.frontmatter.id == id)
+        .ok_or("Task not found")?;
+
+    Ok(json!({
+        "id": task.frontmatter.id,
+        "code": task.code(),
+        "title": task.frontmatter.title,
+        "type": match task.frontmatter.item_type {
+            ItemType::Task => "task",
+            ItemType::Goal => "goal",
+            ItemType::Note => "note",
+            ItemType::Project => "project",
+        },
+        "status": task.frontmatter.status.as_str(),
+        "priority": task.frontmatter.priority.as_str(),
+        "tags": task.frontmatter.tags,
+        "due_date": task.frontmatter.due_date,
+        "start_date": task.frontmatter.start_date,
+        "estimate_minutes": task.frontmatter.estimate_minutes,
+        "delegated_to": task.frontmatter.delegated_to,
+        "delegated_at": task.frontmatter.delegated_at,
+        "jira_key": task.frontmatter.jira_key,
+        "custom": task.frontmatter.custom,
+        "fields": task.frontmatter.fields,
+        "habit": task.frontmatter.habit,
+        "streak": task.frontmatter.habit.map(|_| task.current_streak(models::today())),
+        "best_streak": task.frontmatter.habit.map(|_| task.best_streak()),
+        "created_at": task.frontmatter.created_at,
+        "completed_at": task.frontmatter.completed_at,
+        "updated_at": task.last_touched(),
+        "notes": activity::notes(&task.body).iter().map(|n| json!({ "at": n.at, "text": n.text })).collect::<Vec<_>>(),
+        "body": task.body,
+    }))
+}
+
+fn complete_task(storage: &Storage, config: &AppConfig, args: Value) -> Result<Value, String> {
+    let id_str = args
+        .get("id")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing id")?;
+
+    let mut tasks = storage
+        .load_all_tasks()
+        .map_err(|e| format!("Failed to load tasks: {}", e))?;
+    let id = models::resolve_id(&tasks, id_str)?;
+
+    let task = tasks
+        .iter_mut()
+        .find(|t| t.frontmatter.id == id)
+        .ok_or("Task not found")?;
+
+    let before = task.clone();
+    task.set_status(Status::Done);
+
+    if is_dry_run(&args) {
+        return dry_run(storage, &[before], std::slice::from_mut(task));
+    }
+
+    storage
+        .write_task(task)
+        .map_err(|e| format!("Failed to write task: {}", e))?;
+    warn_on_sync_error(storage);
+
+    if task.frontmatter.habit.is_some() {
+        return Ok(json!({ "status": "checked_off", "streak": task.current_streak(models::today()) }));
+    }
+    let mut result = json!({ "status": "completed" });
+    // The task stays done even if the linked issue can't be moved
+    if let (Some(jira_config), Some(key)) = (&config.jira, &task.frontmatter.jira_key) {
+        match jira::complete(jira_config, key) {
+            Ok(Some(status)) => result["jira"] = json!({ "key": key, "status": status }),
+            Ok(None) => {}
+            Err(e) => result["jira"] = json!({ "key": key, "error": format!("{:#}", e) }),
+        }
+    }
+    Ok(result)
+}
+
+/// List available resources
+pub fn list_resources() -> Result<Value, String> {
+    Ok(json!({
+        "resources": [
+            {
+                "uri": "tasktui://daily_summary",
+                "name": "Daily Summary",
+                "description": "A summary of today's high-priority tasks",
+                "mimeType": "application/json"
+            },
+            {
+                "uri": "tasktui://projects",
+                "name": "Projects",
+                "description": "All projects with their dates, progress and task counts",
+                "mimeType": "application/json"
+            },
+            {
+                "uri": "tasktui://retro",
+                "name": "This Week's Retrospective",
+                "description": "This week's completions by workstream, stalled tasks and goal progress, plus the saved review if one was written",
+                "mimeType": "application/json"
+            }
+        ],
+        "resourceTemplates": resource_templates(),
+    }))
+}
+
+/// List resource templates
+pub fn list_resource_templates() -> Result<Value, String> {
+    Ok(json!({ "resourceTemplates": resource_templates() }))
+}
+
+fn resource_templates() -> Value {
+    json!([
+        {
+            "uriTemplate": "tasktui://project/{id}",
+            "name": "Project",
+            "description": "A project's tasks with Gantt start/end dates and progress; {id} may be a short code",
+            "mimeType": "application/json"
+        },
+        {
+            "uriTemplate": "tasktui://project/{id}/flow",
+            "name": "Project Flow",
+            "description": "Daily burndown (open tasks and the ideal line) and cumulative flow (tasks per status) series for a project, for plotting",
+            "mimeType": "application/json"
+        },
+        {
+            "uriTemplate": "tasktui://tag/{name}",
+            "name": "Tag",
+            "description": "Tasks with a tag, grouped by status",
+            "mimeType": "application/json"
+        },
+        {
+            "uriTemplate": "tasktui://retro/{week}",
+            "name": "Weekly Retrospective",
+            "description": "Retrospective facts for an ISO week (YYYY-WW), plus the saved review if one was written",
+            "mimeType": "application/json"
+        }
+    ])
+}
+
+/// Read a resource
+pub fn read_resource(storage: &Storage, config: &AppConfig, params: Value) -> Result<Value, String> {
+    let uri = params
+        .get("uri")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing uri")?;
+
+    if let Some(id) = uri.strip_prefix("tasktui://project/").and_then(|rest| rest.strip_suffix("/flow")) {
+        let id = storage.resolve_id(id).map_err(|e| e.to_string())?;
+        return flow_resource(storage, id);
+    }
+    if let Some(id) = uri.strip_prefix("tasktui://project/") {
+        let id = storage.resolve_id(id).map_err(|e| e.to_string())?;
+        return project_resource(storage, id);
+    }
+    if let Some(tag) = uri.strip_prefix("tasktui://tag/") {
+        return tag_resource(storage, tag);
+    }
+    if let Some(week) = uri.strip_prefix("tasktui://retro/") {
+        let monday = retro::parse_week(week).ok_or_else(|| format!("Invalid week '{}', expected YYYY-WW", week))?;
+        return retro_resource(storage, config, monday);
+    }
+
+    match uri {
+        "tasktui://daily_summary" => daily_summary(storage),
+        "tasktui://projects" => projects_resource(storage),
+        "tasktui://retro" => retro_resource(storage, config, retro::week_start(models::today())),
+        _ => Err(format!("Unknown resource: {}", uri)),
+    }
+}
+
+fn projects_resource(storage: &Storage) -> Result<Value, String> {
+    let tasks = storage
+        .load_all_tasks()
+        .map_err(|e| format!("Failed to load tasks: {}", e))?;
+
+    let projects: Vec<Value> = tasks
+        .iter()
+        .filter(|t| t.is_project())
+        .map(|project| {
+            let id = project.frontmatter.id;
+            let children = tasks.iter().filter(|t| t.frontmatter.parent_goal_id == Some(id));
+            let (total, done) = children.fold((0, 0), |(total, done), t| {
+                (total + 1, done + t.frontmatter.status.is_closed() as usize)
+            });
+            json!({
+                "id": id,
+                "title": project.frontmatter.title,
+                "status": project.frontmatter.status.as_str(),
+                "start_date": project.frontmatter.start_date,
+                "end_date": project.frontmatter.end_date,
+                "progress": models::project_progress(&tasks, id),
+                "tasks": { "total": total, "done": done },
+            })
+        })
+        .collect();
+
+    Ok(json!({ "projects": projects }))
+}
+
+fn project_resource(storage: &Storage, id: uuid::Uuid) -> Result<Value, String> {
+    let tasks = storage
+        .load_all_tasks()
+        .map_err(|e| format!("Failed to load tasks: {}", e))?;
+    let project = tasks
+        .iter()
+        .find(|t| t.frontmatter.id == id && t.is_project())
+        .ok_or("Project not found")?;
+
+    let today = models::today();
+    let children: Vec<Value> = tasks
+        .iter()
+        .filter(|t| t.frontmatter.parent_goal_id == Some(id))
+        .map(|task| {
+            let (start, end) = task.schedule(today);
+            json!({
+                "id": task.frontmatter.id,
+                "title": task.frontmatter.title,
+                "status": task.frontmatter.status.as_str(),
+                "priority": task.frontmatter.priority.as_str(),
+                "due_date": task.frontmatter.due_date,
+                "gantt": { "start": start, "end": end, "progress": task.percent_done() },
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "project": {
+            "id": id,
+            "title": project.frontmatter.title,
+            "status": project.frontmatter.status.as_str(),
+            "start_date": project.frontmatter.start_date,
+            "end_date": project.frontmatter.end_date,
+            "progress": models::project_progress(&tasks, id),
+            "body": project.body,
+        },
+        "tasks": children,
+    }))
+}
+
+fn flow_resource(storage: &Storage, id: uuid::Uuid) -> Result<Value, String> {
+    let tasks = storage
+        .load_all_tasks()
+        .map_err(|e| format!("Failed to load tasks: {}", e))?;
+    let project = tasks
+        .iter()
+        .find(|t| t.frontmatter.id == id && t.is_project())
+        .ok_or("Project not found")?;
+
+    let mut flow = ProjectFlow::new(&tasks, project, models::today()).to_json();
+    flow["project"] = json!({ "id": id, "title": project.frontmatter.title });
+    Ok(flow)
+}
+
+fn retro_resource(storage: &Storage, config: &AppConfig, monday: chrono::NaiveDate) -> Result<Value, String> {
+    let tasks = storage
+        .load_all_tasks()
+        .map_err(|e| format!("Failed to load tasks: {}", e))?;
+    let review = WeekReview::new(&tasks, config, monday);
+    let saved = fs::read_to_string(retro::review_path(&storage.data_dir, &review.week)).ok();
+
+    Ok(json!({
+        "review": review,
+        "markdown": saved.unwrap_or_else(|| review.to_markdown()),
+    }))
+}
+
+fn tag_resource(storage: &Storage, tag: &str) -> Result<Value, String> {
+    let tag = tag.trim_start_matches('#').to_lowercase();
+    let filter = TaskFilter { tags: vec![tag.clone()], ..Default::default() };
+    let tasks = storage
+        .list_tasks(&filter)
+        .map_err(|e| format!("Failed to list tasks: {}", e))?;
+
+    let mut by_status = serde_json::Map::new();
+    for status in [Status::Inbox, Status::Active, Status::Next, Status::Waiting, Status::Done, Status::Archived] {
+        let group: Vec<Value> = tasks
+            .iter()
+            .filter(|t| t.frontmatter.status == status)
+            .map(|t| json!({
+                "id": t.frontmatter.id,
+                "title": t.frontmatter.title,
+                "priority": t.frontmatter.priority.as_str(),
+                "due_date": t.frontmatter.due_date,
+            }))
+            .collect();
+        if !group.is_empty() {
+            by_status.insert(status.as_str().to_string(), Value::Array(group));
+        }
+    }
+
+    Ok(json!({ "tag": tag, "count": tasks.len(), "tasks": by_status }))
+}
+
+fn daily_summary(storage: &Storage) -> Result<Value, String> {
+    let filter = TaskFilter {
+        status: Some(Status::Active),
+        limit: Some(10),
+        ..Default::default()
+    };
+
+    let tasks = storage
+        .list_tasks(&filter)
+        .map_err(|e| format!("Failed to list tasks: {}", e))?;
+
+    let high_priority: Vec<_> = tasks
+        .iter()
+        .filter(|t| t.frontmatter.priority.rank() <= Priority::P1.rank())
+        .collect();
+
+    let due_today: Vec<_> = tasks.iter().filter(|t| t.is_due_today()).collect();
+
+    Ok(json!({
+        "summary": {
+            "total_active": tasks.len(),
+            "high_priority_count": high_priority.len(),
+            "due_today_count": due_today.len(),
+            "high_priority_tasks": high_priority.iter().map(|t| {
+                json!({
+                    "id": t.frontmatter.id,
+                    "title": t.frontmatter.title,
+                    "tags": t.frontmatter.tags,
+                })
+            }).collect::<Vec<_>>(),
+            "due_today_tasks": due_today.iter().map(|t| {
+                json!({
+                    "id": t.frontmatter.id,
+                    "title": t.frontmatter.title,
+                    "tags": t.frontmatter.tags,
+                })
+            }).collect::<Vec<_>>(),
+        }
+    }))
+}
+
+/// Git sync failures don't fail the tool call; record them in the log
+fn is_dry_run(args: &Value) -> bool {
+    args.get("dry_run").and_then(|v| v.as_bool()).unwrap_or(false)
+}
+
+/// The result of a `dry_run` call: what writing `after` would change compared
+/// to `before`, per task, after the same checks a real write runs
+fn dry_run(storage: &Storage, before: &[TaskItem], after: &mut [TaskItem]) -> Result<Value, String> {
+    let mut tasks = Vec::new();
+    for task in after.iter_mut() {
+        storage
+            .preview(task)
+            .map_err(|e| format!("'{}' can't be written: {:#}", task.frontmatter.title, e))?;
+        let old = before.iter().find(|t| t.frontmatter.id == task.frontmatter.id);
+        let changes = field_changes(old, task);
+        if old.is_none() || !changes.is_empty() {
+            tasks.push(json!({
+                "id": task.frontmatter.id,
+                "code": task.code(),
+                "title": task.frontmatter.title,
+                "created": old.is_none(),
+                "changes": changes,
+            }));
+        }
+    }
+    Ok(json!({ "dry_run": true, "would_change": tasks.len(), "tasks": tasks }))
+}
+
+/// Fields that differ between two versions of a task, as `{field: {from, to}}`.
+/// Bookkeeping stamps are left out.
+fn field_changes(before: Option<&TaskItem>, after: &TaskItem) -> serde_json::Map<String, Value> {
+    let fields = |task: Option<&TaskItem>| {
+        let mut fields = match task.map(|t| serde_json::to_value(&t.frontmatter)) {
+            Some(Ok(Value::Object(fields))) => fields,
+            _ => serde_json::Map::new(),
+        };
+        fields.insert("body".to_string(), json!(task.map(|t| t.body.as_str())));
+        for stamp in ["updated_at", "code"] {
+            fields.remove(stamp);
+        }
+        fields
+    };
+    let (old, new) = (fields(before), fields(Some(after)));
+
+    // Unset optional fields aren't serialized, so look at both sides
+    let mut changes = serde_json::Map::new();
+    for field in new.keys().chain(old.keys()) {
+        let (from, to) = (old.get(field).unwrap_or(&Value::Null), new.get(field).unwrap_or(&Value::Null));
+        if from != to {
+            changes.insert(field.clone(), json!({ "from": from, "to": to }));
+        }
+    }
+    changes
+}
+
+fn warn_on_sync_error(storage: &Storage) {
+    if let Some(e) = storage.last_sync_error() {
+        tracing::warn!(error = %e, "Git sync failed, changes saved locally");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::FieldType;
+    use tempfile::TempDir;
+
+    fn update(storage: &Storage, id: uuid::Uuid, field: &str, value: Value, mode: Option<&str>) -> Result<TaskItem, String> {
+        update_task(storage, &AppConfig::default(), json!({ "id": id.to_string(), "field": field, "value": value, "mode": mode }))?;
+        let path = storage.data_dir.join(format!("{}.md", id));
+        storage.parse_file(&path).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_update_tags_and_due_date() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut task = TaskItem::new("Tagged".to_string(), ItemType::Task);
+        storage.write_task(&mut task).unwrap();
+        let id = task.frontmatter.id;
+
+        let task = update(&storage, id, "tags", json!(["work", "Urgent"]), None).unwrap();
+        assert_eq!(task.frontmatter.tags, ["work", "urgent"]);
+        let task = update(&storage, id, "tags", json!("home"), Some("add")).unwrap();
+        assert_eq!(task.frontmatter.tags, ["work", "urgent", "home"]);
+        let task = update(&storage, id, "tags", json!(["#urgent"]), Some("remove")).unwrap();
+        assert_eq!(task.frontmatter.tags, ["work", "home"]);
+        let task = update(&storage, id, "tags", Value::Null, None).unwrap();
+        assert!(task.frontmatter.tags.is_empty());
+
+        let task = update(&storage, id, "due_date", json!("2025-03-02T15:00"), None).unwrap();
+        assert_eq!(task.frontmatter.due_date.map(|d| d.to_string()).as_deref(), Some("2025-03-02T15:00"));
+        assert!(update(&storage, id, "due_date", json!("next week"), None).is_err());
+        let task = update(&storage, id, "due_date", Value::Null, None).unwrap();
+        assert_eq!(task.frontmatter.due_date, None);
+
+        // Goal links must name a configured goal
+        assert!(update(&storage, id, "goal_id", json!(uuid::Uuid::new_v4().to_string()), None).is_err());
+        let task = update(&storage, id, "goal_id", Value::Null, None).unwrap();
+        assert_eq!(task.frontmatter.goal_id, None);
+
+        // Short codes work wherever an id does
+        let details = read_task_details(&storage, json!({ "id": task.code() })).unwrap();
+        assert_eq!(details["id"], json!(id));
+        assert_eq!(details["code"], json!(task.code()));
+        assert!(complete_task(&storage, &AppConfig::default(), json!({ "id": "#zzzz" })).is_err());
+    }
+
+    #[test]
+    fn test_update_custom_fields() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut project = TaskItem::new_project("Support".to_string());
+        storage.write_task(&mut project).unwrap();
+        let mut task = TaskItem::new("Refund order".to_string(), ItemType::Task);
+        task.frontmatter.parent_goal_id = Some(project.frontmatter.id);
+        storage.write_task(&mut task).unwrap();
+        let id = task.frontmatter.id;
+
+        assert!(update(&storage, id, "fields", json!([{ "name": "points" }]), None).is_err());
+        let fields = json!([{ "name": "severity", "type": "choice", "values": ["low", "high"] }, { "name": "points", "type": "number" }]);
+        let project = update(&storage, project.frontmatter.id, "fields", fields, None).unwrap();
+        assert_eq!(project.frontmatter.fields[0].kind, FieldType::Choice);
+
+        let task = update(&storage, id, "custom", json!({ "severity": "high", "points": 3 }), None).unwrap();
+        assert_eq!(task.frontmatter.custom.get("points").map(String::as_str), Some("3"));
+        let err = update(&storage, id, "custom", json!({ "severity": "urgent" }), None).unwrap_err();
+        assert!(err.contains("severity"), "{}", err);
+        let task = update(&storage, id, "custom", json!({ "points": null }), None).unwrap();
+        assert_eq!(task.frontmatter.custom.keys().collect::<Vec<_>>(), ["severity"]);
+
+        let details = read_task_details(&storage, json!({ "id": id.to_string() })).unwrap();
+        assert_eq!(details["custom"], json!({ "severity": "high" }));
+    }
+
+    #[test]
+    fn test_habit() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut task = TaskItem::new("Stretch".to_string(), ItemType::Task);
+        storage.write_task(&mut task).unwrap();
+        let id = task.frontmatter.id;
+
+        assert!(update(&storage, id, "habit", json!("hourly"), None).is_err());
+        let task = update(&storage, id, "habit", json!("daily"), None).unwrap();
+        assert_eq!(task.frontmatter.habit, Some(Habit::Daily));
+
+        let result = complete_task(&storage, &AppConfig::default(), json!({ "id": id.to_string() })).unwrap();
+        assert_eq!(result, json!({ "status": "checked_off", "streak": 1 }));
+        let details = read_task_details(&storage, json!({ "id": id.to_string() })).unwrap();
+        assert_eq!(details["status"], "active");
+        assert_eq!((details["streak"].clone(), details["best_streak"].clone()), (json!(1), json!(1)));
+    }
+
+    #[test]
+    fn test_dry_run() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut task = TaskItem::new("Draft".to_string(), ItemType::Task);
+        task.frontmatter.due_date = Due::parse("2025-03-02");
+        storage.write_task(&mut task).unwrap();
+        let id = task.frontmatter.id.to_string();
+        let on_disk = || fs::read_to_string(temp_dir.path().join(format!("{}.md", id))).unwrap();
+        let original = on_disk();
+
+        let result = update_task(&storage, &AppConfig::default(), json!({
+            "id": id, "field": "due_date", "value": null, "dry_run": true,
+        })).unwrap();
+        assert_eq!(result["dry_run"], true);
+        assert_eq!(result["tasks"][0]["changes"]["due_date"], json!({ "from": "2025-03-02", "to": null }));
+
+        let result = complete_task(&storage, &AppConfig::default(), json!({ "id": id, "dry_run": true })).unwrap();
+        assert_eq!(result["tasks"][0]["changes"]["status"], json!({ "from": "active", "to": "done" }));
+
+        let result = bulk_update(&storage, json!({
+            "filter": { "ids": [id] }, "operation": "add_tag", "value": "Review", "dry_run": true,
+        })).unwrap();
+        assert_eq!(result["would_change"], 1);
+        assert_eq!(result["tasks"][0]["changes"]["tags"]["to"], json!(["review"]));
+        assert_eq!(on_disk(), original);
+
+        let enricher = TaskEnricher::new(None);
+        let result = create_task(&storage, &enricher, &AppConfig::default(), json!({ "title": "New", "dry_run": true })).unwrap();
+        assert_eq!(result["tasks"][0]["created"], true);
+        assert_eq!(storage.load_all_tasks().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_enrich_text() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+        let enricher = TaskEnricher::new(None);
+        let config = AppConfig::default();
+        let result = call_tool(&storage, &enricher, &config, json!({
+            "name": "enrich_text",
+            "arguments": { "text": "urgent call the bank tomorrow" },
+        })).unwrap();
+        assert_eq!(result["priority"], "p1");
+        assert!(result["due_date"].is_string());
+        assert_eq!(result["llm"], false);
+        assert!(!result["title"].as_str().unwrap().contains("tomorrow"));
+        assert!(storage.load_all_tasks().unwrap().is_empty());
+        assert!(enrich_text(&enricher, &config, json!({ "text": " " })).is_err());
+        assert!(suggest_priorities(&storage, &enricher, &config).unwrap_err().contains("needs an LLM"));
+    }
+
+    #[test]
+    fn test_create_task_placement() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+        let enricher = TaskEnricher::new(None);
+        let config = AppConfig::default();
+        let mut project = TaskItem::new_project("Launch".to_string());
+        storage.write_task(&mut project).unwrap();
+
+        let result = create_task(&storage, &enricher, &config, json!({
+            "title": "Write copy",
+            "status": "next",
+            "parent_project_id": project.code(),
+            "start_date": "2025-03-10",
+            "estimate_minutes": 90,
+        })).unwrap();
+        let details = read_task_details(&storage, json!({ "id": result["code"] })).unwrap();
+        assert_eq!(details["status"], "next");
+        assert_eq!(details["start_date"], "2025-03-10");
+        assert_eq!(details["estimate_minutes"], 90);
+        let task = storage.load_all_tasks().unwrap().into_iter().find(|t| t.frontmatter.title == "Write copy").unwrap();
+        assert_eq!(task.frontmatter.parent_goal_id, Some(project.frontmatter.id));
+
+        let create = |args: Value| create_task(&storage, &enricher, &config, args);
+        assert!(create(json!({ "title": "x", "status": "done" })).is_err());
+        assert!(create(json!({ "title": "x", "estimate_minutes": -5 })).is_err());
+        assert!(create(json!({ "title": "x", "start_date": "soon" })).is_err());
+        assert!(create(json!({ "title": "x", "parent_project_id": result["code"] })).unwrap_err().contains("not a project"));
+        project.set_status(Status::Archived);
+        storage.write_task(&mut project).unwrap();
+        assert!(create(json!({ "title": "x", "parent_project_id": project.code() })).is_err());
+        assert_eq!(storage.load_all_tasks().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_reparent_tasks() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut old = TaskItem::new_project("Old".to_string());
+        old.set_status(Status::Archived);
+        let mut new = TaskItem::new_project("New".to_string());
+        storage.write_task(&mut old).unwrap();
+        storage.write_task(&mut new).unwrap();
+        let mut ids = Vec::new();
+        for parent in [old.frontmatter.id, uuid::Uuid::new_v4()] {
+            let mut task = TaskItem::new("Child".to_string(), ItemType::Task);
+            task.frontmatter.parent_goal_id = Some(parent);
+            storage.write_task(&mut task).unwrap();
+            ids.push(task.frontmatter.id);
+        }
+
+        let result = reparent_tasks(&storage, json!({ "orphaned": true, "to_project": new.code() })).unwrap();
+        assert_eq!(result["updated"], 2);
+        let tasks = storage.load_all_tasks().unwrap();
+        assert!(models::orphans(&tasks).is_empty());
+        assert_eq!(models::open_children(&tasks, new.frontmatter.id).len(), 2);
+
+        let result = reparent_tasks(&storage, json!({ "ids": [ids[0].to_string()], "to_project": null })).unwrap();
+        assert_eq!(result["updated"], 1);
+        let task = storage.parse_file(&storage.data_dir.join(format!("{}.md", ids[0]))).unwrap();
+        assert_eq!(task.frontmatter.parent_goal_id, None);
+
+        assert!(reparent_tasks(&storage, json!({ "ids": [ids[1].to_string()], "to_project": old.code() })).is_err());
+        assert!(reparent_tasks(&storage, json!({ "to_project": new.code() })).is_err());
+    }
+
+    #[test]
+    fn test_bulk_update() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut ids = Vec::new();
+        for (title, completed) in [("Old", "2025-02-10T09:00:00Z"), ("Recent", "2025-03-05T09:00:00Z")] {
+            let mut task = TaskItem::new(title.to_string(), ItemType::Task);
+            task.set_status(Status::Done);
+            task.frontmatter.completed_at = Some(completed.parse().unwrap());
+            storage.write_task(&mut task).unwrap();
+            ids.push(task.frontmatter.id);
+        }
+        let mut open = TaskItem::new("Open".to_string(), ItemType::Task);
+        storage.write_task(&mut open).unwrap();
+
+        let result = bulk_update(&storage, json!({
+            "filter": { "status": "done", "query": "completed:..2025-02-28" },
+            "operation": "archive",
+        })).unwrap();
+        assert_eq!(result["updated"], 1);
+        assert_eq!(result["ids"], json!([ids[0]]));
+        let archived = storage.list_tasks(&TaskFilter { status: Some(Status::Archived), ..Default::default() }).unwrap();
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].frontmatter.title, "Old");
+
+        // Tasks that already have the tag aren't counted
+        let all = json!([ids[0], ids[1], open.frontmatter.id]);
+        let result = bulk_update(&storage, json!({ "filter": { "ids": [ids[1]] }, "operation": "add_tag", "value": "Review" })).unwrap();
+        assert_eq!(result["updated"], 1);
+        let result = bulk_update(&storage, json!({ "filter": { "ids": all }, "operation": "add_tag", "value": "review" })).unwrap();
+        assert_eq!(result["updated"], 2);
+
+        assert!(bulk_update(&storage, json!({ "filter": {}, "operation": "archive" })).is_err());
+
+        // In strict mode an invalid tag fails the whole batch before anything is written
+        let mut storage = storage;
+        storage.strict = true;
+        assert!(bulk_update(&storage, json!({ "filter": { "ids": all }, "operation": "add_tag", "value": "a b" })).is_err());
+        let tagged = storage.list_tasks(&TaskFilter { tags: vec!["review".to_string()], ..Default::default() }).unwrap();
+        assert!(tagged.iter().all(|t| t.frontmatter.tags == ["review"]));
+        assert!(bulk_update(&storage, json!({ "filter": { "tag": "review" }, "operation": "set_priority", "value": "urgent" })).is_err());
+    }
+
+    #[test]
+    fn test_list_tasks_paging_and_sorting() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+        for (title, due) in [("C", None), ("A", Some("2025-03-01")), ("B", Some("2025-02-01T09:00"))] {
+            let mut task = TaskItem::new(title.to_string(), ItemType::Task);
+            task.frontmatter.due_date = due.and_then(Due::parse);
+            storage.write_task(&mut task).unwrap();
+        }
+
+        let page = list_tasks(&storage, json!({ "sort_by": "due", "limit": 2, "fields": ["title"] })).unwrap();
+        assert_eq!(page["total"], 3);
+        assert_eq!(page["tasks"].as_array().unwrap().iter().map(|t| t["title"].clone()).collect::<Vec<_>>(), [json!("B"), json!("A")]);
+        // id and code are always included
+        assert_eq!(page["tasks"][0].as_object().unwrap().len(), 3);
+        assert_eq!(page["next_cursor"], "2");
+
+        let rest = list_tasks(&storage, json!({ "sort_by": "due", "cursor": "2" })).unwrap();
+        assert_eq!(rest["tasks"][0]["title"], "C");
+        assert_eq!(rest["tasks"][0]["status"], "active");
+        assert!(rest["next_cursor"].is_null());
+
+        // Recently touched first
+        let a = storage.load_all_tasks().unwrap().into_iter().find(|t| t.frontmatter.title == "A").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        update_task(&storage, &AppConfig::default(), json!({ "id": a.code(), "field": "priority", "value": "low" })).unwrap();
+        let touched = list_tasks(&storage, json!({ "sort_by": "updated", "fields": ["updated_at"] })).unwrap();
+        assert_eq!(touched["tasks"][0]["code"], a.code());
+        assert!(touched["tasks"][0]["updated_at"].is_string());
+
+        assert!(list_tasks(&storage, json!({ "sort_by": "size" })).is_err());
+        assert!(list_tasks(&storage, json!({ "fields": ["body"] })).is_err());
+    }
+
+    #[test]
+    fn test_search_tasks() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+        for (title, body) in [("Vendor review", "Check the contract renewal clause"), ("Groceries", "")] {
+            let mut task = TaskItem::new(title.to_string(), ItemType::Task);
+            task.body = body.to_string();
+            storage.write_task(&mut task).unwrap();
+        }
+
+        let config = AppConfig::default();
+        let result = search_tasks(&storage, &config, json!({ "query": "renewal" })).unwrap();
+        assert_eq!(result["tasks"].as_array().unwrap().len(), 1);
+        assert_eq!(result["tasks"][0]["title"], "Vendor review");
+        assert_eq!(result["tasks"][0]["snippet"], "Check the contract renewal clause");
+        assert!(search_tasks(&storage, &config, json!({})).is_err());
+        assert!(search_tasks(&storage, &config, json!({ "query": "renewal", "mode": "fuzzy" })).is_err());
+        // Semantic search needs an embeddings API
+        let error = search_tasks(&storage, &config, json!({ "query": "renewal", "mode": "semantic" })).unwrap_err();
+        assert!(error.contains("embeddings"));
+    }
+
+    #[test]
+    fn test_project_and_tag_resources() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut project = TaskItem::new_project("Launch".to_string());
+        storage.write_task(&mut project).unwrap();
+        let project_id = project.frontmatter.id;
+        for (title, status) in [("Write copy", Status::Done), ("Ship", Status::Active)] {
+            let mut task = TaskItem::new(title.to_string(), ItemType::Task);
+            task.frontmatter.parent_goal_id = Some(project_id);
+            task.frontmatter.tags = vec!["launch".to_string()];
+            task.set_status(status);
+            storage.write_task(&mut task).unwrap();
+        }
+
+        let config = AppConfig::default();
+        let read = |uri: String| read_resource(&storage, &config, json!({ "uri": uri })).unwrap();
+        let projects = read("tasktui://projects".to_string());
+        assert_eq!(projects["projects"][0]["progress"], 50);
+        assert_eq!(projects["projects"][0]["tasks"]["total"], 2);
+
+        let detail = read(format!("tasktui://project/{}", project_id));
+        assert_eq!(detail["project"]["title"], "Launch");
+        assert_eq!(detail["tasks"].as_array().unwrap().len(), 2);
+        assert!(detail["tasks"][0]["gantt"]["start"].is_string());
+
+        let flow = read(format!("tasktui://project/{}/flow", project.code()));
+        assert_eq!(flow["project"]["title"], "Launch");
+        assert_eq!(flow["burndown"]["remaining"].as_array().unwrap().last(), Some(&json!(1)));
+        assert_eq!(flow["cumulative_flow"]["done"].as_array().unwrap().last(), Some(&json!(1)));
+
+        let tagged = read("tasktui://tag/Launch".to_string());
+        assert_eq!(tagged["count"], 2);
+        assert_eq!(tagged["tasks"]["done"][0]["title"], "Write copy");
+        assert_eq!(tagged["tasks"]["active"][0]["title"], "Ship");
+
+        assert!(read_resource(&storage, &config, json!({ "uri": format!("tasktui://project/{}", uuid::Uuid::new_v4()) })).is_err());
+        assert!(list_resources().unwrap()["resourceTemplates"].as_array().is_some_and(|t| t.len() == 4));
+    }
+}
@@ -0,0 +1,970 @@
+use crate::filter::FilterExpr;
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use uuid::Uuid;
+
+/// Task status enum
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Inbox,
+    Active,
+    Next,
+    Waiting,
+    Done,
+    Archived,
+}
+
+impl Status {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Status::Inbox => "inbox",
+            Status::Active => "active",
+            Status::Next => "next",
+            Status::Waiting => "waiting",
+            Status::Done => "done",
+            Status::Archived => "archived",
+        }
+    }
+
+    /// A status by its name, as `as_str` writes it
+    pub fn parse(text: &str) -> Option<Status> {
+        match text.trim().to_lowercase().as_str() {
+            "inbox" => Some(Status::Inbox),
+            "active" => Some(Status::Active),
+            "next" => Some(Status::Next),
+            "waiting" => Some(Status::Waiting),
+            "done" => Some(Status::Done),
+            "archived" => Some(Status::Archived),
+            _ => None,
+        }
+    }
+
+    /// Done or archived: finished with, whether or not it was completed
+    pub fn is_closed(&self) -> bool {
+        matches!(self, Status::Done | Status::Archived)
+    }
+}
+
+/// Item type enum
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemType {
+    Task,
+    Goal,
+    Note,
+    Project,
+}
+
+/// How often a habit repeats. Streaks count consecutive periods with a
+/// check-off: days, weekdays (weekends neither count nor break one) or weeks.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Habit {
+    Daily,
+    Weekdays,
+    Weekly,
+}
+
+impl Habit {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Habit::Daily => "daily",
+            Habit::Weekdays => "weekdays",
+            Habit::Weekly => "weekly",
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Habit> {
+        match text.trim().to_lowercase().as_str() {
+            "daily" | "day" => Some(Habit::Daily),
+            "weekdays" | "weekday" => Some(Habit::Weekdays),
+            "weekly" | "week" => Some(Habit::Weekly),
+            _ => None,
+        }
+    }
+}
+
+/// Priority level, from P0 (drop everything) to P4 (someday). Files from
+/// before levels were numbered say `high`, `medium` or `low`, read as P1–P3.
+/// There's deliberately no `Ord`: compare by `rank` so "more urgent" is explicit.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Priority {
+    #[serde(rename = "p0", alias = "P0")]
+    P0,
+    #[serde(rename = "p1", alias = "P1", alias = "high")]
+    P1,
+    #[serde(rename = "p2", alias = "P2", alias = "medium")]
+    P2,
+    #[serde(rename = "p3", alias = "P3", alias = "low")]
+    P3,
+    #[serde(rename = "p4", alias = "P4")]
+    P4,
+}
+
+impl Priority {
+    /// Every level, most urgent first
+    pub const ALL: [Priority; 5] = [Priority::P0, Priority::P1, Priority::P2, Priority::P3, Priority::P4];
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Priority::P0 => "p0",
+            Priority::P1 => "p1",
+            Priority::P2 => "p2",
+            Priority::P3 => "p3",
+            Priority::P4 => "p4",
+        }
+    }
+
+    /// The badge text, `P0`–`P4`
+    pub fn label(&self) -> &str {
+        match self {
+            Priority::P0 => "P0",
+            Priority::P1 => "P1",
+            Priority::P2 => "P2",
+            Priority::P3 => "P3",
+            Priority::P4 => "P4",
+        }
+    }
+
+    /// Position in urgency order: 0 for P0 up to 4 for P4. Sort ascending by
+    /// rank to put the most urgent first.
+    pub fn rank(&self) -> u8 {
+        match self {
+            Priority::P0 => 0,
+            Priority::P1 => 1,
+            Priority::P2 => 2,
+            Priority::P3 => 3,
+            Priority::P4 => 4,
+        }
+    }
+
+    /// `p0`–`p4`, a bare digit, or one of the old names (`high`, `med`, `l`, ...)
+    pub fn parse(text: &str) -> Option<Priority> {
+        match text.trim().to_lowercase().as_str() {
+            "p0" | "0" | "critical" => Some(Priority::P0),
+            "p1" | "1" | "high" | "h" => Some(Priority::P1),
+            "p2" | "2" | "medium" | "med" | "m" => Some(Priority::P2),
+            "p3" | "3" | "low" | "l" => Some(Priority::P3),
+            "p4" | "4" | "someday" => Some(Priority::P4),
+            _ => None,
+        }
+    }
+
+    /// One level more urgent, wrapping from P0 round to P4
+    pub fn raised(&self) -> Priority {
+        let count = Priority::ALL.len();
+        Priority::ALL[(self.rank() as usize + count - 1) % count].clone()
+    }
+
+    /// Emoji for plain-text contexts such as Slack, and `priority_style: emoji`
+    pub fn emoji(&self) -> &str {
+        match self {
+            Priority::P0 => "🚨",
+            Priority::P1 => "🔴",
+            Priority::P2 => "🟠",
+            Priority::P3 => "⚪",
+            Priority::P4 => "💤",
+        }
+    }
+}
+
+/// The kind of value a custom field holds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    #[default]
+    Text,
+    Number,
+    /// `YYYY-MM-DD`
+    Date,
+    /// One of the field's `values`
+    Choice,
+}
+
+/// A custom field a project defines for its tasks, e.g. `client` or `severity`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldDef {
+    pub name: String,
+    #[serde(rename = "type", default)]
+    pub kind: FieldType,
+    /// Allowed values of a `choice` field, in display order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<String>,
+}
+
+impl FieldDef {
+    /// Whether `value` fits the field's type, with the reason if not
+    pub fn check(&self, value: &str) -> Result<(), String> {
+        let ok = match self.kind {
+            FieldType::Text => true,
+            FieldType::Number => value.trim().parse::<f64>().is_ok(),
+            FieldType::Date => NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").is_ok(),
+            FieldType::Choice => self.values.iter().any(|v| v == value),
+        };
+        match (ok, self.kind) {
+            (true, _) => Ok(()),
+            (false, FieldType::Choice) => Err(format!("'{}' isn't one of {}", value, self.values.join(", "))),
+            (false, FieldType::Date) => Err(format!("'{}' isn't a YYYY-MM-DD date", value)),
+            (false, kind) => Err(format!("'{}' isn't a {}", value, kind.as_str())),
+        }
+    }
+}
+
+impl FieldType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            FieldType::Text => "text",
+            FieldType::Number => "number",
+            FieldType::Date => "date",
+            FieldType::Choice => "choice",
+        }
+    }
+}
+
+/// Custom field values are written as plain YAML scalars; numbers and booleans
+/// are read back as their text
+fn deserialize_custom<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BTreeMap<String, String>, D::Error> {
+    let values = BTreeMap::<String, serde_yaml::Value>::deserialize(deserializer)?;
+    let mut custom = BTreeMap::new();
+    for (key, value) in values {
+        let text = match value {
+            serde_yaml::Value::Null => continue,
+            serde_yaml::Value::String(text) => text,
+            serde_yaml::Value::Number(number) => number.to_string(),
+            serde_yaml::Value::Bool(flag) => flag.to_string(),
+            _ => return Err(de::Error::custom(format!("custom field '{}' must be a single value", key))),
+        };
+        custom.insert(key, text);
+    }
+    Ok(custom)
+}
+
+/// YAML Frontmatter structure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Frontmatter {
+    pub id: Uuid,
+    /// Short code for typing instead of the id; see `short_code`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(rename = "type")]
+    pub item_type: ItemType,
+    pub title: String,
+    pub status: Status,
+    #[serde(default = "default_priority")]
+    pub priority: Priority,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default, deserialize_with = "deserialize_due", skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<Due>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_goal_id: Option<Uuid>,
+    /// The config goal this task or project works toward
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub goal_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    // Project-specific fields
+    #[serde(default, deserialize_with = "deserialize_date", skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<NaiveDate>,
+    #[serde(default, deserialize_with = "deserialize_date", skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<u8>,
+    /// Expected effort in minutes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate_minutes: Option<u32>,
+    // Delegation fields
+    #[serde(default, alias = "assigned_to", skip_serializing_if = "Option::is_none")]
+    pub delegated_to: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegated_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub follow_up_of: Option<Uuid>,
+    /// When the task last moved into Done
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+    /// When the task was last saved with a change (or reviewed in the Stale view)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Linked Jira issue, e.g. `OPS-142`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jira_key: Option<String>,
+    /// Values of custom fields, by field name
+    #[serde(default, deserialize_with = "deserialize_custom", skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom: BTreeMap<String, String>,
+    /// Custom fields this project's tasks carry (projects only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldDef>,
+    /// Makes the task a habit: marking it done checks off the day and keeps it open
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub habit: Option<Habit>,
+    /// Days a habit was checked off, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub habit_log: Vec<NaiveDate>,
+}
+
+fn default_priority() -> Priority {
+    Priority::P2
+}
+
+/// Today in the local timezone (set `TZ` to override). Every "due today", overdue
+/// and relative-date decision goes through this so they flip at local midnight.
+pub fn today() -> NaiveDate {
+    now_local().date()
+}
+
+/// The local wall-clock time, comparable with `Due::deadline`
+pub fn now_local() -> NaiveDateTime {
+    if let Some(now) = FROZEN_NOW.with(|frozen| frozen.get()) {
+        return now;
+    }
+    Local::now().naive_local()
+}
+
+thread_local! {
+    static FROZEN_NOW: std::cell::Cell<Option<NaiveDateTime>> = const { std::cell::Cell::new(None) };
+}
+
+/// Pin `today` and `now_local` on the calling thread, so tests that render
+/// relative dates don't change from day to day
+#[doc(hidden)]
+pub fn freeze_clock(now: NaiveDateTime) {
+    FROZEN_NOW.with(|frozen| frozen.set(Some(now)));
+}
+
+/// Parse a stored date: `YYYY-MM-DD`, or a timestamp whose date part is kept
+pub fn parse_date(value: &str) -> Option<NaiveDate> {
+    let value = value.trim();
+    let date = match value.char_indices().nth(10) {
+        Some((idx, 'T' | ' ')) => &value[..idx],
+        _ => value,
+    };
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+/// Dates were free-form strings in older files: accept timestamps and blanks, and
+/// reject anything else so the file shows up in `doctor`
+fn deserialize_date<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<NaiveDate>, D::Error> {
+    match Option::<String>::deserialize(deserializer)?.as_deref().map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(value) => parse_date(value)
+            .map(Some)
+            .ok_or_else(|| de::Error::custom(format!("'{}' is not a YYYY-MM-DD date", value))),
+    }
+}
+
+fn deserialize_due<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Due>, D::Error> {
+    match Option::<String>::deserialize(deserializer)?.as_deref().map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(value) => Due::parse(value)
+            .map(Some)
+            .ok_or_else(|| de::Error::custom(format!("'{}' is not a YYYY-MM-DD[THH:MM] date", value))),
+    }
+}
+
+/// A due date with an optional time of day, written `2025-03-02` or
+/// `2025-03-02T15:00`. Times without a UTC offset are local time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Due {
+    pub date: NaiveDate,
+    pub time: Option<NaiveTime>,
+    /// Offset written after the time (`Z`, `+01:00`), if any
+    pub offset: Option<FixedOffset>,
+}
+
+impl Due {
+    pub fn on(date: NaiveDate) -> Self {
+        Self { date, time: None, offset: None }
+    }
+
+    /// Parse `YYYY-MM-DD`, optionally followed by `T` (or a space) and `HH:MM[:SS]`
+    /// with an optional `Z`/`±HH:MM` offset
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let Some((date, time)) = value.split_once(['T', ' ']) else {
+            return NaiveDate::parse_from_str(value, "%Y-%m-%d").ok().map(Self::on);
+        };
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+
+        let (time, offset) = match time.find(['Z', 'z', '+', '-']) {
+            Some(idx) => {
+                let offset = match &time[idx..] {
+                    "Z" | "z" => FixedOffset::east_opt(0),
+                    offset => DateTime::parse_from_str(&format!("2000-01-01T00:00{}", offset), "%Y-%m-%dT%H:%M%:z")
+                        .ok()
+                        .map(|dt| *dt.offset()),
+                };
+                (&time[..idx], Some(offset?))
+            }
+            None => (time, None),
+        };
+        let time = NaiveTime::parse_from_str(time, "%H:%M")
+            .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M:%S"))
+            .ok()?;
+        Some(Self { date, time: Some(time), offset })
+    }
+
+    /// When the task is due, in local time: the exact time, or the end of the day
+    pub fn deadline(&self) -> NaiveDateTime {
+        let Some(time) = self.time else {
+            return self.date.and_time(NaiveTime::from_hms_opt(23, 59, 59).unwrap_or_default());
+        };
+        let written = self.date.and_time(time);
+        match self.offset.and_then(|offset| offset.from_local_datetime(&written).single()) {
+            Some(instant) => instant.with_timezone(&Local).naive_local(),
+            None => written,
+        }
+    }
+
+    /// The local calendar day the task is due on
+    pub fn local_date(&self) -> NaiveDate {
+        self.deadline().date()
+    }
+
+    /// Short form for lists: `today 15:00`, `tomorrow`, `2025-03-02 15:00`
+    pub fn label(&self, today: NaiveDate) -> String {
+        let date = self.local_date();
+        let day = match (date - today).num_days() {
+            0 => "today".to_string(),
+            1 => "tomorrow".to_string(),
+            -1 => "yesterday".to_string(),
+            _ => date.format("%Y-%m-%d").to_string(),
+        };
+        match self.time {
+            Some(_) => format!("{} {}", day, self.deadline().format("%H:%M")),
+            None => day,
+        }
+    }
+}
+
+impl fmt::Display for Due {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.date.format("%Y-%m-%d"))?;
+        if let Some(time) = self.time {
+            let format = if time.second() == 0 { "T%H:%M" } else { "T%H:%M:%S" };
+            write!(f, "{}", time.format(format))?;
+            match self.offset {
+                Some(offset) if offset.local_minus_utc() == 0 => write!(f, "Z")?,
+                Some(offset) => write!(f, "{}", offset)?,
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for Due {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Complete task item (frontmatter + body)
+#[derive(Debug, Clone)]
+pub struct TaskItem {
+    pub frontmatter: Frontmatter,
+    pub body: String,
+    pub file_path: std::path::PathBuf,
+}
+
+impl TaskItem {
+    /// Create a new task item
+    pub fn new(title: String, item_type: ItemType) -> Self {
+        let id = Uuid::new_v4();
+        Self {
+            frontmatter: Frontmatter {
+                id,
+                code: Some(short_code(id)),
+                item_type,
+                title,
+                status: Status::Active,
+                priority: Priority::P2,
+                tags: Vec::new(),
+                due_date: None,
+                parent_goal_id: None,
+                goal_id: None,
+                created_at: Utc::now(),
+                start_date: None,
+                end_date: None,
+                progress: None,
+                estimate_minutes: None,
+                delegated_to: None,
+                delegated_at: None,
+                follow_up_of: None,
+                completed_at: None,
+                updated_at: None,
+                jira_key: None,
+                custom: BTreeMap::new(),
+                fields: Vec::new(),
+                habit: None,
+                habit_log: Vec::new(),
+            },
+            body: String::new(),
+            file_path: std::path::PathBuf::new(),
+        }
+    }
+
+    /// Create a new project
+    pub fn new_project(title: String) -> Self {
+        let id = Uuid::new_v4();
+        let today = today();
+        Self {
+            frontmatter: Frontmatter {
+                id,
+                code: Some(short_code(id)),
+                item_type: ItemType::Project,
+                title,
+                status: Status::Active,
+                priority: Priority::P2,
+                tags: Vec::new(),
+                due_date: None,
+                parent_goal_id: None,
+                goal_id: None,
+                created_at: Utc::now(),
+                start_date: Some(today),
+                end_date: None,
+                progress: Some(0),
+                estimate_minutes: None,
+                delegated_to: None,
+                delegated_at: None,
+                follow_up_of: None,
+                completed_at: None,
+                updated_at: None,
+                jira_key: None,
+                custom: BTreeMap::new(),
+                fields: Vec::new(),
+                habit: None,
+                habit_log: Vec::new(),
+            },
+            body: String::new(),
+            file_path: std::path::PathBuf::new(),
+        }
+    }
+
+    /// Check if this is a project
+    pub fn is_project(&self) -> bool {
+        self.frontmatter.item_type == ItemType::Project
+    }
+
+    /// Check if task matches a tag filter
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.frontmatter.tags.iter().any(|t| t == tag)
+    }
+
+    /// Check if task is due today
+    pub fn is_due_today(&self) -> bool {
+        self.frontmatter.due_date.is_some_and(|due| due.local_date() == today())
+    }
+
+    /// Past its deadline and not finished yet. Date-only tasks are overdue the day after.
+    pub fn is_overdue(&self, now: NaiveDateTime) -> bool {
+        self.frontmatter.due_date.is_some_and(|due| due.deadline() < now)
+            && !self.frontmatter.status.is_closed()
+    }
+
+    /// The span a Gantt bar covers: start and end dates, falling back to the due
+    /// date, then to a week from today
+    pub fn schedule(&self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let due = self.frontmatter.due_date.map(|d| d.local_date());
+        let start = self.frontmatter.start_date.or(due).unwrap_or(today);
+        let end = self.frontmatter.end_date.or(due).unwrap_or(start + chrono::Duration::days(7));
+        (start, end)
+    }
+
+    /// Percent complete: 100 once finished, otherwise the stored `progress`
+    pub fn percent_done(&self) -> u8 {
+        if self.frontmatter.status.is_closed() {
+            100
+        } else {
+            self.frontmatter.progress.unwrap_or(0)
+        }
+    }
+
+    /// Change status, stamping `completed_at` on the way into Done and clearing it on reopen.
+    /// A habit isn't moved to Done: today is checked off instead and it stays open.
+    pub fn set_status(&mut self, status: Status) {
+        if status == Status::Done && self.frontmatter.habit.is_some() {
+            self.check_off(today());
+            return;
+        }
+        match status {
+            Status::Done => {
+                if self.frontmatter.status != Status::Done || self.frontmatter.completed_at.is_none() {
+                    self.frontmatter.completed_at = Some(Utc::now());
+                }
+            }
+            Status::Archived => {}
+            _ => self.frontmatter.completed_at = None,
+        }
+        self.frontmatter.status = status;
+    }
+
+    /// Hand the task off to someone else and park it in Waiting
+    pub fn delegate(&mut self, person: String) {
+        self.set_status(Status::Waiting);
+        self.frontmatter.delegated_to = Some(person);
+        self.frontmatter.delegated_at = Some(Utc::now());
+    }
+
+    /// Whole days this task has been waiting on its delegate
+    pub fn days_waiting(&self, now: DateTime<Utc>) -> Option<i64> {
+        if self.frontmatter.status != Status::Waiting {
+            return None;
+        }
+        self.frontmatter.delegated_to.as_ref()?;
+        let since = self.frontmatter.delegated_at.unwrap_or(self.frontmatter.created_at);
+        Some((now - since).num_days())
+    }
+
+    /// When the task was last updated: `updated_at`, or creation for older files
+    pub fn last_touched(&self) -> DateTime<Utc> {
+        self.frontmatter.updated_at.unwrap_or(self.frontmatter.created_at)
+    }
+
+    /// A task (not a project) that isn't done or archived
+    pub fn is_open(&self) -> bool {
+        !self.is_project() && !self.frontmatter.status.is_closed()
+    }
+
+    /// Open and not updated for more than `days` days. Projects and tasks parked
+    /// as someday are never stale; `days` of 0 disables the check.
+    pub fn is_stale(&self, now: DateTime<Utc>, days: u32) -> bool {
+        days > 0
+            && self.is_open()
+            && !self.has_tag("someday")
+            && (now - self.last_touched()).num_days() > days as i64
+    }
+
+    /// The stored short code, or the one derived from the id for older files
+    pub fn code(&self) -> String {
+        self.frontmatter.code.clone().unwrap_or_else(|| short_code(self.frontmatter.id))
+    }
+}
+
+/// Characters of the id used as its short code
+const SHORT_CODE_LEN: usize = 6;
+/// Shortest id prefix accepted in place of a code
+const MIN_PREFIX_LEN: usize = 4;
+
+/// A task's short code: the first six hex digits of its id (e.g. `3fa85f`).
+/// Being a prefix of the id, a longer prefix always disambiguates a clash.
+pub fn short_code(id: Uuid) -> String {
+    id.simple().to_string()[..SHORT_CODE_LEN].to_string()
+}
+
+/// Resolve what a user typed as a task id: a full UUID, a short code, or a
+/// unique id prefix of at least four hex digits (a leading `#` is ignored)
+pub fn resolve_id(tasks: &[TaskItem], text: &str) -> Result<Uuid, String> {
+    let text = text.trim().trim_start_matches('#').to_lowercase();
+    if let Ok(id) = Uuid::parse_str(&text) {
+        return Ok(id);
+    }
+    if text.len() < MIN_PREFIX_LEN {
+        return Err(format!("Invalid id '{}': expected a UUID or a short code", text));
+    }
+    let prefix = text.replace('-', "");
+    let matches: Vec<Uuid> = tasks.iter()
+        .filter(|t| t.code() == text || t.frontmatter.id.simple().to_string().starts_with(&prefix))
+        .map(|t| t.frontmatter.id)
+        .collect();
+    match matches[..] {
+        [id] => Ok(id),
+        [] => Err(format!("No task with id or code '{}'", text)),
+        _ => Err(format!("Code '{}' matches {} tasks; add more characters of the id", text, matches.len())),
+    }
+}
+
+/// Share of a project's tasks that are done or archived, in percent
+pub fn project_progress(tasks: &[TaskItem], project_id: Uuid) -> u8 {
+    let (total, done) = tasks.iter()
+        .filter(|t| t.frontmatter.parent_goal_id == Some(project_id))
+        .fold((0, 0), |(total, done), t| {
+            (total + 1, done + t.frontmatter.status.is_closed() as usize)
+        });
+    if total == 0 {
+        return 0;
+    }
+    ((done as f64 / total as f64) * 100.0) as u8
+}
+
+/// Why an open task's `parent_goal_id` no longer leads anywhere useful
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orphan {
+    /// No project with that id exists
+    Missing,
+    /// The project is archived
+    Archived,
+}
+
+/// Open tasks whose project was deleted or archived
+pub fn orphans(tasks: &[TaskItem]) -> Vec<(&TaskItem, Orphan)> {
+    tasks.iter()
+        .filter(|t| !t.frontmatter.status.is_closed())
+        .filter_map(|t| {
+            let parent_id = t.frontmatter.parent_goal_id?;
+            match tasks.iter().find(|p| p.frontmatter.id == parent_id) {
+                None => Some((t, Orphan::Missing)),
+                Some(p) if p.frontmatter.status == Status::Archived => Some((t, Orphan::Archived)),
+                Some(_) => None,
+            }
+        })
+        .collect()
+}
+
+/// The open tasks in a project
+pub fn open_children(tasks: &[TaskItem], project_id: Uuid) -> Vec<&TaskItem> {
+    tasks.iter()
+        .filter(|t| t.frontmatter.parent_goal_id == Some(project_id))
+        .filter(|t| !t.frontmatter.status.is_closed())
+        .collect()
+}
+
+/// The custom fields defined by `task`'s project, if it has one
+pub fn field_defs<'a>(tasks: &'a [TaskItem], task: &TaskItem) -> &'a [FieldDef] {
+    task.frontmatter.parent_goal_id
+        .and_then(|id| tasks.iter().find(|p| p.frontmatter.id == id))
+        .map_or(&[], |project| project.frontmatter.fields.as_slice())
+}
+
+/// The projects and tasks working toward one goal. Tasks count when linked
+/// directly or through their project.
+pub struct GoalLinks<'a> {
+    pub projects: Vec<&'a TaskItem>,
+    pub tasks: Vec<&'a TaskItem>,
+}
+
+impl<'a> GoalLinks<'a> {
+    pub fn new(items: &'a [TaskItem], goal_id: Uuid) -> Self {
+        let projects: Vec<&TaskItem> = items.iter()
+            .filter(|t| t.is_project() && t.frontmatter.goal_id == Some(goal_id))
+            .collect();
+        let tasks = items.iter()
+            .filter(|t| !t.is_project())
+            .filter(|t| {
+                t.frontmatter.goal_id == Some(goal_id)
+                    || t.frontmatter.parent_goal_id.is_some_and(|p| projects.iter().any(|project| project.frontmatter.id == p))
+            })
+            .collect();
+        Self { projects, tasks }
+    }
+
+    /// Share of linked tasks that are done or archived, in percent
+    pub fn progress(&self) -> u8 {
+        if self.tasks.is_empty() {
+            return 0;
+        }
+        let done = self.tasks.iter().filter(|t| t.frontmatter.status.is_closed()).count();
+        ((done as f64 / self.tasks.len() as f64) * 100.0) as u8
+    }
+
+    /// Linked tasks that are active or next, i.e. something to do now
+    pub fn next_actions(&self) -> usize {
+        self.tasks.iter().filter(|t| matches!(t.frontmatter.status, Status::Active | Status::Next)).count()
+    }
+}
+
+/// Filter criteria for listing tasks
+#[derive(Debug, Clone, Default)]
+pub struct TaskFilter {
+    pub status: Option<Status>,
+    pub tags: Vec<String>,
+    pub item_type: Option<ItemType>,
+    pub limit: Option<usize>,
+    pub project_id: Option<Uuid>,
+    pub expr: Option<FilterExpr>,
+}
+
+impl TaskFilter {
+    /// Whether `item` passes the filter; `today` anchors relative dates in `expr`
+    pub fn matches(&self, item: &TaskItem, today: NaiveDate) -> bool {
+        // Status filter
+        if let Some(status) = &self.status {
+            if &item.frontmatter.status != status {
+                return false;
+            }
+        }
+
+        // Type filter
+        if let Some(item_type) = &self.item_type {
+            if &item.frontmatter.item_type != item_type {
+                return false;
+            }
+        }
+
+        // Tags filter (item must have ALL specified tags)
+        if !self.tags.is_empty() {
+            for tag in &self.tags {
+                if !item.has_tag(tag) {
+                    return false;
+                }
+            }
+        }
+
+        // Project filter (check parent_goal_id)
+        if let Some(project_id) = &self.project_id {
+            if item.frontmatter.parent_goal_id.as_ref() != Some(project_id) {
+                return false;
+            }
+        }
+
+        // Compound filter expression
+        if let Some(expr) = &self.expr {
+            if !expr.matches(item, today) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_due_parse_and_display() {
+        for text in ["2025-03-02", "2025-03-02T15:00", "2025-03-02T15:00:30Z", "2025-03-02T15:00+01:00"] {
+            assert_eq!(Due::parse(text).map(|d| d.to_string()).as_deref(), Some(text));
+        }
+        assert_eq!(Due::parse("2025-03-02 09:15").map(|d| d.to_string()).as_deref(), Some("2025-03-02T09:15"));
+        assert_eq!(Due::parse("2025-03-02T25:00"), None);
+        assert_eq!(Due::parse("tomorrow"), None);
+
+        let day = NaiveDate::from_ymd_opt(2025, 3, 2).unwrap();
+        let timed = Due::parse("2025-03-02T15:00").unwrap();
+        assert_eq!(timed.label(day), "today 15:00");
+        assert_eq!(timed.label(day.pred_opt().unwrap()), "tomorrow 15:00");
+        assert_eq!(Due::on(day).label(day.succ_opt().unwrap()), "yesterday");
+
+        // Date-only tasks are due at the end of the day, so they sort after timed ones
+        assert!(timed.deadline() < Due::on(day).deadline());
+    }
+
+    #[test]
+    fn test_orphans() {
+        let mut archived = TaskItem::new_project("Old".to_string());
+        archived.frontmatter.status = Status::Archived;
+        let open = TaskItem::new_project("Current".to_string());
+        let child = |title: &str, parent: Uuid, status: Status| {
+            let mut task = TaskItem::new(title.to_string(), ItemType::Task);
+            task.frontmatter.parent_goal_id = Some(parent);
+            task.frontmatter.status = status;
+            task
+        };
+        let items = [
+            child("In archived", archived.frontmatter.id, Status::Next),
+            child("Done in archived", archived.frontmatter.id, Status::Done),
+            child("In deleted", Uuid::new_v4(), Status::Inbox),
+            child("In open", open.frontmatter.id, Status::Active),
+            archived,
+            open,
+        ];
+
+        let found: Vec<_> = orphans(&items).into_iter().map(|(t, why)| (t.frontmatter.title.as_str(), why)).collect();
+        assert_eq!(found, [("In archived", Orphan::Archived), ("In deleted", Orphan::Missing)]);
+        assert_eq!(open_children(&items, items[4].frontmatter.id).len(), 1);
+    }
+
+    #[test]
+    fn test_goal_links() {
+        let goal = Uuid::new_v4();
+        let mut project = TaskItem::new_project("Launch".to_string());
+        project.frontmatter.goal_id = Some(goal);
+        let mut in_project = TaskItem::new("Write copy".to_string(), ItemType::Task);
+        in_project.frontmatter.parent_goal_id = Some(project.frontmatter.id);
+        in_project.frontmatter.status = Status::Done;
+        let mut direct = TaskItem::new("Book venue".to_string(), ItemType::Task);
+        direct.frontmatter.goal_id = Some(goal);
+        let unrelated = TaskItem::new("Fix bike".to_string(), ItemType::Task);
+        let items = [project, in_project, direct, unrelated];
+
+        let links = GoalLinks::new(&items, goal);
+        assert_eq!(links.projects.len(), 1);
+        assert_eq!(links.tasks.len(), 2);
+        assert_eq!(links.progress(), 50);
+        assert_eq!(links.next_actions(), 1);
+        assert!(GoalLinks::new(&items, Uuid::new_v4()).tasks.is_empty());
+    }
+
+    #[test]
+    fn test_status_parse() {
+        for status in [Status::Inbox, Status::Active, Status::Next, Status::Waiting, Status::Done, Status::Archived] {
+            assert_eq!(Status::parse(status.as_str()), Some(status));
+        }
+        assert_eq!(Status::parse(" Done "), Some(Status::Done));
+        assert_eq!(Status::parse("blocked"), None);
+    }
+
+    #[test]
+    fn test_priority() {
+        // Files from before numbered levels keep their meaning; new ones are written as p0-p4
+        for (old, level) in [("high", Priority::P1), ("medium", Priority::P2), ("low", Priority::P3), ("P0", Priority::P0)] {
+            assert_eq!(serde_yaml::from_str::<Priority>(old).unwrap(), level);
+        }
+        assert_eq!(serde_yaml::to_string(&Priority::P4).unwrap().trim(), "p4");
+        assert!(serde_yaml::from_str::<Priority>("urgent").is_err());
+
+        assert_eq!(Priority::parse(" High "), Some(Priority::P1));
+        assert_eq!(Priority::parse("0"), Some(Priority::P0));
+        assert_eq!(Priority::parse("p5"), None);
+
+        let mut levels = vec![Priority::P3, Priority::P0, Priority::P4, Priority::P1];
+        levels.sort_by_key(Priority::rank);
+        assert_eq!(levels, [Priority::P0, Priority::P1, Priority::P3, Priority::P4]);
+        assert_eq!(Priority::P2.raised(), Priority::P1);
+        assert_eq!(Priority::P0.raised(), Priority::P4);
+    }
+
+    #[test]
+    fn test_custom_fields() {
+        let yaml = "id: 5eed0000-0000-4000-8000-000000000001\ntype: task\ntitle: Checkout bug\nstatus: active\npriority: medium\n\
+            created_at: 2025-03-02T09:00:00Z\ncustom:\n  points: 3\n  client: Acme\n  billable: true\n  blank: null\n";
+        let fm: Frontmatter = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(fm.custom.get("points").map(String::as_str), Some("3"));
+        assert_eq!(fm.custom.get("billable").map(String::as_str), Some("true"));
+        assert!(!fm.custom.contains_key("blank"));
+        assert!(serde_yaml::from_str::<Frontmatter>(&format!("{}  nested: [1, 2]\n", yaml)).is_err());
+
+        let severity = FieldDef { name: "severity".to_string(), kind: FieldType::Choice, values: vec!["low".to_string(), "high".to_string()] };
+        assert!(severity.check("high").is_ok());
+        assert_eq!(severity.check("urgent"), Err("'urgent' isn't one of low, high".to_string()));
+        let points = FieldDef { name: "points".to_string(), kind: FieldType::Number, values: Vec::new() };
+        assert!(points.check("2.5").is_ok());
+        assert!(points.check("lots").is_err());
+        let due = FieldDef { name: "signed".to_string(), kind: FieldType::Date, values: Vec::new() };
+        assert!(due.check("2025-03-02").is_ok());
+        assert!(due.check("March").is_err());
+
+        let mut project = TaskItem::new_project("Billing".to_string());
+        project.frontmatter.fields = vec![severity];
+        let mut task = TaskItem::new("Refund".to_string(), ItemType::Task);
+        task.frontmatter.parent_goal_id = Some(project.frontmatter.id);
+        let items = [project, TaskItem::new("Loose".to_string(), ItemType::Task)];
+        assert_eq!(field_defs(&items, &task).len(), 1);
+        assert!(field_defs(&items, &items[1]).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_id() {
+        let task = TaskItem::new("Task".to_string(), ItemType::Task);
+        let id = task.frontmatter.id;
+        let code = task.code();
+        assert_eq!(code.len(), 6);
+        assert!(id.to_string().starts_with(&code));
+
+        let tasks = [task, TaskItem::new("Other".to_string(), ItemType::Task)];
+        assert_eq!(resolve_id(&tasks, &id.to_string()), Ok(id));
+        assert_eq!(resolve_id(&tasks, &format!("#{}", code.to_uppercase())), Ok(id));
+        assert_eq!(resolve_id(&tasks, &id.to_string()[..10]), Ok(id));
+        assert!(resolve_id(&tasks, &code[..3]).is_err());
+        assert!(resolve_id(&tasks, "zzzzzz").is_err());
+
+        // Files written before codes existed still resolve by the derived code
+        let mut legacy = tasks[1].clone();
+        legacy.frontmatter.code = None;
+        assert_eq!(resolve_id(&[legacy.clone()], &short_code(legacy.frontmatter.id)), Ok(legacy.frontmatter.id));
+    }
+}
@@ -0,0 +1,345 @@
+//! WASM plugins loaded from `plugins/` in the data directory. A plugin is a
+//! `.wasm` (or `.wat`) module that registers tools, which the MCP server and
+//! `tasktui plugin` call. Values cross the boundary as JSON in the module's
+//! memory, addressed by a pointer and length packed into an `i64`
+//! (`ptr << 32 | len`).
+//!
+//! A plugin exports:
+//! - `memory`
+//! - `alloc(len: i32) -> i32`, which the host calls to place JSON in the plugin
+//! - `tasktui_manifest() -> i64`: `{"name": ..., "tools": [{"name", "description", "inputSchema"}]}`
+//! - `tasktui_call(ptr: i32, len: i32) -> i64`: called with `{"tool": ..., "arguments": ...}`,
+//!   returning any JSON result, or `{"error": "..."}`
+//!
+//! and may import from the `tasktui` module:
+//! - `list_tasks() -> i64`: every task, as hook commands see them
+//! - `write_task(ptr: i32, len: i32) -> i64`: creates a task, or updates the one
+//!   with the given `id`, from `title`, `status`, `priority`, `tags` and `body`;
+//!   returns the written task or `{"error": "..."}`
+//! - `log(ptr: i32, len: i32)`: a line for the log file
+
+use crate::hooks;
+use crate::models::{ItemType, Priority, Status, TaskItem};
+use crate::storage::Storage;
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use wasmtime::{AsContext, AsContextMut, Caller, Config, Engine, Instance, Linker, Memory, Module, Store, TypedFunc};
+
+/// Where plugins are loaded from, inside the data directory
+pub const PLUGIN_DIR: &str = "plugins";
+
+/// Instructions a plugin may run per call, so a runaway loop fails the call
+/// instead of hanging the server
+const FUEL_PER_CALL: u64 = 1_000_000_000;
+
+/// What host functions reach through a plugin's store
+struct Host {
+    storage: Rc<Storage>,
+}
+
+/// A loaded plugin and the tools it registered
+pub struct Plugin {
+    pub name: String,
+    pub path: PathBuf,
+    /// MCP tool definitions: `name`, `description` and `inputSchema`
+    pub tools: Vec<Value>,
+    store: Store<Host>,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+    call: TypedFunc<(i32, i32), i64>,
+}
+
+/// Every plugin in the data directory
+#[derive(Default)]
+pub struct Plugins {
+    pub loaded: Vec<Plugin>,
+    /// Modules that failed to load, with why
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+impl Plugins {
+    /// Load the modules in `plugins/`, giving them `storage` to read and write
+    /// tasks through. Tools named like one in `reserved`, or like one an
+    /// earlier plugin registered, are left out.
+    pub fn load(storage: Storage, reserved: &[&str]) -> Result<Self> {
+        let dir = storage.data_dir.join(PLUGIN_DIR);
+        let mut plugins = Plugins::default();
+        if !dir.is_dir() {
+            return Ok(plugins);
+        }
+        let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+            .with_context(|| format!("Failed to read {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| matches!(p.extension().and_then(|e| e.to_str()), Some("wasm" | "wat")))
+            .collect();
+        paths.sort();
+
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)?;
+        let linker = linker(&engine)?;
+        let storage = Rc::new(storage);
+        for path in paths {
+            match Plugin::load(&engine, &linker, &path, storage.clone()) {
+                Ok(mut plugin) => {
+                    plugin.tools.retain(|tool| {
+                        let name = tool["name"].as_str().unwrap_or("");
+                        let taken = reserved.contains(&name) || plugins.tool(name).is_some();
+                        if taken {
+                            tracing::warn!(plugin = %plugin.name, tool = name, "Plugin tool name already taken, skipping");
+                        }
+                        !name.is_empty() && !taken
+                    });
+                    tracing::info!(plugin = %plugin.name, tools = plugin.tools.len(), "Loaded plugin");
+                    plugins.loaded.push(plugin);
+                }
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "Failed to load plugin");
+                    plugins.failed.push((path, format!("{:#}", e)));
+                }
+            }
+        }
+        Ok(plugins)
+    }
+
+    /// Tool definitions from every plugin
+    pub fn tools(&self) -> impl Iterator<Item = &Value> {
+        self.loaded.iter().flat_map(|p| p.tools.iter())
+    }
+
+    fn tool(&self, name: &str) -> Option<usize> {
+        self.loaded.iter().position(|p| p.tools.iter().any(|t| t["name"] == name))
+    }
+
+    /// Whether a plugin registered the tool `name`
+    pub fn has_tool(&self, name: &str) -> bool {
+        self.tool(name).is_some()
+    }
+
+    /// Call the plugin tool `name`
+    pub fn call(&mut self, name: &str, arguments: Value) -> Result<Value> {
+        let index = self.tool(name).with_context(|| format!("Unknown tool: {}", name))?;
+        self.loaded[index].call(name, arguments)
+    }
+}
+
+impl Plugin {
+    fn load(engine: &Engine, linker: &Linker<Host>, path: &Path, storage: Rc<Storage>) -> Result<Self> {
+        let module = Module::from_file(engine, path)?;
+        let mut store = Store::new(engine, Host { storage });
+        store.set_fuel(FUEL_PER_CALL)?;
+        let instance = linker.instantiate(&mut store, &module)?;
+        let memory = instance.get_memory(&mut store, "memory").context("Plugin doesn't export memory")?;
+        let alloc = instance.get_typed_func::<i32, i32>(&mut store, "alloc")?;
+        let call = instance.get_typed_func::<(i32, i32), i64>(&mut store, "tasktui_call")?;
+        let manifest = manifest(&mut store, &instance, memory)?;
+
+        let name = manifest["name"].as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| path.file_stem().unwrap_or_default().to_string_lossy().into_owned());
+        let tools = manifest["tools"].as_array().cloned().unwrap_or_default();
+        Ok(Self { name, path: path.to_path_buf(), tools, store, memory, alloc, call })
+    }
+
+    fn call(&mut self, tool: &str, arguments: Value) -> Result<Value> {
+        self.store.set_fuel(FUEL_PER_CALL)?;
+        let input = serde_json::to_vec(&json!({ "tool": tool, "arguments": arguments }))?;
+        let (ptr, len) = put(&mut self.store, self.memory, &self.alloc, &input)?;
+        let packed = self.call.call(&mut self.store, (ptr, len))
+            .map_err(anyhow::Error::from)
+            .with_context(|| format!("Plugin {} failed in {}", self.name, tool))?;
+        let output: Value = serde_json::from_slice(&get(&self.store, self.memory, packed)?)
+            .with_context(|| format!("Plugin {} returned invalid JSON from {}", self.name, tool))?;
+        match output.get("error").and_then(|e| e.as_str()) {
+            Some(error) => anyhow::bail!("{}", error),
+            None => Ok(output),
+        }
+    }
+}
+
+fn manifest(store: &mut Store<Host>, instance: &Instance, memory: Memory) -> Result<Value> {
+    let manifest = instance.get_typed_func::<(), i64>(&mut *store, "tasktui_manifest")?;
+    let packed = manifest.call(&mut *store, ())?;
+    serde_json::from_slice(&get(&*store, memory, packed)?).context("Plugin manifest isn't valid JSON")
+}
+
+/// The host API plugins import from the `tasktui` module
+fn linker(engine: &Engine) -> Result<Linker<Host>> {
+    let mut linker = Linker::new(engine);
+    linker.func_wrap("tasktui", "list_tasks", |mut caller: Caller<'_, Host>| -> wasmtime::Result<i64> {
+        let tasks: Vec<Value> = match caller.data().storage.load_all_tasks() {
+            Ok(tasks) => tasks.iter().map(hooks::task_payload).collect(),
+            Err(e) => return reply(&mut caller, &json!({ "error": e.to_string() })),
+        };
+        reply(&mut caller, &json!(tasks))
+    })?;
+    linker.func_wrap("tasktui", "write_task", |mut caller: Caller<'_, Host>, ptr: i32, len: i32| -> wasmtime::Result<i64> {
+        let input = read(&mut caller, ptr, len)?;
+        let result = serde_json::from_slice(&input)
+            .map_err(anyhow::Error::from)
+            .and_then(|fields| write_task(&caller.data().storage, &fields));
+        let output = result.unwrap_or_else(|e| json!({ "error": format!("{:#}", e) }));
+        reply(&mut caller, &output)
+    })?;
+    linker.func_wrap("tasktui", "log", |mut caller: Caller<'_, Host>, ptr: i32, len: i32| -> wasmtime::Result<()> {
+        let line = read(&mut caller, ptr, len)?;
+        tracing::info!(message = %String::from_utf8_lossy(&line), "Plugin log");
+        Ok(())
+    })?;
+    Ok(linker)
+}
+
+/// Create a task, or update the one with `fields.id`, from a plugin's fields
+fn write_task(storage: &Storage, fields: &Value) -> Result<Value> {
+    let mut task = match fields.get("id").and_then(|v| v.as_str()) {
+        Some(id) => {
+            let id = storage.resolve_id(id)?;
+            storage.load_all_tasks()?
+                .into_iter()
+                .find(|t| t.frontmatter.id == id)
+                .with_context(|| format!("No task with id {}", id))?
+        }
+        None => {
+            let title = fields.get("title").and_then(|v| v.as_str()).context("New tasks need a title")?;
+            TaskItem::new(title.to_string(), ItemType::Task)
+        }
+    };
+    if let Some(title) = fields.get("title").and_then(|v| v.as_str()) {
+        task.frontmatter.title = title.to_string();
+    }
+    if let Some(status) = fields.get("status").and_then(|v| v.as_str()) {
+        task.frontmatter.status = Status::parse(status).with_context(|| format!("Unknown status: {}", status))?;
+    }
+    if let Some(priority) = fields.get("priority").and_then(|v| v.as_str()) {
+        task.frontmatter.priority = Priority::parse(priority).with_context(|| format!("Unknown priority: {}", priority))?;
+    }
+    if let Some(tags) = fields.get("tags").and_then(|v| v.as_array()) {
+        task.frontmatter.tags = tags.iter().filter_map(|t| t.as_str().map(str::to_string)).collect();
+    }
+    if let Some(body) = fields.get("body").and_then(|v| v.as_str()) {
+        task.body = body.to_string();
+    }
+    storage.write_task(&mut task)?;
+    Ok(hooks::task_payload(&task))
+}
+
+/// Copy `value` into the calling plugin's memory, returning it packed
+fn reply(caller: &mut Caller<'_, Host>, value: &Value) -> wasmtime::Result<i64> {
+    let memory = export_memory(caller)?;
+    let alloc = caller.get_export("alloc")
+        .and_then(|e| e.into_func())
+        .ok_or_else(|| wasmtime::format_err!("Plugin doesn't export alloc"))?
+        .typed::<i32, i32>(&*caller)?;
+    let (ptr, len) = put(&mut *caller, memory, &alloc, &serde_json::to_vec(value)?)?;
+    Ok(pack(ptr, len))
+}
+
+fn read(caller: &mut Caller<'_, Host>, ptr: i32, len: i32) -> wasmtime::Result<Vec<u8>> {
+    let memory = export_memory(caller)?;
+    get(&*caller, memory, pack(ptr, len))
+}
+
+fn export_memory(caller: &mut Caller<'_, Host>) -> wasmtime::Result<Memory> {
+    caller.get_export("memory")
+        .and_then(|e| e.into_memory())
+        .ok_or_else(|| wasmtime::format_err!("Plugin doesn't export memory"))
+}
+
+fn put(mut cx: impl AsContextMut<Data = Host>, memory: Memory, alloc: &TypedFunc<i32, i32>, bytes: &[u8]) -> wasmtime::Result<(i32, i32)> {
+    let len = i32::try_from(bytes.len())?;
+    let ptr = alloc.call(&mut cx, len)?;
+    memory.write(&mut cx, ptr as u32 as usize, bytes)?;
+    Ok((ptr, len))
+}
+
+fn get(cx: impl AsContext<Data = Host>, memory: Memory, packed: i64) -> wasmtime::Result<Vec<u8>> {
+    let (ptr, len) = ((packed >> 32) as u32 as usize, packed as u32 as usize);
+    let bytes = memory.data(&cx)
+        .get(ptr..ptr + len)
+        .ok_or_else(|| wasmtime::format_err!("Plugin pointer out of bounds"))?;
+    Ok(bytes.to_vec())
+}
+
+fn pack(ptr: i32, len: i32) -> i64 {
+    ((ptr as u32 as i64) << 32) | len as u32 as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const MANIFEST: &str = r#"{"name":"demo","tools":[
+        {"name":"add_task","description":"Add a task","inputSchema":{"type":"object"}},
+        {"name":"list_all","description":"List tasks","inputSchema":{"type":"object"}},
+        {"name":"list_tasks","description":"Clashes with a built-in","inputSchema":{"type":"object"}}]}"#;
+    const FIELDS: &str = r#"{"title":"From a plugin","tags":["plugin"]}"#;
+
+    /// A plugin whose `add_task` writes `FIELDS` as a new task and whose
+    /// `list_all` returns the host's task list unchanged. A bump allocator from
+    /// offset 1024 is enough for a test.
+    fn plugin() -> String {
+        let escape = |s: &str| s.replace('"', "\\\"").replace('\n', "\\n");
+        format!(r#"
+(module
+  (import "tasktui" "list_tasks" (func $list_tasks (result i64)))
+  (import "tasktui" "write_task" (func $write_task (param i32 i32) (result i64)))
+  (import "tasktui" "log" (func $log (param i32 i32)))
+  (memory (export "memory") 1)
+  (global $next (mut i32) (i32.const 1024))
+  (data (i32.const 0) "{manifest}")
+  (data (i32.const 512) "{fields}")
+  (data (i32.const 600) "called")
+  (func (export "alloc") (param $len i32) (result i32)
+    (local $ptr i32)
+    (local.set $ptr (global.get $next))
+    (global.set $next (i32.add (global.get $next) (local.get $len)))
+    (local.get $ptr))
+  (func (export "tasktui_manifest") (result i64)
+    (i64.const {manifest_len}))
+  (func (export "tasktui_call") (param $ptr i32) (param $len i32) (result i64)
+    (call $log (i32.const 600) (i32.const 6))
+    ;; {{"arguments":...,"tool":"add_task"}}: the third byte from the end is the 'k' of add_task
+    (if (result i64) (i32.eq (i32.load8_u (i32.sub (i32.add (local.get $ptr) (local.get $len)) (i32.const 3))) (i32.const 107))
+      (then (call $write_task (i32.const 512) (i32.const {fields_len})))
+      (else (call $list_tasks)))))
+"#,
+            manifest = escape(MANIFEST),
+            manifest_len = MANIFEST.len(),
+            fields = escape(FIELDS),
+            fields_len = FIELDS.len(),
+        )
+    }
+
+    #[test]
+    fn test_plugin_tools() {
+        let dir = TempDir::new().unwrap();
+        let plugins_dir = dir.path().join(PLUGIN_DIR);
+        fs::create_dir_all(&plugins_dir).unwrap();
+        fs::write(plugins_dir.join("demo.wat"), plugin()).unwrap();
+        fs::write(plugins_dir.join("broken.wat"), "(module").unwrap();
+
+        let storage = Storage::new(dir.path().to_path_buf()).unwrap();
+        let mut plugins = Plugins::load(storage, &["list_tasks"]).unwrap();
+        assert_eq!(plugins.loaded.len(), 1);
+        assert_eq!(plugins.failed.len(), 1);
+        assert_eq!(plugins.loaded[0].name, "demo");
+        let names: Vec<&str> = plugins.tools().filter_map(|t| t["name"].as_str()).collect();
+        assert_eq!(names, vec!["add_task", "list_all"]);
+
+        let task = plugins.call("add_task", json!({})).unwrap();
+        assert_eq!(task["title"], "From a plugin");
+        assert_eq!(task["tags"], json!(["plugin"]));
+        let tasks = Storage::new(dir.path().to_path_buf()).unwrap().load_all_tasks().unwrap();
+        assert_eq!(tasks.len(), 1);
+
+        let listed = plugins.call("list_all", json!({})).unwrap();
+        assert_eq!(listed.as_array().unwrap().len(), 1);
+        assert_eq!(listed[0]["title"], "From a plugin");
+
+        assert!(plugins.call("list_tasks", json!({})).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+use crate::config::AppConfig;
+use crate::llm::extract_json;
+use crate::models::{self, Priority, TaskItem};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Open tasks sent to the LLM, most urgent first; the rest are left as they are
+pub const MAX_TASKS: usize = 150;
+
+/// Room for a reason per changed task
+pub const PRIORITIZE_MAX_TOKENS: u32 = 1500;
+
+/// System prompt for reviewing the backlog's priorities against the goals
+pub const PRIORITIZE_PROMPT: &str = "You review the backlog of a personal task manager. \
+Priorities run from p0 (drop everything) to p4 (someday). Given the open tasks and the active \
+goals, propose priority changes only where a task's priority clearly doesn't fit: work toward \
+high-priority goals, close deadlines or blocked follow-ups ranked too low, or busywork ranked too \
+high. Leave everything else alone. Reply with JSON only: \
+{\"changes\": [{\"code\": \"<task code>\", \"priority\": \"p0-p4\", \"reason\": \"<one short sentence>\"}]}";
+
+/// A proposed priority for one task, with the LLM's reason
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PriorityChange {
+    pub id: Uuid,
+    pub code: String,
+    pub title: String,
+    pub from: Priority,
+    pub to: Priority,
+    pub reason: String,
+}
+
+#[derive(Deserialize)]
+struct Reply {
+    #[serde(default)]
+    changes: Vec<ReplyChange>,
+}
+
+#[derive(Deserialize)]
+struct ReplyChange {
+    code: String,
+    priority: String,
+    #[serde(default)]
+    reason: String,
+}
+
+/// The open tasks and active goals as the prompt's user message
+pub fn backlog_prompt(tasks: &[TaskItem], config: &AppConfig) -> String {
+    let mut open: Vec<&TaskItem> = tasks.iter().filter(|t| t.is_open()).collect();
+    open.sort_by_key(|t| {
+        let due = t.frontmatter.due_date.map(|d| d.deadline());
+        (t.frontmatter.priority.rank(), due.is_none(), due)
+    });
+
+    let mut prompt = format!("Today is {}.\n\n", models::today().format("%Y-%m-%d"));
+    let goals = config.goals_context();
+    prompt.push_str(if goals.is_empty() { "No active goals.\n" } else { &goals });
+    prompt.push_str("\nOpen tasks:\n");
+    for task in open.iter().take(MAX_TASKS) {
+        let fm = &task.frontmatter;
+        let mut details = vec![format!("status: {}", fm.status.as_str())];
+        if let Some(due) = &fm.due_date {
+            details.push(format!("due {}", due));
+        }
+        if !fm.tags.is_empty() {
+            details.push(fm.tags.iter().map(|t| format!("#{}", t)).collect::<Vec<_>>().join(" "));
+        }
+        if let Some(goal) = fm.goal_id.and_then(|id| config.goals.iter().find(|g| g.id == id)) {
+            details.push(format!("goal: {}", goal.description));
+        }
+        prompt.push_str(&format!("- {} [{}] {} ({})\n", task.code(), fm.priority.as_str(), fm.title, details.join(", ")));
+    }
+    prompt
+}
+
+/// The changes in the LLM's reply that still apply: unknown or closed tasks,
+/// invalid priorities and ones the task already has are dropped
+pub fn parse_changes(tasks: &[TaskItem], response: &str) -> Result<Vec<PriorityChange>, String> {
+    let reply: Reply = serde_json::from_str(&extract_json(response)?)
+        .map_err(|e| format!("JSON parse error: {}", e))?;
+
+    let mut changes: Vec<PriorityChange> = Vec::new();
+    for change in reply.changes {
+        let Ok(id) = models::resolve_id(tasks, &change.code) else {
+            continue;
+        };
+        let Some(task) = tasks.iter().find(|t| t.frontmatter.id == id && t.is_open()) else {
+            continue;
+        };
+        let Some(to) = Priority::parse(&change.priority) else {
+            continue;
+        };
+        if to == task.frontmatter.priority || changes.iter().any(|c| c.id == id) {
+            continue;
+        }
+        changes.push(PriorityChange {
+            id,
+            code: task.code(),
+            title: task.frontmatter.title.clone(),
+            from: task.frontmatter.priority.clone(),
+            to,
+            reason: change.reason.trim().to_string(),
+        });
+    }
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Goal;
+    use crate::models::{ItemType, Status};
+
+    #[test]
+    fn test_backlog_prompt_and_changes() {
+        let mut config = AppConfig::default();
+        let mut goal = Goal::new("Ship v2".to_string(), "work".to_string());
+        goal.active = true;
+        config.goals.push(goal.clone());
+
+        let mut launch = TaskItem::new("Write launch post".to_string(), ItemType::Task);
+        launch.frontmatter.goal_id = Some(goal.id);
+        launch.frontmatter.priority = Priority::P3;
+        let chores = TaskItem::new("Sort photos".to_string(), ItemType::Task);
+        let mut done = TaskItem::new("Old thing".to_string(), ItemType::Task);
+        done.set_status(Status::Done);
+        let tasks = vec![launch.clone(), chores.clone(), done.clone()];
+
+        let prompt = backlog_prompt(&tasks, &config);
+        assert!(prompt.contains(&format!("- {} [p3] Write launch post (status: active, goal: Ship v2)", launch.code())));
+        assert!(prompt.contains("Ship v2"));
+        assert!(!prompt.contains("Old thing"));
+
+        let response = format!(
+            "```json\n{{\"changes\": [\
+                {{\"code\": \"{}\", \"priority\": \"p1\", \"reason\": \"Moves Ship v2 forward.\"}},\
+                {{\"code\": \"{}\", \"priority\": \"p2\", \"reason\": \"Already p2\"}},\
+                {{\"code\": \"{}\", \"priority\": \"p0\", \"reason\": \"Done already\"}},\
+                {{\"code\": \"ffffff\", \"priority\": \"p0\", \"reason\": \"No such task\"}}\
+            ]}}\n```",
+            launch.code(), chores.code(), done.code()
+        );
+        let changes = parse_changes(&tasks, &response).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!((changes[0].id, &changes[0].from, &changes[0].to), (launch.frontmatter.id, &Priority::P3, &Priority::P1));
+        assert_eq!(changes[0].reason, "Moves Ship v2 forward.");
+        assert!(parse_changes(&tasks, "no idea").is_err());
+    }
+}
@@ -0,0 +1,131 @@
+use crate::filter::resolve_date;
+use crate::models::{Due, Priority};
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Weekday};
+
+/// Inline tokens parsed out of new-task input, e.g.
+/// `Call Sam #work !p1 @phone due:fri +launch`.
+/// Tokens are whole words; everything else stays in the title.
+#[derive(Debug, Default, PartialEq)]
+pub struct QuickAdd {
+    pub title: String,
+    pub tags: Vec<String>,
+    /// `@context` words, stored on the task as `@context` tags
+    pub contexts: Vec<String>,
+    pub priority: Option<Priority>,
+    pub due_date: Option<Due>,
+    /// `+project` name, matched against project titles by the caller
+    pub project: Option<String>,
+}
+
+impl QuickAdd {
+    pub fn parse(input: &str, today: NaiveDate) -> Self {
+        let mut parsed = QuickAdd::default();
+        let mut title = Vec::new();
+
+        for word in input.split_whitespace() {
+            if let Some(tag) = word.strip_prefix('#').filter(|t| !t.is_empty()) {
+                if !parsed.tags.iter().any(|t| t == tag) {
+                    parsed.tags.push(tag.to_string());
+                }
+            } else if let Some(context) = word.strip_prefix('@').filter(|c| !c.is_empty()) {
+                if !parsed.contexts.iter().any(|c| c == context) {
+                    parsed.contexts.push(context.to_string());
+                }
+            } else if let Some(priority) = word.strip_prefix('!').and_then(Priority::parse) {
+                parsed.priority = Some(priority);
+            } else if let Some(due) = word.strip_prefix("due:").and_then(|d| parse_due(d, today)) {
+                parsed.due_date = Some(due);
+            } else if let Some(project) = word.strip_prefix('+').filter(|p| !p.is_empty()) {
+                parsed.project = Some(project.to_string());
+            } else {
+                title.push(word);
+            }
+        }
+
+        parsed.title = title.join(" ");
+        parsed
+    }
+
+    /// Tags to store on the task: plain tags, then contexts as `@context`
+    pub fn all_tags(&self) -> Vec<String> {
+        self.tags.iter()
+            .cloned()
+            .chain(self.contexts.iter().map(|c| format!("@{}", c)))
+            .collect()
+    }
+
+    pub fn has_tokens(&self) -> bool {
+        !self.tags.is_empty()
+            || !self.contexts.is_empty()
+            || self.priority.is_some()
+            || self.due_date.is_some()
+            || self.project.is_some()
+    }
+}
+
+/// Due dates: a full `2025-03-02T15:00`, or a day optionally followed by `@HH:MM`
+/// ("fri@15:00")
+fn parse_due(value: &str, today: NaiveDate) -> Option<Due> {
+    if let Some(due) = Due::parse(value) {
+        return Some(due);
+    }
+    let (day, time) = match value.split_once('@') {
+        Some((day, time)) => (day, Some(NaiveTime::parse_from_str(time, "%H:%M").ok()?)),
+        None => (value, None),
+    };
+    Some(Due { date: parse_day(day, today)?, time, offset: None })
+}
+
+/// Days: anything `resolve_date` accepts, "tomorrow", or a weekday name
+/// ("fri", "friday") meaning its next occurrence, today included
+pub fn parse_day(value: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let value = value.to_lowercase();
+    if value == "tomorrow" || value == "tom" {
+        return today.succ_opt();
+    }
+    if let Ok(weekday) = value.parse::<Weekday>() {
+        let days = (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+        return today.checked_add_signed(Duration::days(days as i64));
+    }
+    resolve_date(&value, today)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        // A Tuesday
+        NaiveDate::from_ymd_opt(2025, 11, 25).unwrap()
+    }
+
+    #[test]
+    fn test_parse_tokens() {
+        let parsed = QuickAdd::parse("Call Sam #work !high @phone due:fri +launch about C# #work", today());
+        assert_eq!(parsed.title, "Call Sam about C#");
+        assert_eq!(parsed.tags, vec!["work"]);
+        assert_eq!(parsed.all_tags(), vec!["work", "@phone"]);
+        assert_eq!(parsed.priority, Some(Priority::P1));
+        assert_eq!(parsed.due_date, NaiveDate::from_ymd_opt(2025, 11, 28).map(Due::on));
+        assert_eq!(parsed.project.as_deref(), Some("launch"));
+        assert!(parsed.has_tokens());
+
+        let timed = QuickAdd::parse("Standup due:tomorrow@09:30", today());
+        assert_eq!(timed.due_date.map(|d| d.to_string()).as_deref(), Some("2025-11-26T09:30"));
+    }
+
+    #[test]
+    fn test_parse_due_forms() {
+        assert_eq!(parse_day("tue", today()), Some(today()));
+        assert_eq!(parse_day("Monday", today()), NaiveDate::from_ymd_opt(2025, 12, 1));
+        assert_eq!(parse_day("tomorrow", today()), NaiveDate::from_ymd_opt(2025, 11, 26));
+        assert_eq!(parse_day("today+7", today()), NaiveDate::from_ymd_opt(2025, 12, 2));
+        assert_eq!(parse_day("2026-01-05", today()), NaiveDate::from_ymd_opt(2026, 1, 5));
+        assert_eq!(parse_day("someday", today()), None);
+
+        // Unknown values are left in the title
+        let parsed = QuickAdd::parse("Plan due:someday !urgent", today());
+        assert_eq!(parsed.title, "Plan due:someday !urgent");
+        assert!(!parsed.has_tokens());
+    }
+}
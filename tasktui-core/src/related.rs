@@ -0,0 +1,91 @@
+use crate::activity::LOG_HEADING;
+use crate::models::TaskItem;
+use std::collections::HashSet;
+
+/// Tasks shown under "Related" in the preview
+pub const RELATED_LIMIT: usize = 5;
+
+/// Below this score two tasks have only a stray word in common
+const MIN_SCORE: f64 = 0.12;
+
+/// Weight of shared tags against shared words in the score
+const TAG_WEIGHT: f64 = 0.4;
+
+pub(crate) const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "with", "from", "that", "this", "are", "was", "but", "not", "you", "all",
+    "can", "has", "have", "into", "out", "our", "about", "after", "before", "then", "than", "will",
+];
+
+/// Lowercase words of three or more letters, without common filler
+fn words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .filter(|w| !STOPWORDS.contains(&w.as_str()))
+        .collect()
+}
+
+/// Words of the title and body, leaving out the activity log, which every
+/// task's reads alike
+fn task_words(task: &TaskItem) -> HashSet<String> {
+    let body = crate::activity::without_section(&task.body, LOG_HEADING);
+    words(&format!("{} {}", task.frontmatter.title, body))
+}
+
+/// Shared share of two sets (Jaccard index), 0 when both are empty
+fn overlap<T: Eq + std::hash::Hash>(a: &HashSet<T>, b: &HashSet<T>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Up to `limit` open tasks most like `task`, by tag overlap and words in
+/// common, most similar first
+pub fn related<'a>(tasks: &'a [TaskItem], task: &TaskItem, limit: usize) -> Vec<&'a TaskItem> {
+    let tags: HashSet<&String> = task.frontmatter.tags.iter().collect();
+    let text = task_words(task);
+    let mut scored: Vec<(f64, &TaskItem)> = tasks.iter()
+        .filter(|t| t.frontmatter.id != task.frontmatter.id && t.is_open())
+        .map(|t| {
+            let other_tags: HashSet<&String> = t.frontmatter.tags.iter().collect();
+            let score = TAG_WEIGHT * overlap(&tags, &other_tags) + (1.0 - TAG_WEIGHT) * overlap(&text, &task_words(t));
+            (score, t)
+        })
+        .filter(|(score, _)| *score >= MIN_SCORE)
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored.into_iter().take(limit).map(|(_, t)| t).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ItemType, Status};
+
+    #[test]
+    fn test_related() {
+        let task = |title: &str, tags: &[&str], body: &str| {
+            let mut task = TaskItem::new(title.to_string(), ItemType::Task);
+            task.frontmatter.tags = tags.iter().map(|t| t.to_string()).collect();
+            task.body = body.to_string();
+            task
+        };
+        let renew = task("Renew passport", &["personal"], "Book an appointment at the passport office");
+        let mut done = task("Passport photos", &["personal"], "");
+        done.set_status(Status::Done);
+        let tasks = vec![
+            renew.clone(),
+            task("Passport appointment", &["personal"], "Bring the old passport"),
+            task("Book dentist appointment", &["health"], ""),
+            task("Deploy release", &["work"], "## Log\n- 2025-03-01 status: active"),
+            done,
+        ];
+
+        let titles: Vec<_> = related(&tasks, &renew, RELATED_LIMIT).iter().map(|t| t.frontmatter.title.as_str()).collect();
+        assert_eq!(titles, ["Passport appointment", "Book dentist appointment"]);
+        assert_eq!(related(&tasks, &renew, 1).len(), 1);
+        assert!(words("The plan for a re-launch").contains("launch"));
+    }
+}
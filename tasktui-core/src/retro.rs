@@ -0,0 +1,253 @@
+use crate::config::AppConfig;
+use crate::models::{self, GoalLinks, Status, TaskItem};
+use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Active or next tasks older than this, still open, count as stalled
+const STALLED_OPEN_DAYS: i64 = 14;
+/// Waiting tasks delegated longer ago than this count as stalled
+const STALLED_WAITING_DAYS: i64 = 7;
+
+/// What happened in one ISO week: the facts a retrospective is written from
+#[derive(Debug, Serialize)]
+pub struct WeekReview {
+    /// `YYYY-WW`
+    pub week: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub completed: Vec<TaskRef>,
+    /// Completions per workstream; tasks in no workstream count under "other"
+    pub by_workstream: BTreeMap<String, usize>,
+    pub stalled: Vec<Stall>,
+    pub goals: Vec<GoalProgress>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TaskRef {
+    pub id: Uuid,
+    pub title: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Stall {
+    pub id: Uuid,
+    pub title: String,
+    /// e.g. "waiting on Sam for 12 days"
+    pub reason: String,
+}
+
+/// Completions this week and open tasks among those linked to a goal
+#[derive(Debug, Serialize)]
+pub struct GoalProgress {
+    pub goal: String,
+    pub area: String,
+    pub completed: usize,
+    pub open: usize,
+    /// Linked tasks that are active or next; an active goal with none isn't moving
+    pub next_actions: usize,
+}
+
+/// Parse `2025-48` or `2025-W48` into that week's Monday
+pub fn parse_week(value: &str) -> Option<NaiveDate> {
+    let (year, week) = value.split_once('-')?;
+    let week = week.trim_start_matches(['W', 'w']);
+    NaiveDate::from_isoywd_opt(year.parse().ok()?, week.parse().ok()?, Weekday::Mon)
+}
+
+/// Monday of the week containing `date`
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// `YYYY-WW` for the ISO week containing `date`
+pub fn week_label(date: NaiveDate) -> String {
+    let week = date.iso_week();
+    format!("{}-{:02}", week.year(), week.week())
+}
+
+/// Where a week's retrospective is saved: `reviews/YYYY-WW.md` in the data directory
+pub fn review_path(data_dir: &Path, week: &str) -> PathBuf {
+    data_dir.join("reviews").join(format!("{}.md", week))
+}
+
+impl WeekReview {
+    /// Gather the week starting on `monday`. Stalls are judged as of the week's
+    /// end, or today for the current week.
+    pub fn new(tasks: &[TaskItem], config: &AppConfig, monday: NaiveDate) -> Self {
+        let start = monday;
+        let end = monday + Duration::days(6);
+        let as_of = end.min(models::today());
+        let completed_in_week = |t: &&TaskItem| {
+            t.frontmatter.completed_at
+                .map(|at| at.with_timezone(&Local).date_naive())
+                .is_some_and(|day| start <= day && day <= end)
+        };
+
+        let completed: Vec<&TaskItem> = tasks.iter().filter(|t| !t.is_project()).filter(completed_in_week).collect();
+
+        let mut by_workstream = BTreeMap::new();
+        for task in &completed {
+            let streams: Vec<_> = config.workstreams.iter().filter(|ws| task.has_tag(&ws.name.to_lowercase())).collect();
+            if streams.is_empty() {
+                *by_workstream.entry("other".to_string()).or_insert(0) += 1;
+            }
+            for ws in streams {
+                *by_workstream.entry(ws.name.clone()).or_insert(0) += 1;
+            }
+        }
+
+        let stalled = tasks.iter()
+            .filter(|t| !t.is_project())
+            .filter_map(|t| {
+                let fm = &t.frontmatter;
+                let age = |since: chrono::DateTime<chrono::Utc>| (as_of - since.with_timezone(&Local).date_naive()).num_days();
+                let reason = match fm.status {
+                    Status::Waiting => {
+                        let days = age(fm.delegated_at.unwrap_or(fm.created_at));
+                        let who = fm.delegated_to.as_deref().map(|p| format!(" on {}", p)).unwrap_or_default();
+                        (days > STALLED_WAITING_DAYS).then(|| format!("waiting{} for {} days", who, days))
+                    }
+                    Status::Active | Status::Next => {
+                        let days = age(fm.created_at);
+                        (days > STALLED_OPEN_DAYS).then(|| format!("{} for {} days", fm.status.as_str(), days))
+                    }
+                    _ => None,
+                }?;
+                Some(Stall { id: fm.id, title: fm.title.clone(), reason })
+            })
+            .collect();
+
+        let goals = config.active_goals().into_iter()
+            .map(|goal| {
+                let links = GoalLinks::new(tasks, goal.id);
+                GoalProgress {
+                    goal: goal.description.clone(),
+                    area: goal.area.clone(),
+                    completed: links.tasks.iter().copied().filter(completed_in_week).count(),
+                    open: links.tasks.iter()
+                        .filter(|t| matches!(t.frontmatter.status, Status::Active | Status::Next | Status::Waiting))
+                        .count(),
+                    next_actions: links.next_actions(),
+                }
+            })
+            .collect();
+
+        Self {
+            week: week_label(monday),
+            start,
+            end,
+            completed: completed.iter().map(|t| TaskRef { id: t.frontmatter.id, title: t.frontmatter.title.clone() }).collect(),
+            by_workstream,
+            stalled,
+            goals,
+        }
+    }
+
+    /// The facts as markdown: the whole review without the LLM, and its input with it
+    pub fn to_markdown(&self) -> String {
+        let mut md = format!("## Week {} ({} – {})\n\n", self.week, self.start, self.end);
+
+        md.push_str(&format!("### Completed ({})\n\n", self.completed.len()));
+        for task in &self.completed {
+            md.push_str(&format!("- {}\n", task.title));
+        }
+        if self.completed.is_empty() {
+            md.push_str("None.\n");
+        }
+        if !self.by_workstream.is_empty() {
+            let streams: Vec<_> = self.by_workstream.iter().map(|(ws, n)| format!("{} {}", ws, n)).collect();
+            md.push_str(&format!("\nBy workstream: {}\n", streams.join(", ")));
+        }
+
+        md.push_str(&format!("\n### Stalled ({})\n\n", self.stalled.len()));
+        for stall in &self.stalled {
+            md.push_str(&format!("- {} — {}\n", stall.title, stall.reason));
+        }
+        if self.stalled.is_empty() {
+            md.push_str("None.\n");
+        }
+
+        if !self.goals.is_empty() {
+            md.push_str("\n### Goals\n\n");
+            for goal in &self.goals {
+                md.push_str(&format!(
+                    "- {} ({}): {} done this week, {} open\n",
+                    goal.goal, goal.area, goal.completed, goal.open
+                ));
+            }
+            for goal in self.goals.iter().filter(|g| g.next_actions == 0) {
+                md.push_str(&format!("\n⚠ No next actions linked to \"{}\"\n", goal.goal));
+            }
+        }
+        md
+    }
+}
+
+/// Room for a few paragraphs
+pub const RETRO_MAX_TOKENS: u32 = 700;
+
+/// System prompt for turning a week's facts into a retrospective
+pub const RETRO_PROMPT: &str = "You write short weekly retrospectives for a personal task manager. \
+Given the week's completions, stalled tasks and goal progress, write a markdown narrative of \
+three short sections: \"What went well\", \"What got stuck\" (with a concrete suggestion for each \
+stalled item) and \"Next week\" (three focus points tied to the goals). Use only the facts given; \
+don't invent tasks. Start directly with the first heading.";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ItemType;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_week_review() {
+        let monday = parse_week("2025-W48").unwrap();
+        assert_eq!(monday, NaiveDate::from_ymd_opt(2025, 11, 24).unwrap());
+        assert_eq!(parse_week("2025-48"), Some(monday));
+        assert_eq!(week_label(monday + Duration::days(6)), "2025-48");
+        assert_eq!(week_start(monday + Duration::days(6)), monday);
+
+        let mut config = AppConfig::default();
+        config.add_goal("Ship the launch".to_string(), "work".to_string());
+        config.add_goal("Get fit".to_string(), "health".to_string());
+        let launch = config.goals[0].id;
+        let at = |day: u32| Utc.with_ymd_and_hms(2025, 11, day, 12, 0, 0).unwrap();
+
+        let mut tasks = Vec::new();
+        for (title, tags, completed) in [("Draft spec", vec!["work"], 25), ("Fix bike", vec![], 27), ("Old win", vec!["work"], 10)] {
+            let mut task = TaskItem::new(title.to_string(), ItemType::Task);
+            task.frontmatter.goal_id = tags.contains(&"work").then_some(launch);
+            task.frontmatter.tags = tags.into_iter().map(String::from).collect();
+            task.frontmatter.status = Status::Done;
+            task.frontmatter.completed_at = Some(at(completed));
+            tasks.push(task);
+        }
+        let mut waiting = TaskItem::new("Contract review".to_string(), ItemType::Task);
+        waiting.delegate("Sam".to_string());
+        waiting.frontmatter.delegated_at = Some(at(14));
+        tasks.push(waiting);
+        let mut open = TaskItem::new("Plan offsite".to_string(), ItemType::Task);
+        open.frontmatter.tags = vec!["work".to_string()];
+        open.frontmatter.goal_id = Some(launch);
+        open.frontmatter.created_at = at(25);
+        tasks.push(open);
+
+        let review = WeekReview::new(&tasks, &config, monday);
+        let titles: Vec<_> = review.completed.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, ["Draft spec", "Fix bike"]);
+        assert_eq!(review.by_workstream, BTreeMap::from([("other".to_string(), 1), ("work".to_string(), 1)]));
+        assert_eq!(review.stalled.len(), 1);
+        assert_eq!(review.stalled[0].reason, "waiting on Sam for 16 days");
+        assert_eq!((review.goals[0].completed, review.goals[0].open, review.goals[0].next_actions), (1, 1, 1));
+        assert_eq!(review.goals[1].next_actions, 0);
+
+        let md = review.to_markdown();
+        assert!(md.starts_with("## Week 2025-48 (2025-11-24 – 2025-11-30)"));
+        assert!(md.contains("- Contract review — waiting on Sam for 16 days"));
+        assert!(md.contains("⚠ No next actions linked to \"Get fit\""));
+        assert!(!md.contains("linked to \"Ship the launch\""));
+    }
+}
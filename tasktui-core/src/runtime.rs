@@ -0,0 +1,11 @@
+use std::future::Future;
+
+/// Run a future from synchronous code, on a fresh runtime. Inside an existing
+/// runtime that has to happen on another thread, since runtimes can't nest.
+pub fn block_on<T: Send>(future: impl Future<Output = T> + Send) -> Option<T> {
+    let run = || tokio::runtime::Runtime::new().ok().map(|rt| rt.block_on(future));
+    match tokio::runtime::Handle::try_current() {
+        Ok(_) => std::thread::scope(|s| s.spawn(run).join().ok().flatten()),
+        Err(_) => run(),
+    }
+}
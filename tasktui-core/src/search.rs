@@ -0,0 +1,135 @@
+use crate::activity::LOG_HEADING;
+use crate::models::TaskItem;
+
+/// Weight of a term found in the title, against one found in the body
+const TITLE_WEIGHT: f64 = 3.0;
+
+/// Weight of a term found in a tag
+const TAG_WEIGHT: f64 = 2.0;
+
+/// Characters of context kept around a body match in the snippet
+const SNIPPET_CONTEXT: usize = 30;
+
+/// A task matching a search, with the body text around the first match
+pub struct Hit<'a> {
+    pub task: &'a TaskItem,
+    pub score: f64,
+    pub snippet: Option<String>,
+}
+
+/// Lowercase terms of a query; quoted phrases stay one term
+pub fn terms(query: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    for (idx, part) in query.split('"').enumerate() {
+        if idx % 2 == 1 {
+            let phrase = part.trim().to_lowercase();
+            if !phrase.is_empty() {
+                terms.push(phrase);
+            }
+        } else {
+            terms.extend(part.split_whitespace().map(str::to_lowercase));
+        }
+    }
+    terms
+}
+
+/// Tasks containing every term of `query` in their title, tags or body (the
+/// activity log left out), best first: title matches count most, then tags,
+/// then each time a term appears in the body. An empty query matches nothing.
+pub fn search<'a>(tasks: &'a [TaskItem], query: &str, limit: usize) -> Vec<Hit<'a>> {
+    let terms = terms(query);
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    let mut hits: Vec<Hit> = tasks.iter()
+        .filter_map(|task| {
+            let title = task.frontmatter.title.to_lowercase();
+            let tags: Vec<String> = task.frontmatter.tags.iter().map(|t| t.to_lowercase()).collect();
+            let body = crate::activity::without_section(&task.body, LOG_HEADING);
+            let lower_body = body.to_lowercase();
+
+            let mut score = 0.0;
+            for term in &terms {
+                let in_title = title.contains(term.as_str());
+                let in_tags = tags.iter().any(|t| t.contains(term.as_str()));
+                let in_body = lower_body.matches(term.as_str()).count();
+                if !in_title && !in_tags && in_body == 0 {
+                    return None;
+                }
+                score += if in_title { TITLE_WEIGHT } else { 0.0 }
+                    + if in_tags { TAG_WEIGHT } else { 0.0 }
+                    + (in_body as f64).min(5.0);
+            }
+            Some(Hit { task, score, snippet: snippet_for(&body, &terms) })
+        })
+        .collect();
+
+    hits.sort_by(|a, b| {
+        b.score.total_cmp(&a.score).then_with(|| b.task.last_touched().cmp(&a.task.last_touched()))
+    });
+    hits.truncate(limit);
+    hits
+}
+
+/// The line of `body` holding the first of `terms` found in it, cut to some
+/// context either side
+pub(crate) fn snippet_for(body: &str, terms: &[String]) -> Option<String> {
+    let lower_body = body.to_lowercase();
+    terms.iter().find_map(|term| snippet(body, &lower_body, term))
+}
+
+/// The line of `body` holding `term`, cut to some context either side
+fn snippet(body: &str, lower_body: &str, term: &str) -> Option<String> {
+    let at = lower_body.find(term)?;
+    // Lowercasing can change byte lengths, so map the match back by line
+    let line_idx = lower_body[..at].matches('\n').count();
+    let line = body.lines().nth(line_idx)?.trim();
+    let lower_line = line.to_lowercase();
+    let chars: Vec<char> = line.chars().collect();
+    let start_char = lower_line.find(term).map_or(0, |at| lower_line[..at].chars().count());
+    let from = start_char.saturating_sub(SNIPPET_CONTEXT);
+    let to = (start_char + term.chars().count() + SNIPPET_CONTEXT).min(chars.len());
+
+    let mut text: String = chars[from..to].iter().collect();
+    if from > 0 {
+        text.insert(0, '…');
+    }
+    if to < chars.len() {
+        text.push('…');
+    }
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ItemType;
+
+    #[test]
+    fn test_search() {
+        let task = |title: &str, tags: &[&str], body: &str| {
+            let mut task = TaskItem::new(title.to_string(), ItemType::Task);
+            task.frontmatter.tags = tags.iter().map(|t| t.to_string()).collect();
+            task.body = body.to_string();
+            task
+        };
+        let tasks = vec![
+            task("Review vendor agreement", &["legal"], "Check the contract renewal clause before March."),
+            task("Contract renewal", &[], ""),
+            task("Buy milk", &["home"], "## Log\n- contract mentioned in the log only"),
+        ];
+
+        let hits = search(&tasks, "contract", 10);
+        let titles: Vec<&str> = hits.iter().map(|h| h.task.frontmatter.title.as_str()).collect();
+        assert_eq!(titles, vec!["Contract renewal", "Review vendor agreement"]);
+        assert_eq!(hits[1].snippet.as_deref(), Some("Check the contract renewal clause before March."));
+
+        assert_eq!(search(&tasks, "CONTRACT legal", 10).len(), 1);
+        assert_eq!(search(&tasks, "\"renewal clause\"", 10).len(), 1);
+        assert_eq!(search(&tasks, "home", 10)[0].task.frontmatter.title, "Buy milk");
+        assert!(search(&tasks, "contract milk", 10).is_empty());
+        assert!(search(&tasks, "  ", 10).is_empty());
+        assert_eq!(search(&tasks, "contract", 1).len(), 1);
+    }
+}
@@ -0,0 +1,335 @@
+//! A tantivy index of task titles, tags and bodies in `.tasktui-index/`, kept
+//! up to date by `Storage` as tasks are written, deleted or found changed on
+//! load. It answers `/` search, the MCP `search_tasks` tool and the preview's
+//! related tasks when the `search-index` feature is on; without it those scan
+//! every task (`search::search`, `related::related`).
+
+use crate::activity::LOG_HEADING;
+use crate::llm::cache::fnv1a;
+use crate::models::TaskItem;
+use crate::related::STOPWORDS;
+use crate::search::{self, Hit};
+use anyhow::{Context, Result};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tantivy::collector::TopDocs;
+use tantivy::directory::MmapDirectory;
+use tantivy::query::{
+    BooleanQuery, BoostQuery, FuzzyTermQuery, MoreLikeThisQuery, Occur, PhraseQuery, Query, TermQuery,
+};
+use tantivy::schema::{Field, IndexRecordOption, OwnedValue, Schema, Value, FAST, STORED, STRING, TEXT};
+use tantivy::{doc, Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument, Term};
+use uuid::Uuid;
+
+/// Memory the writer may buffer before flushing a segment
+const WRITER_MEMORY: usize = 15_000_000;
+
+/// Boost of a term found in the title, against one found in the body
+const TITLE_BOOST: f32 = 3.0;
+
+/// Boost of a term found in a tag
+const TAG_BOOST: f32 = 2.0;
+
+/// Extra hits fetched to make up for indexed tasks the caller no longer has
+const SLACK: usize = 20;
+
+struct Fields {
+    id: Field,
+    hash: Field,
+    title: Field,
+    tags: Field,
+    body: Field,
+}
+
+pub struct SearchIndex {
+    index: Index,
+    reader: IndexReader,
+    fields: Fields,
+    /// Text hash per indexed task, so unchanged tasks aren't indexed again
+    hashes: RefCell<HashMap<Uuid, u64>>,
+    /// Whether a `sync` has checked the index against the whole vault
+    synced: Cell<bool>,
+}
+
+impl SearchIndex {
+    pub const DIR_NAME: &'static str = ".tasktui-index";
+
+    pub fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join(Self::DIR_NAME)
+    }
+
+    /// Open the vault's index, creating it when missing
+    pub fn open(data_dir: &Path) -> Result<Self> {
+        let mut builder = Schema::builder();
+        let fields = Fields {
+            id: builder.add_text_field("id", STRING | STORED),
+            hash: builder.add_u64_field("hash", STORED | FAST),
+            title: builder.add_text_field("title", TEXT),
+            tags: builder.add_text_field("tags", TEXT),
+            body: builder.add_text_field("body", TEXT),
+        };
+        let path = Self::path(data_dir);
+        fs::create_dir_all(&path).context("Failed to create the search index folder")?;
+        let directory = MmapDirectory::open(&path).context("Failed to open the search index folder")?;
+        let index = Index::open_or_create(directory, builder.build()).context("Failed to open the search index")?;
+        let reader = index.reader_builder().reload_policy(ReloadPolicy::Manual).try_into()?;
+
+        let index = Self { index, reader, fields, hashes: RefCell::new(HashMap::new()), synced: Cell::new(false) };
+        *index.hashes.borrow_mut() = index.stored_hashes()?;
+        Ok(index)
+    }
+
+    /// Text hash of every task in the index
+    fn stored_hashes(&self) -> Result<HashMap<Uuid, u64>> {
+        let searcher = self.reader.searcher();
+        let mut hashes = HashMap::new();
+        for segment in searcher.segment_readers() {
+            let store = segment.get_store_reader(1)?;
+            for doc_id in segment.doc_ids_alive() {
+                let doc: TantivyDocument = store.get(doc_id)?;
+                let id = doc.get_first(self.fields.id).and_then(|v| v.as_str()).and_then(|id| id.parse().ok());
+                let hash = doc.get_first(self.fields.hash).and_then(|v| v.as_u64());
+                if let (Some(id), Some(hash)) = (id, hash) {
+                    hashes.insert(id, hash);
+                }
+            }
+        }
+        Ok(hashes)
+    }
+
+    /// Index `changed` tasks whose text differs from what's indexed and drop
+    /// `removed` ones. Returns how many tasks were reindexed.
+    pub fn update<'a>(&self, changed: impl IntoIterator<Item = &'a TaskItem>, removed: &[Uuid]) -> Result<usize> {
+        let stale: Vec<(&TaskItem, u64)> = {
+            let hashes = self.hashes.borrow();
+            changed
+                .into_iter()
+                .map(|task| (task, fnv1a(&task_text(task))))
+                .filter(|(task, hash)| hashes.get(&task.frontmatter.id) != Some(hash))
+                .collect()
+        };
+        let removed: Vec<Uuid> = {
+            let hashes = self.hashes.borrow();
+            removed.iter().copied().filter(|id| hashes.contains_key(id)).collect()
+        };
+        if stale.is_empty() && removed.is_empty() {
+            return Ok(0);
+        }
+
+        // One writer per change: another tasktui process may be writing the same vault
+        let mut writer: IndexWriter = self.index
+            .writer_with_num_threads(1, WRITER_MEMORY)
+            .context("Failed to lock the search index")?;
+        for id in &removed {
+            writer.delete_term(Term::from_field_text(self.fields.id, &id.to_string()));
+        }
+        for (task, hash) in &stale {
+            let id = task.frontmatter.id.to_string();
+            writer.delete_term(Term::from_field_text(self.fields.id, &id));
+            let body = crate::activity::without_section(&task.body, LOG_HEADING);
+            let mut document = doc!(
+                self.fields.id => id,
+                self.fields.hash => *hash,
+                self.fields.title => task.frontmatter.title.as_str(),
+                self.fields.body => body,
+            );
+            for tag in &task.frontmatter.tags {
+                document.add_text(self.fields.tags, tag);
+            }
+            writer.add_document(document)?;
+        }
+        writer.commit().context("Failed to save the search index")?;
+        writer.wait_merging_threads()?;
+        self.reader.reload()?;
+
+        let mut hashes = self.hashes.borrow_mut();
+        for id in &removed {
+            hashes.remove(id);
+        }
+        for (task, hash) in &stale {
+            hashes.insert(task.frontmatter.id, *hash);
+        }
+        Ok(stale.len())
+    }
+
+    /// Bring the index in line with the whole vault: index new and changed
+    /// tasks and drop the ones no longer in `tasks`
+    pub fn sync<'a>(&self, tasks: impl IntoIterator<Item = &'a TaskItem>) -> Result<usize> {
+        let tasks: Vec<&TaskItem> = tasks.into_iter().collect();
+        let ids: HashSet<Uuid> = tasks.iter().map(|t| t.frontmatter.id).collect();
+        let removed: Vec<Uuid> = self.hashes.borrow().keys().filter(|id| !ids.contains(id)).copied().collect();
+        let updated = self.update(tasks, &removed)?;
+        self.synced.set(true);
+        Ok(updated)
+    }
+
+    /// Whether the index was checked against the whole vault since it was
+    /// opened. Until then, tasks deleted while no tasktui was running may
+    /// still be in it.
+    pub fn is_synced(&self) -> bool {
+        self.synced.get()
+    }
+
+    /// Lowercase tokens of `text` as the index splits them
+    fn tokens(&self, field: Field, text: &str) -> Result<Vec<String>> {
+        let mut analyzer = self.index.tokenizer_for_field(field)?;
+        let mut stream = analyzer.token_stream(text);
+        let mut tokens = Vec::new();
+        while let Some(token) = stream.next() {
+            tokens.push(token.text.clone());
+        }
+        Ok(tokens)
+    }
+
+    /// Matches for one query term in `field`: a phrase for several words, and a
+    /// prefix for the last word being typed
+    fn term_query(&self, field: Field, term: &str, prefix: bool) -> Result<Option<Box<dyn Query>>> {
+        let tokens = self.tokens(field, term)?;
+        let terms: Vec<Term> = tokens.iter().map(|token| Term::from_field_text(field, token)).collect();
+        Ok(match terms.len() {
+            0 => None,
+            1 if prefix => Some(Box::new(FuzzyTermQuery::new_prefix(terms[0].clone(), 0, true))),
+            1 => Some(Box::new(TermQuery::new(terms[0].clone(), IndexRecordOption::WithFreqs))),
+            _ => Some(Box::new(PhraseQuery::new(terms))),
+        })
+    }
+
+    /// Tasks of `tasks` containing every term of `query` in their title, tags
+    /// or body, best first by BM25 with title and tag matches boosted. The last
+    /// word also matches as a prefix, so results follow typing.
+    pub fn search<'a>(&self, tasks: &'a [TaskItem], query: &str, limit: usize) -> Result<Vec<Hit<'a>>> {
+        let terms = search::terms(query);
+        let typing = !query.ends_with(|c: char| c.is_whitespace() || c == '"');
+        let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+        for (n, term) in terms.iter().enumerate() {
+            let prefix = typing && n == terms.len() - 1 && !term.contains(' ');
+            let mut alternatives: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+            for (field, boost) in [(self.fields.title, TITLE_BOOST), (self.fields.tags, TAG_BOOST), (self.fields.body, 1.0)] {
+                if let Some(query) = self.term_query(field, term, prefix)? {
+                    alternatives.push((Occur::Should, Box::new(BoostQuery::new(query, boost))));
+                }
+            }
+            if !alternatives.is_empty() {
+                clauses.push((Occur::Must, Box::new(BooleanQuery::new(alternatives))));
+            }
+        }
+        if clauses.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let by_id: HashMap<Uuid, &TaskItem> = tasks.iter().map(|t| (t.frontmatter.id, t)).collect();
+        let hits = self.top(&BooleanQuery::new(clauses), limit + SLACK)?
+            .into_iter()
+            .filter_map(|(score, id)| {
+                let task = *by_id.get(&id)?;
+                let body = crate::activity::without_section(&task.body, LOG_HEADING);
+                let snippet = search::snippet_for(&body, &terms);
+                Some(Hit { task, score: score as f64, snippet })
+            })
+            .take(limit)
+            .collect();
+        Ok(hits)
+    }
+
+    /// Up to `limit` open tasks of `tasks` most like `task`: the terms of its
+    /// title, tags and body that are rarest across the vault, searched for in
+    /// the others
+    pub fn related<'a>(&self, tasks: &'a [TaskItem], task: &TaskItem, limit: usize) -> Result<Vec<&'a TaskItem>> {
+        let body = crate::activity::without_section(&task.body, LOG_HEADING);
+        let text = |value: &str| vec![OwnedValue::from(value.to_string())];
+        let mut fields = vec![(self.fields.title, text(&task.frontmatter.title)), (self.fields.body, text(&body))];
+        fields.push((self.fields.tags, task.frontmatter.tags.iter().map(|t| OwnedValue::from(t.clone())).collect()));
+        let query = MoreLikeThisQuery::builder()
+            .with_min_doc_frequency(2)
+            .with_min_term_frequency(1)
+            .with_min_word_length(3)
+            .with_stop_words(STOPWORDS.iter().map(|w| w.to_string()).collect())
+            .with_document_fields(fields);
+
+        let by_id: HashMap<Uuid, &TaskItem> = tasks.iter().map(|t| (t.frontmatter.id, t)).collect();
+        Ok(self.top(&query, limit + SLACK)?
+            .into_iter()
+            .filter_map(|(_, id)| by_id.get(&id).copied())
+            .filter(|t| t.frontmatter.id != task.frontmatter.id && t.is_open())
+            .take(limit)
+            .collect())
+    }
+
+    /// Ids of the `limit` best matches of `query`, with their scores
+    fn top(&self, query: &dyn Query, limit: usize) -> Result<Vec<(f32, Uuid)>> {
+        // Pick up commits made by other processes on the same vault
+        self.reader.reload()?;
+        let searcher = self.reader.searcher();
+        let mut hits = Vec::new();
+        for (score, address) in searcher.search(query, &TopDocs::with_limit(limit))? {
+            let doc: TantivyDocument = searcher.doc(address)?;
+            if let Some(id) = doc.get_first(self.fields.id).and_then(|v| v.as_str()).and_then(|id| id.parse().ok()) {
+                hits.push((score, id));
+            }
+        }
+        Ok(hits)
+    }
+}
+
+/// What changing the indexed text of a task changes: its title, tags and body
+/// without the activity log
+fn task_text(task: &TaskItem) -> String {
+    let body = crate::activity::without_section(&task.body, LOG_HEADING);
+    format!("{}\n{}\n{}", task.frontmatter.title, task.frontmatter.tags.join("\n"), body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ItemType, Status};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_index_search_and_related() {
+        let task = |title: &str, tags: &[&str], body: &str| {
+            let mut task = TaskItem::new(title.to_string(), ItemType::Task);
+            task.frontmatter.tags = tags.iter().map(|t| t.to_string()).collect();
+            task.body = body.to_string();
+            task
+        };
+        let mut tasks = vec![
+            task("Review vendor agreement", &["legal"], "Check the contract renewal clause before March."),
+            task("Contract renewal", &[], ""),
+            task("Buy milk", &["home"], "## Log\n- contract mentioned in the log only"),
+            task("Renew passport", &["personal"], "Book an appointment at the passport office"),
+            task("Passport appointment", &["personal"], "Bring the old passport"),
+        ];
+        let dir = TempDir::new().unwrap();
+        let index = SearchIndex::open(dir.path()).unwrap();
+        assert_eq!(index.sync(&tasks).unwrap(), 5);
+        assert_eq!(index.sync(&tasks).unwrap(), 0);
+
+        let titles = |hits: Vec<Hit>| hits.iter().map(|h| h.task.frontmatter.title.clone()).collect::<Vec<_>>();
+        assert_eq!(titles(index.search(&tasks, "contract ", 10).unwrap()), ["Contract renewal", "Review vendor agreement"]);
+        assert_eq!(index.search(&tasks, "contract legal", 10).unwrap().len(), 1);
+        assert_eq!(index.search(&tasks, "\"renewal clause\"", 10).unwrap().len(), 1);
+        assert!(index.search(&tasks, "\"clause renewal\"", 10).unwrap().is_empty());
+        // The word being typed matches as a prefix
+        assert_eq!(titles(index.search(&tasks, "passp", 10).unwrap()).len(), 2);
+        assert!(index.search(&tasks, "passp ", 10).unwrap().is_empty());
+        let hits = index.search(&tasks, "clause", 10).unwrap();
+        assert_eq!(hits[0].snippet.as_deref(), Some("Check the contract renewal clause before March."));
+
+        let related = index.related(&tasks, &tasks[3], 5).unwrap();
+        assert_eq!(related.iter().map(|t| t.frontmatter.title.as_str()).collect::<Vec<_>>(), ["Passport appointment"]);
+
+        // Changes and deletions carry over, also into a reopened index
+        tasks[4].set_status(Status::Done);
+        tasks[1].frontmatter.title = "Cancel subscription".to_string();
+        let removed = tasks.remove(2);
+        assert_eq!(index.update(&tasks, &[removed.frontmatter.id]).unwrap(), 1);
+        assert!(index.related(&tasks, &tasks[2], 5).unwrap().is_empty());
+        drop(index);
+        let index = SearchIndex::open(dir.path()).unwrap();
+        assert_eq!(index.sync(&tasks).unwrap(), 0);
+        assert_eq!(titles(index.search(&tasks, "contract ", 10).unwrap()), ["Review vendor agreement"]);
+        assert_eq!(titles(index.search(&tasks, "subscription", 10).unwrap()), ["Cancel subscription"]);
+    }
+}
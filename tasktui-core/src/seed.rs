@@ -0,0 +1,160 @@
+use crate::models::{Due, ItemType, Priority, Status, TaskItem};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use fastrand::Rng;
+use uuid::{Builder, Uuid};
+
+const VERBS: &[&str] = &[
+    "Write", "Review", "Draft", "Fix", "Plan", "Call", "Email", "Update", "Prepare", "Book",
+    "Clean up", "Research", "Schedule", "Test", "Order", "Refactor", "Sketch", "Send",
+];
+const OBJECTS: &[&str] = &[
+    "quarterly report", "onboarding doc", "login bug", "team offsite", "dentist", "landlord",
+    "release notes", "budget sheet", "design review", "API docs", "invoice", "garage", "tax forms",
+    "migration plan", "demo script", "hiring rubric", "flaky tests", "birthday gift", "slides",
+    "newsletter", "car service", "backup script", "roadmap", "standup notes",
+];
+const PROJECTS: &[&str] = &[
+    "Website relaunch", "Q3 planning", "Kitchen renovation", "Mobile app beta", "Data migration",
+    "Conference talk", "Hiring round", "Garden", "Billing v2", "Security audit", "Move house",
+    "Docs overhaul",
+];
+const TAGS: &[&str] = &["work", "personal", "errand", "deep-work", "admin", "health", "finance", "@phone", "@computer"];
+const PEOPLE: &[&str] = &["Ana", "Ben", "Chloe", "Dev", "Eli", "Fatima", "Gus"];
+
+/// A synthetic vault: `projects` projects and `tasks` tasks with a spread of
+/// statuses, priorities, tags, due dates and project links around `today`.
+/// The same `seed` gives the same vault.
+pub fn generate(tasks: usize, projects: usize, seed: u64, today: NaiveDate) -> Vec<TaskItem> {
+    let mut rng = Rng::with_seed(seed);
+    let now = today.and_time(NaiveTime::MIN).and_utc();
+
+    let mut items: Vec<TaskItem> = (0..projects).map(|n| project(&mut rng, n, today, now)).collect();
+    for _ in 0..tasks {
+        let project = (projects > 0 && rng.u8(..100) < 60)
+            .then(|| &items[rng.usize(..projects)])
+            .map(|p| (p.frontmatter.id, p.frontmatter.start_date.unwrap_or(today)));
+        let task = task(&mut rng, project, today, now);
+        items.push(task);
+    }
+    items
+}
+
+fn project(rng: &mut Rng, n: usize, today: NaiveDate, now: DateTime<Utc>) -> TaskItem {
+    let name = PROJECTS[n % PROJECTS.len()];
+    let title = match n / PROJECTS.len() {
+        0 => name.to_string(),
+        round => format!("{} {}", name, round + 1),
+    };
+    let mut project = TaskItem::new_project(title);
+    project.frontmatter.id = uuid(rng);
+    project.frontmatter.code = None;
+    let start = today - Duration::days(rng.i64(0..60));
+    project.frontmatter.start_date = Some(start);
+    project.frontmatter.end_date = Some(today + Duration::days(rng.i64(7..90)));
+    project.frontmatter.created_at = backdate(now, today - start);
+    project.frontmatter.tags = vec![pick(rng, &TAGS[..2]).to_string()];
+    project.frontmatter.priority = priority(rng);
+    project
+}
+
+fn task(rng: &mut Rng, project: Option<(Uuid, NaiveDate)>, today: NaiveDate, now: DateTime<Utc>) -> TaskItem {
+    let mut task = TaskItem::new(format!("{} {}", pick(rng, VERBS), pick(rng, OBJECTS)), ItemType::Task);
+    let fm = &mut task.frontmatter;
+    fm.id = uuid(rng);
+    fm.code = None;
+    fm.priority = priority(rng);
+    fm.parent_goal_id = project.map(|(id, _)| id);
+
+    let age = rng.i64(0..=project.map_or(90, |(_, start)| (today - start).num_days().max(0)));
+    fm.created_at = backdate(now, Duration::days(age));
+
+    let mut tags: Vec<String> = (0..rng.usize(0..3)).map(|_| pick(rng, TAGS).to_string()).collect();
+    tags.sort();
+    tags.dedup();
+    fm.tags = tags;
+
+    if rng.u8(..100) < 45 {
+        let date = today + Duration::days(rng.i64(-20..40));
+        fm.due_date = Some(match rng.u8(..100) < 20 {
+            true => Due { date, time: NaiveTime::from_hms_opt(rng.u32(8..18), 0, 0), offset: None },
+            false => Due::on(date),
+        });
+    }
+
+    // Roughly: a third done, a fifth inbox, the rest spread over the open statuses
+    fm.status = match rng.u8(..100) {
+        0..=19 => Status::Inbox,
+        20..=31 => Status::Active,
+        32..=51 => Status::Next,
+        52..=59 => Status::Waiting,
+        60..=93 => Status::Done,
+        _ => Status::Archived,
+    };
+    match fm.status {
+        Status::Done => fm.completed_at = Some(backdate(now, Duration::days(rng.i64(0..=age)))),
+        Status::Waiting => {
+            fm.delegated_to = Some(pick(rng, PEOPLE).to_string());
+            fm.delegated_at = Some(backdate(now, Duration::days(rng.i64(0..=age.min(14)))));
+        }
+        _ => {}
+    }
+
+    if rng.u8(..100) < 15 {
+        task.body = format!("Context for {}.\n\n- [ ] First step\n- [ ] Follow up\n", task.frontmatter.title.to_lowercase());
+    }
+    task
+}
+
+fn priority(rng: &mut Rng) -> Priority {
+    match rng.u8(..100) {
+        0..=4 => Priority::P0,
+        5..=19 => Priority::P1,
+        20..=69 => Priority::P2,
+        70..=94 => Priority::P3,
+        _ => Priority::P4,
+    }
+}
+
+fn pick<'a>(rng: &mut Rng, items: &[&'a str]) -> &'a str {
+    items[rng.usize(..items.len())]
+}
+
+/// A v4 UUID drawn from `rng`, so seeded vaults get the same ids
+fn uuid(rng: &mut Rng) -> Uuid {
+    Builder::from_random_bytes(rng.u128(..).to_le_bytes()).into_uuid()
+}
+
+/// `ago` before `now`, at a daytime hour that varies with the day
+fn backdate(now: DateTime<Utc>, ago: Duration) -> DateTime<Utc> {
+    now - ago + Duration::minutes((ago.num_days().rem_euclid(10) * 53 + 8 * 60) % (24 * 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 2).unwrap();
+        let items = generate(200, 5, 7, today);
+        assert_eq!(items.len(), 205);
+        assert_eq!(items.iter().filter(|t| t.is_project()).count(), 5);
+
+        // Every project link points at a generated project
+        let linked = items.iter().filter_map(|t| t.frontmatter.parent_goal_id).collect::<Vec<_>>();
+        assert!(!linked.is_empty());
+        assert!(linked.iter().all(|id| items.iter().any(|p| p.is_project() && p.frontmatter.id == *id)));
+
+        // A mix of statuses, and done tasks know when they were finished
+        for status in [Status::Inbox, Status::Next, Status::Waiting, Status::Done] {
+            assert!(items.iter().any(|t| t.frontmatter.status == status), "no {:?} tasks", status);
+        }
+        assert!(items.iter()
+            .filter(|t| t.frontmatter.status == Status::Done)
+            .all(|t| t.frontmatter.completed_at.is_some_and(|at| at >= t.frontmatter.created_at)));
+
+        let ids = |items: &[TaskItem]| items.iter().map(|t| t.frontmatter.id).collect::<Vec<_>>();
+        assert_eq!(ids(&items), ids(&generate(200, 5, 7, today)));
+        assert_ne!(ids(&items), ids(&generate(200, 5, 8, today)));
+    }
+}
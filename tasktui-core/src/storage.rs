@@ -0,0 +1,1266 @@
+use crate::activity;
+use crate::models::{self, Frontmatter, TaskItem, TaskFilter};
+use crate::backup;
+use crate::config::{BackupConfig, GitConfig, Hooks};
+use crate::git::GitSync;
+use crate::hooks::{self, HookEvent};
+use crate::ignore::{self, IgnoreRules};
+use crate::related;
+use crate::search::{self, Hit};
+#[cfg(feature = "search-index")]
+use crate::search_index::SearchIndex;
+use crate::validate;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde_json::{json, Value};
+#[cfg(feature = "search-index")]
+use std::cell::OnceCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::SystemTime;
+use uuid::Uuid;
+
+/// Git's id for the empty tree, to diff a first pull against
+const EMPTY_TREE: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+/// Storage manager for task files
+pub struct Storage {
+    pub data_dir: PathBuf,
+    pub git_sync: Option<GitSync>,
+    /// Record status/priority/date changes in the task body's `## Log` section
+    pub activity_log: bool,
+    /// Refuse to write tasks whose frontmatter fails validation
+    pub strict: bool,
+    /// Commands run after tasks are created or completed and after git pushes
+    pub hooks: Hooks,
+    /// How many automatic snapshots `snapshot` keeps
+    pub backups: BackupConfig,
+    /// Error from the most recent git sync, cleared by the next successful one
+    last_sync_error: RefCell<Option<String>>,
+    /// Git pulls and pushes attempted so far
+    syncs: Cell<u64>,
+    /// Task files changed by pulls, until taken with `take_pulled`
+    pulled: RefCell<Vec<PathBuf>>,
+    /// `.tasktuiignore` as of the last load
+    ignore: RefCell<IgnoreRules>,
+    /// Parsed tasks by path, with the file stamp they were parsed at. Loads only
+    /// reparse files whose stamp changed, so long-lived callers stay warm.
+    cache: RefCell<HashMap<PathBuf, (FileStamp, TaskItem)>>,
+    /// Full-text index, opened on first use; `None` when it couldn't be opened
+    #[cfg(feature = "search-index")]
+    search_index: OnceCell<Option<SearchIndex>>,
+}
+
+/// A file's modification time and size, taken before it's read. A changed stamp
+/// means the cached parse is stale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileStamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl FileStamp {
+    fn of(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        Some(Self { modified: metadata.modified().ok()?, len: metadata.len() })
+    }
+}
+
+/// Tasks parsed by the background loader, with the stamps they were parsed at
+pub type LoadBatch = Vec<(FileStamp, TaskItem)>;
+
+impl Storage {
+    /// Create a new storage manager
+    pub fn new(data_dir: PathBuf) -> Result<Self> {
+        // Create data directory if it doesn't exist
+        if !data_dir.exists() {
+            fs::create_dir_all(&data_dir)
+                .context("Failed to create data directory")?;
+        }
+
+        // Initialize git sync (optional - won't fail if git not available)
+        let git_sync = GitSync::new(data_dir.clone());
+        let git_sync = if git_sync.is_git_repo() {
+            Some(git_sync)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            ignore: RefCell::new(IgnoreRules::load(&data_dir)),
+            data_dir,
+            git_sync,
+            activity_log: true,
+            strict: false,
+            hooks: Hooks::default(),
+            backups: BackupConfig::default(),
+            last_sync_error: RefCell::new(None),
+            syncs: Cell::new(0),
+            pulled: RefCell::new(Vec::new()),
+            cache: RefCell::new(HashMap::new()),
+            #[cfg(feature = "search-index")]
+            search_index: OnceCell::new(),
+        })
+    }
+
+    /// Sign in to the git remote as `config` says
+    pub fn set_git_config(&mut self, config: &GitConfig) {
+        if let Some(git_sync) = &mut self.git_sync {
+            git_sync.config = config.clone();
+        }
+    }
+
+    /// Parse a markdown file with YAML frontmatter
+    /// Resolve a full id, short code or unique id prefix to a task id
+    pub fn resolve_id(&self, text: &str) -> Result<Uuid> {
+        if let Ok(id) = Uuid::parse_str(text.trim()) {
+            return Ok(id);
+        }
+        models::resolve_id(&self.load_all_tasks()?, text).map_err(|e| anyhow::anyhow!(e))
+    }
+
+    pub fn parse_file(&self, path: &Path) -> Result<TaskItem> {
+        parse_task_file(path)
+    }
+
+    /// Serialize a task item to markdown with frontmatter
+    pub fn serialize_task(&self, item: &TaskItem) -> Result<String> {
+        let frontmatter = serde_yaml::to_string(&item.frontmatter)
+            .context("Failed to serialize frontmatter")?;
+
+        Ok(format!(
+            "---\n{}---\n\n{}",
+            frontmatter,
+            item.body
+        ))
+    }
+
+    /// Write a task item to disk.
+    /// Tags are normalized first; with `strict` on, invalid frontmatter is refused.
+    /// With `activity_log` on, changes against the previous version on disk are
+    /// appended to the item's body before writing.
+    pub fn write_task(&self, item: &mut TaskItem) -> Result<PathBuf> {
+        self.check(item)?;
+
+        // Pre-sync: pull if git is available
+        if let Some(git_sync) = &self.git_sync {
+            self.pull(git_sync);
+        }
+
+        let (path, content, events) = self.prepare(item)?;
+        fs::write(&path, content)
+            .context("Failed to write task file")?;
+        self.remember(item, &path);
+        #[cfg(feature = "search-index")]
+        self.reindex([&*item], &[]);
+
+        // Post-sync: commit and push if git is available
+        if let Some(git_sync) = &self.git_sync {
+            let message = format!("Update: {}", item.frontmatter.title);
+            self.push(git_sync, &message);
+        }
+
+        self.run_task_hooks(&events, item, &path);
+        Ok(path)
+    }
+
+    /// Write several task items as one change: every item is checked and
+    /// serialized before any file is touched, a failed write restores the files
+    /// already written, and git gets a single commit with `message`.
+    pub fn write_tasks(&self, items: &mut [TaskItem], message: &str) -> Result<()> {
+        for item in items.iter_mut() {
+            self.check(item)
+                .with_context(|| format!("'{}'", item.frontmatter.title))?;
+        }
+
+        if let Some(git_sync) = &self.git_sync {
+            self.pull(git_sync);
+        }
+
+        let prepared = items.iter_mut()
+            .map(|item| self.prepare(item))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut written: Vec<(&PathBuf, Option<Vec<u8>>)> = Vec::new();
+        for (path, content, _) in &prepared {
+            let previous = fs::read(path).ok();
+            if let Err(e) = fs::write(path, content) {
+                for (path, previous) in written {
+                    let _ = match previous {
+                        Some(previous) => fs::write(path, previous),
+                        None => fs::remove_file(path),
+                    };
+                    self.cache.borrow_mut().remove(path);
+                }
+                return Err(e).with_context(|| format!("Failed to write {}", path.display()));
+            }
+            written.push((path, previous));
+        }
+        for (item, (path, _, _)) in items.iter().zip(&prepared) {
+            self.remember(item, path);
+        }
+        #[cfg(feature = "search-index")]
+        self.reindex(items.iter(), &[]);
+
+        if let Some(git_sync) = &self.git_sync {
+            self.push(git_sync, message);
+        }
+
+        for (item, (path, _, events)) in items.iter().zip(&prepared) {
+            self.run_task_hooks(events, item, path);
+        }
+        Ok(())
+    }
+
+    /// Run the checks and changes a write would make to `item` (normalized tags,
+    /// activity log, `updated_at`) without touching the disk
+    pub fn preview(&self, item: &mut TaskItem) -> Result<()> {
+        self.check(item)?;
+        self.prepare(item).map(|_| ())
+    }
+
+    /// Normalize tags, give older items a short code and, in strict mode, refuse
+    /// invalid frontmatter, including custom values the project's fields reject
+    fn check(&self, item: &mut TaskItem) -> Result<()> {
+        validate::normalize_tags(&mut item.frontmatter.tags);
+        if item.frontmatter.code.is_none() {
+            item.frontmatter.code = Some(item.code());
+        }
+        if self.strict {
+            let mut warnings = validate::validate(&item.frontmatter);
+            let project = item.frontmatter.parent_goal_id
+                .and_then(|id| self.read_current(&self.data_dir.join(format!("{}.md", id))));
+            if let Some(project) = project {
+                warnings.extend(validate::custom_fields(&item.frontmatter, &project.frontmatter.fields));
+            }
+            if !warnings.is_empty() {
+                let list = warnings.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ");
+                anyhow::bail!("Invalid task: {}", list);
+            }
+        }
+        Ok(())
+    }
+
+    /// The item's path and file content, with the activity log applied and
+    /// `updated_at` stamped if anything changed, and the hook events the write
+    /// fires. Fails if the file on disk was updated after this copy of the item
+    /// was loaded.
+    fn prepare(&self, item: &mut TaskItem) -> Result<(PathBuf, String, Vec<HookEvent>)> {
+        let filename = format!("{}.md", item.frontmatter.id);
+        let path = self.data_dir.join(&filename);
+
+        let Some(previous) = self.read_current(&path) else {
+            item.frontmatter.updated_at = Some(Utc::now());
+            let content = self.serialize_task(item)?;
+            return Ok((path, content, hooks::events(None, item)));
+        };
+
+        if let Some(updated) = previous.frontmatter.updated_at.filter(|at| Some(*at) > item.frontmatter.updated_at) {
+            anyhow::bail!(
+                "'{}' was changed elsewhere at {} after it was loaded; reload and try again",
+                item.frontmatter.title,
+                updated.format("%Y-%m-%d %H:%M:%S")
+            );
+        }
+
+        if self.activity_log {
+            let entries = activity::changes(&previous.frontmatter, &item.frontmatter);
+            item.body = activity::append_log(&item.body, &entries, Utc::now());
+        }
+
+        // Compare with the stored stamp so only real changes count; an explicit
+        // newer stamp (a reviewed task) is kept as is
+        let stamped = item.frontmatter.updated_at;
+        item.frontmatter.updated_at = previous.frontmatter.updated_at;
+        let changed = self.serialize_task(&previous)? != self.serialize_task(item)?;
+        item.frontmatter.updated_at = if changed { Some(Utc::now()) } else { stamped };
+
+        let content = self.serialize_task(item)?;
+        let content = match fs::read_to_string(&path) {
+            Ok(on_disk) => keep_formatting(&on_disk, &content),
+            Err(_) => content,
+        };
+        Ok((path, content, hooks::events(Some(&previous), item)))
+    }
+
+    /// The version of a task on disk, from the cache while the file is unchanged
+    fn read_current(&self, path: &Path) -> Option<TaskItem> {
+        let stamp = FileStamp::of(path)?;
+        match self.cache.borrow().get(path) {
+            Some((cached, task)) if *cached == stamp => Some(task.clone()),
+            _ => self.parse_file(path).ok(),
+        }
+    }
+
+    /// Cache a freshly written item under its new stamp
+    fn remember(&self, item: &TaskItem, path: &Path) {
+        if let Some(stamp) = FileStamp::of(path) {
+            let mut written = item.clone();
+            written.file_path = path.to_path_buf();
+            self.cache.borrow_mut().insert(path.to_path_buf(), (stamp, written));
+        }
+    }
+
+    /// Load all tasks from the data directory, reparsing only files that were
+    /// added or changed since the last load
+    pub fn load_all_tasks(&self) -> Result<Vec<TaskItem>> {
+        self.load_matching(|_| true)
+    }
+
+    /// Like `load_all_tasks`, but only copies out the tasks `keep` accepts
+    fn load_matching(&self, keep: impl Fn(&TaskItem) -> bool) -> Result<Vec<TaskItem>> {
+        if !self.data_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        *self.ignore.borrow_mut() = IgnoreRules::load(&self.data_dir);
+        let mut cache = self.cache.borrow_mut();
+        let mut fresh = HashMap::new();
+        let mut tasks = Vec::new();
+        let mut reparsed = Vec::new();
+
+        for path in task_files(&self.data_dir)? {
+            let Some(stamp) = FileStamp::of(&path) else { continue };
+            let task = match cache.remove(&path) {
+                Some((cached, task)) if cached == stamp => task,
+                _ => match parse_or_warn(&path) {
+                    Some(task) => {
+                        reparsed.push(path.clone());
+                        task
+                    }
+                    None => continue,
+                },
+            };
+            if keep(&task) {
+                tasks.push(task.clone());
+            }
+            fresh.insert(path, (stamp, task));
+        }
+
+        // Whatever is left in the old cache was removed from disk
+        #[cfg(feature = "search-index")]
+        if let Some(index) = self.search_index() {
+            let result = if index.is_synced() {
+                let changed = reparsed.iter().filter_map(|path| fresh.get(path)).map(|(_, task)| task);
+                let ids: std::collections::HashSet<Uuid> = fresh.values().map(|(_, t)| t.frontmatter.id).collect();
+                let removed: Vec<Uuid> = cache.values()
+                    .map(|(_, t)| t.frontmatter.id)
+                    .filter(|id| !ids.contains(id))
+                    .collect();
+                index.update(changed, &removed)
+            } else {
+                index.sync(fresh.values().map(|(_, task)| task))
+            };
+            if let Err(err) = result {
+                tracing::warn!(error = ?err, "Failed to update the search index");
+            }
+        }
+        *cache = fresh;
+        Ok(tasks)
+    }
+
+    /// The vault's search index, opened (and kept out of git) on first use
+    #[cfg(feature = "search-index")]
+    fn search_index(&self) -> Option<&SearchIndex> {
+        self.search_index
+            .get_or_init(|| {
+                if let Some(git_sync) = &self.git_sync {
+                    if let Err(err) = git_sync.exclude(&format!("/{}/", SearchIndex::DIR_NAME)) {
+                        tracing::warn!(error = ?err, "Failed to keep the search index out of git");
+                    }
+                }
+                SearchIndex::open(&self.data_dir)
+                    .map_err(|err| tracing::warn!(error = ?err, "Failed to open the search index"))
+                    .ok()
+            })
+            .as_ref()
+    }
+
+    /// Index written tasks and drop deleted ones. A failed update is only
+    /// logged: the next load catches the index up.
+    #[cfg(feature = "search-index")]
+    fn reindex<'a>(&self, changed: impl IntoIterator<Item = &'a TaskItem>, removed: &[Uuid]) {
+        if let Some(index) = self.search_index() {
+            if let Err(err) = index.update(changed, removed) {
+                tracing::warn!(error = ?err, "Failed to update the search index");
+            }
+        }
+    }
+
+    /// Bring the search index in line with `tasks`, the whole vault, after a
+    /// load that went around `load_all_tasks` (the background loader)
+    #[cfg(feature = "search-index")]
+    pub fn sync_search_index(&self, tasks: &[TaskItem]) {
+        if let Some(index) = self.search_index() {
+            if let Err(err) = index.sync(tasks) {
+                tracing::warn!(error = ?err, "Failed to update the search index");
+            }
+        }
+    }
+
+    /// Tasks of `tasks` matching `query`, best first: from the search index
+    /// with the `search-index` feature, otherwise by scanning them
+    pub fn search<'a>(&self, tasks: &'a [TaskItem], query: &str, limit: usize) -> Vec<Hit<'a>> {
+        #[cfg(feature = "search-index")]
+        if let Some(index) = self.search_index() {
+            match index.search(tasks, query, limit) {
+                Ok(hits) => return hits,
+                Err(err) => tracing::warn!(error = ?err, "Search index failed, scanning tasks instead"),
+            }
+        }
+        search::search(tasks, query, limit)
+    }
+
+    /// Open tasks of `tasks` most like `task`, from the search index with the
+    /// `search-index` feature, otherwise by comparing their words
+    pub fn related<'a>(&self, tasks: &'a [TaskItem], task: &TaskItem, limit: usize) -> Vec<&'a TaskItem> {
+        #[cfg(feature = "search-index")]
+        if let Some(index) = self.search_index() {
+            match index.related(tasks, task, limit) {
+                Ok(related) => return related,
+                Err(err) => tracing::warn!(error = ?err, "Search index failed, comparing tasks instead"),
+            }
+        }
+        related::related(tasks, task, limit)
+    }
+
+    /// Task files that don't parse or fail validation, with what's wrong with each
+    pub fn diagnose(&self) -> Result<Vec<(PathBuf, Vec<String>)>> {
+        let mut problems = Vec::new();
+        for path in task_files(&self.data_dir)? {
+            let found = match parse_task_file(&path) {
+                Ok(task) => validate::validate(&task.frontmatter).iter().map(ToString::to_string).collect(),
+                Err(e) => vec![format!("{:#}", e)],
+            };
+            if !found.is_empty() {
+                problems.push((path, found));
+            }
+        }
+        problems.sort();
+        Ok(problems)
+    }
+
+    /// Take tasks from the background loader, remembering their stamps so the
+    /// next load doesn't parse them again
+    pub fn adopt(&self, batch: LoadBatch) -> Vec<TaskItem> {
+        let mut cache = self.cache.borrow_mut();
+        batch
+            .into_iter()
+            .map(|(stamp, task)| {
+                cache.insert(task.file_path.clone(), (stamp, task.clone()));
+                task
+            })
+            .collect()
+    }
+
+    /// List tasks with filtering
+    pub fn list_tasks(&self, filter: &TaskFilter) -> Result<Vec<TaskItem>> {
+        let today = models::today();
+        let mut tasks = self.load_matching(|task| filter.matches(task, today))?;
+
+        // Sort by priority (P0 first) then by created date
+        tasks.sort_by(|a, b| {
+            a.frontmatter.priority.rank().cmp(&b.frontmatter.priority.rank())
+                .then_with(|| b.frontmatter.created_at.cmp(&a.frontmatter.created_at))
+        });
+
+        // Apply limit
+        if let Some(limit) = filter.limit {
+            tasks.truncate(limit);
+        }
+
+        Ok(tasks)
+    }
+
+    /// Snapshot the data directory into `.backups` before a batch change
+    /// described by `reason` (`import`, `tag-rename`), unless `backups.keep`
+    /// turns snapshots off. A failed snapshot should stop the change.
+    pub fn snapshot(&self, reason: &str) -> Result<()> {
+        if self.backups.keep == 0 {
+            return Ok(());
+        }
+        backup::snapshot(&self.data_dir, reason).context("Failed to back up before the change; nothing was changed")?;
+        backup::prune(&self.data_dir, self.backups.keep)
+    }
+
+    /// Put the data directory back as it was in the backup at `path` and
+    /// commit the result. The current state is backed up first, as an
+    /// automatic snapshot or, with those turned off, a backup that's kept;
+    /// its path is returned.
+    pub fn restore_backup(&self, path: &Path, passphrase: Option<&backup::SecretString>) -> Result<PathBuf> {
+        let safety = match self.backups.keep {
+            0 => backup::create(&self.data_dir, &backup::default_dir(&self.data_dir), None),
+            _ => backup::snapshot(&self.data_dir, "restore"),
+        }
+        .context("Failed to back up the current state; nothing was restored")?;
+        backup::restore(&self.data_dir, path, passphrase)?;
+        // Only now, so the backup restored from can't be the one pruned
+        if self.backups.keep > 0 {
+            backup::prune(&self.data_dir, self.backups.keep)?;
+        }
+        self.cache.borrow_mut().clear();
+        *self.ignore.borrow_mut() = IgnoreRules::load(&self.data_dir);
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        self.commit(&format!("Restore backup {}", name));
+        Ok(safety)
+    }
+
+    /// Commit and push files written outside `write_task`, such as reviews
+    pub fn commit(&self, message: &str) {
+        if let Some(git_sync) = &self.git_sync {
+            self.push(git_sync, message);
+        }
+    }
+
+    /// Run the hooks configured for `event` with `payload` on their stdin
+    pub fn run_hooks(&self, event: HookEvent, payload: &Value) {
+        hooks::run(&self.hooks, event, &self.data_dir, payload);
+    }
+
+    fn run_task_hooks(&self, events: &[HookEvent], item: &TaskItem, path: &Path) {
+        if events.iter().all(|event| self.hooks.commands(*event).is_empty()) {
+            return;
+        }
+        let mut payload = hooks::task_payload(item);
+        payload["path"] = json!(path);
+        for event in events {
+            self.run_hooks(*event, &payload);
+        }
+    }
+
+    /// Pull, noting the task files the pull brought changes to
+    fn pull(&self, git_sync: &GitSync) {
+        // Before the first commit everything pulled is new
+        let before = git_sync.head().unwrap_or_else(|| EMPTY_TREE.to_string());
+        self.record_sync(git_sync.pull());
+        let Some(after) = git_sync.head() else {
+            return;
+        };
+        if before == after {
+            return;
+        }
+        match git_sync.changed_files(&before, &after) {
+            Ok(paths) => {
+                let mut pulled = self.pulled.borrow_mut();
+                for path in paths.into_iter().filter(|p| p.extension().is_some_and(|e| e == "md")) {
+                    if !pulled.contains(&path) {
+                        pulled.push(path);
+                    }
+                }
+            }
+            Err(e) => tracing::warn!(error = %e, "Failed to list pulled changes"),
+        }
+    }
+
+    /// Task files changed by pulls since the last call, e.g. to compare with
+    /// copies loaded before them
+    pub fn take_pulled(&self) -> Vec<PathBuf> {
+        self.pulled.take()
+    }
+
+    /// Commit and push, running the `on_sync` hooks once the push went through
+    fn push(&self, git_sync: &GitSync, message: &str) {
+        let result = git_sync.commit_and_push(message);
+        if result.is_ok() {
+            self.run_hooks(HookEvent::Sync, &json!({ "target": "git", "message": message }));
+        }
+        self.record_sync(result);
+    }
+
+    /// Whether `.tasktuiignore` keeps the task's file on this machine, out of
+    /// git commits and pushes
+    pub fn is_local_only(&self, item: &TaskItem) -> bool {
+        item.file_path.strip_prefix(&self.data_dir).is_ok_and(|path| self.ignore.borrow().is_ignored(path))
+    }
+
+    /// Keep the task local or let it sync again, committing straight away.
+    /// A task committed before stays on the remote as it was, and only its
+    /// later changes stay here; returns whether that's the case.
+    pub fn set_local_only(&self, item: &TaskItem, local_only: bool) -> Result<bool> {
+        let path = item.file_path.strip_prefix(&self.data_dir)
+            .context("Task file is outside the data directory")?;
+        ignore::set_ignored(&self.data_dir, path, local_only)?;
+        *self.ignore.borrow_mut() = IgnoreRules::load(&self.data_dir);
+
+        let mut committed = false;
+        if let Some(git_sync) = &self.git_sync {
+            committed = local_only && git_sync.is_committed(path);
+            let verb = if local_only { "Keep local" } else { "Sync" };
+            self.push(git_sync, &format!("{}: {}", verb, item.frontmatter.title));
+        }
+        Ok(committed)
+    }
+
+    /// Delete a task file
+    pub fn delete_task(&self, item: &TaskItem) -> Result<()> {
+        fs::remove_file(&item.file_path)
+            .context("Failed to delete task file")?;
+        self.cache.borrow_mut().remove(&item.file_path);
+        #[cfg(feature = "search-index")]
+        self.reindex([], &[item.frontmatter.id]);
+
+        if let Some(git_sync) = &self.git_sync {
+            let message = format!("Delete: {}", item.frontmatter.title);
+            self.push(git_sync, &message);
+        }
+
+        Ok(())
+    }
+
+    /// Remember the outcome of a git operation. Sync failures never fail the write itself;
+    /// changes stay saved locally and the error is surfaced through `last_sync_error`.
+    fn record_sync(&self, result: Result<()>) {
+        self.syncs.set(self.syncs.get() + 1);
+        if let Err(e) = &result {
+            tracing::warn!(error = %e, "Git sync failed");
+        }
+        *self.last_sync_error.borrow_mut() = result.err().map(|e| e.to_string().trim().to_string());
+    }
+
+    /// Git pulls and pushes attempted so far; a change means the branch may
+    /// have moved against its upstream
+    pub fn sync_count(&self) -> u64 {
+        self.syncs.get()
+    }
+
+    /// Error from the most recent git sync, if it failed
+    pub fn last_sync_error(&self) -> Option<String> {
+        self.last_sync_error.borrow().clone()
+    }
+}
+
+/// Parse a markdown file with YAML frontmatter
+fn parse_task_file(path: &Path) -> Result<TaskItem> {
+    let content = fs::read_to_string(path)
+        .context("Failed to read file")?;
+    parse_task(&content, path)
+}
+
+/// Parse task file contents: YAML frontmatter between `---` lines, then the body
+pub fn parse_task(content: &str, path: &Path) -> Result<TaskItem> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content).replace("\r\n", "\n");
+    let Some((frontmatter, body)) = split_frontmatter(&content) else {
+        anyhow::bail!("Invalid file format: missing frontmatter delimiters");
+    };
+
+    let frontmatter: Frontmatter = serde_yaml::from_str(frontmatter)
+        .context("Failed to parse frontmatter")?;
+
+    Ok(TaskItem {
+        frontmatter,
+        // Blank lines around the body go, but not the indent of a leading code block
+        body: body.trim_start_matches('\n').trim_end().to_string(),
+        file_path: path.to_path_buf(),
+    })
+}
+
+/// Split `---` delimited frontmatter from the body. Delimiters only count as a whole
+/// line, so `---` rules in the body or inside values are left alone.
+fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    let is_delimiter = |line: &str| line.trim_end() == "---";
+
+    let first_end = content.find('\n')?;
+    if !is_delimiter(&content[..first_end]) {
+        return None;
+    }
+
+    let rest = &content[first_end + 1..];
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if is_delimiter(line) {
+            return Some((&rest[..offset], &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// `updated` (a serialized task) laid out like the file it replaces, so a
+/// hand-edited file's diff shows only what changed: fields whose value is
+/// unchanged keep their original lines (order, quoting, comments), changed ones
+/// are rewritten in place, and new ones go at the end. Falls back to `updated`
+/// when the result wouldn't read back as the same task.
+fn keep_formatting(on_disk: &str, updated: &str) -> String {
+    let on_disk = on_disk.strip_prefix('\u{feff}').unwrap_or(on_disk).replace("\r\n", "\n");
+    let (Some((old, _)), Some((new, body))) = (split_frontmatter(&on_disk), split_frontmatter(updated)) else {
+        return updated.to_string();
+    };
+    let new_blocks = frontmatter_blocks(new);
+    let mut old_blocks = frontmatter_blocks(old);
+    if old_blocks.is_empty() || old == new {
+        return updated.to_string();
+    }
+    // Comments after the last field stay with it
+    if let Some((None, trailing)) = old_blocks.last().cloned() {
+        old_blocks.pop();
+        match old_blocks.last_mut() {
+            Some((_, text)) => text.push_str(&trailing),
+            None => return updated.to_string(),
+        }
+    }
+
+    let value = |text: &str| serde_yaml::from_str::<serde_yaml::Value>(text).ok();
+    let mut merged = String::new();
+    for (key, text) in &old_blocks {
+        let Some((_, new_text)) = new_blocks.iter().find(|(k, _)| k == key) else {
+            continue;
+        };
+        let (comments, field) = split_comments(text);
+        if value(field).is_some() && value(field) == value(new_text) {
+            merged.push_str(text);
+        } else {
+            merged.push_str(comments);
+            merged.push_str(new_text);
+        }
+        if !merged.ends_with('\n') {
+            merged.push('\n');
+        }
+    }
+    for (key, text) in &new_blocks {
+        if !old_blocks.iter().any(|(k, _)| k == key) {
+            merged.push_str(text);
+        }
+    }
+
+    let same_task = |a: &str, b: &str| {
+        match (serde_yaml::from_str::<Frontmatter>(a), serde_yaml::from_str::<Frontmatter>(b)) {
+            (Ok(a), Ok(b)) => serde_yaml::to_string(&a).ok() == serde_yaml::to_string(&b).ok(),
+            _ => false,
+        }
+    };
+    if !same_task(&merged, new) {
+        return updated.to_string();
+    }
+    format!("---\n{}---\n{}", merged, body)
+}
+
+/// Frontmatter split into top-level fields, each with its key and its text:
+/// comments and blank lines before it, its own line and any nested lines.
+/// A `None` key holds comments after the last field.
+fn frontmatter_blocks(frontmatter: &str) -> Vec<(Option<String>, String)> {
+    let mut blocks: Vec<(Option<String>, String)> = Vec::new();
+    let mut pending = String::new();
+    for line in frontmatter.split_inclusive('\n') {
+        let starts_field = !line.starts_with([' ', '\t', '#', '-', '\n']) && line.contains(':');
+        if starts_field {
+            let key = line.split(':').next().unwrap_or_default().trim().to_string();
+            blocks.push((Some(key), std::mem::take(&mut pending) + line));
+        } else if line.trim().is_empty() || line.trim_start().starts_with('#') || blocks.is_empty() {
+            pending.push_str(line);
+        } else if let Some((_, text)) = blocks.last_mut() {
+            // A nested line: anything comment-like before it belongs to the field too
+            text.push_str(&std::mem::take(&mut pending));
+            text.push_str(line);
+        }
+    }
+    if !pending.is_empty() {
+        blocks.push((None, pending));
+    }
+    blocks
+}
+
+/// A block's leading comments and blank lines, and the field itself
+fn split_comments(block: &str) -> (&str, &str) {
+    let mut offset = 0;
+    for line in block.split_inclusive('\n') {
+        if !(line.trim().is_empty() || line.trim_start().starts_with('#')) {
+            break;
+        }
+        offset += line.len();
+    }
+    block.split_at(offset)
+}
+
+/// Parse a task file, logging and skipping files that aren't valid tasks
+fn parse_or_warn(path: &Path) -> Option<TaskItem> {
+    match parse_task_file(path) {
+        Ok(task) => Some(task),
+        Err(e) => {
+            tracing::warn!(path = %path.display(), error = %e, "Failed to parse task file");
+            None
+        }
+    }
+}
+
+/// Markdown files in the data directory
+fn task_files(data_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(data_dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|s| s.to_str()) == Some("md") {
+            paths.push(path);
+        }
+    }
+    // Directory order varies by filesystem; keep loads (and ties in sorted views) stable
+    paths.sort();
+    Ok(paths)
+}
+
+/// Load tasks on a background thread, sending them in batches of `batch_size` as
+/// they're parsed. The channel closes once every file has been read.
+pub fn load_in_background(data_dir: PathBuf, batch_size: usize) -> Receiver<Result<LoadBatch>> {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let paths = match task_files(&data_dir) {
+            Ok(paths) => paths,
+            Err(e) => {
+                let _ = sender.send(Err(e.context("Failed to read data directory")));
+                return;
+            }
+        };
+
+        for chunk in paths.chunks(batch_size.max(1)) {
+            let batch = chunk
+                .iter()
+                .filter_map(|path| Some((FileStamp::of(path)?, parse_or_warn(path)?)))
+                .collect();
+            // The app stopped listening (e.g. a manual reload replaced this load)
+            if sender.send(Ok(batch)).is_err() {
+                return;
+            }
+        }
+    });
+
+    receiver
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Due, ItemType, Priority, Status};
+    use proptest::prelude::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_and_write() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+
+        let mut task = TaskItem::new("Test Task".to_string(), ItemType::Task);
+        task.body = "This is a test task.".to_string();
+        task.frontmatter.priority = Priority::P1;
+        task.frontmatter.tags = vec!["test".to_string(), "work".to_string()];
+
+        let path = storage.write_task(&mut task).unwrap();
+        let loaded = storage.parse_file(&path).unwrap();
+
+        assert_eq!(loaded.frontmatter.title, "Test Task");
+        assert_eq!(loaded.body, "This is a test task.");
+        assert_eq!(loaded.frontmatter.priority, Priority::P1);
+    }
+
+    #[test]
+    fn test_activity_log_on_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+
+        let mut task = TaskItem::new("Logged".to_string(), ItemType::Task);
+        task.body = "Notes".to_string();
+        let path = storage.write_task(&mut task).unwrap();
+        assert_eq!(storage.parse_file(&path).unwrap().body, "Notes");
+
+        task.set_status(Status::Done);
+        storage.write_task(&mut task).unwrap();
+        let loaded = storage.parse_file(&path).unwrap();
+        assert!(loaded.body.starts_with("Notes\n\n## Log\n\n- "));
+        assert!(loaded.body.ends_with("status: active → done"));
+        assert_eq!(loaded.body, task.body);
+
+        // Rewriting without changes adds nothing
+        storage.write_task(&mut task).unwrap();
+        assert_eq!(storage.parse_file(&path).unwrap().body, task.body);
+    }
+
+    #[test]
+    fn test_updated_at_and_conflicts() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+
+        let mut task = TaskItem::new("Touched".to_string(), ItemType::Task);
+        let path = storage.write_task(&mut task).unwrap();
+        let created = task.frontmatter.updated_at.unwrap();
+
+        // Rewriting without changes keeps the stamp
+        storage.write_task(&mut task).unwrap();
+        assert_eq!(task.frontmatter.updated_at, Some(created));
+
+        let mut stale_copy = task.clone();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        task.frontmatter.priority = Priority::P1;
+        storage.write_task(&mut task).unwrap();
+        assert!(task.frontmatter.updated_at.unwrap() > created);
+        assert_eq!(storage.parse_file(&path).unwrap().frontmatter.updated_at, task.frontmatter.updated_at);
+
+        // A copy loaded before that write can't overwrite it
+        stale_copy.body = "Old edit".to_string();
+        let error = storage.write_task(&mut stale_copy).unwrap_err();
+        assert!(error.to_string().contains("was changed elsewhere"));
+        assert_eq!(storage.parse_file(&path).unwrap().frontmatter.priority, Priority::P1);
+    }
+
+    #[test]
+    fn test_delete_task() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+
+        let mut task = TaskItem::new("Trash me".to_string(), ItemType::Task);
+        task.frontmatter.status = Status::Inbox;
+        task.file_path = storage.write_task(&mut task).unwrap();
+
+        let loaded = storage.load_all_tasks().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].frontmatter.status, Status::Inbox);
+
+        storage.delete_task(&task).unwrap();
+        assert!(storage.load_all_tasks().unwrap().is_empty());
+    }
+
+    /// Writes, deletes and edits made outside tasktui all reach the index
+    #[cfg(feature = "search-index")]
+    #[test]
+    fn test_search_index_follows_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut renew = TaskItem::new("Renew passport".to_string(), ItemType::Task);
+        let mut milk = TaskItem::new("Buy milk".to_string(), ItemType::Task);
+        storage.write_tasks(std::slice::from_mut(&mut renew), "Add").unwrap();
+        milk.file_path = storage.write_task(&mut milk).unwrap();
+
+        // Searching hands the index tasks it may not have: only indexed matches come back
+        let everything = vec![renew.clone(), milk.clone()];
+        let titles = |query: &str| -> Vec<String> {
+            storage.search(&everything, query, 10).iter().map(|h| h.task.frontmatter.title.clone()).collect()
+        };
+        assert_eq!(titles("passport"), ["Renew passport"]);
+        assert_eq!(titles("milk"), ["Buy milk"]);
+
+        storage.delete_task(&milk).unwrap();
+        assert!(titles("milk").is_empty());
+
+        // A hand edit is picked up by the next load
+        let path = temp_dir.path().join(format!("{}.md", renew.frontmatter.id));
+        let edited = fs::read_to_string(&path).unwrap().replace("Renew passport", "Renew driving licence");
+        fs::write(&path, edited).unwrap();
+        let tasks = storage.load_all_tasks().unwrap();
+        assert_eq!(storage.search(&tasks, "licence", 10).len(), 1);
+        assert!(storage.search(&tasks, "passport", 10).is_empty());
+    }
+
+    #[test]
+    fn test_load_in_background() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+        for idx in 0..5 {
+            storage.write_task(&mut TaskItem::new(format!("Task {}", idx), ItemType::Task)).unwrap();
+        }
+        fs::write(temp_dir.path().join("broken.md"), "no frontmatter").unwrap();
+
+        let batches: Vec<_> = load_in_background(temp_dir.path().to_path_buf(), 2)
+            .iter()
+            .map(|batch| batch.unwrap())
+            .collect();
+        assert_eq!(batches.len(), 3);
+        assert_eq!(batches.iter().map(Vec::len).sum::<usize>(), 5);
+    }
+
+    #[test]
+    fn test_load_reparses_only_changed_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+        let mut kept = TaskItem::new("Kept".to_string(), ItemType::Task);
+        let mut removed = TaskItem::new("Removed".to_string(), ItemType::Task);
+        let kept_path = storage.write_task(&mut kept).unwrap();
+        let removed_path = storage.write_task(&mut removed).unwrap();
+        assert_eq!(storage.load_all_tasks().unwrap().len(), 2);
+
+        // Same size and mtime: the cached parse is used, so garbage isn't noticed
+        let modified = fs::metadata(&kept_path).unwrap().modified().unwrap();
+        let len = fs::metadata(&kept_path).unwrap().len() as usize;
+        fs::write(&kept_path, "x".repeat(len)).unwrap();
+        fs::File::options().write(true).open(&kept_path).unwrap().set_modified(modified).unwrap();
+
+        // Changes from other writers are picked up
+        fs::remove_file(&removed_path).unwrap();
+        let other = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+        other.write_task(&mut TaskItem::new("Added".to_string(), ItemType::Task)).unwrap();
+
+        let mut titles: Vec<_> = storage.load_all_tasks().unwrap()
+            .into_iter()
+            .map(|t| t.frontmatter.title)
+            .collect();
+        titles.sort();
+        assert_eq!(titles, ["Added", "Kept"]);
+    }
+
+    #[test]
+    fn test_frontmatter_edge_cases() {
+        let path = Path::new("task.md");
+        let storage = Storage::new(TempDir::new().unwrap().path().to_path_buf()).unwrap();
+        let task = TaskItem::new("Rules".to_string(), ItemType::Task);
+        let file = storage.serialize_task(&task).unwrap();
+
+        // Horizontal rules in the body stay in the body
+        let with_rule = format!("{}Above\n\n---\n\nBelow", file);
+        assert_eq!(parse_task(&with_rule, path).unwrap().body, "Above\n\n---\n\nBelow");
+
+        // CRLF line endings and a byte order mark
+        let windows = format!("\u{feff}{}", with_rule.replace('\n', "\r\n"));
+        let parsed = parse_task(&windows, path).unwrap();
+        assert_eq!(parsed.frontmatter.title, "Rules");
+        assert_eq!(parsed.body, "Above\n\n---\n\nBelow");
+
+        // `---` inside a value isn't a delimiter
+        let dashes = file.replace("title: Rules", "title: a---b");
+        assert_eq!(parse_task(&dashes, path).unwrap().frontmatter.title, "a---b");
+
+        // A body opening with an indented code block keeps its indent
+        let code = format!("{}    let x = 1;\n    x + 1\n", file);
+        assert_eq!(parse_task(&code, path).unwrap().body, "    let x = 1;\n    x + 1");
+
+        assert!(parse_task("title: no delimiters\n", path).is_err());
+        assert!(parse_task("---\ntitle: unterminated\n", path).is_err());
+    }
+
+    #[test]
+    fn test_write_keeps_hand_edited_formatting() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+        storage.activity_log = false;
+        let id = Uuid::new_v4();
+        let path = temp_dir.path().join(format!("{}.md", id));
+        let original = format!(
+            "---\n\
+             title: 'Renew passport'\n\
+             # Added on the phone\n\
+             status: next\n\
+             tags: [admin, travel]\n\
+             id: {}\n\
+             type: task\n\
+             priority: p2\n\
+             created_at: 2025-01-02T03:04:05Z\n\
+             updated_at: 2025-01-02T03:04:05Z\n\
+             ---\n\nBring photos.\n",
+            id
+        );
+        fs::write(&path, &original).unwrap();
+
+        let mut task = storage.parse_file(&path).unwrap();
+        task.set_status(Status::Active);
+        storage.write_task(&mut task).unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+        let removed: Vec<_> = original.lines().filter(|line| !written.lines().any(|l| l == *line)).collect();
+        let added: Vec<_> = written.lines().filter(|line| !original.lines().any(|l| l == *line)).collect();
+        assert_eq!(removed, ["status: next", "updated_at: 2025-01-02T03:04:05Z"]);
+        assert_eq!(added.len(), 3, "{}", written);
+        assert_eq!(added[0], "status: active");
+        // The short code is assigned on the first write
+        assert!(added[1].starts_with("updated_at: ") && added[2].starts_with("code: "));
+        assert!(written.starts_with("---\ntitle: 'Renew passport'\n# Added on the phone\nstatus: active\ntags: [admin, travel]\n"));
+        assert!(written.ends_with("---\n\nBring photos."));
+        assert_eq!(storage.parse_file(&path).unwrap().frontmatter.status, Status::Active);
+
+        // A new field goes at the end of the frontmatter; a removed one goes away
+        task.frontmatter.due_date = Due::parse("2025-06-01");
+        task.frontmatter.tags.clear();
+        storage.write_task(&mut task).unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+        assert!(!written.contains("tags: [admin"), "{}", written);
+        assert!(written.contains("due_date: 2025-06-01\n---"), "{}", written);
+        assert!(written.starts_with("---\ntitle: 'Renew passport'\n"));
+    }
+
+    /// Pieces YAML or the `---` fence read specially
+    const FRAGMENTS: &[&str] = &[
+        "---", "\n---\n", "\n", " ", "#", " #", ": ", "- ", "'", "\"", "|", ">", "[[link]]",
+        "{x}", "\t", "null", "true", "42", "2024-01-01", "\\", "%", "&a", "*b", "...", "\n...\n",
+        "%YAML 1.2", "\u{feff}", "\u{7}", "\u{85}", "\u{2028}", "\u{a0}", "~", "!tag", "? ", ",",
+        "`", "@", "<<", "0x1F", ".inf", "-", "=",
+    ];
+
+    /// Up to `max` pieces: YAML-special fragments, any unicode, and plain words
+    fn awkward_text(max: usize) -> impl Strategy<Value = String> {
+        let piece = prop_oneof![
+            3 => prop::sample::select(FRAGMENTS).prop_map(str::to_string),
+            2 => any::<char>().prop_map(String::from),
+            1 => prop::sample::select(&["word", "é", "🚀", "日本"][..]).prop_map(str::to_string),
+        ];
+        prop::collection::vec(piece, 0..=max).prop_map(|pieces| pieces.concat())
+    }
+
+    /// Titles and tags that start like YAML syntax: `-`, quotes, `#`, `:`
+    fn yaml_special(max: usize) -> impl Strategy<Value = String> {
+        (prop::sample::select(&["-", "- ", "'", "\"", "#", ":", ": ", "---", "? ", "&", "*", "!"][..]), awkward_text(max))
+            .prop_map(|(lead, rest)| format!("{}{}", lead, rest))
+    }
+
+    fn title() -> impl Strategy<Value = String> {
+        prop_oneof![awkward_text(6), yaml_special(4)]
+    }
+
+    fn tags() -> impl Strategy<Value = Vec<String>> {
+        prop::collection::vec(prop_oneof![awkward_text(3), yaml_special(2)], 0..3)
+    }
+
+    /// Bodies as they come back from a parse: LF line endings, no blank lines around them
+    fn body() -> impl Strategy<Value = String> {
+        awkward_text(12).prop_map(|body| body.replace('\r', "").trim_start_matches('\n').trim_end().to_string())
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(2000))]
+
+        /// Generated titles, tags and bodies survive serialize and parse, with
+        /// LF or CRLF line endings
+        #[test]
+        fn test_round_trip_property(title in title(), tags in tags(), body in body(), crlf in any::<bool>()) {
+            let temp_dir = TempDir::new().unwrap();
+            let storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+            let mut task = TaskItem::new(title, ItemType::Task);
+            task.frontmatter.tags = tags;
+            task.body = body;
+
+            let mut file = storage.serialize_task(&task).unwrap();
+            if crlf {
+                file = file.replace('\n', "\r\n");
+            }
+            let parsed = parse_task(&file, Path::new("task.md"))
+                .map_err(|e| TestCaseError::fail(format!("{:#}\n{}", e, file)))?;
+            prop_assert_eq!(parsed.frontmatter.title, task.frontmatter.title);
+            prop_assert_eq!(parsed.frontmatter.tags, task.frontmatter.tags);
+            prop_assert_eq!(parsed.body, task.body);
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(500))]
+
+        /// Through the disk, with every optional text field set: the whole frontmatter
+        /// survives, after the normalization a write applies
+        #[test]
+        fn test_write_round_trip_property(
+            title in title(),
+            tags in tags(),
+            delegated_to in awkward_text(4),
+            jira_key in yaml_special(2),
+            due in prop::sample::select(&["2025-03-02", "2025-03-02T09:30", "2025-03-02T09:30+05:45"][..]),
+            body in body(),
+        ) {
+            let temp_dir = TempDir::new().unwrap();
+            let mut storage = Storage::new(temp_dir.path().to_path_buf()).unwrap();
+            storage.activity_log = false;
+            let mut task = TaskItem::new(title, ItemType::Task);
+            task.frontmatter.tags = tags;
+            task.frontmatter.delegated_to = Some(delegated_to);
+            task.frontmatter.jira_key = Some(jira_key);
+            task.frontmatter.due_date = Due::parse(due);
+            task.body = body;
+
+            let path = storage.write_task(&mut task).unwrap();
+            let parsed = storage.parse_file(&path)
+                .map_err(|e| TestCaseError::fail(format!("{:#}\n{}", e, fs::read_to_string(&path).unwrap())))?;
+            let fields = |t: &TaskItem| serde_json::to_value(&t.frontmatter).unwrap();
+            prop_assert_eq!(fields(&parsed), fields(&task));
+            prop_assert_eq!(parsed.body, task.body);
+        }
+    }
+
+    #[test]
+    fn test_legacy_date_strings() {
+        let file = |dates: &str| format!(
+            "---\nid: 6b1f0c3e-1c7e-4c1f-9a43-0a5e9f7e2b11\ntype: task\ntitle: Dates\nstatus: active\n{}created_at: 2024-01-01T00:00:00Z\n---\n",
+            dates
+        );
+        let path = Path::new("task.md");
+
+        let parsed = parse_task(&file("due_date: 2024-05-01T09:30:00Z\nstart_date: ''\nend_date: 2024-05-03\n"), path).unwrap();
+        assert_eq!(parsed.frontmatter.due_date.map(|d| d.to_string()).as_deref(), Some("2024-05-01T09:30Z"));
+        assert_eq!(parsed.frontmatter.start_date, None);
+        assert_eq!(parsed.frontmatter.end_date, chrono::NaiveDate::from_ymd_opt(2024, 5, 3));
+
+        let error = parse_task(&file("due_date: tomorow\n"), path).unwrap_err();
+        assert!(format!("{:#}", error).contains("'tomorow' is not a YYYY-MM-DD"));
+    }
+
+    #[test]
+    fn test_pull_reports_changed_task_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let git = |dir: &Path, args: &[&str]| {
+            let output = std::process::Command::new("git").args(args).current_dir(dir).output().unwrap();
+            assert!(output.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+        };
+        git(temp_dir.path(), &["init", "--bare", "remote.git"]);
+        let clone = |name: &str| {
+            git(temp_dir.path(), &["clone", "-q", "remote.git", name]);
+            let dir = temp_dir.path().join(name);
+            git(&dir, &["config", "user.name", "Test"]);
+            git(&dir, &["config", "user.email", "test@example.com"]);
+            dir
+        };
+        let (laptop, desktop) = (clone("laptop"), clone("desktop"));
+
+        let laptop = Storage::new(laptop).unwrap();
+        let mut first = TaskItem::new("First".to_string(), ItemType::Task);
+        laptop.write_task(&mut first).unwrap();
+        assert_eq!(laptop.last_sync_error(), None);
+
+        // The desktop's next write pulls the laptop's task in
+        let desktop = Storage::new(desktop).unwrap();
+        let mut second = TaskItem::new("Second".to_string(), ItemType::Task);
+        desktop.write_task(&mut second).unwrap();
+        assert_eq!(desktop.take_pulled(), [desktop.data_dir.join(format!("{}.md", first.frontmatter.id))]);
+        assert!(desktop.take_pulled().is_empty());
+
+        laptop.write_task(&mut first).unwrap();
+        assert_eq!(laptop.take_pulled(), [laptop.data_dir.join(format!("{}.md", second.frontmatter.id))]);
+        // Nothing new to pull
+        laptop.write_task(&mut first).unwrap();
+        assert!(laptop.take_pulled().is_empty());
+    }
+
+    #[test]
+    fn test_local_only_tasks_stay_out_of_git() {
+        let temp_dir = TempDir::new().unwrap();
+        let git = |dir: &Path, args: &[&str]| {
+            let output = std::process::Command::new("git").args(args).current_dir(dir).output().unwrap();
+            assert!(output.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        };
+        git(temp_dir.path(), &["init", "--bare", "remote.git"]);
+        git(temp_dir.path(), &["clone", "-q", "remote.git", "vault"]);
+        let dir = temp_dir.path().join("vault");
+        git(&dir, &["config", "user.name", "Test"]);
+        git(&dir, &["config", "user.email", "test@example.com"]);
+        fs::write(dir.join(ignore::IGNORE_FILE), "inbox-private/\n").unwrap();
+        fs::create_dir(dir.join("inbox-private")).unwrap();
+        fs::write(dir.join("inbox-private/scratch.md"), "scratch").unwrap();
+
+        let storage = Storage::new(dir.clone()).unwrap();
+        let mut shared = TaskItem::new("Shared".to_string(), ItemType::Task);
+        let mut scratch = TaskItem::new("Scratch".to_string(), ItemType::Task);
+        shared.file_path = storage.write_task(&mut shared).unwrap();
+        scratch.file_path = storage.write_task(&mut scratch).unwrap();
+        let remote = || git(&temp_dir.path().join("remote.git"), &["ls-tree", "-r", "--name-only", "HEAD"]);
+        assert!(remote().contains(&format!("{}.md", scratch.frontmatter.id)));
+        assert!(!remote().contains("inbox-private"));
+
+        // A pushed task made local stays on the remote as it was, without later edits
+        assert!(storage.set_local_only(&scratch, true).unwrap());
+        assert!(storage.is_local_only(&scratch) && !storage.is_local_only(&shared));
+        let scratch_file = format!("HEAD:{}.md", scratch.frontmatter.id);
+        scratch.frontmatter.title = "Scratch notes".to_string();
+        storage.write_task(&mut scratch).unwrap();
+        let pushed = || git(&temp_dir.path().join("remote.git"), &["show", &scratch_file]);
+        assert!(pushed().contains("title: Scratch\n"), "{}", pushed());
+        assert_eq!(storage.load_all_tasks().unwrap().len(), 2);
+
+        assert!(!storage.set_local_only(&scratch, false).unwrap());
+        assert!(!storage.is_local_only(&scratch));
+        assert!(pushed().contains("title: Scratch notes\n"), "{}", pushed());
+    }
+}
+
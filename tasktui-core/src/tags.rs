@@ -0,0 +1,59 @@
+use crate::models::TaskItem;
+use crate::validate;
+use anyhow::Result;
+
+/// A tag as typed: without a leading `#`, lowercased, and checked
+pub fn parse_tag(text: &str) -> Result<String> {
+    let tag = text.trim().trim_start_matches('#').to_lowercase();
+    validate::check_tag(&tag).map_err(|e| anyhow::anyhow!("Invalid tag: {}", e))?;
+    Ok(tag)
+}
+
+/// Copies of the tasks tagged `old`, retagged `new`. A task that already has
+/// `new` just loses `old`. Tag order is kept.
+pub fn rename(tasks: &[TaskItem], old: &str, new: &str) -> Vec<TaskItem> {
+    tasks.iter()
+        .filter(|t| t.has_tag(old))
+        .map(|task| {
+            let mut task = task.clone();
+            let had_new = task.has_tag(new);
+            task.frontmatter.tags = task.frontmatter.tags
+                .into_iter()
+                .filter_map(|t| match t == old {
+                    true if had_new => None,
+                    true => Some(new.to_string()),
+                    false => Some(t),
+                })
+                .collect();
+            task
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ItemType;
+
+    #[test]
+    fn test_rename() {
+        let tagged = |title: &str, tags: &[&str]| {
+            let mut task = TaskItem::new(title.to_string(), ItemType::Task);
+            task.frontmatter.tags = tags.iter().map(|t| t.to_string()).collect();
+            task
+        };
+        let tasks = [
+            tagged("A", &["urgent", "work", "q1"]),
+            tagged("B", &["work", "job"]),
+            tagged("C", &["personal"]),
+        ];
+
+        let renamed = rename(&tasks, "work", "job");
+        assert_eq!(renamed.len(), 2);
+        assert_eq!(renamed[0].frontmatter.tags, ["urgent", "job", "q1"]);
+        assert_eq!(renamed[1].frontmatter.tags, ["job"]);
+
+        assert_eq!(parse_tag(" #Work ").unwrap(), "work");
+        assert!(parse_tag("two words").is_err());
+    }
+}
@@ -0,0 +1,99 @@
+use crate::models::{FieldDef, Frontmatter};
+use std::fmt;
+
+/// Characters allowed in tags besides letters and digits
+const TAG_PUNCTUATION: &[char] = &['-', '_', '/', '.', '@'];
+
+/// A frontmatter value that's present but wrong
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Check date order and tags. An empty result means the frontmatter is valid.
+pub fn validate(frontmatter: &Frontmatter) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    let mut warn = |field, message: String| warnings.push(Warning { field, message });
+
+    // Unparseable dates fail when the file is read, so only their order is checked here
+    if let (Some(start), Some(end)) = (frontmatter.start_date, frontmatter.end_date) {
+        if start > end {
+            warn("end_date", format!("{} is before start_date {}", end, start));
+        }
+    }
+
+    for tag in &frontmatter.tags {
+        if let Err(message) = check_tag(tag) {
+            warn("tags", message);
+        }
+    }
+
+    warnings
+}
+
+/// Check custom field values against the project's field definitions. Fields
+/// the project doesn't define are left alone.
+pub fn custom_fields(frontmatter: &Frontmatter, defs: &[FieldDef]) -> Vec<Warning> {
+    defs.iter()
+        .filter_map(|def| {
+            let value = frontmatter.custom.get(&def.name)?;
+            let message = def.check(value).err()?;
+            Some(Warning { field: "custom", message: format!("{}: {}", def.name, message) })
+        })
+        .collect()
+}
+
+/// Tags are lowercase letters, digits and a little punctuation
+pub fn check_tag(tag: &str) -> Result<(), String> {
+    if tag.is_empty() {
+        return Err("empty tag".to_string());
+    }
+    if let Some(c) = tag.chars().find(|c| !c.is_alphanumeric() && !TAG_PUNCTUATION.contains(c)) {
+        return Err(format!("'{}' contains '{}'", tag, c));
+    }
+    if tag.chars().any(char::is_uppercase) {
+        return Err(format!("'{}' should be lowercase", tag));
+    }
+    Ok(())
+}
+
+/// Lowercase tags, drop a leading `#` and surrounding whitespace, and remove
+/// empties and duplicates
+pub fn normalize_tags(tags: &mut Vec<String>) {
+    let mut seen = Vec::with_capacity(tags.len());
+    for tag in tags.drain(..) {
+        let tag = tag.trim().trim_start_matches('#').to_lowercase();
+        if !tag.is_empty() && !seen.contains(&tag) {
+            seen.push(tag);
+        }
+    }
+    *tags = seen;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ItemType, TaskItem};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_validate() {
+        let mut task = TaskItem::new("Check".to_string(), ItemType::Project);
+        task.frontmatter.start_date = NaiveDate::from_ymd_opt(2024, 5, 10);
+        task.frontmatter.end_date = NaiveDate::from_ymd_opt(2024, 5, 1);
+        task.frontmatter.tags = vec!["work".to_string(), "Home".to_string(), "a b".to_string(), "@phone".to_string()];
+
+        let fields: Vec<_> = validate(&task.frontmatter).iter().map(|w| w.field).collect();
+        assert_eq!(fields, ["end_date", "tags", "tags"]);
+
+        normalize_tags(&mut task.frontmatter.tags);
+        assert_eq!(task.frontmatter.tags, ["work", "home", "a b", "@phone"]);
+    }
+}